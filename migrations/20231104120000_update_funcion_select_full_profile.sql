@@ -0,0 +1,80 @@
+DROP FUNCTION IF EXISTS full_profile_by;
+
+CREATE OR REPLACE FUNCTION full_profile_by(field TEXT, value TEXT)
+RETURNS TABLE (
+  id TEXT,
+  name TEXT,
+  username TEXT,
+  birth_date DATE,
+  gender TEXT,
+  telephones JSONB,
+  emails JSONB,
+  street TEXT,
+  neighborhood TEXT,
+  postal_code INT,
+  city TEXT,
+  state TEXT,
+  country TEXT
+) AS $$
+BEGIN
+  RETURN QUERY EXECUTE 
+    'SELECT 
+        profiles.id, 
+        profiles.name, 
+        profiles.username, 
+        profiles.birth_date, 
+        genders.name AS gender, 
+        COALESCE(
+          jsonb_agg(DISTINCT
+            jsonb_build_object(
+              ''number'', telephones.number, 
+              ''checked'', telephones.checked
+            )) FILTER (WHERE telephones.id IS NOT NULL),
+          ''[]''::jsonb
+        ) AS telephones,
+        COALESCE(
+          jsonb_agg(DISTINCT
+            jsonb_build_object(
+              ''address'', emails.address, 
+              ''checked'', emails.checked
+            )) FILTER (WHERE emails.id IS NOT NULL),
+          ''[]''::jsonb
+        ) AS emails,
+        addresses.street, 
+        addresses.neighborhood, 
+        addresses.postal_code,
+        cities.name AS city, 
+        states.name AS state, 
+        countries.name AS country
+      FROM 
+        profiles
+      JOIN 
+        addresses ON profiles.address_id = addresses.id
+      JOIN 
+        genders ON profiles.gender_id = genders.id
+      JOIN 
+        cities ON addresses.city_id = cities.id
+      JOIN 
+        states ON cities.state_id = states.id
+      JOIN 
+        countries ON states.country_id = countries.id
+      LEFT JOIN 
+        telephones ON profiles.id = telephones.profile_id
+      LEFT JOIN 
+        emails ON profiles.id = emails.profile_id
+      WHERE ' || field || ' = $1
+      GROUP BY 
+        profiles.id, 
+        profiles.name, 
+        profiles.username, 
+        profiles.birth_date, 
+        genders.name, 
+        addresses.street, 
+        addresses.neighborhood, 
+        addresses.postal_code,
+        cities.name,
+        states.name,
+        countries.name;'
+  USING value;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,27 @@
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "refresh_tokens" (
+    "id" SERIAL NOT NULL,
+    "token_hash" TEXT NOT NULL,
+    "family_id" TEXT NOT NULL,
+    "profile_id" TEXT NOT NULL,
+    "expires_at" TIMESTAMP(3) NOT NULL,
+    "used_at" TIMESTAMP(3),
+    "revoked_at" TIMESTAMP(3),
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "refresh_tokens_profile_id_fkey" FOREIGN KEY ("profile_id") 
+      REFERENCES "profiles"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "refresh_tokens_id_key" 
+  ON "refresh_tokens"("id");
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "refresh_tokens_token_hash_key" 
+  ON "refresh_tokens"("token_hash");
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "refresh_tokens_family_id_idx" 
+  ON "refresh_tokens"("family_id");
@@ -0,0 +1,14 @@
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "revoked_tokens" (
+    "jti" TEXT NOT NULL,
+    "expires_at" TIMESTAMP(3) NOT NULL,
+    "revoked_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "revoked_tokens_jti_key" 
+  ON "revoked_tokens"("jti");
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "revoked_tokens_expires_at_idx" 
+  ON "revoked_tokens"("expires_at");
@@ -0,0 +1,22 @@
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "sign_in_events" (
+    "id" SERIAL NOT NULL,
+    "profile_id" TEXT NOT NULL,
+    "method" TEXT NOT NULL,
+    "succeeded" BOOLEAN NOT NULL,
+    "ip_address" TEXT,
+    "user_agent" TEXT,
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "sign_in_events_profile_id_fkey" FOREIGN KEY ("profile_id") 
+      REFERENCES "profiles"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "sign_in_events_id_key" 
+  ON "sign_in_events"("id");
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "sign_in_events_profile_id_created_at_idx" 
+  ON "sign_in_events"("profile_id", "created_at" DESC);
@@ -0,0 +1,85 @@
+DROP FUNCTION IF EXISTS sign_in_by;
+
+CREATE OR REPLACE FUNCTION sign_in_by(field TEXT, value TEXT)
+RETURNS TABLE (
+  id TEXT,
+  name TEXT,
+  username TEXT,
+  password TEXT,
+  account_disabled BOOLEAN,
+  requested_deletion BOOLEAN,
+  blocked_by_attempts BOOLEAN,
+  blocked_at TIMESTAMP(3)
+) AS $$
+BEGIN
+  RETURN QUERY EXECUTE 
+    'SELECT 
+      profiles.id, 
+      profiles.name, 
+      profiles.username,
+      users.password,
+      users.account_disabled,
+      users.requested_deletion,
+      users.blocked_by_attempts,
+      users.blocked_at
+    FROM 
+      profiles
+    JOIN
+      users ON profiles.user_id = users.id
+    LEFT JOIN 
+      telephones ON profiles.id = telephones.profile_id
+    LEFT JOIN 
+      emails ON profiles.id = emails.profile_id
+    WHERE ' || field || ' = $1
+    GROUP BY
+      profiles.id, 
+      profiles.name, 
+      profiles.username, 
+      users.password,
+      users.account_disabled,
+      users.requested_deletion,
+      users.blocked_by_attempts,
+      users.blocked_at'
+    USING value;
+END;
+$$ LANGUAGE plpgsql;
+
+-- Hard deletes every account whose deletion request is older than the grace
+-- period, addresses are removed only when no other profile shares them.
+CREATE OR REPLACE FUNCTION purge_requested_deletions(grace_period_days INTEGER)
+RETURNS INTEGER AS $$
+DECLARE
+  profile_ids TEXT[];
+  user_ids INTEGER[];
+  sign_in_ids INTEGER[];
+  address_ids INTEGER[];
+BEGIN
+  SELECT
+    array_agg(profiles.id),
+    array_agg(users.id),
+    array_agg(users.sign_in_id),
+    array_agg(profiles.address_id)
+  INTO profile_ids, user_ids, sign_in_ids, address_ids
+  FROM users
+  JOIN profiles ON profiles.user_id = users.id
+  WHERE
+    users.requested_deletion
+    AND users.request_deletion_at <= (NOW() AT TIME ZONE 'UTC') - make_interval(days => grace_period_days);
+
+  IF profile_ids IS NULL THEN
+    RETURN 0;
+  END IF;
+
+  DELETE FROM emails WHERE profile_id = ANY(profile_ids);
+  DELETE FROM telephones WHERE profile_id = ANY(profile_ids);
+  DELETE FROM profiles WHERE id = ANY(profile_ids);
+  DELETE FROM users WHERE id = ANY(user_ids);
+  DELETE FROM sign_ins WHERE id = ANY(sign_in_ids);
+  DELETE FROM addresses
+  WHERE
+    id = ANY(address_ids)
+    AND NOT EXISTS (SELECT 1 FROM profiles WHERE profiles.address_id = addresses.id);
+
+  RETURN cardinality(profile_ids);
+END;
+$$ LANGUAGE plpgsql;
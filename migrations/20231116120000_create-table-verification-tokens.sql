@@ -0,0 +1,77 @@
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "verification_tokens" (
+    "id" SERIAL NOT NULL,
+    "token_hash" TEXT NOT NULL,
+    "purpose" TEXT NOT NULL,
+    "profile_id" TEXT NOT NULL,
+    "target" TEXT NOT NULL,
+    "expires_at" TIMESTAMP(3) NOT NULL,
+    "used_at" TIMESTAMP(3),
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "verification_tokens_profile_id_fkey" FOREIGN KEY ("profile_id") 
+      REFERENCES "profiles"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "verification_tokens_id_key" 
+  ON "verification_tokens"("id");
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "verification_tokens_token_hash_key" 
+  ON "verification_tokens"("token_hash");
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "verification_tokens_profile_id_purpose_idx" 
+  ON "verification_tokens"("profile_id", "purpose");
+
+DROP FUNCTION IF EXISTS sign_in_by;
+
+-- email_checked tells whether one of the joined emails is verified, when
+-- searching by emails.address it is the state of that address.
+CREATE OR REPLACE FUNCTION sign_in_by(field TEXT, value TEXT)
+RETURNS TABLE (
+  id TEXT,
+  name TEXT,
+  username TEXT,
+  password TEXT,
+  account_disabled BOOLEAN,
+  requested_deletion BOOLEAN,
+  blocked_by_attempts BOOLEAN,
+  blocked_at TIMESTAMP(3),
+  email_checked BOOLEAN
+) AS $$
+BEGIN
+  RETURN QUERY EXECUTE 
+    'SELECT 
+      profiles.id, 
+      profiles.name, 
+      profiles.username,
+      users.password,
+      users.account_disabled,
+      users.requested_deletion,
+      users.blocked_by_attempts,
+      users.blocked_at,
+      COALESCE(bool_or(emails.checked), false)
+    FROM 
+      profiles
+    JOIN
+      users ON profiles.user_id = users.id
+    LEFT JOIN 
+      telephones ON profiles.id = telephones.profile_id
+    LEFT JOIN 
+      emails ON profiles.id = emails.profile_id
+    WHERE ' || field || ' = $1
+    GROUP BY
+      profiles.id, 
+      profiles.name, 
+      profiles.username, 
+      users.password,
+      users.account_disabled,
+      users.requested_deletion,
+      users.blocked_by_attempts,
+      users.blocked_at'
+    USING value;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,63 @@
+-- Numeric SMS codes are short enough to repeat, so token hashes can no longer
+-- be unique. Codes are looked up by profile and number instead, and every
+-- guess is counted in attempts.
+ALTER TABLE "verification_tokens"
+  ADD COLUMN IF NOT EXISTS "attempts" INTEGER NOT NULL DEFAULT 0;
+
+DROP INDEX IF EXISTS "verification_tokens_token_hash_key";
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "verification_tokens_token_hash_idx" 
+  ON "verification_tokens"("token_hash");
+
+DROP FUNCTION IF EXISTS sign_in_by;
+
+-- telephone_checked follows the same rule as email_checked: when searching by
+-- telephones.number it is the state of that number.
+CREATE OR REPLACE FUNCTION sign_in_by(field TEXT, value TEXT)
+RETURNS TABLE (
+  id TEXT,
+  name TEXT,
+  username TEXT,
+  password TEXT,
+  account_disabled BOOLEAN,
+  requested_deletion BOOLEAN,
+  blocked_by_attempts BOOLEAN,
+  blocked_at TIMESTAMP(3),
+  email_checked BOOLEAN,
+  telephone_checked BOOLEAN
+) AS $$
+BEGIN
+  RETURN QUERY EXECUTE 
+    'SELECT 
+      profiles.id, 
+      profiles.name, 
+      profiles.username,
+      users.password,
+      users.account_disabled,
+      users.requested_deletion,
+      users.blocked_by_attempts,
+      users.blocked_at,
+      COALESCE(bool_or(emails.checked), false),
+      COALESCE(bool_or(telephones.checked), false)
+    FROM 
+      profiles
+    JOIN
+      users ON profiles.user_id = users.id
+    LEFT JOIN 
+      telephones ON profiles.id = telephones.profile_id
+    LEFT JOIN 
+      emails ON profiles.id = emails.profile_id
+    WHERE ' || field || ' = $1
+    GROUP BY
+      profiles.id, 
+      profiles.name, 
+      profiles.username, 
+      users.password,
+      users.account_disabled,
+      users.requested_deletion,
+      users.blocked_by_attempts,
+      users.blocked_at'
+    USING value;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,97 @@
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "totp_authenticators" (
+    "profile_id" TEXT NOT NULL,
+    "secret" TEXT NOT NULL,
+    "last_used_step" BIGINT,
+    "confirmed_at" TIMESTAMP(3),
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "totp_authenticators_profile_id_fkey" FOREIGN KEY ("profile_id") 
+      REFERENCES "profiles"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "totp_authenticators_profile_id_key" 
+  ON "totp_authenticators"("profile_id");
+
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "recovery_codes" (
+    "id" SERIAL NOT NULL,
+    "profile_id" TEXT NOT NULL,
+    "code_hash" TEXT NOT NULL,
+    "used_at" TIMESTAMP(3),
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "recovery_codes_profile_id_fkey" FOREIGN KEY ("profile_id") 
+      REFERENCES "profiles"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "recovery_codes_id_key" 
+  ON "recovery_codes"("id");
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "recovery_codes_profile_id_code_hash_idx" 
+  ON "recovery_codes"("profile_id", "code_hash");
+
+DROP FUNCTION IF EXISTS sign_in_by;
+
+-- mfa_enabled is set once an authenticator has been confirmed, sign-ins then
+-- need a second factor.
+CREATE OR REPLACE FUNCTION sign_in_by(field TEXT, value TEXT)
+RETURNS TABLE (
+  id TEXT,
+  name TEXT,
+  username TEXT,
+  password TEXT,
+  account_disabled BOOLEAN,
+  requested_deletion BOOLEAN,
+  blocked_by_attempts BOOLEAN,
+  blocked_at TIMESTAMP(3),
+  email_checked BOOLEAN,
+  telephone_checked BOOLEAN,
+  mfa_enabled BOOLEAN
+) AS $$
+BEGIN
+  RETURN QUERY EXECUTE 
+    'SELECT 
+      profiles.id, 
+      profiles.name, 
+      profiles.username,
+      users.password,
+      users.account_disabled,
+      users.requested_deletion,
+      users.blocked_by_attempts,
+      users.blocked_at,
+      COALESCE(bool_or(emails.checked), false),
+      COALESCE(bool_or(telephones.checked), false),
+      EXISTS (
+        SELECT 1 
+        FROM totp_authenticators 
+        WHERE 
+          totp_authenticators.profile_id = profiles.id 
+          AND totp_authenticators.confirmed_at IS NOT NULL
+      )
+    FROM 
+      profiles
+    JOIN
+      users ON profiles.user_id = users.id
+    LEFT JOIN 
+      telephones ON profiles.id = telephones.profile_id
+    LEFT JOIN 
+      emails ON profiles.id = emails.profile_id
+    WHERE ' || field || ' = $1
+    GROUP BY
+      profiles.id, 
+      profiles.name, 
+      profiles.username, 
+      users.password,
+      users.account_disabled,
+      users.requested_deletion,
+      users.blocked_by_attempts,
+      users.blocked_at'
+    USING value;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,27 @@
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "webauthn_credentials" (
+    "id" SERIAL NOT NULL,
+    "credential_id" TEXT NOT NULL,
+    "profile_id" TEXT NOT NULL,
+    "public_key" BYTEA NOT NULL,
+    "sign_count" BIGINT NOT NULL DEFAULT 0,
+    "name" TEXT NOT NULL,
+    "last_used_at" TIMESTAMP(3),
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "webauthn_credentials_profile_id_fkey" FOREIGN KEY ("profile_id") 
+      REFERENCES "profiles"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "webauthn_credentials_id_key" 
+  ON "webauthn_credentials"("id");
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "webauthn_credentials_credential_id_key" 
+  ON "webauthn_credentials"("credential_id");
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "webauthn_credentials_profile_id_idx" 
+  ON "webauthn_credentials"("profile_id");
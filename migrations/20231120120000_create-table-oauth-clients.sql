@@ -0,0 +1,48 @@
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "oauth_clients" (
+    "id" SERIAL NOT NULL,
+    "client_id" TEXT NOT NULL,
+    "secret_hash" TEXT,
+    "name" TEXT NOT NULL,
+    "redirect_uris" TEXT[] NOT NULL,
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "oauth_clients_id_key" 
+  ON "oauth_clients"("id");
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "oauth_clients_client_id_key" 
+  ON "oauth_clients"("client_id");
+
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "oauth_authorization_codes" (
+    "id" SERIAL NOT NULL,
+    "code_hash" TEXT NOT NULL,
+    "client_id" TEXT NOT NULL,
+    "profile_id" TEXT NOT NULL,
+    "redirect_uri" TEXT NOT NULL,
+    "scope" TEXT NOT NULL,
+    "code_challenge" TEXT NOT NULL,
+    "nonce" TEXT,
+    "expires_at" TIMESTAMP(3) NOT NULL,
+    "used_at" TIMESTAMP(3),
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "oauth_authorization_codes_client_id_fkey" FOREIGN KEY ("client_id") 
+      REFERENCES "oauth_clients"("client_id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE,
+    CONSTRAINT "oauth_authorization_codes_profile_id_fkey" FOREIGN KEY ("profile_id") 
+      REFERENCES "profiles"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "oauth_authorization_codes_id_key" 
+  ON "oauth_authorization_codes"("id");
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "oauth_authorization_codes_code_hash_key" 
+  ON "oauth_authorization_codes"("code_hash");
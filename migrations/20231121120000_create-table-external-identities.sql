@@ -0,0 +1,53 @@
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "external_identities" (
+    "id" SERIAL NOT NULL,
+    "provider" TEXT NOT NULL,
+    "subject" TEXT NOT NULL,
+    "profile_id" TEXT NOT NULL,
+    "email" TEXT,
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "external_identities_profile_id_fkey" FOREIGN KEY ("profile_id") 
+      REFERENCES "profiles"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "external_identities_id_key" 
+  ON "external_identities"("id");
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "external_identities_provider_subject_key" 
+  ON "external_identities"("provider", "subject");
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "external_identities_profile_id_idx" 
+  ON "external_identities"("profile_id");
+
+-- CreateTable
+-- A sign-in started with an external provider. When the provider account
+-- matches no profile its claims are kept until the sign-up is completed.
+CREATE TABLE IF NOT EXISTS "external_sign_ins" (
+    "id" SERIAL NOT NULL,
+    "state_hash" TEXT NOT NULL,
+    "provider" TEXT NOT NULL,
+    "code_verifier" TEXT NOT NULL,
+    "nonce" TEXT NOT NULL,
+    "expires_at" TIMESTAMP(3) NOT NULL,
+    "used_at" TIMESTAMP(3),
+    "subject" TEXT,
+    "email" TEXT,
+    "email_verified" BOOLEAN NOT NULL DEFAULT FALSE,
+    "name" TEXT,
+    "preferred_username" TEXT,
+    "completed_at" TIMESTAMP(3),
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "external_sign_ins_id_key" 
+  ON "external_sign_ins"("id");
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "external_sign_ins_state_hash_key" 
+  ON "external_sign_ins"("state_hash");
@@ -0,0 +1,74 @@
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "roles" (
+    "id" SERIAL NOT NULL,
+    "name" TEXT NOT NULL,
+    "description" TEXT NOT NULL,
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "roles_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "roles_name_key" 
+  ON "roles"("name");
+
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "permissions" (
+    "id" SERIAL NOT NULL,
+    "name" TEXT NOT NULL,
+    "description" TEXT NOT NULL,
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "permissions_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE UNIQUE INDEX IF NOT EXISTS "permissions_name_key" 
+  ON "permissions"("name");
+
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "role_permissions" (
+    "role_id" INTEGER NOT NULL,
+    "permission_id" INTEGER NOT NULL,
+    CONSTRAINT "role_permissions_pkey" PRIMARY KEY ("role_id", "permission_id"),
+    CONSTRAINT "role_permissions_role_id_fkey" FOREIGN KEY ("role_id") 
+      REFERENCES "roles"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE,
+    CONSTRAINT "role_permissions_permission_id_fkey" FOREIGN KEY ("permission_id") 
+      REFERENCES "permissions"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE
+);
+
+-- CreateTable
+CREATE TABLE IF NOT EXISTS "profile_roles" (
+    "profile_id" TEXT NOT NULL,
+    "role_id" INTEGER NOT NULL,
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "profile_roles_pkey" PRIMARY KEY ("profile_id", "role_id"),
+    CONSTRAINT "profile_roles_profile_id_fkey" FOREIGN KEY ("profile_id") 
+      REFERENCES "profiles"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE,
+    CONSTRAINT "profile_roles_role_id_fkey" FOREIGN KEY ("role_id") 
+      REFERENCES "roles"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE
+);
+
+-- SeedData
+INSERT INTO "roles" ("name", "description")
+  VALUES ('admin', 'Manages user accounts and OpenID Connect clients')
+  ON CONFLICT DO NOTHING;
+
+INSERT INTO "permissions" ("name", "description")
+  VALUES
+    ('users:admin', 'Disable, enable and manage user accounts'),
+    ('oauth_clients:admin', 'Register OpenID Connect clients')
+  ON CONFLICT DO NOTHING;
+
+INSERT INTO "role_permissions" ("role_id", "permission_id")
+  SELECT "roles"."id", "permissions"."id"
+  FROM "roles", "permissions"
+  WHERE "roles"."name" = 'admin'
+    AND "permissions"."name" IN ('users:admin', 'oauth_clients:admin')
+  ON CONFLICT DO NOTHING;
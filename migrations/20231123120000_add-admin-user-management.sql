@@ -0,0 +1,70 @@
+-- AlterTable
+ALTER TABLE "users"
+  ADD COLUMN IF NOT EXISTS "password_reset_required" BOOLEAN NOT NULL DEFAULT false;
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "profiles_created_at_idx" 
+  ON "profiles"("created_at");
+
+DROP FUNCTION IF EXISTS sign_in_by;
+
+-- password_reset_required is set by an administrator, password sign-ins are
+-- refused until the password is reset.
+CREATE OR REPLACE FUNCTION sign_in_by(field TEXT, value TEXT)
+RETURNS TABLE (
+  id TEXT,
+  name TEXT,
+  username TEXT,
+  password TEXT,
+  account_disabled BOOLEAN,
+  requested_deletion BOOLEAN,
+  blocked_by_attempts BOOLEAN,
+  blocked_at TIMESTAMP(3),
+  email_checked BOOLEAN,
+  telephone_checked BOOLEAN,
+  mfa_enabled BOOLEAN,
+  password_reset_required BOOLEAN
+) AS $$
+BEGIN
+  RETURN QUERY EXECUTE 
+    'SELECT 
+      profiles.id, 
+      profiles.name, 
+      profiles.username,
+      users.password,
+      users.account_disabled,
+      users.requested_deletion,
+      users.blocked_by_attempts,
+      users.blocked_at,
+      COALESCE(bool_or(emails.checked), false),
+      COALESCE(bool_or(telephones.checked), false),
+      EXISTS (
+        SELECT 1 
+        FROM totp_authenticators 
+        WHERE 
+          totp_authenticators.profile_id = profiles.id 
+          AND totp_authenticators.confirmed_at IS NOT NULL
+      ),
+      users.password_reset_required
+    FROM 
+      profiles
+    JOIN
+      users ON profiles.user_id = users.id
+    LEFT JOIN 
+      telephones ON profiles.id = telephones.profile_id
+    LEFT JOIN 
+      emails ON profiles.id = emails.profile_id
+    WHERE ' || field || ' = $1
+    GROUP BY
+      profiles.id, 
+      profiles.name, 
+      profiles.username, 
+      users.password,
+      users.account_disabled,
+      users.requested_deletion,
+      users.blocked_by_attempts,
+      users.blocked_at,
+      users.password_reset_required'
+    USING value;
+END;
+$$ LANGUAGE plpgsql;
@@ -0,0 +1,53 @@
+-- CreateTable
+-- Rows are chained: hash covers the previous row's hash and this row's
+-- fields, so editing or removing a row breaks every hash after it.
+CREATE TABLE IF NOT EXISTS "audit_events" (
+    "id" BIGSERIAL NOT NULL,
+    "actor_id" TEXT,
+    "subject_id" TEXT,
+    "action" TEXT NOT NULL,
+    "outcome" TEXT NOT NULL,
+    "ip_address" TEXT,
+    "user_agent" TEXT,
+    "metadata" JSONB NOT NULL DEFAULT '{}',
+    "created_at" TIMESTAMP(6) NOT NULL,
+    "previous_hash" TEXT NOT NULL,
+    "hash" TEXT NOT NULL,
+    CONSTRAINT "audit_events_pkey" PRIMARY KEY ("id")
+);
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "audit_events_actor_id_idx" 
+  ON "audit_events"("actor_id");
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "audit_events_subject_id_idx" 
+  ON "audit_events"("subject_id");
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "audit_events_action_created_at_idx" 
+  ON "audit_events"("action", "created_at" DESC);
+
+-- the audit log is append-only
+CREATE OR REPLACE FUNCTION audit_events_append_only()
+RETURNS TRIGGER AS $$
+BEGIN
+  RAISE EXCEPTION 'audit_events is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE OR REPLACE TRIGGER audit_events_append_only
+  BEFORE UPDATE OR DELETE OR TRUNCATE ON "audit_events"
+  FOR EACH STATEMENT EXECUTE FUNCTION audit_events_append_only();
+
+-- SeedData
+INSERT INTO "permissions" ("name", "description")
+  VALUES ('audit_events:read', 'Query and verify the security audit log')
+  ON CONFLICT DO NOTHING;
+
+INSERT INTO "role_permissions" ("role_id", "permission_id")
+  SELECT "roles"."id", "permissions"."id"
+  FROM "roles", "permissions"
+  WHERE "roles"."name" = 'admin'
+    AND "permissions"."name" = 'audit_events:read'
+  ON CONFLICT DO NOTHING;
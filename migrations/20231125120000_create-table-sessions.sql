@@ -0,0 +1,22 @@
+-- CreateTable
+-- A session is the refresh token family started by a sign-in, its id is the
+-- family id and the sid claim of the access tokens issued for it.
+CREATE TABLE IF NOT EXISTS "sessions" (
+    "id" TEXT NOT NULL,
+    "profile_id" TEXT NOT NULL,
+    "device_label" TEXT NOT NULL,
+    "ip_address" TEXT,
+    "user_agent" TEXT,
+    "created_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "last_seen_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "revoked_at" TIMESTAMP(3),
+    CONSTRAINT "sessions_pkey" PRIMARY KEY ("id"),
+    CONSTRAINT "sessions_profile_id_fkey" FOREIGN KEY ("profile_id") 
+      REFERENCES "profiles"("id") 
+      ON DELETE CASCADE 
+      ON UPDATE CASCADE
+);
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "sessions_profile_id_last_seen_at_idx" 
+  ON "sessions"("profile_id", "last_seen_at" DESC);
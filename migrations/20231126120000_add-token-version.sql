@@ -0,0 +1,5 @@
+-- AlterTable
+-- Access tokens carry the version they were issued with and are refused once
+-- it is bumped, which every password change or reset does.
+ALTER TABLE "users"
+  ADD COLUMN IF NOT EXISTS "token_version" INTEGER NOT NULL DEFAULT 0;
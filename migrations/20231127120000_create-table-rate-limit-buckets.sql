@@ -0,0 +1,13 @@
+-- CreateTable
+-- Token buckets of the rate limiter when it is shared between instances. A
+-- bucket is deleted once full_at has passed, as it is full again by then.
+CREATE TABLE IF NOT EXISTS "rate_limit_buckets" (
+    "key" TEXT NOT NULL,
+    "tokens" DOUBLE PRECISION NOT NULL,
+    "updated_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    "full_at" TIMESTAMP(3) NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT "rate_limit_buckets_pkey" PRIMARY KEY ("key")
+);
+
+-- CreateIndex
+CREATE INDEX IF NOT EXISTS "rate_limit_buckets_full_at_idx" ON "rate_limit_buckets"("full_at");
@@ -19,6 +19,12 @@ impl From<AppError> for HttpResponse {
       Code::Unauthenticated => HttpResponse::Unauthorized()
         .content_type("text/html; charset=utf-8")
         .body(error.message),
+      Code::Locked => HttpResponse::Locked()
+        .content_type("text/html; charset=utf-8")
+        .body(error.message),
+      Code::TooManyRequests => HttpResponse::TooManyRequests()
+        .content_type("text/html; charset=utf-8")
+        .body(error.message),
       Code::Internal => HttpResponse::InternalServerError()
         .content_type("text/html; charset=utf-8")
         .body("Internal error"),
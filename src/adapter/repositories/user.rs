@@ -1,9 +1,29 @@
 use crate::domain::{
-  core::user::{repository::UserRepository, sign_up},
-  entities::user::{UserColumns, UserData},
+  core::user::{repository::UserRepository, sign_up, update_profile},
+  entities::{
+    external_identity::{
+      ExternalClaims, ExternalIdentityData, ExternalSignInData, NewExternalIdentity,
+      NewExternalSignIn,
+    },
+    oauth::{AuthorizationCodeData, NewAuthorizationCode, NewOAuthClient, OAuthClientData},
+    refresh_token::{NewRefreshToken, RefreshTokenData},
+    role::Grants,
+    session::{NewSession, Session},
+    sign_in_event::{NewSignInEvent, SignInEvent, SignInEventPage, SignInMethod},
+    totp::TotpAuthenticatorData,
+    user::{
+      Address, Email, FullProfile, Telephone, UserColumns, UserData, UserFilter, UserSummary,
+      UserSummaryPage,
+    },
+    verification_token::{NewVerificationToken, VerificationPurpose, VerificationTokenData},
+    webauthn::{NewWebAuthnCredential, WebAuthnCredentialData},
+  },
   error::AppError,
 };
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use sqlx::{Pool, Postgres};
 
 pub struct UserRepositoryDB<'a, P> {
@@ -12,15 +32,38 @@ pub struct UserRepositoryDB<'a, P> {
 
 type NewUser<'a> = sign_up::Request<'a, String, sign_up::encrypter::PasswordEncrypted>;
 
+#[derive(Deserialize)]
+struct TelephoneJson {
+  number: String,
+  checked: bool,
+}
+
+#[derive(Deserialize)]
+struct EmailJson {
+  address: String,
+  checked: bool,
+}
+
+fn hash_token(token: &str) -> String {
+  Sha256::digest(token.as_bytes())
+    .iter()
+    .map(|byte| format!("{:02x}", byte))
+    .collect()
+}
+
+fn columns_to_field<'a>(column: &UserColumns<'a>) -> (&'static str, &'a str) {
+  match column {
+    UserColumns::Username(username) => ("profiles.username", *username),
+    UserColumns::Email(email) => ("emails.address", *email),
+    UserColumns::Telephone(telephone) => ("telephones.number", *telephone),
+    UserColumns::Id(id) => ("profiles.id", *id),
+  }
+}
+
 #[async_trait]
 impl UserRepository for UserRepositoryDB<'_, Pool<Postgres>> {
   async fn find_user_by<'a>(&self, column: &UserColumns<'a>) -> Result<UserData, AppError> {
-    let (col, value) = match column {
-      UserColumns::Username(username) => ("profiles.username", *username),
-      UserColumns::Email(email) => ("emails.address", *email),
-      UserColumns::Telephone(telephone) => ("telephones.number", *telephone),
-      UserColumns::Id(id) => ("profiles.id", *id),
-    };
+    let (col, value) = columns_to_field(column);
 
     let user = sqlx::query!("SELECT * FROM sign_in_by($1, $2)", col, value)
       .fetch_one(self.pool)
@@ -32,6 +75,78 @@ impl UserRepository for UserRepositoryDB<'_, Pool<Postgres>> {
       name: user.name.unwrap(),
       username: user.username.unwrap(),
       password: user.password.unwrap(),
+      account_disabled: user.account_disabled.unwrap(),
+      requested_deletion: user.requested_deletion.unwrap(),
+      blocked_by_attempts: user.blocked_by_attempts.unwrap(),
+      blocked_at: user.blocked_at,
+      email_checked: user.email_checked.unwrap(),
+      telephone_checked: user.telephone_checked.unwrap(),
+      mfa_enabled: user.mfa_enabled.unwrap(),
+      password_reset_required: user.password_reset_required.unwrap(),
+    })
+  }
+
+  async fn find_full_profile_by<'a>(
+    &self,
+    column: &UserColumns<'a>,
+  ) -> Result<FullProfile, AppError> {
+    let (col, value) = columns_to_field(column);
+
+    let profile = sqlx::query!(
+      r#"SELECT
+        id AS "id!",
+        name AS "name!",
+        username AS "username!",
+        birth_date AS "birth_date!: chrono::NaiveDate",
+        gender AS "gender!",
+        telephones AS "telephones!",
+        emails AS "emails!",
+        street AS "street!",
+        neighborhood AS "neighborhood!",
+        postal_code AS "postal_code!",
+        city AS "city!",
+        state AS "state!",
+        country AS "country!"
+      FROM full_profile_by($1, $2)"#,
+      col,
+      value
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    let telephones: Vec<TelephoneJson> = serde_json::from_value(profile.telephones)
+      .map_err(|err| AppError::internal(format!("invalid telephones data: {}", err)))?;
+    let emails: Vec<EmailJson> = serde_json::from_value(profile.emails)
+      .map_err(|err| AppError::internal(format!("invalid emails data: {}", err)))?;
+
+    Ok(FullProfile {
+      id: profile.id,
+      name: profile.name,
+      username: profile.username,
+      birth_date: profile.birth_date,
+      gender: profile.gender,
+      telephones: telephones
+        .into_iter()
+        .map(|telephone| Telephone {
+          number: telephone.number,
+          checked: telephone.checked,
+        })
+        .collect(),
+      emails: emails
+        .into_iter()
+        .map(|email| Email {
+          address: email.address,
+          checked: email.checked,
+        })
+        .collect(),
+      address: Address {
+        street: profile.street,
+        neighborhood: profile.neighborhood,
+        postal_code: profile.postal_code,
+        city: profile.city,
+        state: profile.state,
+        country: profile.country,
+      },
     })
   }
 
@@ -61,7 +176,9 @@ impl UserRepository for UserRepositoryDB<'_, Pool<Postgres>> {
     let query_result = sqlx::query!(
       "UPDATE users 
       SET 
-        password = $1
+        password = $1,
+        password_reset_required = false,
+        token_version = token_version + 1
       FROM
         profiles 
       WHERE 
@@ -81,243 +198,2913 @@ impl UserRepository for UserRepositoryDB<'_, Pool<Postgres>> {
 
     Ok(())
   }
-}
 
-#[cfg(test)]
-mod test {
-  use crate::{
-    domain::error::Code,
-    tests_e2e::helpers::user_repository::{assert_user, insert_user},
-  };
+  async fn rehash_password(
+    &self,
+    password: &str,
+    verified_password: &str,
+    profile_id: &str,
+  ) -> Result<(), AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE users 
+      SET password = $1
+      FROM profiles 
+      WHERE 
+        profiles.user_id = users.id
+        AND profiles.id = $3
+        AND users.password = $2",
+      password,
+      verified_password,
+      profile_id
+    )
+    .execute(self.pool)
+    .await?;
 
-  use super::*;
-  use chrono::NaiveDate;
-  use sqlx::PgPool;
+    if query_result.rows_affected() < 1 {
+      return Err(AppError::not_found(
+        "user does not exist or changed the password",
+      ));
+    }
 
-  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
-  async fn test_store_new_user(pool: PgPool) -> sqlx::Result<()> {
-    let sut = UserRepositoryDB { pool: &pool };
+    Ok(())
+  }
 
-    sut
-      .store(&sign_up::Request {
-        id: ID.to_owned(),
-        name: NAME,
-        username: USERNAME,
-        birth_date: NaiveDate::parse_from_str(BIRTH_DATE, "%Y-%m-%d").unwrap(),
-        gender_id: GENDER_ID,
-        password: sign_up::encrypter::PasswordEncrypted(PASSWORD.to_string()),
-        street: STREET,
-        neighborhood: NEIGHBORHOOD,
-        city_id: CITY_ID,
-        postal_code: POSTAL_CODE,
-        email_address: EMAIL_ADDRESS,
-        telephone_number: Some(TELEPHONE_NUMBER),
-      })
-      .await
-      .unwrap();
+  async fn update_profile<'a>(
+    &self,
+    profile: &update_profile::Request<'a>,
+  ) -> Result<(), AppError> {
+    let address = profile.address.as_ref();
 
-    assert_user(
-      &pool,
-      &UserColumns::Username(USERNAME),
-      UserData {
-        id: ID.to_owned(),
-        name: NAME.to_owned(),
-        username: USERNAME.to_owned(),
-        password: PASSWORD.to_owned(),
-      },
-      Some(|req_pwd, db_pwd| req_pwd == db_pwd),
+    let updated = sqlx::query!(
+      "SELECT update_user_profile($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+      profile.profile_id,
+      profile.name,
+      profile.birth_date,
+      profile.gender_id,
+      address.map(|address| address.street),
+      address.map(|address| address.neighborhood),
+      address.map(|address| address.city_id),
+      address.map(|address| address.postal_code),
+      profile.email_address,
+      profile.telephone_number,
     )
-    .await;
+    .fetch_one(self.pool)
+    .await?;
+
+    if updated.update_user_profile.is_none() {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
 
     Ok(())
   }
 
-  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
-  async fn test_try_store_duplicate_user(pool: PgPool) -> sqlx::Result<()> {
-    insert_user_default(&pool).await.unwrap();
-
-    let sut = UserRepositoryDB { pool: &pool };
-    let response = sut
-      .store(&sign_up::Request {
-        id: ID.to_owned(),
-        name: NAME,
-        username: USERNAME,
-        birth_date: NaiveDate::parse_from_str(BIRTH_DATE, "%Y-%m-%d").unwrap(),
-        gender_id: GENDER_ID,
-        password: sign_up::encrypter::PasswordEncrypted(PASSWORD.to_string()),
-        street: STREET,
-        neighborhood: NEIGHBORHOOD,
-        city_id: CITY_ID,
-        postal_code: POSTAL_CODE,
-        email_address: EMAIL_ADDRESS,
-        telephone_number: Some(TELEPHONE_NUMBER),
-      })
-      .await;
-
-    match response {
-      Ok(_) => panic!("should not be success"),
-      Err(error) => {
-        assert_eq!(error.code, Code::AlreadyExists, "fail but wrong code");
-      }
-    };
+  async fn register_sign_in(&self, profile_id: &str) -> Result<(), AppError> {
+    sqlx::query!(
+      "UPDATE sign_ins
+      SET
+        sign_in_count = sign_ins.sign_in_count + 1,
+        failed_attempts = 0
+      FROM
+        users
+        JOIN profiles ON profiles.user_id = users.id
+      WHERE
+        users.sign_in_id = sign_ins.id
+        AND profiles.id = $1",
+      profile_id
+    )
+    .execute(self.pool)
+    .await?;
 
     Ok(())
   }
 
-  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
-  async fn test_find_user_using_username(pool: PgPool) -> sqlx::Result<()> {
-    insert_user_default(&pool).await.unwrap();
+  async fn register_failed_sign_in(
+    &self,
+    profile_id: &str,
+    max_failed_attempts: i32,
+  ) -> Result<bool, AppError> {
+    let blocked = sqlx::query!(
+      r#"WITH attempt AS (
+        UPDATE sign_ins
+        SET failed_attempts = sign_ins.failed_attempts + 1
+        FROM
+          users
+          JOIN profiles ON profiles.user_id = users.id
+        WHERE
+          users.sign_in_id = sign_ins.id
+          AND profiles.id = $1
+        RETURNING users.id AS user_id, sign_ins.failed_attempts
+      )
+      UPDATE users
+      SET
+        blocked_by_attempts = true,
+        blocked_at = NOW() AT TIME ZONE 'UTC'
+      FROM attempt
+      WHERE
+        users.id = attempt.user_id
+        AND attempt.failed_attempts >= $2"#,
+      profile_id,
+      max_failed_attempts
+    )
+    .execute(self.pool)
+    .await?;
 
-    let sut = UserRepositoryDB { pool: &pool };
+    Ok(blocked.rows_affected() > 0)
+  }
 
-    let response = sut
-      .find_user_by(&UserColumns::Username(USERNAME))
-      .await
-      .unwrap();
+  async fn unlock_user(&self, profile_id: &str) -> Result<(), AppError> {
+    let query_result = sqlx::query!(
+      "WITH unlocked AS (
+        UPDATE users
+        SET
+          blocked_by_attempts = false,
+          blocked_at = NULL
+        FROM profiles
+        WHERE
+          profiles.user_id = users.id
+          AND profiles.id = $1
+        RETURNING users.sign_in_id
+      )
+      UPDATE sign_ins
+      SET failed_attempts = 0
+      FROM unlocked
+      WHERE sign_ins.id = unlocked.sign_in_id",
+      profile_id
+    )
+    .execute(self.pool)
+    .await?;
 
-    assert_eq!(
-      response,
-      UserData {
-        id: ID.to_string(),
-        name: NAME.to_string(),
-        password: PASSWORD.to_string(),
-        username: USERNAME.to_string(),
-      }
-    );
+    if query_result.rows_affected() < 1 {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
 
     Ok(())
   }
 
-  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
-  async fn test_find_user_using_id(pool: PgPool) -> sqlx::Result<()> {
-    insert_user_default(&pool).await.unwrap();
-
-    let sut = UserRepositoryDB { pool: &pool };
-
-    let response = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+  async fn update_account_disabled(
+    &self,
+    profile_id: &str,
+    disabled: bool,
+  ) -> Result<(), AppError> {
+    let updated = sqlx::query_scalar!(
+      r#"WITH updated AS (
+        UPDATE users
+        SET
+          account_disabled = $1,
+          token_version = token_version + CASE WHEN $1 THEN 1 ELSE 0 END
+        FROM profiles
+        WHERE
+          profiles.user_id = users.id
+          AND profiles.id = $2
+        RETURNING users.id
+      ), revoked_tokens AS (
+        UPDATE refresh_tokens
+        SET revoked_at = CURRENT_TIMESTAMP
+        FROM profiles, updated
+        WHERE
+          $1
+          AND profiles.user_id = updated.id
+          AND refresh_tokens.profile_id = profiles.id
+          AND refresh_tokens.revoked_at IS NULL
+      )
+      SELECT COUNT(*) AS "updated!" FROM updated"#,
+      disabled,
+      profile_id
+    )
+    .fetch_one(self.pool)
+    .await?;
 
-    assert_eq!(
-      response,
-      UserData {
-        id: ID.to_string(),
-        name: NAME.to_string(),
-        password: PASSWORD.to_string(),
-        username: USERNAME.to_string(),
-      }
-    );
+    if updated < 1 {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
 
     Ok(())
   }
 
-  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
-  async fn test_find_user_using_email(pool: PgPool) -> sqlx::Result<()> {
-    insert_user_default(&pool).await.unwrap();
+  async fn request_deletion(&self, profile_id: &str) -> Result<(), AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE users
+      SET
+        requested_deletion = true,
+        request_deletion_at = NOW() AT TIME ZONE 'UTC'
+      FROM profiles
+      WHERE
+        profiles.user_id = users.id
+        AND profiles.id = $1",
+      profile_id
+    )
+    .execute(self.pool)
+    .await?;
 
-    let sut = UserRepositoryDB { pool: &pool };
+    if query_result.rows_affected() < 1 {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
 
-    let response = sut
-      .find_user_by(&UserColumns::Email(EMAIL_ADDRESS))
-      .await
-      .unwrap();
+    Ok(())
+  }
 
-    assert_eq!(
-      response,
-      UserData {
-        id: ID.to_string(),
-        name: NAME.to_string(),
-        password: PASSWORD.to_string(),
-        username: USERNAME.to_string(),
-      }
-    );
+  async fn cancel_deletion(&self, profile_id: &str) -> Result<(), AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE users
+      SET
+        requested_deletion = false,
+        request_deletion_at = NULL
+      FROM profiles
+      WHERE
+        profiles.user_id = users.id
+        AND profiles.id = $1",
+      profile_id
+    )
+    .execute(self.pool)
+    .await?;
+
+    if query_result.rows_affected() < 1 {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
 
     Ok(())
   }
 
-  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
-  async fn test_find_user_using_telephone(pool: PgPool) -> sqlx::Result<()> {
-    insert_user_default(&pool).await.unwrap();
-
-    let sut = UserRepositoryDB { pool: &pool };
+  async fn purge_requested_deletions(&self, grace_period_days: i32) -> Result<i32, AppError> {
+    let purged = sqlx::query_scalar!(
+      r#"SELECT purge_requested_deletions($1) AS "purged!""#,
+      grace_period_days
+    )
+    .fetch_one(self.pool)
+    .await?;
 
-    let response = sut
-      .find_user_by(&UserColumns::Telephone(TELEPHONE_NUMBER))
-      .await
-      .unwrap();
+    Ok(purged)
+  }
 
-    assert_eq!(
-      response,
-      UserData {
-        id: ID.to_string(),
-        name: NAME.to_string(),
-        password: PASSWORD.to_string(),
-        username: USERNAME.to_string(),
-      }
-    );
+  async fn store_sign_in_event(&self, event: &NewSignInEvent) -> Result<(), AppError> {
+    sqlx::query!(
+      "INSERT INTO sign_in_events (profile_id, method, succeeded, ip_address, user_agent)
+      VALUES ($1, $2, $3, $4, $5)",
+      event.profile_id,
+      event.method.as_str(),
+      event.succeeded,
+      event.ip_address,
+      event.user_agent,
+    )
+    .execute(self.pool)
+    .await?;
 
     Ok(())
   }
 
-  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
-  async fn test_search_with_invalid_user(pool: PgPool) -> sqlx::Result<()> {
-    const WRONG_ID: &str = "3d59b3cc-4ba4-09a4-90cb-956c4a2df911";
+  async fn find_sign_in_events(
+    &self,
+    profile_id: &str,
+    page: i64,
+    per_page: i64,
+  ) -> Result<SignInEventPage, AppError> {
+    let total = sqlx::query_scalar!(
+      r#"SELECT COUNT(*) AS "total!" FROM sign_in_events WHERE profile_id = $1"#,
+      profile_id
+    )
+    .fetch_one(self.pool)
+    .await?;
 
-    insert_user_default(&pool).await.unwrap();
+    let events = sqlx::query!(
+      "SELECT method, succeeded, ip_address, user_agent, created_at
+      FROM sign_in_events
+      WHERE profile_id = $1
+      ORDER BY created_at DESC, id DESC
+      LIMIT $2 OFFSET $3",
+      profile_id,
+      per_page,
+      (page - 1) * per_page
+    )
+    .fetch_all(self.pool)
+    .await?;
 
-    let sut = UserRepositoryDB { pool: &pool };
+    Ok(SignInEventPage {
+      events: events
+        .into_iter()
+        .map(|event| {
+          Ok(SignInEvent {
+            method: SignInMethod::try_from(event.method.as_str())?,
+            succeeded: event.succeeded,
+            ip_address: event.ip_address,
+            user_agent: event.user_agent,
+            created_at: event.created_at,
+          })
+        })
+        .collect::<Result<_, AppError>>()?,
+      page,
+      per_page,
+      total,
+    })
+  }
 
-    let response = sut.find_user_by(&UserColumns::Id(WRONG_ID)).await;
+  async fn store_refresh_token(&self, refresh_token: &NewRefreshToken) -> Result<(), AppError> {
+    sqlx::query!(
+      "INSERT INTO refresh_tokens (token_hash, family_id, profile_id, expires_at)
+      VALUES ($1, $2, $3, $4)",
+      hash_token(&refresh_token.token),
+      refresh_token.family_id,
+      refresh_token.profile_id,
+      refresh_token.expires_at,
+    )
+    .execute(self.pool)
+    .await?;
 
-    match response {
-      Ok(_) => panic!("this test not expected success"),
-      Err(error) => assert_eq!(error.code, Code::NotFound),
-    };
+    // a refresh token is issued when the session starts and each time it
+    // refreshes, the only times this service sees it
+    sqlx::query!(
+      "UPDATE sessions
+      SET last_seen_at = CURRENT_TIMESTAMP
+      WHERE id = $1",
+      refresh_token.family_id
+    )
+    .execute(self.pool)
+    .await?;
 
     Ok(())
   }
 
-  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
-  async fn test_update_user_password(pool: PgPool) -> sqlx::Result<()> {
-    const NEW_PASSWORD: &str = "987654321";
+  async fn find_refresh_token(&self, token: &str) -> Result<RefreshTokenData, AppError> {
+    let refresh_token = sqlx::query!(
+      "SELECT
+        refresh_tokens.id,
+        refresh_tokens.family_id,
+        refresh_tokens.profile_id,
+        refresh_tokens.expires_at,
+        refresh_tokens.used_at,
+        refresh_tokens.revoked_at,
+        users.account_disabled
+      FROM refresh_tokens
+      INNER JOIN profiles ON profiles.id = refresh_tokens.profile_id
+      INNER JOIN users ON users.id = profiles.user_id
+      WHERE refresh_tokens.token_hash = $1",
+      hash_token(token)
+    )
+    .fetch_one(self.pool)
+    .await?;
 
-    insert_user_default(&pool).await.unwrap();
+    Ok(RefreshTokenData {
+      id: refresh_token.id,
+      family_id: refresh_token.family_id,
+      profile_id: refresh_token.profile_id,
+      expires_at: refresh_token.expires_at,
+      used: refresh_token.used_at.is_some(),
+      revoked: refresh_token.revoked_at.is_some(),
+      account_disabled: refresh_token.account_disabled,
+    })
+  }
 
+  async fn use_refresh_token(&self, id: i32) -> Result<bool, AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE refresh_tokens
+      SET used_at = CURRENT_TIMESTAMP
+      WHERE id = $1 AND used_at IS NULL AND revoked_at IS NULL",
+      id
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(query_result.rows_affected() == 1)
+  }
+
+  async fn revoke_refresh_token_family(&self, family_id: &str) -> Result<(), AppError> {
+    sqlx::query!(
+      "UPDATE refresh_tokens
+      SET revoked_at = CURRENT_TIMESTAMP
+      WHERE family_id = $1 AND revoked_at IS NULL",
+      family_id
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(())
+  }
+
+  async fn revoke_refresh_tokens(&self, profile_id: &str) -> Result<(), AppError> {
+    sqlx::query!(
+      "UPDATE refresh_tokens
+      SET revoked_at = CURRENT_TIMESTAMP
+      WHERE profile_id = $1 AND revoked_at IS NULL",
+      profile_id
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(())
+  }
+
+  async fn store_session(&self, session: &NewSession) -> Result<(), AppError> {
+    sqlx::query!(
+      "INSERT INTO sessions (id, profile_id, device_label, ip_address, user_agent)
+      VALUES ($1, $2, $3, $4, $5)",
+      session.id,
+      session.profile_id,
+      session.device_label,
+      session.ip_address,
+      session.user_agent
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(())
+  }
+
+  async fn find_sessions(&self, profile_id: &str) -> Result<Vec<Session>, AppError> {
+    let sessions = sqlx::query_as!(
+      Session,
+      "SELECT id, device_label, ip_address, user_agent, created_at, last_seen_at
+      FROM sessions
+      WHERE
+        profile_id = $1
+        AND revoked_at IS NULL
+        AND EXISTS (
+          SELECT 1 FROM refresh_tokens
+          WHERE
+            refresh_tokens.family_id = sessions.id
+            AND refresh_tokens.used_at IS NULL
+            AND refresh_tokens.revoked_at IS NULL
+            AND refresh_tokens.expires_at > CURRENT_TIMESTAMP
+        )
+      ORDER BY last_seen_at DESC, created_at DESC",
+      profile_id
+    )
+    .fetch_all(self.pool)
+    .await?;
+
+    Ok(sessions)
+  }
+
+  async fn revoke_session(&self, profile_id: &str, session_id: &str) -> Result<(), AppError> {
+    let revoked = sqlx::query_scalar!(
+      r#"WITH revoked AS (
+        UPDATE sessions
+        SET revoked_at = CURRENT_TIMESTAMP
+        WHERE id = $1 AND profile_id = $2 AND revoked_at IS NULL
+        RETURNING id
+      ), revoked_tokens AS (
+        UPDATE refresh_tokens
+        SET revoked_at = CURRENT_TIMESTAMP
+        FROM revoked
+        WHERE refresh_tokens.family_id = revoked.id AND refresh_tokens.revoked_at IS NULL
+      )
+      SELECT id AS "id!" FROM revoked"#,
+      session_id,
+      profile_id
+    )
+    .fetch_optional(self.pool)
+    .await?;
+
+    if revoked.is_none() {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
+
+    Ok(())
+  }
+
+  async fn revoke_other_sessions<'a>(
+    &self,
+    profile_id: &str,
+    keep_session_id: Option<&'a str>,
+  ) -> Result<Vec<String>, AppError> {
+    let revoked = sqlx::query_scalar!(
+      r#"WITH revoked AS (
+        UPDATE sessions
+        SET revoked_at = CURRENT_TIMESTAMP
+        WHERE
+          profile_id = $1
+          AND revoked_at IS NULL
+          AND ($2::TEXT IS NULL OR id <> $2)
+        RETURNING id
+      ), revoked_tokens AS (
+        UPDATE refresh_tokens
+        SET revoked_at = CURRENT_TIMESTAMP
+        FROM revoked
+        WHERE refresh_tokens.family_id = revoked.id AND refresh_tokens.revoked_at IS NULL
+      )
+      SELECT id AS "id!" FROM revoked"#,
+      profile_id,
+      keep_session_id
+    )
+    .fetch_all(self.pool)
+    .await?;
+
+    Ok(revoked)
+  }
+
+  async fn store_verification_token(&self, token: &NewVerificationToken) -> Result<(), AppError> {
+    sqlx::query!(
+      "INSERT INTO verification_tokens (token_hash, purpose, profile_id, target, expires_at)
+      VALUES ($1, $2, $3, $4, $5)",
+      hash_token(&token.token),
+      token.purpose.as_str(),
+      token.profile_id,
+      token.target,
+      token.expires_at
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(())
+  }
+
+  async fn find_verification_token(
+    &self,
+    token: &str,
+    purpose: VerificationPurpose,
+  ) -> Result<VerificationTokenData, AppError> {
+    let verification_token = sqlx::query!(
+      "SELECT id, purpose, profile_id, target, expires_at, used_at, attempts
+      FROM verification_tokens
+      WHERE token_hash = $1 AND purpose = $2",
+      hash_token(token),
+      purpose.as_str()
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    Ok(VerificationTokenData {
+      id: verification_token.id,
+      purpose: VerificationPurpose::try_from(verification_token.purpose.as_str())?,
+      profile_id: verification_token.profile_id,
+      target: verification_token.target,
+      expires_at: verification_token.expires_at,
+      used: verification_token.used_at.is_some(),
+      attempts: verification_token.attempts,
+    })
+  }
+
+  async fn use_verification_token(&self, id: i32) -> Result<bool, AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE verification_tokens
+      SET used_at = CURRENT_TIMESTAMP
+      WHERE id = $1 AND used_at IS NULL",
+      id
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(query_result.rows_affected() == 1)
+  }
+
+  async fn check_email(&self, profile_id: &str, address: &str) -> Result<(), AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE emails
+      SET checked = true, checked_at = CURRENT_TIMESTAMP
+      WHERE profile_id = $1 AND address = $2",
+      profile_id,
+      address
+    )
+    .execute(self.pool)
+    .await?;
+
+    if query_result.rows_affected() < 1 {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
+
+    Ok(())
+  }
+
+  async fn count_verification_tokens(
+    &self,
+    profile_id: &str,
+    purpose: VerificationPurpose,
+    since: NaiveDateTime,
+  ) -> Result<i64, AppError> {
+    let count = sqlx::query!(
+      r#"SELECT COUNT(*) AS "count!"
+      FROM verification_tokens
+      WHERE profile_id = $1 AND purpose = $2 AND created_at >= $3"#,
+      profile_id,
+      purpose.as_str(),
+      since
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    Ok(count.count)
+  }
+
+  async fn find_latest_verification_token(
+    &self,
+    profile_id: &str,
+    purpose: VerificationPurpose,
+    target: &str,
+  ) -> Result<VerificationTokenData, AppError> {
+    let verification_token = sqlx::query!(
+      "SELECT id, purpose, profile_id, target, expires_at, used_at, attempts
+      FROM verification_tokens
+      WHERE profile_id = $1 AND purpose = $2 AND target = $3
+      ORDER BY created_at DESC, id DESC
+      LIMIT 1",
+      profile_id,
+      purpose.as_str(),
+      target
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    Ok(VerificationTokenData {
+      id: verification_token.id,
+      purpose: VerificationPurpose::try_from(verification_token.purpose.as_str())?,
+      profile_id: verification_token.profile_id,
+      target: verification_token.target,
+      expires_at: verification_token.expires_at,
+      used: verification_token.used_at.is_some(),
+      attempts: verification_token.attempts,
+    })
+  }
+
+  async fn attempt_verification_token(
+    &self,
+    id: i32,
+    token: &str,
+    max_attempts: i32,
+  ) -> Result<Option<bool>, AppError> {
+    let attempt = sqlx::query!(
+      r#"UPDATE verification_tokens
+      SET attempts = attempts + 1
+      WHERE id = $1 AND attempts < $3 AND used_at IS NULL
+      RETURNING token_hash = $2 AS "matches!""#,
+      id,
+      hash_token(token),
+      max_attempts
+    )
+    .fetch_optional(self.pool)
+    .await?;
+
+    Ok(attempt.map(|attempt| attempt.matches))
+  }
+
+  async fn check_telephone(&self, profile_id: &str, number: &str) -> Result<(), AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE telephones
+      SET checked = true, checked_at = CURRENT_TIMESTAMP
+      WHERE profile_id = $1 AND number = $2",
+      profile_id,
+      number
+    )
+    .execute(self.pool)
+    .await?;
+
+    if query_result.rows_affected() < 1 {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
+
+    Ok(())
+  }
+
+  async fn find_totp_authenticator(
+    &self,
+    profile_id: &str,
+  ) -> Result<TotpAuthenticatorData, AppError> {
+    let authenticator = sqlx::query!(
+      "SELECT profile_id, secret, last_used_step, confirmed_at
+      FROM totp_authenticators
+      WHERE profile_id = $1",
+      profile_id
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    Ok(TotpAuthenticatorData {
+      profile_id: authenticator.profile_id,
+      secret: authenticator.secret,
+      last_used_step: authenticator.last_used_step,
+      confirmed: authenticator.confirmed_at.is_some(),
+    })
+  }
+
+  async fn store_totp_authenticator(&self, profile_id: &str, secret: &str) -> Result<(), AppError> {
+    sqlx::query!(
+      "INSERT INTO totp_authenticators (profile_id, secret)
+      VALUES ($1, $2)
+      ON CONFLICT (profile_id) DO UPDATE
+      SET
+        secret = EXCLUDED.secret,
+        last_used_step = NULL,
+        created_at = CURRENT_TIMESTAMP
+      WHERE totp_authenticators.confirmed_at IS NULL",
+      profile_id,
+      secret
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(())
+  }
+
+  async fn confirm_totp_authenticator(
+    &self,
+    profile_id: &str,
+    step: i64,
+    recovery_codes: &[String],
+  ) -> Result<(), AppError> {
+    let mut transaction = self.pool.begin().await?;
+
+    let query_result = sqlx::query!(
+      "UPDATE totp_authenticators
+      SET confirmed_at = CURRENT_TIMESTAMP, last_used_step = $2
+      WHERE profile_id = $1 AND confirmed_at IS NULL",
+      profile_id,
+      step
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    if query_result.rows_affected() < 1 {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
+
+    sqlx::query!(
+      "DELETE FROM recovery_codes WHERE profile_id = $1",
+      profile_id
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    let code_hashes: Vec<String> = recovery_codes.iter().map(|code| hash_token(code)).collect();
+    sqlx::query!(
+      "INSERT INTO recovery_codes (profile_id, code_hash)
+      SELECT $1, code_hash FROM UNNEST($2::TEXT[]) AS code_hash",
+      profile_id,
+      &code_hashes
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+  }
+
+  async fn use_totp_step(&self, profile_id: &str, step: i64) -> Result<bool, AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE totp_authenticators
+      SET last_used_step = $2
+      WHERE
+        profile_id = $1
+        AND confirmed_at IS NOT NULL
+        AND (last_used_step IS NULL OR last_used_step < $2)",
+      profile_id,
+      step
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(query_result.rows_affected() == 1)
+  }
+
+  async fn use_recovery_code(&self, profile_id: &str, code: &str) -> Result<bool, AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE recovery_codes
+      SET used_at = CURRENT_TIMESTAMP
+      WHERE id = (
+        SELECT id
+        FROM recovery_codes
+        WHERE profile_id = $1 AND code_hash = $2 AND used_at IS NULL
+        LIMIT 1
+      )",
+      profile_id,
+      hash_token(code)
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(query_result.rows_affected() == 1)
+  }
+
+  async fn store_webauthn_credential(
+    &self,
+    credential: &NewWebAuthnCredential,
+  ) -> Result<(), AppError> {
+    sqlx::query!(
+      "INSERT INTO webauthn_credentials (credential_id, profile_id, public_key, sign_count, name)
+      VALUES ($1, $2, $3, $4, $5)",
+      credential.credential_id,
+      credential.profile_id,
+      credential.public_key,
+      credential.sign_count,
+      credential.name
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(())
+  }
+
+  async fn find_webauthn_credential(
+    &self,
+    credential_id: &str,
+  ) -> Result<WebAuthnCredentialData, AppError> {
+    let credential = sqlx::query!(
+      "SELECT id, credential_id, profile_id, public_key, sign_count, name
+      FROM webauthn_credentials
+      WHERE credential_id = $1",
+      credential_id
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    Ok(WebAuthnCredentialData {
+      id: credential.id,
+      credential_id: credential.credential_id,
+      profile_id: credential.profile_id,
+      public_key: credential.public_key,
+      sign_count: credential.sign_count,
+      name: credential.name,
+    })
+  }
+
+  async fn find_webauthn_credentials(
+    &self,
+    profile_id: &str,
+  ) -> Result<Vec<WebAuthnCredentialData>, AppError> {
+    let credentials = sqlx::query!(
+      "SELECT id, credential_id, profile_id, public_key, sign_count, name
+      FROM webauthn_credentials
+      WHERE profile_id = $1
+      ORDER BY id",
+      profile_id
+    )
+    .fetch_all(self.pool)
+    .await?;
+
+    Ok(
+      credentials
+        .into_iter()
+        .map(|credential| WebAuthnCredentialData {
+          id: credential.id,
+          credential_id: credential.credential_id,
+          profile_id: credential.profile_id,
+          public_key: credential.public_key,
+          sign_count: credential.sign_count,
+          name: credential.name,
+        })
+        .collect(),
+    )
+  }
+
+  async fn update_webauthn_sign_count(&self, id: i32, sign_count: i64) -> Result<(), AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE webauthn_credentials
+      SET sign_count = $2, last_used_at = CURRENT_TIMESTAMP
+      WHERE id = $1",
+      id,
+      sign_count
+    )
+    .execute(self.pool)
+    .await?;
+
+    if query_result.rows_affected() < 1 {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
+
+    Ok(())
+  }
+
+  async fn store_oauth_client(&self, client: &NewOAuthClient) -> Result<(), AppError> {
+    sqlx::query!(
+      "INSERT INTO oauth_clients (client_id, secret_hash, name, redirect_uris)
+      VALUES ($1, $2, $3, $4)",
+      client.client_id,
+      client.secret_hash,
+      client.name,
+      &client.redirect_uris
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(())
+  }
+
+  async fn find_oauth_client(&self, client_id: &str) -> Result<OAuthClientData, AppError> {
+    let client = sqlx::query!(
+      "SELECT id, client_id, secret_hash, name, redirect_uris
+      FROM oauth_clients
+      WHERE client_id = $1",
+      client_id
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    Ok(OAuthClientData {
+      id: client.id,
+      client_id: client.client_id,
+      name: client.name,
+      redirect_uris: client.redirect_uris,
+      secret_hash: client.secret_hash,
+    })
+  }
+
+  async fn store_authorization_code(&self, code: &NewAuthorizationCode) -> Result<(), AppError> {
+    sqlx::query!(
+      "INSERT INTO oauth_authorization_codes 
+        (code_hash, client_id, profile_id, redirect_uri, scope, code_challenge, nonce, expires_at)
+      VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+      hash_token(&code.code),
+      code.client_id,
+      code.profile_id,
+      code.redirect_uri,
+      code.scope,
+      code.code_challenge,
+      code.nonce,
+      code.expires_at
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(())
+  }
+
+  async fn find_authorization_code(&self, code: &str) -> Result<AuthorizationCodeData, AppError> {
+    let code = sqlx::query!(
+      "SELECT id, client_id, profile_id, redirect_uri, scope, code_challenge, nonce, 
+        expires_at, used_at
+      FROM oauth_authorization_codes
+      WHERE code_hash = $1",
+      hash_token(code)
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    Ok(AuthorizationCodeData {
+      id: code.id,
+      client_id: code.client_id,
+      profile_id: code.profile_id,
+      redirect_uri: code.redirect_uri,
+      scope: code.scope,
+      code_challenge: code.code_challenge,
+      nonce: code.nonce,
+      expires_at: code.expires_at,
+      used: code.used_at.is_some(),
+    })
+  }
+
+  async fn use_authorization_code(&self, id: i32) -> Result<bool, AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE oauth_authorization_codes
+      SET used_at = CURRENT_TIMESTAMP
+      WHERE id = $1 AND used_at IS NULL",
+      id
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(query_result.rows_affected() == 1)
+  }
+
+  async fn store_external_sign_in(&self, sign_in: &NewExternalSignIn) -> Result<(), AppError> {
+    sqlx::query!(
+      "INSERT INTO external_sign_ins (state_hash, provider, code_verifier, nonce, expires_at)
+      VALUES ($1, $2, $3, $4, $5)",
+      hash_token(&sign_in.state),
+      sign_in.provider,
+      sign_in.code_verifier,
+      sign_in.nonce,
+      sign_in.expires_at
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(())
+  }
+
+  async fn find_external_sign_in(&self, state: &str) -> Result<ExternalSignInData, AppError> {
+    let sign_in = sqlx::query!(
+      "SELECT id, provider, code_verifier, nonce, expires_at, used_at, subject, email,
+        email_verified, name, preferred_username, completed_at
+      FROM external_sign_ins
+      WHERE state_hash = $1",
+      hash_token(state)
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    let claims = match (sign_in.subject, sign_in.email) {
+      (Some(subject), Some(email)) => Some(ExternalClaims {
+        subject,
+        email,
+        email_verified: sign_in.email_verified,
+        name: sign_in.name,
+        preferred_username: sign_in.preferred_username,
+      }),
+      _ => None,
+    };
+
+    Ok(ExternalSignInData {
+      id: sign_in.id,
+      provider: sign_in.provider,
+      code_verifier: sign_in.code_verifier,
+      nonce: sign_in.nonce,
+      expires_at: sign_in.expires_at,
+      used: sign_in.used_at.is_some(),
+      claims,
+      completed: sign_in.completed_at.is_some(),
+    })
+  }
+
+  async fn use_external_sign_in(&self, id: i32) -> Result<bool, AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE external_sign_ins
+      SET used_at = CURRENT_TIMESTAMP
+      WHERE id = $1 AND used_at IS NULL",
+      id
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(query_result.rows_affected() == 1)
+  }
+
+  async fn hold_external_sign_up(
+    &self,
+    id: i32,
+    claims: &ExternalClaims,
+    expires_at: NaiveDateTime,
+  ) -> Result<(), AppError> {
+    sqlx::query!(
+      "UPDATE external_sign_ins
+      SET subject = $2, email = $3, email_verified = $4, name = $5, preferred_username = $6,
+        expires_at = $7
+      WHERE id = $1",
+      id,
+      claims.subject,
+      claims.email,
+      claims.email_verified,
+      claims.name,
+      claims.preferred_username,
+      expires_at
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(())
+  }
+
+  async fn complete_external_sign_up(&self, id: i32) -> Result<bool, AppError> {
+    let query_result = sqlx::query!(
+      "UPDATE external_sign_ins
+      SET completed_at = CURRENT_TIMESTAMP
+      WHERE id = $1 AND completed_at IS NULL",
+      id
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(query_result.rows_affected() == 1)
+  }
+
+  async fn find_external_identity(
+    &self,
+    provider: &str,
+    subject: &str,
+  ) -> Result<ExternalIdentityData, AppError> {
+    let identity = sqlx::query_as!(
+      ExternalIdentityData,
+      "SELECT id, provider, subject, profile_id
+      FROM external_identities
+      WHERE provider = $1 AND subject = $2",
+      provider,
+      subject
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    Ok(identity)
+  }
+
+  async fn store_external_identity(&self, identity: &NewExternalIdentity) -> Result<(), AppError> {
+    sqlx::query!(
+      "INSERT INTO external_identities (provider, subject, profile_id, email)
+      VALUES ($1, $2, $3, $4)",
+      identity.provider,
+      identity.subject,
+      identity.profile_id,
+      identity.email
+    )
+    .execute(self.pool)
+    .await?;
+
+    Ok(())
+  }
+
+  async fn find_grants(&self, profile_id: &str) -> Result<Grants, AppError> {
+    let grants = sqlx::query_as!(
+      Grants,
+      r#"SELECT
+        COALESCE(ARRAY_AGG(DISTINCT r.name), '{}') AS "roles!",
+        COALESCE(ARRAY_AGG(DISTINCT p.name) FILTER (WHERE p.name IS NOT NULL), '{}')
+          AS "permissions!"
+      FROM profile_roles pr
+      JOIN roles r ON r.id = pr.role_id
+      LEFT JOIN role_permissions rp ON rp.role_id = r.id
+      LEFT JOIN permissions p ON p.id = rp.permission_id
+      WHERE pr.profile_id = $1"#,
+      profile_id
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    Ok(grants)
+  }
+
+  async fn find_token_version(&self, profile_id: &str) -> Result<i32, AppError> {
+    let user = sqlx::query!(
+      "SELECT users.token_version
+      FROM users
+      JOIN profiles ON profiles.user_id = users.id
+      WHERE profiles.id = $1",
+      profile_id
+    )
+    .fetch_optional(self.pool)
+    .await?
+    .ok_or_else(|| AppError::not_found("DB: nothing found with given parameters"))?;
+
+    Ok(user.token_version)
+  }
+
+  async fn grant_role(&self, profile_id: &str, role: &str) -> Result<bool, AppError> {
+    let granted = sqlx::query!(
+      r#"WITH target AS (
+        SELECT profiles.id AS profile_id, roles.id AS role_id
+        FROM profiles, roles
+        WHERE profiles.id = $1 AND roles.name = $2
+      ), inserted AS (
+        INSERT INTO profile_roles (profile_id, role_id)
+        SELECT profile_id, role_id FROM target
+        ON CONFLICT DO NOTHING
+        RETURNING 1
+      )
+      SELECT
+        EXISTS (SELECT 1 FROM target) AS "found!",
+        EXISTS (SELECT 1 FROM inserted) AS "changed!""#,
+      profile_id,
+      role
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    if !granted.found {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
+
+    Ok(granted.changed)
+  }
+
+  async fn revoke_role(&self, profile_id: &str, role: &str) -> Result<bool, AppError> {
+    let revoked = sqlx::query!(
+      r#"WITH target AS (
+        SELECT profiles.id AS profile_id, roles.id AS role_id
+        FROM profiles, roles
+        WHERE profiles.id = $1 AND roles.name = $2
+      ), deleted AS (
+        DELETE FROM profile_roles
+        USING target
+        WHERE
+          profile_roles.profile_id = target.profile_id
+          AND profile_roles.role_id = target.role_id
+        RETURNING 1
+      ), bumped AS (
+        UPDATE users
+        SET token_version = token_version + 1
+        FROM target, profiles
+        WHERE
+          profiles.id = target.profile_id
+          AND users.id = profiles.user_id
+          AND EXISTS (SELECT 1 FROM deleted)
+      )
+      SELECT
+        EXISTS (SELECT 1 FROM target) AS "found!",
+        EXISTS (SELECT 1 FROM deleted) AS "changed!""#,
+      profile_id,
+      role
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    if !revoked.found {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
+
+    Ok(revoked.changed)
+  }
+
+  async fn require_password_reset(&self, profile_id: &str) -> Result<(), AppError> {
+    let updated = sqlx::query_scalar!(
+      r#"WITH updated AS (
+        UPDATE users
+        SET
+          password_reset_required = true,
+          token_version = token_version + 1
+        FROM profiles
+        WHERE
+          profiles.user_id = users.id
+          AND profiles.id = $1
+        RETURNING users.id
+      ), revoked_tokens AS (
+        UPDATE refresh_tokens
+        SET revoked_at = CURRENT_TIMESTAMP
+        FROM profiles, updated
+        WHERE
+          profiles.user_id = updated.id
+          AND refresh_tokens.profile_id = profiles.id
+          AND refresh_tokens.revoked_at IS NULL
+      )
+      SELECT COUNT(*) AS "updated!" FROM updated"#,
+      profile_id
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    if updated < 1 {
+      return Err(AppError::not_found(
+        "DB: nothing found with given parameters",
+      ));
+    }
+
+    Ok(())
+  }
+
+  async fn find_users(
+    &self,
+    filter: &UserFilter,
+    page: i64,
+    per_page: i64,
+  ) -> Result<UserSummaryPage, AppError> {
+    let total = sqlx::query_scalar!(
+      r#"SELECT COUNT(*) AS "total!"
+      FROM profiles
+      WHERE
+        ($1::TEXT IS NULL OR strpos(lower(profiles.username), lower($1)) > 0)
+        AND ($2::TEXT IS NULL OR EXISTS (
+          SELECT 1 FROM emails
+          WHERE emails.profile_id = profiles.id AND strpos(lower(emails.address), lower($2)) > 0
+        ))
+        AND ($3::DATE IS NULL OR profiles.created_at >= $3)
+        AND ($4::DATE IS NULL OR profiles.created_at < $4 + 1)"#,
+      filter.username,
+      filter.email,
+      filter.created_from,
+      filter.created_to
+    )
+    .fetch_one(self.pool)
+    .await?;
+
+    let users = sqlx::query!(
+      r#"SELECT
+        profiles.id,
+        profiles.name,
+        profiles.username,
+        ARRAY(
+          SELECT emails.address FROM emails
+          WHERE emails.profile_id = profiles.id
+          ORDER BY emails.address
+        ) AS "emails!",
+        ARRAY(
+          SELECT roles.name FROM profile_roles
+          JOIN roles ON roles.id = profile_roles.role_id
+          WHERE profile_roles.profile_id = profiles.id
+          ORDER BY roles.name
+        ) AS "roles!",
+        users.account_disabled,
+        users.blocked_by_attempts,
+        users.requested_deletion,
+        users.password_reset_required,
+        profiles.created_at
+      FROM profiles
+      JOIN users ON users.id = profiles.user_id
+      WHERE
+        ($1::TEXT IS NULL OR strpos(lower(profiles.username), lower($1)) > 0)
+        AND ($2::TEXT IS NULL OR EXISTS (
+          SELECT 1 FROM emails
+          WHERE emails.profile_id = profiles.id AND strpos(lower(emails.address), lower($2)) > 0
+        ))
+        AND ($3::DATE IS NULL OR profiles.created_at >= $3)
+        AND ($4::DATE IS NULL OR profiles.created_at < $4 + 1)
+      ORDER BY
+        CASE WHEN $5 = 'created_at' THEN profiles.created_at END ASC,
+        CASE WHEN $5 = '-created_at' THEN profiles.created_at END DESC,
+        CASE WHEN $5 = 'username' THEN profiles.username END ASC,
+        CASE WHEN $5 = '-username' THEN profiles.username END DESC,
+        profiles.id
+      LIMIT $6 OFFSET $7"#,
+      filter.username,
+      filter.email,
+      filter.created_from,
+      filter.created_to,
+      filter.sort.as_str(),
+      per_page,
+      (page - 1) * per_page
+    )
+    .fetch_all(self.pool)
+    .await?;
+
+    Ok(UserSummaryPage {
+      users: users
+        .into_iter()
+        .map(|user| UserSummary {
+          id: user.id,
+          name: user.name,
+          username: user.username,
+          emails: user.emails,
+          roles: user.roles,
+          account_disabled: user.account_disabled,
+          blocked_by_attempts: user.blocked_by_attempts,
+          requested_deletion: user.requested_deletion,
+          password_reset_required: user.password_reset_required,
+          created_at: user.created_at,
+        })
+        .collect(),
+      page,
+      per_page,
+      total,
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use crate::{
+    domain::{
+      entities::role::{permissions, ADMIN_ROLE},
+      error::Code,
+    },
+    tests_e2e::helpers::user_repository::{assert_user, insert_user},
+  };
+
+  use super::*;
+  use chrono::{Duration, NaiveDate, Utc};
+  use sqlx::PgPool;
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_store_new_user(pool: PgPool) -> sqlx::Result<()> {
+    let sut = UserRepositoryDB { pool: &pool };
+
+    sut
+      .store(&sign_up::Request {
+        id: ID.to_owned(),
+        name: NAME,
+        username: USERNAME,
+        birth_date: NaiveDate::parse_from_str(BIRTH_DATE, "%Y-%m-%d").unwrap(),
+        gender_id: GENDER_ID,
+        password: sign_up::encrypter::PasswordEncrypted(PASSWORD.to_string()),
+        street: STREET,
+        neighborhood: NEIGHBORHOOD,
+        city_id: CITY_ID,
+        postal_code: POSTAL_CODE,
+        email_address: EMAIL_ADDRESS,
+        telephone_number: Some(TELEPHONE_NUMBER),
+      })
+      .await
+      .unwrap();
+
+    assert_user(
+      &pool,
+      &UserColumns::Username(USERNAME),
+      UserData {
+        id: ID.to_owned(),
+        name: NAME.to_owned(),
+        username: USERNAME.to_owned(),
+        password: PASSWORD.to_owned(),
+        ..Default::default()
+      },
+      Some(|req_pwd, db_pwd| req_pwd == db_pwd),
+    )
+    .await;
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_try_store_duplicate_user(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+    let response = sut
+      .store(&sign_up::Request {
+        id: ID.to_owned(),
+        name: NAME,
+        username: USERNAME,
+        birth_date: NaiveDate::parse_from_str(BIRTH_DATE, "%Y-%m-%d").unwrap(),
+        gender_id: GENDER_ID,
+        password: sign_up::encrypter::PasswordEncrypted(PASSWORD.to_string()),
+        street: STREET,
+        neighborhood: NEIGHBORHOOD,
+        city_id: CITY_ID,
+        postal_code: POSTAL_CODE,
+        email_address: EMAIL_ADDRESS,
+        telephone_number: Some(TELEPHONE_NUMBER),
+      })
+      .await;
+
+    match response {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => {
+        assert_eq!(error.code, Code::AlreadyExists, "fail but wrong code");
+      }
+    };
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_find_user_using_username(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut
+      .find_user_by(&UserColumns::Username(USERNAME))
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response,
+      UserData {
+        id: ID.to_string(),
+        name: NAME.to_string(),
+        password: PASSWORD.to_string(),
+        username: USERNAME.to_string(),
+        account_disabled: false,
+        mfa_enabled: false,
+        requested_deletion: false,
+        email_checked: false,
+        telephone_checked: false,
+        blocked_by_attempts: false,
+        blocked_at: None,
+        password_reset_required: false,
+      }
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_find_user_using_id(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+
+    assert_eq!(
+      response,
+      UserData {
+        id: ID.to_string(),
+        name: NAME.to_string(),
+        password: PASSWORD.to_string(),
+        username: USERNAME.to_string(),
+        account_disabled: false,
+        mfa_enabled: false,
+        requested_deletion: false,
+        email_checked: false,
+        telephone_checked: false,
+        blocked_by_attempts: false,
+        blocked_at: None,
+        password_reset_required: false,
+      }
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_find_user_using_email(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut
+      .find_user_by(&UserColumns::Email(EMAIL_ADDRESS))
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response,
+      UserData {
+        id: ID.to_string(),
+        name: NAME.to_string(),
+        password: PASSWORD.to_string(),
+        username: USERNAME.to_string(),
+        account_disabled: false,
+        mfa_enabled: false,
+        requested_deletion: false,
+        email_checked: false,
+        telephone_checked: false,
+        blocked_by_attempts: false,
+        blocked_at: None,
+        password_reset_required: false,
+      }
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_find_user_using_telephone(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut
+      .find_user_by(&UserColumns::Telephone(TELEPHONE_NUMBER))
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response,
+      UserData {
+        id: ID.to_string(),
+        name: NAME.to_string(),
+        password: PASSWORD.to_string(),
+        username: USERNAME.to_string(),
+        account_disabled: false,
+        mfa_enabled: false,
+        requested_deletion: false,
+        email_checked: false,
+        telephone_checked: false,
+        blocked_by_attempts: false,
+        blocked_at: None,
+        password_reset_required: false,
+      }
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_search_with_invalid_user(pool: PgPool) -> sqlx::Result<()> {
+    const WRONG_ID: &str = "3d59b3cc-4ba4-09a4-90cb-956c4a2df911";
+
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut.find_user_by(&UserColumns::Id(WRONG_ID)).await;
+
+    match response {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    };
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_find_full_profile_using_id(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut
+      .find_full_profile_by(&UserColumns::Id(ID))
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response,
+      FullProfile {
+        id: ID.to_string(),
+        name: NAME.to_string(),
+        username: USERNAME.to_string(),
+        birth_date: NaiveDate::parse_from_str(BIRTH_DATE, "%Y-%m-%d").unwrap(),
+        gender: "Male".to_string(),
+        telephones: vec![Telephone {
+          number: TELEPHONE_NUMBER.to_string(),
+          checked: false,
+        }],
+        emails: vec![Email {
+          address: EMAIL_ADDRESS.to_string(),
+          checked: false,
+        }],
+        address: Address {
+          street: STREET.to_string(),
+          neighborhood: NEIGHBORHOOD.to_string(),
+          postal_code: POSTAL_CODE,
+          city: "New York City".to_string(),
+          state: "New York".to_string(),
+          country: "United States".to_string(),
+        },
+      }
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_find_full_profile_without_telephone(pool: PgPool) -> sqlx::Result<()> {
+    insert_user(
+      &pool,
+      ID,
+      NAME,
+      USERNAME,
+      BIRTH_DATE,
+      GENDER_ID,
+      PASSWORD,
+      STREET,
+      NEIGHBORHOOD,
+      CITY_ID,
+      POSTAL_CODE,
+      EMAIL_ADDRESS,
+      None,
+    )
+    .await;
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut
+      .find_full_profile_by(&UserColumns::Username(USERNAME))
+      .await
+      .unwrap();
+
+    assert!(response.telephones.is_empty());
+    assert_eq!(response.emails.len(), 1);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_find_full_profile_with_invalid_user(pool: PgPool) -> sqlx::Result<()> {
+    const WRONG_ID: &str = "3d59b3cc-4ba4-09a4-90cb-956c4a2df911";
+
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut.find_full_profile_by(&UserColumns::Id(WRONG_ID)).await;
+
+    match response {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    };
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_update_user_password(pool: PgPool) -> sqlx::Result<()> {
+    const NEW_PASSWORD: &str = "987654321";
+
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+    sut.update_password(NEW_PASSWORD, ID).await.unwrap();
+
+    assert_user(
+      &pool,
+      &UserColumns::Id(ID),
+      UserData {
+        id: ID.to_owned(),
+        name: NAME.to_owned(),
+        username: USERNAME.to_owned(),
+        password: NEW_PASSWORD.to_owned(),
+        ..Default::default()
+      },
+      Some(|new_pwd, db_pwd| new_pwd == db_pwd),
+    )
+    .await;
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_update_user_password_bumps_token_version(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+    assert_eq!(sut.find_token_version(ID).await.unwrap(), 0);
+
+    sut.update_password("987654321", ID).await.unwrap();
+    sut.update_password("123456789", ID).await.unwrap();
+
+    assert_eq!(sut.find_token_version(ID).await.unwrap(), 2);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_rehash_user_password_keeps_token_version(pool: PgPool) -> sqlx::Result<()> {
+    const NEW_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaGhhc2g";
+
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+    let user = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+    sut
+      .rehash_password(NEW_HASH, &user.password, ID)
+      .await
+      .unwrap();
+
+    assert_user(
+      &pool,
+      &UserColumns::Id(ID),
+      UserData {
+        id: ID.to_owned(),
+        name: NAME.to_owned(),
+        username: USERNAME.to_owned(),
+        password: NEW_HASH.to_owned(),
+        ..Default::default()
+      },
+      Some(|new_pwd, db_pwd| new_pwd == db_pwd),
+    )
+    .await;
+    assert_eq!(sut.find_token_version(ID).await.unwrap(), 0);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_skip_rehash_of_changed_password(pool: PgPool) -> sqlx::Result<()> {
+    const NEW_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaGhhc2g";
+
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+    let verified = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+    sut.update_password("987654321", ID).await.unwrap();
+
+    let response = sut.rehash_password(NEW_HASH, &verified.password, ID).await;
+
+    assert!(response.is_err());
+    let user = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+    assert_eq!(user.password, "987654321");
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_find_token_version_of_unknown_user(pool: PgPool) -> sqlx::Result<()> {
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut.find_token_version(ID).await;
+
+    assert!(response.is_err());
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_update_user_password_with_wrong_id(pool: PgPool) -> sqlx::Result<()> {
+    const NEW_PASSWORD: &str = "987654321";
+    const WRONG_ID: &str = "3d59b3cc-4ba4-09a4-90cb-956c4a2df911";
+
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut.update_password(NEW_PASSWORD, WRONG_ID).await;
+
+    match response {
+      Ok(_) => panic!("this test should no be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::invalid_argument("wrong old password or user does not exist")
+      ),
+    };
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_update_user_profile(pool: PgPool) -> sqlx::Result<()> {
+    const NEW_NAME: &str = "Jane Doe";
+    const NEW_STREET: &str = "1 Wall St";
+    const NEW_EMAIL_ADDRESS: &str = "janedoe@company.com";
+
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+    sut
+      .update_profile(&update_profile::Request {
+        profile_id: ID,
+        name: Some(NEW_NAME),
+        address: Some(update_profile::Address {
+          street: NEW_STREET,
+          neighborhood: NEIGHBORHOOD,
+          city_id: CITY_ID,
+          postal_code: POSTAL_CODE,
+        }),
+        email_address: Some(NEW_EMAIL_ADDRESS),
+        ..Default::default()
+      })
+      .await
+      .unwrap();
+
+    let profile = sut
+      .find_full_profile_by(&UserColumns::Id(ID))
+      .await
+      .unwrap();
+
+    assert_eq!(profile.name, NEW_NAME);
+    assert_eq!(profile.username, USERNAME);
+    assert_eq!(profile.address.street, NEW_STREET);
+    assert_eq!(profile.emails.len(), 2);
+    assert!(profile
+      .emails
+      .iter()
+      .any(|email| email.address == NEW_EMAIL_ADDRESS));
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_update_user_profile_with_wrong_id(pool: PgPool) -> sqlx::Result<()> {
+    const WRONG_ID: &str = "3d59b3cc-4ba4-09a4-90cb-956c4a2df911";
+
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut
+      .update_profile(&update_profile::Request {
+        profile_id: WRONG_ID,
+        name: Some(NAME),
+        ..Default::default()
+      })
+      .await;
+
+    match response {
+      Ok(_) => panic!("this test should no be success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    };
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_register_failed_sign_in_blocks_user(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    assert!(!sut.register_failed_sign_in(ID, 2).await.unwrap());
+    let user = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+    assert!(!user.blocked_by_attempts);
+
+    assert!(sut.register_failed_sign_in(ID, 2).await.unwrap());
+    let user = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+    assert!(user.blocked_by_attempts);
+    assert!(user.blocked_at.is_some());
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_register_sign_in_resets_failed_attempts(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    sut.register_failed_sign_in(ID, 2).await.unwrap();
+    sut.register_sign_in(ID).await.unwrap();
+
+    assert!(!sut.register_failed_sign_in(ID, 2).await.unwrap());
+
+    let sign_in = sqlx::query!("SELECT sign_in_count, failed_attempts FROM sign_ins")
+      .fetch_one(&pool)
+      .await?;
+    assert_eq!(sign_in.sign_in_count, 1);
+    assert_eq!(sign_in.failed_attempts, 1);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_unlock_user(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    assert!(sut.register_failed_sign_in(ID, 1).await.unwrap());
+    sut.unlock_user(ID).await.unwrap();
+
+    let user = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+    assert!(!user.blocked_by_attempts);
+    assert_eq!(user.blocked_at, None);
+
+    let sign_in = sqlx::query!("SELECT failed_attempts FROM sign_ins")
+      .fetch_one(&pool)
+      .await?;
+    assert_eq!(sign_in.failed_attempts, 0);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_unlock_user_with_wrong_id(pool: PgPool) -> sqlx::Result<()> {
+    const WRONG_ID: &str = "3d59b3cc-4ba4-09a4-90cb-956c4a2df911";
+
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut.unlock_user(WRONG_ID).await;
+
+    match response {
+      Ok(_) => panic!("this test should no be success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    };
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_update_account_disabled(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+    store_refresh_token_default(&sut).await;
+
+    sut.update_account_disabled(ID, true).await.unwrap();
+    let user = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+    assert!(user.account_disabled);
+    assert_eq!(sut.find_token_version(ID).await.unwrap(), 1);
+    let refresh_token = sut.find_refresh_token(REFRESH_TOKEN).await.unwrap();
+    assert!(refresh_token.revoked);
+    assert!(refresh_token.account_disabled);
+
+    sut.update_account_disabled(ID, false).await.unwrap();
+    let user = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+    assert!(!user.account_disabled);
+    assert_eq!(sut.find_token_version(ID).await.unwrap(), 1);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_update_account_disabled_with_wrong_id(pool: PgPool) -> sqlx::Result<()> {
+    const WRONG_ID: &str = "3d59b3cc-4ba4-09a4-90cb-956c4a2df911";
+
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut.update_account_disabled(WRONG_ID, true).await;
+
+    match response {
+      Ok(_) => panic!("this test should no be success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    };
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_require_password_reset(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    store_refresh_token_default(&sut).await;
+
+    sut.require_password_reset(ID).await.unwrap();
+
+    let user = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+    assert!(user.password_reset_required);
+    assert_eq!(sut.find_token_version(ID).await.unwrap(), 1);
+    assert!(sut.find_refresh_token(REFRESH_TOKEN).await.unwrap().revoked);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_store_and_find_sign_in_events(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    for succeeded in [false, false, true] {
+      sut
+        .store_sign_in_event(&NewSignInEvent {
+          profile_id: ID.to_string(),
+          method: SignInMethod::Email,
+          succeeded,
+          ip_address: Some(String::from("203.0.113.7")),
+          user_agent: None,
+        })
+        .await
+        .unwrap();
+    }
+
+    let first_page = sut.find_sign_in_events(ID, 1, 2).await.unwrap();
+
+    assert_eq!(first_page.total, 3);
+    assert_eq!(first_page.events.len(), 2);
+    assert!(first_page.events[0].succeeded);
+    assert_eq!(first_page.events[0].method, SignInMethod::Email);
+    assert_eq!(
+      first_page.events[0].ip_address,
+      Some(String::from("203.0.113.7"))
+    );
+    assert_eq!(first_page.events[0].user_agent, None);
+
+    let second_page = sut.find_sign_in_events(ID, 2, 2).await.unwrap();
+
+    assert_eq!(second_page.events.len(), 1);
+    assert!(!second_page.events[0].succeeded);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_store_and_find_refresh_token(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+    let expires_at = refresh_token_expiration();
+
+    sut
+      .store_refresh_token(&NewRefreshToken {
+        token: REFRESH_TOKEN.to_string(),
+        family_id: FAMILY_ID.to_string(),
+        profile_id: ID.to_string(),
+        expires_at,
+      })
+      .await
+      .unwrap();
+
+    let stored = sqlx::query!("SELECT token_hash FROM refresh_tokens")
+      .fetch_one(&pool)
+      .await?;
+    assert_ne!(stored.token_hash, REFRESH_TOKEN);
+
+    let refresh_token = sut.find_refresh_token(REFRESH_TOKEN).await.unwrap();
+
+    assert_eq!(refresh_token.family_id, FAMILY_ID);
+    assert_eq!(refresh_token.profile_id, ID);
+    assert_eq!(refresh_token.expires_at, expires_at);
+    assert!(!refresh_token.used);
+    assert!(!refresh_token.revoked);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_find_nonexistent_refresh_token(pool: PgPool) -> sqlx::Result<()> {
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut.find_refresh_token(REFRESH_TOKEN).await;
+
+    match response {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    };
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_use_refresh_token_only_once(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    let id = store_refresh_token_default(&sut).await;
+
+    assert!(sut.use_refresh_token(id).await.unwrap());
+    assert!(!sut.use_refresh_token(id).await.unwrap());
+    assert!(sut.find_refresh_token(REFRESH_TOKEN).await.unwrap().used);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_revoke_refresh_token_family(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    let id = store_refresh_token_default(&sut).await;
+
+    sut.revoke_refresh_token_family(FAMILY_ID).await.unwrap();
+
+    assert!(sut.find_refresh_token(REFRESH_TOKEN).await.unwrap().revoked);
+    assert!(!sut.use_refresh_token(id).await.unwrap());
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_revoke_refresh_tokens(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    store_refresh_token_default(&sut).await;
+
+    sut.revoke_refresh_tokens(ID).await.unwrap();
+
+    assert!(sut.find_refresh_token(REFRESH_TOKEN).await.unwrap().revoked);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_store_and_use_verification_token(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    let expires_at = refresh_token_expiration();
+
+    sut
+      .store_verification_token(&NewVerificationToken {
+        token: VERIFICATION_TOKEN.to_string(),
+        purpose: VerificationPurpose::Email,
+        profile_id: ID.to_string(),
+        target: EMAIL_ADDRESS.to_string(),
+        expires_at,
+      })
+      .await
+      .unwrap();
+
+    let stored = sqlx::query!("SELECT token_hash FROM verification_tokens")
+      .fetch_one(&pool)
+      .await?;
+    assert_ne!(stored.token_hash, VERIFICATION_TOKEN);
+
+    let token = sut
+      .find_verification_token(VERIFICATION_TOKEN, VerificationPurpose::Email)
+      .await
+      .unwrap();
+
+    assert_eq!(token.profile_id, ID);
+    assert_eq!(token.target, EMAIL_ADDRESS);
+    assert_eq!(token.expires_at, expires_at);
+    assert!(!token.used);
+
+    assert!(sut.use_verification_token(token.id).await.unwrap());
+    assert!(!sut.use_verification_token(token.id).await.unwrap());
+    assert!(
+      sut
+        .find_verification_token(VERIFICATION_TOKEN, VerificationPurpose::Email)
+        .await
+        .unwrap()
+        .used
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_find_nonexistent_verification_token(pool: PgPool) -> sqlx::Result<()> {
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut
+      .find_verification_token(VERIFICATION_TOKEN, VerificationPurpose::Email)
+      .await;
+
+    match response {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    }
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_check_email(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+
+    sut.check_email(ID, EMAIL_ADDRESS).await.unwrap();
+
+    let user = sut
+      .find_user_by(&UserColumns::Email(EMAIL_ADDRESS))
+      .await
+      .unwrap();
+    assert!(user.email_checked);
+
+    let email = sqlx::query!(
+      "SELECT checked_at FROM emails WHERE address = $1",
+      EMAIL_ADDRESS
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert!(email.checked_at.is_some());
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_check_email_with_wrong_id(pool: PgPool) -> sqlx::Result<()> {
+    const WRONG_ID: &str = "3d59b3cc-4ba4-09a4-90cb-956c4a2df911";
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut.check_email(WRONG_ID, EMAIL_ADDRESS).await;
+
+    match response {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    }
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_store_and_attempt_telephone_code(pool: PgPool) -> sqlx::Result<()> {
+    const CODE: &str = "482910";
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    let since = Utc::now().naive_utc() - Duration::hours(1);
+
+    assert_eq!(
+      sut
+        .count_verification_tokens(ID, VerificationPurpose::Telephone, since)
+        .await
+        .unwrap(),
+      0
+    );
+
+    sut
+      .store_verification_token(&NewVerificationToken {
+        token: CODE.to_string(),
+        purpose: VerificationPurpose::Telephone,
+        profile_id: ID.to_string(),
+        target: TELEPHONE_NUMBER.to_string(),
+        expires_at: refresh_token_expiration(),
+      })
+      .await
+      .unwrap();
+
+    assert_eq!(
+      sut
+        .count_verification_tokens(ID, VerificationPurpose::Telephone, since)
+        .await
+        .unwrap(),
+      1
+    );
+
+    let code = sut
+      .find_latest_verification_token(ID, VerificationPurpose::Telephone, TELEPHONE_NUMBER)
+      .await
+      .unwrap();
+    assert_eq!(code.attempts, 0);
+
+    assert_eq!(
+      sut
+        .attempt_verification_token(code.id, "000000", 2)
+        .await
+        .unwrap(),
+      Some(false)
+    );
+    assert_eq!(
+      sut
+        .attempt_verification_token(code.id, CODE, 2)
+        .await
+        .unwrap(),
+      Some(true)
+    );
+    assert_eq!(
+      sut
+        .attempt_verification_token(code.id, CODE, 2)
+        .await
+        .unwrap(),
+      None
+    );
+
+    let code = sut
+      .find_latest_verification_token(ID, VerificationPurpose::Telephone, TELEPHONE_NUMBER)
+      .await
+      .unwrap();
+    assert_eq!(code.attempts, 2);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_find_latest_nonexistent_verification_token(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut
+      .find_latest_verification_token(ID, VerificationPurpose::Telephone, TELEPHONE_NUMBER)
+      .await;
+
+    match response {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    }
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_check_telephone(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+
+    sut.check_telephone(ID, TELEPHONE_NUMBER).await.unwrap();
+
+    let user = sut
+      .find_user_by(&UserColumns::Telephone(TELEPHONE_NUMBER))
+      .await
+      .unwrap();
+    assert!(user.telephone_checked);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_check_telephone_with_wrong_id(pool: PgPool) -> sqlx::Result<()> {
+    const WRONG_ID: &str = "3d59b3cc-4ba4-09a4-90cb-956c4a2df911";
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let response = sut.check_telephone(WRONG_ID, TELEPHONE_NUMBER).await;
+
+    match response {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    }
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_request_and_cancel_deletion(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    sut.request_deletion(ID).await.unwrap();
+    let user = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+    assert!(user.requested_deletion);
+
+    sut.cancel_deletion(ID).await.unwrap();
+    let user = sut.find_user_by(&UserColumns::Id(ID)).await.unwrap();
+    assert!(!user.requested_deletion);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_request_deletion_with_wrong_id(pool: PgPool) -> sqlx::Result<()> {
+    const WRONG_ID: &str = "3d59b3cc-4ba4-09a4-90cb-956c4a2df911";
+
+    let sut = UserRepositoryDB { pool: &pool };
+
+    match sut.request_deletion(WRONG_ID).await {
+      Ok(_) => panic!("this test should no be success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    };
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_purge_requested_deletions(pool: PgPool) -> sqlx::Result<()> {
+    const OTHER_ID: &str = "3d59b3cc-4ba4-09a4-90cb-956c4a2df911";
+
+    insert_user_default(&pool).await.unwrap();
+    insert_user(
+      &pool,
+      OTHER_ID,
+      NAME,
+      "jane.doe",
+      BIRTH_DATE,
+      GENDER_ID,
+      PASSWORD,
+      STREET,
+      NEIGHBORHOOD,
+      CITY_ID,
+      POSTAL_CODE,
+      "janedoe@company.com",
+      None,
+    )
+    .await;
+
+    let sut = UserRepositoryDB { pool: &pool };
+    store_refresh_token_default(&sut).await;
+    sut.request_deletion(ID).await.unwrap();
+
+    assert_eq!(sut.purge_requested_deletions(30).await.unwrap(), 0);
+
+    sqlx::query!("UPDATE users SET request_deletion_at = request_deletion_at - INTERVAL '31 days'")
+      .execute(&pool)
+      .await?;
+
+    assert_eq!(sut.purge_requested_deletions(30).await.unwrap(), 1);
+
+    match sut.find_user_by(&UserColumns::Id(ID)).await {
+      Ok(_) => panic!("the purged user should not be found"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    };
+    let remaining = sqlx::query!(
+      r#"SELECT
+        (SELECT COUNT(*) FROM emails) AS "emails!",
+        (SELECT COUNT(*) FROM telephones) AS "telephones!",
+        (SELECT COUNT(*) FROM refresh_tokens) AS "refresh_tokens!",
+        (SELECT COUNT(*) FROM addresses) AS "addresses!""#
+    )
+    .fetch_one(&pool)
+    .await?;
+    assert_eq!(remaining.emails, 1);
+    assert_eq!(remaining.telephones, 0);
+    assert_eq!(remaining.refresh_tokens, 0);
+    assert_eq!(remaining.addresses, 1);
+    assert!(sut.find_user_by(&UserColumns::Id(OTHER_ID)).await.is_ok());
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_store_and_confirm_totp_authenticator(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+
+    sut
+      .store_totp_authenticator(ID, "FIRSTSECRET")
+      .await
+      .unwrap();
+    sut.store_totp_authenticator(ID, TOTP_SECRET).await.unwrap();
+
+    let pending = sut.find_totp_authenticator(ID).await.unwrap();
+    assert_eq!(pending.secret, TOTP_SECRET);
+    assert!(!pending.confirmed);
+    assert!(
+      !sut
+        .find_user_by(&UserColumns::Id(ID))
+        .await
+        .unwrap()
+        .mfa_enabled
+    );
+
+    sut
+      .confirm_totp_authenticator(ID, 100, &[RECOVERY_CODE.to_string()])
+      .await
+      .unwrap();
+
+    let confirmed = sut.find_totp_authenticator(ID).await.unwrap();
+    assert!(confirmed.confirmed);
+    assert_eq!(confirmed.last_used_step, Some(100));
+    assert!(
+      sut
+        .find_user_by(&UserColumns::Id(ID))
+        .await
+        .unwrap()
+        .mfa_enabled
+    );
+
+    // A confirmed authenticator is not replaced by a new enrollment.
+    sut
+      .store_totp_authenticator(ID, "OTHERSECRET")
+      .await
+      .unwrap();
+    assert_eq!(
+      sut.find_totp_authenticator(ID).await.unwrap().secret,
+      TOTP_SECRET
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_confirm_nonexistent_totp_authenticator(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
     let sut = UserRepositoryDB { pool: &pool };
-    sut.update_password(NEW_PASSWORD, ID).await.unwrap();
 
-    assert_user(
-      &pool,
-      &UserColumns::Id(ID),
-      UserData {
-        id: ID.to_owned(),
-        name: NAME.to_owned(),
-        username: USERNAME.to_owned(),
-        password: NEW_PASSWORD.to_owned(),
-      },
-      Some(|new_pwd, db_pwd| new_pwd == db_pwd),
-    )
-    .await;
+    match sut.confirm_totp_authenticator(ID, 100, &[]).await {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    }
 
     Ok(())
   }
 
   #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
-  async fn test_update_user_password_with_wrong_id(pool: PgPool) -> sqlx::Result<()> {
-    const NEW_PASSWORD: &str = "987654321";
-    const WRONG_ID: &str = "3d59b3cc-4ba4-09a4-90cb-956c4a2df911";
+  async fn test_use_totp_step_only_forward(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    sut.store_totp_authenticator(ID, TOTP_SECRET).await.unwrap();
+
+    assert!(!sut.use_totp_step(ID, 101).await.unwrap());
+
+    sut.confirm_totp_authenticator(ID, 100, &[]).await.unwrap();
+
+    assert!(!sut.use_totp_step(ID, 100).await.unwrap());
+    assert!(sut.use_totp_step(ID, 101).await.unwrap());
+    assert!(!sut.use_totp_step(ID, 101).await.unwrap());
+    assert_eq!(
+      sut
+        .find_totp_authenticator(ID)
+        .await
+        .unwrap()
+        .last_used_step,
+      Some(101)
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_store_and_find_webauthn_credentials(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+
+    assert!(sut.find_webauthn_credentials(ID).await.unwrap().is_empty());
+
+    sut
+      .store_webauthn_credential(&NewWebAuthnCredential {
+        credential_id: CREDENTIAL_ID.to_string(),
+        profile_id: ID.to_string(),
+        public_key: vec![4; 65],
+        sign_count: 1,
+        name: String::from("Laptop"),
+      })
+      .await
+      .unwrap();
+
+    let credential = sut.find_webauthn_credential(CREDENTIAL_ID).await.unwrap();
+    assert_eq!(credential.profile_id, ID);
+    assert_eq!(credential.public_key, vec![4; 65]);
+    assert_eq!(credential.sign_count, 1);
+    assert_eq!(credential.name, "Laptop");
+    assert_eq!(
+      sut.find_webauthn_credentials(ID).await.unwrap(),
+      vec![credential.clone()]
+    );
+
+    sut
+      .update_webauthn_sign_count(credential.id, 5)
+      .await
+      .unwrap();
+    assert_eq!(
+      sut
+        .find_webauthn_credential(CREDENTIAL_ID)
+        .await
+        .unwrap()
+        .sign_count,
+      5
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_store_already_registered_webauthn_credential(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    let credential = NewWebAuthnCredential {
+      credential_id: CREDENTIAL_ID.to_string(),
+      profile_id: ID.to_string(),
+      ..Default::default()
+    };
+
+    sut.store_webauthn_credential(&credential).await.unwrap();
+
+    match sut.store_webauthn_credential(&credential).await {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::AlreadyExists),
+    }
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_find_nonexistent_webauthn_credential(pool: PgPool) -> sqlx::Result<()> {
+    let sut = UserRepositoryDB { pool: &pool };
+
+    match sut.find_webauthn_credential(CREDENTIAL_ID).await {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    }
+    match sut.update_webauthn_sign_count(1, 1).await {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    }
+
+    Ok(())
+  }
 
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_use_recovery_code_only_once(pool: PgPool) -> sqlx::Result<()> {
     insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    sut.store_totp_authenticator(ID, TOTP_SECRET).await.unwrap();
+    sut
+      .confirm_totp_authenticator(ID, 100, &[RECOVERY_CODE.to_string()])
+      .await
+      .unwrap();
+
+    let stored = sqlx::query!("SELECT code_hash FROM recovery_codes")
+      .fetch_one(&pool)
+      .await?;
+    assert_ne!(stored.code_hash, RECOVERY_CODE);
+
+    assert!(!sut.use_recovery_code(ID, "9999999999").await.unwrap());
+    assert!(sut.use_recovery_code(ID, RECOVERY_CODE).await.unwrap());
+    assert!(!sut.use_recovery_code(ID, RECOVERY_CODE).await.unwrap());
 
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_store_and_find_oauth_client(pool: PgPool) -> sqlx::Result<()> {
     let sut = UserRepositoryDB { pool: &pool };
+    let client = NewOAuthClient {
+      client_id: CLIENT_ID.to_string(),
+      name: String::from("Local client"),
+      redirect_uris: vec![REDIRECT_URI.to_string()],
+      secret_hash: None,
+    };
 
-    let response = sut.update_password(NEW_PASSWORD, WRONG_ID).await;
+    sut.store_oauth_client(&client).await.unwrap();
 
-    match response {
-      Ok(_) => panic!("this test should no be success"),
-      Err(error) => assert_eq!(
-        error,
-        AppError::invalid_argument("wrong old password or user does not exist")
-      ),
+    let stored = sut.find_oauth_client(CLIENT_ID).await.unwrap();
+    assert_eq!(stored.name, client.name);
+    assert_eq!(stored.redirect_uris, client.redirect_uris);
+    assert_eq!(stored.secret_hash, None);
+
+    match sut.store_oauth_client(&client).await {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::AlreadyExists),
+    }
+
+    match sut.find_oauth_client("unknown").await {
+      Ok(_) => panic!("this test not expected success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    }
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_use_authorization_code_only_once(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    sut
+      .store_oauth_client(&NewOAuthClient {
+        client_id: CLIENT_ID.to_string(),
+        name: String::from("Local client"),
+        redirect_uris: vec![REDIRECT_URI.to_string()],
+        secret_hash: None,
+      })
+      .await
+      .unwrap();
+    sut
+      .store_authorization_code(&NewAuthorizationCode {
+        code: VERIFICATION_TOKEN.to_string(),
+        client_id: CLIENT_ID.to_string(),
+        profile_id: ID.to_string(),
+        redirect_uri: REDIRECT_URI.to_string(),
+        scope: String::from("openid"),
+        code_challenge: String::from("E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"),
+        nonce: Some(String::from("n-0S6_WzA2Mj")),
+        expires_at: refresh_token_expiration(),
+      })
+      .await
+      .unwrap();
+
+    let stored = sqlx::query!("SELECT code_hash FROM oauth_authorization_codes")
+      .fetch_one(&pool)
+      .await?;
+    assert_ne!(stored.code_hash, VERIFICATION_TOKEN);
+
+    let code = sut
+      .find_authorization_code(VERIFICATION_TOKEN)
+      .await
+      .unwrap();
+    assert_eq!(code.profile_id, ID);
+    assert_eq!(code.nonce, Some(String::from("n-0S6_WzA2Mj")));
+    assert!(!code.used);
+
+    assert!(sut.use_authorization_code(code.id).await.unwrap());
+    assert!(!sut.use_authorization_code(code.id).await.unwrap());
+    assert!(
+      sut
+        .find_authorization_code(VERIFICATION_TOKEN)
+        .await
+        .unwrap()
+        .used
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test]
+  async fn test_external_sign_in_held_for_sign_up(pool: PgPool) -> sqlx::Result<()> {
+    let sut = UserRepositoryDB { pool: &pool };
+    sut
+      .store_external_sign_in(&NewExternalSignIn {
+        state: VERIFICATION_TOKEN.to_string(),
+        provider: PROVIDER.to_string(),
+        code_verifier: REFRESH_TOKEN.to_string(),
+        nonce: String::from("n-0S6_WzA2Mj"),
+        expires_at: refresh_token_expiration(),
+      })
+      .await
+      .unwrap();
+
+    let sign_in = sut.find_external_sign_in(VERIFICATION_TOKEN).await.unwrap();
+    assert_eq!(sign_in.provider, PROVIDER);
+    assert_eq!(sign_in.code_verifier, REFRESH_TOKEN);
+    assert_eq!(sign_in.claims, None);
+    assert!(!sign_in.used);
+
+    assert!(sut.use_external_sign_in(sign_in.id).await.unwrap());
+    assert!(!sut.use_external_sign_in(sign_in.id).await.unwrap());
+
+    let claims = ExternalClaims {
+      subject: String::from("248289761001"),
+      email: EMAIL_ADDRESS.to_string(),
+      email_verified: true,
+      name: Some(NAME.to_string()),
+      preferred_username: None,
     };
+    sut
+      .hold_external_sign_up(sign_in.id, &claims, refresh_token_expiration())
+      .await
+      .unwrap();
+
+    let sign_in = sut.find_external_sign_in(VERIFICATION_TOKEN).await.unwrap();
+    assert!(sign_in.used);
+    assert_eq!(sign_in.claims, Some(claims));
+    assert!(!sign_in.completed);
+
+    assert!(sut.complete_external_sign_up(sign_in.id).await.unwrap());
+    assert!(!sut.complete_external_sign_up(sign_in.id).await.unwrap());
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_store_and_find_external_identity(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+
+    let error = sut
+      .find_external_identity(PROVIDER, "248289761001")
+      .await
+      .unwrap_err();
+    assert_eq!(error.code, Code::NotFound);
+
+    sut
+      .store_external_identity(&NewExternalIdentity {
+        provider: PROVIDER.to_string(),
+        subject: String::from("248289761001"),
+        profile_id: ID.to_string(),
+        email: Some(EMAIL_ADDRESS.to_string()),
+      })
+      .await
+      .unwrap();
+
+    let identity = sut
+      .find_external_identity(PROVIDER, "248289761001")
+      .await
+      .unwrap();
+    assert_eq!(identity.profile_id, ID);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_grant_role_and_find_grants(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+
+    assert_eq!(sut.find_grants(ID).await.unwrap(), Grants::default());
+
+    assert!(sut.grant_role(ID, ADMIN_ROLE).await.unwrap());
+    assert!(!sut.grant_role(ID, ADMIN_ROLE).await.unwrap());
+    assert_eq!(
+      sut.grant_role(ID, "unknown").await.unwrap_err().code,
+      Code::NotFound
+    );
+
+    let grants = sut.find_grants(ID).await.unwrap();
+    assert_eq!(grants.roles, vec![ADMIN_ROLE.to_string()]);
+    assert_eq!(
+      grants.permissions,
+      vec![
+        permissions::AUDIT_EVENTS_READ.to_string(),
+        permissions::OAUTH_CLIENTS_ADMIN.to_string(),
+        permissions::USERS_ADMIN.to_string()
+      ]
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_revoke_role_bumps_token_version(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    sut.grant_role(ID, ADMIN_ROLE).await.unwrap();
+    assert_eq!(sut.find_token_version(ID).await.unwrap(), 0);
+
+    assert!(sut.revoke_role(ID, ADMIN_ROLE).await.unwrap());
+    assert_eq!(sut.find_token_version(ID).await.unwrap(), 1);
+    assert_eq!(sut.find_grants(ID).await.unwrap(), Grants::default());
+
+    assert!(!sut.revoke_role(ID, ADMIN_ROLE).await.unwrap());
+    assert_eq!(sut.find_token_version(ID).await.unwrap(), 1);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_store_and_find_sessions(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+
+    store_session_default(&sut, FAMILY_ID).await;
+    store_session_default(&sut, OTHER_FAMILY_ID).await;
+    store_refresh_token_default(&sut).await;
+
+    // a session without live refresh tokens has expired
+    let sessions = sut.find_sessions(ID).await.unwrap();
+
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].id, FAMILY_ID);
+    assert_eq!(sessions[0].device_label, DEVICE_LABEL);
+    assert_eq!(sessions[0].ip_address, Some(IP_ADDRESS.to_string()));
+    assert!(sessions[0].last_seen_at >= sessions[0].created_at);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_revoke_session(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    store_session_default(&sut, FAMILY_ID).await;
+    store_refresh_token_default(&sut).await;
+
+    sut.revoke_session(ID, FAMILY_ID).await.unwrap();
+
+    assert!(sut.find_sessions(ID).await.unwrap().is_empty());
+    assert!(sut.find_refresh_token(REFRESH_TOKEN).await.unwrap().revoked);
+    assert_eq!(
+      sut.revoke_session(ID, FAMILY_ID).await.unwrap_err().code,
+      Code::NotFound
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_revoke_session_of_other_profile(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    store_session_default(&sut, FAMILY_ID).await;
+
+    let response = sut.revoke_session(OTHER_FAMILY_ID, FAMILY_ID).await;
+
+    assert_eq!(response.unwrap_err().code, Code::NotFound);
+
+    Ok(())
+  }
+
+  #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+  async fn test_revoke_other_sessions(pool: PgPool) -> sqlx::Result<()> {
+    insert_user_default(&pool).await.unwrap();
+    let sut = UserRepositoryDB { pool: &pool };
+    store_session_default(&sut, FAMILY_ID).await;
+    store_session_default(&sut, OTHER_FAMILY_ID).await;
+    store_refresh_token_default(&sut).await;
+    sut
+      .store_refresh_token(&NewRefreshToken {
+        token: OTHER_REFRESH_TOKEN.to_string(),
+        family_id: OTHER_FAMILY_ID.to_string(),
+        profile_id: ID.to_string(),
+        expires_at: refresh_token_expiration(),
+      })
+      .await
+      .unwrap();
+
+    let revoked = sut
+      .revoke_other_sessions(ID, Some(FAMILY_ID))
+      .await
+      .unwrap();
+
+    assert_eq!(revoked, vec![OTHER_FAMILY_ID.to_string()]);
+    assert!(
+      sut
+        .find_refresh_token(OTHER_REFRESH_TOKEN)
+        .await
+        .unwrap()
+        .revoked
+    );
+    assert!(!sut.find_refresh_token(REFRESH_TOKEN).await.unwrap().revoked);
+
+    let sessions = sut.find_sessions(ID).await.unwrap();
+    assert_eq!(sessions.len(), 1);
+    assert_eq!(sessions[0].id, FAMILY_ID);
 
     Ok(())
   }
@@ -334,6 +3121,53 @@ mod test {
   const POSTAL_CODE: i32 = 10019;
   const EMAIL_ADDRESS: &str = "johndoe@company.com";
   const TELEPHONE_NUMBER: &str = "+1 151 999-9999";
+  const REFRESH_TOKEN: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+  const FAMILY_ID: &str = "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed";
+  const OTHER_FAMILY_ID: &str = "5c0d6a8e-2b3f-4e1d-8a9c-7f6e5d4c3b2a";
+  const OTHER_REFRESH_TOKEN: &str = "Qp8sX2nV5bL0cR7mK4jH1gF9dS6aZ3wE8tY5uI2oP0l";
+  const DEVICE_LABEL: &str = "Firefox on Linux";
+  const IP_ADDRESS: &str = "203.0.113.7";
+  const VERIFICATION_TOKEN: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+  const TOTP_SECRET: &str = "JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP";
+  const RECOVERY_CODE: &str = "0123456789";
+  const CREDENTIAL_ID: &str = "BwcHBwcHBwcHBwcHBwcHBw";
+  const CLIENT_ID: &str = "5f1c2a9e-7b4d-4e3a-9c8f-1d2e3f4a5b6c";
+  const REDIRECT_URI: &str = "http://localhost:3000/callback";
+  const PROVIDER: &str = "acme";
+
+  fn refresh_token_expiration() -> chrono::NaiveDateTime {
+    NaiveDate::from_ymd_opt(2100, 1, 1)
+      .unwrap()
+      .and_hms_milli_opt(0, 0, 0, 0)
+      .unwrap()
+  }
+
+  async fn store_refresh_token_default(sut: &UserRepositoryDB<'_, Pool<Postgres>>) -> i32 {
+    sut
+      .store_refresh_token(&NewRefreshToken {
+        token: REFRESH_TOKEN.to_string(),
+        family_id: FAMILY_ID.to_string(),
+        profile_id: ID.to_string(),
+        expires_at: refresh_token_expiration(),
+      })
+      .await
+      .unwrap();
+
+    sut.find_refresh_token(REFRESH_TOKEN).await.unwrap().id
+  }
+
+  async fn store_session_default(sut: &UserRepositoryDB<'_, Pool<Postgres>>, id: &str) {
+    sut
+      .store_session(&NewSession {
+        id: id.to_string(),
+        profile_id: ID.to_string(),
+        device_label: DEVICE_LABEL.to_string(),
+        ip_address: Some(IP_ADDRESS.to_string()),
+        user_agent: None,
+      })
+      .await
+      .unwrap();
+  }
 
   async fn insert_user_default(pool: &Pool<Postgres>) -> Result<(), AppError> {
     insert_user(
@@ -349,7 +3183,7 @@ mod test {
       CITY_ID,
       POSTAL_CODE,
       EMAIL_ADDRESS,
-      Some(TELEPHONE_NUMBER).as_deref(),
+      Some(TELEPHONE_NUMBER),
     )
     .await;
 
@@ -0,0 +1,97 @@
+use actix_web::{
+  dev::Payload, error::InternalError, http::header, web, Error, FromRequest, HttpMessage,
+  HttpRequest, HttpResponse,
+};
+use futures_util::future::LocalBoxFuture;
+
+use crate::{
+  adapter::{
+    services::{
+      audit_log::AuditLogDB, identity_provider::HttpIdentityProviderClient, jwt::JWTService,
+      token_revocation::TokenRevocationStoreDB,
+    },
+    utilities::{id_generator::NewID, phc::PhcCrypto, primitives::CryptoPrimitives},
+  },
+  application::services::{
+    notification::{email_sender::EmailSender, sms_sender::SmsSender},
+    security::authenticated_user::AuthenticatedUser,
+    Services,
+  },
+  domain::{
+    error::{AppError, Code},
+    utilities::Utilities,
+  },
+};
+
+use super::{actix_bearer_token::extract_bearer_token, client_ip::request_client_ip};
+
+pub type AppServices = Services<
+  JWTService<'static, TokenRevocationStoreDB>,
+  Box<dyn EmailSender>,
+  Box<dyn SmsSender>,
+  HttpIdentityProviderClient,
+  AuditLogDB,
+>;
+
+pub type AppUtilities = Utilities<PhcCrypto, NewID, CryptoPrimitives>;
+
+/// Resolves the user behind the request's access token. The token is only
+/// decoded once per request, later calls reuse the stored principal.
+pub async fn authenticate(req: &HttpRequest) -> Result<AuthenticatedUser, AppError> {
+  if let Some(user) = req.extensions().get::<AuthenticatedUser>() {
+    return Ok(user.clone());
+  }
+
+  let services = req
+    .app_data::<web::Data<AppServices>>()
+    .ok_or_else(|| AppError::internal("services are not configured"))?;
+  let token = extract_bearer_token(req)
+    .ok_or_else(|| AppError::unauthenticated("Missing or malformed Bearer token"))?;
+
+  let ip_address = request_client_ip(req);
+  let user_agent = req
+    .headers()
+    .get(header::USER_AGENT)
+    .and_then(|user_agent| user_agent.to_str().ok())
+    .map(str::to_string);
+
+  let user = AuthenticatedUser {
+    ip_address,
+    user_agent,
+    ..services.authenticate(token).await?
+  };
+  req.extensions_mut().insert(user.clone());
+
+  Ok(user)
+}
+
+/// Error response for a request that could not be authenticated or
+/// authorized, challenging the client for a Bearer token on 401.
+pub fn error_response(error: AppError) -> HttpResponse {
+  let challenge = error.code == Code::Unauthenticated;
+  let mut response: HttpResponse = error.into();
+  if challenge {
+    response.headers_mut().insert(
+      header::WWW_AUTHENTICATE,
+      header::HeaderValue::from_static("Bearer"),
+    );
+  }
+
+  response
+}
+
+impl FromRequest for AuthenticatedUser {
+  type Error = Error;
+  type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+  fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+    let req = req.clone();
+
+    Box::pin(async move {
+      authenticate(&req).await.map_err(|error| {
+        let message = error.message.clone();
+        InternalError::from_response(message, error_response(error)).into()
+      })
+    })
+  }
+}
@@ -0,0 +1,16 @@
+use actix_web::HttpRequest;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+/// User and password of an `Authorization: Basic` header, each of them
+/// form-urlencoded as OAuth clients send their credentials.
+pub fn extract_basic_credentials(req: &HttpRequest) -> Option<(String, String)> {
+  let value = req.headers().get("Authorization")?.to_str().ok()?;
+  let encoded = value.strip_prefix("Basic ")?;
+  let decoded = String::from_utf8(STANDARD.decode(encoded.trim()).ok()?).ok()?;
+  let (user, password) = decoded.split_once(':')?;
+
+  Some((
+    urlencoding::decode(user).ok()?.into_owned(),
+    urlencoding::decode(password).ok()?.into_owned(),
+  ))
+}
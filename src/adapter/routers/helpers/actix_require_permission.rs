@@ -0,0 +1,92 @@
+use std::{
+  future::{ready, Ready},
+  rc::Rc,
+};
+
+use actix_web::{
+  body::EitherBody,
+  dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+  web, Error, HttpRequest,
+};
+use futures_util::future::LocalBoxFuture;
+
+use crate::domain::error::AppError;
+
+use super::actix_authenticated_user::{authenticate, error_response, AppServices};
+
+/// Lets requests through only with an access token granting the permission,
+/// declared on the route:
+/// `#[post("/v1/admin/...", wrap = "RequirePermission(permissions::USERS_ADMIN)")]`.
+pub struct RequirePermission(pub &'static str);
+
+impl<S, B> Transform<S, ServiceRequest> for RequirePermission
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<EitherBody<B>>;
+  type Error = Error;
+  type Transform = RequirePermissionMiddleware<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(RequirePermissionMiddleware {
+      service: Rc::new(service),
+      permission: self.0,
+    }))
+  }
+}
+
+pub struct RequirePermissionMiddleware<S> {
+  service: Rc<S>,
+  permission: &'static str,
+}
+
+impl<S, B> Service<ServiceRequest> for RequirePermissionMiddleware<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<EitherBody<B>>;
+  type Error = Error;
+  type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  forward_ready!(service);
+
+  fn call(&self, req: ServiceRequest) -> Self::Future {
+    let service = self.service.clone();
+    let permission = self.permission;
+
+    Box::pin(async move {
+      if let Err(error) = authorize(req.request(), permission).await {
+        return Ok(
+          req
+            .into_response(error_response(error))
+            .map_into_right_body(),
+        );
+      }
+
+      service
+        .call(req)
+        .await
+        .map(ServiceResponse::map_into_left_body)
+    })
+  }
+}
+
+async fn authorize(req: &HttpRequest, permission: &str) -> Result<(), AppError> {
+  let user = authenticate(req).await?;
+  if !user.has_permission(permission) {
+    if let Some(services) = req.app_data::<web::Data<AppServices>>() {
+      services
+        .record_permission_denied(&user, permission, req.method().as_str(), req.path())
+        .await;
+    }
+    return Err(AppError::permission_denied(
+      "Given token not have permission for this operation",
+    ));
+  }
+
+  Ok(())
+}
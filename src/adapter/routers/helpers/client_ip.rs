@@ -0,0 +1,80 @@
+use std::net::SocketAddr;
+
+use actix_web::{web, HttpRequest};
+
+/// Whether the `Forwarded`/`X-Forwarded-For` headers name the client, only
+/// true behind a proxy that sets them. Otherwise anyone could pick the IP
+/// recorded for their requests.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClientIpConfig {
+  pub trust_forwarded_for: bool,
+}
+
+/// IP of the client behind the request, the peer address unless
+/// `trust_forwarded_for`.
+pub fn client_ip(req: &HttpRequest, trust_forwarded_for: bool) -> Option<String> {
+  if !trust_forwarded_for {
+    return req.peer_addr().map(|addr| addr.ip().to_string());
+  }
+
+  // without forwarding headers the peer address comes with its port
+  req
+    .connection_info()
+    .realip_remote_addr()
+    .map(|addr| match addr.parse::<SocketAddr>() {
+      Ok(addr) => addr.ip().to_string(),
+      Err(_) => addr.to_string(),
+    })
+}
+
+/// `client_ip` with the `ClientIpConfig` of the app, the peer address when
+/// none is configured.
+pub fn request_client_ip(req: &HttpRequest) -> Option<String> {
+  let trust_forwarded_for = req
+    .app_data::<web::Data<ClientIpConfig>>()
+    .is_some_and(|config| config.trust_forwarded_for);
+
+  client_ip(req, trust_forwarded_for)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use actix_web::test::TestRequest;
+
+  const PEER: &str = "203.0.113.7:40000";
+  const FORWARDED: &str = "198.51.100.23";
+
+  fn request() -> TestRequest {
+    TestRequest::default()
+      .peer_addr(PEER.parse().unwrap())
+      .insert_header(("X-Forwarded-For", FORWARDED))
+  }
+
+  #[test]
+  fn test_ignore_forwarded_for_by_default() {
+    let req = request().to_http_request();
+
+    assert_eq!(request_client_ip(&req).as_deref(), Some("203.0.113.7"));
+  }
+
+  #[test]
+  fn test_trust_forwarded_for_when_configured() {
+    let req = request()
+      .app_data(web::Data::new(ClientIpConfig {
+        trust_forwarded_for: true,
+      }))
+      .to_http_request();
+
+    assert_eq!(request_client_ip(&req).as_deref(), Some(FORWARDED));
+  }
+
+  #[test]
+  fn test_trust_forwarded_for_without_headers() {
+    let req = TestRequest::default()
+      .peer_addr(PEER.parse().unwrap())
+      .to_http_request();
+
+    assert_eq!(client_ip(&req, true).as_deref(), Some("203.0.113.7"));
+  }
+}
@@ -1 +1,5 @@
+pub mod actix_authenticated_user;
+pub mod actix_basic_auth;
 pub mod actix_bearer_token;
+pub mod actix_require_permission;
+pub mod client_ip;
@@ -0,0 +1,13 @@
+use actix_web::{get, web, HttpResponse};
+
+use crate::adapter::routers::helpers::actix_authenticated_user::AppServices;
+
+#[utoipa::path(
+  responses(
+      (status = 200, description = "JSON Web Key Set with the public keys that verify our tokens")
+  )
+)]
+#[get("/.well-known/jwks.json")]
+pub async fn jwks_get(services: web::Data<AppServices>) -> HttpResponse {
+  HttpResponse::Ok().json(services.token.keys.jwks())
+}
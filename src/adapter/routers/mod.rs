@@ -1,7 +1,11 @@
 pub mod helpers;
 pub mod index;
+pub mod jwks;
 pub mod not_found;
+pub mod openid_configuration;
 pub mod v1;
 
 pub use self::index::index_get;
+pub use self::jwks::jwks_get;
 pub use self::not_found::not_found;
+pub use self::openid_configuration::openid_configuration_get;
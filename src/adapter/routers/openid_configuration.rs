@@ -0,0 +1,82 @@
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::adapter::routers::helpers::actix_authenticated_user::AppServices;
+
+/// OpenID Provider Metadata, endpoints are published under the host the
+/// client used to reach us.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct OpenIdConfigurationHttp {
+  #[schema(example = "phoenix")]
+  pub issuer: String,
+  #[schema(example = "https://auth.example.com/v1/oauth/authorize")]
+  pub authorization_endpoint: String,
+  #[schema(example = "https://auth.example.com/v1/oauth/token")]
+  pub token_endpoint: String,
+  #[schema(example = "https://auth.example.com/v1/oauth/userinfo")]
+  pub userinfo_endpoint: String,
+  #[schema(example = "https://auth.example.com/.well-known/jwks.json")]
+  pub jwks_uri: String,
+  pub response_types_supported: Vec<String>,
+  pub grant_types_supported: Vec<String>,
+  pub subject_types_supported: Vec<String>,
+  pub id_token_signing_alg_values_supported: Vec<String>,
+  pub scopes_supported: Vec<String>,
+  pub token_endpoint_auth_methods_supported: Vec<String>,
+  pub code_challenge_methods_supported: Vec<String>,
+  pub claims_supported: Vec<String>,
+}
+
+fn strings(values: &[&str]) -> Vec<String> {
+  values.iter().map(|value| value.to_string()).collect()
+}
+
+#[utoipa::path(
+  responses(
+      (status = 200, description = "OpenID Connect discovery document", body = OpenIdConfigurationHttp)
+  )
+)]
+#[get("/.well-known/openid-configuration")]
+pub async fn openid_configuration_get(
+  req: HttpRequest,
+  services: web::Data<AppServices>,
+) -> HttpResponse {
+  let connection = req.connection_info();
+  let base_url = format!("{}://{}", connection.scheme(), connection.host());
+
+  HttpResponse::Ok().json(OpenIdConfigurationHttp {
+    issuer: services.token.iss.clone(),
+    authorization_endpoint: format!("{}/v1/oauth/authorize", base_url),
+    token_endpoint: format!("{}/v1/oauth/token", base_url),
+    userinfo_endpoint: format!("{}/v1/oauth/userinfo", base_url),
+    jwks_uri: format!("{}/.well-known/jwks.json", base_url),
+    response_types_supported: strings(&["code"]),
+    grant_types_supported: strings(&["authorization_code"]),
+    subject_types_supported: strings(&["public"]),
+    id_token_signing_alg_values_supported: vec![format!("{:?}", services.token.keys.header().alg)],
+    scopes_supported: strings(&["openid", "profile", "email", "phone"]),
+    token_endpoint_auth_methods_supported: strings(&[
+      "client_secret_basic",
+      "client_secret_post",
+      "none",
+    ]),
+    code_challenge_methods_supported: strings(&["S256"]),
+    claims_supported: strings(&[
+      "sub",
+      "iss",
+      "aud",
+      "exp",
+      "iat",
+      "nonce",
+      "name",
+      "preferred_username",
+      "birthdate",
+      "gender",
+      "email",
+      "email_verified",
+      "phone_number",
+      "phone_number_verified",
+    ]),
+  })
+}
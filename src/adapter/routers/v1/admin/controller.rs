@@ -0,0 +1,500 @@
+use crate::{
+  adapter::{
+    repositories::user::UserRepositoryDB,
+    routers::{
+      helpers::{
+        actix_authenticated_user::{AppServices, AppUtilities},
+        actix_require_permission::RequirePermission,
+      },
+      v1::users::dtos::UserProfileResponseHttp,
+    },
+  },
+  application::{
+    services::security::authenticated_user::AuthenticatedUser,
+    use_cases::{
+      admin::{SetAccountStatusRequest, SetRoleRequest, UserAdministration},
+      authenticate::user::UserUseCase,
+    },
+  },
+  domain::entities::role::permissions,
+  AppState,
+};
+use actix_web::{delete, get, post, put, web, HttpResponse};
+
+use super::dtos::{
+  AuditChainVerificationHttp, AuditEventListResponseHttp, ListAuditEventsQuery, ListUsersQuery,
+  OAuthClientResponseHttp, RegisterOAuthClientRequest, UserListResponseHttp,
+};
+
+#[utoipa::path(
+  params(ListUsersQuery),
+  responses(
+      (status = 200, description = "Users matching the given filters", body = UserListResponseHttp),
+      (status = 400, description = "Received invalid pagination, filter or sort"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 403, description = "Given token does not belong to an administrator"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[get(
+  "/v1/admin/users",
+  wrap = "RequirePermission(permissions::USERS_ADMIN)"
+)]
+pub async fn list_users(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  query: web::Query<ListUsersQuery>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.list_users(&(&query.0).into(), &user).await {
+    Ok(users) => {
+      let response: UserListResponseHttp = users.into();
+      HttpResponse::Ok().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  params(
+    ("profile_id" = String, Path, description = "Profile id of the user")
+  ),
+  responses(
+      (status = 200, description = "Full profile of the user", body = UserProfileResponseHttp),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 403, description = "Given token does not belong to an administrator"),
+      (status = 404, description = "No user found with the given profile id"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[get(
+  "/v1/admin/users/{profile_id}",
+  wrap = "RequirePermission(permissions::USERS_ADMIN)"
+)]
+pub async fn get_user(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  profile_id: web::Path<String>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.get_user(&profile_id, &user).await {
+    Ok(profile) => {
+      let response: UserProfileResponseHttp = profile.into();
+      HttpResponse::Ok().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  params(
+    ("profile_id" = String, Path, description = "Profile id of the user who must choose a new password")
+  ),
+  responses(
+      (status = 200, description = "Password sign-ins blocked until reset, refresh tokens revoked and a reset token emailed"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 403, description = "Given token does not belong to an administrator"),
+      (status = 404, description = "No user, or no email address, found with the given profile id"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[post(
+  "/v1/admin/users/{profile_id}/password-reset",
+  wrap = "RequirePermission(permissions::USERS_ADMIN)"
+)]
+pub async fn force_password_reset(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  profile_id: web::Path<String>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.force_password_reset(&profile_id, &user).await {
+    Ok(()) => HttpResponse::Ok().body("password reset required successfully"),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  params(
+    ("profile_id" = String, Path, description = "Profile id of the account locked by failed sign-ins")
+  ),
+  responses(
+      (status = 200, description = "Lockout lifted and failed attempts cleared"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 403, description = "Given token does not belong to an administrator"),
+      (status = 404, description = "No user found with the given profile id"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[post(
+  "/v1/admin/users/{profile_id}/unlock",
+  wrap = "RequirePermission(permissions::USERS_ADMIN)"
+)]
+pub async fn unlock_user(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  profile_id: web::Path<String>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.unlock_account(&profile_id, &user).await {
+    Ok(()) => HttpResponse::Ok().body("account unlocked successfully"),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  params(
+    ("profile_id" = String, Path, description = "Profile id of the user"),
+    ("role" = String, Path, description = "Name of the role to grant")
+  ),
+  responses(
+      (status = 200, description = "Role granted, or already held"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 403, description = "Given token does not belong to an administrator"),
+      (status = 404, description = "No user or role found with the given names"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[put(
+  "/v1/admin/users/{profile_id}/roles/{role}",
+  wrap = "RequirePermission(permissions::USERS_ADMIN)"
+)]
+pub async fn grant_role(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  path: web::Path<(String, String)>,
+) -> HttpResponse {
+  set_role(
+    &user, &app_state, &services, &utilities, &path.0, &path.1, true,
+  )
+  .await
+}
+
+#[utoipa::path(
+  params(
+    ("profile_id" = String, Path, description = "Profile id of the user"),
+    ("role" = String, Path, description = "Name of the role to revoke")
+  ),
+  responses(
+      (status = 200, description = "Role revoked, or not held"),
+      (status = 400, description = "Attempt to revoke your own admin role"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 403, description = "Given token does not belong to an administrator"),
+      (status = 404, description = "No user or role found with the given names"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[delete(
+  "/v1/admin/users/{profile_id}/roles/{role}",
+  wrap = "RequirePermission(permissions::USERS_ADMIN)"
+)]
+pub async fn revoke_role(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  path: web::Path<(String, String)>,
+) -> HttpResponse {
+  set_role(
+    &user, &app_state, &services, &utilities, &path.0, &path.1, false,
+  )
+  .await
+}
+
+async fn set_role(
+  user: &AuthenticatedUser,
+  app_state: &AppState,
+  services: &AppServices,
+  utilities: &AppUtilities,
+  profile_id: &str,
+  role: &str,
+  granted: bool,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services,
+    utilities,
+  };
+
+  let request = SetRoleRequest {
+    profile_id,
+    role,
+    granted,
+  };
+
+  match use_case.set_role(&request, user).await {
+    Ok(true) if granted => HttpResponse::Ok().body("role granted successfully"),
+    Ok(true) => HttpResponse::Ok().body("role revoked successfully"),
+    Ok(false) if granted => HttpResponse::Ok().body("role already granted"),
+    Ok(false) => HttpResponse::Ok().body("role was not granted"),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  params(
+    ("profile_id" = String, Path, description = "Profile id of the account to disable")
+  ),
+  responses(
+      (status = 200, description = "Account disabled and its refresh tokens revoked"),
+      (status = 400, description = "Attempt to disable your own account"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 403, description = "Given token does not belong to an administrator"),
+      (status = 404, description = "No user found with the given profile id"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[post(
+  "/v1/admin/users/{profile_id}/disable",
+  wrap = "RequirePermission(permissions::USERS_ADMIN)"
+)]
+pub async fn disable_user(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  profile_id: web::Path<String>,
+) -> HttpResponse {
+  set_account_disabled(&user, &app_state, &services, &utilities, &profile_id, true).await
+}
+
+#[utoipa::path(
+  params(
+    ("profile_id" = String, Path, description = "Profile id of the account to enable")
+  ),
+  responses(
+      (status = 200, description = "Account enabled"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 403, description = "Given token does not belong to an administrator"),
+      (status = 404, description = "No user found with the given profile id"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[post(
+  "/v1/admin/users/{profile_id}/enable",
+  wrap = "RequirePermission(permissions::USERS_ADMIN)"
+)]
+pub async fn enable_user(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  profile_id: web::Path<String>,
+) -> HttpResponse {
+  set_account_disabled(&user, &app_state, &services, &utilities, &profile_id, false).await
+}
+
+async fn set_account_disabled(
+  user: &AuthenticatedUser,
+  app_state: &AppState,
+  services: &AppServices,
+  utilities: &AppUtilities,
+  profile_id: &str,
+  disabled: bool,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services,
+    utilities,
+  };
+
+  let request = SetAccountStatusRequest {
+    profile_id,
+    disabled,
+  };
+
+  match use_case.set_account_disabled(&request, user).await {
+    Ok(true) => HttpResponse::Ok().body("account disabled successfully"),
+    Ok(false) => HttpResponse::Ok().body("account enabled successfully"),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = RegisterOAuthClientRequest,
+  responses(
+      (status = 201, description = "OpenID Connect client registered, its secret is only shown here", body = OAuthClientResponseHttp),
+      (status = 400, description = "Received invalid data"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 403, description = "Given token does not belong to an administrator"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[post(
+  "/v1/admin/oauth/clients",
+  wrap = "RequirePermission(permissions::OAUTH_CLIENTS_ADMIN)"
+)]
+pub async fn register_oauth_client(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<RegisterOAuthClientRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case
+    .register_oauth_client(&(&request.0).into(), &user)
+    .await
+  {
+    Ok(credentials) => {
+      let response: OAuthClientResponseHttp = credentials.into();
+      HttpResponse::Created().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  params(ListAuditEventsQuery),
+  responses(
+      (status = 200, description = "Audit events matching the given filters, newest first", body = AuditEventListResponseHttp),
+      (status = 400, description = "Received invalid pagination or outcome"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 403, description = "Given token does not allow reading the audit log"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[get(
+  "/v1/admin/audit-events",
+  wrap = "RequirePermission(permissions::AUDIT_EVENTS_READ)"
+)]
+pub async fn list_audit_events(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  query: web::Query<ListAuditEventsQuery>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.list_audit_events(&(&query.0).into(), &user).await {
+    Ok(events) => {
+      let response: AuditEventListResponseHttp = events.into();
+      HttpResponse::Ok().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  responses(
+      (status = 200, description = "Result of recomputing the audit log hash chain", body = AuditChainVerificationHttp),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 403, description = "Given token does not allow reading the audit log"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[get(
+  "/v1/admin/audit-events/verify",
+  wrap = "RequirePermission(permissions::AUDIT_EVENTS_READ)"
+)]
+pub async fn verify_audit_events(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.verify_audit_log(&user).await {
+    Ok(verification) => {
+      let response: AuditChainVerificationHttp = verification.into();
+      HttpResponse::Ok().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
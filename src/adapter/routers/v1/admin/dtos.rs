@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+  application::use_cases::admin,
+  domain::entities::{
+    audit_event::{AuditChainVerification, AuditEvent, AuditEventPage},
+    oauth::OAuthClientCredentials,
+    user::{UserSummary, UserSummaryPage},
+  },
+};
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, ToSchema)]
+pub struct RegisterOAuthClientRequest {
+  #[schema(example = "Example app")]
+  pub name: String,
+  /// Exact URIs the client may receive authorization codes on.
+  #[schema(example = json!(["https://app.example.com/callback"]))]
+  pub redirect_uris: Vec<String>,
+  /// Whether the client can keep a secret, single page and mobile apps
+  /// cannot and rely on PKCE only.
+  #[schema(example = true)]
+  pub confidential: bool,
+}
+
+impl<'a> From<&'a RegisterOAuthClientRequest> for admin::RegisterOAuthClientRequest<'a> {
+  fn from(value: &'a RegisterOAuthClientRequest) -> Self {
+    admin::RegisterOAuthClientRequest {
+      name: &value.name,
+      redirect_uris: &value.redirect_uris,
+      confidential: value.confidential,
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct OAuthClientResponseHttp {
+  #[schema(example = "5f1c2a9e-7b4d-4e3a-9c8f-1d2e3f4a5b6c")]
+  pub client_id: String,
+  /// Shown once, absent for public clients.
+  #[schema(example = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q")]
+  pub client_secret: Option<String>,
+  #[schema(example = "Example app")]
+  pub name: String,
+  #[schema(example = json!(["https://app.example.com/callback"]))]
+  pub redirect_uris: Vec<String>,
+}
+
+impl From<OAuthClientCredentials> for OAuthClientResponseHttp {
+  fn from(value: OAuthClientCredentials) -> Self {
+    OAuthClientResponseHttp {
+      client_id: value.client_id,
+      client_secret: value.client_secret,
+      name: value.name,
+      redirect_uris: value.redirect_uris,
+    }
+  }
+}
+
+#[derive(Deserialize, Clone, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListUsersQuery {
+  /// Page number, starting at 1
+  #[param(example = 1)]
+  pub page: Option<i64>,
+  /// Users per page, at most 100
+  #[param(example = 20)]
+  pub per_page: Option<i64>,
+  /// Part of the username, case insensitive
+  #[param(example = "john")]
+  pub username: Option<String>,
+  /// Part of any email address, case insensitive
+  #[param(example = "@email.com")]
+  pub email: Option<String>,
+  /// Created on or after this date
+  #[param(example = "2023-11-01")]
+  pub created_from: Option<String>,
+  /// Created on or before this date
+  #[param(example = "2023-11-30")]
+  pub created_to: Option<String>,
+  /// `created_at` or `username`, prefixed with `-` for descending order,
+  /// newest first by default
+  #[param(example = "username")]
+  pub sort: Option<String>,
+}
+
+impl<'a> From<&'a ListUsersQuery> for admin::ListUsersRequest<'a> {
+  fn from(value: &'a ListUsersQuery) -> Self {
+    let default = admin::ListUsersRequest::default();
+
+    admin::ListUsersRequest {
+      page: value.page.unwrap_or(default.page),
+      per_page: value.per_page.unwrap_or(default.per_page),
+      username: value.username.as_deref(),
+      email: value.email.as_deref(),
+      created_from: value.created_from.as_deref(),
+      created_to: value.created_to.as_deref(),
+      sort: value.sort.as_deref(),
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct UserSummaryHttp {
+  #[schema(example = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659")]
+  pub id: String,
+  #[schema(example = "John Doe")]
+  pub name: String,
+  #[schema(example = "john_doe")]
+  pub username: String,
+  #[schema(example = json!(["john@email.com"]))]
+  pub emails: Vec<String>,
+  #[schema(example = json!(["admin"]))]
+  pub roles: Vec<String>,
+  #[schema(example = false)]
+  pub account_disabled: bool,
+  /// Locked out after too many failed sign-ins.
+  #[schema(example = false)]
+  pub blocked_by_attempts: bool,
+  #[schema(example = false)]
+  pub requested_deletion: bool,
+  #[schema(example = false)]
+  pub password_reset_required: bool,
+  #[schema(example = "2023-11-13T12:00:00")]
+  pub created_at: String,
+}
+
+impl From<UserSummary> for UserSummaryHttp {
+  fn from(value: UserSummary) -> Self {
+    UserSummaryHttp {
+      id: value.id,
+      name: value.name,
+      username: value.username,
+      emails: value.emails,
+      roles: value.roles,
+      account_disabled: value.account_disabled,
+      blocked_by_attempts: value.blocked_by_attempts,
+      requested_deletion: value.requested_deletion,
+      password_reset_required: value.password_reset_required,
+      created_at: value.created_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct UserListResponseHttp {
+  pub items: Vec<UserSummaryHttp>,
+  #[schema(example = 1)]
+  pub page: i64,
+  #[schema(example = 20)]
+  pub per_page: i64,
+  #[schema(example = 1)]
+  pub total: i64,
+}
+
+impl From<UserSummaryPage> for UserListResponseHttp {
+  fn from(value: UserSummaryPage) -> Self {
+    UserListResponseHttp {
+      items: value.users.into_iter().map(Into::into).collect(),
+      page: value.page,
+      per_page: value.per_page,
+      total: value.total,
+    }
+  }
+}
+
+#[derive(Deserialize, Clone, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListAuditEventsQuery {
+  /// Page number, starting at 1
+  #[param(example = 1)]
+  pub page: Option<i64>,
+  /// Events per page, at most 100
+  #[param(example = 20)]
+  pub per_page: Option<i64>,
+  /// Profile id of who performed the action
+  #[param(example = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659")]
+  pub actor_id: Option<String>,
+  /// Profile id the action was applied to
+  #[param(example = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659")]
+  pub subject_id: Option<String>,
+  /// Exact action name
+  #[param(example = "user.sign_in")]
+  pub action: Option<String>,
+  /// `success` or `failure`
+  #[param(example = "failure")]
+  pub outcome: Option<String>,
+}
+
+impl<'a> From<&'a ListAuditEventsQuery> for admin::ListAuditEventsRequest<'a> {
+  fn from(value: &'a ListAuditEventsQuery) -> Self {
+    let default = admin::ListAuditEventsRequest::default();
+
+    admin::ListAuditEventsRequest {
+      page: value.page.unwrap_or(default.page),
+      per_page: value.per_page.unwrap_or(default.per_page),
+      actor_id: value.actor_id.as_deref(),
+      subject_id: value.subject_id.as_deref(),
+      action: value.action.as_deref(),
+      outcome: value.outcome.as_deref(),
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct AuditEventHttp {
+  #[schema(example = 1)]
+  pub id: i64,
+  #[schema(example = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659")]
+  pub actor_id: Option<String>,
+  #[schema(example = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659")]
+  pub subject_id: Option<String>,
+  #[schema(example = "user.sign_in")]
+  pub action: String,
+  #[schema(example = "success")]
+  pub outcome: String,
+  #[schema(example = "127.0.0.1")]
+  pub ip_address: Option<String>,
+  #[schema(example = "Mozilla/5.0")]
+  pub user_agent: Option<String>,
+  #[schema(value_type = Object, example = json!({"method": "password", "identifier": "john_doe"}))]
+  pub metadata: serde_json::Value,
+  #[schema(example = "2023-11-24T12:00:00.000000")]
+  pub created_at: String,
+  #[schema(example = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08")]
+  pub hash: String,
+}
+
+impl From<AuditEvent> for AuditEventHttp {
+  fn from(value: AuditEvent) -> Self {
+    AuditEventHttp {
+      id: value.id,
+      actor_id: value.actor_id,
+      subject_id: value.subject_id,
+      action: value.action,
+      outcome: value.outcome.as_str().to_string(),
+      ip_address: value.ip_address,
+      user_agent: value.user_agent,
+      metadata: value.metadata,
+      created_at: value.created_at.format("%Y-%m-%dT%H:%M:%S%.6f").to_string(),
+      hash: value.hash,
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct AuditEventListResponseHttp {
+  pub items: Vec<AuditEventHttp>,
+  #[schema(example = 1)]
+  pub page: i64,
+  #[schema(example = 20)]
+  pub per_page: i64,
+  #[schema(example = 1)]
+  pub total: i64,
+}
+
+impl From<AuditEventPage> for AuditEventListResponseHttp {
+  fn from(value: AuditEventPage) -> Self {
+    AuditEventListResponseHttp {
+      items: value.events.into_iter().map(Into::into).collect(),
+      page: value.page,
+      per_page: value.per_page,
+      total: value.total,
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct AuditChainVerificationHttp {
+  /// Whether every event matches its hash and its predecessor.
+  #[schema(example = true)]
+  pub valid: bool,
+  #[schema(example = 42)]
+  pub checked: i64,
+  /// First event found altered, removed from under or inserted into the chain.
+  #[schema(example = json!(null))]
+  pub first_invalid_id: Option<i64>,
+}
+
+impl From<AuditChainVerification> for AuditChainVerificationHttp {
+  fn from(value: AuditChainVerification) -> Self {
+    AuditChainVerificationHttp {
+      valid: value.first_invalid_id.is_none(),
+      checked: value.checked,
+      first_invalid_id: value.first_invalid_id,
+    }
+  }
+}
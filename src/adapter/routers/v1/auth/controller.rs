@@ -1,39 +1,50 @@
 use crate::{
   adapter::{
     repositories::user::UserRepositoryDB,
-    routers::helpers::actix_bearer_token::extract_bearer_token,
-    services::jwt::JWTService,
-    utilities::{bcrypt::BCrypt, id_generator::NewID},
+    routers::helpers::{
+      actix_authenticated_user::{AppServices, AppUtilities},
+      client_ip::request_client_ip,
+    },
   },
   application::{
-    services::Services,
-    use_cases::authenticate::{user::UserUseCase, UserAuthentication},
+    services::security::authenticated_user::AuthenticatedUser,
+    use_cases::authenticate::{
+      self, user::UserUseCase, ExternalSignInResponse, SignInResponse, UserAuthentication,
+    },
   },
-  domain::utilities::Utilities,
   AppState,
 };
-use actix_web::{post, put, web, HttpRequest, HttpResponse};
+use actix_web::{get, http::header::USER_AGENT, post, put, web, HttpRequest, HttpResponse};
 
 use super::dtos::{
-  ChangeUserPasswordRequest, UserAuthenticationResponseHttp, UserRegistrationRequest,
-  UserSignInRequest,
+  ChangeUserPasswordRequest, ExternalAuthorizationHttp, ExternalCallbackRequest,
+  ExternalSignUpRequest, ExternalSignUpRequiredHttp, ForgotPasswordRequest,
+  MfaChallengeResponseHttp, RefreshTokenRequest, RegisterWebAuthnRequest, ResetPasswordRequest,
+  UserAuthenticationResponseHttp, UserRegistrationRequest, UserSignInRequest, VerifyEmailRequest,
+  VerifyMfaRequest, WebAuthnCredentialResponseHttp, WebAuthnRegistrationOptionsHttp,
+  WebAuthnSignInOptionsHttp, WebAuthnSignInOptionsRequest, WebAuthnSignInRequest,
 };
 
 #[utoipa::path(
   request_body = UserSignInRequest,
   responses(
       (status = 200, description = "Sign in to an existing user by providing a valid password", body = UserAuthenticationResponseHttp),
+      (status = 202, description = "Password accepted, the account requires a second factor at /v1/auth/mfa/verify", body = MfaChallengeResponseHttp),
       (status = 400, description = "Sign in with invalid data"),
       (status = 401, description = "Sign in with invalid password"),
+      (status = 403, description = "Account disabled by an administrator"),
       (status = 404, description = "No user as found with provide username, email or telephone"),
+      (status = 423, description = "Account blocked after too many failed sign-in attempts"),
+      (status = 429, description = "Too many requests from this address or for this account, retry after Retry-After seconds"),
       (status = 500, description = "Internal server error")
   )
 )]
 #[post("/v1/auth/sign_in")]
 pub async fn sign_in(
+  req: HttpRequest,
   app_state: web::Data<AppState>,
-  services: web::Data<Services<JWTService<'_>>>,
-  utilities: web::Data<Utilities<BCrypt, NewID>>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
   request: web::Json<UserSignInRequest>,
 ) -> HttpResponse {
   let use_case = UserUseCase {
@@ -44,7 +55,72 @@ pub async fn sign_in(
     utilities: &utilities,
   };
 
-  match use_case.sign_in(&(&request.0).into()).await {
+  let ip_address = request_client_ip(&req);
+  let user_agent = req
+    .headers()
+    .get(USER_AGENT)
+    .and_then(|user_agent| user_agent.to_str().ok());
+
+  let request = authenticate::UserSignInRequest {
+    ip_address: ip_address.as_deref(),
+    user_agent,
+    ..(&request.0).into()
+  };
+
+  match use_case.sign_in(&request).await {
+    Ok(SignInResponse::Authenticated(user)) => {
+      let response: UserAuthenticationResponseHttp = user.into();
+      HttpResponse::Ok().json(response)
+    }
+    Ok(SignInResponse::MfaRequired { mfa_token }) => {
+      HttpResponse::Accepted().json(MfaChallengeResponseHttp {
+        mfa_required: true,
+        mfa_token,
+      })
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = VerifyMfaRequest,
+  responses(
+      (status = 200, description = "Second factor accepted, sign-in completed", body = UserAuthenticationResponseHttp),
+      (status = 400, description = "Received invalid data"),
+      (status = 401, description = "Token from sign in invalid or expired, or wrong code"),
+      (status = 423, description = "Account blocked after too many failed sign-in attempts"),
+      (status = 500, description = "Internal server error")
+  )
+)]
+#[post("/v1/auth/mfa/verify")]
+pub async fn verify_mfa(
+  req: HttpRequest,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<VerifyMfaRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  let ip_address = request_client_ip(&req);
+  let user_agent = req
+    .headers()
+    .get(USER_AGENT)
+    .and_then(|user_agent| user_agent.to_str().ok());
+
+  let request = authenticate::VerifyMfaRequest {
+    ip_address: ip_address.as_deref(),
+    user_agent,
+    ..(&request.0).into()
+  };
+
+  match use_case.verify_mfa(&request).await {
     Ok(user) => {
       let response: UserAuthenticationResponseHttp = user.into();
       HttpResponse::Ok().json(response)
@@ -59,14 +135,16 @@ pub async fn sign_in(
       (status = 201, description = "Register with valid data", body = UserAuthenticationResponseHttp),
       (status = 400, description = "Register with invalid data"),
       (status = 409, description = "Already existing user"),
+      (status = 429, description = "Too many requests from this address or for this account, retry after Retry-After seconds"),
       (status = 500, description = "Internal server error")
   )
 )]
 #[post("/v1/auth/register")]
 pub async fn register(
+  req: HttpRequest,
   app_state: web::Data<AppState>,
-  services: web::Data<Services<JWTService<'_>>>,
-  utilities: web::Data<Utilities<BCrypt, NewID>>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
   request: web::Json<UserRegistrationRequest>,
 ) -> HttpResponse {
   let use_case = UserUseCase {
@@ -77,7 +155,19 @@ pub async fn register(
     utilities: &utilities,
   };
 
-  match use_case.register(&(&request.0).into()).await {
+  let ip_address = request_client_ip(&req);
+  let user_agent = req
+    .headers()
+    .get(USER_AGENT)
+    .and_then(|user_agent| user_agent.to_str().ok());
+
+  let request = authenticate::UserRegistrationRequest {
+    ip_address: ip_address.as_deref(),
+    user_agent,
+    ..(&request.0).into()
+  };
+
+  match use_case.register(&request).await {
     Ok(user) => {
       let response: UserAuthenticationResponseHttp = user.into();
       HttpResponse::Created().json(response)
@@ -89,9 +179,9 @@ pub async fn register(
 #[utoipa::path(
   request_body = ChangeUserPasswordRequest,
   responses(
-      (status = 200, description = "Password changed successfully"),
+      (status = 200, description = "Password changed, every other session signed out and a new token pair issued for this one", body = UserAuthenticationResponseHttp),
       (status = 400, description = "Received invalid data"),
-      (status = 401, description = "Received JWT token invalid or expired"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
       (status = 403, description = "Received JWT token not have permission for the given user"),
       (status = 500, description = "Internal server error")
   ),
@@ -101,18 +191,109 @@ pub async fn register(
 )]
 #[put("/v1/auth/change_password")]
 pub async fn change_password(
-  req: HttpRequest,
+  user: AuthenticatedUser,
   app_state: web::Data<AppState>,
-  services: web::Data<Services<JWTService<'_>>>,
-  utilities: web::Data<Utilities<BCrypt, NewID>>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
   request: web::Json<ChangeUserPasswordRequest>,
 ) -> HttpResponse {
-  let token = if let Some(token) = extract_bearer_token(&req) {
-    token
-  } else {
-    return HttpResponse::BadRequest().body("Invalid Bearer token");
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.update_password(&(&request.0).into(), &user).await {
+    Ok(user) => {
+      let response: UserAuthenticationResponseHttp = user.into();
+      HttpResponse::Ok().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = RefreshTokenRequest,
+  responses(
+      (status = 200, description = "Exchange a valid refresh token for a new token pair", body = UserAuthenticationResponseHttp),
+      (status = 400, description = "Received invalid data"),
+      (status = 401, description = "Refresh token invalid, expired, revoked or already used"),
+      (status = 500, description = "Internal server error")
+  )
+)]
+#[post("/v1/auth/refresh")]
+pub async fn refresh(
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<RefreshTokenRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.refresh(&(&request.0).into()).await {
+    Ok(user) => {
+      let response: UserAuthenticationResponseHttp = user.into();
+      HttpResponse::Ok().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  responses(
+      (status = 200, description = "Access token revoked along with its session"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid, expired or already revoked"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[post("/v1/auth/logout")]
+pub async fn logout(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
   };
 
+  match use_case.logout(&user).await {
+    Ok(_) => HttpResponse::Ok().body("logged out successfully"),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = VerifyEmailRequest,
+  responses(
+      (status = 200, description = "Email address verified"),
+      (status = 400, description = "Verification token invalid, expired or already used"),
+      (status = 404, description = "Email address no longer belongs to the user"),
+      (status = 500, description = "Internal server error")
+  )
+)]
+#[post("/v1/auth/verify-email")]
+pub async fn verify_email(
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<VerifyEmailRequest>,
+) -> HttpResponse {
   let use_case = UserUseCase {
     user_repository: &UserRepositoryDB {
       pool: &app_state.postgres_pool,
@@ -121,8 +302,390 @@ pub async fn change_password(
     utilities: &utilities,
   };
 
-  match use_case.update_password(&(&request.0).into(), token).await {
+  match use_case.verify_email(&(&request.0).into()).await {
+    Ok(address) => HttpResponse::Ok().body(format!("email {} verified successfully", address)),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = ForgotPasswordRequest,
+  responses(
+      (status = 202, description = "A reset token is emailed when the account exists, the answer is the same either way"),
+      (status = 400, description = "Received invalid data"),
+      (status = 500, description = "Internal server error")
+  )
+)]
+#[post("/v1/auth/password/forgot")]
+pub async fn forgot_password(
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<ForgotPasswordRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.forgot_password(&(&request.0).into()).await {
+    Ok(_) => HttpResponse::Accepted().body("if the account exists a reset token was sent"),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = ResetPasswordRequest,
+  responses(
+      (status = 200, description = "Password changed and every session of the account revoked"),
+      (status = 400, description = "Received invalid data or reset token invalid, expired or already used"),
+      (status = 500, description = "Internal server error")
+  )
+)]
+#[post("/v1/auth/password/reset")]
+pub async fn reset_password(
+  req: HttpRequest,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<ResetPasswordRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  let ip_address = request_client_ip(&req);
+  let user_agent = req
+    .headers()
+    .get(USER_AGENT)
+    .and_then(|user_agent| user_agent.to_str().ok());
+
+  let request = authenticate::ResetPasswordRequest {
+    ip_address: ip_address.as_deref(),
+    user_agent,
+    ..(&request.0).into()
+  };
+
+  match use_case.reset_password(&request).await {
     Ok(_) => HttpResponse::Ok().body("password changed successfully"),
     Err(error) => error.into(),
   }
 }
+
+#[utoipa::path(
+  responses(
+      (status = 200, description = "Options to create a passkey with the authenticator", body = WebAuthnRegistrationOptionsHttp),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid, expired or revoked"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[post("/v1/auth/webauthn/register/options")]
+pub async fn webauthn_registration_options(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.webauthn_registration_options(&user).await {
+    Ok(options) => {
+      let response: WebAuthnRegistrationOptionsHttp = options.into();
+      HttpResponse::Ok().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = RegisterWebAuthnRequest,
+  responses(
+      (status = 201, description = "Passkey registered", body = WebAuthnCredentialResponseHttp),
+      (status = 400, description = "Received invalid data, or challenge invalid, expired or already used"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid, expired or revoked"),
+      (status = 409, description = "Passkey already registered"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[post("/v1/auth/webauthn/register")]
+pub async fn register_webauthn(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<RegisterWebAuthnRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case
+    .register_webauthn(&(&request.0).into(), &user)
+    .await
+  {
+    Ok(credential_id) => {
+      HttpResponse::Created().json(WebAuthnCredentialResponseHttp { credential_id })
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = WebAuthnSignInOptionsRequest,
+  responses(
+      (status = 200, description = "Options to sign in with one of the passkeys of the user", body = WebAuthnSignInOptionsHttp),
+      (status = 400, description = "Received invalid data or the user has no passkey"),
+      (status = 404, description = "No user found with given username"),
+      (status = 500, description = "Internal server error")
+  )
+)]
+#[post("/v1/auth/webauthn/sign_in/options")]
+pub async fn webauthn_sign_in_options(
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<WebAuthnSignInOptionsRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case
+    .webauthn_sign_in_options(&(&request.0).into())
+    .await
+  {
+    Ok(options) => {
+      let response: WebAuthnSignInOptionsHttp = options.into();
+      HttpResponse::Ok().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = WebAuthnSignInRequest,
+  responses(
+      (status = 200, description = "Signed in with a passkey, no second factor needed", body = UserAuthenticationResponseHttp),
+      (status = 400, description = "Received invalid data"),
+      (status = 401, description = "Unknown passkey, invalid signature or challenge invalid, expired or already used"),
+      (status = 403, description = "Account disabled by an administrator"),
+      (status = 423, description = "Account blocked after too many failed sign-in attempts"),
+      (status = 500, description = "Internal server error")
+  )
+)]
+#[post("/v1/auth/webauthn/sign_in")]
+pub async fn webauthn_sign_in(
+  req: HttpRequest,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<WebAuthnSignInRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  let ip_address = request_client_ip(&req);
+  let user_agent = req
+    .headers()
+    .get(USER_AGENT)
+    .and_then(|user_agent| user_agent.to_str().ok());
+
+  let request = authenticate::WebAuthnSignInRequest {
+    ip_address: ip_address.as_deref(),
+    user_agent,
+    ..(&request.0).into()
+  };
+
+  match use_case.webauthn_sign_in(&request).await {
+    Ok(user) => {
+      let response: UserAuthenticationResponseHttp = user.into();
+      HttpResponse::Ok().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  params(
+    ("provider" = String, Path, description = "Name of the configured identity provider")
+  ),
+  responses(
+      (status = 200, description = "Sign-in started, the browser is to be sent to the provider", body = ExternalAuthorizationHttp),
+      (status = 404, description = "No identity provider configured with the given name"),
+      (status = 500, description = "Internal server error")
+  )
+)]
+#[get("/v1/auth/external/{provider}/authorize")]
+pub async fn external_authorize(
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  provider: web::Path<String>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.start_external_sign_in(&provider).await {
+    Ok(authorization_url) => {
+      HttpResponse::Ok().json(ExternalAuthorizationHttp { authorization_url })
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  params(
+    ("provider" = String, Path, description = "Name of the configured identity provider")
+  ),
+  request_body = ExternalCallbackRequest,
+  responses(
+      (status = 200, description = "Signed in with the profile linked to the provider account, or with the same verified email", body = UserAuthenticationResponseHttp),
+      (status = 202, description = "No profile matched: the sign-up is completed at /v1/auth/external/sign_up. With two-factor authentication a `MfaChallengeResponseHttp` is returned instead", body = ExternalSignUpRequiredHttp),
+      (status = 400, description = "Received invalid data or the provider shared no email"),
+      (status = 401, description = "State invalid, expired or already used, or code or ID token refused"),
+      (status = 403, description = "Account disabled by an administrator"),
+      (status = 404, description = "No identity provider configured with the given name"),
+      (status = 409, description = "Email already registered but not verified on both sides"),
+      (status = 423, description = "Account blocked after too many failed sign-in attempts"),
+      (status = 500, description = "Internal server error")
+  )
+)]
+#[post("/v1/auth/external/{provider}/callback")]
+pub async fn external_callback(
+  req: HttpRequest,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  provider: web::Path<String>,
+  request: web::Json<ExternalCallbackRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  let ip_address = request_client_ip(&req);
+  let user_agent = req
+    .headers()
+    .get(USER_AGENT)
+    .and_then(|user_agent| user_agent.to_str().ok());
+
+  let request = authenticate::ExternalSignInRequest {
+    provider: &provider,
+    code: &request.code,
+    state: &request.state,
+    ip_address: ip_address.as_deref(),
+    user_agent,
+  };
+
+  match use_case.external_sign_in(&request).await {
+    Ok(ExternalSignInResponse::SignedIn(SignInResponse::Authenticated(user))) => {
+      let response: UserAuthenticationResponseHttp = user.into();
+      HttpResponse::Ok().json(response)
+    }
+    Ok(ExternalSignInResponse::SignedIn(SignInResponse::MfaRequired { mfa_token })) => {
+      HttpResponse::Accepted().json(MfaChallengeResponseHttp {
+        mfa_required: true,
+        mfa_token,
+      })
+    }
+    Ok(ExternalSignInResponse::SignUpRequired {
+      signup_token,
+      name,
+      email,
+    }) => HttpResponse::Accepted().json(ExternalSignUpRequiredHttp {
+      signup_required: true,
+      signup_token,
+      name,
+      email,
+    }),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = ExternalSignUpRequest,
+  responses(
+      (status = 201, description = "Profile created and linked to the provider account", body = UserAuthenticationResponseHttp),
+      (status = 400, description = "Register with invalid data"),
+      (status = 401, description = "Sign-up token invalid, expired or already used"),
+      (status = 409, description = "Already existing user"),
+      (status = 500, description = "Internal server error")
+  )
+)]
+#[post("/v1/auth/external/sign_up")]
+pub async fn external_sign_up(
+  req: HttpRequest,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<ExternalSignUpRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  let ip_address = request_client_ip(&req);
+  let user_agent = req
+    .headers()
+    .get(USER_AGENT)
+    .and_then(|user_agent| user_agent.to_str().ok());
+
+  let request = authenticate::ExternalSignUpRequest {
+    ip_address: ip_address.as_deref(),
+    user_agent,
+    ..(&request.0).into()
+  };
+
+  match use_case.external_sign_up(&request).await {
+    Ok(user) => {
+      let response: UserAuthenticationResponseHttp = user.into();
+      HttpResponse::Created().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
@@ -0,0 +1,180 @@
+use crate::{
+  adapter::{
+    repositories::user::UserRepositoryDB,
+    routers::helpers::{
+      actix_authenticated_user::{AppServices, AppUtilities},
+      actix_basic_auth::extract_basic_credentials,
+      actix_bearer_token::extract_bearer_token,
+    },
+  },
+  application::{
+    services::security::authenticated_user::AuthenticatedUser,
+    use_cases::{
+      authenticate::user::UserUseCase,
+      oauth::{OpenIdProvider, TokenRequest},
+    },
+  },
+  domain::error::Code,
+  AppState,
+};
+use actix_web::{
+  get,
+  http::{header::CACHE_CONTROL, StatusCode},
+  post, web, HttpRequest, HttpResponse,
+};
+
+use super::dtos::{
+  AuthorizationRedirectHttp, AuthorizeQuery, OAuthErrorHttp, TokenRequestForm, TokenResponseHttp,
+  UserInfoHttp,
+};
+
+#[utoipa::path(
+  params(AuthorizeQuery),
+  responses(
+      (status = 200, description = "Where to redirect the browser, carrying the authorization code or an OAuth error", body = AuthorizationRedirectHttp),
+      (status = 400, description = "Unknown client or unregistered redirect_uri"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[get("/v1/oauth/authorize")]
+pub async fn authorize(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  query: web::Query<AuthorizeQuery>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.authorize(&(&query.0).into(), &user).await {
+    Ok(redirect_to) => HttpResponse::Ok().json(AuthorizationRedirectHttp { redirect_to }),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body(content = TokenRequestForm, content_type = "application/x-www-form-urlencoded"),
+  responses(
+      (status = 200, description = "ID token and userinfo access token", body = TokenResponseHttp),
+      (status = 400, description = "Invalid authorization code, redirect_uri or code_verifier", body = OAuthErrorHttp),
+      (status = 401, description = "Invalid client credentials", body = OAuthErrorHttp),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    (),
+    ("oauth_client_basic" = [])
+  )
+)]
+#[post("/v1/oauth/token")]
+pub async fn exchange_code(
+  req: HttpRequest,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  form: web::Form<TokenRequestForm>,
+) -> HttpResponse {
+  if form.grant_type != "authorization_code" {
+    return oauth_error(
+      StatusCode::BAD_REQUEST,
+      "unsupported_grant_type",
+      "only authorization_code is supported",
+    );
+  }
+
+  let basic_credentials = extract_basic_credentials(&req);
+  let (client_id, client_secret) = match &basic_credentials {
+    Some((client_id, client_secret)) => (client_id.as_str(), Some(client_secret.as_str())),
+    None => (
+      form.client_id.as_deref().unwrap_or_default(),
+      form.client_secret.as_deref(),
+    ),
+  };
+
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  let request = TokenRequest {
+    client_id,
+    client_secret,
+    code: &form.code,
+    redirect_uri: &form.redirect_uri,
+    code_verifier: &form.code_verifier,
+  };
+
+  match use_case.exchange_code(&request).await {
+    Ok(tokens) => HttpResponse::Ok()
+      .insert_header((CACHE_CONTROL, "no-store"))
+      .json(TokenResponseHttp::from(tokens)),
+    Err(error) => match error.code {
+      Code::InvalidArgument => {
+        oauth_error(StatusCode::BAD_REQUEST, "invalid_grant", &error.message)
+      }
+      Code::Unauthenticated => {
+        oauth_error(StatusCode::UNAUTHORIZED, "invalid_client", &error.message)
+      }
+      _ => error.into(),
+    },
+  }
+}
+
+fn oauth_error(status: StatusCode, error: &str, description: &str) -> HttpResponse {
+  HttpResponse::build(status)
+    .insert_header((CACHE_CONTROL, "no-store"))
+    .json(OAuthErrorHttp {
+      error: error.to_owned(),
+      error_description: description.to_owned(),
+    })
+}
+
+#[utoipa::path(
+  responses(
+      (status = 200, description = "Claims about the user of the access token", body = UserInfoHttp),
+      (status = 400, description = "Malformed Bearer token"),
+      (status = 401, description = "Access token invalid, expired or not issued by the token endpoint"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[get("/v1/oauth/userinfo")]
+pub async fn userinfo(
+  req: HttpRequest,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+) -> HttpResponse {
+  let token = if let Some(token) = extract_bearer_token(&req) {
+    token
+  } else {
+    return HttpResponse::BadRequest().body("Invalid Bearer token");
+  };
+
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.userinfo(token).await {
+    Ok(profile) => HttpResponse::Ok().json(UserInfoHttp::from(profile)),
+    Err(error) => error.into(),
+  }
+}
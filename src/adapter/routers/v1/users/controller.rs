@@ -0,0 +1,427 @@
+use crate::{
+  adapter::{
+    repositories::user::UserRepositoryDB,
+    routers::helpers::actix_authenticated_user::{AppServices, AppUtilities},
+  },
+  application::{
+    services::security::authenticated_user::AuthenticatedUser,
+    use_cases::{
+      authenticate::user::UserUseCase,
+      profile::{self, UserProfile},
+    },
+  },
+  AppState,
+};
+use actix_web::{delete, get, patch, post, web, HttpResponse};
+
+use super::dtos::{
+  ConfirmTotpRequest, DeleteUserRequest, ListSignInsQuery, RecoveryCodesHttp, SessionHttp,
+  SessionListResponseHttp, SignInHistoryResponseHttp, TelephoneHttp, TotpEnrollmentHttp,
+  UpdateUserProfileRequest, UserProfileResponseHttp, VerifyTelephoneRequest,
+};
+
+#[utoipa::path(
+  responses(
+      (status = 200, description = "Profile of the user owner of the given token", body = UserProfileResponseHttp),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 404, description = "No profile found for the given token"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[get("/v1/users/me")]
+pub async fn get_me(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.get_profile(&user).await {
+    Ok(profile) => {
+      let response: UserProfileResponseHttp = profile.into();
+      HttpResponse::Ok().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = UpdateUserProfileRequest,
+  responses(
+      (status = 200, description = "Profile updated, returns the updated profile", body = UserProfileResponseHttp),
+      (status = 400, description = "Received invalid data"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 404, description = "No profile found for the given token"),
+      (status = 409, description = "Email or telephone already in use"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[patch("/v1/users/me")]
+pub async fn update_me(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<UpdateUserProfileRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.update_profile(&(&request.0).into(), &user).await {
+    Ok(profile) => {
+      let response: UserProfileResponseHttp = profile.into();
+      HttpResponse::Ok().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  params(ListSignInsQuery),
+  responses(
+      (status = 200, description = "Sign-in attempts of the user owner of the given token, newest first", body = SignInHistoryResponseHttp),
+      (status = 400, description = "Received invalid pagination"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[get("/v1/users/me/sign-ins")]
+pub async fn list_my_sign_ins(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  query: web::Query<ListSignInsQuery>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.list_sign_ins(&(&query.0).into(), &user).await {
+    Ok(sign_ins) => {
+      let response: SignInHistoryResponseHttp = sign_ins.into();
+      HttpResponse::Ok().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  responses(
+      (status = 200, description = "Sessions still signed in for the user owner of the given token, most recently used first", body = SessionListResponseHttp),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[get("/v1/users/me/sessions")]
+pub async fn list_my_sessions(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.list_sessions(&user).await {
+    Ok(sessions) => HttpResponse::Ok().json(SessionListResponseHttp {
+      items: sessions
+        .into_iter()
+        .map(|session| SessionHttp::new(session, user.session_id.as_deref()))
+        .collect(),
+    }),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  params(
+    ("id" = String, Path, description = "Id of the session to sign out")
+  ),
+  responses(
+      (status = 200, description = "Session signed out, its access and refresh tokens no longer accepted"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 404, description = "No session with the given id signed in for the profile"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[delete("/v1/users/me/sessions/{id}")]
+pub async fn revoke_my_session(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  id: web::Path<String>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.revoke_session(&id, &user).await {
+    Ok(_) => HttpResponse::Ok().body("session signed out"),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  responses(
+      (status = 200, description = "Every other session signed out, the one of the given token is kept"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[delete("/v1/users/me/sessions")]
+pub async fn revoke_my_other_sessions(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.revoke_other_sessions(&user).await {
+    Ok(_) => HttpResponse::Ok().body("other sessions signed out"),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = DeleteUserRequest,
+  responses(
+      (status = 202, description = "Account scheduled for deletion, signing in before the grace period ends cancels it"),
+      (status = 400, description = "Received invalid data"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 403, description = "The given password is invalid"),
+      (status = 404, description = "No profile found for the given token"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[delete("/v1/users/me")]
+pub async fn delete_me(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<DeleteUserRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.request_deletion(&(&request.0).into(), &user).await {
+    Ok(_) => HttpResponse::Accepted().body("account scheduled for deletion"),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  params(
+    ("number" = String, Path, description = "Telephone number of the profile to verify")
+  ),
+  responses(
+      (status = 202, description = "Verification code sent by SMS to the given number"),
+      (status = 400, description = "Number already verified"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 404, description = "The number does not belong to the profile of the given token"),
+      (status = 429, description = "Too many verification codes requested in the last hour"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[post("/v1/users/me/telephones/{number}/verification")]
+pub async fn send_telephone_code(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  number: web::Path<String>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  let request = profile::TelephoneCodeRequest { number: &number };
+
+  match use_case.send_telephone_code(&request, &user).await {
+    Ok(_) => HttpResponse::Accepted().body("verification code sent"),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  params(
+    ("number" = String, Path, description = "Telephone number of the profile to verify")
+  ),
+  request_body = VerifyTelephoneRequest,
+  responses(
+      (status = 200, description = "Telephone number verified", body = TelephoneHttp),
+      (status = 400, description = "Received invalid, expired or already used code"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 429, description = "Too many wrong codes, a new one must be requested"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[post("/v1/users/me/telephones/{number}/verify")]
+pub async fn verify_telephone(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  number: web::Path<String>,
+  request: web::Json<VerifyTelephoneRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  let request = profile::VerifyTelephoneRequest {
+    number: &number,
+    code: &request.code,
+  };
+
+  match use_case.verify_telephone(&request, &user).await {
+    Ok(number) => HttpResponse::Ok().json(TelephoneHttp {
+      number,
+      checked: true,
+    }),
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  responses(
+      (status = 201, description = "TOTP secret generated, to be confirmed with a code from the authenticator", body = TotpEnrollmentHttp),
+      (status = 400, description = "Two-factor authentication already enabled"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[post("/v1/users/me/mfa/totp")]
+pub async fn enroll_totp(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.enroll_totp(&user).await {
+    Ok(enrollment) => {
+      let response: TotpEnrollmentHttp = enrollment.into();
+      HttpResponse::Created().json(response)
+    }
+    Err(error) => error.into(),
+  }
+}
+
+#[utoipa::path(
+  request_body = ConfirmTotpRequest,
+  responses(
+      (status = 200, description = "Two-factor authentication enabled", body = RecoveryCodesHttp),
+      (status = 400, description = "Received invalid code, no enrollment started"),
+      (status = 401, description = "Missing Bearer token, or received JWT token invalid or expired"),
+      (status = 500, description = "Internal server error")
+  ),
+  security(
+    ("api_jwt_token" = [])
+  )
+)]
+#[post("/v1/users/me/mfa/totp/confirm")]
+pub async fn confirm_totp(
+  user: AuthenticatedUser,
+  app_state: web::Data<AppState>,
+  services: web::Data<AppServices>,
+  utilities: web::Data<AppUtilities>,
+  request: web::Json<ConfirmTotpRequest>,
+) -> HttpResponse {
+  let use_case = UserUseCase {
+    user_repository: &UserRepositoryDB {
+      pool: &app_state.postgres_pool,
+    },
+    services: &services,
+    utilities: &utilities,
+  };
+
+  match use_case.confirm_totp(&(&request.0).into(), &user).await {
+    Ok(recovery_codes) => HttpResponse::Ok().json(RecoveryCodesHttp { recovery_codes }),
+    Err(error) => error.into(),
+  }
+}
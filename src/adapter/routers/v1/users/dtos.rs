@@ -0,0 +1,311 @@
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+use crate::{
+  application::use_cases::profile,
+  domain::entities::{
+    session::Session,
+    sign_in_event::{SignInEvent, SignInEventPage},
+    totp::TotpEnrollment,
+    user::{Address, Email, FullProfile, Telephone},
+  },
+};
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct TelephoneHttp {
+  #[schema(example = "+1 151 999-9999")]
+  pub number: String,
+  #[schema(example = false)]
+  pub checked: bool,
+}
+
+impl From<Telephone> for TelephoneHttp {
+  fn from(value: Telephone) -> Self {
+    TelephoneHttp {
+      number: value.number,
+      checked: value.checked,
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct EmailHttp {
+  #[schema(example = "johndoe@company.com")]
+  pub address: String,
+  #[schema(example = false)]
+  pub checked: bool,
+}
+
+impl From<Email> for EmailHttp {
+  fn from(value: Email) -> Self {
+    EmailHttp {
+      address: value.address,
+      checked: value.checked,
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct AddressHttp {
+  #[schema(example = "153 W 57th St")]
+  pub street: String,
+  #[schema(example = "manhattan")]
+  pub neighborhood: String,
+  #[schema(example = 10019)]
+  pub postal_code: i32,
+  #[schema(example = "New York City")]
+  pub city: String,
+  #[schema(example = "New York")]
+  pub state: String,
+  #[schema(example = "United States")]
+  pub country: String,
+}
+
+impl From<Address> for AddressHttp {
+  fn from(value: Address) -> Self {
+    AddressHttp {
+      street: value.street,
+      neighborhood: value.neighborhood,
+      postal_code: value.postal_code,
+      city: value.city,
+      state: value.state,
+      country: value.country,
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct UserProfileResponseHttp {
+  #[schema(example = "77285939-bdaa-4c5f-9805-3873fca3396e")]
+  pub id: String,
+  #[schema(example = "John Doe")]
+  pub name: String,
+  #[schema(example = "john.doe")]
+  pub username: String,
+  #[schema(example = "1999-12-31")]
+  pub birth_date: String,
+  #[schema(example = "Male")]
+  pub gender: String,
+  pub telephones: Vec<TelephoneHttp>,
+  pub emails: Vec<EmailHttp>,
+  pub address: AddressHttp,
+}
+
+impl From<FullProfile> for UserProfileResponseHttp {
+  fn from(value: FullProfile) -> Self {
+    UserProfileResponseHttp {
+      id: value.id,
+      name: value.name,
+      username: value.username,
+      birth_date: value.birth_date.format("%Y-%m-%d").to_string(),
+      gender: value.gender,
+      telephones: value.telephones.into_iter().map(Into::into).collect(),
+      emails: value.emails.into_iter().map(Into::into).collect(),
+      address: value.address.into(),
+    }
+  }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, ToSchema)]
+pub struct UpdateUserProfileRequest {
+  #[schema(example = "John Doe")]
+  pub name: Option<String>,
+  #[schema(example = "1999-12-31")]
+  pub birth_date: Option<String>,
+  #[schema(example = 1)]
+  pub gender_id: Option<i32>,
+  #[schema(example = "153 W 57th St")]
+  pub address_street: Option<String>,
+  #[schema(example = "manhattan")]
+  pub address_neighborhood: Option<String>,
+  #[schema(example = 4)]
+  pub address_city_id: Option<i32>,
+  #[schema(example = 10019)]
+  pub address_postal_code: Option<i32>,
+  #[schema(example = "johndoe@company.com")]
+  pub email: Option<String>,
+  #[schema(example = "+1 151 999-9999")]
+  pub telephone: Option<String>,
+}
+
+impl<'a> From<&'a UpdateUserProfileRequest> for profile::UpdateUserProfileRequest<'a> {
+  fn from(data: &'a UpdateUserProfileRequest) -> Self {
+    profile::UpdateUserProfileRequest {
+      name: data.name.as_deref(),
+      birth_date: data.birth_date.as_deref(),
+      gender_id: data.gender_id,
+      address_street: data.address_street.as_deref(),
+      address_neighborhood: data.address_neighborhood.as_deref(),
+      address_city_id: data.address_city_id,
+      address_postal_code: data.address_postal_code,
+      email: data.email.as_deref(),
+      telephone: data.telephone.as_deref(),
+    }
+  }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, ToSchema)]
+pub struct DeleteUserRequest {
+  #[schema(example = "12345678")]
+  pub password: String,
+}
+
+impl<'a> From<&'a DeleteUserRequest> for profile::DeleteUserRequest<'a> {
+  fn from(data: &'a DeleteUserRequest) -> Self {
+    profile::DeleteUserRequest {
+      password: &data.password,
+    }
+  }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, ToSchema)]
+pub struct VerifyTelephoneRequest {
+  #[schema(example = "482910")]
+  pub code: String,
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct TotpEnrollmentHttp {
+  #[schema(example = "JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP")]
+  pub secret: String,
+  #[schema(
+    example = "otpauth://totp/skeleton_rust_rest_api:john.doe?secret=JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP&issuer=skeleton_rust_rest_api&algorithm=SHA1&digits=6&period=30"
+  )]
+  pub provisioning_uri: String,
+}
+
+impl From<TotpEnrollment> for TotpEnrollmentHttp {
+  fn from(value: TotpEnrollment) -> Self {
+    TotpEnrollmentHttp {
+      secret: value.secret,
+      provisioning_uri: value.provisioning_uri,
+    }
+  }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, Default, ToSchema)]
+pub struct ConfirmTotpRequest {
+  #[schema(example = "482910")]
+  pub code: String,
+}
+
+impl<'a> From<&'a ConfirmTotpRequest> for profile::ConfirmTotpRequest<'a> {
+  fn from(value: &'a ConfirmTotpRequest) -> Self {
+    profile::ConfirmTotpRequest { code: &value.code }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct RecoveryCodesHttp {
+  /// Shown once, each code signs in a single time in place of the authenticator.
+  #[schema(example = json!(["0123456789", "9876543210"]))]
+  pub recovery_codes: Vec<String>,
+}
+
+#[derive(Deserialize, Clone, Debug, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListSignInsQuery {
+  /// Page number, starting at 1
+  #[param(example = 1)]
+  pub page: Option<i64>,
+  /// Events per page, at most 100
+  #[param(example = 20)]
+  pub per_page: Option<i64>,
+}
+
+impl From<&ListSignInsQuery> for profile::ListSignInsRequest {
+  fn from(data: &ListSignInsQuery) -> Self {
+    let default = profile::ListSignInsRequest::default();
+
+    profile::ListSignInsRequest {
+      page: data.page.unwrap_or(default.page),
+      per_page: data.per_page.unwrap_or(default.per_page),
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct SignInEventHttp {
+  #[schema(example = "username")]
+  pub method: String,
+  #[schema(example = true)]
+  pub succeeded: bool,
+  #[schema(example = "203.0.113.7")]
+  pub ip_address: Option<String>,
+  #[schema(example = "Mozilla/5.0 (X11; Linux x86_64)")]
+  pub user_agent: Option<String>,
+  #[schema(example = "2023-11-13T12:00:00")]
+  pub created_at: String,
+}
+
+impl From<SignInEvent> for SignInEventHttp {
+  fn from(value: SignInEvent) -> Self {
+    SignInEventHttp {
+      method: value.method.as_str().to_string(),
+      succeeded: value.succeeded,
+      ip_address: value.ip_address,
+      user_agent: value.user_agent,
+      created_at: value.created_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct SignInHistoryResponseHttp {
+  pub items: Vec<SignInEventHttp>,
+  #[schema(example = 1)]
+  pub page: i64,
+  #[schema(example = 20)]
+  pub per_page: i64,
+  #[schema(example = 1)]
+  pub total: i64,
+}
+
+impl From<SignInEventPage> for SignInHistoryResponseHttp {
+  fn from(value: SignInEventPage) -> Self {
+    SignInHistoryResponseHttp {
+      items: value.events.into_iter().map(Into::into).collect(),
+      page: value.page,
+      per_page: value.per_page,
+      total: value.total,
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct SessionHttp {
+  #[schema(example = "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed")]
+  pub id: String,
+  #[schema(example = "Firefox on Linux")]
+  pub device_label: String,
+  #[schema(example = "203.0.113.7")]
+  pub ip_address: Option<String>,
+  #[schema(example = "Mozilla/5.0 (X11; Linux x86_64; rv:120.0) Gecko/20100101 Firefox/120.0")]
+  pub user_agent: Option<String>,
+  #[schema(example = "2023-11-25T12:00:00")]
+  pub created_at: String,
+  #[schema(example = "2023-11-25T12:30:00")]
+  pub last_seen_at: String,
+  /// Whether the session is the one of the token making the request.
+  #[schema(example = true)]
+  pub current: bool,
+}
+
+impl SessionHttp {
+  pub fn new(value: Session, current_session_id: Option<&str>) -> Self {
+    SessionHttp {
+      current: current_session_id == Some(value.id.as_str()),
+      id: value.id,
+      device_label: value.device_label,
+      ip_address: value.ip_address,
+      user_agent: value.user_agent,
+      created_at: value.created_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+      last_seen_at: value.last_seen_at.format("%Y-%m-%dT%H:%M:%S").to_string(),
+    }
+  }
+}
+
+#[derive(Serialize, Deserialize, ToSchema, Clone, Debug)]
+pub struct SessionListResponseHttp {
+  pub items: Vec<SessionHttp>,
+}
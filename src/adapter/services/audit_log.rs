@@ -0,0 +1,369 @@
+use crate::{
+  application::services::audit_log::AuditLog,
+  domain::{
+    entities::audit_event::{
+      AuditChainVerification, AuditEvent, AuditEventFilter, AuditEventPage, AuditOutcome,
+      NewAuditEvent,
+    },
+    error::AppError,
+  },
+};
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use futures_util::TryStreamExt;
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use sqlx::{Pool, Postgres};
+
+/// Previous hash of the first event.
+const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+/// Key of the transaction level advisory lock held while appending an event.
+const CHAIN_HEAD_LOCK: i64 = 0x6175_6469_7400;
+
+pub struct AuditLogDB {
+  pub pool: Pool<Postgres>,
+}
+
+/// JSONB does not keep the key order, objects are hashed with sorted keys so
+/// that the hash survives the round trip.
+fn canonical(value: &Value) -> Value {
+  match value {
+    Value::Object(object) => {
+      let mut keys: Vec<&String> = object.keys().collect();
+      keys.sort();
+
+      let mut sorted = Map::new();
+      for key in keys {
+        sorted.insert(key.clone(), canonical(&object[key]));
+      }
+      Value::Object(sorted)
+    }
+    Value::Array(items) => Value::Array(items.iter().map(canonical).collect()),
+    value => value.clone(),
+  }
+}
+
+fn chain_hash(previous_hash: &str, event: &NewAuditEvent, created_at: &NaiveDateTime) -> String {
+  let content = json!([
+    previous_hash,
+    created_at.format("%Y-%m-%dT%H:%M:%S%.6f").to_string(),
+    event.actor_id,
+    event.subject_id,
+    event.action,
+    event.outcome.as_str(),
+    event.ip_address,
+    event.user_agent,
+    canonical(&event.metadata),
+  ]);
+
+  Sha256::digest(content.to_string().as_bytes())
+    .iter()
+    .map(|byte| format!("{:02x}", byte))
+    .collect()
+}
+
+#[async_trait]
+impl AuditLog for AuditLogDB {
+  async fn record(&self, event: &NewAuditEvent) -> Result<(), AppError> {
+    // stored with microseconds, the precision of the column
+    let created_at = NaiveDateTime::from_timestamp_micros(Utc::now().timestamp_micros())
+      .ok_or_else(|| AppError::internal("invalid audit event timestamp"))?;
+
+    let mut transaction = self.pool.begin().await?;
+
+    // one writer at a time, so every event chains to the latest one; unlike a
+    // table lock it leaves vacuum and the other users of the table alone
+    sqlx::query!("SELECT pg_advisory_xact_lock($1)", CHAIN_HEAD_LOCK)
+      .execute(&mut *transaction)
+      .await?;
+
+    let previous_hash =
+      sqlx::query_scalar!("SELECT hash FROM audit_events ORDER BY id DESC LIMIT 1")
+        .fetch_optional(&mut *transaction)
+        .await?
+        .unwrap_or_else(|| GENESIS_HASH.to_string());
+    let hash = chain_hash(&previous_hash, event, &created_at);
+
+    sqlx::query!(
+      "INSERT INTO audit_events (
+        actor_id, subject_id, action, outcome, ip_address, user_agent, metadata,
+        created_at, previous_hash, hash
+      )
+      VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)",
+      event.actor_id,
+      event.subject_id,
+      event.action,
+      event.outcome.as_str(),
+      event.ip_address,
+      event.user_agent,
+      event.metadata,
+      created_at,
+      previous_hash,
+      hash,
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(())
+  }
+
+  async fn find_events(
+    &self,
+    filter: &AuditEventFilter,
+    page: i64,
+    per_page: i64,
+  ) -> Result<AuditEventPage, AppError> {
+    let outcome = filter.outcome.map(|outcome| outcome.as_str());
+
+    let total = sqlx::query_scalar!(
+      r#"SELECT COUNT(*) AS "total!"
+      FROM audit_events
+      WHERE ($1::TEXT IS NULL OR actor_id = $1)
+        AND ($2::TEXT IS NULL OR subject_id = $2)
+        AND ($3::TEXT IS NULL OR action = $3)
+        AND ($4::TEXT IS NULL OR outcome = $4)"#,
+      filter.actor_id,
+      filter.subject_id,
+      filter.action,
+      outcome,
+    )
+    .fetch_one(&self.pool)
+    .await?;
+
+    let events = sqlx::query!(
+      "SELECT
+        id, actor_id, subject_id, action, outcome, ip_address, user_agent, metadata,
+        created_at, hash
+      FROM audit_events
+      WHERE ($1::TEXT IS NULL OR actor_id = $1)
+        AND ($2::TEXT IS NULL OR subject_id = $2)
+        AND ($3::TEXT IS NULL OR action = $3)
+        AND ($4::TEXT IS NULL OR outcome = $4)
+      ORDER BY id DESC
+      LIMIT $5 OFFSET $6",
+      filter.actor_id,
+      filter.subject_id,
+      filter.action,
+      outcome,
+      per_page,
+      (page - 1) * per_page
+    )
+    .fetch_all(&self.pool)
+    .await?;
+
+    Ok(AuditEventPage {
+      events: events
+        .into_iter()
+        .map(|event| {
+          Ok(AuditEvent {
+            id: event.id,
+            actor_id: event.actor_id,
+            subject_id: event.subject_id,
+            action: event.action,
+            outcome: AuditOutcome::try_from(event.outcome.as_str())?,
+            ip_address: event.ip_address,
+            user_agent: event.user_agent,
+            metadata: event.metadata,
+            created_at: event.created_at,
+            hash: event.hash,
+          })
+        })
+        .collect::<Result<_, AppError>>()?,
+      page,
+      per_page,
+      total,
+    })
+  }
+
+  async fn verify_chain(&self) -> Result<AuditChainVerification, AppError> {
+    let mut events = sqlx::query!(
+      "SELECT
+        id, actor_id, subject_id, action, outcome, ip_address, user_agent, metadata,
+        created_at, previous_hash, hash
+      FROM audit_events
+      ORDER BY id"
+    )
+    .fetch(&self.pool);
+
+    let mut verification = AuditChainVerification::default();
+    let mut expected_previous_hash = GENESIS_HASH.to_string();
+
+    while let Some(event) = events.try_next().await? {
+      verification.checked += 1;
+
+      let content = NewAuditEvent {
+        actor_id: event.actor_id,
+        subject_id: event.subject_id,
+        action: event.action,
+        // an unknown outcome was tampered with, it can never hash the same
+        outcome: AuditOutcome::try_from(event.outcome.as_str()).unwrap_or_default(),
+        ip_address: event.ip_address,
+        user_agent: event.user_agent,
+        metadata: event.metadata,
+      };
+      let valid = event.previous_hash == expected_previous_hash
+        && event.outcome == content.outcome.as_str()
+        && event.hash == chain_hash(&event.previous_hash, &content, &event.created_at);
+
+      if !valid {
+        verification.first_invalid_id = Some(event.id);
+        break;
+      }
+      expected_previous_hash = event.hash;
+    }
+
+    Ok(verification)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use sqlx::PgPool;
+
+  const ACTOR_ID: &str = "0f0e6d1c-4a3b-4c2d-9e8f-7a6b5c4d3e2f";
+  const SUBJECT_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  fn event(action: &str) -> NewAuditEvent {
+    NewAuditEvent {
+      actor_id: Some(ACTOR_ID.to_owned()),
+      subject_id: Some(SUBJECT_ID.to_owned()),
+      action: action.to_owned(),
+      ip_address: Some("203.0.113.7".to_owned()),
+      metadata: json!({ "role": "admin", "attempt": 1 }),
+      ..Default::default()
+    }
+  }
+
+  #[sqlx::test]
+  async fn test_record_and_find_events(pool: PgPool) -> sqlx::Result<()> {
+    let sut = AuditLogDB { pool };
+
+    sut.record(&event("admin.users.grant_role")).await.unwrap();
+    sut
+      .record(&NewAuditEvent {
+        outcome: AuditOutcome::Failure,
+        ..event("admin.users.unlock")
+      })
+      .await
+      .unwrap();
+
+    let page = sut
+      .find_events(
+        &AuditEventFilter {
+          outcome: Some(AuditOutcome::Failure),
+          ..Default::default()
+        },
+        1,
+        20,
+      )
+      .await
+      .unwrap();
+
+    assert_eq!(page.total, 1);
+    assert_eq!(page.events[0].action, "admin.users.unlock");
+    assert_eq!(
+      page.events[0].metadata,
+      json!({ "attempt": 1, "role": "admin" })
+    );
+
+    let page = sut
+      .find_events(&AuditEventFilter::default(), 1, 20)
+      .await
+      .unwrap();
+
+    assert_eq!(page.total, 2);
+    assert_eq!(page.events[0].action, "admin.users.unlock");
+
+    Ok(())
+  }
+
+  #[sqlx::test]
+  async fn test_verify_chain(pool: PgPool) -> sqlx::Result<()> {
+    let sut = AuditLogDB { pool };
+
+    for action in ["user.register", "user.sign_in", "user.change_password"] {
+      sut.record(&event(action)).await.unwrap();
+    }
+
+    assert_eq!(
+      sut.verify_chain().await.unwrap(),
+      AuditChainVerification {
+        checked: 3,
+        first_invalid_id: None,
+      }
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test]
+  async fn test_verify_chain_of_concurrent_records(pool: PgPool) -> sqlx::Result<()> {
+    let sut = AuditLogDB { pool };
+    let events = vec![event("user.sign_in"); 8];
+
+    futures_util::future::try_join_all(events.iter().map(|event| sut.record(event)))
+      .await
+      .unwrap();
+
+    assert_eq!(
+      sut.verify_chain().await.unwrap(),
+      AuditChainVerification {
+        checked: 8,
+        first_invalid_id: None,
+      }
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test]
+  async fn test_verify_chain_with_tampered_event(pool: PgPool) -> sqlx::Result<()> {
+    let sut = AuditLogDB { pool };
+
+    for action in ["user.register", "user.sign_in", "user.change_password"] {
+      sut.record(&event(action)).await.unwrap();
+    }
+
+    let tampered_id =
+      sqlx::query_scalar!("SELECT id FROM audit_events ORDER BY id LIMIT 1 OFFSET 1")
+        .fetch_one(&sut.pool)
+        .await?;
+    sqlx::query!("ALTER TABLE audit_events DISABLE TRIGGER audit_events_append_only")
+      .execute(&sut.pool)
+      .await?;
+    sqlx::query!(
+      "UPDATE audit_events SET outcome = 'failure' WHERE id = $1",
+      tampered_id
+    )
+    .execute(&sut.pool)
+    .await?;
+
+    assert_eq!(
+      sut.verify_chain().await.unwrap(),
+      AuditChainVerification {
+        checked: 2,
+        first_invalid_id: Some(tampered_id),
+      }
+    );
+
+    Ok(())
+  }
+
+  #[sqlx::test]
+  async fn test_audit_events_are_append_only(pool: PgPool) -> sqlx::Result<()> {
+    let sut = AuditLogDB { pool };
+
+    sut.record(&event("user.register")).await.unwrap();
+
+    let deleted = sqlx::query!("DELETE FROM audit_events")
+      .execute(&sut.pool)
+      .await;
+
+    assert!(deleted.is_err());
+
+    Ok(())
+  }
+}
@@ -0,0 +1,242 @@
+use std::{fs, path::PathBuf, time::Duration};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use lettre::{
+  message::Mailbox, transport::smtp::authentication::Credentials, AsyncSmtpTransport,
+  AsyncTransport, Message, Tokio1Executor,
+};
+use log::info;
+use uuid::Uuid;
+
+use crate::{
+  application::services::notification::email_sender::{EmailMessage, EmailSender},
+  domain::error::AppError,
+};
+
+/// Local stand-in for an SMTP relay: every message is written as an `.eml`
+/// file to `outbox_dir` and logged, so links can be followed during development.
+/// Messages carry live tokens, only use it in the dev profile.
+pub struct FileEmailSender {
+  pub outbox_dir: PathBuf,
+}
+
+#[async_trait]
+impl EmailSender for FileEmailSender {
+  async fn send(&self, message: &EmailMessage) -> Result<(), AppError> {
+    fs::create_dir_all(&self.outbox_dir)?;
+
+    let file = self.outbox_dir.join(format!(
+      "{}-{}.eml",
+      Utc::now().format("%Y%m%dT%H%M%S%.3f"),
+      Uuid::new_v4()
+    ));
+    fs::write(
+      &file,
+      format!(
+        "To: {}\r\nSubject: {}\r\n\r\n{}\r\n",
+        message.to, message.subject, message.body
+      ),
+    )?;
+
+    info!("email to {} written to {}", message.to, file.display());
+
+    Ok(())
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SmtpSecurity {
+  /// Plain connection upgraded with `STARTTLS`, refused when the relay does not offer it.
+  StartTls,
+  /// TLS from the first byte, usually on port 465.
+  Tls,
+  /// No encryption, only meant for a relay on the same host.
+  None,
+}
+
+/// Delivers messages through an SMTP relay.
+pub struct SmtpEmailSender {
+  transport: AsyncSmtpTransport<Tokio1Executor>,
+  from: Mailbox,
+}
+
+impl SmtpEmailSender {
+  pub fn new(
+    host: &str,
+    port: u16,
+    security: SmtpSecurity,
+    credentials: Option<(&str, &str)>,
+    from: &str,
+    timeout: Duration,
+  ) -> Result<Self, AppError> {
+    let from = from
+      .parse()
+      .map_err(|error| AppError::internal(format!("Invalid email sender {}: {}", from, error)))?;
+    let builder = match security {
+      SmtpSecurity::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host),
+      SmtpSecurity::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(host),
+      SmtpSecurity::None => Ok(AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(
+        host,
+      )),
+    }
+    .map_err(|error| AppError::internal(format!("SMTP transport setup failed: {}", error)))?;
+    let builder = builder.port(port).timeout(Some(timeout));
+    let builder = match credentials {
+      Some((username, password)) => {
+        builder.credentials(Credentials::new(username.to_string(), password.to_string()))
+      }
+      None => builder,
+    };
+
+    Ok(SmtpEmailSender {
+      transport: builder.build(),
+      from,
+    })
+  }
+}
+
+#[async_trait]
+impl EmailSender for SmtpEmailSender {
+  async fn send(&self, message: &EmailMessage) -> Result<(), AppError> {
+    let to = message.to.parse::<Mailbox>().map_err(|error| {
+      AppError::invalid_argument(format!("Invalid email recipient {}: {}", message.to, error))
+    })?;
+    let email = Message::builder()
+      .from(self.from.clone())
+      .to(to)
+      .subject(&message.subject)
+      .body(message.body.clone())
+      .map_err(|error| AppError::internal(format!("Email building failed: {}", error)))?;
+
+    self
+      .transport
+      .send(email)
+      .await
+      .map_err(|error| AppError::internal(format!("Email delivery failed: {}", error)))?;
+
+    info!("email to {} handed to the SMTP relay", message.to);
+
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    thread::{self, JoinHandle},
+  };
+
+  use super::*;
+
+  /// Answers one SMTP session and returns everything the client sent.
+  fn smtp_relay_once() -> (u16, JoinHandle<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let handle = thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+      let mut reader = BufReader::new(stream.try_clone().unwrap());
+      let mut transcript = String::new();
+      let mut in_data = false;
+      stream.write_all(b"220 localhost ESMTP\r\n").unwrap();
+
+      loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).unwrap() == 0 {
+          break;
+        }
+        transcript.push_str(&line);
+        let reply: &[u8] = if in_data {
+          if line != ".\r\n" {
+            continue;
+          }
+          in_data = false;
+          b"250 queued\r\n"
+        } else if line.starts_with("DATA") {
+          in_data = true;
+          b"354 go ahead\r\n"
+        } else if line.starts_with("QUIT") {
+          stream.write_all(b"221 bye\r\n").unwrap();
+          break;
+        } else {
+          b"250 ok\r\n"
+        };
+        stream.write_all(reply).unwrap();
+      }
+
+      transcript
+    });
+
+    (port, handle)
+  }
+
+  #[tokio::test]
+  async fn test_send_writes_message_to_outbox() {
+    let outbox_dir = std::env::temp_dir().join(format!("outbox-{}", Uuid::new_v4()));
+    let sut = FileEmailSender {
+      outbox_dir: outbox_dir.clone(),
+    };
+
+    sut
+      .send(&EmailMessage {
+        to: String::from("johndoe@company.com"),
+        subject: String::from("Verify your email"),
+        body: String::from("token"),
+      })
+      .await
+      .unwrap();
+
+    let files: Vec<_> = fs::read_dir(&outbox_dir).unwrap().collect();
+    assert_eq!(files.len(), 1);
+    let content = fs::read_to_string(files[0].as_ref().unwrap().path()).unwrap();
+    assert!(content.starts_with("To: johndoe@company.com\r\nSubject: Verify your email\r\n"));
+
+    fs::remove_dir_all(outbox_dir).unwrap();
+  }
+
+  #[tokio::test]
+  async fn test_send_hands_message_to_relay() {
+    let (port, relay) = smtp_relay_once();
+    let sut = SmtpEmailSender::new(
+      "127.0.0.1",
+      port,
+      SmtpSecurity::None,
+      None,
+      "Example <no-reply@example.com>",
+      Duration::from_secs(5),
+    )
+    .unwrap();
+
+    sut
+      .send(&EmailMessage {
+        to: String::from("johndoe@company.com"),
+        subject: String::from("Verify your email"),
+        body: String::from("token"),
+      })
+      .await
+      .unwrap();
+
+    let transcript = relay.join().unwrap();
+    assert!(transcript.contains("MAIL FROM:<no-reply@example.com>"));
+    assert!(transcript.contains("RCPT TO:<johndoe@company.com>"));
+    assert!(transcript.contains("Subject: Verify your email\r\n"));
+    assert!(transcript.contains("\r\ntoken\r\n"));
+  }
+
+  #[test]
+  fn test_refuse_invalid_sender() {
+    let result = SmtpEmailSender::new(
+      "127.0.0.1",
+      25,
+      SmtpSecurity::None,
+      None,
+      "not an address",
+      Duration::from_secs(5),
+    );
+
+    assert!(result.is_err());
+  }
+}
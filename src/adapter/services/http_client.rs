@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+use reqwest::{Client, RequestBuilder};
+
+use crate::domain::error::AppError;
+
+/// Largest response body read, token responses and key sets are a few KiB.
+pub const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+#[derive(Debug, PartialEq)]
+pub struct HttpResponse {
+  pub status: u16,
+  pub body: Vec<u8>,
+}
+
+/// Client for the few server to server calls we make. `https` URLs are
+/// verified against the Mozilla root certificates, and bodies over
+/// `max_body_bytes` are refused so a peer can not make us buffer without end.
+#[derive(Clone)]
+pub struct HttpClient {
+  client: Client,
+  max_body_bytes: usize,
+}
+
+impl HttpClient {
+  pub fn new(timeout: Duration, max_body_bytes: usize) -> Result<Self, AppError> {
+    let client = Client::builder()
+      .timeout(timeout)
+      .build()
+      .map_err(|error| AppError::internal(format!("HTTP client setup failed: {}", error)))?;
+
+    Ok(HttpClient {
+      client,
+      max_body_bytes,
+    })
+  }
+
+  pub async fn get(&self, url: &str) -> Result<HttpResponse, AppError> {
+    self
+      .send(
+        url,
+        self.client.get(url).header("Accept", "application/json"),
+      )
+      .await
+  }
+
+  pub async fn post_form(
+    &self,
+    url: &str,
+    headers: &[(&str, &str)],
+    body: &str,
+  ) -> Result<HttpResponse, AppError> {
+    let mut request = self
+      .client
+      .post(url)
+      .header("Content-Type", "application/x-www-form-urlencoded")
+      .header("Accept", "application/json")
+      .body(body.to_string());
+    for (name, value) in headers {
+      request = request.header(*name, *value);
+    }
+
+    self.send(url, request).await
+  }
+
+  async fn send(&self, url: &str, request: RequestBuilder) -> Result<HttpResponse, AppError> {
+    let http_error = |error: &dyn std::fmt::Display| {
+      AppError::internal(format!("HTTP request to {} failed: {}", url, error))
+    };
+    let too_large = || http_error(&format!("response body over {} bytes", self.max_body_bytes));
+
+    let mut response = request.send().await.map_err(|error| http_error(&error))?;
+    let status = response.status().as_u16();
+
+    if response
+      .content_length()
+      .is_some_and(|length| length > self.max_body_bytes as u64)
+    {
+      return Err(too_large());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|error| http_error(&error))? {
+      if body.len() + chunk.len() > self.max_body_bytes {
+        return Err(too_large());
+      }
+      body.extend_from_slice(&chunk);
+    }
+
+    Ok(HttpResponse { status, body })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::{
+    io::{Read, Write},
+    net::TcpListener,
+    thread,
+  };
+
+  use super::*;
+
+  /// Serves `response` once on localhost, returning its URL.
+  fn serve_once(response: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/token", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+      let (mut stream, _) = listener.accept().unwrap();
+      let mut request = [0; 4096];
+      let _ = stream.read(&mut request).unwrap();
+      stream.write_all(response.as_bytes()).unwrap();
+    });
+
+    url
+  }
+
+  fn client(max_body_bytes: usize) -> HttpClient {
+    HttpClient::new(Duration::from_secs(5), max_body_bytes).unwrap()
+  }
+
+  #[tokio::test]
+  async fn test_post_form() {
+    let url = serve_once(
+      "HTTP/1.1 400 Bad Request\r\nContent-Length: 7\r\nConnection: close\r\n\r\n{\"a\":1}",
+    );
+
+    let response = client(MAX_BODY_BYTES)
+      .post_form(&url, &[("Authorization", "Basic eDp5")], "a=1")
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response,
+      HttpResponse {
+        status: 400,
+        body: b"{\"a\":1}".to_vec(),
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_refuse_body_over_limit() {
+    let url =
+      serve_once("HTTP/1.1 200 OK\r\nContent-Length: 10\r\nConnection: close\r\n\r\n0123456789");
+
+    let error = client(4).get(&url).await.unwrap_err();
+
+    assert!(error.message.ends_with("response body over 4 bytes"));
+  }
+
+  #[tokio::test]
+  async fn test_refuse_chunked_body_over_limit() {
+    let url = serve_once(
+      "HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n3\r\n012\r\n3\r\n345\r\n0\r\n\r\n",
+    );
+
+    let error = client(4).get(&url).await.unwrap_err();
+
+    assert!(error.message.ends_with("response body over 4 bytes"));
+  }
+}
@@ -0,0 +1,329 @@
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use jsonwebtoken::{
+  jwk::{AlgorithmParameters, Jwk, JwkSet},
+  Algorithm, DecodingKey, Validation,
+};
+use serde::Deserialize;
+
+use crate::{
+  application::services::security::{
+    external_identity_policy::ExternalProvider, identity_provider::IdentityProviderClient,
+  },
+  domain::{entities::external_identity::ExternalIdToken, error::AppError},
+};
+
+use super::http_client::HttpClient;
+
+/// Talks to the token endpoints of the external providers over HTTP, sending
+/// the client credentials with `client_secret_basic`.
+pub struct HttpIdentityProviderClient {
+  pub http: HttpClient,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+  id_token: String,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Audience {
+  One(String),
+  Many(Vec<String>),
+}
+
+/// Some providers send booleans as strings.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Flag {
+  Bool(bool),
+  Text(String),
+}
+
+#[derive(Deserialize)]
+struct IdTokenClaims {
+  iss: String,
+  aud: Audience,
+  exp: i64,
+  nonce: Option<String>,
+  sub: String,
+  email: Option<String>,
+  email_verified: Option<Flag>,
+  name: Option<String>,
+  preferred_username: Option<String>,
+}
+
+/// Algorithms a key of the set may sign with, never a symmetric one as the
+/// key set is public.
+fn key_algorithms(jwk: &Jwk) -> &'static [Algorithm] {
+  match jwk.algorithm {
+    AlgorithmParameters::RSA(_) => &[
+      Algorithm::RS256,
+      Algorithm::RS384,
+      Algorithm::RS512,
+      Algorithm::PS256,
+      Algorithm::PS384,
+      Algorithm::PS512,
+    ],
+    AlgorithmParameters::EllipticCurve(_) => &[Algorithm::ES256, Algorithm::ES384],
+    AlgorithmParameters::OctetKeyPair(_) => &[Algorithm::EdDSA],
+    AlgorithmParameters::OctetKey(_) => &[],
+  }
+}
+
+/// The signature must verify with the key of the provider's key set named by
+/// the `kid` of the token, or its only key when the token names none. The
+/// claims are checked by the caller.
+fn decode_id_token(id_token: &str, jwks: &JwkSet) -> Result<ExternalIdToken, AppError> {
+  let invalid_token =
+    || AppError::unauthenticated("identity provider returned an invalid ID token");
+
+  let header = jsonwebtoken::decode_header(id_token).map_err(|_| invalid_token())?;
+  let jwk = match &header.kid {
+    Some(kid) => jwks.find(kid),
+    None if jwks.keys.len() == 1 => jwks.keys.first(),
+    None => None,
+  }
+  .ok_or_else(invalid_token)?;
+  if !key_algorithms(jwk).contains(&header.alg)
+    || jwk
+      .common
+      .algorithm
+      .is_some_and(|algorithm| algorithm != header.alg)
+  {
+    return Err(invalid_token());
+  }
+
+  let key = DecodingKey::from_jwk(jwk).map_err(|_| invalid_token())?;
+  let mut validation = Validation::new(header.alg);
+  validation.validate_exp = false;
+  validation.required_spec_claims.clear();
+  let claims = jsonwebtoken::decode::<IdTokenClaims>(id_token, &key, &validation)
+    .map_err(|_| invalid_token())?
+    .claims;
+
+  Ok(ExternalIdToken {
+    iss: claims.iss,
+    aud: match claims.aud {
+      Audience::One(aud) => vec![aud],
+      Audience::Many(aud) => aud,
+    },
+    exp: claims.exp,
+    nonce: claims.nonce,
+    sub: claims.sub,
+    email: claims.email,
+    email_verified: match claims.email_verified {
+      Some(Flag::Bool(verified)) => verified,
+      Some(Flag::Text(verified)) => verified == "true",
+      None => false,
+    },
+    name: claims.name,
+    preferred_username: claims.preferred_username,
+  })
+}
+
+impl HttpIdentityProviderClient {
+  async fn key_set(&self, provider: &ExternalProvider) -> Result<JwkSet, AppError> {
+    let response = self.http.get(&provider.jwks_uri).await?;
+
+    if response.status != 200 {
+      return Err(AppError::internal(format!(
+        "identity provider {} answered {} for its key set",
+        provider.name, response.status
+      )));
+    }
+
+    serde_json::from_slice(&response.body).map_err(|error| {
+      AppError::internal(format!(
+        "invalid key set from identity provider {}: {}",
+        provider.name, error
+      ))
+    })
+  }
+}
+
+#[async_trait]
+impl IdentityProviderClient for HttpIdentityProviderClient {
+  async fn exchange_code(
+    &self,
+    provider: &ExternalProvider,
+    code: &str,
+    code_verifier: &str,
+  ) -> Result<ExternalIdToken, AppError> {
+    let body = [
+      ("grant_type", "authorization_code"),
+      ("code", code),
+      ("redirect_uri", provider.redirect_uri.as_str()),
+      ("code_verifier", code_verifier),
+    ]
+    .iter()
+    .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+    .collect::<Vec<_>>()
+    .join("&");
+    let authorization = format!(
+      "Basic {}",
+      STANDARD.encode(format!(
+        "{}:{}",
+        urlencoding::encode(&provider.client_id),
+        urlencoding::encode(&provider.client_secret)
+      ))
+    );
+
+    let response = self
+      .http
+      .post_form(
+        &provider.token_endpoint,
+        &[("Authorization", &authorization)],
+        &body,
+      )
+      .await?;
+
+    if response.status != 200 {
+      return Err(AppError::unauthenticated(format!(
+        "identity provider {} refused the authorization code",
+        provider.name
+      )));
+    }
+
+    let tokens: TokenResponse = serde_json::from_slice(&response.body).map_err(|error| {
+      AppError::internal(format!(
+        "invalid token response from identity provider {}: {}",
+        provider.name, error
+      ))
+    })?;
+
+    // fetched for every sign-in, so rotated keys are picked up right away
+    let jwks = self.key_set(provider).await?;
+
+    decode_id_token(&tokens.id_token, &jwks)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::adapter::services::jwt_keys::{
+    fixtures::{rsa_key, rsa_rotated_key, RSA_KID, RSA_ROTATED_KID},
+    JWTKeys,
+  };
+  use jsonwebtoken::Header;
+  use serde_json::json;
+
+  fn keys() -> JWTKeys {
+    JWTKeys::from_pem(RSA_KID, &[rsa_key()]).unwrap()
+  }
+
+  fn id_token(claims: serde_json::Value) -> String {
+    let keys = keys();
+
+    jsonwebtoken::encode(&keys.header(), &claims, keys.encoding_key()).unwrap()
+  }
+
+  #[test]
+  fn test_decode_id_token() {
+    let token = id_token(json!({
+      "iss": "https://idp.example.com",
+      "aud": "our-client",
+      "exp": 1700000000,
+      "nonce": "n-0S6_WzA2Mj",
+      "sub": "248289761001",
+      "email": "johndoe@company.com",
+      "email_verified": true,
+      "name": "John Doe",
+    }));
+
+    assert_eq!(
+      decode_id_token(&token, &keys().jwks()).unwrap(),
+      ExternalIdToken {
+        iss: String::from("https://idp.example.com"),
+        aud: vec![String::from("our-client")],
+        exp: 1700000000,
+        nonce: Some(String::from("n-0S6_WzA2Mj")),
+        sub: String::from("248289761001"),
+        email: Some(String::from("johndoe@company.com")),
+        email_verified: true,
+        name: Some(String::from("John Doe")),
+        preferred_username: None,
+      }
+    );
+  }
+
+  #[test]
+  fn test_decode_id_token_with_audience_list_and_text_flag() {
+    let token = id_token(json!({
+      "iss": "https://idp.example.com",
+      "aud": ["our-client", "other"],
+      "exp": 1700000000,
+      "sub": "248289761001",
+      "email_verified": "true",
+    }));
+
+    let claims = decode_id_token(&token, &keys().jwks()).unwrap();
+
+    assert_eq!(claims.aud, vec!["our-client", "other"]);
+    assert!(claims.email_verified);
+  }
+
+  #[test]
+  fn test_decode_malformed_id_token() {
+    let error = decode_id_token("not-a-jwt", &keys().jwks()).unwrap_err();
+
+    assert_eq!(
+      error.message,
+      "identity provider returned an invalid ID token"
+    );
+  }
+
+  #[test]
+  fn test_decode_id_token_signed_by_another_key() {
+    let other_keys = JWTKeys::from_pem(RSA_ROTATED_KID, &[rsa_rotated_key()]).unwrap();
+    let token = jsonwebtoken::encode(
+      &Header {
+        kid: Some(RSA_KID.to_string()),
+        ..other_keys.header()
+      },
+      &json!({ "iss": "https://idp.example.com", "aud": "our-client", "exp": 1700000000, "sub": "248289761001" }),
+      other_keys.encoding_key(),
+    )
+    .unwrap();
+
+    let error = decode_id_token(&token, &keys().jwks()).unwrap_err();
+
+    assert_eq!(
+      error.message,
+      "identity provider returned an invalid ID token"
+    );
+  }
+
+  #[test]
+  fn test_decode_id_token_of_unknown_key() {
+    let token = id_token(json!({ "sub": "248289761001" }));
+    let other_jwks = JWTKeys::from_pem(RSA_ROTATED_KID, &[rsa_rotated_key()])
+      .unwrap()
+      .jwks();
+
+    assert!(decode_id_token(&token, &other_jwks).is_err());
+  }
+
+  #[test]
+  fn test_decode_id_token_with_symmetric_algorithm() {
+    let jwks = keys().jwks();
+    let modulus = match &jwks.keys[0].algorithm {
+      AlgorithmParameters::RSA(rsa) => rsa.n.clone(),
+      _ => unreachable!(),
+    };
+    // HMAC keyed with the public key, which anybody can fetch
+    let token = jsonwebtoken::encode(
+      &Header {
+        kid: Some(RSA_KID.to_string()),
+        ..Header::new(Algorithm::HS256)
+      },
+      &json!({ "sub": "248289761001" }),
+      &jsonwebtoken::EncodingKey::from_secret(modulus.as_bytes()),
+    )
+    .unwrap();
+
+    assert!(decode_id_token(&token, &jwks).is_err());
+  }
+}
@@ -1,29 +1,50 @@
 use crate::{
-  application::services::security::token_service::{Token, TokenService},
-  domain::error::AppError,
+  application::services::security::{
+    token_revocation::TokenRevocationStore,
+    token_service::{IdToken, Token, TokenService},
+  },
+  domain::{entities::role::Grants, error::AppError},
 };
-use jsonwebtoken::{get_current_timestamp, DecodingKey, EncodingKey, Header, Validation};
+use async_trait::async_trait;
+use jsonwebtoken::{get_current_timestamp, Validation};
+use uuid::Uuid;
 
-pub struct JWTService<'a> {
+use super::jwt_keys::JWTKeys;
+
+pub struct JWTService<'a, S: TokenRevocationStore> {
   pub iss: String,
-  pub key: &'a [u8],
+  pub keys: &'a JWTKeys,
+  pub revocation_store: S,
+  /// Tokens without `ver`, issued by releases that did not version every
+  /// token, are accepted until this timestamp.
+  pub unversioned_until: usize,
 }
 
-impl TokenService for JWTService<'_> {
-  fn encode(&self, sub: String, aud: String, exp_min: u64) -> Result<String, AppError> {
+#[async_trait]
+impl<S: TokenRevocationStore> TokenService for JWTService<'_, S> {
+  fn encode(
+    &self,
+    sub: String,
+    aud: String,
+    grants: Grants,
+    session_id: Option<String>,
+    version: i32,
+    exp_min: u64,
+  ) -> Result<String, AppError> {
     let user_token = Token {
       sub,
       iss: self.iss.clone(),
       aud,
       iat: get_current_timestamp() as usize,
-      exp: (get_current_timestamp() + 1000 * 60 * exp_min) as usize,
+      exp: (get_current_timestamp() + 60 * exp_min) as usize,
+      jti: Uuid::new_v4().to_string(),
+      roles: grants.roles,
+      scope: grants.permissions.join(" "),
+      sid: session_id,
+      ver: Some(version),
     };
 
-    match jsonwebtoken::encode(
-      &Header::default(),
-      &user_token,
-      &EncodingKey::from_secret(self.key),
-    ) {
+    match jsonwebtoken::encode(&self.keys.header(), &user_token, self.keys.encoding_key()) {
       Ok(token) => Ok(token),
       Err(error) => Err(AppError::internal(format!(
         "failed to encode token :{}",
@@ -32,37 +53,108 @@ impl TokenService for JWTService<'_> {
     }
   }
 
-  fn decode(&self, token: &str) -> Result<Token, AppError> {
-    match jsonwebtoken::decode::<Token>(
-      token,
-      &DecodingKey::from_secret(self.key),
-      &Validation::default(),
-    ) {
-      Ok(token) => Ok(token.claims),
-      Err(_) => Err(AppError::unauthenticated("expired or invalid JWT token")),
+  fn encode_id_token(
+    &self,
+    sub: String,
+    aud: String,
+    nonce: Option<String>,
+    exp_min: u64,
+  ) -> Result<String, AppError> {
+    let id_token = IdToken {
+      sub,
+      iss: self.iss.clone(),
+      aud,
+      iat: get_current_timestamp() as usize,
+      exp: (get_current_timestamp() + 60 * exp_min) as usize,
+      nonce,
+    };
+
+    jsonwebtoken::encode(&self.keys.header(), &id_token, self.keys.encoding_key())
+      .map_err(|error| AppError::internal(format!("failed to encode ID token :{}", error)))
+  }
+
+  async fn decode(&self, token: &str) -> Result<Token, AppError> {
+    let invalid_token = || AppError::unauthenticated("expired or invalid JWT token");
+
+    let header = jsonwebtoken::decode_header(token).map_err(|_| invalid_token())?;
+    let (key, algorithm) = self.keys.decoding_key(&header).ok_or_else(invalid_token)?;
+
+    // a token signed with a shared key by another issuer is not ours
+    let mut validation = Validation::new(algorithm);
+    validation.set_issuer(&[&self.iss]);
+
+    let token = jsonwebtoken::decode::<Token>(token, key, &validation)
+      .map_err(|_| invalid_token())?
+      .claims;
+
+    if self.revocation_store.is_revoked(&token.jti).await? {
+      return Err(AppError::unauthenticated("JWT token has been revoked"));
+    }
+
+    // sessions are revoked by id, which shares the store with token ids
+    if let Some(sid) = &token.sid {
+      if self.revocation_store.is_revoked(sid).await? {
+        return Err(AppError::unauthenticated("session has been revoked"));
+      }
+    }
+
+    match token.ver {
+      Some(ver) => {
+        if self.revocation_store.token_version(&token.sub).await? != Some(ver) {
+          return Err(AppError::unauthenticated(
+            "JWT token issued before the profile tokens were revoked",
+          ));
+        }
+      }
+      None => {
+        if get_current_timestamp() as usize >= self.unversioned_until {
+          return Err(AppError::unauthenticated("JWT token has no version"));
+        }
+      }
     }
+
+    Ok(token)
+  }
+
+  async fn revoke(&self, token_id: &str, expires_at: usize) -> Result<(), AppError> {
+    self.revocation_store.revoke(token_id, expires_at).await
   }
 }
 
 #[cfg(test)]
 mod test {
   use super::*;
+  use crate::adapter::services::{
+    jwt_keys::{fixtures::*, JWTKeyPem},
+    token_revocation::TokenRevocationStoreMemory,
+  };
+  use jsonwebtoken::{DecodingKey, EncodingKey, Header};
 
   const ISS: &str = "my_app";
   const KEY: &[u8] = b"token_key";
   const SUB: &str = "some_id";
   const AUD: &str = "service_name";
   const EXP_MIN: u64 = 120;
+  const JTI: &str = "0b8e1e9a-5f4c-4f7b-9d0a-3b1f0a6c2e71";
 
   #[test]
   fn encode() {
     let jwt_service = JWTService {
       iss: ISS.to_owned(),
-      key: KEY,
+      keys: &JWTKeys::from_secret(KEY),
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
     };
 
     let token = jwt_service
-      .encode(SUB.to_owned(), AUD.to_owned(), EXP_MIN)
+      .encode(
+        SUB.to_owned(),
+        AUD.to_owned(),
+        Grants::default(),
+        None,
+        0,
+        EXP_MIN,
+      )
       .expect("encode failed");
 
     let token_decoded = jsonwebtoken::decode::<Token>(
@@ -75,17 +167,83 @@ mod test {
 
     assert_eq!(token_decoded.sub, SUB);
     assert_eq!(token_decoded.aud, AUD);
-    assert!(token_decoded.exp > (get_current_timestamp() + 1000 * 60 * (EXP_MIN - 1)) as usize);
+    assert!(token_decoded.exp > (get_current_timestamp() + 60 * (EXP_MIN - 1)) as usize);
   }
 
   #[test]
-  fn decode() {
+  fn encode_with_grants() {
+    let jwt_service = JWTService {
+      iss: ISS.to_owned(),
+      keys: &JWTKeys::from_secret(KEY),
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    };
+
+    let grants = Grants {
+      roles: vec![String::from("admin")],
+      permissions: vec![
+        String::from("oauth_clients:admin"),
+        String::from("users:admin"),
+      ],
+    };
+    let token = jwt_service
+      .encode(SUB.to_owned(), AUD.to_owned(), grants, None, 0, EXP_MIN)
+      .expect("encode failed");
+
+    let token_decoded = jsonwebtoken::decode::<Token>(
+      &token,
+      &DecodingKey::from_secret(KEY),
+      &Validation::default(),
+    )
+    .expect("decode failed")
+    .claims;
+
+    assert_eq!(token_decoded.roles, vec![String::from("admin")]);
+    assert_eq!(token_decoded.scope, "oauth_clients:admin users:admin");
+  }
+
+  #[test]
+  fn encode_id_token_with_nonce() {
+    let jwt_service = JWTService {
+      iss: ISS.to_owned(),
+      keys: &JWTKeys::from_secret(KEY),
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    };
+
+    let token = jwt_service
+      .encode_id_token(
+        SUB.to_owned(),
+        AUD.to_owned(),
+        Some(String::from("n-0S6_WzA2Mj")),
+        EXP_MIN,
+      )
+      .expect("encode failed");
+
+    let mut validation = Validation::default();
+    validation.set_audience(&[AUD]);
+    validation.set_issuer(&[ISS]);
+    let token_decoded =
+      jsonwebtoken::decode::<IdToken>(&token, &DecodingKey::from_secret(KEY), &validation)
+        .expect("decode failed")
+        .claims;
+
+    assert_eq!(token_decoded.sub, SUB);
+    assert_eq!(token_decoded.nonce, Some(String::from("n-0S6_WzA2Mj")));
+    assert!(token_decoded.exp > (get_current_timestamp() + 60 * (EXP_MIN - 1)) as usize);
+  }
+
+  #[tokio::test]
+  async fn decode() {
     let user_token = Token {
       sub: SUB.to_owned(),
       iss: ISS.to_owned(),
       aud: AUD.to_owned(),
       iat: get_current_timestamp() as usize,
-      exp: (get_current_timestamp() + 1000 * 60 * EXP_MIN) as usize,
+      exp: (get_current_timestamp() + 60 * EXP_MIN) as usize,
+      jti: JTI.to_owned(),
+      ver: Some(0),
+      ..Default::default()
     };
 
     let token = jsonwebtoken::encode(
@@ -97,24 +255,64 @@ mod test {
 
     let jwt_service = JWTService {
       iss: ISS.to_owned(),
-      key: KEY,
+      keys: &JWTKeys::from_secret(KEY),
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
     };
 
-    let token_decoded = jwt_service.decode(&token).expect("decode failed");
+    let token_decoded = jwt_service.decode(&token).await.expect("decode failed");
 
     assert_eq!(token_decoded.sub, SUB);
     assert_eq!(token_decoded.aud, AUD);
-    assert!(token_decoded.exp > (get_current_timestamp() + 1000 * 60 * (EXP_MIN - 1)) as usize);
+    assert!(token_decoded.exp > (get_current_timestamp() + 60 * (EXP_MIN - 1)) as usize);
   }
 
-  #[test]
-  fn decode_expired_token() {
+  #[tokio::test]
+  async fn decode_token_of_other_issuer() {
+    let user_token = Token {
+      sub: SUB.to_owned(),
+      iss: String::from("other_app"),
+      aud: AUD.to_owned(),
+      iat: get_current_timestamp() as usize,
+      exp: (get_current_timestamp() + 60 * EXP_MIN) as usize,
+      jti: JTI.to_owned(),
+      ver: Some(0),
+      ..Default::default()
+    };
+
+    let token = jsonwebtoken::encode(
+      &Header::default(),
+      &user_token,
+      &EncodingKey::from_secret(KEY),
+    )
+    .expect("fail to build an encode token to test the encode function");
+
+    let jwt_service = JWTService {
+      iss: ISS.to_owned(),
+      keys: &JWTKeys::from_secret(KEY),
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    };
+
+    match jwt_service.decode(&token).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::unauthenticated("expired or invalid JWT token")
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn decode_expired_token() {
     let user_token = Token {
       sub: SUB.to_owned(),
       iss: ISS.to_owned(),
       aud: AUD.to_owned(),
       iat: get_current_timestamp() as usize,
-      exp: (get_current_timestamp() - 1000 * 60 * EXP_MIN) as usize,
+      exp: (get_current_timestamp() - 60 * EXP_MIN) as usize,
+      jti: JTI.to_owned(),
+      ..Default::default()
     };
 
     let token = jsonwebtoken::encode(
@@ -126,10 +324,338 @@ mod test {
 
     let jwt_service = JWTService {
       iss: ISS.to_owned(),
-      key: KEY,
+      keys: &JWTKeys::from_secret(KEY),
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    };
+
+    match jwt_service.decode(&token).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::unauthenticated("expired or invalid JWT token")
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn decode_revoked_token() {
+    let jwt_service = JWTService {
+      iss: ISS.to_owned(),
+      keys: &JWTKeys::from_secret(KEY),
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    };
+
+    let token = jwt_service
+      .encode(
+        SUB.to_owned(),
+        AUD.to_owned(),
+        Grants::default(),
+        None,
+        0,
+        EXP_MIN,
+      )
+      .expect("encode failed");
+    let token_decoded = jwt_service.decode(&token).await.expect("decode failed");
+
+    jwt_service
+      .revoke(&token_decoded.jti, token_decoded.exp)
+      .await
+      .expect("revoke failed");
+
+    match jwt_service.decode(&token).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::unauthenticated("JWT token has been revoked")
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn decode_token_of_revoked_session() {
+    const SID: &str = "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed";
+
+    let jwt_service = JWTService {
+      iss: ISS.to_owned(),
+      keys: &JWTKeys::from_secret(KEY),
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    };
+
+    let token = jwt_service
+      .encode(
+        SUB.to_owned(),
+        AUD.to_owned(),
+        Grants::default(),
+        Some(SID.to_owned()),
+        0,
+        EXP_MIN,
+      )
+      .expect("encode failed");
+    let token_decoded = jwt_service.decode(&token).await.expect("decode failed");
+
+    assert_eq!(token_decoded.sid, Some(SID.to_owned()));
+
+    jwt_service
+      .revoke(SID, token_decoded.exp)
+      .await
+      .expect("revoke failed");
+
+    match jwt_service.decode(&token).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error, AppError::unauthenticated("session has been revoked")),
+    }
+  }
+
+  #[tokio::test]
+  async fn decode_token_of_previous_version() {
+    let jwt_service = JWTService {
+      iss: ISS.to_owned(),
+      keys: &JWTKeys::from_secret(KEY),
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    };
+    jwt_service.revocation_store.set_token_version(SUB, 2);
+
+    let encode = |version| {
+      jwt_service
+        .encode(
+          SUB.to_owned(),
+          AUD.to_owned(),
+          Grants::default(),
+          Some(JTI.to_owned()),
+          version,
+          EXP_MIN,
+        )
+        .expect("encode failed")
+    };
+
+    let token_decoded = jwt_service.decode(&encode(2)).await.expect("decode failed");
+
+    assert_eq!(token_decoded.ver, Some(2));
+
+    match jwt_service.decode(&encode(1)).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::unauthenticated("JWT token issued before the profile tokens were revoked")
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn decode_token_without_version() {
+    let user_token = Token {
+      sub: SUB.to_owned(),
+      iss: ISS.to_owned(),
+      aud: AUD.to_owned(),
+      iat: get_current_timestamp() as usize,
+      exp: (get_current_timestamp() + 60 * EXP_MIN) as usize,
+      jti: JTI.to_owned(),
+      ..Default::default()
+    };
+    let token = jsonwebtoken::encode(
+      &Header::default(),
+      &user_token,
+      &EncodingKey::from_secret(KEY),
+    )
+    .expect("fail to build an encode token to test the decode function");
+    let keys = JWTKeys::from_secret(KEY);
+    let jwt_service = |unversioned_until| JWTService {
+      iss: ISS.to_owned(),
+      keys: &keys,
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until,
+    };
+
+    let token_decoded = jwt_service(get_current_timestamp() as usize + 60)
+      .decode(&token)
+      .await
+      .expect("decode failed");
+
+    assert_eq!(token_decoded.ver, None);
+
+    match jwt_service(get_current_timestamp() as usize)
+      .decode(&token)
+      .await
+    {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error, AppError::unauthenticated("JWT token has no version")),
+    }
+  }
+
+  #[test]
+  fn encode_unique_jti() {
+    let jwt_service = JWTService {
+      iss: ISS.to_owned(),
+      keys: &JWTKeys::from_secret(KEY),
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    };
+
+    let first = jwt_service
+      .encode(
+        SUB.to_owned(),
+        AUD.to_owned(),
+        Grants::default(),
+        None,
+        0,
+        EXP_MIN,
+      )
+      .expect("encode failed");
+    let second = jwt_service
+      .encode(
+        SUB.to_owned(),
+        AUD.to_owned(),
+        Grants::default(),
+        None,
+        0,
+        EXP_MIN,
+      )
+      .expect("encode failed");
+
+    let jti = |token: &str| {
+      jsonwebtoken::decode::<Token>(
+        token,
+        &DecodingKey::from_secret(KEY),
+        &Validation::default(),
+      )
+      .expect("decode failed")
+      .claims
+      .jti
+    };
+
+    assert_ne!(jti(&first), jti(&second));
+  }
+
+  #[tokio::test]
+  async fn encode_and_decode_with_rsa_key() {
+    let keys = JWTKeys::from_pem(RSA_KID, &[rsa_key()]).unwrap();
+    let jwt_service = JWTService {
+      iss: ISS.to_owned(),
+      keys: &keys,
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    };
+
+    let token = jwt_service
+      .encode(
+        SUB.to_owned(),
+        AUD.to_owned(),
+        Grants::default(),
+        None,
+        0,
+        EXP_MIN,
+      )
+      .expect("encode failed");
+    let header = jsonwebtoken::decode_header(&token).unwrap();
+    let token_decoded = jwt_service.decode(&token).await.expect("decode failed");
+
+    assert_eq!(header.alg, jsonwebtoken::Algorithm::RS256);
+    assert_eq!(header.kid, Some(RSA_KID.to_owned()));
+    assert_eq!(token_decoded.sub, SUB);
+  }
+
+  #[tokio::test]
+  async fn encode_and_decode_with_ed25519_key() {
+    let keys = JWTKeys::from_pem(ED25519_KID, &[ed25519_key()]).unwrap();
+    let jwt_service = JWTService {
+      iss: ISS.to_owned(),
+      keys: &keys,
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    };
+
+    let token = jwt_service
+      .encode(
+        SUB.to_owned(),
+        AUD.to_owned(),
+        Grants::default(),
+        None,
+        0,
+        EXP_MIN,
+      )
+      .expect("encode failed");
+    let header = jsonwebtoken::decode_header(&token).unwrap();
+    let token_decoded = jwt_service.decode(&token).await.expect("decode failed");
+
+    assert_eq!(header.alg, jsonwebtoken::Algorithm::EdDSA);
+    assert_eq!(header.kid, Some(ED25519_KID.to_owned()));
+    assert_eq!(token_decoded.sub, SUB);
+  }
+
+  #[tokio::test]
+  async fn decode_token_signed_with_rotated_key() {
+    let old_keys = JWTKeys::from_pem(RSA_ROTATED_KID, &[rsa_rotated_key()]).unwrap();
+    let old_token = JWTService {
+      iss: ISS.to_owned(),
+      keys: &old_keys,
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    }
+    .encode(
+      SUB.to_owned(),
+      AUD.to_owned(),
+      Grants::default(),
+      None,
+      0,
+      EXP_MIN,
+    )
+    .expect("encode failed");
+
+    let keys = JWTKeys::from_pem(
+      RSA_KID,
+      &[
+        rsa_key(),
+        JWTKeyPem {
+          private_pem: None,
+          ..rsa_rotated_key()
+        },
+      ],
+    )
+    .unwrap();
+    let jwt_service = JWTService {
+      iss: ISS.to_owned(),
+      keys: &keys,
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    };
+
+    let token_decoded = jwt_service.decode(&old_token).await.expect("decode failed");
+
+    assert_eq!(token_decoded.sub, SUB);
+  }
+
+  #[tokio::test]
+  async fn decode_token_signed_with_unknown_key() {
+    let other_keys = JWTKeys::from_pem(RSA_ROTATED_KID, &[rsa_rotated_key()]).unwrap();
+    let token = JWTService {
+      iss: ISS.to_owned(),
+      keys: &other_keys,
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
+    }
+    .encode(
+      SUB.to_owned(),
+      AUD.to_owned(),
+      Grants::default(),
+      None,
+      0,
+      EXP_MIN,
+    )
+    .expect("encode failed");
+
+    let keys = JWTKeys::from_pem(RSA_KID, &[rsa_key()]).unwrap();
+    let jwt_service = JWTService {
+      iss: ISS.to_owned(),
+      keys: &keys,
+      revocation_store: TokenRevocationStoreMemory::default(),
+      unversioned_until: 0,
     };
 
-    match jwt_service.decode(&token) {
+    match jwt_service.decode(&token).await {
       Ok(_) => panic!("should not be success"),
       Err(error) => assert_eq!(
         error,
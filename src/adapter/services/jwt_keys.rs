@@ -0,0 +1,322 @@
+use crate::domain::error::AppError;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{
+  jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, OctetKeyPairParameters,
+    OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+  },
+  Algorithm, DecodingKey, EncodingKey, Header,
+};
+use rsa::{
+  pkcs1::DecodeRsaPublicKey, pkcs8::DecodePublicKey, traits::PublicKeyParts, RsaPublicKey,
+};
+
+/// DER prefix of an Ed25519 SubjectPublicKeyInfo, followed by the 32 key bytes.
+const ED25519_SPKI_PREFIX: [u8; 12] = [
+  0x30, 0x2a, 0x30, 0x05, 0x06, 0x03, 0x2b, 0x65, 0x70, 0x03, 0x21, 0x00,
+];
+
+pub struct JWTKeyPem<'a> {
+  pub kid: &'a str,
+  pub algorithm: Algorithm,
+  pub private_pem: Option<&'a [u8]>,
+  pub public_pem: &'a [u8],
+}
+
+struct SigningKey {
+  kid: Option<String>,
+  algorithm: Algorithm,
+  key: EncodingKey,
+}
+
+struct VerificationKey {
+  kid: Option<String>,
+  algorithm: Algorithm,
+  key: DecodingKey,
+  jwk: Option<Jwk>,
+}
+
+pub struct JWTKeys {
+  signing: SigningKey,
+  verification: Vec<VerificationKey>,
+}
+
+impl JWTKeys {
+  pub fn from_secret(secret: &[u8]) -> Self {
+    JWTKeys {
+      signing: SigningKey {
+        kid: None,
+        algorithm: Algorithm::HS256,
+        key: EncodingKey::from_secret(secret),
+      },
+      verification: vec![VerificationKey {
+        kid: None,
+        algorithm: Algorithm::HS256,
+        key: DecodingKey::from_secret(secret),
+        jwk: None,
+      }],
+    }
+  }
+
+  /// Every key verifies tokens so rotated keys keep validating the tokens
+  /// they signed; only the key identified by `signing_kid` signs new ones.
+  pub fn from_pem(signing_kid: &str, keys: &[JWTKeyPem]) -> Result<Self, AppError> {
+    let mut signing = None;
+    let mut verification = Vec::with_capacity(keys.len());
+
+    for key in keys {
+      let jwk = public_jwk(key.kid, key.algorithm, key.public_pem)?;
+
+      verification.push(VerificationKey {
+        kid: Some(key.kid.to_string()),
+        algorithm: key.algorithm,
+        key: DecodingKey::from_jwk(&jwk).map_err(|error| invalid_key(key.kid, error))?,
+        jwk: Some(jwk),
+      });
+
+      if key.kid == signing_kid {
+        let private_pem = key
+          .private_pem
+          .ok_or_else(|| AppError::internal(format!("JWT key {}: missing private key", key.kid)))?;
+
+        let encoding_key = match key.algorithm {
+          Algorithm::RS256 => EncodingKey::from_rsa_pem(private_pem),
+          Algorithm::EdDSA => EncodingKey::from_ed_pem(private_pem),
+          algorithm => return Err(unsupported_algorithm(key.kid, algorithm)),
+        }
+        .map_err(|error| invalid_key(key.kid, error))?;
+
+        signing = Some(SigningKey {
+          kid: Some(key.kid.to_string()),
+          algorithm: key.algorithm,
+          key: encoding_key,
+        });
+      }
+    }
+
+    let signing = signing
+      .ok_or_else(|| AppError::internal(format!("JWT signing key {} not found", signing_kid)))?;
+
+    Ok(JWTKeys {
+      signing,
+      verification,
+    })
+  }
+
+  pub fn header(&self) -> Header {
+    Header {
+      kid: self.signing.kid.clone(),
+      ..Header::new(self.signing.algorithm)
+    }
+  }
+
+  pub fn encoding_key(&self) -> &EncodingKey {
+    &self.signing.key
+  }
+
+  /// The header algorithm must match the one configured for the key, so a
+  /// token can not pick a weaker algorithm than the key was issued for.
+  pub fn decoding_key(&self, header: &Header) -> Option<(&DecodingKey, Algorithm)> {
+    self
+      .verification
+      .iter()
+      .find(|key| key.kid == header.kid && key.algorithm == header.alg)
+      .map(|key| (&key.key, key.algorithm))
+  }
+
+  pub fn jwks(&self) -> JwkSet {
+    JwkSet {
+      keys: self
+        .verification
+        .iter()
+        .filter_map(|key| key.jwk.clone())
+        .collect(),
+    }
+  }
+}
+
+fn public_jwk(kid: &str, algorithm: Algorithm, public_pem: &[u8]) -> Result<Jwk, AppError> {
+  let public_pem = std::str::from_utf8(public_pem).map_err(|error| invalid_key(kid, error))?;
+
+  let parameters = match algorithm {
+    Algorithm::RS256 => {
+      let public_key = RsaPublicKey::from_public_key_pem(public_pem)
+        .or_else(|_| RsaPublicKey::from_pkcs1_pem(public_pem))
+        .map_err(|error| invalid_key(kid, error))?;
+
+      AlgorithmParameters::RSA(RSAKeyParameters {
+        key_type: RSAKeyType::RSA,
+        n: URL_SAFE_NO_PAD.encode(public_key.n().to_bytes_be()),
+        e: URL_SAFE_NO_PAD.encode(public_key.e().to_bytes_be()),
+      })
+    }
+    Algorithm::EdDSA => {
+      let der = pem::parse(public_pem).map_err(|error| invalid_key(kid, error))?;
+      let public_key = der
+        .contents
+        .strip_prefix(&ED25519_SPKI_PREFIX)
+        .filter(|public_key| public_key.len() == 32)
+        .ok_or_else(|| AppError::internal(format!("JWT key {}: not an Ed25519 public key", kid)))?;
+
+      AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+        key_type: OctetKeyPairType::OctetKeyPair,
+        curve: EllipticCurve::Ed25519,
+        x: URL_SAFE_NO_PAD.encode(public_key),
+      })
+    }
+    algorithm => return Err(unsupported_algorithm(kid, algorithm)),
+  };
+
+  Ok(Jwk {
+    common: CommonParameters {
+      public_key_use: Some(PublicKeyUse::Signature),
+      algorithm: Some(algorithm),
+      key_id: Some(kid.to_string()),
+      ..Default::default()
+    },
+    algorithm: parameters,
+  })
+}
+
+fn invalid_key(kid: &str, error: impl std::fmt::Display) -> AppError {
+  AppError::internal(format!("JWT key {}: {}", kid, error))
+}
+
+fn unsupported_algorithm(kid: &str, algorithm: Algorithm) -> AppError {
+  AppError::internal(format!(
+    "JWT key {}: unsupported algorithm {:?}",
+    kid, algorithm
+  ))
+}
+
+#[cfg(test)]
+pub mod fixtures {
+  use super::*;
+
+  pub const RSA_KID: &str = "rsa-2023-11";
+  pub const RSA_ROTATED_KID: &str = "rsa-2023-10";
+  pub const ED25519_KID: &str = "ed25519-2023-11";
+
+  pub const RSA_PRIVATE: &[u8] = include_bytes!("fixtures/rsa_private.pem");
+  pub const RSA_PUBLIC: &[u8] = include_bytes!("fixtures/rsa_public.pem");
+  pub const RSA_ROTATED_PRIVATE: &[u8] = include_bytes!("fixtures/rsa_rotated_private.pem");
+  pub const RSA_ROTATED_PUBLIC: &[u8] = include_bytes!("fixtures/rsa_rotated_public.pem");
+  pub const ED25519_PRIVATE: &[u8] = include_bytes!("fixtures/ed25519_private.pem");
+  pub const ED25519_PUBLIC: &[u8] = include_bytes!("fixtures/ed25519_public.pem");
+
+  pub fn rsa_key() -> JWTKeyPem<'static> {
+    JWTKeyPem {
+      kid: RSA_KID,
+      algorithm: Algorithm::RS256,
+      private_pem: Some(RSA_PRIVATE),
+      public_pem: RSA_PUBLIC,
+    }
+  }
+
+  pub fn rsa_rotated_key() -> JWTKeyPem<'static> {
+    JWTKeyPem {
+      kid: RSA_ROTATED_KID,
+      algorithm: Algorithm::RS256,
+      private_pem: Some(RSA_ROTATED_PRIVATE),
+      public_pem: RSA_ROTATED_PUBLIC,
+    }
+  }
+
+  pub fn ed25519_key() -> JWTKeyPem<'static> {
+    JWTKeyPem {
+      kid: ED25519_KID,
+      algorithm: Algorithm::EdDSA,
+      private_pem: Some(ED25519_PRIVATE),
+      public_pem: ED25519_PUBLIC,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{fixtures::*, *};
+
+  #[test]
+  fn test_keys_from_secret() {
+    let keys = JWTKeys::from_secret(b"token_key");
+
+    assert_eq!(keys.header().alg, Algorithm::HS256);
+    assert_eq!(keys.header().kid, None);
+    assert!(keys.decoding_key(&Header::default()).is_some());
+    assert!(keys.jwks().keys.is_empty());
+  }
+
+  #[test]
+  fn test_keys_from_pem() {
+    let keys = JWTKeys::from_pem(RSA_KID, &[rsa_key(), ed25519_key()]).unwrap();
+
+    assert_eq!(keys.header().alg, Algorithm::RS256);
+    assert_eq!(keys.header().kid, Some(RSA_KID.to_string()));
+
+    let jwks = keys.jwks();
+    assert_eq!(jwks.keys.len(), 2);
+    assert!(matches!(
+      jwks.find(RSA_KID).unwrap().algorithm,
+      AlgorithmParameters::RSA(_)
+    ));
+    assert!(matches!(
+      jwks.find(ED25519_KID).unwrap().algorithm,
+      AlgorithmParameters::OctetKeyPair(_)
+    ));
+  }
+
+  #[test]
+  fn test_decoding_key_requires_matching_algorithm() {
+    let keys = JWTKeys::from_pem(RSA_KID, &[rsa_key()]).unwrap();
+
+    let header = Header {
+      kid: Some(RSA_KID.to_string()),
+      ..Header::new(Algorithm::HS256)
+    };
+
+    assert!(keys.decoding_key(&header).is_none());
+    assert!(keys.decoding_key(&keys.header()).is_some());
+  }
+
+  #[test]
+  fn test_keys_from_pem_without_signing_key() {
+    let keys = JWTKeys::from_pem("unknown", &[rsa_key()]);
+
+    assert_eq!(
+      keys.err(),
+      Some(AppError::internal("JWT signing key unknown not found"))
+    );
+  }
+
+  #[test]
+  fn test_keys_from_pem_signing_key_without_private_key() {
+    let keys = JWTKeys::from_pem(
+      RSA_KID,
+      &[JWTKeyPem {
+        private_pem: None,
+        ..rsa_key()
+      }],
+    );
+
+    assert_eq!(
+      keys.err(),
+      Some(AppError::internal(format!(
+        "JWT key {}: missing private key",
+        RSA_KID
+      )))
+    );
+  }
+
+  #[test]
+  fn test_keys_from_pem_with_mismatched_key_type() {
+    let keys = JWTKeys::from_pem(
+      RSA_KID,
+      &[JWTKeyPem {
+        public_pem: ED25519_PUBLIC,
+        ..rsa_key()
+      }],
+    );
+
+    assert!(keys.is_err());
+  }
+}
@@ -1 +1,9 @@
+pub mod audit_log;
+pub mod email;
+pub mod http_client;
+pub mod identity_provider;
 pub mod jwt;
+pub mod jwt_keys;
+pub mod rate_limit;
+pub mod sms;
+pub mod token_revocation;
@@ -0,0 +1,241 @@
+use std::{
+  collections::HashMap,
+  sync::Mutex,
+  time::{Duration, Instant},
+};
+
+use crate::{
+  application::services::security::rate_limit::{RateLimit, RateLimitDecision, RateLimitStore},
+  domain::error::AppError,
+};
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres};
+
+/// Buckets kept before the full ones are dropped from memory.
+const MEMORY_BUCKETS_BEFORE_PURGE: usize = 1024;
+
+/// Buckets shared by every instance using the database.
+pub struct RateLimitStoreDB {
+  pub pool: Pool<Postgres>,
+}
+
+impl RateLimitStoreDB {
+  /// Deletes the buckets refilled to capacity, returning how many.
+  pub async fn purge_full_buckets(&self) -> Result<u64, AppError> {
+    let result =
+      sqlx::query!("DELETE FROM rate_limit_buckets WHERE full_at < (NOW() AT TIME ZONE 'UTC')")
+        .execute(&self.pool)
+        .await?;
+
+    Ok(result.rows_affected())
+  }
+}
+
+#[async_trait]
+impl RateLimitStore for RateLimitStoreDB {
+  async fn take(&self, key: &str, limit: &RateLimit) -> Result<RateLimitDecision, AppError> {
+    let mut transaction = self.pool.begin().await?;
+
+    // the bucket is created full when missing, so that concurrent requests
+    // for a new key all wait on the lock of the same row below
+    sqlx::query!(
+      "INSERT INTO rate_limit_buckets (key, tokens, updated_at, full_at)
+      VALUES ($1, $2, NOW() AT TIME ZONE 'UTC', NOW() AT TIME ZONE 'UTC')
+      ON CONFLICT (key) DO NOTHING",
+      key,
+      f64::from(limit.capacity)
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    let bucket = sqlx::query!(
+      r#"SELECT tokens,
+        EXTRACT(EPOCH FROM (NOW() AT TIME ZONE 'UTC') - updated_at)::DOUBLE PRECISION AS "elapsed_sec!",
+        full_at < (NOW() AT TIME ZONE 'UTC') AS "full!"
+      FROM rate_limit_buckets
+      WHERE key = $1
+      FOR UPDATE"#,
+      key
+    )
+    .fetch_one(&mut *transaction)
+    .await?;
+
+    // a bucket past full_at is as good as a new one, the others are left to
+    // `purge_full_buckets` so requests do not contend on them
+    let (tokens, decision) = if bucket.full {
+      limit.take(None, 0.0)
+    } else {
+      limit.take(Some(bucket.tokens), bucket.elapsed_sec)
+    };
+
+    sqlx::query!(
+      "UPDATE rate_limit_buckets
+      SET
+        tokens = $2,
+        updated_at = NOW() AT TIME ZONE 'UTC',
+        full_at = (NOW() AT TIME ZONE 'UTC') + make_interval(secs => $3)
+      WHERE key = $1",
+      key,
+      tokens,
+      decision.reset_sec as f64
+    )
+    .execute(&mut *transaction)
+    .await?;
+
+    transaction.commit().await?;
+
+    Ok(decision)
+  }
+}
+
+struct MemoryBucket {
+  tokens: f64,
+  updated_at: Instant,
+  full_at: Instant,
+}
+
+/// Buckets of a single instance.
+#[derive(Default)]
+pub struct RateLimitStoreMemory {
+  buckets: Mutex<HashMap<String, MemoryBucket>>,
+}
+
+#[async_trait]
+impl RateLimitStore for RateLimitStoreMemory {
+  async fn take(&self, key: &str, limit: &RateLimit) -> Result<RateLimitDecision, AppError> {
+    let now = Instant::now();
+    let mut buckets = self
+      .buckets
+      .lock()
+      .map_err(|_| AppError::internal("rate limit buckets are poisoned"))?;
+
+    if buckets.len() >= MEMORY_BUCKETS_BEFORE_PURGE {
+      buckets.retain(|_, bucket| bucket.full_at > now);
+    }
+
+    let (tokens, decision) = match buckets.get(key) {
+      Some(bucket) => limit.take(
+        Some(bucket.tokens),
+        now.duration_since(bucket.updated_at).as_secs_f64(),
+      ),
+      None => limit.take(None, 0.0),
+    };
+
+    buckets.insert(
+      key.to_string(),
+      MemoryBucket {
+        tokens,
+        updated_at: now,
+        full_at: now + Duration::from_secs(decision.reset_sec),
+      },
+    );
+
+    Ok(decision)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use futures_util::future::join_all;
+  use sqlx::PgPool;
+
+  const KEY: &str = "ip:203.0.113.7";
+  const OTHER_KEY: &str = "ip:198.51.100.23";
+  const LIMIT: RateLimit = RateLimit {
+    capacity: 2,
+    refill_per_min: 1,
+  };
+
+  #[sqlx::test]
+  async fn test_take_until_empty(pool: PgPool) -> sqlx::Result<()> {
+    let sut = RateLimitStoreDB { pool };
+
+    let first = sut.take(KEY, &LIMIT).await.unwrap();
+    let second = sut.take(KEY, &LIMIT).await.unwrap();
+    let third = sut.take(KEY, &LIMIT).await.unwrap();
+
+    assert!(first.allowed);
+    assert_eq!(first.remaining, 1);
+    assert!(second.allowed);
+    assert_eq!(second.remaining, 0);
+    assert!(!third.allowed);
+    assert!(third.retry_after_sec > 0 && third.retry_after_sec <= 60);
+    assert!(sut.take(OTHER_KEY, &LIMIT).await.unwrap().allowed);
+
+    Ok(())
+  }
+
+  #[sqlx::test]
+  async fn test_concurrent_takes_from_new_bucket(pool: PgPool) -> sqlx::Result<()> {
+    let sut = RateLimitStoreDB { pool };
+
+    let decisions = join_all((0..16).map(|_| sut.take(KEY, &LIMIT))).await;
+
+    let allowed = decisions
+      .into_iter()
+      .filter(|decision| decision.as_ref().unwrap().allowed)
+      .count();
+    assert_eq!(allowed, 2);
+
+    Ok(())
+  }
+
+  async fn insert_full_bucket(pool: &PgPool, key: &str) -> sqlx::Result<()> {
+    sqlx::query!(
+      "INSERT INTO rate_limit_buckets (key, tokens, updated_at, full_at)
+      VALUES ($1, 0, NOW() AT TIME ZONE 'UTC', (NOW() AT TIME ZONE 'UTC') - INTERVAL '1 second')",
+      key
+    )
+    .execute(pool)
+    .await?;
+
+    Ok(())
+  }
+
+  #[sqlx::test]
+  async fn test_take_resets_own_full_bucket(pool: PgPool) -> sqlx::Result<()> {
+    insert_full_bucket(&pool, KEY).await?;
+    insert_full_bucket(&pool, OTHER_KEY).await?;
+    let sut = RateLimitStoreDB { pool: pool.clone() };
+
+    let decision = sut.take(KEY, &LIMIT).await.unwrap();
+
+    assert!(decision.allowed);
+    assert_eq!(decision.remaining, 1);
+    let buckets = sqlx::query!(r#"SELECT COUNT(*) AS "count!" FROM rate_limit_buckets"#)
+      .fetch_one(&pool)
+      .await?;
+    assert_eq!(buckets.count, 2);
+
+    Ok(())
+  }
+
+  #[sqlx::test]
+  async fn test_purge_full_buckets(pool: PgPool) -> sqlx::Result<()> {
+    insert_full_bucket(&pool, OTHER_KEY).await?;
+    let sut = RateLimitStoreDB { pool: pool.clone() };
+    sut.take(KEY, &LIMIT).await.unwrap();
+
+    let purged = sut.purge_full_buckets().await.unwrap();
+
+    assert_eq!(purged, 1);
+    let buckets = sqlx::query!(r#"SELECT key FROM rate_limit_buckets"#)
+      .fetch_all(&pool)
+      .await?;
+    assert_eq!(buckets.len(), 1);
+    assert_eq!(buckets[0].key, KEY);
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_memory_store_take_until_empty() {
+    let sut = RateLimitStoreMemory::default();
+
+    assert!(sut.take(KEY, &LIMIT).await.unwrap().allowed);
+    assert!(sut.take(KEY, &LIMIT).await.unwrap().allowed);
+    assert!(!sut.take(KEY, &LIMIT).await.unwrap().allowed);
+    assert!(sut.take(OTHER_KEY, &LIMIT).await.unwrap().allowed);
+  }
+}
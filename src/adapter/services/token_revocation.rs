@@ -0,0 +1,201 @@
+use crate::{
+  application::services::security::token_revocation::TokenRevocationStore, domain::error::AppError,
+};
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use sqlx::{Pool, Postgres};
+
+pub struct TokenRevocationStoreDB {
+  pub pool: Pool<Postgres>,
+}
+
+#[async_trait]
+impl TokenRevocationStore for TokenRevocationStoreDB {
+  async fn revoke(&self, jti: &str, expires_at: usize) -> Result<(), AppError> {
+    let expires_at = NaiveDateTime::from_timestamp_opt(expires_at as i64, 0)
+      .ok_or_else(|| AppError::internal("invalid token expiration"))?;
+
+    sqlx::query!(
+      "INSERT INTO revoked_tokens (jti, expires_at)
+      VALUES ($1, $2)
+      ON CONFLICT (jti) DO NOTHING",
+      jti,
+      expires_at
+    )
+    .execute(&self.pool)
+    .await?;
+
+    // expired tokens are already rejected by the signature validation
+    sqlx::query!("DELETE FROM revoked_tokens WHERE expires_at < (NOW() AT TIME ZONE 'UTC')")
+      .execute(&self.pool)
+      .await?;
+
+    Ok(())
+  }
+
+  async fn is_revoked(&self, jti: &str) -> Result<bool, AppError> {
+    let revoked = sqlx::query!(
+      r#"SELECT EXISTS(SELECT 1 FROM revoked_tokens WHERE jti = $1) AS "revoked!""#,
+      jti
+    )
+    .fetch_one(&self.pool)
+    .await?;
+
+    Ok(revoked.revoked)
+  }
+
+  async fn token_version(&self, profile_id: &str) -> Result<Option<i32>, AppError> {
+    let user = sqlx::query!(
+      "SELECT users.token_version
+      FROM users
+      JOIN profiles ON profiles.user_id = users.id
+      WHERE profiles.id = $1",
+      profile_id
+    )
+    .fetch_optional(&self.pool)
+    .await?;
+
+    Ok(user.map(|user| user.token_version))
+  }
+}
+
+#[cfg(test)]
+#[derive(Default)]
+pub struct TokenRevocationStoreMemory {
+  revoked: std::sync::Mutex<std::collections::HashMap<String, usize>>,
+  /// Profiles not listed are at version 0.
+  token_versions: std::sync::Mutex<std::collections::HashMap<String, i32>>,
+}
+
+#[cfg(test)]
+impl TokenRevocationStoreMemory {
+  pub fn set_token_version(&self, profile_id: &str, version: i32) {
+    self
+      .token_versions
+      .lock()
+      .unwrap()
+      .insert(profile_id.to_string(), version);
+  }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl TokenRevocationStore for TokenRevocationStoreMemory {
+  async fn revoke(&self, jti: &str, expires_at: usize) -> Result<(), AppError> {
+    self
+      .revoked
+      .lock()
+      .map_err(|_| AppError::internal("token revocation store poisoned"))?
+      .insert(jti.to_string(), expires_at);
+
+    Ok(())
+  }
+
+  async fn is_revoked(&self, jti: &str) -> Result<bool, AppError> {
+    let revoked = self
+      .revoked
+      .lock()
+      .map_err(|_| AppError::internal("token revocation store poisoned"))?
+      .contains_key(jti);
+
+    Ok(revoked)
+  }
+
+  async fn token_version(&self, profile_id: &str) -> Result<Option<i32>, AppError> {
+    let version = self
+      .token_versions
+      .lock()
+      .map_err(|_| AppError::internal("token revocation store poisoned"))?
+      .get(profile_id)
+      .copied()
+      .unwrap_or_default();
+
+    Ok(Some(version))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use jsonwebtoken::get_current_timestamp;
+  use sqlx::PgPool;
+
+  const JTI: &str = "0b8e1e9a-5f4c-4f7b-9d0a-3b1f0a6c2e71";
+  const OTHER_JTI: &str = "5c0d6a8e-2b3f-4e1d-8a9c-7f6e5d4c3b2a";
+
+  #[sqlx::test]
+  async fn test_revoke_token(pool: PgPool) -> sqlx::Result<()> {
+    let sut = TokenRevocationStoreDB { pool };
+
+    sut
+      .revoke(JTI, (get_current_timestamp() + 60) as usize)
+      .await
+      .unwrap();
+
+    assert!(sut.is_revoked(JTI).await.unwrap());
+    assert!(!sut.is_revoked(OTHER_JTI).await.unwrap());
+
+    Ok(())
+  }
+
+  #[sqlx::test]
+  async fn test_revoke_token_twice(pool: PgPool) -> sqlx::Result<()> {
+    let sut = TokenRevocationStoreDB { pool };
+    let expires_at = (get_current_timestamp() + 60) as usize;
+
+    sut.revoke(JTI, expires_at).await.unwrap();
+    sut.revoke(JTI, expires_at).await.unwrap();
+
+    assert!(sut.is_revoked(JTI).await.unwrap());
+
+    Ok(())
+  }
+
+  #[sqlx::test]
+  async fn test_revoke_purges_expired_tokens(pool: PgPool) -> sqlx::Result<()> {
+    let sut = TokenRevocationStoreDB { pool };
+
+    sut
+      .revoke(OTHER_JTI, (get_current_timestamp() - 60) as usize)
+      .await
+      .unwrap();
+    sut
+      .revoke(JTI, (get_current_timestamp() + 60) as usize)
+      .await
+      .unwrap();
+
+    assert!(!sut.is_revoked(OTHER_JTI).await.unwrap());
+    assert!(sut.is_revoked(JTI).await.unwrap());
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_memory_store_revoke_token() {
+    let sut = TokenRevocationStoreMemory::default();
+
+    sut.revoke(JTI, 0).await.unwrap();
+
+    assert!(sut.is_revoked(JTI).await.unwrap());
+    assert!(!sut.is_revoked(OTHER_JTI).await.unwrap());
+  }
+
+  #[sqlx::test]
+  async fn test_token_version_of_unknown_profile(pool: PgPool) -> sqlx::Result<()> {
+    let sut = TokenRevocationStoreDB { pool };
+
+    assert_eq!(sut.token_version(JTI).await.unwrap(), None);
+
+    Ok(())
+  }
+
+  #[tokio::test]
+  async fn test_memory_store_token_version() {
+    let sut = TokenRevocationStoreMemory::default();
+
+    sut.set_token_version(JTI, 2);
+
+    assert_eq!(sut.token_version(JTI).await.unwrap(), Some(2));
+    assert_eq!(sut.token_version(OTHER_JTI).await.unwrap(), Some(0));
+  }
+}
@@ -0,0 +1,66 @@
+use argon2::{
+  password_hash::{
+    rand_core::OsRng, Error as PasswordHashError, PasswordHash, PasswordHasher, PasswordVerifier,
+    SaltString,
+  },
+  Algorithm, Params, Version,
+};
+
+use crate::domain::{error::AppError, utilities::crypto::Crypto};
+
+/// Argon2id hashing with `memory_kib` KiB of memory, `iterations` passes over
+/// it and `parallelism` lanes.
+pub struct Argon2 {
+  pub memory_kib: u32,
+  pub iterations: u32,
+  pub parallelism: u32,
+}
+
+impl Argon2 {
+  fn hasher(&self) -> Result<argon2::Argon2<'static>, AppError> {
+    let params = Params::new(self.memory_kib, self.iterations, self.parallelism, None)
+      .map_err(|err| AppError::internal(err.to_string()))?;
+
+    Ok(argon2::Argon2::new(
+      Algorithm::Argon2id,
+      Version::V0x13,
+      params,
+    ))
+  }
+}
+
+impl Crypto for Argon2 {
+  fn hash_password(&self, password: &str) -> Result<String, AppError> {
+    let salt = SaltString::generate(&mut OsRng);
+
+    self
+      .hasher()?
+      .hash_password(password.as_bytes(), &salt)
+      .map(|hash| hash.to_string())
+      .map_err(|_| AppError::internal("hash password failed"))
+  }
+  fn verify_password(&self, hash: &str, password: &str) -> Result<bool, AppError> {
+    let hash = PasswordHash::new(hash).map_err(|err| AppError::internal(err.to_string()))?;
+
+    // the algorithm and parameters are read from the hash
+    match argon2::Argon2::default().verify_password(password.as_bytes(), &hash) {
+      Ok(()) => Ok(true),
+      Err(PasswordHashError::Password) => Ok(false),
+      Err(err) => Err(AppError::internal(err.to_string())),
+    }
+  }
+  fn needs_rehash(&self, hash: &str) -> bool {
+    let Ok(hash) = PasswordHash::new(hash) else {
+      return true;
+    };
+    let Ok(params) = Params::try_from(&hash) else {
+      return true;
+    };
+
+    hash.algorithm != Algorithm::Argon2id.ident()
+      || hash.version != Some(Version::V0x13.into())
+      || params.m_cost() != self.memory_kib
+      || params.t_cost() != self.iterations
+      || params.p_cost() != self.parallelism
+  }
+}
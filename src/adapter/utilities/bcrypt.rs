@@ -11,4 +11,9 @@ impl Crypto for BCrypt {
   fn verify_password(&self, hash: &str, password: &str) -> Result<bool, AppError> {
     bcrypt::verify(password, hash).map_err(|err| AppError::internal(err.to_string()))
   }
+  fn needs_rehash(&self, hash: &str) -> bool {
+    hash
+      .parse::<bcrypt::HashParts>()
+      .map_or(true, |parts| parts.get_cost() != self.cost)
+  }
 }
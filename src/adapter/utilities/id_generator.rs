@@ -1,3 +1,6 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use data_encoding::BASE32_NOPAD;
+use rand::{Rng, RngCore};
 use uuid::Uuid;
 
 use crate::domain::utilities::id_generator::IDGenerator;
@@ -8,4 +11,24 @@ impl IDGenerator for NewID {
   fn new_uuid(&self) -> String {
     Uuid::new_v4().to_string()
   }
+
+  fn new_secret(&self) -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+  }
+
+  fn new_numeric_code(&self, length: usize) -> String {
+    let mut rng = rand::thread_rng();
+
+    (0..length)
+      .map(|_| char::from(b'0' + rng.gen_range(0..10)))
+      .collect()
+  }
+
+  fn new_totp_secret(&self) -> String {
+    let mut bytes = [0u8; 20];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    BASE32_NOPAD.encode(&bytes)
+  }
 }
@@ -1,2 +1,7 @@
+pub mod argon2;
 pub mod bcrypt;
 pub mod id_generator;
+pub mod phc;
+pub mod primitives;
+pub mod totp;
+pub mod webauthn;
@@ -0,0 +1,124 @@
+use super::{argon2::Argon2, bcrypt::BCrypt};
+use crate::domain::{error::AppError, utilities::crypto::Crypto};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PasswordAlgorithm {
+  Argon2id,
+  BCrypt,
+}
+
+/// Hashes new passwords with `algorithm` and verifies hashes of either
+/// algorithm, told apart by the identifier their PHC string (or bcrypt's
+/// modular crypt format) starts with. Hashes of the other algorithm, or with
+/// other parameters, need a rehash.
+pub struct PhcCrypto {
+  pub algorithm: PasswordAlgorithm,
+  pub argon2: Argon2,
+  pub bcrypt: BCrypt,
+}
+
+impl PhcCrypto {
+  fn hasher(&self) -> &dyn Crypto {
+    match self.algorithm {
+      PasswordAlgorithm::Argon2id => &self.argon2,
+      PasswordAlgorithm::BCrypt => &self.bcrypt,
+    }
+  }
+}
+
+impl Crypto for PhcCrypto {
+  fn hash_password(&self, password: &str) -> Result<String, AppError> {
+    self.hasher().hash_password(password)
+  }
+  fn verify_password(&self, hash: &str, password: &str) -> Result<bool, AppError> {
+    if hash.starts_with("$argon2") {
+      return self.argon2.verify_password(hash, password);
+    }
+    if hash.starts_with("$2") {
+      return self.bcrypt.verify_password(hash, password);
+    }
+
+    Err(AppError::internal("unsupported password hash"))
+  }
+  fn needs_rehash(&self, hash: &str) -> bool {
+    self.hasher().needs_rehash(hash)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const PASSWORD: &str = "12345678";
+  const WRONG_PASSWORD: &str = "87654321";
+
+  fn sut(algorithm: PasswordAlgorithm) -> PhcCrypto {
+    PhcCrypto {
+      algorithm,
+      argon2: Argon2 {
+        memory_kib: 64,
+        iterations: 1,
+        parallelism: 1,
+      },
+      bcrypt: BCrypt { cost: 4 },
+    }
+  }
+
+  #[test]
+  fn test_hash_and_verify_argon2() {
+    let sut = sut(PasswordAlgorithm::Argon2id);
+
+    let hash = sut.hash_password(PASSWORD).unwrap();
+
+    assert!(hash.starts_with("$argon2id$v=19$m=64,t=1,p=1$"));
+    assert!(sut.verify_password(&hash, PASSWORD).unwrap());
+    assert!(!sut.verify_password(&hash, WRONG_PASSWORD).unwrap());
+    assert!(!sut.needs_rehash(&hash));
+  }
+
+  #[test]
+  fn test_verify_bcrypt_hash_and_upgrade_it() {
+    let sut = sut(PasswordAlgorithm::Argon2id);
+    let hash = bcrypt::hash(PASSWORD, 4).unwrap();
+
+    assert!(sut.verify_password(&hash, PASSWORD).unwrap());
+    assert!(!sut.verify_password(&hash, WRONG_PASSWORD).unwrap());
+    assert!(sut.needs_rehash(&hash));
+  }
+
+  #[test]
+  fn test_rehash_argon2_with_other_parameters() {
+    let sut = sut(PasswordAlgorithm::Argon2id);
+    let stronger = Argon2 {
+      memory_kib: 128,
+      iterations: 1,
+      parallelism: 1,
+    };
+
+    let hash = stronger.hash_password(PASSWORD).unwrap();
+
+    assert!(sut.verify_password(&hash, PASSWORD).unwrap());
+    assert!(sut.needs_rehash(&hash));
+  }
+
+  #[test]
+  fn test_hash_with_bcrypt() {
+    let sut = sut(PasswordAlgorithm::BCrypt);
+
+    let hash = sut.hash_password(PASSWORD).unwrap();
+
+    assert!(hash.starts_with("$2b$04$"));
+    assert!(sut.verify_password(&hash, PASSWORD).unwrap());
+    assert!(!sut.needs_rehash(&hash));
+    assert!(sut.needs_rehash(&bcrypt::hash(PASSWORD, 5).unwrap()));
+    assert!(sut.needs_rehash(&sut.argon2.hash_password(PASSWORD).unwrap()));
+  }
+
+  #[test]
+  fn test_verify_unsupported_hash() {
+    let sut = sut(PasswordAlgorithm::Argon2id);
+
+    assert!(sut.verify_password("plain text", PASSWORD).is_err());
+    assert!(sut.needs_rehash("plain text"));
+  }
+}
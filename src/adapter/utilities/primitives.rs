@@ -0,0 +1,31 @@
+use sha2::{Digest, Sha256};
+
+use super::{totp::hotp, webauthn::verify_es256};
+use crate::domain::{error::AppError, utilities::primitives::Primitives};
+
+pub struct CryptoPrimitives;
+
+impl Primitives for CryptoPrimitives {
+  fn totp_code(&self, secret: &str, step: i64) -> Result<String, AppError> {
+    hotp(secret, step as u64)
+  }
+  fn sha256(&self, data: &[u8]) -> Vec<u8> {
+    Sha256::digest(data).to_vec()
+  }
+  fn verify_es256(&self, public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    verify_es256(public_key, message, signature)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_sha256() {
+    assert_eq!(
+      data_encoding::HEXLOWER.encode(&CryptoPrimitives.sha256(b"abc")),
+      "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+  }
+}
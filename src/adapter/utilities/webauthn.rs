@@ -0,0 +1,70 @@
+use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_ASN1};
+
+/// ES256 (COSE algorithm -7) check of a WebAuthn assertion signature, which
+/// authenticators encode in ASN.1 DER. A malformed key is just a mismatch.
+pub fn verify_es256(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+  UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, public_key)
+    .verify(message, signature)
+    .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+  use ring::{
+    rand::SystemRandom,
+    signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING},
+  };
+
+  use super::*;
+
+  const MESSAGE: &[u8] = b"authenticator data and client data hash";
+
+  fn key_pair() -> EcdsaKeyPair {
+    let rng = SystemRandom::new();
+    let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+
+    EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref()).unwrap()
+  }
+
+  #[test]
+  fn test_verify_es256_valid_signature() {
+    let key_pair = key_pair();
+    let signature = key_pair.sign(&SystemRandom::new(), MESSAGE).unwrap();
+
+    assert!(verify_es256(
+      key_pair.public_key().as_ref(),
+      MESSAGE,
+      signature.as_ref()
+    ));
+  }
+
+  #[test]
+  fn test_verify_es256_other_message() {
+    let key_pair = key_pair();
+    let signature = key_pair.sign(&SystemRandom::new(), MESSAGE).unwrap();
+
+    assert!(!verify_es256(
+      key_pair.public_key().as_ref(),
+      b"other message",
+      signature.as_ref()
+    ));
+  }
+
+  #[test]
+  fn test_verify_es256_other_key() {
+    let signature = key_pair().sign(&SystemRandom::new(), MESSAGE).unwrap();
+
+    assert!(!verify_es256(
+      key_pair().public_key().as_ref(),
+      MESSAGE,
+      signature.as_ref()
+    ));
+  }
+
+  #[test]
+  fn test_verify_es256_malformed_key() {
+    let signature = key_pair().sign(&SystemRandom::new(), MESSAGE).unwrap();
+
+    assert!(!verify_es256(&[4, 1, 2, 3], MESSAGE, signature.as_ref()));
+  }
+}
@@ -0,0 +1,26 @@
+use crate::domain::{
+  entities::audit_event::{
+    AuditChainVerification, AuditEventFilter, AuditEventPage, NewAuditEvent,
+  },
+  error::AppError,
+};
+
+use async_trait::async_trait;
+use mockall::automock;
+
+/// Append-only record of security events, each chained to the previous one
+/// by its hash so that edited or removed events can be detected.
+#[automock]
+#[async_trait]
+pub trait AuditLog: Sync + Send {
+  async fn record(&self, event: &NewAuditEvent) -> Result<(), AppError>;
+  /// Events matching `filter`, newest first.
+  async fn find_events(
+    &self,
+    filter: &AuditEventFilter,
+    page: i64,
+    per_page: i64,
+  ) -> Result<AuditEventPage, AppError>;
+  /// Recomputes the hash of every event, oldest first.
+  async fn verify_chain(&self) -> Result<AuditChainVerification, AppError>;
+}
@@ -1,5 +1,36 @@
-use self::security::token_service::TokenService;
+use self::{
+  audit_log::AuditLog,
+  notification::{email_sender::EmailSender, sms_sender::SmsSender},
+  security::{
+    email_verification_policy::EmailVerificationPolicy,
+    external_identity_policy::ExternalIdentityPolicy, identity_provider::IdentityProviderClient,
+    mfa_policy::MfaPolicy, oidc_policy::OidcPolicy, password_reset_policy::PasswordResetPolicy,
+    sign_in_policy::SignInPolicy, telephone_verification_policy::TelephoneVerificationPolicy,
+    token_policy::TokenPolicy, token_service::TokenService, webauthn_policy::WebAuthnPolicy,
+  },
+};
+pub mod audit_log;
+pub mod notification;
 pub mod security;
-pub struct Services<Token: TokenService> {
+pub struct Services<
+  Token: TokenService,
+  Email: EmailSender,
+  Sms: SmsSender,
+  Idp: IdentityProviderClient,
+  Audit: AuditLog,
+> {
   pub token: Token,
+  pub email: Email,
+  pub sms: Sms,
+  pub identity_provider: Idp,
+  pub audit: Audit,
+  pub token_policy: TokenPolicy,
+  pub sign_in_policy: SignInPolicy,
+  pub email_verification_policy: EmailVerificationPolicy,
+  pub telephone_verification_policy: TelephoneVerificationPolicy,
+  pub password_reset_policy: PasswordResetPolicy,
+  pub mfa_policy: MfaPolicy,
+  pub webauthn_policy: WebAuthnPolicy,
+  pub external_identity_policy: ExternalIdentityPolicy,
+  pub oidc_policy: OidcPolicy,
 }
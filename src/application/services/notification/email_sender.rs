@@ -0,0 +1,25 @@
+use crate::domain::error::AppError;
+
+use async_trait::async_trait;
+use mockall::automock;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct EmailMessage {
+  pub to: String,
+  pub subject: String,
+  pub body: String,
+}
+
+#[automock]
+#[async_trait]
+pub trait EmailSender: Sync + Send {
+  async fn send(&self, message: &EmailMessage) -> Result<(), AppError>;
+}
+
+/// Lets the delivery be picked from settings at startup.
+#[async_trait]
+impl EmailSender for Box<dyn EmailSender> {
+  async fn send(&self, message: &EmailMessage) -> Result<(), AppError> {
+    (**self).send(message).await
+  }
+}
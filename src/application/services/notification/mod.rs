@@ -0,0 +1,2 @@
+pub mod email_sender;
+pub mod sms_sender;
@@ -0,0 +1,24 @@
+use crate::domain::error::AppError;
+
+use async_trait::async_trait;
+use mockall::automock;
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SmsMessage {
+  pub to: String,
+  pub body: String,
+}
+
+#[automock]
+#[async_trait]
+pub trait SmsSender: Sync + Send {
+  async fn send(&self, message: &SmsMessage) -> Result<(), AppError>;
+}
+
+/// Lets the delivery be picked from settings at startup.
+#[async_trait]
+impl SmsSender for Box<dyn SmsSender> {
+  async fn send(&self, message: &SmsMessage) -> Result<(), AppError> {
+    (**self).send(message).await
+  }
+}
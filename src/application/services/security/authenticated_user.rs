@@ -0,0 +1,103 @@
+use super::{identity_provider::IdentityProviderClient, token_service::TokenService};
+use crate::{
+  application::services::{
+    audit_log::AuditLog,
+    notification::{email_sender::EmailSender, sms_sender::SmsSender},
+    Services,
+  },
+  domain::{
+    entities::audit_event::{actions, AuditOutcome, NewAuditEvent},
+    error::AppError,
+  },
+};
+use log::error;
+use serde_json::json;
+
+/// User an access token was issued to. The HTTP layer decodes and checks the
+/// token once per request and hands this to the use cases.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct AuthenticatedUser {
+  pub profile_id: String,
+  pub roles: Vec<String>,
+  pub permissions: Vec<String>,
+  /// Session the token was issued for, when it names one.
+  pub session_id: Option<String>,
+  /// Id of the token itself and when it expires, to revoke it.
+  pub token_id: String,
+  pub expires_at: usize,
+  /// Where the request came from, recorded in the audit log.
+  pub ip_address: Option<String>,
+  pub user_agent: Option<String>,
+}
+
+impl AuthenticatedUser {
+  pub fn has_permission(&self, permission: &str) -> bool {
+    self.permissions.iter().any(|granted| granted == permission)
+  }
+
+  /// Audit event of an action this user took on `subject_id`.
+  pub fn audit_event(&self, action: &str, subject_id: Option<&str>) -> NewAuditEvent {
+    NewAuditEvent {
+      actor_id: Some(self.profile_id.clone()),
+      subject_id: subject_id.map(str::to_string),
+      action: action.to_string(),
+      ip_address: self.ip_address.clone(),
+      user_agent: self.user_agent.clone(),
+      ..Default::default()
+    }
+  }
+}
+
+impl<
+    Token: TokenService,
+    Email: EmailSender,
+    Sms: SmsSender,
+    Idp: IdentityProviderClient,
+    Audit: AuditLog,
+  > Services<Token, Email, Sms, Idp, Audit>
+{
+  /// Decodes an access token of this service, refusing expired and revoked
+  /// tokens as well as those issued for another audience.
+  pub async fn authenticate(&self, token: &str) -> Result<AuthenticatedUser, AppError> {
+    let token_decoded = self.token.decode(token).await?;
+    if token_decoded.aud != self.token_policy.authentication_audience {
+      return Err(AppError::unauthenticated(
+        "Given token is not valid for this service",
+      ));
+    }
+
+    Ok(AuthenticatedUser {
+      profile_id: token_decoded.sub,
+      roles: token_decoded.roles,
+      permissions: token_decoded
+        .scope
+        .split_whitespace()
+        .map(str::to_string)
+        .collect(),
+      session_id: token_decoded.sid,
+      token_id: token_decoded.jti,
+      expires_at: token_decoded.exp,
+      ..Default::default()
+    })
+  }
+
+  /// Records a request refused for lacking `permission`. A failed write is
+  /// only logged, the request is refused either way.
+  pub async fn record_permission_denied(
+    &self,
+    user: &AuthenticatedUser,
+    permission: &str,
+    method: &str,
+    path: &str,
+  ) {
+    let event = NewAuditEvent {
+      outcome: AuditOutcome::Failure,
+      metadata: json!({ "permission": permission, "method": method, "path": path }),
+      ..user.audit_event(actions::PERMISSION_DENIED, None)
+    };
+
+    if let Err(audit_error) = self.audit.record(&event).await {
+      error!(target: "audit", "{} not recorded: {}", event.action, audit_error);
+    }
+  }
+}
@@ -0,0 +1,16 @@
+/// How long verification links stay valid and whether signing in with an
+/// email address requires that address to be verified.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailVerificationPolicy {
+  pub token_expiration_min: i64,
+  pub required_for_sign_in: bool,
+}
+
+impl Default for EmailVerificationPolicy {
+  fn default() -> Self {
+    Self {
+      token_expiration_min: 60 * 24,
+      required_for_sign_in: false,
+    }
+  }
+}
@@ -0,0 +1,76 @@
+use crate::domain::{entities::external_identity::ExternalSignInStart, error::AppError};
+
+/// External OpenID Connect provider users can sign in with, as registered
+/// with it: we are a confidential client using the authorization code flow.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExternalProvider {
+  /// Path segment of the sign-in endpoints, stored with every linked identity.
+  pub name: String,
+  /// Expected `iss` of its ID tokens.
+  pub issuer: String,
+  pub client_id: String,
+  pub client_secret: String,
+  pub authorization_endpoint: String,
+  pub token_endpoint: String,
+  /// Key set the signatures of its ID tokens are checked against.
+  pub jwks_uri: String,
+  /// Page of the front end the provider sends the browser back to.
+  pub redirect_uri: String,
+  pub scope: String,
+}
+
+impl ExternalProvider {
+  /// Where to send the browser to sign in at the provider.
+  pub fn authorization_url(&self, start: &ExternalSignInStart) -> String {
+    let separator = if self.authorization_endpoint.contains('?') {
+      '&'
+    } else {
+      '?'
+    };
+    let query = [
+      ("response_type", "code"),
+      ("client_id", &self.client_id),
+      ("redirect_uri", &self.redirect_uri),
+      ("scope", &self.scope),
+      ("state", &start.state),
+      ("nonce", &start.nonce),
+      ("code_challenge", &start.code_challenge),
+      ("code_challenge_method", "S256"),
+    ]
+    .iter()
+    .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+    .collect::<Vec<_>>()
+    .join("&");
+
+    format!("{}{}{}", self.authorization_endpoint, separator, query)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalIdentityPolicy {
+  pub providers: Vec<ExternalProvider>,
+  /// Time to come back from the provider once the sign-in has started.
+  pub state_expiration_min: i64,
+  /// Time to fill in the profile when a new user signs up through a provider.
+  pub sign_up_expiration_min: i64,
+}
+
+impl ExternalIdentityPolicy {
+  pub fn provider(&self, name: &str) -> Result<&ExternalProvider, AppError> {
+    self
+      .providers
+      .iter()
+      .find(|provider| provider.name == name)
+      .ok_or_else(|| AppError::not_found(format!("unknown identity provider {}", name)))
+  }
+}
+
+impl Default for ExternalIdentityPolicy {
+  fn default() -> Self {
+    Self {
+      providers: Vec::new(),
+      state_expiration_min: 10,
+      sign_up_expiration_min: 30,
+    }
+  }
+}
@@ -0,0 +1,19 @@
+use crate::domain::{entities::external_identity::ExternalIdToken, error::AppError};
+
+use async_trait::async_trait;
+use mockall::automock;
+
+use super::external_identity_policy::ExternalProvider;
+
+#[automock]
+#[async_trait]
+pub trait IdentityProviderClient: Sync + Send {
+  /// Redeems `code` at the token endpoint of `provider`, returning the claims
+  /// of the ID token it answers with.
+  async fn exchange_code(
+    &self,
+    provider: &ExternalProvider,
+    code: &str,
+    code_verifier: &str,
+  ) -> Result<ExternalIdToken, AppError>;
+}
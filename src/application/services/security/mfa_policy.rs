@@ -0,0 +1,22 @@
+/// Two-factor authentication with TOTP authenticator apps. The sign-in token
+/// waiting for the second factor lasts `token_expiration_min`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MfaPolicy {
+  /// Name authenticator apps show next to the account.
+  pub issuer: String,
+  pub token_expiration_min: u64,
+  pub recovery_code_count: usize,
+  /// 30 seconds steps accepted before and after the current one.
+  pub allowed_drift_steps: i64,
+}
+
+impl Default for MfaPolicy {
+  fn default() -> Self {
+    Self {
+      issuer: env!("CARGO_PKG_NAME").to_string(),
+      token_expiration_min: 5,
+      recovery_code_count: 10,
+      allowed_drift_steps: 1,
+    }
+  }
+}
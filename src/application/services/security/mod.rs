@@ -1 +1,14 @@
+pub mod authenticated_user;
+pub mod email_verification_policy;
+pub mod external_identity_policy;
+pub mod identity_provider;
+pub mod mfa_policy;
+pub mod oidc_policy;
+pub mod password_reset_policy;
+pub mod rate_limit;
+pub mod sign_in_policy;
+pub mod telephone_verification_policy;
+pub mod token_policy;
+pub mod token_revocation;
 pub mod token_service;
+pub mod webauthn_policy;
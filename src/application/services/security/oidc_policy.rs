@@ -0,0 +1,20 @@
+/// OpenID Connect provider mode. Clients exchange authorization codes for an
+/// ID token and an access token that is only accepted by `/userinfo`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OidcPolicy {
+  /// Audience of the access tokens issued to clients, kept apart from ours so
+  /// they cannot call the rest of the API.
+  pub userinfo_audience: String,
+  pub code_expiration_min: i64,
+  pub token_expiration_min: u64,
+}
+
+impl Default for OidcPolicy {
+  fn default() -> Self {
+    Self {
+      userinfo_audience: String::from("oidc_userinfo"),
+      code_expiration_min: 5,
+      token_expiration_min: 60,
+    }
+  }
+}
@@ -0,0 +1,13 @@
+/// How long a password reset token sent by email stays valid.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PasswordResetPolicy {
+  pub token_expiration_min: i64,
+}
+
+impl Default for PasswordResetPolicy {
+  fn default() -> Self {
+    Self {
+      token_expiration_min: 60,
+    }
+  }
+}
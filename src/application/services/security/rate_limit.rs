@@ -0,0 +1,115 @@
+use crate::domain::error::AppError;
+
+use async_trait::async_trait;
+use mockall::automock;
+
+/// Token bucket holding up to `capacity` requests, refilled with
+/// `refill_per_min` tokens a minute.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimit {
+  pub capacity: u32,
+  pub refill_per_min: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RateLimitDecision {
+  pub allowed: bool,
+  pub limit: u32,
+  pub remaining: u32,
+  /// Seconds until the bucket is full again.
+  pub reset_sec: u64,
+  /// Seconds until the next request is allowed, zero while it is.
+  pub retry_after_sec: u64,
+}
+
+impl RateLimit {
+  /// Takes a token from a bucket left with `tokens` (full when unknown)
+  /// `elapsed_sec` seconds ago, returning the tokens left in it.
+  pub fn take(&self, tokens: Option<f64>, elapsed_sec: f64) -> (f64, RateLimitDecision) {
+    let capacity = f64::from(self.capacity);
+    let refill_per_sec = f64::from(self.refill_per_min) / 60.0;
+
+    let available = match tokens {
+      Some(tokens) => (tokens + elapsed_sec.max(0.0) * refill_per_sec).min(capacity),
+      None => capacity,
+    };
+    let allowed = available >= 1.0;
+    let tokens = if allowed { available - 1.0 } else { available };
+
+    let decision = RateLimitDecision {
+      allowed,
+      limit: self.capacity,
+      remaining: tokens.floor() as u32,
+      reset_sec: ((capacity - tokens) / refill_per_sec).ceil() as u64,
+      retry_after_sec: if allowed {
+        0
+      } else {
+        ((1.0 - tokens) / refill_per_sec).ceil() as u64
+      },
+    };
+
+    (tokens, decision)
+  }
+}
+
+#[automock]
+#[async_trait]
+pub trait RateLimitStore: Sync + Send {
+  /// Takes a token from the bucket stored under `key`.
+  async fn take(&self, key: &str, limit: &RateLimit) -> Result<RateLimitDecision, AppError>;
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const LIMIT: RateLimit = RateLimit {
+    capacity: 3,
+    refill_per_min: 6,
+  };
+
+  #[test]
+  fn test_take_from_new_bucket() {
+    let (tokens, decision) = LIMIT.take(None, 0.0);
+
+    assert_eq!(tokens, 2.0);
+    assert_eq!(
+      decision,
+      RateLimitDecision {
+        allowed: true,
+        limit: 3,
+        remaining: 2,
+        reset_sec: 10,
+        retry_after_sec: 0,
+      }
+    );
+  }
+
+  #[test]
+  fn test_take_from_empty_bucket() {
+    let (tokens, decision) = LIMIT.take(Some(0.0), 5.0);
+
+    assert_eq!(tokens, 0.5);
+    assert!(!decision.allowed);
+    assert_eq!(decision.remaining, 0);
+    assert_eq!(decision.reset_sec, 25);
+    assert_eq!(decision.retry_after_sec, 5);
+  }
+
+  #[test]
+  fn test_take_after_refill() {
+    let (tokens, decision) = LIMIT.take(Some(0.5), 5.0);
+
+    assert_eq!(tokens, 0.0);
+    assert!(decision.allowed);
+    assert_eq!(decision.remaining, 0);
+  }
+
+  #[test]
+  fn test_refill_stops_at_capacity() {
+    let (tokens, decision) = LIMIT.take(Some(1.0), 3600.0);
+
+    assert_eq!(tokens, 2.0);
+    assert_eq!(decision.remaining, 2);
+  }
+}
@@ -0,0 +1,14 @@
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignInPolicy {
+  pub max_failed_attempts: i32,
+  pub lockout_duration_min: i64,
+}
+
+impl Default for SignInPolicy {
+  fn default() -> Self {
+    Self {
+      max_failed_attempts: 5,
+      lockout_duration_min: 15,
+    }
+  }
+}
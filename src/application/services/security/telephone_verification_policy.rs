@@ -0,0 +1,23 @@
+/// Shape and lifetime of the one-time codes sent by SMS, how many can be
+/// requested per hour and guessed per code, and whether signing in with a
+/// telephone number requires that number to be verified.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TelephoneVerificationPolicy {
+  pub code_length: usize,
+  pub code_expiration_min: i64,
+  pub max_codes_per_hour: i64,
+  pub max_attempts: i32,
+  pub required_for_sign_in: bool,
+}
+
+impl Default for TelephoneVerificationPolicy {
+  fn default() -> Self {
+    Self {
+      code_length: 6,
+      code_expiration_min: 10,
+      max_codes_per_hour: 5,
+      max_attempts: 5,
+      required_for_sign_in: false,
+    }
+  }
+}
@@ -0,0 +1,19 @@
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenPolicy {
+  pub authentication_audience: String,
+  /// Audience of the sign-in token waiting for a second factor.
+  pub mfa_audience: String,
+  pub access_token_expiration_min: u64,
+  pub refresh_token_expiration_min: i64,
+}
+
+impl Default for TokenPolicy {
+  fn default() -> Self {
+    Self {
+      authentication_audience: String::from("authentication_user"),
+      mfa_audience: String::from("mfa_pending"),
+      access_token_expiration_min: 120,
+      refresh_token_expiration_min: 60 * 24 * 30,
+    }
+  }
+}
@@ -0,0 +1,13 @@
+use crate::domain::error::AppError;
+
+use async_trait::async_trait;
+use mockall::automock;
+
+#[automock]
+#[async_trait]
+pub trait TokenRevocationStore: Sync + Send {
+  async fn revoke(&self, jti: &str, expires_at: usize) -> Result<(), AppError>;
+  async fn is_revoked(&self, jti: &str) -> Result<bool, AppError>;
+  /// Current token version of the profile, none when it no longer exists.
+  async fn token_version(&self, profile_id: &str) -> Result<Option<i32>, AppError>;
+}
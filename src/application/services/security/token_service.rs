@@ -1,19 +1,65 @@
-use crate::domain::error::AppError;
+use crate::domain::{entities::role::Grants, error::AppError};
 
+use async_trait::async_trait;
 use mockall::automock;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Token {
   pub sub: String,
   pub iss: String,
   pub aud: String,
   pub iat: usize,
   pub exp: usize,
+  pub jti: String,
+  #[serde(default, skip_serializing_if = "Vec::is_empty")]
+  pub roles: Vec<String>,
+  /// Permissions granted by `roles`, separated by spaces.
+  #[serde(default, skip_serializing_if = "String::is_empty")]
+  pub scope: String,
+  /// Session the token was issued for.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub sid: Option<String>,
+  /// Token version of the profile when the token was issued, the token is
+  /// refused once the profile version moves past it. Only missing on tokens
+  /// issued before every token carried it.
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub ver: Option<i32>,
+}
+
+/// OpenID Connect ID token, telling the client `aud` who signed in.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IdToken {
+  pub sub: String,
+  pub iss: String,
+  pub aud: String,
+  pub iat: usize,
+  pub exp: usize,
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub nonce: Option<String>,
 }
 
 #[automock]
+#[async_trait]
 pub trait TokenService: Sync + Send {
-  fn encode(&self, sub: String, aud: String, exp_min: u64) -> Result<String, AppError>;
-  fn decode(&self, token: &str) -> Result<Token, AppError>;
+  /// Token of profile `sub`, issued at its current token `version` and for
+  /// the session `session_id` when it belongs to one.
+  fn encode(
+    &self,
+    sub: String,
+    aud: String,
+    grants: Grants,
+    session_id: Option<String>,
+    version: i32,
+    exp_min: u64,
+  ) -> Result<String, AppError>;
+  fn encode_id_token(
+    &self,
+    sub: String,
+    aud: String,
+    nonce: Option<String>,
+    exp_min: u64,
+  ) -> Result<String, AppError>;
+  async fn decode(&self, token: &str) -> Result<Token, AppError>;
+  async fn revoke(&self, token_id: &str, expires_at: usize) -> Result<(), AppError>;
 }
@@ -0,0 +1,21 @@
+/// Relying party of WebAuthn passkeys. Passkeys are scoped to `rp_id`, a
+/// domain that `origin`, where the front end is served, must belong to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebAuthnPolicy {
+  pub rp_id: String,
+  /// Name authenticators show next to the passkey.
+  pub rp_name: String,
+  pub origin: String,
+  pub challenge_expiration_min: i64,
+}
+
+impl Default for WebAuthnPolicy {
+  fn default() -> Self {
+    Self {
+      rp_id: String::from("localhost"),
+      rp_name: env!("CARGO_PKG_NAME").to_string(),
+      origin: String::from("http://localhost:8080"),
+      challenge_expiration_min: 5,
+    }
+  }
+}
@@ -0,0 +1,217 @@
+#[cfg(test)]
+mod test_utils;
+#[cfg(test)]
+use test_utils::*;
+
+pub mod user;
+
+use crate::application::services::security::authenticated_user::AuthenticatedUser;
+use crate::domain::{
+  core::user::list_users,
+  entities::{
+    audit_event::{AuditChainVerification, AuditEventFilter, AuditEventPage, AuditOutcome},
+    oauth::OAuthClientCredentials,
+    user::{FullProfile, UserFilter, UserSort, UserSummaryPage},
+  },
+  error::AppError,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use mockall::automock;
+use validator::{Validate, ValidationError};
+
+#[async_trait]
+#[automock]
+pub trait UserAdministration: Send + Sync {
+  async fn set_account_disabled(
+    &self,
+    request: &SetAccountStatusRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<bool, AppError>;
+  /// Registers an application allowed to sign users in through our OpenID
+  /// Connect provider. The client secret is only returned here.
+  async fn register_oauth_client(
+    &self,
+    request: &RegisterOAuthClientRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<OAuthClientCredentials, AppError>;
+  async fn list_users(
+    &self,
+    request: &ListUsersRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<UserSummaryPage, AppError>;
+  async fn get_user(
+    &self,
+    profile_id: &str,
+    user: &AuthenticatedUser,
+  ) -> Result<FullProfile, AppError>;
+  /// Blocks password sign-ins until the user picks a new password, revokes
+  /// their refresh tokens and mails them a reset token.
+  async fn force_password_reset(
+    &self,
+    profile_id: &str,
+    user: &AuthenticatedUser,
+  ) -> Result<(), AppError>;
+  async fn unlock_account(
+    &self,
+    profile_id: &str,
+    user: &AuthenticatedUser,
+  ) -> Result<(), AppError>;
+  /// Grants or revokes a role, returning whether the assignment changed.
+  async fn set_role(
+    &self,
+    request: &SetRoleRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<bool, AppError>;
+  async fn list_audit_events(
+    &self,
+    request: &ListAuditEventsRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<AuditEventPage, AppError>;
+  /// Recomputes the hash chain of the audit log to detect tampering.
+  async fn verify_audit_log(
+    &self,
+    user: &AuthenticatedUser,
+  ) -> Result<AuditChainVerification, AppError>;
+}
+
+#[derive(Default)]
+pub struct SetAccountStatusRequest<'a> {
+  pub profile_id: &'a str,
+  pub disabled: bool,
+}
+
+#[derive(Validate)]
+pub struct ListUsersRequest<'a> {
+  #[validate(range(min = 1, message = "Page must be greater than zero!"))]
+  pub page: i64,
+  #[validate(range(min = 1, max = 100, message = "Page size must be between 1 and 100!"))]
+  pub per_page: i64,
+  pub username: Option<&'a str>,
+  pub email: Option<&'a str>,
+  pub created_from: Option<&'a str>,
+  pub created_to: Option<&'a str>,
+  pub sort: Option<&'a str>,
+}
+
+impl Default for ListUsersRequest<'_> {
+  fn default() -> Self {
+    Self {
+      page: 1,
+      per_page: 20,
+      username: None,
+      email: None,
+      created_from: None,
+      created_to: None,
+      sort: None,
+    }
+  }
+}
+
+impl ListUsersRequest<'_> {
+  pub fn to_domain_request(&self) -> Result<list_users::Request, AppError> {
+    let parse_date = |date: Option<&str>| {
+      date
+        .map(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+        .transpose()
+        .map_err(|_| AppError::invalid_argument("creation date format invalid"))
+    };
+
+    Ok(list_users::Request {
+      filter: UserFilter {
+        username: self.username.map(str::to_owned),
+        email: self.email.map(str::to_owned),
+        created_from: parse_date(self.created_from)?,
+        created_to: parse_date(self.created_to)?,
+        sort: self
+          .sort
+          .map(UserSort::try_from)
+          .transpose()?
+          .unwrap_or_default(),
+      },
+      page: self.page,
+      per_page: self.per_page,
+    })
+  }
+}
+
+#[derive(Validate)]
+pub struct ListAuditEventsRequest<'a> {
+  #[validate(range(min = 1, message = "Page must be greater than zero!"))]
+  pub page: i64,
+  #[validate(range(min = 1, max = 100, message = "Page size must be between 1 and 100!"))]
+  pub per_page: i64,
+  pub actor_id: Option<&'a str>,
+  pub subject_id: Option<&'a str>,
+  pub action: Option<&'a str>,
+  pub outcome: Option<&'a str>,
+}
+
+impl Default for ListAuditEventsRequest<'_> {
+  fn default() -> Self {
+    Self {
+      page: 1,
+      per_page: 20,
+      actor_id: None,
+      subject_id: None,
+      action: None,
+      outcome: None,
+    }
+  }
+}
+
+impl ListAuditEventsRequest<'_> {
+  pub fn to_filter(&self) -> Result<AuditEventFilter, AppError> {
+    Ok(AuditEventFilter {
+      actor_id: self.actor_id.map(str::to_owned),
+      subject_id: self.subject_id.map(str::to_owned),
+      action: self.action.map(str::to_owned),
+      outcome: self.outcome.map(AuditOutcome::try_from).transpose()?,
+    })
+  }
+}
+
+#[derive(Default)]
+pub struct SetRoleRequest<'a> {
+  pub profile_id: &'a str,
+  pub role: &'a str,
+  pub granted: bool,
+}
+
+#[derive(Validate, Default)]
+#[validate(schema(function = "validate_redirect_uris"))]
+pub struct RegisterOAuthClientRequest<'a> {
+  #[validate(length(min = 3, message = "Client name must contain minimum 3 characters!"))]
+  pub name: &'a str,
+  pub redirect_uris: &'a [String],
+  pub confidential: bool,
+}
+
+/// Redirect URIs are compared as exact strings, so they are only accepted
+/// over https, or http for local development, and never with a fragment.
+fn validate_redirect_uris(request: &RegisterOAuthClientRequest) -> Result<(), ValidationError> {
+  let is_local = |authority: &str| {
+    ["localhost", "127.0.0.1"].iter().any(|host| {
+      authority
+        .strip_prefix(host)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with([':', '/']))
+    })
+  };
+  let is_valid = |uri: &String| {
+    let authority = uri
+      .strip_prefix("https://")
+      .or_else(|| uri.strip_prefix("http://").filter(|rest| is_local(rest)));
+
+    authority.is_some_and(|authority| !authority.is_empty()) && !uri.contains('#')
+  };
+
+  if request.redirect_uris.is_empty() || !request.redirect_uris.iter().all(is_valid) {
+    let mut error = ValidationError::new("redirect_uris");
+    error.message = Some(
+      "Please provide redirect URIs using https, or http on localhost, without fragment".into(),
+    );
+    return Err(error);
+  }
+
+  Ok(())
+}
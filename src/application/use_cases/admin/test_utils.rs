@@ -0,0 +1,347 @@
+use crate::{
+  application::services::{
+    audit_log::MockAuditLog, notification::email_sender::MockEmailSender,
+    security::authenticated_user::AuthenticatedUser,
+  },
+  domain::{
+    core::user::{
+      mocks::repository::{
+        build_mock_user_repository, Expectations, FindFullProfileBy, FindUsers, GrantRole,
+        RequirePasswordReset, StoreOAuthClient, StoreVerificationToken, UnlockUser,
+        UpdateAccountDisabled,
+      },
+      repository::MockUserRepository,
+    },
+    entities::{
+      audit_event::{
+        actions, AuditChainVerification, AuditEvent, AuditEventFilter, AuditEventPage, AuditOutcome,
+      },
+      role::{permissions, ADMIN_ROLE},
+      user::{Email, FullProfile, UserColumns, UserFilter, UserSort, UserSummary, UserSummaryPage},
+      verification_token::VerificationPurpose,
+    },
+    error::AppError,
+    utilities::{crypto::MockCrypto, id_generator::MockIDGenerator},
+  },
+};
+use jsonwebtoken::get_current_timestamp;
+use mockall::predicate;
+use serde_json::json;
+
+pub(super) const ADMIN_ID: &str = "0f0e6d1c-4a3b-4c2d-9e8f-7a6b5c4d3e2f";
+pub(super) const USER_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+pub(super) const CLIENT_ID: &str = "5f1c2a9e-7b4d-4e3a-9c8f-1d2e3f4a5b6c";
+pub(super) const CLIENT_SECRET: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+pub(super) const AUDIT_EVENT_HASH: &str =
+  "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+pub(super) const CLIENT_SECRET_HASH: &str = "$2b$12$client_secret_hash";
+pub(super) const JTI: &str = "0b8e1e9a-5f4c-4f7b-9d0a-3b1f0a6c2e71";
+pub(super) const USERNAME: &str = "john_doe";
+pub(super) const EMAIL_ADDRESS: &str = "john@email.com";
+pub(super) const RESET_TOKEN: &str = "Hq7nW2xK5vB9cM4tR8yL3jF6gD1sA0pZ2uE5iO8nP4w";
+
+fn principal(profile_id: &str) -> AuthenticatedUser {
+  AuthenticatedUser {
+    profile_id: profile_id.to_owned(),
+    token_id: JTI.to_owned(),
+    expires_at: (get_current_timestamp() + 60 * 120) as usize,
+    ..Default::default()
+  }
+}
+
+pub(super) fn admin() -> AuthenticatedUser {
+  AuthenticatedUser {
+    roles: vec![ADMIN_ROLE.to_owned()],
+    permissions: vec![
+      permissions::AUDIT_EVENTS_READ.to_owned(),
+      permissions::OAUTH_CLIENTS_ADMIN.to_owned(),
+      permissions::USERS_ADMIN.to_owned(),
+    ],
+    ..principal(ADMIN_ID)
+  }
+}
+
+pub(super) fn user() -> AuthenticatedUser {
+  principal(USER_ID)
+}
+
+pub(super) fn repository_disable_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    update_account_disabled: Some(UpdateAccountDisabled {
+      calls: 1,
+      param_profile_id: USER_ID.to_owned(),
+      param_disabled: true,
+      fn_returning: |_, _| Ok(()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_enable_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    update_account_disabled: Some(UpdateAccountDisabled {
+      calls: 1,
+      param_profile_id: USER_ID.to_owned(),
+      param_disabled: false,
+      fn_returning: |_, _| Ok(()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_update_account_disabled_not_found() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    update_account_disabled: Some(UpdateAccountDisabled {
+      calls: 1,
+      param_profile_id: USER_ID.to_owned(),
+      param_disabled: true,
+      fn_returning: |_, _| {
+        Err(AppError::not_found(
+          "DB: nothing found with given parameters",
+        ))
+      },
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_store_oauth_client() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    store_oauth_client: Some(StoreOAuthClient {
+      calls: 1,
+      param_client_id: CLIENT_ID.to_owned(),
+      param_secret_hash: Some(CLIENT_SECRET_HASH.to_owned()),
+      fn_returning: |_| Ok(()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn id_generator_client_credentials() -> MockIDGenerator {
+  let mut id_generator_mock = MockIDGenerator::new();
+
+  id_generator_mock
+    .expect_new_uuid()
+    .times(1)
+    .returning(|| CLIENT_ID.to_owned());
+  id_generator_mock
+    .expect_new_secret()
+    .times(1)
+    .returning(|| CLIENT_SECRET.to_owned());
+
+  id_generator_mock
+}
+
+pub(super) fn crypto_hash_client_secret() -> MockCrypto {
+  let mut crypto_mock = MockCrypto::new();
+
+  crypto_mock
+    .expect_hash_password()
+    .times(1)
+    .with(predicate::eq(CLIENT_SECRET))
+    .returning(|_| Ok(CLIENT_SECRET_HASH.to_owned()));
+
+  crypto_mock
+}
+
+pub(super) fn user_summary() -> UserSummary {
+  UserSummary {
+    id: USER_ID.to_owned(),
+    username: USERNAME.to_owned(),
+    emails: vec![EMAIL_ADDRESS.to_owned()],
+    ..Default::default()
+  }
+}
+
+pub(super) fn repository_find_users_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_users: Some(FindUsers {
+      calls: 1,
+      param_filter: UserFilter {
+        username: Some("john".to_owned()),
+        sort: UserSort::UsernameAsc,
+        ..Default::default()
+      },
+      param_page: 1,
+      param_per_page: 20,
+      fn_returning: |_, page, per_page| {
+        Ok(UserSummaryPage {
+          users: vec![user_summary()],
+          page,
+          per_page,
+          total: 1,
+        })
+      },
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn full_profile() -> FullProfile {
+  FullProfile {
+    id: USER_ID.to_owned(),
+    username: USERNAME.to_owned(),
+    emails: vec![Email {
+      address: EMAIL_ADDRESS.to_owned(),
+      checked: true,
+    }],
+    ..Default::default()
+  }
+}
+
+pub(super) fn repository_find_full_profile_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_full_profile_by: Some(FindFullProfileBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(USER_ID),
+      fn_returning: |_| Ok(full_profile()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_force_password_reset_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_full_profile_by: Some(FindFullProfileBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(USER_ID),
+      fn_returning: |_| Ok(full_profile()),
+    }),
+    require_password_reset: Some(RequirePasswordReset {
+      calls: 1,
+      param_profile_id: USER_ID.to_owned(),
+      fn_returning: |_| Ok(()),
+    }),
+    store_verification_token: Some(StoreVerificationToken {
+      calls: 1,
+      param_purpose: VerificationPurpose::PasswordReset,
+      param_profile_id: USER_ID.to_owned(),
+      param_target: EMAIL_ADDRESS.to_owned(),
+      fn_returning: |_| Ok(()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn id_generator_reset_token() -> MockIDGenerator {
+  let mut id_generator_mock = MockIDGenerator::new();
+
+  id_generator_mock
+    .expect_new_secret()
+    .times(1)
+    .returning(|| RESET_TOKEN.to_owned());
+
+  id_generator_mock
+}
+
+pub(super) fn email_sender_send_password_reset() -> MockEmailSender {
+  let mut sender = MockEmailSender::new();
+
+  sender
+    .expect_send()
+    .times(1)
+    .withf(|message| message.to == EMAIL_ADDRESS && message.body.contains(RESET_TOKEN))
+    .returning(|_| Ok(()));
+
+  sender
+}
+
+pub(super) fn repository_unlock_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    unlock_user: Some(UnlockUser {
+      calls: 1,
+      param_profile_id: USER_ID.to_owned(),
+      fn_returning: |_| Ok(()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_grant_admin_role() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    grant_role: Some(GrantRole {
+      calls: 1,
+      param_profile_id: USER_ID.to_owned(),
+      param_role: ADMIN_ROLE.to_owned(),
+      fn_returning: |_, _| Ok(true),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn audit_log_record(action: &'static str, outcome: AuditOutcome) -> MockAuditLog {
+  let mut audit_log = MockAuditLog::new();
+
+  audit_log
+    .expect_record()
+    .times(1)
+    .withf(move |event| event.action == action && event.outcome == outcome)
+    .returning(|_| Ok(()));
+
+  audit_log
+}
+
+pub(super) fn audit_event() -> AuditEvent {
+  AuditEvent {
+    id: 1,
+    actor_id: Some(USER_ID.to_owned()),
+    subject_id: Some(USER_ID.to_owned()),
+    action: actions::SIGN_IN.to_owned(),
+    outcome: AuditOutcome::Failure,
+    metadata: json!({ "method": "password", "error": "Invalid password" }),
+    hash: AUDIT_EVENT_HASH.to_owned(),
+    ..Default::default()
+  }
+}
+
+pub(super) fn audit_log_find_failed_sign_ins() -> MockAuditLog {
+  let mut audit_log = audit_log_record(actions::LIST_AUDIT_EVENTS, AuditOutcome::Success);
+
+  audit_log
+    .expect_find_events()
+    .times(1)
+    .with(
+      predicate::eq(AuditEventFilter {
+        subject_id: Some(USER_ID.to_owned()),
+        action: Some(actions::SIGN_IN.to_owned()),
+        outcome: Some(AuditOutcome::Failure),
+        ..Default::default()
+      }),
+      predicate::eq(1),
+      predicate::eq(20),
+    )
+    .returning(|_, page, per_page| {
+      Ok(AuditEventPage {
+        events: vec![audit_event()],
+        page,
+        per_page,
+        total: 1,
+      })
+    });
+
+  audit_log
+}
+
+pub(super) fn audit_log_verify_tampered_chain() -> MockAuditLog {
+  let mut audit_log = audit_log_record(actions::VERIFY_AUDIT_EVENTS, AuditOutcome::Success);
+
+  audit_log.expect_verify_chain().times(1).returning(|| {
+    Ok(AuditChainVerification {
+      checked: 3,
+      first_invalid_id: Some(2),
+    })
+  });
+
+  audit_log
+}
+
+pub(super) fn audit_log_record_unavailable() -> MockAuditLog {
+  let mut audit_log = MockAuditLog::new();
+
+  audit_log
+    .expect_record()
+    .times(1)
+    .returning(|_| Err(AppError::database_error("Audit log unavailable")));
+
+  audit_log
+}
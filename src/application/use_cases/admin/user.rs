@@ -0,0 +1,1251 @@
+use super::{
+  ListAuditEventsRequest, ListUsersRequest, RegisterOAuthClientRequest, SetAccountStatusRequest,
+  SetRoleRequest, UserAdministration,
+};
+use crate::{
+  application::{
+    services::{
+      audit_log::AuditLog,
+      notification::{email_sender::EmailSender, sms_sender::SmsSender},
+      security::{
+        authenticated_user::AuthenticatedUser, identity_provider::IdentityProviderClient,
+        token_service::TokenService,
+      },
+    },
+    use_cases::authenticate::user::UserUseCase,
+  },
+  domain::{
+    core::user::{
+      get_profile, register_oauth_client, repository::UserRepository, request_password_reset,
+      set_account_status, set_role, unlock_account, User,
+    },
+    entities::{
+      audit_event::{actions, AuditChainVerification, AuditEventPage, NewAuditEvent},
+      oauth::OAuthClientCredentials,
+      role::{permissions, ADMIN_ROLE},
+      user::{FullProfile, UserColumns, UserSummaryPage},
+    },
+    error::AppError,
+    utilities::{crypto::Crypto, id_generator::IDGenerator, primitives::Primitives},
+  },
+};
+use async_trait::async_trait;
+use serde_json::json;
+use validator::Validate;
+
+fn require_permission(user: &AuthenticatedUser, permission: &str) -> Result<(), AppError> {
+  if !user.has_permission(permission) {
+    return Err(AppError::permission_denied(
+      "Given token not have permission for this operation",
+    ));
+  }
+
+  Ok(())
+}
+
+#[async_trait]
+impl<
+    Repository: UserRepository,
+    Token: TokenService,
+    Email: EmailSender,
+    Sms: SmsSender,
+    Idp: IdentityProviderClient,
+    Audit: AuditLog,
+    C: Crypto,
+    ID: IDGenerator,
+    P: Primitives,
+  > UserAdministration for UserUseCase<'_, Repository, Token, Email, Sms, Idp, Audit, C, ID, P>
+{
+  async fn set_account_disabled(
+    &self,
+    request: &SetAccountStatusRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<bool, AppError> {
+    let result = async {
+      require_permission(user, permissions::USERS_ADMIN)?;
+
+      if request.disabled && request.profile_id == user.profile_id {
+        return Err(AppError::invalid_argument(
+          "Administrators cannot disable their own account",
+        ));
+      }
+
+      let disabled = User::new(self.user_repository)
+        .set_account_status(set_account_status::Request {
+          profile_id: request.profile_id,
+          disabled: request.disabled,
+        })
+        .save()
+        .await?
+        .response();
+
+      Ok(disabled)
+    }
+    .await;
+
+    let action = if request.disabled {
+      actions::DISABLE_USER
+    } else {
+      actions::ENABLE_USER
+    };
+    self
+      .audit(user.audit_event(action, Some(request.profile_id)), result)
+      .await
+  }
+
+  async fn register_oauth_client(
+    &self,
+    request: &RegisterOAuthClientRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<OAuthClientCredentials, AppError> {
+    let result = async {
+      require_permission(user, permissions::OAUTH_CLIENTS_ADMIN)?;
+
+      request
+        .validate()
+        .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+      let credentials = User::new(self.user_repository)
+        .register_oauth_client(register_oauth_client::Request {
+          name: request.name,
+          redirect_uris: request.redirect_uris,
+          confidential: request.confidential,
+        })
+        .generate(&self.utilities.id_generator, &self.utilities.crypto)?
+        .save()
+        .await?
+        .response();
+
+      Ok(credentials)
+    }
+    .await;
+
+    let client_id = result
+      .as_ref()
+      .ok()
+      .map(|credentials| credentials.client_id.as_str());
+    let event = NewAuditEvent {
+      metadata: json!({ "client_id": client_id, "name": request.name }),
+      ..user.audit_event(actions::REGISTER_OAUTH_CLIENT, None)
+    };
+    self.audit(event, result).await
+  }
+
+  async fn list_users(
+    &self,
+    request: &ListUsersRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<UserSummaryPage, AppError> {
+    let result = async {
+      require_permission(user, permissions::USERS_ADMIN)?;
+
+      request
+        .validate()
+        .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+      let users = User::new(self.user_repository)
+        .list_users(request.to_domain_request()?)
+        .find_users()
+        .await?
+        .response();
+
+      Ok(users)
+    }
+    .await;
+
+    let event = NewAuditEvent {
+      metadata: json!({
+        "page": request.page,
+        "per_page": request.per_page,
+        "username": request.username,
+        "email": request.email,
+        "created_from": request.created_from,
+        "created_to": request.created_to,
+        "sort": request.sort,
+      }),
+      ..user.audit_event(actions::LIST_USERS, None)
+    };
+    self.audit(event, result).await
+  }
+
+  async fn get_user(
+    &self,
+    profile_id: &str,
+    user: &AuthenticatedUser,
+  ) -> Result<FullProfile, AppError> {
+    let result = async {
+      require_permission(user, permissions::USERS_ADMIN)?;
+
+      let profile = User::new(self.user_repository)
+        .get_profile(get_profile::Request { profile_id })
+        .find_profile()
+        .await?
+        .response();
+
+      Ok(profile)
+    }
+    .await;
+
+    self
+      .audit(
+        user.audit_event(actions::VIEW_USER, Some(profile_id)),
+        result,
+      )
+      .await
+  }
+
+  async fn force_password_reset(
+    &self,
+    profile_id: &str,
+    user: &AuthenticatedUser,
+  ) -> Result<(), AppError> {
+    let result = async {
+      require_permission(user, permissions::USERS_ADMIN)?;
+
+      let target = User::new(self.user_repository)
+        .request_password_reset(request_password_reset::Request {
+          column: UserColumns::Id(profile_id),
+        })
+        .find_target()
+        .await?
+        .require_reset()
+        .await?
+        .response();
+
+      self.send_password_reset(target).await
+    }
+    .await;
+
+    self
+      .audit(
+        user.audit_event(actions::FORCE_PASSWORD_RESET, Some(profile_id)),
+        result,
+      )
+      .await
+  }
+
+  async fn unlock_account(
+    &self,
+    profile_id: &str,
+    user: &AuthenticatedUser,
+  ) -> Result<(), AppError> {
+    let result = async {
+      require_permission(user, permissions::USERS_ADMIN)?;
+
+      User::new(self.user_repository)
+        .unlock_account(unlock_account::Request { profile_id })
+        .save()
+        .await?;
+
+      Ok(())
+    }
+    .await;
+
+    self
+      .audit(
+        user.audit_event(actions::UNLOCK_USER, Some(profile_id)),
+        result,
+      )
+      .await
+  }
+
+  async fn set_role(
+    &self,
+    request: &SetRoleRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<bool, AppError> {
+    let result = async {
+      require_permission(user, permissions::USERS_ADMIN)?;
+
+      if !request.granted && request.role == ADMIN_ROLE && request.profile_id == user.profile_id {
+        return Err(AppError::invalid_argument(
+          "Administrators cannot revoke their own admin role",
+        ));
+      }
+
+      let changed = User::new(self.user_repository)
+        .set_role(set_role::Request {
+          profile_id: request.profile_id,
+          role: request.role,
+          granted: request.granted,
+        })
+        .save()
+        .await?
+        .response();
+
+      Ok(changed)
+    }
+    .await;
+
+    let action = if request.granted {
+      actions::GRANT_ROLE
+    } else {
+      actions::REVOKE_ROLE
+    };
+    let event = NewAuditEvent {
+      metadata: json!({ "role": request.role }),
+      ..user.audit_event(action, Some(request.profile_id))
+    };
+    self.audit(event, result).await
+  }
+
+  async fn list_audit_events(
+    &self,
+    request: &ListAuditEventsRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<AuditEventPage, AppError> {
+    let result = async {
+      require_permission(user, permissions::AUDIT_EVENTS_READ)?;
+
+      request
+        .validate()
+        .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+      self
+        .services
+        .audit
+        .find_events(&request.to_filter()?, request.page, request.per_page)
+        .await
+    }
+    .await;
+
+    let event = NewAuditEvent {
+      metadata: json!({
+        "page": request.page,
+        "per_page": request.per_page,
+        "actor_id": request.actor_id,
+        "subject_id": request.subject_id,
+        "action": request.action,
+        "outcome": request.outcome,
+      }),
+      ..user.audit_event(actions::LIST_AUDIT_EVENTS, None)
+    };
+    self.audit(event, result).await
+  }
+
+  async fn verify_audit_log(
+    &self,
+    user: &AuthenticatedUser,
+  ) -> Result<AuditChainVerification, AppError> {
+    let result = async {
+      require_permission(user, permissions::AUDIT_EVENTS_READ)?;
+
+      self.services.audit.verify_chain().await
+    }
+    .await;
+
+    let event = NewAuditEvent {
+      metadata: match &result {
+        Ok(verification) => json!({
+          "checked": verification.checked,
+          "first_invalid_id": verification.first_invalid_id,
+        }),
+        Err(_) => json!({}),
+      },
+      ..user.audit_event(actions::VERIFY_AUDIT_EVENTS, None)
+    };
+    self.audit(event, result).await
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::super::*;
+  use super::*;
+  use crate::{
+    application::services::{
+      notification::{email_sender::MockEmailSender, sms_sender::MockSmsSender},
+      security::{
+        email_verification_policy::EmailVerificationPolicy,
+        external_identity_policy::ExternalIdentityPolicy,
+        identity_provider::MockIdentityProviderClient, mfa_policy::MfaPolicy,
+        oidc_policy::OidcPolicy, password_reset_policy::PasswordResetPolicy,
+        sign_in_policy::SignInPolicy, telephone_verification_policy::TelephoneVerificationPolicy,
+        token_policy::TokenPolicy, token_service::MockTokenService,
+        webauthn_policy::WebAuthnPolicy,
+      },
+      Services,
+    },
+    domain::{
+      core::user::mocks::repository::{build_mock_user_repository, Expectations},
+      utilities::{
+        crypto::MockCrypto, id_generator::MockIDGenerator, primitives::MockPrimitives, Utilities,
+      },
+    },
+  };
+
+  #[tokio::test]
+  async fn test_disable_account_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_disable_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::DISABLE_USER, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = SetAccountStatusRequest {
+      profile_id: USER_ID,
+      disabled: true,
+    };
+
+    assert_eq!(sut.set_account_disabled(&request, &admin()).await, Ok(true));
+  }
+
+  #[tokio::test]
+  async fn test_enable_account_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_enable_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::ENABLE_USER, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = SetAccountStatusRequest {
+      profile_id: USER_ID,
+      disabled: false,
+    };
+
+    assert_eq!(
+      sut.set_account_disabled(&request, &admin()).await,
+      Ok(false)
+    );
+  }
+
+  #[tokio::test]
+  async fn test_disable_account_with_non_admin_token() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::DISABLE_USER, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = SetAccountStatusRequest {
+      profile_id: USER_ID,
+      disabled: true,
+    };
+
+    assert_eq!(
+      sut.set_account_disabled(&request, &user()).await,
+      Err(AppError::permission_denied(
+        "Given token not have permission for this operation"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_disable_own_account() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::DISABLE_USER, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = SetAccountStatusRequest {
+      profile_id: ADMIN_ID,
+      disabled: true,
+    };
+
+    assert_eq!(
+      sut.set_account_disabled(&request, &admin()).await,
+      Err(AppError::invalid_argument(
+        "Administrators cannot disable their own account"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_disable_nonexistent_account() {
+    let sut = UserUseCase {
+      user_repository: &repository_update_account_disabled_not_found(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::DISABLE_USER, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = SetAccountStatusRequest {
+      profile_id: USER_ID,
+      disabled: true,
+    };
+
+    assert_eq!(
+      sut.set_account_disabled(&request, &admin()).await,
+      Err(AppError::not_found(
+        "DB: nothing found with given parameters"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_register_oauth_client_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_store_oauth_client(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::REGISTER_OAUTH_CLIENT, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_hash_client_secret(),
+        id_generator: id_generator_client_credentials(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let redirect_uris = vec![String::from("https://app.example.com/callback")];
+    let request = RegisterOAuthClientRequest {
+      name: "Example app",
+      redirect_uris: &redirect_uris,
+      confidential: true,
+    };
+
+    assert_eq!(
+      sut.register_oauth_client(&request, &admin()).await,
+      Ok(OAuthClientCredentials {
+        client_id: CLIENT_ID.to_owned(),
+        client_secret: Some(CLIENT_SECRET.to_owned()),
+        name: String::from("Example app"),
+        redirect_uris,
+      })
+    );
+  }
+
+  #[tokio::test]
+  async fn test_register_oauth_client_with_non_admin_token() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::REGISTER_OAUTH_CLIENT, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let redirect_uris = vec![String::from("https://app.example.com/callback")];
+    let request = RegisterOAuthClientRequest {
+      name: "Example app",
+      redirect_uris: &redirect_uris,
+      confidential: true,
+    };
+
+    assert_eq!(
+      sut.register_oauth_client(&request, &user()).await,
+      Err(AppError::permission_denied(
+        "Given token not have permission for this operation"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_register_oauth_client_with_insecure_redirect_uri() {
+    for uri in [
+      "http://app.example.com/callback",
+      "http://localhost.example.com/callback",
+      "https://app.example.com/callback#fragment",
+    ] {
+      let sut = UserUseCase {
+        user_repository: &build_mock_user_repository(Expectations {
+          ..Default::default()
+        }),
+        services: &Services {
+          token: MockTokenService::new(),
+          email: MockEmailSender::new(),
+          sms: MockSmsSender::new(),
+          identity_provider: MockIdentityProviderClient::new(),
+          audit: audit_log_record(actions::REGISTER_OAUTH_CLIENT, AuditOutcome::Failure),
+          token_policy: TokenPolicy::default(),
+          sign_in_policy: SignInPolicy::default(),
+          email_verification_policy: EmailVerificationPolicy::default(),
+          telephone_verification_policy: TelephoneVerificationPolicy::default(),
+          password_reset_policy: PasswordResetPolicy::default(),
+          mfa_policy: MfaPolicy::default(),
+          webauthn_policy: WebAuthnPolicy::default(),
+          external_identity_policy: ExternalIdentityPolicy::default(),
+          oidc_policy: OidcPolicy::default(),
+        },
+        utilities: &Utilities {
+          crypto: MockCrypto::new(),
+          id_generator: MockIDGenerator::new(),
+          primitives: MockPrimitives::new(),
+        },
+      };
+
+      let redirect_uris = vec![uri.to_string()];
+      let request = RegisterOAuthClientRequest {
+        name: "Example app",
+        redirect_uris: &redirect_uris,
+        confidential: false,
+      };
+
+      assert_eq!(
+        sut
+          .register_oauth_client(&request, &admin())
+          .await
+          .map_err(|error| error.code),
+        Err(crate::domain::error::Code::InvalidArgument),
+        "{} should be refused",
+        uri
+      );
+    }
+  }
+
+  #[tokio::test]
+  async fn test_list_users_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_users_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::LIST_USERS, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = ListUsersRequest {
+      username: Some("john"),
+      sort: Some("username"),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      sut.list_users(&request, &admin()).await,
+      Ok(UserSummaryPage {
+        users: vec![user_summary()],
+        page: 1,
+        per_page: 20,
+        total: 1,
+      })
+    );
+  }
+
+  #[tokio::test]
+  async fn test_list_users_with_invalid_filters() {
+    for request in [
+      ListUsersRequest {
+        per_page: 500,
+        ..Default::default()
+      },
+      ListUsersRequest {
+        sort: Some("email"),
+        ..Default::default()
+      },
+      ListUsersRequest {
+        created_from: Some("23/11/2023"),
+        ..Default::default()
+      },
+    ] {
+      let sut = UserUseCase {
+        user_repository: &build_mock_user_repository(Expectations {
+          ..Default::default()
+        }),
+        services: &Services {
+          token: MockTokenService::new(),
+          email: MockEmailSender::new(),
+          sms: MockSmsSender::new(),
+          identity_provider: MockIdentityProviderClient::new(),
+          audit: audit_log_record(actions::LIST_USERS, AuditOutcome::Failure),
+          token_policy: TokenPolicy::default(),
+          sign_in_policy: SignInPolicy::default(),
+          email_verification_policy: EmailVerificationPolicy::default(),
+          telephone_verification_policy: TelephoneVerificationPolicy::default(),
+          password_reset_policy: PasswordResetPolicy::default(),
+          mfa_policy: MfaPolicy::default(),
+          webauthn_policy: WebAuthnPolicy::default(),
+          external_identity_policy: ExternalIdentityPolicy::default(),
+          oidc_policy: OidcPolicy::default(),
+        },
+        utilities: &Utilities {
+          crypto: MockCrypto::new(),
+          id_generator: MockIDGenerator::new(),
+          primitives: MockPrimitives::new(),
+        },
+      };
+
+      assert_eq!(
+        sut
+          .list_users(&request, &admin())
+          .await
+          .map_err(|error| error.code),
+        Err(crate::domain::error::Code::InvalidArgument)
+      );
+    }
+  }
+
+  #[tokio::test]
+  async fn test_list_users_with_non_admin_token() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::LIST_USERS, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    assert_eq!(
+      sut.list_users(&ListUsersRequest::default(), &user()).await,
+      Err(AppError::permission_denied(
+        "Given token not have permission for this operation"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_get_user_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_full_profile_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::VIEW_USER, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    assert_eq!(sut.get_user(USER_ID, &admin()).await, Ok(full_profile()));
+  }
+
+  #[tokio::test]
+  async fn test_force_password_reset_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_force_password_reset_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: email_sender_send_password_reset(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::FORCE_PASSWORD_RESET, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: id_generator_reset_token(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    assert_eq!(sut.force_password_reset(USER_ID, &admin()).await, Ok(()));
+  }
+
+  #[tokio::test]
+  async fn test_force_password_reset_with_non_admin_token() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::FORCE_PASSWORD_RESET, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    assert_eq!(
+      sut.force_password_reset(USER_ID, &user()).await,
+      Err(AppError::permission_denied(
+        "Given token not have permission for this operation"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_unlock_account_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_unlock_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::UNLOCK_USER, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    assert_eq!(sut.unlock_account(USER_ID, &admin()).await, Ok(()));
+  }
+
+  #[tokio::test]
+  async fn test_grant_role_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_grant_admin_role(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::GRANT_ROLE, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = SetRoleRequest {
+      profile_id: USER_ID,
+      role: ADMIN_ROLE,
+      granted: true,
+    };
+
+    assert_eq!(sut.set_role(&request, &admin()).await, Ok(true));
+  }
+
+  #[tokio::test]
+  async fn test_revoke_own_admin_role() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::REVOKE_ROLE, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = SetRoleRequest {
+      profile_id: ADMIN_ID,
+      role: ADMIN_ROLE,
+      granted: false,
+    };
+
+    assert_eq!(
+      sut.set_role(&request, &admin()).await,
+      Err(AppError::invalid_argument(
+        "Administrators cannot revoke their own admin role"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_list_audit_events_successfully() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_find_failed_sign_ins(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = ListAuditEventsRequest {
+      subject_id: Some(USER_ID),
+      action: Some(actions::SIGN_IN),
+      outcome: Some("failure"),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      sut.list_audit_events(&request, &admin()).await,
+      Ok(AuditEventPage {
+        events: vec![audit_event()],
+        page: 1,
+        per_page: 20,
+        total: 1,
+      })
+    );
+  }
+
+  #[tokio::test]
+  async fn test_list_audit_events_with_unknown_outcome() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::LIST_AUDIT_EVENTS, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = ListAuditEventsRequest {
+      outcome: Some("denied"),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      sut.list_audit_events(&request, &admin()).await,
+      Err(AppError::invalid_argument(
+        "unknown outcome denied, use success or failure"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_list_audit_events_with_non_admin_token() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::LIST_AUDIT_EVENTS, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    assert_eq!(
+      sut
+        .list_audit_events(&ListAuditEventsRequest::default(), &user())
+        .await,
+      Err(AppError::permission_denied(
+        "Given token not have permission for this operation"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_verify_audit_log_with_tampered_event() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_verify_tampered_chain(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    assert_eq!(
+      sut.verify_audit_log(&admin()).await,
+      Ok(AuditChainVerification {
+        checked: 3,
+        first_invalid_id: Some(2),
+      })
+    );
+  }
+
+  #[tokio::test]
+  async fn test_unlock_account_when_audit_log_unavailable() {
+    let sut = UserUseCase {
+      user_repository: &repository_unlock_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record_unavailable(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    assert_eq!(sut.unlock_account(USER_ID, &admin()).await, Ok(()));
+  }
+}
@@ -1,14 +1,15 @@
 #[cfg(test)]
-pub(self) mod test_utils;
+mod test_utils;
 use serde::Serialize;
 #[cfg(test)]
 use test_utils::*;
 
 pub mod user;
 
+use crate::application::services::security::authenticated_user::AuthenticatedUser;
 use crate::domain::{
   core::user::{
-    change_password, sign_in,
+    change_password, request_password_reset, sign_in,
     sign_up::{self, NotId},
   },
   entities::user::UserColumns,
@@ -24,9 +25,13 @@ use validator::Validate;
 #[async_trait]
 #[automock]
 pub trait UserAuthentication: Send + Sync {
-  async fn sign_in(
+  /// Accounts with two-factor authentication get a short-lived token to be
+  /// exchanged at `verify_mfa` instead of a session.
+  async fn sign_in(&self, request: &UserSignInRequest<'_>) -> Result<SignInResponse, AppError>;
+  /// Completes a sign-in waiting for the second factor.
+  async fn verify_mfa(
     &self,
-    request: &UserSignInRequest<'_>,
+    request: &VerifyMfaRequest<'_>,
   ) -> Result<UserAuthenticationResponse, AppError>;
   async fn register(
     &self,
@@ -35,8 +40,56 @@ pub trait UserAuthentication: Send + Sync {
   async fn update_password(
     &self,
     request: &ChangeUserPasswordRequest<'_>,
-    token: &str,
-  ) -> Result<(), AppError>;
+    user: &AuthenticatedUser,
+  ) -> Result<UserAuthenticationResponse, AppError>;
+  async fn refresh(
+    &self,
+    request: &RefreshTokenRequest<'_>,
+  ) -> Result<UserAuthenticationResponse, AppError>;
+  async fn logout(&self, user: &AuthenticatedUser) -> Result<(), AppError>;
+  /// Redeems an email verification token, returning the verified address.
+  async fn verify_email(&self, request: &VerifyEmailRequest<'_>) -> Result<String, AppError>;
+  /// Emails a password reset token when the account exists, and succeeds
+  /// either way so callers cannot tell which accounts exist.
+  async fn forgot_password(&self, request: &ForgotPasswordRequest<'_>) -> Result<(), AppError>;
+  /// Sets a new password with a reset token and revokes existing sessions.
+  async fn reset_password(&self, request: &ResetPasswordRequest<'_>) -> Result<(), AppError>;
+  /// Starts the registration of a passkey for `user`.
+  async fn webauthn_registration_options(
+    &self,
+    user: &AuthenticatedUser,
+  ) -> Result<WebAuthnRegistrationOptions, AppError>;
+  /// Stores the passkey created by the authenticator, returning its id.
+  async fn register_webauthn(
+    &self,
+    request: &RegisterWebAuthnRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<String, AppError>;
+  async fn webauthn_sign_in_options(
+    &self,
+    request: &WebAuthnSignInOptionsRequest<'_>,
+  ) -> Result<WebAuthnSignInOptions, AppError>;
+  /// Signs in with a passkey. The authenticator verifies the user, so no
+  /// second factor is asked for.
+  async fn webauthn_sign_in(
+    &self,
+    request: &WebAuthnSignInRequest<'_>,
+  ) -> Result<UserAuthenticationResponse, AppError>;
+  /// Starts a sign-in at an external identity provider, returning the URL
+  /// to send the browser to.
+  async fn start_external_sign_in(&self, provider: &str) -> Result<String, AppError>;
+  /// Completes the sign-in when the provider sends the browser back. A
+  /// provider account matching no profile is linked to the one with the
+  /// same verified email, or has to complete a sign-up.
+  async fn external_sign_in(
+    &self,
+    request: &ExternalSignInRequest<'_>,
+  ) -> Result<ExternalSignInResponse, AppError>;
+  /// Creates the profile of a provider account and links them.
+  async fn external_sign_up(
+    &self,
+    request: &ExternalSignUpRequest<'_>,
+  ) -> Result<UserAuthenticationResponse, AppError>;
 }
 
 #[derive(Validate, Default)]
@@ -52,6 +105,8 @@ pub struct UserSignInRequest<'a> {
   pub telephone: Option<&'a str>,
   #[validate(length(min = 8, message = "Password must contain minimum 8 characters!"))]
   pub password: &'a str,
+  pub ip_address: Option<&'a str>,
+  pub user_agent: Option<&'a str>,
 }
 
 impl<'a> TryFrom<&'a UserSignInRequest<'a>> for sign_in::Request<'a> {
@@ -68,6 +123,10 @@ impl<'a> TryFrom<&'a UserSignInRequest<'a>> for sign_in::Request<'a> {
     let req = sign_in::Request {
       column,
       password: value.password,
+      ip_address: value.ip_address,
+      user_agent: value.user_agent,
+      require_verified_email: false,
+      require_verified_telephone: false,
     };
 
     Ok(req)
@@ -111,6 +170,8 @@ pub struct UserRegistrationRequest<'a> {
   pub email: &'a str,
   #[validate(phone)]
   pub telephone: Option<&'a str>,
+  pub ip_address: Option<&'a str>,
+  pub user_agent: Option<&'a str>,
 }
 
 impl<'a> TryFrom<&'a UserRegistrationRequest<'a>> for sign_up::Request<'a, NotId, Password> {
@@ -145,6 +206,77 @@ pub struct UserAuthenticationResponse {
   pub name: String,
   pub username: String,
   pub token: String,
+  pub refresh_token: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SignInResponse {
+  Authenticated(UserAuthenticationResponse),
+  MfaRequired { mfa_token: String },
+}
+
+#[derive(Validate, Default)]
+pub struct VerifyMfaRequest<'a> {
+  #[validate(length(min = 1, message = "Please provide the token received at sign in!"))]
+  pub mfa_token: &'a str,
+  #[validate(length(equal = 6, message = "Authentication code must contain 6 digits!"))]
+  pub code: Option<&'a str>,
+  #[validate(length(min = 1, message = "Please provide a recovery code!"))]
+  pub recovery_code: Option<&'a str>,
+  pub ip_address: Option<&'a str>,
+  pub user_agent: Option<&'a str>,
+}
+
+#[derive(Validate, Default)]
+pub struct RefreshTokenRequest<'a> {
+  #[validate(length(min = 1, message = "Please provide a refresh token!"))]
+  pub refresh_token: &'a str,
+}
+
+#[derive(Validate, Default)]
+pub struct VerifyEmailRequest<'a> {
+  #[validate(length(min = 1, message = "Please provide a verification token!"))]
+  pub token: &'a str,
+}
+
+#[derive(Validate, Default)]
+pub struct ForgotPasswordRequest<'a> {
+  #[validate(length(
+    min = 5,
+    message = "Please provide a valid username, minimum 5 characters!"
+  ))]
+  pub username: Option<&'a str>,
+  #[validate(email)]
+  pub email: Option<&'a str>,
+  #[validate(length(min = 7, message = "Please provide a valid telephone number!"))]
+  pub telephone: Option<&'a str>,
+}
+
+impl<'a> TryFrom<&'a ForgotPasswordRequest<'a>> for request_password_reset::Request<'a> {
+  type Error = AppError;
+
+  fn try_from(value: &'a ForgotPasswordRequest) -> Result<Self, Self::Error> {
+    let column = value
+      .username
+      .map(UserColumns::Username)
+      .or_else(|| value.email.map(UserColumns::Email))
+      .or_else(|| value.telephone.map(UserColumns::Telephone))
+      .ok_or_else(|| AppError::invalid_argument("no username, email ou telephone provide"))?;
+
+    Ok(request_password_reset::Request { column })
+  }
+}
+
+#[derive(Validate, Default)]
+pub struct ResetPasswordRequest<'a> {
+  #[validate(length(min = 1, message = "Please provide a password reset token!"))]
+  pub token: &'a str,
+  #[validate(length(min = 8, message = "Password must contain minimum 8 characters!"))]
+  pub password: &'a str,
+  #[validate(must_match = "password")]
+  pub password_repetition: &'a str,
+  pub ip_address: Option<&'a str>,
+  pub user_agent: Option<&'a str>,
 }
 
 #[derive(Validate)]
@@ -171,3 +303,121 @@ impl<'a> From<&'a ChangeUserPasswordRequest<'a>> for change_password::Request<'a
     }
   }
 }
+
+/// What the client passes to `navigator.credentials.create()`.
+#[derive(Debug, PartialEq)]
+pub struct WebAuthnRegistrationOptions {
+  pub challenge: String,
+  pub rp_id: String,
+  pub rp_name: String,
+  pub user_id: String,
+  pub user_name: String,
+  pub user_display_name: String,
+  /// Passkeys already registered, so the authenticator does not create a
+  /// second one.
+  pub exclude_credentials: Vec<String>,
+  pub timeout_ms: i64,
+}
+
+/// What the client passes to `navigator.credentials.get()`.
+#[derive(Debug, PartialEq)]
+pub struct WebAuthnSignInOptions {
+  pub challenge: String,
+  pub rp_id: String,
+  pub allow_credentials: Vec<String>,
+  pub timeout_ms: i64,
+}
+
+#[derive(Validate, Default)]
+pub struct RegisterWebAuthnRequest<'a> {
+  #[validate(length(min = 1, message = "Please provide the credential id!"))]
+  pub credential_id: &'a str,
+  #[validate(length(min = 1, message = "Please provide the client data!"))]
+  pub client_data_json: &'a str,
+  #[validate(length(min = 1, message = "Please provide the attestation object!"))]
+  pub attestation_object: &'a str,
+  #[validate(length(
+    min = 1,
+    max = 64,
+    message = "Passkey name must contain between 1 and 64 characters!"
+  ))]
+  pub name: Option<&'a str>,
+}
+
+#[derive(Validate, Default)]
+pub struct WebAuthnSignInOptionsRequest<'a> {
+  #[validate(length(
+    min = 5,
+    message = "Please provide a valid username, minimum 5 characters!"
+  ))]
+  pub username: &'a str,
+}
+
+#[derive(Validate, Default)]
+pub struct WebAuthnSignInRequest<'a> {
+  #[validate(length(min = 1, message = "Please provide the credential id!"))]
+  pub credential_id: &'a str,
+  #[validate(length(min = 1, message = "Please provide the client data!"))]
+  pub client_data_json: &'a str,
+  #[validate(length(min = 1, message = "Please provide the authenticator data!"))]
+  pub authenticator_data: &'a str,
+  #[validate(length(min = 1, message = "Please provide the signature!"))]
+  pub signature: &'a str,
+  pub ip_address: Option<&'a str>,
+  pub user_agent: Option<&'a str>,
+}
+
+#[derive(Validate, Default)]
+pub struct ExternalSignInRequest<'a> {
+  pub provider: &'a str,
+  #[validate(length(min = 1, message = "Please provide the authorization code!"))]
+  pub code: &'a str,
+  #[validate(length(min = 1, message = "Please provide the state!"))]
+  pub state: &'a str,
+  pub ip_address: Option<&'a str>,
+  pub user_agent: Option<&'a str>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ExternalSignInResponse {
+  SignedIn(SignInResponse),
+  /// The provider account matched no profile, `signup_token` completes its
+  /// sign-up. `name` and `email` are what the provider shared.
+  SignUpRequired {
+    signup_token: String,
+    name: Option<String>,
+    email: String,
+  },
+}
+
+#[derive(Validate, Default)]
+pub struct ExternalSignUpRequest<'a> {
+  #[validate(length(min = 1, message = "Please provide the sign-up token!"))]
+  pub signup_token: &'a str,
+  #[validate(length(
+    min = 5,
+    message = "Please provide a valid name, minimum 5 characters!!"
+  ))]
+  pub name: &'a str,
+  pub birth_date: &'a str,
+  #[validate(range(min = 1))]
+  pub gender_id: i32,
+  #[validate(length(
+    min = 3,
+    message = "Please provide a valid street, minimum 3 characters!!"
+  ))]
+  pub address_street: &'a str,
+  #[validate(length(
+    min = 3,
+    message = "Please provide a valid neighborhood, minimum 3 characters!!"
+  ))]
+  pub address_neighborhood: &'a str,
+  #[validate(range(min = 1))]
+  pub address_city_id: i32,
+  #[validate(range(min = 1))]
+  pub address_postal_code: i32,
+  #[validate(phone)]
+  pub telephone: Option<&'a str>,
+  pub ip_address: Option<&'a str>,
+  pub user_agent: Option<&'a str>,
+}
@@ -1,54 +1,476 @@
 use super::{
-  ChangeUserPasswordRequest, UserAuthentication, UserAuthenticationResponse,
-  UserRegistrationRequest, UserSignInRequest,
+  ChangeUserPasswordRequest, ExternalSignInRequest, ExternalSignInResponse, ExternalSignUpRequest,
+  ForgotPasswordRequest, RefreshTokenRequest, RegisterWebAuthnRequest, ResetPasswordRequest,
+  SignInResponse, UserAuthentication, UserAuthenticationResponse, UserRegistrationRequest,
+  UserSignInRequest, VerifyEmailRequest, VerifyMfaRequest, WebAuthnRegistrationOptions,
+  WebAuthnSignInOptions, WebAuthnSignInOptionsRequest, WebAuthnSignInRequest,
 };
 use crate::{
-  application::services::{security::token_service::TokenService, Services},
+  application::services::{
+    audit_log::AuditLog,
+    notification::{
+      email_sender::{EmailMessage, EmailSender},
+      sms_sender::SmsSender,
+    },
+    security::{
+      authenticated_user::AuthenticatedUser, identity_provider::IdentityProviderClient,
+      token_service::TokenService,
+    },
+    Services,
+  },
   domain::{
-    core::user::{repository::UserRepository, User},
-    error::AppError,
-    utilities::{crypto::Crypto, id_generator::IDGenerator, Utilities},
+    core::user::{
+      change_password, create_session, external_sign_in, external_sign_up, get_grants, get_profile,
+      issue_refresh_token, issue_verification_token, issue_webauthn_challenge,
+      redeem_password_reset, register_webauthn, repository::UserRepository,
+      request_password_reset::ResetTarget, revoke_sessions, rotate_refresh_token, sign_in,
+      sign_in::Lockout, sign_up, start_external_sign_in, verify_email, verify_mfa,
+      webauthn_sign_in, User,
+    },
+    entities::{
+      audit_event::{actions, AuditOutcome, NewAuditEvent},
+      external_identity::{ExternalAccount, NewExternalIdentity},
+      role::Grants,
+      sign_in_event::SignInMethod,
+      user::{PublicUserData, UserColumns},
+      verification_token::VerificationPurpose,
+      webauthn::WebAuthnChallenge,
+    },
+    error::{AppError, Code},
+    types::Password,
+    utilities::{crypto::Crypto, id_generator::IDGenerator, primitives::Primitives, Utilities},
   },
 };
 use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use log::error;
+use serde_json::{json, Map, Value};
 use validator::Validate;
 
-pub struct UserUseCase<'a, Repository, Token: TokenService, C: Crypto, ID: IDGenerator> {
+/// Sign-in attempt for the audit log, naming the profile once it is known.
+fn sign_in_event(
+  user: &Result<PublicUserData, AppError>,
+  metadata: Value,
+  ip_address: Option<&str>,
+  user_agent: Option<&str>,
+) -> NewAuditEvent {
+  let profile_id = user.as_ref().ok().map(|user| user.id.clone());
+
+  NewAuditEvent {
+    actor_id: profile_id.clone(),
+    subject_id: profile_id,
+    action: actions::SIGN_IN.to_string(),
+    ip_address: ip_address.map(str::to_string),
+    user_agent: user_agent.map(str::to_string),
+    metadata,
+    ..Default::default()
+  }
+}
+
+pub struct UserUseCase<
+  'a,
+  Repository,
+  Token: TokenService,
+  Email: EmailSender,
+  Sms: SmsSender,
+  Idp: IdentityProviderClient,
+  Audit: AuditLog,
+  C: Crypto,
+  ID: IDGenerator,
+  P: Primitives,
+> {
   pub user_repository: &'a Repository,
-  pub services: &'a Services<Token>,
-  pub utilities: &'a Utilities<C, ID>,
+  pub services: &'a Services<Token, Email, Sms, Idp, Audit>,
+  pub utilities: &'a Utilities<C, ID, P>,
+}
+
+impl<
+    Repository: UserRepository,
+    Token: TokenService,
+    Email: EmailSender,
+    Sms: SmsSender,
+    Idp: IdentityProviderClient,
+    Audit: AuditLog,
+    C: Crypto,
+    ID: IDGenerator,
+    P: Primitives,
+  > UserUseCase<'_, Repository, Token, Email, Sms, Idp, Audit, C, ID, P>
+{
+  /// Appends the outcome of an action to the audit log. The action is already
+  /// done by then, so an event that cannot be recorded is logged and the
+  /// result of the action returned all the same.
+  pub(crate) async fn audit<T>(
+    &self,
+    event: NewAuditEvent,
+    result: Result<T, AppError>,
+  ) -> Result<T, AppError> {
+    let mut metadata = match event.metadata {
+      Value::Object(metadata) => metadata,
+      _ => Map::new(),
+    };
+    let outcome = match &result {
+      Ok(_) => AuditOutcome::Success,
+      Err(error) => {
+        metadata.insert("error".to_string(), json!(error.message));
+        AuditOutcome::Failure
+      }
+    };
+    let event = NewAuditEvent {
+      outcome,
+      metadata: Value::Object(metadata),
+      ..event
+    };
+
+    if let Err(audit_error) = self.services.audit.record(&event).await {
+      error!(target: "audit", "{} not recorded: {}", event.action, audit_error);
+    }
+
+    result
+  }
+
+  async fn issue_refresh_token(
+    &self,
+    profile_id: &str,
+    family_id: Option<&str>,
+  ) -> Result<String, AppError> {
+    let refresh_token = User::new(self.user_repository)
+      .issue_refresh_token(issue_refresh_token::Request {
+        profile_id,
+        family_id,
+        expires_in_min: self.services.token_policy.refresh_token_expiration_min,
+      })
+      .generate(&self.utilities.id_generator)
+      .store()
+      .await?
+      .response();
+
+    Ok(refresh_token)
+  }
+
+  /// Issues a single-use token for `address` and mails it to the user.
+  pub(crate) async fn send_email_verification(
+    &self,
+    profile_id: &str,
+    address: &str,
+  ) -> Result<(), AppError> {
+    let token = User::new(self.user_repository)
+      .issue_verification_token(issue_verification_token::Request {
+        profile_id,
+        purpose: VerificationPurpose::Email,
+        target: address,
+        expires_in_min: self.services.email_verification_policy.token_expiration_min,
+      })
+      .generate(&self.utilities.id_generator)
+      .store()
+      .await?
+      .response();
+
+    self
+      .services
+      .email
+      .send(&EmailMessage {
+        to: address.to_string(),
+        subject: "Verify your email address".to_string(),
+        body: format!(
+          "Use the following token to verify your email address: {}",
+          token
+        ),
+      })
+      .await
+  }
+
+  /// Issues a single-use password reset token and mails it to the target.
+  pub(crate) async fn send_password_reset(&self, target: ResetTarget) -> Result<(), AppError> {
+    let token = User::new(self.user_repository)
+      .issue_verification_token(issue_verification_token::Request {
+        profile_id: &target.profile_id,
+        purpose: VerificationPurpose::PasswordReset,
+        target: &target.address,
+        expires_in_min: self.services.password_reset_policy.token_expiration_min,
+      })
+      .generate(&self.utilities.id_generator)
+      .store()
+      .await?
+      .response();
+
+    self
+      .services
+      .email
+      .send(&EmailMessage {
+        to: target.address,
+        subject: "Reset your password".to_string(),
+        body: format!(
+          "Use the following token to choose a new password: {}",
+          token
+        ),
+      })
+      .await
+  }
+
+  async fn issue_webauthn_challenge(
+    &self,
+    column: UserColumns<'_>,
+    purpose: VerificationPurpose,
+  ) -> Result<WebAuthnChallenge, AppError> {
+    let policy = &self.services.webauthn_policy;
+
+    User::new(self.user_repository)
+      .issue_webauthn_challenge(issue_webauthn_challenge::Request {
+        column,
+        purpose,
+        rp_id: &policy.rp_id,
+        expires_in_min: policy.challenge_expiration_min,
+      })
+      .get_user()
+      .await?
+      .generate(&self.utilities.id_generator)
+      .store()
+      .await
+      .map(|user| user.response())
+  }
+
+  /// Accounts with two-factor authentication only get a token for it.
+  async fn start_session(
+    &self,
+    user: PublicUserData,
+    ip_address: Option<&str>,
+    user_agent: Option<&str>,
+  ) -> Result<SignInResponse, AppError> {
+    if user.mfa_enabled {
+      let version = self.user_repository.find_token_version(&user.id).await?;
+      let mfa_token = self.services.token.encode(
+        user.id,
+        self.services.token_policy.mfa_audience.clone(),
+        Grants::default(),
+        None,
+        version,
+        self.services.mfa_policy.token_expiration_min,
+      )?;
+
+      return Ok(SignInResponse::MfaRequired { mfa_token });
+    }
+
+    let (token, refresh_token) = self.open_session(&user.id, ip_address, user_agent).await?;
+
+    Ok(SignInResponse::Authenticated(UserAuthenticationResponse {
+      id: user.id,
+      name: user.name,
+      username: user.username,
+      token,
+      refresh_token,
+    }))
+  }
+
+  /// Records where the user signed in. The session id names the refresh
+  /// token family and goes in the `sid` claim of every access token of it.
+  async fn open_session(
+    &self,
+    profile_id: &str,
+    ip_address: Option<&str>,
+    user_agent: Option<&str>,
+  ) -> Result<(String, String), AppError> {
+    let session_id = User::new(self.user_repository)
+      .create_session(create_session::Request {
+        profile_id,
+        ip_address,
+        user_agent,
+      })
+      .generate(&self.utilities.id_generator)
+      .store()
+      .await?
+      .response();
+
+    let token = self
+      .encode_access_token(profile_id.to_string(), session_id.clone())
+      .await?;
+    let refresh_token = self
+      .issue_refresh_token(profile_id, Some(&session_id))
+      .await?;
+
+    Ok((token, refresh_token))
+  }
+
+  /// Ends sessions along with the access tokens still alive in them.
+  pub(crate) async fn revoke_sessions(
+    &self,
+    profile_id: &str,
+    sessions: revoke_sessions::Sessions<'_>,
+  ) -> Result<Vec<String>, AppError> {
+    let revoked = User::new(self.user_repository)
+      .revoke_sessions(revoke_sessions::Request {
+        profile_id,
+        sessions,
+      })
+      .revoke()
+      .await?
+      .response();
+
+    // access tokens of the session outlive it until they expire
+    let expires_at = Utc::now().timestamp() as usize
+      + 60 * self.services.token_policy.access_token_expiration_min as usize;
+    for session_id in &revoked {
+      self.services.token.revoke(session_id, expires_at).await?;
+    }
+
+    Ok(revoked)
+  }
+
+  /// Access tokens carry the roles of the profile and their permissions, and
+  /// the token version so a password update revokes them.
+  async fn encode_access_token(
+    &self,
+    profile_id: String,
+    session_id: String,
+  ) -> Result<String, AppError> {
+    let grants = User::new(self.user_repository)
+      .get_grants(get_grants::Request {
+        profile_id: &profile_id,
+      })
+      .find_grants()
+      .await?
+      .response();
+    let version = self.user_repository.find_token_version(&profile_id).await?;
+    let policy = &self.services.token_policy;
+
+    self.services.token.encode(
+      profile_id,
+      policy.authentication_audience.clone(),
+      grants,
+      Some(session_id),
+      version,
+      policy.access_token_expiration_min,
+    )
+  }
 }
 
 #[async_trait]
-impl<Repository: UserRepository, Token: TokenService, C: Crypto, ID: IDGenerator> UserAuthentication
-  for UserUseCase<'_, Repository, Token, C, ID>
+impl<
+    Repository: UserRepository,
+    Token: TokenService,
+    Email: EmailSender,
+    Sms: SmsSender,
+    Idp: IdentityProviderClient,
+    Audit: AuditLog,
+    C: Crypto,
+    ID: IDGenerator,
+    P: Primitives,
+  > UserAuthentication for UserUseCase<'_, Repository, Token, Email, Sms, Idp, Audit, C, ID, P>
 {
-  async fn sign_in(
+  async fn sign_in(&self, request: &UserSignInRequest<'_>) -> Result<SignInResponse, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let policy = &self.services.sign_in_policy;
+    let lockout = Lockout {
+      max_failed_attempts: policy.max_failed_attempts,
+      duration_min: policy.lockout_duration_min,
+    };
+
+    let sign_in_request = sign_in::Request {
+      require_verified_email: self.services.email_verification_policy.required_for_sign_in,
+      require_verified_telephone: self
+        .services
+        .telephone_verification_policy
+        .required_for_sign_in,
+      ..request.try_into()?
+    };
+    let metadata = json!({
+      "method": SignInMethod::try_from(&sign_in_request.column)?.as_str(),
+      "identifier": request.username.or(request.email).or(request.telephone),
+    });
+
+    let user = async {
+      let user = User::new(self.user_repository)
+        .sign_in(sign_in_request)
+        .get_user()
+        .await?
+        .check_password(&self.utilities.crypto, &lockout)
+        .await?
+        .rehash_password(&self.utilities.crypto)
+        .await
+        .response();
+
+      Ok(user)
+    }
+    .await;
+
+    let event = sign_in_event(&user, metadata, request.ip_address, request.user_agent);
+    let user = self.audit(event, user).await?;
+
+    self
+      .start_session(user, request.ip_address, request.user_agent)
+      .await
+  }
+
+  async fn verify_mfa(
     &self,
-    request: &UserSignInRequest<'_>,
+    request: &VerifyMfaRequest<'_>,
   ) -> Result<UserAuthenticationResponse, AppError> {
     request
       .validate()
       .map_err(|err| AppError::invalid_argument(err.to_string()))?;
 
-    let user = User::new(self.user_repository)
-      .sign_in(request.try_into()?)
-      .get_user()
+    let token_decoded = self.services.token.decode(request.mfa_token).await?;
+    if token_decoded.aud != self.services.token_policy.mfa_audience {
+      return Err(AppError::unauthenticated(
+        "Given token is not valid for this service",
+      ));
+    }
+
+    let sign_in_policy = &self.services.sign_in_policy;
+    let checked = User::new(self.user_repository)
+      .verify_mfa(verify_mfa::Request {
+        profile_id: &token_decoded.sub,
+        code: request.code,
+        recovery_code: request.recovery_code,
+        allowed_drift_steps: self.services.mfa_policy.allowed_drift_steps,
+        lockout: Lockout {
+          max_failed_attempts: sign_in_policy.max_failed_attempts,
+          duration_min: sign_in_policy.lockout_duration_min,
+        },
+        ip_address: request.ip_address,
+        user_agent: request.user_agent,
+      })
+      .get_data()
       .await?
-      .check_password(&self.utilities.crypto)?
-      .response();
+      .check_code(&self.utilities.primitives)
+      .await
+      .map(|user| user.response());
 
-    let token =
+    // The pending token is single-use once accepted, and is dropped as well
+    // when guessing codes blocked the account.
+    let locked = matches!(&checked, Err(error) if error.code == Code::Locked);
+    if checked.is_ok() || locked {
       self
         .services
         .token
-        .encode(user.id.clone(), String::from("authentication_user"), 120)?;
+        .revoke(&token_decoded.jti, token_decoded.exp)
+        .await?;
+    }
+
+    let event = NewAuditEvent {
+      actor_id: Some(token_decoded.sub.clone()),
+      subject_id: Some(token_decoded.sub.clone()),
+      ..sign_in_event(
+        &checked,
+        json!({ "method": "mfa" }),
+        request.ip_address,
+        request.user_agent,
+      )
+    };
+    let user = self.audit(event, checked).await?;
+
+    let (token, refresh_token) = self
+      .open_session(&user.id, request.ip_address, request.user_agent)
+      .await?;
 
     Ok(UserAuthenticationResponse {
       id: user.id,
       name: user.name,
       username: user.username,
       token,
+      refresh_token,
     })
   }
 
@@ -60,206 +482,2171 @@ impl<Repository: UserRepository, Token: TokenService, C: Crypto, ID: IDGenerator
       .validate()
       .map_err(|err| AppError::invalid_argument(err.to_string()))?;
 
-    let user_id = User::new(self.user_repository)
-      .sign_up(request.try_into()?)
-      .encrypt_password(&self.utilities.crypto)?
-      .create_id(&self.utilities.id_generator)
-      .store()
-      .await?
-      .response();
+    let user_id = async {
+      let user_id = User::new(self.user_repository)
+        .sign_up(request.try_into()?)
+        .encrypt_password(&self.utilities.crypto)?
+        .create_id(&self.utilities.id_generator)
+        .store()
+        .await?
+        .response();
+
+      Ok(user_id)
+    }
+    .await;
+
+    let profile_id = user_id.as_ref().ok().cloned();
+    let event = NewAuditEvent {
+      actor_id: profile_id.clone(),
+      subject_id: profile_id,
+      action: actions::REGISTER.to_string(),
+      ip_address: request.ip_address.map(str::to_string),
+      user_agent: request.user_agent.map(str::to_string),
+      metadata: json!({ "username": request.username }),
+      ..Default::default()
+    };
+    let user_id = self.audit(event, user_id).await?;
+
+    // the account is already committed, the address just stays unverified
+    if let Err(error) = self.send_email_verification(&user_id, request.email).await {
+      error!("email verification delivery failed: {}", error);
+    }
 
-    let token = self.services.token.encode(
-      user_id.to_string(),
-      String::from("authentication_user"),
-      120,
-    )?;
+    let (token, refresh_token) = self
+      .open_session(&user_id, request.ip_address, request.user_agent)
+      .await?;
 
     Ok(UserAuthenticationResponse {
       id: user_id.to_string(),
       name: request.name.to_string(),
       username: request.username.to_string(),
       token,
+      refresh_token,
+    })
+  }
+
+  async fn update_password(
+    &self,
+    request: &ChangeUserPasswordRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<UserAuthenticationResponse, AppError> {
+    let result = async {
+      if user.profile_id != request.profile_id {
+        return Err(AppError::permission_denied(
+          "Given token not have permission for this profile",
+        ));
+      }
+
+      request
+        .validate()
+        .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+      let profile = User::new(self.user_repository)
+        .change_password(request.into())
+        .get_user()
+        .await?
+        .check_password(&self.utilities.crypto)?
+        .encrypt_password(&self.utilities.crypto)?
+        .save()
+        .await?
+        .revoke_sessions()
+        .await?
+        .response();
+
+      Ok(profile)
+    }
+    .await;
+
+    let event = user.audit_event(actions::CHANGE_PASSWORD, Some(request.profile_id));
+    let profile = self.audit(event, result).await?;
+
+    // saving bumped the token version, only the current session goes on
+    let (token, refresh_token) = match &user.session_id {
+      Some(session_id) => {
+        let refresh_token = self
+          .issue_refresh_token(&profile.id, Some(session_id))
+          .await?;
+        let token = self
+          .encode_access_token(profile.id.clone(), session_id.clone())
+          .await?;
+
+        (token, refresh_token)
+      }
+      None => {
+        self
+          .open_session(
+            &profile.id,
+            user.ip_address.as_deref(),
+            user.user_agent.as_deref(),
+          )
+          .await?
+      }
+    };
+
+    Ok(UserAuthenticationResponse {
+      id: profile.id,
+      name: profile.name,
+      username: profile.username,
+      token,
+      refresh_token,
+    })
+  }
+
+  async fn refresh(
+    &self,
+    request: &RefreshTokenRequest<'_>,
+  ) -> Result<UserAuthenticationResponse, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let rotated = User::new(self.user_repository)
+      .rotate_refresh_token(rotate_refresh_token::Request {
+        token: request.refresh_token,
+      })
+      .get_token()
+      .await?
+      .check_token()
+      .await?
+      .response();
+
+    let refresh_token = self
+      .issue_refresh_token(&rotated.profile_id, Some(&rotated.family_id))
+      .await?;
+
+    let profile = User::new(self.user_repository)
+      .get_profile(get_profile::Request {
+        profile_id: &rotated.profile_id,
+      })
+      .find_profile()
+      .await?
+      .response();
+
+    let token = self
+      .encode_access_token(profile.id.clone(), rotated.family_id)
+      .await?;
+
+    Ok(UserAuthenticationResponse {
+      id: profile.id,
+      name: profile.name,
+      username: profile.username,
+      token,
+      refresh_token,
+    })
+  }
+
+  async fn logout(&self, user: &AuthenticatedUser) -> Result<(), AppError> {
+    self
+      .services
+      .token
+      .revoke(&user.token_id, user.expires_at)
+      .await?;
+
+    if let Some(session_id) = &user.session_id {
+      self
+        .revoke_sessions(&user.profile_id, revoke_sessions::Sessions::One(session_id))
+        .await?;
+    }
+
+    Ok(())
+  }
+
+  async fn verify_email(&self, request: &VerifyEmailRequest<'_>) -> Result<String, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let address = User::new(self.user_repository)
+      .verify_email(verify_email::Request {
+        token: request.token,
+      })
+      .get_token()
+      .await?
+      .check_token()
+      .await?
+      .save()
+      .await?
+      .response();
+
+    Ok(address)
+  }
+
+  async fn forgot_password(&self, request: &ForgotPasswordRequest<'_>) -> Result<(), AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let target = match User::new(self.user_repository)
+      .request_password_reset(request.try_into()?)
+      .find_target()
+      .await
+    {
+      Ok(user) => user.response(),
+      Err(error) if error.code == Code::NotFound => return Ok(()),
+      Err(error) => return Err(error),
+    };
+
+    // answered like an unknown account, a delivery error would tell them apart
+    if let Err(error) = self.send_password_reset(target).await {
+      error!("password reset delivery failed: {}", error);
+    }
+
+    Ok(())
+  }
+
+  async fn reset_password(&self, request: &ResetPasswordRequest<'_>) -> Result<(), AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let token = async {
+      let token = User::new(self.user_repository)
+        .redeem_password_reset(redeem_password_reset::Request {
+          token: request.token,
+        })
+        .get_token()
+        .await?
+        .check_token()
+        .await?
+        .response();
+
+      Ok::<_, AppError>(token)
+    }
+    .await;
+
+    let profile_id = token.as_ref().ok().map(|token| token.profile_id.clone());
+    let result = async {
+      let token = token?;
+
+      User::new(self.user_repository)
+        .change_password(change_password::Request {
+          profile_id: &token.profile_id,
+          password: request.password,
+          ..Default::default()
+        })
+        .check_reset_token(&token)?
+        .encrypt_password(&self.utilities.crypto)?
+        .save()
+        .await?
+        .revoke_sessions()
+        .await?;
+
+      Ok(())
+    }
+    .await;
+
+    let event = NewAuditEvent {
+      actor_id: profile_id.clone(),
+      subject_id: profile_id,
+      action: actions::RESET_PASSWORD.to_string(),
+      ip_address: request.ip_address.map(str::to_string),
+      user_agent: request.user_agent.map(str::to_string),
+      ..Default::default()
+    };
+    self.audit(event, result).await
+  }
+
+  async fn webauthn_registration_options(
+    &self,
+    user: &AuthenticatedUser,
+  ) -> Result<WebAuthnRegistrationOptions, AppError> {
+    let challenge = self
+      .issue_webauthn_challenge(
+        UserColumns::Id(&user.profile_id),
+        VerificationPurpose::WebAuthnRegistration,
+      )
+      .await?;
+    let policy = &self.services.webauthn_policy;
+
+    Ok(WebAuthnRegistrationOptions {
+      challenge: challenge.challenge,
+      rp_id: policy.rp_id.clone(),
+      rp_name: policy.rp_name.clone(),
+      user_id: challenge.profile_id,
+      user_name: challenge.username,
+      user_display_name: challenge.name,
+      exclude_credentials: challenge.credential_ids,
+      timeout_ms: policy.challenge_expiration_min * 60_000,
+    })
+  }
+
+  async fn register_webauthn(
+    &self,
+    request: &RegisterWebAuthnRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<String, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let policy = &self.services.webauthn_policy;
+    let credential_id = User::new(self.user_repository)
+      .register_webauthn(register_webauthn::Request {
+        profile_id: &user.profile_id,
+        rp_id: &policy.rp_id,
+        origin: &policy.origin,
+        credential_id: request.credential_id,
+        client_data_json: request.client_data_json,
+        attestation_object: request.attestation_object,
+        name: request.name.unwrap_or("Passkey"),
+      })
+      .get_challenge()
+      .await?
+      .check_attestation(&self.utilities.primitives)?
+      .save()
+      .await?
+      .response();
+
+    Ok(credential_id)
+  }
+
+  async fn webauthn_sign_in_options(
+    &self,
+    request: &WebAuthnSignInOptionsRequest<'_>,
+  ) -> Result<WebAuthnSignInOptions, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let challenge = self
+      .issue_webauthn_challenge(
+        UserColumns::Username(request.username),
+        VerificationPurpose::WebAuthnSignIn,
+      )
+      .await?;
+    let policy = &self.services.webauthn_policy;
+
+    Ok(WebAuthnSignInOptions {
+      challenge: challenge.challenge,
+      rp_id: policy.rp_id.clone(),
+      allow_credentials: challenge.credential_ids,
+      timeout_ms: policy.challenge_expiration_min * 60_000,
     })
   }
 
-  async fn update_password(
-    &self,
-    request: &ChangeUserPasswordRequest<'_>,
-    token: &str,
-  ) -> Result<(), AppError> {
-    let token_decoded = self.services.token.decode(token)?;
-    if token_decoded.aud != "authentication_user" {
-      return Err(AppError::unauthenticated(
-        "Given token is not valid for this service",
-      ));
-    } else if token_decoded.sub != request.profile_id {
-      return Err(AppError::permission_denied(
-        "Given token not have permission for this profile",
-      ));
+  async fn webauthn_sign_in(
+    &self,
+    request: &WebAuthnSignInRequest<'_>,
+  ) -> Result<UserAuthenticationResponse, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let policy = &self.services.webauthn_policy;
+    let sign_in_policy = &self.services.sign_in_policy;
+    let user = async {
+      let user = User::new(self.user_repository)
+        .webauthn_sign_in(webauthn_sign_in::Request {
+          credential_id: request.credential_id,
+          client_data_json: request.client_data_json,
+          authenticator_data: request.authenticator_data,
+          signature: request.signature,
+          rp_id: &policy.rp_id,
+          origin: &policy.origin,
+          ip_address: request.ip_address,
+          user_agent: request.user_agent,
+          lockout: Lockout {
+            max_failed_attempts: sign_in_policy.max_failed_attempts,
+            duration_min: sign_in_policy.lockout_duration_min,
+          },
+        })
+        .get_data()
+        .await?
+        .check_assertion(&self.utilities.primitives)
+        .await?
+        .response();
+
+      Ok(user)
+    }
+    .await;
+
+    let metadata = json!({ "method": SignInMethod::Passkey.as_str() });
+    let event = sign_in_event(&user, metadata, request.ip_address, request.user_agent);
+    let user = self.audit(event, user).await?;
+
+    let (token, refresh_token) = self
+      .open_session(&user.id, request.ip_address, request.user_agent)
+      .await?;
+
+    Ok(UserAuthenticationResponse {
+      id: user.id,
+      name: user.name,
+      username: user.username,
+      token,
+      refresh_token,
+    })
+  }
+
+  async fn start_external_sign_in(&self, provider: &str) -> Result<String, AppError> {
+    let policy = &self.services.external_identity_policy;
+    let provider = policy.provider(provider)?;
+
+    let start = User::new(self.user_repository)
+      .start_external_sign_in(start_external_sign_in::Request {
+        provider: &provider.name,
+        expires_in_min: policy.state_expiration_min,
+      })
+      .generate(&self.utilities.id_generator)
+      .store()
+      .await?
+      .response(&self.utilities.primitives);
+
+    Ok(provider.authorization_url(&start))
+  }
+
+  async fn external_sign_in(
+    &self,
+    request: &ExternalSignInRequest<'_>,
+  ) -> Result<ExternalSignInResponse, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let policy = &self.services.external_identity_policy;
+    let sign_in_policy = &self.services.sign_in_policy;
+    let provider = policy.provider(request.provider)?;
+
+    let account = async {
+      let user = User::new(self.user_repository)
+        .external_sign_in(external_sign_in::Request {
+          provider: &provider.name,
+          state: request.state,
+          issuer: &provider.issuer,
+          client_id: &provider.client_id,
+          ip_address: request.ip_address,
+          user_agent: request.user_agent,
+          lockout: Lockout {
+            max_failed_attempts: sign_in_policy.max_failed_attempts,
+            duration_min: sign_in_policy.lockout_duration_min,
+          },
+          sign_up_expires_in_min: policy.sign_up_expiration_min,
+        })
+        .get_data()
+        .await?;
+
+      let id_token = self
+        .services
+        .identity_provider
+        .exchange_code(provider, request.code, user.code_verifier())
+        .await?;
+
+      Ok(user.check_id_token(id_token)?.resolve().await?.response())
+    }
+    .await;
+
+    // a pending sign-up is not a sign-in yet, it is recorded on registration
+    let user = match account {
+      Ok(ExternalAccount::SignUpPending(claims)) => {
+        return Ok(ExternalSignInResponse::SignUpRequired {
+          signup_token: request.state.to_string(),
+          name: claims.name,
+          email: claims.email,
+        })
+      }
+      Ok(ExternalAccount::SignedIn(user)) => Ok(user),
+      Err(error) => Err(error),
+    };
+
+    let metadata = json!({
+      "method": SignInMethod::External.as_str(),
+      "provider": provider.name,
+    });
+    let event = sign_in_event(&user, metadata, request.ip_address, request.user_agent);
+    let user = self.audit(event, user).await?;
+
+    Ok(ExternalSignInResponse::SignedIn(
+      self
+        .start_session(user, request.ip_address, request.user_agent)
+        .await?,
+    ))
+  }
+
+  async fn external_sign_up(
+    &self,
+    request: &ExternalSignUpRequest<'_>,
+  ) -> Result<UserAuthenticationResponse, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let birth_date = NaiveDate::parse_from_str(request.birth_date, "%Y-%m-%d")
+      .map_err(|_| AppError::invalid_argument("birth date format invalid"))?;
+
+    let registered = async {
+      let sign_up = User::new(self.user_repository)
+        .external_sign_up(external_sign_up::Request {
+          state: request.signup_token,
+        })
+        .get_data()
+        .await?
+        .generate_username(&self.utilities.id_generator)
+        .response();
+      let claims = &sign_up.claims;
+
+      // Nobody knows the password, the user can set one with a password reset.
+      let user_id = User::new(self.user_repository)
+        .sign_up(sign_up::Request {
+          name: request.name,
+          username: &sign_up.username,
+          birth_date,
+          gender_id: request.gender_id,
+          password: Password(self.utilities.id_generator.new_secret()),
+          street: request.address_street,
+          neighborhood: request.address_neighborhood,
+          city_id: request.address_city_id,
+          postal_code: request.address_postal_code,
+          email_address: &claims.email,
+          telephone_number: request.telephone,
+          ..Default::default()
+        })
+        .encrypt_password(&self.utilities.crypto)?
+        .create_id(&self.utilities.id_generator)
+        .store()
+        .await?
+        .response();
+
+      self
+        .user_repository
+        .store_external_identity(&NewExternalIdentity {
+          provider: sign_up.provider.clone(),
+          subject: claims.subject.clone(),
+          profile_id: user_id.clone(),
+          email: Some(claims.email.clone()),
+        })
+        .await?;
+
+      Ok((sign_up, user_id))
+    }
+    .await;
+
+    let profile_id = registered.as_ref().ok().map(|(_, user_id)| user_id.clone());
+    let metadata = match &registered {
+      Ok((sign_up, _)) => json!({ "username": sign_up.username, "provider": sign_up.provider }),
+      Err(_) => json!({}),
+    };
+    let event = NewAuditEvent {
+      actor_id: profile_id.clone(),
+      subject_id: profile_id,
+      action: actions::REGISTER.to_string(),
+      ip_address: request.ip_address.map(str::to_string),
+      user_agent: request.user_agent.map(str::to_string),
+      metadata,
+      ..Default::default()
+    };
+    let (sign_up, user_id) = self.audit(event, registered).await?;
+    let claims = &sign_up.claims;
+
+    if claims.email_verified {
+      self
+        .user_repository
+        .check_email(&user_id, &claims.email)
+        .await?;
+    } else if let Err(error) = self.send_email_verification(&user_id, &claims.email).await {
+      error!("email verification delivery failed: {}", error);
+    }
+
+    let (token, refresh_token) = self
+      .open_session(&user_id, request.ip_address, request.user_agent)
+      .await?;
+
+    Ok(UserAuthenticationResponse {
+      id: user_id,
+      name: request.name.to_string(),
+      username: sign_up.username,
+      token,
+      refresh_token,
+    })
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::super::*;
+  use super::*;
+  use crate::{
+    application::services::{
+      audit_log::MockAuditLog,
+      notification::{email_sender::MockEmailSender, sms_sender::MockSmsSender},
+      security::{
+        email_verification_policy::EmailVerificationPolicy,
+        external_identity_policy::ExternalIdentityPolicy,
+        identity_provider::MockIdentityProviderClient, mfa_policy::MfaPolicy,
+        oidc_policy::OidcPolicy, password_reset_policy::PasswordResetPolicy,
+        sign_in_policy::SignInPolicy, telephone_verification_policy::TelephoneVerificationPolicy,
+        token_policy::TokenPolicy, token_service::MockTokenService,
+        webauthn_policy::WebAuthnPolicy,
+      },
+    },
+    domain::{
+      core::user::mocks::{
+        pkce,
+        repository::{build_mock_user_repository, Expectations, RehashPassword},
+      },
+      error::Code,
+      utilities::{crypto::MockCrypto, id_generator::MockIDGenerator, primitives::MockPrimitives},
+    },
+  };
+
+  #[tokio::test]
+  async fn test_sign_in_successfully_with_username() {
+    let request = UserSignInRequest {
+      username: Some(USERNAME),
+      password: PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_find_by_username_successfully(),
+      services: &Services {
+        token: token_service_encode(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_sign_in_successfully(),
+        id_generator: generate_refresh_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.sign_in(&request).await.unwrap();
+
+    assert_eq!(
+      response,
+      SignInResponse::Authenticated(user_auth_response())
+    )
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_rehashes_outdated_password() {
+    let request = UserSignInRequest {
+      username: Some(USERNAME),
+      password: PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        rehash_password: Some(RehashPassword {
+          param_password: HASH_NEW_PASSWORD.to_owned(),
+          param_verified_password: HASH_PASSWORD.to_owned(),
+          param_profile_id: ID.to_owned(),
+          ..Default::default()
+        }),
+        ..repository_find_by_username_expectations()
+      }),
+      services: &Services {
+        token: token_service_encode(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_sign_in_with_outdated_hash(),
+        id_generator: generate_refresh_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.sign_in(&request).await.unwrap();
+
+    assert_eq!(
+      response,
+      SignInResponse::Authenticated(user_auth_response())
+    )
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_successfully_with_roles() {
+    let request = UserSignInRequest {
+      username: Some(USERNAME),
+      password: PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_find_admin_by_username_successfully(),
+      services: &Services {
+        token: token_service_encode_admin(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_sign_in_successfully(),
+        id_generator: generate_refresh_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.sign_in(&request).await.unwrap();
+
+    assert_eq!(
+      response,
+      SignInResponse::Authenticated(user_auth_response())
+    )
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_successfully_with_email() {
+    let request = UserSignInRequest {
+      email: Some(EMAIL_ADDRESS),
+      password: PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_find_by_email_successfully(),
+      services: &Services {
+        token: token_service_encode(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_sign_in_successfully(),
+        id_generator: generate_refresh_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.sign_in(&request).await.unwrap();
+
+    assert_eq!(
+      response,
+      SignInResponse::Authenticated(user_auth_response())
+    )
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_with_unverified_email_when_required() {
+    let request = UserSignInRequest {
+      email: Some(EMAIL_ADDRESS),
+      password: PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_find_by_unverified_email(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy {
+          required_for_sign_in: true,
+          ..Default::default()
+        },
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_verify_successfully(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.sign_in(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::permission_denied("email address not verified")
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_successfully_with_telephone() {
+    let request = UserSignInRequest {
+      telephone: Some(TELEPHONE_NUMBER),
+      password: PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_find_by_telephone_successfully(),
+      services: &Services {
+        token: token_service_encode(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_sign_in_successfully(),
+        id_generator: generate_refresh_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.sign_in(&request).await.unwrap();
+
+    assert_eq!(
+      response,
+      SignInResponse::Authenticated(user_auth_response())
+    )
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_with_wrong_password() {
+    let request = UserSignInRequest {
+      username: Some(USERNAME),
+      password: WRONG_PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_find_by_username_wrong_password(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_verify_wrong_password(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.sign_in(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => {
+        assert_eq!(error, AppError::unauthenticated("invalid password"))
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_with_wrong_password_blocking_account() {
+    let request = UserSignInRequest {
+      username: Some(USERNAME),
+      password: WRONG_PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_find_by_username_wrong_password_blocking(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_verify_wrong_password(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.sign_in(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::Locked),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_with_blocked_account() {
+    let request = UserSignInRequest {
+      username: Some(USERNAME),
+      password: PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_find_by_username_blocked(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.sign_in(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::Locked),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_with_disabled_account() {
+    let request = UserSignInRequest {
+      username: Some(USERNAME),
+      password: PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_find_by_username_disabled(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_verify_successfully(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.sign_in(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::PermissionDenied),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_with_nonexistent_username() {
+    let request = UserSignInRequest {
+      username: Some(NONEXISTENT_USERNAME),
+      password: WRONG_PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_find_by_username_not_found(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.sign_in(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => {
+        assert_eq!(
+          error,
+          AppError::not_found("not found user with given username")
+        )
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_with_empty_password() {
+    let request = UserSignInRequest {
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.sign_in(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => {
+        assert_eq!(
+          error,
+          AppError::invalid_argument("password: Password must contain minimum 8 characters!")
+        )
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_with_no_method() {
+    let request = UserSignInRequest {
+      password: PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.sign_in(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => {
+        assert_eq!(
+          error,
+          AppError::invalid_argument("no username, email ou telephone provide")
+        )
+      }
+    }
+  }
+
+  #[tokio::test]
+  async fn test_sign_in_with_mfa_enabled() {
+    let request = UserSignInRequest {
+      username: Some(USERNAME),
+      password: PASSWORD,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_find_by_username_with_mfa(),
+      services: &Services {
+        token: token_service_encode_mfa(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_sign_in_successfully(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.sign_in(&request).await.unwrap();
+
+    assert_eq!(
+      response,
+      SignInResponse::MfaRequired {
+        mfa_token: MFA_TOKEN.to_owned()
+      }
+    )
+  }
+
+  #[tokio::test]
+  async fn test_verify_mfa_successfully_with_recovery_code() {
+    let request = VerifyMfaRequest {
+      mfa_token: MFA_TOKEN,
+      recovery_code: Some(RECOVERY_CODE),
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_verify_mfa_recovery_code_successfully(),
+      services: &Services {
+        token: token_service_verify_mfa_successfully(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: generate_refresh_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.verify_mfa(&request).await.unwrap();
+
+    assert_eq!(response, user_auth_response())
+  }
+
+  #[tokio::test]
+  async fn test_verify_mfa_with_wrong_recovery_code() {
+    let request = VerifyMfaRequest {
+      mfa_token: MFA_TOKEN,
+      recovery_code: Some(RECOVERY_CODE),
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_verify_mfa_wrong_recovery_code(),
+      services: &Services {
+        token: token_service_decode_mfa(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.verify_mfa(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::Unauthenticated),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_verify_mfa_with_authentication_token() {
+    let request = VerifyMfaRequest {
+      mfa_token: MFA_TOKEN,
+      recovery_code: Some(RECOVERY_CODE),
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: token_service_decode_authentication_as_mfa(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.verify_mfa(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::unauthenticated("Given token is not valid for this service")
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_verify_mfa_with_short_code() {
+    let request = VerifyMfaRequest {
+      mfa_token: MFA_TOKEN,
+      code: Some("123"),
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.verify_mfa(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_register_successfully() {
+    let request = &user_register_request();
+
+    let sut = UserUseCase {
+      user_repository: &repository_save_successfully(),
+      services: &Services {
+        token: token_service_encode(),
+        email: email_sender_send_verification(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::REGISTER, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_hash_successfully(),
+        id_generator: generate_id_verification_and_refresh_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.register(request).await.unwrap();
+
+    assert_eq!(response, user_auth_response())
+  }
+
+  #[tokio::test]
+  async fn test_register_with_email_delivery_failure() {
+    let request = &user_register_request();
+    let mut email = MockEmailSender::new();
+    email
+      .expect_send()
+      .times(1)
+      .returning(|_| Err(AppError::internal("smtp server unavailable")));
+
+    let sut = UserUseCase {
+      user_repository: &repository_save_successfully(),
+      services: &Services {
+        token: token_service_encode(),
+        email,
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::REGISTER, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_hash_successfully(),
+        id_generator: generate_id_verification_and_refresh_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.register(request).await.unwrap();
+
+    assert_eq!(response, user_auth_response())
+  }
+
+  #[tokio::test]
+  async fn test_register_already_existing() {
+    let request = &user_register_request();
+
+    let sut = UserUseCase {
+      user_repository: &repository_save_already_existing(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::REGISTER, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_hash_successfully(),
+        id_generator: generate_id_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.register(request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error, AppError::already_exists("already exists")),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_register_with_empty_fields() {
+    let request = &UserRegistrationRequest {
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.register(request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_change_password_successfully() {
+    let request = &user_change_password_request();
+
+    let sut = UserUseCase {
+      user_repository: &repository_change_password_successfully(),
+      services: &Services {
+        token: token_service_encode_after_password_change(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::CHANGE_PASSWORD, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_verify_and_hash_successfully(),
+        id_generator: generate_refresh_token_for_session(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let user = AuthenticatedUser {
+      session_id: Some(FAMILY_ID.to_owned()),
+      ..authenticated_user()
+    };
+    let response = sut.update_password(request, &user).await.unwrap();
+
+    assert_eq!(response, user_auth_response());
+  }
+
+  #[tokio::test]
+  async fn test_change_password_with_token_without_session() {
+    let request = &user_change_password_request();
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        store_session: Some(store_session_successfully()),
+        ..repository_change_password_expectations()
+      }),
+      services: &Services {
+        token: token_service_encode_after_password_change(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::CHANGE_PASSWORD, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_verify_and_hash_successfully(),
+        id_generator: generate_refresh_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut
+      .update_password(request, &authenticated_user())
+      .await
+      .unwrap();
+
+    assert_eq!(response, user_auth_response());
+  }
+
+  #[tokio::test]
+  async fn test_change_password_nonexisting_id() {
+    let request = &ChangeUserPasswordRequest {
+      profile_id: NONEXISTENT_ID,
+      ..user_change_password_request()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::CHANGE_PASSWORD, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.update_password(request, &authenticated_user()).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::permission_denied("Given token not have permission for this profile",)
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_refresh_successfully() {
+    let request = RefreshTokenRequest {
+      refresh_token: REFRESH_TOKEN,
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_refresh_successfully(),
+      services: &Services {
+        token: token_service_encode(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: rotate_refresh_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.refresh(&request).await.unwrap();
+
+    assert_eq!(
+      response,
+      UserAuthenticationResponse {
+        refresh_token: NEW_REFRESH_TOKEN.to_owned(),
+        ..user_auth_response()
+      }
+    )
+  }
+
+  #[tokio::test]
+  async fn test_refresh_with_reused_token() {
+    let request = RefreshTokenRequest {
+      refresh_token: REFRESH_TOKEN,
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_refresh_reused(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.refresh(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::unauthenticated("refresh token reuse detected")
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_refresh_with_empty_token() {
+    let request = RefreshTokenRequest {
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.refresh(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_logout_successfully() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: token_service_revoke_successfully(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    sut.logout(&authenticated_user()).await.unwrap();
+  }
+
+  #[tokio::test]
+  async fn test_logout_ends_the_session() {
+    let sut = UserUseCase {
+      user_repository: &repository_revoke_session_successfully(),
+      services: &Services {
+        token: token_service_revoke_token_and_session(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let user = AuthenticatedUser {
+      session_id: Some(FAMILY_ID.to_owned()),
+      ..authenticated_user()
+    };
+
+    sut.logout(&user).await.unwrap();
+  }
+
+  #[tokio::test]
+  async fn test_verify_email_successfully() {
+    let request = VerifyEmailRequest {
+      token: VERIFICATION_TOKEN,
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_verify_email_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.verify_email(&request).await.unwrap();
+
+    assert_eq!(response, EMAIL_ADDRESS);
+  }
+
+  #[tokio::test]
+  async fn test_verify_email_with_unknown_token() {
+    let request = VerifyEmailRequest {
+      token: VERIFICATION_TOKEN,
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_verify_email_not_found(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.verify_email(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::invalid_argument("invalid verification token")
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_verify_email_with_empty_token() {
+    let request = VerifyEmailRequest {
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.verify_email(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
     }
+  }
 
-    request
-      .validate()
-      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+  #[tokio::test]
+  async fn test_forgot_password_successfully() {
+    let request = ForgotPasswordRequest {
+      username: Some(USERNAME),
+      ..Default::default()
+    };
 
-    User::new(self.user_repository)
-      .change_password(request.into())
-      .get_user()
-      .await?
-      .check_password(&self.utilities.crypto)?
-      .encrypt_password(&self.utilities.crypto)?
-      .save()
-      .await?;
+    let sut = UserUseCase {
+      user_repository: &repository_forgot_password_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: email_sender_send_password_reset(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: generate_reset_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
 
-    Ok(())
+    assert_eq!(sut.forgot_password(&request).await, Ok(()));
   }
-}
-
-#[cfg(test)]
-mod test {
-  use super::super::*;
-  use super::*;
-  use crate::{
-    application::services::security::token_service::MockTokenService,
-    domain::{
-      core::user::mocks::repository::{build_mock_user_repository, Expectations},
-      error::Code,
-      utilities::{crypto::MockCrypto, id_generator::MockIDGenerator},
-    },
-  };
 
   #[tokio::test]
-  async fn test_sign_in_successfully_with_username() {
-    let request = UserSignInRequest {
+  async fn test_forgot_password_hides_delivery_failure() {
+    let request = ForgotPasswordRequest {
       username: Some(USERNAME),
-      password: PASSWORD,
       ..Default::default()
     };
+    let mut email = MockEmailSender::new();
+    email
+      .expect_send()
+      .times(1)
+      .returning(|_| Err(AppError::internal("smtp server unavailable")));
 
     let sut = UserUseCase {
-      user_repository: &repository_find_by_username_successfully(),
+      user_repository: &repository_forgot_password_successfully(),
       services: &Services {
-        token: token_service_encode(),
+        token: MockTokenService::new(),
+        email,
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
-        crypto: crypto_verify_successfully(),
-        id_generator: MockIDGenerator::new(),
+        crypto: MockCrypto::new(),
+        id_generator: generate_reset_token_successfully(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    let response = sut.sign_in(&request).await.unwrap();
-
-    assert_eq!(response, user_auth_response())
+    assert_eq!(sut.forgot_password(&request).await, Ok(()));
   }
 
   #[tokio::test]
-  async fn test_sign_in_successfully_with_email() {
-    let request = UserSignInRequest {
-      email: Some(EMAIL_ADDRESS),
-      password: PASSWORD,
+  async fn test_forgot_password_with_nonexistent_username() {
+    let request = ForgotPasswordRequest {
+      username: Some(NONEXISTENT_USERNAME),
       ..Default::default()
     };
 
     let sut = UserUseCase {
-      user_repository: &repository_find_by_email_successfully(),
+      user_repository: &repository_forgot_password_not_found(),
       services: &Services {
-        token: token_service_encode(),
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
-        crypto: crypto_verify_successfully(),
+        crypto: MockCrypto::new(),
         id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    let response = sut.sign_in(&request).await.unwrap();
-
-    assert_eq!(response, user_auth_response())
+    assert_eq!(sut.forgot_password(&request).await, Ok(()));
   }
 
   #[tokio::test]
-  async fn test_sign_in_successfully_with_telephone() {
-    let request = UserSignInRequest {
-      telephone: Some(TELEPHONE_NUMBER),
-      password: PASSWORD,
+  async fn test_forgot_password_with_no_method() {
+    let request = ForgotPasswordRequest {
       ..Default::default()
     };
 
     let sut = UserUseCase {
-      user_repository: &repository_find_by_telephone_successfully(),
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
       services: &Services {
-        token: token_service_encode(),
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
-        crypto: crypto_verify_successfully(),
+        crypto: MockCrypto::new(),
         id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    let response = sut.sign_in(&request).await.unwrap();
-
-    assert_eq!(response, user_auth_response())
+    match sut.forgot_password(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
+    }
   }
 
   #[tokio::test]
-  async fn test_sign_in_with_wrong_password() {
-    let request = UserSignInRequest {
-      username: Some(USERNAME),
-      password: WRONG_PASSWORD,
+  async fn test_reset_password_successfully() {
+    let request = ResetPasswordRequest {
+      token: VERIFICATION_TOKEN,
+      password: NEW_PASSWORD,
+      password_repetition: NEW_PASSWORD,
       ..Default::default()
     };
 
     let sut = UserUseCase {
-      user_repository: &repository_find_by_username_successfully(),
+      user_repository: &repository_reset_password_successfully(),
       services: &Services {
         token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::RESET_PASSWORD, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
-        crypto: crypto_verify_wrong_password(),
+        crypto: crypto_hash_new_password_successfully(),
         id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    match sut.sign_in(&request).await {
-      Ok(_) => panic!("should not be success"),
-      Err(error) => {
-        assert_eq!(error, AppError::unauthenticated("invalid password"))
-      }
-    }
+    assert_eq!(sut.reset_password(&request).await, Ok(()));
   }
 
   #[tokio::test]
-  async fn test_sign_in_with_nonexistent_username() {
-    let request = UserSignInRequest {
-      username: Some(NONEXISTENT_USERNAME),
-      password: WRONG_PASSWORD,
+  async fn test_reset_password_with_unknown_token() {
+    let request = ResetPasswordRequest {
+      token: VERIFICATION_TOKEN,
+      password: NEW_PASSWORD,
+      password_repetition: NEW_PASSWORD,
       ..Default::default()
     };
 
     let sut = UserUseCase {
-      user_repository: &repository_find_by_username_not_found(),
+      user_repository: &repository_reset_password_not_found(),
       services: &Services {
         token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::RESET_PASSWORD, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
         crypto: MockCrypto::new(),
         id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    match sut.sign_in(&request).await {
+    match sut.reset_password(&request).await {
       Ok(_) => panic!("should not be success"),
-      Err(error) => {
-        assert_eq!(
-          error,
-          AppError::not_found("not found user with given username")
-        )
-      }
+      Err(error) => assert_eq!(
+        error,
+        AppError::invalid_argument("invalid password reset token")
+      ),
     }
   }
 
   #[tokio::test]
-  async fn test_sign_in_with_empty_password() {
-    let request = UserSignInRequest {
+  async fn test_reset_password_with_different_repetition() {
+    let request = ResetPasswordRequest {
+      token: VERIFICATION_TOKEN,
+      password: NEW_PASSWORD,
+      password_repetition: PASSWORD,
       ..Default::default()
     };
 
@@ -269,28 +2656,83 @@ mod test {
       }),
       services: &Services {
         token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
         crypto: MockCrypto::new(),
         id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    match sut.sign_in(&request).await {
+    match sut.reset_password(&request).await {
       Ok(_) => panic!("should not be success"),
-      Err(error) => {
-        assert_eq!(
-          error,
-          AppError::invalid_argument("password: Password must contain minimum 8 characters!")
-        )
-      }
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
     }
   }
 
   #[tokio::test]
-  async fn test_sign_in_with_no_method() {
-    let request = UserSignInRequest {
-      password: PASSWORD,
+  async fn test_webauthn_registration_options_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_webauthn_registration_options_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: generate_webauthn_challenge_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut
+      .webauthn_registration_options(&authenticated_user())
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response,
+      WebAuthnRegistrationOptions {
+        challenge: VERIFICATION_TOKEN.to_owned(),
+        rp_id: String::from("localhost"),
+        rp_name: env!("CARGO_PKG_NAME").to_owned(),
+        user_id: ID.to_owned(),
+        user_name: USERNAME.to_owned(),
+        user_display_name: NAME.to_owned(),
+        exclude_credentials: vec![CREDENTIAL_ID.to_owned()],
+        timeout_ms: 300_000,
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_register_webauthn_with_empty_fields() {
+    let request = RegisterWebAuthnRequest {
       ..Default::default()
     };
 
@@ -300,115 +2742,282 @@ mod test {
       }),
       services: &Services {
         token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
         crypto: MockCrypto::new(),
         id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    match sut.sign_in(&request).await {
+    match sut.register_webauthn(&request, &authenticated_user()).await {
       Ok(_) => panic!("should not be success"),
-      Err(error) => {
-        assert_eq!(
-          error,
-          AppError::invalid_argument("no username, email ou telephone provide")
-        )
-      }
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
     }
   }
 
   #[tokio::test]
-  async fn test_register_successfully() {
-    let request = &user_register_request();
+  async fn test_webauthn_sign_in_options_without_passkeys() {
+    let request = WebAuthnSignInOptionsRequest { username: USERNAME };
 
     let sut = UserUseCase {
-      user_repository: &repository_save_successfully(),
+      user_repository: &repository_webauthn_sign_in_options_without_passkeys(),
       services: &Services {
-        token: token_service_encode(),
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
-        crypto: crypto_hash_successfully(),
-        id_generator: generate_id_successfully(),
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    let response = sut.register(request.try_into().unwrap()).await.unwrap();
-
-    assert_eq!(response, user_auth_response())
+    match sut.webauthn_sign_in_options(&request).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error, AppError::invalid_argument("no passkey registered")),
+    }
   }
 
   #[tokio::test]
-  async fn test_register_already_existing() {
-    let request = &user_register_request();
+  async fn test_webauthn_sign_in_with_unknown_passkey() {
+    let request = WebAuthnSignInRequest {
+      credential_id: CREDENTIAL_ID,
+      client_data_json: "e30",
+      authenticator_data: "AA",
+      signature: "AA",
+      ..Default::default()
+    };
 
     let sut = UserUseCase {
-      user_repository: &repository_save_already_existing(),
+      user_repository: &repository_webauthn_sign_in_unknown_passkey(),
       services: &Services {
         token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
-        crypto: crypto_hash_successfully(),
-        id_generator: generate_id_successfully(),
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    match sut.register(request.try_into().unwrap()).await {
+    match sut.webauthn_sign_in(&request).await {
       Ok(_) => panic!("should not be success"),
-      Err(error) => assert_eq!(error, AppError::already_exists("already exists")),
+      Err(error) => assert_eq!(error, AppError::unauthenticated("unknown passkey")),
     }
   }
 
   #[tokio::test]
-  async fn test_register_with_empty_fields() {
-    let request = &UserRegistrationRequest {
-      ..Default::default()
+  async fn test_start_external_sign_in_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_start_external_sign_in_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: external_identity_policy(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: generate_external_sign_in_successfully(),
+        primitives: pkce::mock_sha256(),
+      },
     };
 
+    let url = sut.start_external_sign_in(PROVIDER).await.unwrap();
+
+    assert_eq!(
+      url,
+      format!(
+        "{}/authorize?response_type=code&client_id=phoenix\
+        &redirect_uri=https%3A%2F%2Fapp.example.com%2Fexternal%2Facme\
+        &scope=openid%20email%20profile&state={}&nonce={}\
+        &code_challenge=E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM&code_challenge_method=S256",
+        ISSUER, EXTERNAL_STATE, NONCE
+      )
+    );
+  }
+
+  #[tokio::test]
+  async fn test_start_external_sign_in_with_unknown_provider() {
     let sut = UserUseCase {
       user_repository: &build_mock_user_repository(Expectations {
         ..Default::default()
       }),
       services: &Services {
         token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: external_identity_policy(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
         crypto: MockCrypto::new(),
         id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    match sut.register(request.try_into().unwrap()).await {
+    match sut.start_external_sign_in("unknown").await {
       Ok(_) => panic!("should not be success"),
-      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
+      Err(error) => assert_eq!(
+        error,
+        AppError::not_found("unknown identity provider unknown")
+      ),
     }
   }
 
   #[tokio::test]
-  async fn test_change_password_successfully() {
-    let request = &user_change_password_request();
+  async fn test_external_sign_in_successfully_with_linked_identity() {
+    let request = ExternalSignInRequest {
+      provider: PROVIDER,
+      code: AUTHORIZATION_CODE,
+      state: EXTERNAL_STATE,
+      ..Default::default()
+    };
 
     let sut = UserUseCase {
-      user_repository: &repository_change_password_successfully(),
+      user_repository: &repository_external_sign_in_linked(),
       services: &Services {
-        token: token_service_decode_successfully(),
+        token: token_service_encode(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: identity_provider_exchange_code(),
+        audit: audit_log_record(actions::SIGN_IN, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: external_identity_policy(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
-        crypto: crypto_verify_and_hash_successfully(),
+        crypto: MockCrypto::new(),
+        id_generator: generate_refresh_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.external_sign_in(&request).await.unwrap();
+
+    assert_eq!(
+      response,
+      ExternalSignInResponse::SignedIn(SignInResponse::Authenticated(user_auth_response()))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_external_sign_in_requiring_sign_up() {
+    let request = ExternalSignInRequest {
+      provider: PROVIDER,
+      code: AUTHORIZATION_CODE,
+      state: EXTERNAL_STATE,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_external_sign_in_unknown_email(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: identity_provider_exchange_code(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: external_identity_policy(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
         id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    let response = sut.update_password(request, TOKEN).await.unwrap();
+    let response = sut.external_sign_in(&request).await.unwrap();
 
-    assert_eq!(response, ());
+    assert_eq!(
+      response,
+      ExternalSignInResponse::SignUpRequired {
+        signup_token: EXTERNAL_STATE.to_owned(),
+        name: Some(NAME.to_owned()),
+        email: EMAIL_ADDRESS.to_owned(),
+      }
+    );
   }
 
   #[tokio::test]
-  async fn test_change_password_nonexisting_id() {
-    let request = &ChangeUserPasswordRequest {
-      profile_id: NONEXISTENT_ID,
-      ..user_change_password_request()
+  async fn test_external_sign_in_with_empty_code() {
+    let request = ExternalSignInRequest {
+      provider: PROVIDER,
+      state: EXTERNAL_STATE,
+      ..Default::default()
     };
 
     let sut = UserUseCase {
@@ -416,28 +3025,80 @@ mod test {
         ..Default::default()
       }),
       services: &Services {
-        token: token_service_decode_successfully(),
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: external_identity_policy(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
         crypto: MockCrypto::new(),
         id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    match sut.update_password(request, TOKEN).await {
+    match sut.external_sign_in(&request).await {
       Ok(_) => panic!("should not be success"),
-      Err(error) => assert_eq!(
-        error,
-        AppError::permission_denied("Given token not have permission for this profile",)
-      ),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
     }
   }
 
   #[tokio::test]
-  async fn test_change_password_with_expired_token() {
-    let request = &ChangeUserPasswordRequest {
-      profile_id: ID,
-      ..user_change_password_request()
+  async fn test_external_sign_up_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_external_sign_up_successfully(),
+      services: &Services {
+        token: token_service_encode(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::REGISTER, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: external_identity_policy(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_hash_successfully(),
+        id_generator: generate_external_sign_up_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut
+      .external_sign_up(&external_sign_up_request())
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response,
+      UserAuthenticationResponse {
+        username: EXTERNAL_USERNAME.to_owned(),
+        ..user_auth_response()
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_external_sign_up_with_empty_fields() {
+    let request = ExternalSignUpRequest {
+      signup_token: EXTERNAL_STATE,
+      ..Default::default()
     };
 
     let sut = UserUseCase {
@@ -445,20 +3106,31 @@ mod test {
         ..Default::default()
       }),
       services: &Services {
-        token: token_service_decode_fail(),
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: external_identity_policy(),
+        oidc_policy: OidcPolicy::default(),
       },
       utilities: &Utilities {
         crypto: MockCrypto::new(),
         id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
       },
     };
 
-    match sut.update_password(request, EXPIRED_TOKEN).await {
+    match sut.external_sign_up(&request).await {
       Ok(_) => panic!("should not be success"),
-      Err(error) => assert_eq!(
-        error,
-        AppError::unauthenticated("expired or invalid JWT token",)
-      ),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
     }
   }
 }
@@ -1 +1,4 @@
+pub mod admin;
 pub mod authenticate;
+pub mod oauth;
+pub mod profile;
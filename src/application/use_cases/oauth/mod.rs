@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod test_utils;
+#[cfg(test)]
+use test_utils::*;
+
+pub mod user;
+
+use crate::application::services::security::authenticated_user::AuthenticatedUser;
+use crate::domain::{entities::user::FullProfile, error::AppError};
+use async_trait::async_trait;
+use mockall::automock;
+use validator::Validate;
+
+/// OpenID Connect provider, authorization code flow with PKCE.
+#[async_trait]
+#[automock]
+pub trait OpenIdProvider: Send + Sync {
+  /// Issues an authorization code to the client for `user`,
+  /// returning where to send the browser. Once the client and its redirect
+  /// URI are trusted, request errors are reported through that redirect.
+  async fn authorize(
+    &self,
+    request: &AuthorizeRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<String, AppError>;
+  /// Redeems an authorization code for an ID token and an access token to
+  /// the userinfo endpoint.
+  async fn exchange_code(&self, request: &TokenRequest<'_>) -> Result<OpenIdTokens, AppError>;
+  async fn userinfo(&self, token: &str) -> Result<FullProfile, AppError>;
+}
+
+#[derive(Validate, Default)]
+pub struct AuthorizeRequest<'a> {
+  pub response_type: &'a str,
+  #[validate(length(min = 1, message = "Please provide a client_id!"))]
+  pub client_id: &'a str,
+  #[validate(length(min = 1, message = "Please provide a redirect_uri!"))]
+  pub redirect_uri: &'a str,
+  pub scope: &'a str,
+  pub state: Option<&'a str>,
+  pub nonce: Option<&'a str>,
+  pub code_challenge: &'a str,
+  pub code_challenge_method: &'a str,
+}
+
+#[derive(Validate, Default)]
+pub struct TokenRequest<'a> {
+  #[validate(length(min = 1, message = "Please provide a client_id!"))]
+  pub client_id: &'a str,
+  pub client_secret: Option<&'a str>,
+  #[validate(length(min = 1, message = "Please provide an authorization code!"))]
+  pub code: &'a str,
+  #[validate(length(min = 1, message = "Please provide a redirect_uri!"))]
+  pub redirect_uri: &'a str,
+  #[validate(length(
+    min = 43,
+    max = 128,
+    message = "code_verifier must contain between 43 and 128 characters!"
+  ))]
+  pub code_verifier: &'a str,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct OpenIdTokens {
+  pub access_token: String,
+  pub id_token: String,
+  /// Lifetime of both tokens, in seconds.
+  pub expires_in: u64,
+  pub scope: String,
+}
@@ -0,0 +1,568 @@
+use super::{AuthorizeRequest, OpenIdProvider, OpenIdTokens, TokenRequest};
+use crate::{
+  application::{
+    services::{
+      audit_log::AuditLog,
+      notification::{email_sender::EmailSender, sms_sender::SmsSender},
+      security::{
+        authenticated_user::AuthenticatedUser, identity_provider::IdentityProviderClient,
+        token_service::TokenService,
+      },
+    },
+    use_cases::authenticate::user::UserUseCase,
+  },
+  domain::{
+    core::user::{
+      exchange_authorization_code, get_profile, issue_authorization_code,
+      repository::UserRepository, User,
+    },
+    entities::{role::Grants, user::FullProfile},
+    error::AppError,
+    utilities::{crypto::Crypto, id_generator::IDGenerator, primitives::Primitives},
+  },
+};
+use async_trait::async_trait;
+use validator::Validate;
+
+const CODE_RESPONSE_TYPE: &str = "code";
+
+/// `redirect_uri` with the response parameters added to its query, `state`
+/// being echoed back to the client.
+fn authorization_redirect(
+  redirect_uri: &str,
+  params: &[(&str, &str)],
+  state: Option<&str>,
+) -> String {
+  let query = params
+    .iter()
+    .copied()
+    .chain(state.map(|state| ("state", state)))
+    .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+    .collect::<Vec<_>>()
+    .join("&");
+  let separator = if redirect_uri.contains('?') { '&' } else { '?' };
+
+  format!("{}{}{}", redirect_uri, separator, query)
+}
+
+#[async_trait]
+impl<
+    Repository: UserRepository,
+    Token: TokenService,
+    Email: EmailSender,
+    Sms: SmsSender,
+    Idp: IdentityProviderClient,
+    Audit: AuditLog,
+    C: Crypto,
+    ID: IDGenerator,
+    P: Primitives,
+  > OpenIdProvider for UserUseCase<'_, Repository, Token, Email, Sms, Idp, Audit, C, ID, P>
+{
+  async fn authorize(
+    &self,
+    request: &AuthorizeRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<String, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let user = User::new(self.user_repository)
+      .issue_authorization_code(issue_authorization_code::Request {
+        profile_id: &user.profile_id,
+        client_id: request.client_id,
+        redirect_uri: request.redirect_uri,
+        scope: request.scope,
+        code_challenge: request.code_challenge,
+        code_challenge_method: request.code_challenge_method,
+        nonce: request.nonce,
+        expires_in_min: self.services.oidc_policy.code_expiration_min,
+      })
+      .get_client()
+      .await?;
+
+    if request.response_type != CODE_RESPONSE_TYPE {
+      return Ok(authorization_redirect(
+        request.redirect_uri,
+        &[("error", "unsupported_response_type")],
+        request.state,
+      ));
+    }
+
+    let user = match user.generate(&self.utilities.id_generator) {
+      Ok(user) => user,
+      Err(error) => {
+        return Ok(authorization_redirect(
+          request.redirect_uri,
+          &[
+            ("error", "invalid_request"),
+            ("error_description", &error.message),
+          ],
+          request.state,
+        ))
+      }
+    };
+
+    let code = user.store().await?.response();
+
+    Ok(authorization_redirect(
+      request.redirect_uri,
+      &[("code", &code)],
+      request.state,
+    ))
+  }
+
+  async fn exchange_code(&self, request: &TokenRequest<'_>) -> Result<OpenIdTokens, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let grant = User::new(self.user_repository)
+      .exchange_authorization_code(exchange_authorization_code::Request {
+        client_id: request.client_id,
+        client_secret: request.client_secret,
+        code: request.code,
+        redirect_uri: request.redirect_uri,
+        code_verifier: request.code_verifier,
+      })
+      .get_data()
+      .await?
+      .check_grant(&self.utilities.crypto, &self.utilities.primitives)
+      .await?
+      .response();
+
+    let version = self
+      .user_repository
+      .find_token_version(&grant.profile_id)
+      .await?;
+    let policy = &self.services.oidc_policy;
+    let access_token = self.services.token.encode(
+      grant.profile_id.clone(),
+      policy.userinfo_audience.clone(),
+      Grants::default(),
+      None,
+      version,
+      policy.token_expiration_min,
+    )?;
+    let id_token = self.services.token.encode_id_token(
+      grant.profile_id,
+      grant.client_id,
+      grant.nonce,
+      policy.token_expiration_min,
+    )?;
+
+    Ok(OpenIdTokens {
+      access_token,
+      id_token,
+      expires_in: policy.token_expiration_min * 60,
+      scope: grant.scope,
+    })
+  }
+
+  async fn userinfo(&self, token: &str) -> Result<FullProfile, AppError> {
+    let token_decoded = self.services.token.decode(token).await?;
+    if token_decoded.aud != self.services.oidc_policy.userinfo_audience {
+      return Err(AppError::unauthenticated(
+        "Given token is not valid for this service",
+      ));
+    }
+
+    let profile = User::new(self.user_repository)
+      .get_profile(get_profile::Request {
+        profile_id: &token_decoded.sub,
+      })
+      .find_profile()
+      .await?
+      .response();
+
+    Ok(profile)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::super::*;
+  use super::*;
+  use crate::{
+    application::services::{
+      audit_log::MockAuditLog,
+      notification::{email_sender::MockEmailSender, sms_sender::MockSmsSender},
+      security::{
+        email_verification_policy::EmailVerificationPolicy,
+        external_identity_policy::ExternalIdentityPolicy,
+        identity_provider::MockIdentityProviderClient, mfa_policy::MfaPolicy,
+        oidc_policy::OidcPolicy, password_reset_policy::PasswordResetPolicy,
+        sign_in_policy::SignInPolicy, telephone_verification_policy::TelephoneVerificationPolicy,
+        token_policy::TokenPolicy, token_service::MockTokenService,
+        webauthn_policy::WebAuthnPolicy,
+      },
+      Services,
+    },
+    domain::{
+      core::user::mocks::{
+        pkce,
+        repository::{build_mock_user_repository, Expectations},
+      },
+      error::Code,
+      utilities::{
+        crypto::MockCrypto, id_generator::MockIDGenerator, primitives::MockPrimitives, Utilities,
+      },
+    },
+  };
+
+  #[test]
+  fn test_authorization_redirect_keeps_query() {
+    assert_eq!(
+      authorization_redirect(
+        "https://app.example.com/callback?tenant=1",
+        &[("error", "invalid_request"), ("error_description", "scope must include openid")],
+        Some(STATE),
+      ),
+      "https://app.example.com/callback?tenant=1&error=invalid_request&error_description=scope%20must%20include%20openid&state=af0ifjsldkj"
+    );
+  }
+
+  #[tokio::test]
+  async fn test_authorize_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_store_code(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: id_generator_code(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = AuthorizeRequest {
+      response_type: "code",
+      client_id: CLIENT_ID,
+      redirect_uri: REDIRECT_URI,
+      scope: "openid profile",
+      state: Some(STATE),
+      nonce: Some(NONCE),
+      code_challenge: CODE_CHALLENGE,
+      code_challenge_method: "S256",
+    };
+
+    assert_eq!(
+      sut.authorize(&request, &authenticated_user()).await,
+      Ok(format!("{}?code={}&state={}", REDIRECT_URI, CODE, STATE))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_authorize_unknown_client() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_client_not_found(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = AuthorizeRequest {
+      response_type: "code",
+      client_id: CLIENT_ID,
+      redirect_uri: REDIRECT_URI,
+      scope: "openid profile",
+      state: Some(STATE),
+      nonce: Some(NONCE),
+      code_challenge: CODE_CHALLENGE,
+      code_challenge_method: "S256",
+    };
+
+    assert_eq!(
+      sut.authorize(&request, &authenticated_user()).await,
+      Err(AppError::invalid_argument("unknown OAuth client"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_authorize_with_plain_challenge_redirects_error() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_client(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = AuthorizeRequest {
+      response_type: "code",
+      client_id: CLIENT_ID,
+      redirect_uri: REDIRECT_URI,
+      scope: "openid profile",
+      state: Some(STATE),
+      nonce: Some(NONCE),
+      code_challenge: CODE_CHALLENGE,
+      code_challenge_method: "plain",
+    };
+
+    assert_eq!(
+      sut.authorize(&request, &authenticated_user()).await,
+      Ok(format!(
+        "{}?error=invalid_request&error_description=code_challenge_method%20must%20be%20S256&state={}",
+        REDIRECT_URI, STATE
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_authorize_unsupported_response_type() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_client(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = AuthorizeRequest {
+      response_type: "token",
+      client_id: CLIENT_ID,
+      redirect_uri: REDIRECT_URI,
+      scope: "openid profile",
+      state: Some(STATE),
+      nonce: Some(NONCE),
+      code_challenge: CODE_CHALLENGE,
+      code_challenge_method: "S256",
+    };
+
+    assert_eq!(
+      sut.authorize(&request, &authenticated_user()).await,
+      Ok(format!(
+        "{}?error=unsupported_response_type&state={}",
+        REDIRECT_URI, STATE
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_exchange_code_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_redeem_code(),
+      services: &Services {
+        token: token_service_encode_tokens(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: pkce::mock_sha256(),
+      },
+    };
+
+    let request = TokenRequest {
+      client_id: CLIENT_ID,
+      client_secret: None,
+      code: CODE,
+      redirect_uri: REDIRECT_URI,
+      code_verifier: CODE_VERIFIER,
+    };
+
+    assert_eq!(
+      sut.exchange_code(&request).await,
+      Ok(OpenIdTokens {
+        access_token: ACCESS_TOKEN.to_owned(),
+        id_token: ID_TOKEN.to_owned(),
+        expires_in: 3600,
+        scope: String::from("openid profile"),
+      })
+    );
+  }
+
+  #[tokio::test]
+  async fn test_exchange_code_with_short_code_verifier() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = TokenRequest {
+      client_id: CLIENT_ID,
+      client_secret: None,
+      code: CODE,
+      redirect_uri: REDIRECT_URI,
+      code_verifier: "too-short",
+    };
+
+    assert_eq!(
+      sut
+        .exchange_code(&request)
+        .await
+        .map_err(|error| error.code),
+      Err(Code::InvalidArgument)
+    );
+  }
+
+  #[tokio::test]
+  async fn test_userinfo_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_profile(),
+      services: &Services {
+        token: token_service_decode("oidc_userinfo"),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let profile = sut.userinfo(TOKEN).await.unwrap();
+
+    assert_eq!(profile.id, ID);
+    assert_eq!(profile.username, USERNAME);
+  }
+
+  #[tokio::test]
+  async fn test_userinfo_with_authentication_token() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: token_service_decode("authentication_user"),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    assert_eq!(
+      sut.userinfo(TOKEN).await,
+      Err(AppError::unauthenticated(
+        "Given token is not valid for this service"
+      ))
+    );
+  }
+}
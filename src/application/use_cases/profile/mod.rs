@@ -0,0 +1,216 @@
+#[cfg(test)]
+mod test_utils;
+#[cfg(test)]
+use test_utils::*;
+
+pub mod user;
+
+use crate::application::services::security::authenticated_user::AuthenticatedUser;
+use crate::domain::{
+  core::user::update_profile,
+  entities::{
+    session::Session, sign_in_event::SignInEventPage, totp::TotpEnrollment, user::FullProfile,
+  },
+  error::AppError,
+};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use mockall::automock;
+use validator::{Validate, ValidationError};
+
+#[async_trait]
+#[automock]
+pub trait UserProfile: Send + Sync {
+  async fn get_profile(&self, user: &AuthenticatedUser) -> Result<FullProfile, AppError>;
+  async fn update_profile(
+    &self,
+    request: &UpdateUserProfileRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<FullProfile, AppError>;
+  async fn list_sign_ins(
+    &self,
+    request: &ListSignInsRequest,
+    user: &AuthenticatedUser,
+  ) -> Result<SignInEventPage, AppError>;
+  async fn request_deletion(
+    &self,
+    request: &DeleteUserRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<(), AppError>;
+  /// Texts a one-time code to one of the profile telephones.
+  async fn send_telephone_code(
+    &self,
+    request: &TelephoneCodeRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<(), AppError>;
+  /// Redeems the code, returning the verified number.
+  async fn verify_telephone(
+    &self,
+    request: &VerifyTelephoneRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<String, AppError>;
+  /// Starts a TOTP enrollment, replacing one that was not confirmed yet.
+  async fn enroll_totp(&self, user: &AuthenticatedUser) -> Result<TotpEnrollment, AppError>;
+  /// Enables two-factor authentication, returning the recovery codes.
+  async fn confirm_totp(
+    &self,
+    request: &ConfirmTotpRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<Vec<String>, AppError>;
+  /// Sessions still signed in, most recently used first.
+  async fn list_sessions(&self, user: &AuthenticatedUser) -> Result<Vec<Session>, AppError>;
+  /// Signs one session out, its tokens included.
+  async fn revoke_session(
+    &self,
+    session_id: &str,
+    user: &AuthenticatedUser,
+  ) -> Result<(), AppError>;
+  /// Signs out everywhere but the session of the given token.
+  async fn revoke_other_sessions(&self, user: &AuthenticatedUser) -> Result<(), AppError>;
+}
+
+#[derive(Validate, Default)]
+pub struct DeleteUserRequest<'a> {
+  #[validate(length(min = 8, message = "Password must contain minimum 8 characters!"))]
+  pub password: &'a str,
+}
+
+#[derive(Validate, Default)]
+pub struct TelephoneCodeRequest<'a> {
+  #[validate(length(min = 7, message = "Please provide a valid telephone number!"))]
+  pub number: &'a str,
+}
+
+#[derive(Validate, Default)]
+pub struct VerifyTelephoneRequest<'a> {
+  #[validate(length(min = 7, message = "Please provide a valid telephone number!"))]
+  pub number: &'a str,
+  #[validate(length(min = 1, message = "Please provide a verification code!"))]
+  pub code: &'a str,
+}
+
+#[derive(Validate, Default)]
+pub struct ConfirmTotpRequest<'a> {
+  #[validate(length(equal = 6, message = "Authentication code must contain 6 digits!"))]
+  pub code: &'a str,
+}
+
+#[derive(Validate)]
+pub struct ListSignInsRequest {
+  #[validate(range(min = 1, message = "Page must be greater than zero!"))]
+  pub page: i64,
+  #[validate(range(min = 1, max = 100, message = "Page size must be between 1 and 100!"))]
+  pub per_page: i64,
+}
+
+impl Default for ListSignInsRequest {
+  fn default() -> Self {
+    Self {
+      page: 1,
+      per_page: 20,
+    }
+  }
+}
+
+#[derive(Validate, Default)]
+#[validate(schema(function = "validate_update_profile_request"))]
+pub struct UpdateUserProfileRequest<'a> {
+  #[validate(length(
+    min = 5,
+    message = "Please provide a valid name, minimum 5 characters!!"
+  ))]
+  pub name: Option<&'a str>,
+  pub birth_date: Option<&'a str>,
+  #[validate(range(min = 1))]
+  pub gender_id: Option<i32>,
+  #[validate(length(
+    min = 3,
+    message = "Please provide a valid street, minimum 3 characters!!"
+  ))]
+  pub address_street: Option<&'a str>,
+  #[validate(length(
+    min = 3,
+    message = "Please provide a valid neighborhood, minimum 3 characters!!"
+  ))]
+  pub address_neighborhood: Option<&'a str>,
+  #[validate(range(min = 1))]
+  pub address_city_id: Option<i32>,
+  #[validate(range(min = 1))]
+  pub address_postal_code: Option<i32>,
+  #[validate(email)]
+  pub email: Option<&'a str>,
+  #[validate(phone)]
+  pub telephone: Option<&'a str>,
+}
+
+fn validate_update_profile_request(
+  request: &UpdateUserProfileRequest,
+) -> Result<(), ValidationError> {
+  let address_fields = [
+    request.address_street.is_some(),
+    request.address_neighborhood.is_some(),
+    request.address_city_id.is_some(),
+    request.address_postal_code.is_some(),
+  ];
+
+  if address_fields.contains(&true) && address_fields.contains(&false) {
+    let mut error = ValidationError::new("address");
+    error.message =
+      Some("Please provide the full address: street, neighborhood, city and postal code".into());
+    return Err(error);
+  }
+
+  if request.name.is_none()
+    && request.birth_date.is_none()
+    && request.gender_id.is_none()
+    && !address_fields.contains(&true)
+    && request.email.is_none()
+    && request.telephone.is_none()
+  {
+    let mut error = ValidationError::new("empty");
+    error.message = Some("Please provide at least one field to update".into());
+    return Err(error);
+  }
+
+  Ok(())
+}
+
+impl<'a> UpdateUserProfileRequest<'a> {
+  pub fn to_domain_request(
+    &self,
+    profile_id: &'a str,
+  ) -> Result<update_profile::Request<'a>, AppError> {
+    let birth_date = self
+      .birth_date
+      .map(|birth_date| NaiveDate::parse_from_str(birth_date, "%Y-%m-%d"))
+      .transpose()
+      .map_err(|_| AppError::invalid_argument("birth date format invalid"))?;
+
+    let address = match (
+      self.address_street,
+      self.address_neighborhood,
+      self.address_city_id,
+      self.address_postal_code,
+    ) {
+      (Some(street), Some(neighborhood), Some(city_id), Some(postal_code)) => {
+        Some(update_profile::Address {
+          street,
+          neighborhood,
+          city_id,
+          postal_code,
+        })
+      }
+      _ => None,
+    };
+
+    Ok(update_profile::Request {
+      profile_id,
+      name: self.name,
+      birth_date,
+      gender_id: self.gender_id,
+      address,
+      email_address: self.email,
+      telephone_number: self.telephone,
+    })
+  }
+}
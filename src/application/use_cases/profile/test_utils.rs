@@ -0,0 +1,560 @@
+use crate::{
+  application::services::{
+    audit_log::MockAuditLog,
+    notification::{email_sender::MockEmailSender, sms_sender::MockSmsSender},
+    security::{authenticated_user::AuthenticatedUser, token_service::MockTokenService},
+  },
+  domain::{
+    core::user::{
+      mocks::repository::{
+        build_mock_user_repository, AttemptVerificationToken, CheckTelephone,
+        ConfirmTotpAuthenticator, CountVerificationTokens, Expectations, FindFullProfileBy,
+        FindLatestVerificationToken, FindSessions, FindSignInEvents, FindTotpAuthenticator,
+        FindUserBy, RequestDeletion, RevokeOtherSessions, RevokeRefreshTokens, RevokeSession,
+        StoreTotpAuthenticator, StoreVerificationToken, UpdateProfile, UseVerificationToken,
+      },
+      repository::MockUserRepository,
+      totp, update_profile,
+    },
+    entities::{
+      audit_event::AuditOutcome,
+      session::Session,
+      sign_in_event::{SignInEvent, SignInEventPage, SignInMethod},
+      totp::TotpAuthenticatorData,
+      user::{Address, Email, FullProfile, Telephone, UserColumns, UserData},
+      verification_token::{VerificationPurpose, VerificationTokenData},
+    },
+    error::AppError,
+    utilities::{crypto::MockCrypto, id_generator::MockIDGenerator, primitives::MockPrimitives},
+  },
+};
+use chrono::{Duration, NaiveDate, Utc};
+use jsonwebtoken::get_current_timestamp;
+use mockall::predicate;
+
+pub(super) const ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+pub(super) const NAME: &str = "John Doe";
+pub(super) const NEW_NAME: &str = "Jane Doe";
+pub(super) const USERNAME: &str = "john.doe";
+pub(super) const BIRTH_DATE: &str = "1990-01-01";
+pub(super) const GENDER: &str = "Male";
+pub(super) const STREET: &str = "153 W 57th St";
+pub(super) const NEIGHBORHOOD: &str = "manhattan";
+pub(super) const POSTAL_CODE: i32 = 10019;
+pub(super) const CITY: &str = "New York City";
+pub(super) const STATE: &str = "New York";
+pub(super) const COUNTRY: &str = "United States";
+pub(super) const EMAIL_ADDRESS: &str = "johndoe@company.com";
+pub(super) const TELEPHONE_NUMBER: &str = "+1 4152370800";
+pub(super) const VERIFICATION_TOKEN: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+pub(super) const TELEPHONE_CODE: &str = "482910";
+pub(super) const PASSWORD: &str = "123456789";
+pub(super) const HASH_PASSWORD: &str = "mg3824m1htv8913dxjrn9ui45g801q43tj";
+pub(super) const JTI: &str = "0b8e1e9a-5f4c-4f7b-9d0a-3b1f0a6c2e71";
+pub(super) const TOTP_SECRET: &str = "JBSWY3DPEHPK3PXPJBSWY3DPEHPK3PXP";
+pub(super) const TOTP_CODE: &str = "287082";
+pub(super) const RECOVERY_CODE: &str = "0123456789";
+pub(super) const SESSION_ID: &str = "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed";
+pub(super) const OTHER_SESSION_ID: &str = "5c0d6a8e-2b3f-4e1d-8a9c-7f6e5d4c3b2a";
+
+pub(super) fn full_profile() -> FullProfile {
+  FullProfile {
+    id: ID.to_string(),
+    name: NAME.to_string(),
+    username: USERNAME.to_string(),
+    birth_date: NaiveDate::parse_from_str(BIRTH_DATE, "%Y-%m-%d").unwrap(),
+    gender: GENDER.to_string(),
+    telephones: vec![Telephone {
+      number: TELEPHONE_NUMBER.to_string(),
+      checked: false,
+    }],
+    emails: vec![Email {
+      address: EMAIL_ADDRESS.to_string(),
+      checked: false,
+    }],
+    address: Address {
+      street: STREET.to_string(),
+      neighborhood: NEIGHBORHOOD.to_string(),
+      postal_code: POSTAL_CODE,
+      city: CITY.to_string(),
+      state: STATE.to_string(),
+      country: COUNTRY.to_string(),
+    },
+  }
+}
+
+pub(super) fn repository_find_full_profile_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_full_profile_by: Some(FindFullProfileBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(ID),
+      fn_returning: |_| Ok(full_profile()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_update_profile_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    update_profile: Some(UpdateProfile {
+      calls: 1,
+      param_request: update_profile::Request {
+        profile_id: ID,
+        name: Some(NEW_NAME),
+        ..Default::default()
+      },
+      fn_returning: |_| Ok(()),
+    }),
+    find_full_profile_by: Some(FindFullProfileBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(ID),
+      fn_returning: |_| {
+        Ok(FullProfile {
+          name: NEW_NAME.to_string(),
+          ..full_profile()
+        })
+      },
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_update_email_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    update_profile: Some(UpdateProfile {
+      calls: 1,
+      param_request: update_profile::Request {
+        profile_id: ID,
+        email_address: Some(EMAIL_ADDRESS),
+        ..Default::default()
+      },
+      fn_returning: |_| Ok(()),
+    }),
+    find_full_profile_by: Some(FindFullProfileBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(ID),
+      fn_returning: |_| Ok(full_profile()),
+    }),
+    store_verification_token: Some(StoreVerificationToken {
+      calls: 1,
+      param_purpose: VerificationPurpose::Email,
+      param_profile_id: ID.to_owned(),
+      param_target: EMAIL_ADDRESS.to_owned(),
+      fn_returning: |_| Ok(()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn generate_verification_token_successfully() -> MockIDGenerator {
+  let mut generator = MockIDGenerator::new();
+
+  generator
+    .expect_new_secret()
+    .times(1)
+    .returning(|| VERIFICATION_TOKEN.to_owned());
+
+  generator
+}
+
+pub(super) fn email_sender_send_verification() -> MockEmailSender {
+  let mut sender = MockEmailSender::new();
+
+  sender
+    .expect_send()
+    .times(1)
+    .withf(|message| message.to == EMAIL_ADDRESS && message.body.contains(VERIFICATION_TOKEN))
+    .returning(|_| Ok(()));
+
+  sender
+}
+
+pub(super) fn repository_send_telephone_code_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_full_profile_by: Some(FindFullProfileBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(ID),
+      fn_returning: |_| Ok(full_profile()),
+    }),
+    count_verification_tokens: Some(CountVerificationTokens {
+      calls: 1,
+      param_profile_id: ID.to_owned(),
+      param_purpose: VerificationPurpose::Telephone,
+      fn_returning: |_, _, _| Ok(0),
+    }),
+    store_verification_token: Some(StoreVerificationToken {
+      calls: 1,
+      param_purpose: VerificationPurpose::Telephone,
+      param_profile_id: ID.to_owned(),
+      param_target: TELEPHONE_NUMBER.to_owned(),
+      fn_returning: |_| Ok(()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_find_full_profile_with_checked_telephone() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_full_profile_by: Some(FindFullProfileBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(ID),
+      fn_returning: |_| {
+        Ok(FullProfile {
+          telephones: vec![Telephone {
+            number: TELEPHONE_NUMBER.to_string(),
+            checked: true,
+          }],
+          ..full_profile()
+        })
+      },
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn generate_telephone_code_successfully() -> MockIDGenerator {
+  let mut generator = MockIDGenerator::new();
+
+  generator
+    .expect_new_numeric_code()
+    .times(1)
+    .with(predicate::eq(6))
+    .returning(|_| TELEPHONE_CODE.to_owned());
+
+  generator
+}
+
+pub(super) fn sms_sender_send_code() -> MockSmsSender {
+  let mut sender = MockSmsSender::new();
+
+  sender
+    .expect_send()
+    .times(1)
+    .withf(|message| message.to == TELEPHONE_NUMBER && message.body.contains(TELEPHONE_CODE))
+    .returning(|_| Ok(()));
+
+  sender
+}
+
+pub(super) fn repository_verify_telephone_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_latest_verification_token: Some(FindLatestVerificationToken {
+      calls: 1,
+      param_profile_id: ID.to_owned(),
+      param_purpose: VerificationPurpose::Telephone,
+      param_target: TELEPHONE_NUMBER.to_owned(),
+      fn_returning: |_, _, _| {
+        Ok(VerificationTokenData {
+          id: 1,
+          purpose: VerificationPurpose::Telephone,
+          profile_id: ID.to_owned(),
+          target: TELEPHONE_NUMBER.to_owned(),
+          expires_at: Utc::now().naive_utc() + Duration::minutes(10),
+          used: false,
+          attempts: 0,
+        })
+      },
+    }),
+    attempt_verification_token: Some(AttemptVerificationToken {
+      calls: 1,
+      param_id: 1,
+      param_token: TELEPHONE_CODE.to_owned(),
+      param_max_attempts: 5,
+      fn_returning: |_, _, _| Ok(Some(true)),
+    }),
+    use_verification_token: Some(UseVerificationToken {
+      calls: 1,
+      param_id: 1,
+      fn_returning: |_| Ok(true),
+    }),
+    check_telephone: Some(CheckTelephone {
+      calls: 1,
+      param_profile_id: ID.to_owned(),
+      param_number: TELEPHONE_NUMBER.to_owned(),
+      fn_returning: |_, _| Ok(()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_find_full_profile_not_found() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_full_profile_by: Some(FindFullProfileBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(ID),
+      fn_returning: |_| {
+        Err(AppError::not_found(
+          "DB: nothing found with given parameters",
+        ))
+      },
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn sign_in_event_page() -> SignInEventPage {
+  SignInEventPage {
+    events: vec![SignInEvent {
+      method: SignInMethod::Username,
+      succeeded: true,
+      ..Default::default()
+    }],
+    page: 1,
+    per_page: 20,
+    total: 1,
+  }
+}
+
+pub(super) fn repository_find_sign_in_events_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_sign_in_events: Some(FindSignInEvents {
+      calls: 1,
+      param_profile_id: ID.to_owned(),
+      param_page: 1,
+      param_per_page: 20,
+      fn_returning: |_, _, _| Ok(sign_in_event_page()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_request_deletion_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_user_by: Some(FindUserBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(ID),
+      fn_returning: |_| {
+        Ok(UserData {
+          id: ID.to_owned(),
+          password: HASH_PASSWORD.to_owned(),
+          ..Default::default()
+        })
+      },
+    }),
+    request_deletion: Some(RequestDeletion {
+      calls: 1,
+      param_profile_id: ID.to_owned(),
+      fn_returning: |_| Ok(()),
+    }),
+    revoke_refresh_tokens: Some(RevokeRefreshTokens {
+      calls: 1,
+      param_profile_id: ID.to_owned(),
+      fn_returning: |_| Ok(()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_find_user_for_deletion() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_user_by: Some(FindUserBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(ID),
+      fn_returning: |_| {
+        Ok(UserData {
+          id: ID.to_owned(),
+          password: HASH_PASSWORD.to_owned(),
+          ..Default::default()
+        })
+      },
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn crypto_verify(valid: bool) -> MockCrypto {
+  let mut crypto_mock = MockCrypto::new();
+
+  crypto_mock
+    .expect_verify_password()
+    .times(1)
+    .with(predicate::eq(HASH_PASSWORD), predicate::eq(PASSWORD))
+    .returning(move |_, _| Ok(valid));
+
+  crypto_mock
+}
+
+pub(super) fn authenticated_user() -> AuthenticatedUser {
+  AuthenticatedUser {
+    profile_id: ID.to_owned(),
+    token_id: JTI.to_owned(),
+    expires_at: (get_current_timestamp() + 60 * 120) as usize,
+    ..Default::default()
+  }
+}
+
+pub(super) fn authenticated_user_with_session() -> AuthenticatedUser {
+  AuthenticatedUser {
+    session_id: Some(SESSION_ID.to_owned()),
+    ..authenticated_user()
+  }
+}
+
+pub(super) fn session() -> Session {
+  Session {
+    id: SESSION_ID.to_owned(),
+    device_label: String::from("Firefox on Linux"),
+    ip_address: Some(String::from("203.0.113.7")),
+    ..Default::default()
+  }
+}
+
+pub(super) fn repository_find_sessions_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_sessions: Some(FindSessions {
+      calls: 1,
+      param_profile_id: ID.to_owned(),
+      fn_returning: |_| Ok(vec![session()]),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_revoke_session(
+  fn_returning: fn(&str, &str) -> Result<(), AppError>,
+) -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    revoke_session: Some(RevokeSession {
+      calls: 1,
+      param_profile_id: ID.to_owned(),
+      param_session_id: OTHER_SESSION_ID.to_owned(),
+      fn_returning,
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_revoke_other_sessions_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    revoke_other_sessions: Some(RevokeOtherSessions {
+      calls: 1,
+      param_profile_id: ID.to_owned(),
+      param_keep_session_id: Some(SESSION_ID.to_owned()),
+      fn_returning: |_, _| Ok(vec![OTHER_SESSION_ID.to_owned()]),
+    }),
+    ..Default::default()
+  })
+}
+
+/// Revoked sessions go to the token service so their access tokens stop working.
+pub(super) fn token_service_revoke_session() -> MockTokenService {
+  let mut token_service_mock = MockTokenService::new();
+
+  token_service_mock
+    .expect_revoke()
+    .times(1)
+    .withf(|session_id, _| session_id == OTHER_SESSION_ID)
+    .returning(|_, _| Ok(()));
+
+  token_service_mock
+}
+
+pub(super) fn audit_log_record(action: &'static str, outcome: AuditOutcome) -> MockAuditLog {
+  let mut audit_log = MockAuditLog::new();
+
+  audit_log
+    .expect_record()
+    .times(1)
+    .withf(move |event| event.action == action && event.outcome == outcome)
+    .returning(|_| Ok(()));
+
+  audit_log
+}
+
+pub(super) fn repository_enroll_totp_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_full_profile_by: Some(FindFullProfileBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(ID),
+      fn_returning: |_| Ok(full_profile()),
+    }),
+    find_totp_authenticator: Some(FindTotpAuthenticator {
+      calls: 1,
+      param_profile_id: ID.to_string(),
+      fn_returning: |_| {
+        Err(AppError::not_found(
+          "DB: nothing found with given parameters",
+        ))
+      },
+    }),
+    store_totp_authenticator: Some(StoreTotpAuthenticator {
+      calls: 1,
+      param_profile_id: ID.to_string(),
+      param_secret: TOTP_SECRET.to_string(),
+      fn_returning: |_, _| Ok(()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn generate_totp_secret_successfully() -> MockIDGenerator {
+  let mut generator = MockIDGenerator::new();
+
+  generator
+    .expect_new_totp_secret()
+    .times(1)
+    .returning(|| TOTP_SECRET.to_owned());
+
+  generator
+}
+
+fn find_pending_totp_authenticator() -> FindTotpAuthenticator {
+  FindTotpAuthenticator {
+    calls: 1,
+    param_profile_id: ID.to_string(),
+    fn_returning: |_| {
+      Ok(TotpAuthenticatorData {
+        profile_id: ID.to_string(),
+        secret: TOTP_SECRET.to_string(),
+        last_used_step: None,
+        confirmed: false,
+      })
+    },
+  }
+}
+
+/// The mocked primitives answer the same code for every step, so the oldest
+/// step inside the drift window is the one confirmed.
+pub(super) fn repository_confirm_totp_successfully() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_totp_authenticator: Some(find_pending_totp_authenticator()),
+    confirm_totp_authenticator: Some(ConfirmTotpAuthenticator {
+      calls: 1,
+      param_profile_id: ID.to_string(),
+      param_step: totp::current_step() - 1,
+      param_recovery_codes: vec![RECOVERY_CODE.to_string(); 10],
+      fn_returning: |_, _, _| Ok(()),
+    }),
+    ..Default::default()
+  })
+}
+
+pub(super) fn repository_find_pending_totp() -> MockUserRepository {
+  build_mock_user_repository(Expectations {
+    find_totp_authenticator: Some(find_pending_totp_authenticator()),
+    ..Default::default()
+  })
+}
+
+pub(super) fn primitives_totp_code(code: &'static str) -> MockPrimitives {
+  let mut primitives = MockPrimitives::new();
+
+  primitives
+    .expect_totp_code()
+    .with(predicate::eq(TOTP_SECRET), predicate::always())
+    .returning(move |_, _| Ok(code.to_owned()));
+
+  primitives
+}
+
+pub(super) fn generate_recovery_codes_successfully() -> MockIDGenerator {
+  let mut generator = MockIDGenerator::new();
+
+  generator
+    .expect_new_numeric_code()
+    .times(10)
+    .with(predicate::eq(10))
+    .returning(|_| RECOVERY_CODE.to_owned());
+
+  generator
+}
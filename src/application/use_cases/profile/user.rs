@@ -0,0 +1,1358 @@
+use super::{
+  ConfirmTotpRequest, DeleteUserRequest, ListSignInsRequest, TelephoneCodeRequest,
+  UpdateUserProfileRequest, UserProfile, VerifyTelephoneRequest,
+};
+use crate::{
+  application::{
+    services::{
+      audit_log::AuditLog,
+      notification::{
+        email_sender::EmailSender,
+        sms_sender::{SmsMessage, SmsSender},
+      },
+      security::{
+        authenticated_user::AuthenticatedUser, identity_provider::IdentityProviderClient,
+        token_service::TokenService,
+      },
+    },
+    use_cases::authenticate::user::UserUseCase,
+  },
+  domain::{
+    core::user::{
+      confirm_totp, enroll_totp, get_profile, issue_telephone_code, list_sessions, list_sign_ins,
+      repository::UserRepository, request_deletion, revoke_sessions, verify_telephone, User,
+    },
+    entities::{
+      audit_event::{actions, NewAuditEvent},
+      session::Session,
+      sign_in_event::SignInEventPage,
+      totp::TotpEnrollment,
+      user::FullProfile,
+    },
+    error::AppError,
+    utilities::{crypto::Crypto, id_generator::IDGenerator, primitives::Primitives},
+  },
+};
+use async_trait::async_trait;
+use log::error;
+use serde_json::json;
+use validator::Validate;
+
+#[async_trait]
+impl<
+    Repository: UserRepository,
+    Token: TokenService,
+    Email: EmailSender,
+    Sms: SmsSender,
+    Idp: IdentityProviderClient,
+    Audit: AuditLog,
+    C: Crypto,
+    ID: IDGenerator,
+    P: Primitives,
+  > UserProfile for UserUseCase<'_, Repository, Token, Email, Sms, Idp, Audit, C, ID, P>
+{
+  async fn get_profile(&self, user: &AuthenticatedUser) -> Result<FullProfile, AppError> {
+    let profile = User::new(self.user_repository)
+      .get_profile(get_profile::Request {
+        profile_id: &user.profile_id,
+      })
+      .find_profile()
+      .await?
+      .response();
+
+    Ok(profile)
+  }
+
+  async fn update_profile(
+    &self,
+    request: &UpdateUserProfileRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<FullProfile, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let profile = User::new(self.user_repository)
+      .update_profile(request.to_domain_request(&user.profile_id)?)
+      .save()
+      .await?
+      .find_profile()
+      .await?
+      .response();
+
+    if let Some(address) = request.email {
+      let unverified = profile
+        .emails
+        .iter()
+        .any(|email| email.address == address && !email.checked);
+
+      // the profile is already saved, the address just stays unverified
+      if unverified {
+        if let Err(error) = self.send_email_verification(&profile.id, address).await {
+          error!("email verification delivery failed: {}", error);
+        }
+      }
+    }
+
+    Ok(profile)
+  }
+
+  async fn list_sign_ins(
+    &self,
+    request: &ListSignInsRequest,
+    user: &AuthenticatedUser,
+  ) -> Result<SignInEventPage, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let sign_ins = User::new(self.user_repository)
+      .list_sign_ins(list_sign_ins::Request {
+        profile_id: &user.profile_id,
+        page: request.page,
+        per_page: request.per_page,
+      })
+      .find_sign_ins()
+      .await?
+      .response();
+
+    Ok(sign_ins)
+  }
+
+  async fn request_deletion(
+    &self,
+    request: &DeleteUserRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<(), AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    User::new(self.user_repository)
+      .request_deletion(request_deletion::Request {
+        profile_id: &user.profile_id,
+        password: request.password,
+      })
+      .get_user()
+      .await?
+      .check_password(&self.utilities.crypto)?
+      .save()
+      .await?;
+
+    Ok(())
+  }
+
+  async fn send_telephone_code(
+    &self,
+    request: &TelephoneCodeRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<(), AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let profile = User::new(self.user_repository)
+      .get_profile(get_profile::Request {
+        profile_id: &user.profile_id,
+      })
+      .find_profile()
+      .await?
+      .response();
+
+    let telephone = profile
+      .telephones
+      .iter()
+      .find(|telephone| telephone.number == request.number)
+      .ok_or_else(|| AppError::not_found("telephone number not found in profile"))?;
+    if telephone.checked {
+      return Err(AppError::invalid_argument(
+        "telephone number already verified",
+      ));
+    }
+
+    let policy = &self.services.telephone_verification_policy;
+    let code = User::new(self.user_repository)
+      .issue_telephone_code(issue_telephone_code::Request {
+        profile_id: &profile.id,
+        number: request.number,
+        code_length: policy.code_length,
+        expires_in_min: policy.code_expiration_min,
+        max_codes_per_hour: policy.max_codes_per_hour,
+      })
+      .check_rate_limit()
+      .await?
+      .generate(&self.utilities.id_generator)
+      .store()
+      .await?
+      .response();
+
+    self
+      .services
+      .sms
+      .send(&SmsMessage {
+        to: request.number.to_string(),
+        body: format!("Your verification code is {}", code),
+      })
+      .await
+  }
+
+  async fn verify_telephone(
+    &self,
+    request: &VerifyTelephoneRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<String, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let number = User::new(self.user_repository)
+      .verify_telephone(verify_telephone::Request {
+        profile_id: &user.profile_id,
+        number: request.number,
+        code: request.code,
+        max_attempts: self.services.telephone_verification_policy.max_attempts,
+      })
+      .get_code()
+      .await?
+      .check_code()
+      .await?
+      .save()
+      .await?
+      .response();
+
+    Ok(number)
+  }
+
+  async fn enroll_totp(&self, user: &AuthenticatedUser) -> Result<TotpEnrollment, AppError> {
+    let profile = User::new(self.user_repository)
+      .get_profile(get_profile::Request {
+        profile_id: &user.profile_id,
+      })
+      .find_profile()
+      .await?
+      .response();
+
+    let enrollment = User::new(self.user_repository)
+      .enroll_totp(enroll_totp::Request {
+        profile_id: &profile.id,
+        issuer: &self.services.mfa_policy.issuer,
+        account_name: &profile.username,
+      })
+      .check_enrollment()
+      .await?
+      .generate(&self.utilities.id_generator)
+      .store()
+      .await?
+      .response();
+
+    Ok(enrollment)
+  }
+
+  async fn confirm_totp(
+    &self,
+    request: &ConfirmTotpRequest<'_>,
+    user: &AuthenticatedUser,
+  ) -> Result<Vec<String>, AppError> {
+    request
+      .validate()
+      .map_err(|err| AppError::invalid_argument(err.to_string()))?;
+
+    let policy = &self.services.mfa_policy;
+    let recovery_codes = User::new(self.user_repository)
+      .confirm_totp(confirm_totp::Request {
+        profile_id: &user.profile_id,
+        code: request.code,
+        allowed_drift_steps: policy.allowed_drift_steps,
+        recovery_code_count: policy.recovery_code_count,
+      })
+      .get_authenticator()
+      .await?
+      .check_code(&self.utilities.primitives)?
+      .generate_recovery_codes(&self.utilities.id_generator)
+      .save()
+      .await?
+      .response();
+
+    Ok(recovery_codes)
+  }
+
+  async fn list_sessions(&self, user: &AuthenticatedUser) -> Result<Vec<Session>, AppError> {
+    let sessions = User::new(self.user_repository)
+      .list_sessions(list_sessions::Request {
+        profile_id: &user.profile_id,
+      })
+      .find_sessions()
+      .await?
+      .response();
+
+    Ok(sessions)
+  }
+
+  async fn revoke_session(
+    &self,
+    session_id: &str,
+    user: &AuthenticatedUser,
+  ) -> Result<(), AppError> {
+    let result = self
+      .revoke_sessions(&user.profile_id, revoke_sessions::Sessions::One(session_id))
+      .await
+      .map(|_| ());
+
+    let event = NewAuditEvent {
+      metadata: json!({ "session_id": session_id }),
+      ..user.audit_event(actions::REVOKE_SESSION, Some(&user.profile_id))
+    };
+    self.audit(event, result).await
+  }
+
+  async fn revoke_other_sessions(&self, user: &AuthenticatedUser) -> Result<(), AppError> {
+    let revoked = self
+      .revoke_sessions(
+        &user.profile_id,
+        revoke_sessions::Sessions::AllExcept(user.session_id.as_deref()),
+      )
+      .await;
+
+    let metadata = match &revoked {
+      Ok(revoked) => json!({ "revoked": revoked.len() }),
+      Err(_) => json!({}),
+    };
+    let event = NewAuditEvent {
+      metadata,
+      ..user.audit_event(actions::REVOKE_OTHER_SESSIONS, Some(&user.profile_id))
+    };
+    self.audit(event, revoked).await.map(|_| ())
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::super::*;
+  use super::*;
+  use crate::{
+    application::services::{
+      audit_log::MockAuditLog,
+      notification::{email_sender::MockEmailSender, sms_sender::MockSmsSender},
+      security::{
+        email_verification_policy::EmailVerificationPolicy,
+        external_identity_policy::ExternalIdentityPolicy,
+        identity_provider::MockIdentityProviderClient, mfa_policy::MfaPolicy,
+        oidc_policy::OidcPolicy, password_reset_policy::PasswordResetPolicy,
+        sign_in_policy::SignInPolicy, telephone_verification_policy::TelephoneVerificationPolicy,
+        token_policy::TokenPolicy, token_service::MockTokenService,
+        webauthn_policy::WebAuthnPolicy,
+      },
+      Services,
+    },
+    domain::{
+      core::user::mocks::repository::{build_mock_user_repository, Expectations},
+      entities::audit_event::AuditOutcome,
+      error::Code,
+      utilities::{
+        crypto::MockCrypto, id_generator::MockIDGenerator, primitives::MockPrimitives, Utilities,
+      },
+    },
+  };
+
+  #[tokio::test]
+  async fn test_get_profile_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_full_profile_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.get_profile(&authenticated_user()).await.unwrap();
+
+    assert_eq!(response, full_profile());
+  }
+
+  #[tokio::test]
+  async fn test_update_profile_successfully() {
+    let request = UpdateUserProfileRequest {
+      name: Some(NEW_NAME),
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_update_profile_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut
+      .update_profile(&request, &authenticated_user())
+      .await
+      .unwrap();
+
+    assert_eq!(
+      response,
+      FullProfile {
+        name: NEW_NAME.to_string(),
+        ..full_profile()
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_update_profile_with_unverified_email_sends_verification() {
+    let request = UpdateUserProfileRequest {
+      email: Some(EMAIL_ADDRESS),
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &repository_update_email_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: email_sender_send_verification(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: generate_verification_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut
+      .update_profile(&request, &authenticated_user())
+      .await
+      .unwrap();
+
+    assert_eq!(response, full_profile());
+  }
+
+  #[tokio::test]
+  async fn test_update_profile_with_email_delivery_failure() {
+    let request = UpdateUserProfileRequest {
+      email: Some(EMAIL_ADDRESS),
+      ..Default::default()
+    };
+    let mut email = MockEmailSender::new();
+    email
+      .expect_send()
+      .times(1)
+      .returning(|_| Err(AppError::internal("smtp server unavailable")));
+
+    let sut = UserUseCase {
+      user_repository: &repository_update_email_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email,
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: generate_verification_token_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut
+      .update_profile(&request, &authenticated_user())
+      .await
+      .unwrap();
+
+    assert_eq!(response, full_profile());
+  }
+
+  #[tokio::test]
+  async fn test_update_profile_with_partial_address() {
+    let request = UpdateUserProfileRequest {
+      address_street: Some(STREET),
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.update_profile(&request, &authenticated_user()).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_update_profile_without_fields() {
+    let request = UpdateUserProfileRequest {
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.update_profile(&request, &authenticated_user()).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_update_profile_with_invalid_birth_date() {
+    let request = UpdateUserProfileRequest {
+      birth_date: Some("31/12/1990"),
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.update_profile(&request, &authenticated_user()).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::invalid_argument("birth date format invalid")
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_get_profile_not_found() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_full_profile_not_found(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.get_profile(&authenticated_user()).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::not_found("DB: nothing found with given parameters")
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_list_sign_ins_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_sign_in_events_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut
+      .list_sign_ins(&ListSignInsRequest::default(), &authenticated_user())
+      .await
+      .unwrap();
+
+    assert_eq!(response, sign_in_event_page());
+  }
+
+  #[tokio::test]
+  async fn test_list_sign_ins_with_invalid_page_size() {
+    let request = ListSignInsRequest {
+      per_page: 500,
+      ..Default::default()
+    };
+
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut.list_sign_ins(&request, &authenticated_user()).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_request_deletion_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_request_deletion_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_verify(true),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = DeleteUserRequest { password: PASSWORD };
+
+    assert_eq!(
+      sut.request_deletion(&request, &authenticated_user()).await,
+      Ok(())
+    );
+  }
+
+  #[tokio::test]
+  async fn test_request_deletion_with_wrong_password() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_user_for_deletion(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: crypto_verify(false),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = DeleteUserRequest { password: PASSWORD };
+
+    match sut.request_deletion(&request, &authenticated_user()).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::PermissionDenied),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_request_deletion_with_short_password() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = DeleteUserRequest { password: "123" };
+
+    match sut.request_deletion(&request, &authenticated_user()).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_send_telephone_code_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_send_telephone_code_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: sms_sender_send_code(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: generate_telephone_code_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = TelephoneCodeRequest {
+      number: TELEPHONE_NUMBER,
+    };
+
+    assert_eq!(
+      sut
+        .send_telephone_code(&request, &authenticated_user())
+        .await,
+      Ok(())
+    );
+  }
+
+  #[tokio::test]
+  async fn test_send_telephone_code_for_number_not_in_profile() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_full_profile_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = TelephoneCodeRequest {
+      number: "+1 2025550143",
+    };
+
+    match sut
+      .send_telephone_code(&request, &authenticated_user())
+      .await
+    {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_send_telephone_code_for_verified_number() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_full_profile_with_checked_telephone(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = TelephoneCodeRequest {
+      number: TELEPHONE_NUMBER,
+    };
+
+    match sut
+      .send_telephone_code(&request, &authenticated_user())
+      .await
+    {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::invalid_argument("telephone number already verified")
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_verify_telephone_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_verify_telephone_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = VerifyTelephoneRequest {
+      number: TELEPHONE_NUMBER,
+      code: TELEPHONE_CODE,
+    };
+
+    let response = sut
+      .verify_telephone(&request, &authenticated_user())
+      .await
+      .unwrap();
+
+    assert_eq!(response, TELEPHONE_NUMBER);
+  }
+
+  #[tokio::test]
+  async fn test_verify_telephone_without_code() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = VerifyTelephoneRequest {
+      number: TELEPHONE_NUMBER,
+      code: "",
+    };
+
+    match sut.verify_telephone(&request, &authenticated_user()).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_enroll_totp_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_enroll_totp_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy {
+          issuer: String::from("Rust API"),
+          ..Default::default()
+        },
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: generate_totp_secret_successfully(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.enroll_totp(&authenticated_user()).await.unwrap();
+
+    assert_eq!(response.secret, TOTP_SECRET);
+    assert!(response
+      .provisioning_uri
+      .starts_with("otpauth://totp/Rust%20API:john.doe?secret="));
+  }
+
+  #[tokio::test]
+  async fn test_confirm_totp_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_confirm_totp_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy {
+          issuer: String::from("Rust API"),
+          ..Default::default()
+        },
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: generate_recovery_codes_successfully(),
+        primitives: primitives_totp_code(TOTP_CODE),
+      },
+    };
+
+    let request = ConfirmTotpRequest { code: TOTP_CODE };
+
+    let response = sut
+      .confirm_totp(&request, &authenticated_user())
+      .await
+      .unwrap();
+
+    assert_eq!(response, vec![RECOVERY_CODE.to_string(); 10]);
+  }
+
+  #[tokio::test]
+  async fn test_confirm_totp_with_wrong_code() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_pending_totp(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy {
+          issuer: String::from("Rust API"),
+          ..Default::default()
+        },
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: primitives_totp_code("000000"),
+      },
+    };
+
+    let request = ConfirmTotpRequest { code: TOTP_CODE };
+
+    match sut.confirm_totp(&request, &authenticated_user()).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(
+        error,
+        AppError::invalid_argument("invalid authentication code")
+      ),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_confirm_totp_with_short_code() {
+    let sut = UserUseCase {
+      user_repository: &build_mock_user_repository(Expectations {
+        ..Default::default()
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy {
+          issuer: String::from("Rust API"),
+          ..Default::default()
+        },
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let request = ConfirmTotpRequest { code: "123" };
+
+    match sut.confirm_totp(&request, &authenticated_user()).await {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::InvalidArgument),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_list_sessions_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_find_sessions_successfully(),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: MockAuditLog::new(),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    let response = sut.list_sessions(&authenticated_user()).await.unwrap();
+
+    assert_eq!(response, vec![session()]);
+  }
+
+  #[tokio::test]
+  async fn test_revoke_session_successfully() {
+    let sut = UserUseCase {
+      user_repository: &repository_revoke_session(|_, _| Ok(())),
+      services: &Services {
+        token: token_service_revoke_session(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::REVOKE_SESSION, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    sut
+      .revoke_session(OTHER_SESSION_ID, &authenticated_user_with_session())
+      .await
+      .unwrap();
+  }
+
+  #[tokio::test]
+  async fn test_revoke_unknown_session() {
+    let sut = UserUseCase {
+      user_repository: &repository_revoke_session(|_, _| {
+        Err(AppError::not_found(
+          "DB: nothing found with given parameters",
+        ))
+      }),
+      services: &Services {
+        token: MockTokenService::new(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::REVOKE_SESSION, AuditOutcome::Failure),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    match sut
+      .revoke_session(OTHER_SESSION_ID, &authenticated_user_with_session())
+      .await
+    {
+      Ok(_) => panic!("should not be success"),
+      Err(error) => assert_eq!(error.code, Code::NotFound),
+    }
+  }
+
+  #[tokio::test]
+  async fn test_revoke_other_sessions_keeps_current() {
+    let sut = UserUseCase {
+      user_repository: &repository_revoke_other_sessions_successfully(),
+      services: &Services {
+        token: token_service_revoke_session(),
+        email: MockEmailSender::new(),
+        sms: MockSmsSender::new(),
+        identity_provider: MockIdentityProviderClient::new(),
+        audit: audit_log_record(actions::REVOKE_OTHER_SESSIONS, AuditOutcome::Success),
+        token_policy: TokenPolicy::default(),
+        sign_in_policy: SignInPolicy::default(),
+        email_verification_policy: EmailVerificationPolicy::default(),
+        telephone_verification_policy: TelephoneVerificationPolicy::default(),
+        password_reset_policy: PasswordResetPolicy::default(),
+        mfa_policy: MfaPolicy::default(),
+        webauthn_policy: WebAuthnPolicy::default(),
+        external_identity_policy: ExternalIdentityPolicy::default(),
+        oidc_policy: OidcPolicy::default(),
+      },
+      utilities: &Utilities {
+        crypto: MockCrypto::new(),
+        id_generator: MockIDGenerator::new(),
+        primitives: MockPrimitives::new(),
+      },
+    };
+
+    sut
+      .revoke_other_sessions(&authenticated_user_with_session())
+      .await
+      .unwrap();
+  }
+}
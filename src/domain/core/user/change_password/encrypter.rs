@@ -1,22 +1,21 @@
 use crate::domain::{
   core::user::{repository::UserRepository, User},
-  entities::user::UserData,
   error::AppError,
   utilities::crypto::Crypto,
 };
 
 use super::*;
 
-type UserChangePwdStateIn<'a, R> =
-  User<'a, ChangePassword<Request<'a>, UserData, PasswordChecked, NotSaved>, R>;
-type UserChangePwdStateOut<'a, R> =
-  User<'a, ChangePassword<RequestEncrypted<'a>, UserData, PasswordChecked, NotSaved>, R>;
+type UserChangePwdStateIn<'a, Db, R> =
+  User<'a, ChangePassword<Request<'a>, Db, PasswordChecked, NotSaved>, R>;
+type UserChangePwdStateOut<'a, Db, R> =
+  User<'a, ChangePassword<RequestEncrypted<'a>, Db, PasswordChecked, NotSaved>, R>;
 
-impl<'a, R: UserRepository> UserChangePwdStateIn<'a, R> {
+impl<'a, Db, R: UserRepository> UserChangePwdStateIn<'a, Db, R> {
   pub fn encrypt_password(
     self,
     cryto: &'a impl Crypto,
-  ) -> Result<UserChangePwdStateOut<'a, R>, AppError> {
+  ) -> Result<UserChangePwdStateOut<'a, Db, R>, AppError> {
     let password_hash = cryto.hash_password(self.state.request.password)?;
 
     let request_encrypt = RequestEncrypted {
@@ -44,6 +43,7 @@ mod tests {
 
   use crate::domain::{
     core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    entities::user::UserData,
     utilities::crypto::MockCrypto,
   };
 
@@ -2,6 +2,7 @@ use super::*;
 
 pub mod encrypter;
 pub mod repository;
+pub mod responder;
 pub mod validator;
 
 #[derive(Default)]
@@ -31,7 +32,7 @@ impl<'a, R: UserRepository> User<'a, NoState, R> {
   pub fn change_password(
     self,
     request: Request<'a>,
-  ) -> User<ChangePassword<Request<'a>, NoDbData, PasswordNotChecked, NotSaved>, R> {
+  ) -> User<'a, ChangePassword<Request<'a>, NoDbData, PasswordNotChecked, NotSaved>, R> {
     User {
       repository: self.repository,
       state: ChangePassword {
@@ -32,13 +32,13 @@ impl<'a, R: UserRepository>
   }
 }
 
-impl<'a, R: UserRepository>
-  User<'a, ChangePassword<RequestEncrypted<'a>, UserData, PasswordChecked, NotSaved>, R>
+impl<'a, Db, R: UserRepository>
+  User<'a, ChangePassword<RequestEncrypted<'a>, Db, PasswordChecked, NotSaved>, R>
 {
   pub async fn save(
     self,
   ) -> Result<
-    User<'a, ChangePassword<RequestEncrypted<'a>, UserData, PasswordChecked, UpdateSaved>, R>,
+    User<'a, ChangePassword<RequestEncrypted<'a>, Db, PasswordChecked, UpdateSaved>, R>,
     AppError,
   > {
     self
@@ -58,13 +58,27 @@ impl<'a, R: UserRepository>
   }
 }
 
+impl<'a, Db, R: UserRepository>
+  User<'a, ChangePassword<RequestEncrypted<'a>, Db, PasswordChecked, UpdateSaved>, R>
+{
+  /// Signs the profile out everywhere by revoking its refresh tokens.
+  pub async fn revoke_sessions(self) -> Result<Self, AppError> {
+    self
+      .repository
+      .revoke_refresh_tokens(self.state.request.profile_id)
+      .await?;
+
+    Ok(self)
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
 
   use crate::domain::{
     core::user::mocks::repository::{
-      build_mock_user_repository, Expectations, FindUserBy, UpdatePassword,
+      build_mock_user_repository, Expectations, FindUserBy, RevokeRefreshTokens, UpdatePassword,
     },
     entities::user::UserColumns,
   };
@@ -85,6 +99,7 @@ mod tests {
             name: NAME.to_string(),
             username: USERNAME.to_string(),
             password: PASSWORD.to_string(),
+            ..Default::default()
           })
         },
         param_column_with: UserColumns::Id(UUID),
@@ -114,6 +129,7 @@ mod tests {
         name: NAME.to_string(),
         username: USERNAME.to_string(),
         password: PASSWORD.to_string(),
+        ..Default::default()
       }
     )
   }
@@ -209,4 +225,35 @@ mod tests {
 
     assert_eq!(sut, Some(AppError::database_error(DB_ERROR_MESSAGE)))
   }
+
+  #[tokio::test]
+  async fn test_revoke_sessions_successfully() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      revoke_refresh_tokens: Some(RevokeRefreshTokens {
+        calls: 1,
+        param_profile_id: UUID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ChangePassword {
+        request: RequestEncrypted {
+          profile_id: UUID,
+          ..Default::default()
+        },
+        db_data: NoDbData,
+        password_checked: PasswordChecked(true),
+        saved: UpdateSaved,
+      },
+    };
+
+    let sut = user.revoke_sessions().await.unwrap();
+
+    assert_eq!(sut.state.saved, UpdateSaved)
+  }
 }
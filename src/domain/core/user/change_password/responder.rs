@@ -0,0 +1,66 @@
+use crate::domain::{
+  core::user::repository::UserRepository,
+  entities::user::{PublicUserData, UserData},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, ChangePassword<RequestEncrypted<'a>, UserData, PasswordChecked, UpdateSaved>, R>
+{
+  pub fn response(self) -> PublicUserData {
+    PublicUserData {
+      id: self.state.db_data.id,
+      username: self.state.db_data.username,
+      name: self.state.db_data.name,
+      mfa_enabled: self.state.db_data.mfa_enabled,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_right_data() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+    const NAME: &str = "john doe";
+    const USERNAME: &str = "john_doe";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ChangePassword {
+        request: RequestEncrypted {
+          profile_id: UUID,
+          password: String::from("mg3824m1htv8913dx6jrn9ui45g801q43tj"),
+        },
+        db_data: UserData {
+          id: UUID.to_string(),
+          name: NAME.to_string(),
+          username: USERNAME.to_string(),
+          ..Default::default()
+        },
+        password_checked: PasswordChecked(true),
+        saved: UpdateSaved,
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(
+      sut,
+      PublicUserData {
+        id: UUID.to_string(),
+        name: NAME.to_string(),
+        username: USERNAME.to_string(),
+        mfa_enabled: false,
+      }
+    )
+  }
+}
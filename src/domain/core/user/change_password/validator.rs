@@ -1,6 +1,9 @@
 use crate::domain::{
   core::user::{repository::UserRepository, User},
-  entities::user::UserData,
+  entities::{
+    user::UserData,
+    verification_token::{VerificationPurpose, VerificationTokenData},
+  },
   error::AppError,
   utilities::crypto::Crypto,
 };
@@ -12,6 +15,11 @@ type UserChangePwdStateIn<'a, R> =
 type UserChangePwdStateOut<'a, R> =
   User<'a, ChangePassword<Request<'a>, UserData, PasswordChecked, NotSaved>, R>;
 
+type UserResetPwdStateIn<'a, R> =
+  User<'a, ChangePassword<Request<'a>, NoDbData, PasswordNotChecked, NotSaved>, R>;
+type UserResetPwdStateOut<'a, R> =
+  User<'a, ChangePassword<Request<'a>, NoDbData, PasswordChecked, NotSaved>, R>;
+
 impl<'a, R: UserRepository> UserChangePwdStateIn<'a, R> {
   pub fn check_password(
     self,
@@ -38,6 +46,33 @@ impl<'a, R: UserRepository> UserChangePwdStateIn<'a, R> {
   }
 }
 
+impl<'a, R: UserRepository> UserResetPwdStateIn<'a, R> {
+  /// Stands in for the old password when it was forgotten: a redeemed reset
+  /// token for the same profile authorizes the change.
+  pub fn check_reset_token(
+    self,
+    token: &VerificationTokenData,
+  ) -> Result<UserResetPwdStateOut<'a, R>, AppError> {
+    if token.purpose != VerificationPurpose::PasswordReset
+      || token.profile_id != self.state.request.profile_id
+    {
+      return Err(AppError::permission_denied(
+        "The given token does not allow changing this password",
+      ));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: ChangePassword {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        password_checked: PasswordChecked(true),
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use mockall::predicate;
@@ -136,4 +171,69 @@ mod tests {
       ))
     );
   }
+
+  #[test]
+  fn test_check_reset_token_for_same_profile() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ChangePassword {
+        request: Request {
+          profile_id: UUID,
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user
+      .check_reset_token(&VerificationTokenData {
+        purpose: VerificationPurpose::PasswordReset,
+        profile_id: UUID.to_string(),
+        ..Default::default()
+      })
+      .unwrap();
+
+    assert!(sut.state.password_checked.0);
+  }
+
+  #[test]
+  fn test_check_reset_token_with_other_purpose() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ChangePassword {
+        request: Request {
+          profile_id: UUID,
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user
+      .check_reset_token(&VerificationTokenData {
+        purpose: VerificationPurpose::Email,
+        profile_id: UUID.to_string(),
+        ..Default::default()
+      })
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::permission_denied(
+        "The given token does not allow changing this password",
+      ))
+    );
+  }
 }
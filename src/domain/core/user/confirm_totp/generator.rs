@@ -0,0 +1,85 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::totp::TotpAuthenticatorData,
+  utilities::id_generator::IDGenerator,
+};
+
+use super::*;
+
+const RECOVERY_CODE_LENGTH: usize = 10;
+
+type UserGenerateStateIn<'a, R> =
+  User<'a, ConfirmTotp<Request<'a>, TotpAuthenticatorData, CodeChecked, NoCodes, NotSaved>, R>;
+type UserGenerateStateOut<'a, R> =
+  User<'a, ConfirmTotp<Request<'a>, TotpAuthenticatorData, CodeChecked, Vec<String>, NotSaved>, R>;
+
+impl<'a, R: UserRepository> UserGenerateStateIn<'a, R> {
+  /// One-time codes that stand in for the authenticator when it is lost.
+  pub fn generate_recovery_codes(
+    self,
+    id_generator: &'a impl IDGenerator,
+  ) -> UserGenerateStateOut<'a, R> {
+    let recovery_codes = (0..self.state.request.recovery_code_count)
+      .map(|_| id_generator.new_numeric_code(RECOVERY_CODE_LENGTH))
+      .collect();
+
+    User {
+      repository: self.repository,
+      state: ConfirmTotp {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        code_checked: self.state.code_checked,
+        recovery_codes,
+        saved: self.state.saved,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use mockall::predicate;
+
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    utilities::id_generator::MockIDGenerator,
+  };
+
+  use super::*;
+
+  const RECOVERY_CODE: &str = "0123456789";
+
+  #[test]
+  fn test_generate_recovery_codes() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let mut mock_id_generator = MockIDGenerator::new();
+    mock_id_generator
+      .expect_new_numeric_code()
+      .times(3)
+      .with(predicate::eq(RECOVERY_CODE_LENGTH))
+      .returning(|_| RECOVERY_CODE.to_string());
+
+    let user = User {
+      repository: &mock_repository,
+      state: ConfirmTotp {
+        request: Request {
+          recovery_code_count: 3,
+          ..Default::default()
+        },
+        db_data: TotpAuthenticatorData {
+          ..Default::default()
+        },
+        code_checked: CodeChecked(1),
+        recovery_codes: NoCodes,
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.generate_recovery_codes(&mock_id_generator);
+
+    assert_eq!(sut.state.recovery_codes, vec![RECOVERY_CODE.to_string(); 3]);
+  }
+}
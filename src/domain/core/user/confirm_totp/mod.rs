@@ -0,0 +1,84 @@
+use super::*;
+
+pub mod generator;
+pub mod repository;
+pub mod responder;
+pub mod validator;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct CodeNotChecked;
+
+/// Time step of the accepted code.
+#[derive(Debug, PartialEq)]
+pub struct CodeChecked(pub(super) i64);
+
+#[derive(Default)]
+pub struct NoCodes;
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct ConfirmTotp<Req, Db, Check, Codes, Save> {
+  request: Req,
+  db_data: Db,
+  code_checked: Check,
+  recovery_codes: Codes,
+  saved: Save,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub code: &'a str,
+  /// Steps accepted before and after the current one.
+  pub allowed_drift_steps: i64,
+  pub recovery_code_count: usize,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn confirm_totp(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, ConfirmTotp<Request<'a>, NoDbData, CodeNotChecked, NoCodes, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: ConfirmTotp {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_confirm_totp_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).confirm_totp(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -0,0 +1,184 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::totp::TotpAuthenticatorData,
+  error::{AppError, Code},
+};
+
+use super::*;
+
+const NOT_ENROLLED: &str = "no authenticator enrolled, start the enrollment first";
+
+type UserGetStateIn<'a, R> =
+  User<'a, ConfirmTotp<Request<'a>, NoDbData, CodeNotChecked, NoCodes, NotSaved>, R>;
+type UserGetStateOut<'a, R> =
+  User<'a, ConfirmTotp<Request<'a>, TotpAuthenticatorData, CodeNotChecked, NoCodes, NotSaved>, R>;
+
+impl<'a, R: UserRepository> UserGetStateIn<'a, R> {
+  pub async fn get_authenticator(self) -> Result<UserGetStateOut<'a, R>, AppError> {
+    let db_data = self
+      .repository
+      .find_totp_authenticator(self.state.request.profile_id)
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::invalid_argument(NOT_ENROLLED),
+        _ => error,
+      })?;
+
+    Ok(User {
+      repository: self.repository,
+      state: ConfirmTotp {
+        request: self.state.request,
+        db_data,
+        code_checked: self.state.code_checked,
+        recovery_codes: self.state.recovery_codes,
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+type UserSaveStateIn<'a, R> =
+  User<'a, ConfirmTotp<Request<'a>, TotpAuthenticatorData, CodeChecked, Vec<String>, NotSaved>, R>;
+type UserSaveStateOut<'a, R> =
+  User<'a, ConfirmTotp<Request<'a>, TotpAuthenticatorData, CodeChecked, Vec<String>, Saved>, R>;
+
+impl<'a, R: UserRepository> UserSaveStateIn<'a, R> {
+  /// Enables two-factor authentication, the accepted step being recorded as
+  /// used so the confirmation code cannot sign in afterwards.
+  pub async fn save(self) -> Result<UserSaveStateOut<'a, R>, AppError> {
+    self
+      .repository
+      .confirm_totp_authenticator(
+        self.state.request.profile_id,
+        self.state.code_checked.0,
+        &self.state.recovery_codes,
+      )
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: ConfirmTotp {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        code_checked: self.state.code_checked,
+        recovery_codes: self.state.recovery_codes,
+        saved: Saved(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, ConfirmTotpAuthenticator, Expectations, FindTotpAuthenticator,
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const SECRET: &str = "JBSWY3DPEHPK3PXP";
+  const STEP: i64 = 56_666_666;
+
+  fn mock_request() -> Request<'static> {
+    Request {
+      profile_id: PROFILE_ID,
+      ..Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn test_get_authenticator_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_totp_authenticator: Some(FindTotpAuthenticator {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| {
+          Ok(TotpAuthenticatorData {
+            profile_id: PROFILE_ID.to_string(),
+            secret: SECRET.to_string(),
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ConfirmTotp {
+        request: mock_request(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_authenticator().await.unwrap();
+
+    assert_eq!(
+      sut.state.db_data,
+      TotpAuthenticatorData {
+        profile_id: PROFILE_ID.to_string(),
+        secret: SECRET.to_string(),
+        ..Default::default()
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_get_authenticator_never_enrolled() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_totp_authenticator: Some(FindTotpAuthenticator {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ConfirmTotp {
+        request: mock_request(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_authenticator().await.err();
+
+    assert_eq!(sut, Some(AppError::invalid_argument(NOT_ENROLLED)));
+  }
+
+  #[tokio::test]
+  async fn test_save_confirms_authenticator() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      confirm_totp_authenticator: Some(ConfirmTotpAuthenticator {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_step: STEP,
+        param_recovery_codes: vec![String::from("0123456789")],
+        fn_returning: |_, _, _| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ConfirmTotp {
+        request: mock_request(),
+        db_data: TotpAuthenticatorData {
+          ..Default::default()
+        },
+        code_checked: CodeChecked(STEP),
+        recovery_codes: vec![String::from("0123456789")],
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.save().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+}
@@ -0,0 +1,49 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::totp::TotpAuthenticatorData,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, ConfirmTotp<Request<'a>, TotpAuthenticatorData, CodeChecked, Vec<String>, Saved>, R>
+{
+  /// Returns the recovery codes, only their hashes are kept.
+  pub fn response(self) -> Vec<String> {
+    self.state.recovery_codes
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_recovery_codes() {
+    const RECOVERY_CODE: &str = "0123456789";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ConfirmTotp {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: TotpAuthenticatorData {
+          ..Default::default()
+        },
+        code_checked: CodeChecked(1),
+        recovery_codes: vec![RECOVERY_CODE.to_string()],
+        saved: Saved(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(sut, vec![RECOVERY_CODE.to_string()]);
+  }
+}
@@ -0,0 +1,155 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, totp, User},
+  entities::totp::TotpAuthenticatorData,
+  error::AppError,
+  utilities::primitives::Primitives,
+};
+
+use super::*;
+
+const MFA_ALREADY_ENABLED: &str = "two-factor authentication already enabled";
+const CODE_INVALID: &str = "invalid authentication code";
+
+type UserCheckStateIn<'a, R> =
+  User<'a, ConfirmTotp<Request<'a>, TotpAuthenticatorData, CodeNotChecked, NoCodes, NotSaved>, R>;
+type UserCheckStateOut<'a, R> =
+  User<'a, ConfirmTotp<Request<'a>, TotpAuthenticatorData, CodeChecked, NoCodes, NotSaved>, R>;
+
+impl<'a, R: UserRepository> UserCheckStateIn<'a, R> {
+  /// A code from the authenticator proves it was set up with the secret.
+  pub fn check_code(
+    self,
+    primitives: &impl Primitives,
+  ) -> Result<UserCheckStateOut<'a, R>, AppError> {
+    let authenticator = &self.state.db_data;
+    let request = &self.state.request;
+
+    if authenticator.confirmed {
+      return Err(AppError::invalid_argument(MFA_ALREADY_ENABLED));
+    }
+
+    let step = totp::find_step(
+      primitives,
+      &authenticator.secret,
+      request.code,
+      totp::current_step(),
+      request.allowed_drift_steps,
+      authenticator.last_used_step,
+    )?
+    .ok_or_else(|| AppError::invalid_argument(CODE_INVALID))?;
+
+    Ok(User {
+      repository: self.repository,
+      state: ConfirmTotp {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        code_checked: CodeChecked(step),
+        recovery_codes: self.state.recovery_codes,
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use mockall::predicate;
+
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    utilities::primitives::MockPrimitives,
+  };
+
+  const SECRET: &str = "JBSWY3DPEHPK3PXP";
+  const CODE: &str = "287082";
+
+  fn mock_request() -> Request<'static> {
+    Request {
+      code: CODE,
+      allowed_drift_steps: 1,
+      ..Default::default()
+    }
+  }
+
+  fn mock_primitives(code: &'static str) -> MockPrimitives {
+    let mut mock_primitives = MockPrimitives::new();
+
+    mock_primitives
+      .expect_totp_code()
+      .with(predicate::eq(SECRET), predicate::always())
+      .returning(move |_, _| Ok(code.to_string()));
+
+    mock_primitives
+  }
+
+  fn authenticator() -> TotpAuthenticatorData {
+    TotpAuthenticatorData {
+      secret: SECRET.to_string(),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn test_check_valid_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ConfirmTotp {
+        request: mock_request(),
+        db_data: authenticator(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.check_code(&mock_primitives(CODE)).unwrap();
+
+    assert!(sut.state.code_checked.0 > 0);
+  }
+
+  #[test]
+  fn test_check_wrong_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ConfirmTotp {
+        request: mock_request(),
+        db_data: authenticator(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.check_code(&mock_primitives("000000")).err();
+
+    assert_eq!(sut, Some(AppError::invalid_argument(CODE_INVALID)));
+  }
+
+  #[test]
+  fn test_check_code_already_confirmed() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ConfirmTotp {
+        request: mock_request(),
+        db_data: TotpAuthenticatorData {
+          confirmed: true,
+          ..authenticator()
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.check_code(&MockPrimitives::new()).err();
+
+    assert_eq!(sut, Some(AppError::invalid_argument(MFA_ALREADY_ENABLED)));
+  }
+}
@@ -0,0 +1,171 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::session::NewSession,
+  utilities::id_generator::IDGenerator,
+};
+
+use super::*;
+
+const BROWSERS: [(&str, &str); 6] = [
+  ("Edg/", "Edge"),
+  ("OPR/", "Opera"),
+  ("Firefox/", "Firefox"),
+  ("Chrome/", "Chrome"),
+  ("CriOS/", "Chrome"),
+  ("Safari/", "Safari"),
+];
+
+const SYSTEMS: [(&str, &str); 7] = [
+  ("Windows", "Windows"),
+  ("iPhone", "iOS"),
+  ("iPad", "iPadOS"),
+  ("Android", "Android"),
+  ("CrOS", "ChromeOS"),
+  ("Mac OS X", "macOS"),
+  ("Linux", "Linux"),
+];
+
+/// Names the device after the browser and system in its user agent, the
+/// order of the lists matters as most browsers claim to be the others too.
+/// Other clients are named after their product token.
+fn device_label(user_agent: Option<&str>) -> String {
+  let Some(user_agent) = user_agent.filter(|user_agent| !user_agent.trim().is_empty()) else {
+    return String::from("Unknown device");
+  };
+  let find = |names: &[(&str, &'static str)]| {
+    names
+      .iter()
+      .find(|(token, _)| user_agent.contains(token))
+      .map(|(_, name)| *name)
+  };
+
+  match (find(&BROWSERS), find(&SYSTEMS)) {
+    (Some(browser), Some(system)) => format!("{} on {}", browser, system),
+    (Some(name), None) | (None, Some(name)) => name.to_string(),
+    (None, None) => user_agent
+      .split(['/', ' '])
+      .next()
+      .unwrap_or(user_agent)
+      .to_string(),
+  }
+}
+
+impl<'a, R: UserRepository> User<'a, CreateSession<Request<'a>, NoSession, NotSaved>, R> {
+  pub fn generate(
+    self,
+    id_generator: &'a impl IDGenerator,
+  ) -> User<'a, CreateSession<Request<'a>, NewSession, NotSaved>, R> {
+    let request = &self.state.request;
+    let session = NewSession {
+      id: id_generator.new_uuid(),
+      profile_id: request.profile_id.to_string(),
+      device_label: device_label(request.user_agent),
+      ip_address: request.ip_address.map(str::to_string),
+      user_agent: request.user_agent.map(str::to_string),
+    };
+
+    User {
+      repository: self.repository,
+      state: CreateSession {
+        request: self.state.request,
+        session,
+        saved: self.state.saved,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    utilities::id_generator::MockIDGenerator,
+  };
+
+  use super::*;
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const SESSION_ID: &str = "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed";
+  const FIREFOX_ON_LINUX: &str =
+    "Mozilla/5.0 (X11; Linux x86_64; rv:120.0) Gecko/20100101 Firefox/120.0";
+
+  #[test]
+  fn test_generate_session() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: CreateSession {
+        request: Request {
+          profile_id: PROFILE_ID,
+          ip_address: Some("127.0.0.1"),
+          user_agent: Some(FIREFOX_ON_LINUX),
+        },
+        ..Default::default()
+      },
+    };
+
+    let mut mock_id_generator = MockIDGenerator::new();
+    mock_id_generator
+      .expect_new_uuid()
+      .times(1)
+      .returning(|| SESSION_ID.to_owned());
+
+    let sut = user.generate(&mock_id_generator);
+
+    assert_eq!(
+      sut.state.session,
+      NewSession {
+        id: SESSION_ID.to_owned(),
+        profile_id: PROFILE_ID.to_owned(),
+        device_label: String::from("Firefox on Linux"),
+        ip_address: Some(String::from("127.0.0.1")),
+        user_agent: Some(FIREFOX_ON_LINUX.to_owned()),
+      }
+    );
+  }
+
+  #[test]
+  fn test_device_label() {
+    let cases = [
+      (
+        Some(
+          "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) \
+           Chrome/119.0.0.0 Safari/537.36 Edg/119.0.0.0",
+        ),
+        "Edge on Windows",
+      ),
+      (
+        Some(
+          "Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like \
+           Gecko) Version/17.1 Safari/605.1.15",
+        ),
+        "Safari on macOS",
+      ),
+      (
+        Some(
+          "Mozilla/5.0 (iPhone; CPU iPhone OS 17_1 like Mac OS X) AppleWebKit/605.1.15 (KHTML, \
+           like Gecko) CriOS/119.0.6045.109 Mobile/15E148 Safari/604.1",
+        ),
+        "Chrome on iOS",
+      ),
+      (
+        Some(
+          "Mozilla/5.0 (Linux; Android 14) AppleWebKit/537.36 (KHTML, like Gecko) \
+           Chrome/119.0.6045.163 Mobile Safari/537.36",
+        ),
+        "Chrome on Android",
+      ),
+      (Some(FIREFOX_ON_LINUX), "Firefox on Linux"),
+      (Some("curl/8.4.0"), "curl"),
+      (Some("  "), "Unknown device"),
+      (None, "Unknown device"),
+    ];
+
+    for (user_agent, label) in cases {
+      assert_eq!(device_label(user_agent), label, "{:?}", user_agent);
+    }
+  }
+}
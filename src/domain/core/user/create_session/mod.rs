@@ -0,0 +1,74 @@
+use super::*;
+
+pub mod generator;
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NoSession;
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct CreateSession<Req, Session, Save> {
+  request: Req,
+  session: Session,
+  saved: Save,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub ip_address: Option<&'a str>,
+  pub user_agent: Option<&'a str>,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn create_session(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, CreateSession<Request<'a>, NoSession, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: CreateSession {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_create_session_build() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).create_session(Request {
+      profile_id: UUID,
+      ip_address: Some("127.0.0.1"),
+      user_agent: None,
+    });
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        profile_id: UUID,
+        ip_address: Some("127.0.0.1"),
+        user_agent: None,
+      }
+    );
+  }
+}
@@ -0,0 +1,98 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::session::NewSession,
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, CreateSession<Request<'a>, NewSession, NotSaved>, R> {
+  pub async fn store(
+    self,
+  ) -> Result<User<'a, CreateSession<Request<'a>, NewSession, Saved>, R>, AppError> {
+    self.repository.store_session(&self.state.session).await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: CreateSession {
+        request: self.state.request,
+        session: self.state.session,
+        saved: Saved(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, StoreSession,
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  #[tokio::test]
+  async fn test_store_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_session: Some(StoreSession {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: CreateSession {
+        request: Request {
+          profile_id: PROFILE_ID,
+          ..Default::default()
+        },
+        session: NewSession {
+          profile_id: PROFILE_ID.to_string(),
+          ..Default::default()
+        },
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.store().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+
+  #[tokio::test]
+  async fn test_store_failed() {
+    const DB_ERROR_MESSAGE: &str = "Some error in the database";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_session: Some(StoreSession {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| Err(AppError::database_error(DB_ERROR_MESSAGE)),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: CreateSession {
+        request: Request {
+          profile_id: PROFILE_ID,
+          ..Default::default()
+        },
+        session: NewSession {
+          profile_id: PROFILE_ID.to_string(),
+          ..Default::default()
+        },
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.store().await.err();
+
+    assert_eq!(sut, Some(AppError::database_error(DB_ERROR_MESSAGE)));
+  }
+}
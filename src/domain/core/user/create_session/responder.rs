@@ -0,0 +1,45 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::session::NewSession,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, CreateSession<Request<'a>, NewSession, Saved>, R> {
+  pub fn response(self) -> String {
+    self.state.session.id
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_session_id() {
+    const SESSION_ID: &str = "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: CreateSession {
+        request: Request {
+          ..Default::default()
+        },
+        session: NewSession {
+          id: SESSION_ID.to_string(),
+          ..Default::default()
+        },
+        saved: Saved(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(sut, SESSION_ID);
+  }
+}
@@ -0,0 +1,66 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  utilities::id_generator::IDGenerator,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, EnrollTotp<Request<'a>, EnrollmentChecked, NoSecret, NotSaved>, R>
+{
+  pub fn generate(
+    self,
+    id_generator: &'a impl IDGenerator,
+  ) -> User<'a, EnrollTotp<Request<'a>, EnrollmentChecked, String, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: EnrollTotp {
+        request: self.state.request,
+        enrollment_checked: self.state.enrollment_checked,
+        secret: id_generator.new_totp_secret(),
+        saved: self.state.saved,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    utilities::id_generator::MockIDGenerator,
+  };
+
+  use super::*;
+
+  const SECRET: &str = "JBSWY3DPEHPK3PXP";
+
+  #[test]
+  fn test_generate_secret() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let mut mock_id_generator = MockIDGenerator::new();
+    mock_id_generator
+      .expect_new_totp_secret()
+      .times(1)
+      .returning(|| SECRET.to_string());
+
+    let user = User {
+      repository: &mock_repository,
+      state: EnrollTotp {
+        request: Request {
+          ..Default::default()
+        },
+        enrollment_checked: EnrollmentChecked(true),
+        secret: NoSecret,
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.generate(&mock_id_generator);
+
+    assert_eq!(sut.state.secret, SECRET);
+  }
+}
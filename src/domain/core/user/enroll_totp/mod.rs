@@ -0,0 +1,78 @@
+use super::*;
+
+pub mod generator;
+pub mod repository;
+pub mod responder;
+pub mod validator;
+
+#[derive(Default)]
+pub struct EnrollmentNotChecked;
+
+#[derive(Debug, PartialEq)]
+pub struct EnrollmentChecked(pub(super) bool);
+
+#[derive(Default)]
+pub struct NoSecret;
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct EnrollTotp<Req, Check, Secret, Save> {
+  request: Req,
+  enrollment_checked: Check,
+  secret: Secret,
+  saved: Save,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  /// Shown by authenticator apps next to `account_name`.
+  pub issuer: &'a str,
+  pub account_name: &'a str,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn enroll_totp(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, EnrollTotp<Request<'a>, EnrollmentNotChecked, NoSecret, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: EnrollTotp {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_enroll_totp_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).enroll_totp(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -0,0 +1,71 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  error::AppError,
+};
+
+use super::*;
+
+type UserStoreStateIn<'a, R> =
+  User<'a, EnrollTotp<Request<'a>, EnrollmentChecked, String, NotSaved>, R>;
+type UserStoreStateOut<'a, R> =
+  User<'a, EnrollTotp<Request<'a>, EnrollmentChecked, String, Saved>, R>;
+
+impl<'a, R: UserRepository> UserStoreStateIn<'a, R> {
+  pub async fn store(self) -> Result<UserStoreStateOut<'a, R>, AppError> {
+    self
+      .repository
+      .store_totp_authenticator(self.state.request.profile_id, &self.state.secret)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: EnrollTotp {
+        request: self.state.request,
+        enrollment_checked: self.state.enrollment_checked,
+        secret: self.state.secret,
+        saved: Saved(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, StoreTotpAuthenticator,
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const SECRET: &str = "JBSWY3DPEHPK3PXP";
+
+  #[tokio::test]
+  async fn test_store_secret_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_totp_authenticator: Some(StoreTotpAuthenticator {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_secret: SECRET.to_string(),
+        fn_returning: |_, _| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: EnrollTotp {
+        request: Request {
+          profile_id: PROFILE_ID,
+          ..Default::default()
+        },
+        enrollment_checked: EnrollmentChecked(true),
+        secret: SECRET.to_string(),
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.store().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+}
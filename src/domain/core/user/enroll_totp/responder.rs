@@ -0,0 +1,70 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, totp::PERIOD_SEC, User},
+  entities::totp::TotpEnrollment,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, EnrollTotp<Request<'a>, EnrollmentChecked, String, Saved>, R> {
+  /// Returns the secret with its key URI, the format authenticator apps read
+  /// from QR codes.
+  pub fn response(self) -> TotpEnrollment {
+    let request = &self.state.request;
+    let issuer = urlencoding::encode(request.issuer);
+
+    let provisioning_uri = format!(
+      "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits=6&period={}",
+      issuer,
+      urlencoding::encode(request.account_name),
+      self.state.secret,
+      issuer,
+      PERIOD_SEC
+    );
+
+    TotpEnrollment {
+      secret: self.state.secret,
+      provisioning_uri,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_provisioning_uri() {
+    const SECRET: &str = "JBSWY3DPEHPK3PXP";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: EnrollTotp {
+        request: Request {
+          issuer: "Rust API",
+          account_name: "john_doe",
+          ..Default::default()
+        },
+        enrollment_checked: EnrollmentChecked(true),
+        secret: SECRET.to_string(),
+        saved: Saved(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(
+      sut,
+      TotpEnrollment {
+        secret: SECRET.to_string(),
+        provisioning_uri: String::from(
+          "otpauth://totp/Rust%20API:john_doe?secret=JBSWY3DPEHPK3PXP&issuer=Rust%20API&algorithm=SHA1&digits=6&period=30"
+        ),
+      }
+    );
+  }
+}
@@ -0,0 +1,148 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  error::{AppError, Code},
+};
+
+use super::*;
+
+const MFA_ALREADY_ENABLED: &str = "two-factor authentication already enabled";
+
+type UserEnrollStateIn<'a, R> =
+  User<'a, EnrollTotp<Request<'a>, EnrollmentNotChecked, NoSecret, NotSaved>, R>;
+type UserEnrollStateOut<'a, R> =
+  User<'a, EnrollTotp<Request<'a>, EnrollmentChecked, NoSecret, NotSaved>, R>;
+
+impl<'a, R: UserRepository> UserEnrollStateIn<'a, R> {
+  /// A pending enrollment may be started over, a confirmed authenticator is
+  /// never replaced.
+  pub async fn check_enrollment(self) -> Result<UserEnrollStateOut<'a, R>, AppError> {
+    match self
+      .repository
+      .find_totp_authenticator(self.state.request.profile_id)
+      .await
+    {
+      Ok(authenticator) if authenticator.confirmed => {
+        return Err(AppError::invalid_argument(MFA_ALREADY_ENABLED))
+      }
+      Ok(_) => {}
+      Err(error) if error.code == Code::NotFound => {}
+      Err(error) => return Err(error),
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: EnrollTotp {
+        request: self.state.request,
+        enrollment_checked: EnrollmentChecked(true),
+        secret: self.state.secret,
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{
+      build_mock_user_repository, Expectations, FindTotpAuthenticator,
+    },
+    entities::totp::TotpAuthenticatorData,
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  fn mock_request() -> Request<'static> {
+    Request {
+      profile_id: PROFILE_ID,
+      ..Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn test_check_enrollment_without_authenticator() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_totp_authenticator: Some(FindTotpAuthenticator {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: EnrollTotp {
+        request: mock_request(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.check_enrollment().await.unwrap();
+
+    assert_eq!(sut.state.enrollment_checked, EnrollmentChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_enrollment_with_pending_authenticator() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_totp_authenticator: Some(FindTotpAuthenticator {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| {
+          Ok(TotpAuthenticatorData {
+            confirmed: false,
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: EnrollTotp {
+        request: mock_request(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.check_enrollment().await.unwrap();
+
+    assert_eq!(sut.state.enrollment_checked, EnrollmentChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_enrollment_with_confirmed_authenticator() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_totp_authenticator: Some(FindTotpAuthenticator {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| {
+          Ok(TotpAuthenticatorData {
+            confirmed: true,
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: EnrollTotp {
+        request: mock_request(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.check_enrollment().await.err();
+
+    assert_eq!(sut, Some(AppError::invalid_argument(MFA_ALREADY_ENABLED)));
+  }
+}
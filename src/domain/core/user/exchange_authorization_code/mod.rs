@@ -0,0 +1,78 @@
+use crate::domain::entities::oauth::{AuthorizationCodeData, OAuthClientData};
+
+use super::*;
+
+pub mod repository;
+pub mod responder;
+pub mod validator;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default, Debug, PartialEq)]
+pub struct CodeData {
+  pub code: AuthorizationCodeData,
+  pub client: OAuthClientData,
+}
+
+#[derive(Default)]
+pub struct NoGrant;
+
+#[derive(Default)]
+pub struct ExchangeAuthorizationCode<Req, Db, Grant> {
+  request: Req,
+  db_data: Db,
+  grant: Grant,
+}
+
+/// Token request of the `authorization_code` grant. `client_secret` is only
+/// sent by confidential clients.
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub client_id: &'a str,
+  pub client_secret: Option<&'a str>,
+  pub code: &'a str,
+  pub redirect_uri: &'a str,
+  pub code_verifier: &'a str,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn exchange_authorization_code(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, ExchangeAuthorizationCode<Request<'a>, NoDbData, NoGrant>, R> {
+    User {
+      repository: self.repository,
+      state: ExchangeAuthorizationCode {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_exchange_authorization_code_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).exchange_authorization_code(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -0,0 +1,158 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  error::{AppError, Code},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, ExchangeAuthorizationCode<Request<'a>, NoDbData, NoGrant>, R> {
+  pub async fn get_data(
+    self,
+  ) -> Result<User<'a, ExchangeAuthorizationCode<Request<'a>, CodeData, NoGrant>, R>, AppError> {
+    let client = self
+      .repository
+      .find_oauth_client(self.state.request.client_id)
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::unauthenticated("invalid OAuth client credentials"),
+        _ => error,
+      })?;
+
+    let code = self
+      .repository
+      .find_authorization_code(self.state.request.code)
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::invalid_argument("invalid authorization code"),
+        _ => error,
+      })?;
+
+    Ok(User {
+      repository: self.repository,
+      state: ExchangeAuthorizationCode {
+        request: self.state.request,
+        db_data: CodeData { code, client },
+        grant: self.state.grant,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::{
+      mocks::repository::{
+        build_mock_user_repository, Expectations, FindAuthorizationCode, FindOAuthClient,
+      },
+      repository::MockUserRepository,
+    },
+    entities::oauth::{AuthorizationCodeData, OAuthClientData},
+  };
+
+  const CLIENT_ID: &str = "5f1c2a9e-7b4d-4e3a-9c8f-1d2e3f4a5b6c";
+  const CODE: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+
+  fn exchanging_user(
+    mock_repository: &MockUserRepository,
+  ) -> User<'_, ExchangeAuthorizationCode<Request<'_>, NoDbData, NoGrant>, MockUserRepository> {
+    User {
+      repository: mock_repository,
+      state: ExchangeAuthorizationCode {
+        request: Request {
+          client_id: CLIENT_ID,
+          code: CODE,
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    }
+  }
+
+  #[tokio::test]
+  async fn test_get_data_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_oauth_client: Some(FindOAuthClient {
+        calls: 1,
+        param_client_id: CLIENT_ID.to_string(),
+        fn_returning: |_| {
+          Ok(OAuthClientData {
+            client_id: CLIENT_ID.to_string(),
+            ..Default::default()
+          })
+        },
+      }),
+      find_authorization_code: Some(FindAuthorizationCode {
+        calls: 1,
+        param_code: CODE.to_string(),
+        fn_returning: |_| {
+          Ok(AuthorizationCodeData {
+            id: 1,
+            client_id: CLIENT_ID.to_string(),
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let sut = exchanging_user(&mock_repository).get_data().await.unwrap();
+
+    assert_eq!(sut.state.db_data.code.id, 1);
+    assert_eq!(sut.state.db_data.client.client_id, CLIENT_ID);
+  }
+
+  #[tokio::test]
+  async fn test_get_data_with_unknown_client() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_oauth_client: Some(FindOAuthClient {
+        calls: 1,
+        param_client_id: CLIENT_ID.to_string(),
+        fn_returning: |_| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let sut = exchanging_user(&mock_repository).get_data().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::unauthenticated(
+        "invalid OAuth client credentials"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_get_data_with_unknown_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_oauth_client: Some(FindOAuthClient {
+        calls: 1,
+        param_client_id: CLIENT_ID.to_string(),
+        fn_returning: |_| Ok(Default::default()),
+      }),
+      find_authorization_code: Some(FindAuthorizationCode {
+        calls: 1,
+        param_code: CODE.to_string(),
+        fn_returning: |_| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let sut = exchanging_user(&mock_repository).get_data().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("invalid authorization code"))
+    );
+  }
+}
@@ -0,0 +1,48 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::oauth::AuthorizationGrant,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, ExchangeAuthorizationCode<Request<'a>, CodeData, AuthorizationGrant>, R>
+{
+  pub fn response(self) -> AuthorizationGrant {
+    self.state.grant
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_grant() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let grant = AuthorizationGrant {
+      profile_id: String::from("cc3b95d3-4ba4-4a90-bc09-119fd2a4c659"),
+      client_id: String::from("5f1c2a9e-7b4d-4e3a-9c8f-1d2e3f4a5b6c"),
+      scope: String::from("openid"),
+      nonce: None,
+    };
+
+    let user = User {
+      repository: &mock_repository,
+      state: ExchangeAuthorizationCode {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: CodeData {
+          ..Default::default()
+        },
+        grant: grant.clone(),
+      },
+    };
+
+    assert_eq!(user.response(), grant);
+  }
+}
@@ -0,0 +1,393 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::oauth::AuthorizationGrant,
+  error::AppError,
+  utilities::{crypto::Crypto, primitives::Primitives},
+};
+
+use super::*;
+
+/// S256 challenge of a PKCE code verifier, RFC 7636 section 4.2.
+pub fn pkce_challenge(primitives: &impl Primitives, code_verifier: &str) -> String {
+  URL_SAFE_NO_PAD.encode(primitives.sha256(code_verifier.as_bytes()))
+}
+
+impl<'a, R: UserRepository> User<'a, ExchangeAuthorizationCode<Request<'a>, CodeData, NoGrant>, R> {
+  /// Authenticates the client and checks the code was issued to it for the
+  /// same redirect URI and PKCE verifier, then consumes the code.
+  pub async fn check_grant(
+    self,
+    crypto: &impl Crypto,
+    primitives: &impl Primitives,
+  ) -> Result<
+    User<'a, ExchangeAuthorizationCode<Request<'a>, CodeData, AuthorizationGrant>, R>,
+    AppError,
+  > {
+    let request = &self.state.request;
+    let CodeData { code, client } = &self.state.db_data;
+
+    let client_authenticated = match (&client.secret_hash, request.client_secret) {
+      (None, _) => true,
+      (Some(hash), Some(secret)) => crypto.verify_password(hash, secret)?,
+      (Some(_), None) => false,
+    };
+    if !client_authenticated {
+      return Err(AppError::unauthenticated(
+        "invalid OAuth client credentials",
+      ));
+    }
+
+    if code.client_id != client.client_id {
+      return Err(AppError::invalid_argument("invalid authorization code"));
+    }
+    if code.used {
+      return Err(AppError::invalid_argument(
+        "authorization code already used",
+      ));
+    }
+    if code.expires_at < Utc::now().naive_utc() {
+      return Err(AppError::invalid_argument("authorization code expired"));
+    }
+    if code.redirect_uri != request.redirect_uri {
+      return Err(AppError::invalid_argument(
+        "redirect_uri does not match the authorization request",
+      ));
+    }
+    if pkce_challenge(primitives, request.code_verifier) != code.code_challenge {
+      return Err(AppError::invalid_argument("invalid code_verifier"));
+    }
+
+    if !self.repository.use_authorization_code(code.id).await? {
+      return Err(AppError::invalid_argument(
+        "authorization code already used",
+      ));
+    }
+
+    let grant = AuthorizationGrant {
+      profile_id: code.profile_id.clone(),
+      client_id: code.client_id.clone(),
+      scope: code.scope.clone(),
+      nonce: code.nonce.clone(),
+    };
+
+    Ok(User {
+      repository: self.repository,
+      state: ExchangeAuthorizationCode {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        grant,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Duration;
+
+  use super::*;
+  use crate::domain::{
+    core::user::{
+      mocks::{
+        pkce::{mock_sha256, CODE_CHALLENGE, CODE_VERIFIER},
+        repository::{build_mock_user_repository, Expectations, UseAuthorizationCode},
+      },
+      repository::MockUserRepository,
+    },
+    entities::oauth::{AuthorizationCodeData, OAuthClientData},
+    utilities::{crypto::MockCrypto, primitives::MockPrimitives},
+  };
+
+  const CLIENT_ID: &str = "5f1c2a9e-7b4d-4e3a-9c8f-1d2e3f4a5b6c";
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const REDIRECT_URI: &str = "https://app.example.com/callback";
+  const SECRET: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+  const SECRET_HASH: &str = "$2b$12$secret_hash";
+
+  fn valid_code() -> AuthorizationCodeData {
+    AuthorizationCodeData {
+      id: 1,
+      client_id: CLIENT_ID.to_string(),
+      profile_id: PROFILE_ID.to_string(),
+      redirect_uri: REDIRECT_URI.to_string(),
+      scope: String::from("openid profile"),
+      code_challenge: CODE_CHALLENGE.to_string(),
+      nonce: Some(String::from("n-0S6_WzA2Mj")),
+      expires_at: Utc::now().naive_utc() + Duration::minutes(5),
+      used: false,
+    }
+  }
+
+  fn checking_user<'a>(
+    mock_repository: &'a MockUserRepository,
+    request: Request<'a>,
+    code: AuthorizationCodeData,
+    secret_hash: Option<&str>,
+  ) -> User<'a, ExchangeAuthorizationCode<Request<'a>, CodeData, NoGrant>, MockUserRepository> {
+    User {
+      repository: mock_repository,
+      state: ExchangeAuthorizationCode {
+        request,
+        db_data: CodeData {
+          code,
+          client: OAuthClientData {
+            client_id: CLIENT_ID.to_string(),
+            secret_hash: secret_hash.map(str::to_string),
+            ..Default::default()
+          },
+        },
+        grant: NoGrant,
+      },
+    }
+  }
+
+  fn valid_request() -> Request<'static> {
+    Request {
+      client_id: CLIENT_ID,
+      client_secret: None,
+      code: SECRET,
+      redirect_uri: REDIRECT_URI,
+      code_verifier: CODE_VERIFIER,
+    }
+  }
+
+  fn use_authorization_code(
+    fn_returning: fn(i32) -> Result<bool, AppError>,
+  ) -> Expectations<'static> {
+    Expectations {
+      use_authorization_code: Some(UseAuthorizationCode {
+        calls: 1,
+        param_id: 1,
+        fn_returning,
+      }),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn test_pkce_challenge() {
+    assert_eq!(
+      pkce_challenge(&mock_sha256(), CODE_VERIFIER),
+      CODE_CHALLENGE
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_grant_of_public_client() {
+    let mock_repository = build_mock_user_repository(use_authorization_code(|_| Ok(true)));
+
+    let sut = checking_user(&mock_repository, valid_request(), valid_code(), None)
+      .check_grant(&MockCrypto::new(), &mock_sha256())
+      .await
+      .unwrap();
+
+    assert_eq!(
+      sut.state.grant,
+      AuthorizationGrant {
+        profile_id: PROFILE_ID.to_string(),
+        client_id: CLIENT_ID.to_string(),
+        scope: String::from("openid profile"),
+        nonce: Some(String::from("n-0S6_WzA2Mj")),
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_grant_of_confidential_client() {
+    let mock_repository = build_mock_user_repository(use_authorization_code(|_| Ok(true)));
+
+    let mut mock_crypto = MockCrypto::new();
+    mock_crypto
+      .expect_verify_password()
+      .withf(|hash, secret| hash == SECRET_HASH && secret == SECRET)
+      .times(1)
+      .returning(|_, _| Ok(true));
+
+    let sut = checking_user(
+      &mock_repository,
+      Request {
+        client_secret: Some(SECRET),
+        ..valid_request()
+      },
+      valid_code(),
+      Some(SECRET_HASH),
+    )
+    .check_grant(&mock_crypto, &mock_sha256())
+    .await;
+
+    assert!(sut.is_ok());
+  }
+
+  #[tokio::test]
+  async fn test_check_grant_of_confidential_client_without_secret() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = checking_user(
+      &mock_repository,
+      valid_request(),
+      valid_code(),
+      Some(SECRET_HASH),
+    )
+    .check_grant(&MockCrypto::new(), &MockPrimitives::new())
+    .await
+    .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::unauthenticated(
+        "invalid OAuth client credentials"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_grant_of_code_issued_to_other_client() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = checking_user(
+      &mock_repository,
+      valid_request(),
+      AuthorizationCodeData {
+        client_id: String::from("other_client"),
+        ..valid_code()
+      },
+      None,
+    )
+    .check_grant(&MockCrypto::new(), &MockPrimitives::new())
+    .await
+    .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("invalid authorization code"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_grant_of_used_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = checking_user(
+      &mock_repository,
+      valid_request(),
+      AuthorizationCodeData {
+        used: true,
+        ..valid_code()
+      },
+      None,
+    )
+    .check_grant(&MockCrypto::new(), &MockPrimitives::new())
+    .await
+    .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "authorization code already used"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_grant_of_expired_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = checking_user(
+      &mock_repository,
+      valid_request(),
+      AuthorizationCodeData {
+        expires_at: Utc::now().naive_utc() - Duration::minutes(1),
+        ..valid_code()
+      },
+      None,
+    )
+    .check_grant(&MockCrypto::new(), &MockPrimitives::new())
+    .await
+    .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("authorization code expired"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_grant_with_other_redirect_uri() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = checking_user(
+      &mock_repository,
+      Request {
+        redirect_uri: "https://app.example.com/other",
+        ..valid_request()
+      },
+      valid_code(),
+      None,
+    )
+    .check_grant(&MockCrypto::new(), &MockPrimitives::new())
+    .await
+    .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "redirect_uri does not match the authorization request"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_grant_with_wrong_code_verifier() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = checking_user(
+      &mock_repository,
+      Request {
+        code_verifier: "wrong-verifier-wrong-verifier-wrong-verifier",
+        ..valid_request()
+      },
+      valid_code(),
+      None,
+    )
+    .check_grant(&MockCrypto::new(), &mock_sha256())
+    .await
+    .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("invalid code_verifier"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_grant_of_concurrently_used_code() {
+    let mock_repository = build_mock_user_repository(use_authorization_code(|_| Ok(false)));
+
+    let sut = checking_user(&mock_repository, valid_request(), valid_code(), None)
+      .check_grant(&MockCrypto::new(), &mock_sha256())
+      .await
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "authorization code already used"
+      ))
+    );
+  }
+}
@@ -0,0 +1,79 @@
+use super::*;
+
+pub mod repository;
+pub mod responder;
+pub mod validator;
+
+pub use super::sign_in::Lockout;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct NoIdToken;
+
+#[derive(Default)]
+pub struct NoAccount;
+
+#[derive(Default)]
+pub struct ExternalSignIn<Req, Db, Token, Account> {
+  request: Req,
+  db_data: Db,
+  id_token: Token,
+  account: Account,
+}
+
+/// Callback of `provider` with the state of the sign-in. `issuer` and
+/// `client_id` are what the ID token must have been issued by and for.
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub provider: &'a str,
+  pub state: &'a str,
+  pub issuer: &'a str,
+  pub client_id: &'a str,
+  pub ip_address: Option<&'a str>,
+  pub user_agent: Option<&'a str>,
+  pub lockout: Lockout,
+  pub sign_up_expires_in_min: i64,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn external_sign_in(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, ExternalSignIn<Request<'a>, NoDbData, NoIdToken, NoAccount>, R> {
+    User {
+      repository: self.repository,
+      state: ExternalSignIn {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_external_sign_in_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).external_sign_in(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
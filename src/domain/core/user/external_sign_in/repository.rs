@@ -0,0 +1,642 @@
+use chrono::{Duration, Utc};
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::{
+    external_identity::{
+      ExternalAccount, ExternalClaims, ExternalIdToken, ExternalSignInData, NewExternalIdentity,
+    },
+    sign_in_event::{NewSignInEvent, SignInMethod},
+    user::{PublicUserData, UserColumns, UserData},
+  },
+  error::{AppError, Code},
+};
+
+use super::*;
+
+const INVALID_STATE: &str = "invalid external sign-in state";
+const STATE_USED: &str = "external sign-in state already used";
+
+type UserResolveStateIn<'a, R> =
+  User<'a, ExternalSignIn<Request<'a>, ExternalSignInData, ExternalIdToken, NoAccount>, R>;
+type UserResolveStateOut<'a, R> =
+  User<'a, ExternalSignIn<Request<'a>, ExternalSignInData, ExternalIdToken, ExternalAccount>, R>;
+
+impl<'a, R: UserRepository>
+  User<'a, ExternalSignIn<Request<'a>, NoDbData, NoIdToken, NoAccount>, R>
+{
+  /// A state is good for a single callback, of the provider it was issued
+  /// for, before it expires.
+  pub async fn get_data(
+    self,
+  ) -> Result<
+    User<'a, ExternalSignIn<Request<'a>, ExternalSignInData, NoIdToken, NoAccount>, R>,
+    AppError,
+  > {
+    let request = &self.state.request;
+    let sign_in = self
+      .repository
+      .find_external_sign_in(request.state)
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::unauthenticated(INVALID_STATE),
+        _ => error,
+      })?;
+
+    if sign_in.provider != request.provider {
+      return Err(AppError::unauthenticated(INVALID_STATE));
+    }
+    if sign_in.used {
+      return Err(AppError::unauthenticated(STATE_USED));
+    }
+    if sign_in.expires_at <= Utc::now().naive_utc() {
+      return Err(AppError::unauthenticated("external sign-in expired"));
+    }
+    if !self.repository.use_external_sign_in(sign_in.id).await? {
+      return Err(AppError::unauthenticated(STATE_USED));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: ExternalSignIn {
+        request: self.state.request,
+        db_data: sign_in,
+        id_token: self.state.id_token,
+        account: self.state.account,
+      },
+    })
+  }
+}
+
+impl<'a, R: UserRepository> UserResolveStateIn<'a, R> {
+  async fn record_sign_in(&self, profile_id: &str, succeeded: bool) -> Result<(), AppError> {
+    let request = &self.state.request;
+
+    self
+      .repository
+      .store_sign_in_event(&NewSignInEvent {
+        profile_id: profile_id.to_string(),
+        method: SignInMethod::External,
+        succeeded,
+        ip_address: request.ip_address.map(str::to_string),
+        user_agent: request.user_agent.map(str::to_string),
+      })
+      .await
+  }
+
+  /// Locked and disabled accounts are refused like on password sign-ins, and
+  /// signing in cancels a pending deletion.
+  async fn admit(&self, user: UserData) -> Result<PublicUserData, AppError> {
+    let lockout = &self.state.request.lockout;
+
+    if let Err(error) = lockout.check(&user) {
+      self.record_sign_in(&user.id, false).await?;
+      return Err(error);
+    }
+    if user.blocked_by_attempts {
+      self.repository.unlock_user(&user.id).await?;
+    }
+
+    if user.account_disabled {
+      self.record_sign_in(&user.id, false).await?;
+      return Err(AppError::permission_denied("account disabled"));
+    }
+
+    if user.requested_deletion {
+      self.repository.cancel_deletion(&user.id).await?;
+    }
+
+    self.repository.register_sign_in(&user.id).await?;
+    self.record_sign_in(&user.id, true).await?;
+
+    Ok(PublicUserData {
+      id: user.id,
+      username: user.username,
+      name: user.name,
+      mfa_enabled: user.mfa_enabled,
+    })
+  }
+
+  /// A provider account without a profile is linked to the one using the
+  /// same email only when both the provider and this service verified the
+  /// address, otherwise whoever registered it first at either side could
+  /// take over the other account.
+  async fn match_email(&self) -> Result<ExternalAccount, AppError> {
+    let request = &self.state.request;
+    let id_token = &self.state.id_token;
+    let email = id_token
+      .email
+      .as_deref()
+      .ok_or_else(|| AppError::invalid_argument("identity provider did not share an email"))?;
+
+    match self
+      .repository
+      .find_user_by(&UserColumns::Email(email))
+      .await
+    {
+      Ok(user) => {
+        if !id_token.email_verified || !user.email_checked {
+          return Err(AppError::already_exists(
+            "email already registered, sign in with it to use the identity provider",
+          ));
+        }
+
+        let user = self.admit(user).await?;
+        self
+          .repository
+          .store_external_identity(&NewExternalIdentity {
+            provider: request.provider.to_string(),
+            subject: id_token.sub.clone(),
+            profile_id: user.id.clone(),
+            email: Some(email.to_string()),
+          })
+          .await?;
+
+        Ok(ExternalAccount::SignedIn(user))
+      }
+      Err(error) if error.code == Code::NotFound => {
+        let claims = ExternalClaims {
+          subject: id_token.sub.clone(),
+          email: email.to_string(),
+          email_verified: id_token.email_verified,
+          name: id_token.name.clone(),
+          preferred_username: id_token.preferred_username.clone(),
+        };
+
+        self
+          .repository
+          .hold_external_sign_up(
+            self.state.db_data.id,
+            &claims,
+            Utc::now().naive_utc() + Duration::minutes(request.sign_up_expires_in_min),
+          )
+          .await?;
+
+        Ok(ExternalAccount::SignUpPending(claims))
+      }
+      Err(error) => Err(error),
+    }
+  }
+
+  /// Signs in the profile linked to the provider account, linking one by
+  /// email first when there is none, or keeps the claims for a sign-up.
+  pub async fn resolve(self) -> Result<UserResolveStateOut<'a, R>, AppError> {
+    let identity = self
+      .repository
+      .find_external_identity(self.state.request.provider, &self.state.id_token.sub)
+      .await;
+
+    let account = match identity {
+      Ok(identity) => {
+        let user = self
+          .repository
+          .find_user_by(&UserColumns::Id(&identity.profile_id))
+          .await?;
+
+        ExternalAccount::SignedIn(self.admit(user).await?)
+      }
+      Err(error) if error.code == Code::NotFound => self.match_email().await?,
+      Err(error) => return Err(error),
+    };
+
+    Ok(User {
+      repository: self.repository,
+      state: ExternalSignIn {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        id_token: self.state.id_token,
+        account,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::{
+    mocks::repository::{
+      build_mock_user_repository, CancelDeletion, Expectations, FindExternalIdentity,
+      FindExternalSignIn, FindUserBy, HoldExternalSignUp, RegisterSignIn, StoreExternalIdentity,
+      StoreSignInEvent, UseExternalSignIn,
+    },
+    repository::MockUserRepository,
+  };
+  use crate::domain::entities::external_identity::ExternalIdentityData;
+
+  const PROVIDER: &str = "acme";
+  const STATE: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+  const SUBJECT: &str = "248289761001";
+  const ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const EMAIL_ADDRESS: &str = "johndoe@company.com";
+  const NOT_FOUND: &str = "DB: nothing found with given parameters";
+
+  fn request() -> Request<'static> {
+    Request {
+      provider: PROVIDER,
+      state: STATE,
+      sign_up_expires_in_min: 30,
+      ..Default::default()
+    }
+  }
+
+  fn find_external_sign_in(
+    fn_returning: fn(&str) -> Result<ExternalSignInData, AppError>,
+  ) -> Option<FindExternalSignIn> {
+    Some(FindExternalSignIn {
+      calls: 1,
+      param_state: STATE.to_string(),
+      fn_returning,
+    })
+  }
+
+  fn pending_sign_in(_: &str) -> Result<ExternalSignInData, AppError> {
+    Ok(ExternalSignInData {
+      id: 1,
+      provider: PROVIDER.to_string(),
+      expires_at: Utc::now().naive_utc() + Duration::minutes(5),
+      ..Default::default()
+    })
+  }
+
+  async fn get_data(expectations: Expectations<'static>) -> Result<ExternalSignInData, AppError> {
+    let mock_repository = build_mock_user_repository(expectations);
+
+    User::new(&mock_repository)
+      .external_sign_in(request())
+      .get_data()
+      .await
+      .map(|user| user.state.db_data)
+  }
+
+  #[tokio::test]
+  async fn test_get_data_successfully() {
+    let sut = get_data(Expectations {
+      find_external_sign_in: find_external_sign_in(pending_sign_in),
+      use_external_sign_in: Some(UseExternalSignIn {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(true),
+      }),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(sut.id, 1);
+  }
+
+  #[tokio::test]
+  async fn test_get_data_with_unknown_state() {
+    let sut = get_data(Expectations {
+      find_external_sign_in: find_external_sign_in(|_| Err(AppError::not_found(NOT_FOUND))),
+      ..Default::default()
+    })
+    .await;
+
+    assert_eq!(sut, Err(AppError::unauthenticated(INVALID_STATE)));
+  }
+
+  #[tokio::test]
+  async fn test_get_data_of_another_provider() {
+    let sut = get_data(Expectations {
+      find_external_sign_in: find_external_sign_in(|state| {
+        Ok(ExternalSignInData {
+          provider: String::from("another"),
+          ..pending_sign_in(state)?
+        })
+      }),
+      ..Default::default()
+    })
+    .await;
+
+    assert_eq!(sut, Err(AppError::unauthenticated(INVALID_STATE)));
+  }
+
+  #[tokio::test]
+  async fn test_get_data_with_used_state() {
+    let sut = get_data(Expectations {
+      find_external_sign_in: find_external_sign_in(|state| {
+        Ok(ExternalSignInData {
+          used: true,
+          ..pending_sign_in(state)?
+        })
+      }),
+      ..Default::default()
+    })
+    .await;
+
+    assert_eq!(sut, Err(AppError::unauthenticated(STATE_USED)));
+  }
+
+  #[tokio::test]
+  async fn test_get_data_with_expired_state() {
+    let sut = get_data(Expectations {
+      find_external_sign_in: find_external_sign_in(|state| {
+        Ok(ExternalSignInData {
+          expires_at: Utc::now().naive_utc() - Duration::minutes(1),
+          ..pending_sign_in(state)?
+        })
+      }),
+      ..Default::default()
+    })
+    .await;
+
+    assert_eq!(
+      sut,
+      Err(AppError::unauthenticated("external sign-in expired"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_get_data_with_state_used_concurrently() {
+    let sut = get_data(Expectations {
+      find_external_sign_in: find_external_sign_in(pending_sign_in),
+      use_external_sign_in: Some(UseExternalSignIn {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(false),
+      }),
+      ..Default::default()
+    })
+    .await;
+
+    assert_eq!(sut, Err(AppError::unauthenticated(STATE_USED)));
+  }
+
+  fn resolving_user(
+    mock_repository: &MockUserRepository,
+    email_verified: bool,
+  ) -> UserResolveStateIn<'_, MockUserRepository> {
+    User {
+      repository: mock_repository,
+      state: ExternalSignIn {
+        request: request(),
+        db_data: ExternalSignInData {
+          id: 1,
+          ..Default::default()
+        },
+        id_token: ExternalIdToken {
+          sub: SUBJECT.to_string(),
+          email: Some(EMAIL_ADDRESS.to_string()),
+          email_verified,
+          name: Some(String::from("John Doe")),
+          ..Default::default()
+        },
+        account: NoAccount,
+      },
+    }
+  }
+
+  fn find_external_identity(
+    fn_returning: fn(&str, &str) -> Result<ExternalIdentityData, AppError>,
+  ) -> Option<FindExternalIdentity> {
+    Some(FindExternalIdentity {
+      calls: 1,
+      param_provider: PROVIDER.to_string(),
+      param_subject: SUBJECT.to_string(),
+      fn_returning,
+    })
+  }
+
+  fn find_user_by(
+    column: UserColumns<'static>,
+    fn_returning: fn(&UserColumns<'_>) -> Result<UserData, AppError>,
+  ) -> Option<FindUserBy<'static>> {
+    Some(FindUserBy {
+      calls: 1,
+      param_column_with: column,
+      fn_returning,
+    })
+  }
+
+  fn store_sign_in_event(succeeded: bool) -> Option<StoreSignInEvent> {
+    Some(StoreSignInEvent {
+      calls: 1,
+      param_event: NewSignInEvent {
+        profile_id: ID.to_string(),
+        method: SignInMethod::External,
+        succeeded,
+        ..Default::default()
+      },
+      fn_returning: |_| Ok(()),
+    })
+  }
+
+  fn signed_in_expectations() -> Expectations<'static> {
+    Expectations {
+      register_sign_in: Some(RegisterSignIn {
+        calls: 1,
+        param_profile_id: ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      store_sign_in_event: store_sign_in_event(true),
+      ..Default::default()
+    }
+  }
+
+  fn linked_user(_: &UserColumns<'_>) -> Result<UserData, AppError> {
+    Ok(UserData {
+      id: ID.to_string(),
+      email_checked: true,
+      ..Default::default()
+    })
+  }
+
+  fn signed_in_user() -> PublicUserData {
+    PublicUserData {
+      id: ID.to_string(),
+      username: String::new(),
+      name: String::new(),
+      mfa_enabled: false,
+    }
+  }
+
+  #[tokio::test]
+  async fn test_resolve_linked_identity() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_external_identity: find_external_identity(|_, _| {
+        Ok(ExternalIdentityData {
+          profile_id: ID.to_string(),
+          ..Default::default()
+        })
+      }),
+      find_user_by: find_user_by(UserColumns::Id(ID), linked_user),
+      ..signed_in_expectations()
+    });
+
+    let sut = resolving_user(&mock_repository, true)
+      .resolve()
+      .await
+      .unwrap()
+      .response();
+
+    assert_eq!(sut, ExternalAccount::SignedIn(signed_in_user()));
+  }
+
+  #[tokio::test]
+  async fn test_resolve_linked_identity_cancels_deletion() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_external_identity: find_external_identity(|_, _| {
+        Ok(ExternalIdentityData {
+          profile_id: ID.to_string(),
+          ..Default::default()
+        })
+      }),
+      find_user_by: find_user_by(UserColumns::Id(ID), |column| {
+        Ok(UserData {
+          requested_deletion: true,
+          ..linked_user(column)?
+        })
+      }),
+      cancel_deletion: Some(CancelDeletion {
+        calls: 1,
+        param_profile_id: ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..signed_in_expectations()
+    });
+
+    let sut = resolving_user(&mock_repository, true).resolve().await;
+
+    assert!(sut.is_ok());
+  }
+
+  #[tokio::test]
+  async fn test_resolve_linked_identity_of_disabled_account() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_external_identity: find_external_identity(|_, _| {
+        Ok(ExternalIdentityData {
+          profile_id: ID.to_string(),
+          ..Default::default()
+        })
+      }),
+      find_user_by: find_user_by(UserColumns::Id(ID), |column| {
+        Ok(UserData {
+          account_disabled: true,
+          ..linked_user(column)?
+        })
+      }),
+      store_sign_in_event: store_sign_in_event(false),
+      ..Default::default()
+    });
+
+    let sut = resolving_user(&mock_repository, true).resolve().await.err();
+
+    assert_eq!(sut, Some(AppError::permission_denied("account disabled")));
+  }
+
+  #[tokio::test]
+  async fn test_resolve_links_verified_email() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_external_identity: find_external_identity(|_, _| Err(AppError::not_found(NOT_FOUND))),
+      find_user_by: find_user_by(UserColumns::Email(EMAIL_ADDRESS), linked_user),
+      store_external_identity: Some(StoreExternalIdentity {
+        calls: 1,
+        param_provider: PROVIDER.to_string(),
+        param_subject: SUBJECT.to_string(),
+        param_profile_id: ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..signed_in_expectations()
+    });
+
+    let sut = resolving_user(&mock_repository, true)
+      .resolve()
+      .await
+      .unwrap()
+      .response();
+
+    assert_eq!(sut, ExternalAccount::SignedIn(signed_in_user()));
+  }
+
+  #[tokio::test]
+  async fn test_resolve_refuses_email_unverified_by_provider() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_external_identity: find_external_identity(|_, _| Err(AppError::not_found(NOT_FOUND))),
+      find_user_by: find_user_by(UserColumns::Email(EMAIL_ADDRESS), linked_user),
+      ..Default::default()
+    });
+
+    let sut = resolving_user(&mock_repository, false)
+      .resolve()
+      .await
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::already_exists(
+        "email already registered, sign in with it to use the identity provider"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_resolve_refuses_email_unverified_by_user() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_external_identity: find_external_identity(|_, _| Err(AppError::not_found(NOT_FOUND))),
+      find_user_by: find_user_by(UserColumns::Email(EMAIL_ADDRESS), |column| {
+        Ok(UserData {
+          email_checked: false,
+          ..linked_user(column)?
+        })
+      }),
+      ..Default::default()
+    });
+
+    let sut = resolving_user(&mock_repository, true).resolve().await.err();
+
+    assert_eq!(sut.map(|error| error.code), Some(Code::AlreadyExists));
+  }
+
+  #[tokio::test]
+  async fn test_resolve_holds_sign_up_of_unknown_email() {
+    let claims = ExternalClaims {
+      subject: SUBJECT.to_string(),
+      email: EMAIL_ADDRESS.to_string(),
+      email_verified: true,
+      name: Some(String::from("John Doe")),
+      preferred_username: None,
+    };
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_external_identity: find_external_identity(|_, _| Err(AppError::not_found(NOT_FOUND))),
+      find_user_by: find_user_by(UserColumns::Email(EMAIL_ADDRESS), |_| {
+        Err(AppError::not_found(NOT_FOUND))
+      }),
+      hold_external_sign_up: Some(HoldExternalSignUp {
+        calls: 1,
+        param_id: 1,
+        param_claims: claims.clone(),
+        fn_returning: |_, _, _| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let sut = resolving_user(&mock_repository, true)
+      .resolve()
+      .await
+      .unwrap()
+      .response();
+
+    assert_eq!(sut, ExternalAccount::SignUpPending(claims));
+  }
+
+  #[tokio::test]
+  async fn test_resolve_without_email() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_external_identity: find_external_identity(|_, _| Err(AppError::not_found(NOT_FOUND))),
+      ..Default::default()
+    });
+    let mut user = resolving_user(&mock_repository, true);
+    user.state.id_token.email = None;
+
+    let sut = user.resolve().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "identity provider did not share an email"
+      ))
+    );
+  }
+}
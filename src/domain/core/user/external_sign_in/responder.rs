@@ -0,0 +1,53 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::external_identity::{ExternalAccount, ExternalIdToken, ExternalSignInData},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, ExternalSignIn<Request<'a>, ExternalSignInData, ExternalIdToken, ExternalAccount>, R>
+{
+  pub fn response(self) -> ExternalAccount {
+    self.state.account
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    entities::external_identity::ExternalClaims,
+  };
+
+  #[test]
+  fn test_response_with_account() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let claims = ExternalClaims {
+      subject: String::from("248289761001"),
+      email: String::from("johndoe@company.com"),
+      ..Default::default()
+    };
+
+    let user = User {
+      repository: &mock_repository,
+      state: ExternalSignIn {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: ExternalSignInData {
+          ..Default::default()
+        },
+        id_token: ExternalIdToken {
+          ..Default::default()
+        },
+        account: ExternalAccount::SignUpPending(claims.clone()),
+      },
+    };
+
+    assert_eq!(user.response(), ExternalAccount::SignUpPending(claims));
+  }
+}
@@ -0,0 +1,202 @@
+use chrono::Utc;
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::external_identity::{ExternalIdToken, ExternalSignInData},
+  error::AppError,
+};
+
+use super::*;
+
+type UserCheckStateIn<'a, R> =
+  User<'a, ExternalSignIn<Request<'a>, ExternalSignInData, NoIdToken, NoAccount>, R>;
+type UserCheckStateOut<'a, R> =
+  User<'a, ExternalSignIn<Request<'a>, ExternalSignInData, ExternalIdToken, NoAccount>, R>;
+
+impl<'a, R: UserRepository> UserCheckStateIn<'a, R> {
+  /// Verifier the code must be redeemed with at the provider.
+  pub fn code_verifier(&self) -> &str {
+    &self.state.db_data.code_verifier
+  }
+
+  /// OpenID Connect Core 3.1.3.7: the token was issued by the provider, for
+  /// this client and this sign-in, and has not expired.
+  pub fn check_id_token(
+    self,
+    id_token: ExternalIdToken,
+  ) -> Result<UserCheckStateOut<'a, R>, AppError> {
+    let request = &self.state.request;
+
+    if id_token.iss != request.issuer {
+      return Err(AppError::unauthenticated(
+        "ID token was issued by another provider",
+      ));
+    }
+    if !id_token.aud.iter().any(|aud| aud == request.client_id) {
+      return Err(AppError::unauthenticated(
+        "ID token was issued to another client",
+      ));
+    }
+    if id_token.exp <= Utc::now().timestamp() {
+      return Err(AppError::unauthenticated("ID token expired"));
+    }
+    if id_token.nonce.as_deref() != Some(self.state.db_data.nonce.as_str()) {
+      return Err(AppError::unauthenticated(
+        "ID token nonce does not match the sign-in",
+      ));
+    }
+    if id_token.sub.is_empty() {
+      return Err(AppError::unauthenticated("ID token has no subject"));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: ExternalSignIn {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        id_token,
+        account: self.state.account,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::{
+    mocks::repository::{build_mock_user_repository, Expectations},
+    repository::MockUserRepository,
+  };
+
+  const ISSUER: &str = "https://idp.acme.com";
+  const CLIENT_ID: &str = "phoenix";
+  const NONCE: &str = "Qp8sX2nV5bL0cR7mK4jH1gF9dS6aZ3wE8tY5uI2oP0l";
+
+  fn checking_user(
+    mock_repository: &MockUserRepository,
+  ) -> UserCheckStateIn<'_, MockUserRepository> {
+    User {
+      repository: mock_repository,
+      state: ExternalSignIn {
+        request: Request {
+          provider: "acme",
+          issuer: ISSUER,
+          client_id: CLIENT_ID,
+          ..Default::default()
+        },
+        db_data: ExternalSignInData {
+          nonce: NONCE.to_string(),
+          code_verifier: String::from("verifier"),
+          ..Default::default()
+        },
+        id_token: NoIdToken,
+        account: NoAccount,
+      },
+    }
+  }
+
+  fn valid_id_token() -> ExternalIdToken {
+    ExternalIdToken {
+      iss: ISSUER.to_string(),
+      aud: vec![String::from("another"), CLIENT_ID.to_string()],
+      exp: Utc::now().timestamp() + 300,
+      nonce: Some(NONCE.to_string()),
+      sub: String::from("248289761001"),
+      ..Default::default()
+    }
+  }
+
+  fn check(id_token: ExternalIdToken) -> Result<(), AppError> {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    checking_user(&mock_repository)
+      .check_id_token(id_token)
+      .map(|_| ())
+  }
+
+  #[test]
+  fn test_code_verifier() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    assert_eq!(checking_user(&mock_repository).code_verifier(), "verifier");
+  }
+
+  #[test]
+  fn test_check_valid_id_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = checking_user(&mock_repository)
+      .check_id_token(valid_id_token())
+      .unwrap();
+
+    assert_eq!(sut.state.id_token, valid_id_token());
+  }
+
+  #[test]
+  fn test_check_id_token_of_another_issuer() {
+    assert_eq!(
+      check(ExternalIdToken {
+        iss: String::from("https://evil.example.com"),
+        ..valid_id_token()
+      }),
+      Err(AppError::unauthenticated(
+        "ID token was issued by another provider"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_check_id_token_of_another_audience() {
+    assert_eq!(
+      check(ExternalIdToken {
+        aud: vec![String::from("another")],
+        ..valid_id_token()
+      }),
+      Err(AppError::unauthenticated(
+        "ID token was issued to another client"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_check_expired_id_token() {
+    assert_eq!(
+      check(ExternalIdToken {
+        exp: Utc::now().timestamp() - 1,
+        ..valid_id_token()
+      }),
+      Err(AppError::unauthenticated("ID token expired"))
+    );
+  }
+
+  #[test]
+  fn test_check_id_token_with_another_nonce() {
+    assert_eq!(
+      check(ExternalIdToken {
+        nonce: None,
+        ..valid_id_token()
+      }),
+      Err(AppError::unauthenticated(
+        "ID token nonce does not match the sign-in"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_check_id_token_without_subject() {
+    assert_eq!(
+      check(ExternalIdToken {
+        sub: String::new(),
+        ..valid_id_token()
+      }),
+      Err(AppError::unauthenticated("ID token has no subject"))
+    );
+  }
+}
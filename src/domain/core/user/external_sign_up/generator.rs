@@ -0,0 +1,155 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::external_identity::{ExternalClaims, ExternalSignInData},
+  utilities::id_generator::IDGenerator,
+};
+
+use super::*;
+
+const USERNAME_BASE_MAX_LEN: usize = 20;
+const USERNAME_SUFFIX_LEN: usize = 6;
+
+/// Username the provider suggested, or the local part of the email, keeping
+/// only lowercase letters, digits, dots and underscores.
+fn username_base(claims: &ExternalClaims) -> String {
+  let source = claims
+    .preferred_username
+    .as_deref()
+    .filter(|username| !username.trim().is_empty())
+    .unwrap_or_else(|| claims.email.split('@').next().unwrap_or_default());
+
+  let base: String = source
+    .to_lowercase()
+    .chars()
+    .filter(|char| char.is_ascii_alphanumeric() || *char == '.' || *char == '_')
+    .take(USERNAME_BASE_MAX_LEN)
+    .collect();
+  let base = base.trim_matches('.');
+
+  if base.is_empty() {
+    String::from("user")
+  } else {
+    base.to_string()
+  }
+}
+
+impl<'a, R: UserRepository>
+  User<'a, ExternalSignUp<Request<'a>, ExternalSignInData, NoUsername>, R>
+{
+  /// A random suffix keeps the username from clashing with existing ones.
+  pub fn generate_username(
+    self,
+    id_generator: &impl IDGenerator,
+  ) -> User<'a, ExternalSignUp<Request<'a>, ExternalSignInData, String>, R> {
+    let base = self
+      .state
+      .db_data
+      .claims
+      .as_ref()
+      .map(username_base)
+      .unwrap_or_else(|| String::from("user"));
+    let username = format!(
+      "{}.{}",
+      base,
+      id_generator.new_numeric_code(USERNAME_SUFFIX_LEN)
+    );
+
+    User {
+      repository: self.repository,
+      state: ExternalSignUp {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        username,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    utilities::id_generator::MockIDGenerator,
+  };
+
+  use super::*;
+
+  fn claims(preferred_username: Option<&str>, email: &str) -> ExternalClaims {
+    ExternalClaims {
+      email: email.to_string(),
+      preferred_username: preferred_username.map(str::to_string),
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn test_username_base_from_preferred_username() {
+    assert_eq!(
+      username_base(&claims(Some("John.Doe"), "jd@company.com")),
+      "john.doe"
+    );
+  }
+
+  #[test]
+  fn test_username_base_from_email() {
+    assert_eq!(
+      username_base(&claims(None, "john+news@company.com")),
+      "johnnews"
+    );
+    assert_eq!(
+      username_base(&claims(Some(" "), "johndoe@company.com")),
+      "johndoe"
+    );
+  }
+
+  #[test]
+  fn test_username_base_without_usable_chars() {
+    assert_eq!(
+      username_base(&claims(Some("..."), "jd@company.com")),
+      "user"
+    );
+  }
+
+  #[test]
+  fn test_username_base_is_truncated() {
+    assert_eq!(
+      username_base(&claims(
+        Some("abcdefghijklmnopqrstuvwxyz"),
+        "jd@company.com"
+      )),
+      "abcdefghijklmnopqrst"
+    );
+  }
+
+  #[test]
+  fn test_generate_username() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let mut mock_id_generator = MockIDGenerator::new();
+    mock_id_generator
+      .expect_new_numeric_code()
+      .times(1)
+      .withf(|length| *length == USERNAME_SUFFIX_LEN)
+      .returning(|_| String::from("482913"));
+
+    let user = User {
+      repository: &mock_repository,
+      state: ExternalSignUp {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: ExternalSignInData {
+          claims: Some(claims(None, "johndoe@company.com")),
+          ..Default::default()
+        },
+        username: NoUsername,
+      },
+    };
+
+    let sut = user.generate_username(&mock_id_generator);
+
+    assert_eq!(sut.state.username, "johndoe.482913");
+  }
+}
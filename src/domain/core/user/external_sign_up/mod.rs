@@ -0,0 +1,66 @@
+use super::*;
+
+pub mod generator;
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct NoUsername;
+
+#[derive(Default)]
+pub struct ExternalSignUp<Req, Db, Username> {
+  request: Req,
+  db_data: Db,
+  username: Username,
+}
+
+/// Completion of the sign-up of a provider account that matched no profile,
+/// `state` being the one of its sign-in.
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub state: &'a str,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn external_sign_up(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, ExternalSignUp<Request<'a>, NoDbData, NoUsername>, R> {
+    User {
+      repository: self.repository,
+      state: ExternalSignUp {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_external_sign_up_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).external_sign_up(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -0,0 +1,190 @@
+use chrono::Utc;
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::external_identity::ExternalSignInData,
+  error::{AppError, Code},
+};
+
+use super::*;
+
+const INVALID_TOKEN: &str = "invalid sign-up token";
+const SIGN_UP_COMPLETED: &str = "sign-up already completed";
+
+impl<'a, R: UserRepository> User<'a, ExternalSignUp<Request<'a>, NoDbData, NoUsername>, R> {
+  /// The sign-up is marked completed before the profile is created, so a
+  /// token cannot create two.
+  pub async fn get_data(
+    self,
+  ) -> Result<User<'a, ExternalSignUp<Request<'a>, ExternalSignInData, NoUsername>, R>, AppError>
+  {
+    let sign_in = self
+      .repository
+      .find_external_sign_in(self.state.request.state)
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::unauthenticated(INVALID_TOKEN),
+        _ => error,
+      })?;
+
+    if sign_in.claims.is_none() {
+      return Err(AppError::unauthenticated(INVALID_TOKEN));
+    }
+    if sign_in.completed {
+      return Err(AppError::unauthenticated(SIGN_UP_COMPLETED));
+    }
+    if sign_in.expires_at <= Utc::now().naive_utc() {
+      return Err(AppError::unauthenticated("sign-up token expired"));
+    }
+    if !self
+      .repository
+      .complete_external_sign_up(sign_in.id)
+      .await?
+    {
+      return Err(AppError::unauthenticated(SIGN_UP_COMPLETED));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: ExternalSignUp {
+        request: self.state.request,
+        db_data: sign_in,
+        username: self.state.username,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Duration;
+
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{
+      build_mock_user_repository, CompleteExternalSignUp, Expectations, FindExternalSignIn,
+    },
+    entities::external_identity::ExternalClaims,
+  };
+
+  const STATE: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+
+  fn find_external_sign_in(
+    fn_returning: fn(&str) -> Result<ExternalSignInData, AppError>,
+  ) -> Option<FindExternalSignIn> {
+    Some(FindExternalSignIn {
+      calls: 1,
+      param_state: STATE.to_string(),
+      fn_returning,
+    })
+  }
+
+  fn held_sign_up(_: &str) -> Result<ExternalSignInData, AppError> {
+    Ok(ExternalSignInData {
+      id: 1,
+      provider: String::from("acme"),
+      expires_at: Utc::now().naive_utc() + Duration::minutes(5),
+      used: true,
+      claims: Some(ExternalClaims {
+        subject: String::from("248289761001"),
+        email: String::from("johndoe@company.com"),
+        ..Default::default()
+      }),
+      ..Default::default()
+    })
+  }
+
+  fn complete_external_sign_up(
+    fn_returning: fn(i32) -> Result<bool, AppError>,
+  ) -> Option<CompleteExternalSignUp> {
+    Some(CompleteExternalSignUp {
+      calls: 1,
+      param_id: 1,
+      fn_returning,
+    })
+  }
+
+  async fn get_data(expectations: Expectations<'static>) -> Result<ExternalSignInData, AppError> {
+    let mock_repository = build_mock_user_repository(expectations);
+
+    User::new(&mock_repository)
+      .external_sign_up(Request { state: STATE })
+      .get_data()
+      .await
+      .map(|user| user.state.db_data)
+  }
+
+  #[tokio::test]
+  async fn test_get_data_successfully() {
+    let sut = get_data(Expectations {
+      find_external_sign_in: find_external_sign_in(held_sign_up),
+      complete_external_sign_up: complete_external_sign_up(|_| Ok(true)),
+      ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(sut.id, 1);
+    assert_eq!(sut.claims, held_sign_up(STATE).unwrap().claims);
+  }
+
+  #[tokio::test]
+  async fn test_get_data_without_held_claims() {
+    let sut = get_data(Expectations {
+      find_external_sign_in: find_external_sign_in(|state| {
+        Ok(ExternalSignInData {
+          claims: None,
+          ..held_sign_up(state)?
+        })
+      }),
+      ..Default::default()
+    })
+    .await;
+
+    assert_eq!(sut, Err(AppError::unauthenticated(INVALID_TOKEN)));
+  }
+
+  #[tokio::test]
+  async fn test_get_data_of_completed_sign_up() {
+    let sut = get_data(Expectations {
+      find_external_sign_in: find_external_sign_in(|state| {
+        Ok(ExternalSignInData {
+          completed: true,
+          ..held_sign_up(state)?
+        })
+      }),
+      ..Default::default()
+    })
+    .await;
+
+    assert_eq!(sut, Err(AppError::unauthenticated(SIGN_UP_COMPLETED)));
+  }
+
+  #[tokio::test]
+  async fn test_get_data_of_expired_sign_up() {
+    let sut = get_data(Expectations {
+      find_external_sign_in: find_external_sign_in(|state| {
+        Ok(ExternalSignInData {
+          expires_at: Utc::now().naive_utc() - Duration::minutes(1),
+          ..held_sign_up(state)?
+        })
+      }),
+      ..Default::default()
+    })
+    .await;
+
+    assert_eq!(sut, Err(AppError::unauthenticated("sign-up token expired")));
+  }
+
+  #[tokio::test]
+  async fn test_get_data_completed_concurrently() {
+    let sut = get_data(Expectations {
+      find_external_sign_in: find_external_sign_in(held_sign_up),
+      complete_external_sign_up: complete_external_sign_up(|_| Ok(false)),
+      ..Default::default()
+    })
+    .await;
+
+    assert_eq!(sut, Err(AppError::unauthenticated(SIGN_UP_COMPLETED)));
+  }
+}
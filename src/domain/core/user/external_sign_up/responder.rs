@@ -0,0 +1,61 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::external_identity::{ExternalSignInData, ExternalSignUpData},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, ExternalSignUp<Request<'a>, ExternalSignInData, String>, R> {
+  pub fn response(self) -> ExternalSignUpData {
+    ExternalSignUpData {
+      provider: self.state.db_data.provider,
+      claims: self.state.db_data.claims.unwrap_or_default(),
+      username: self.state.username,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    entities::external_identity::ExternalClaims,
+  };
+
+  #[test]
+  fn test_response_with_sign_up() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let claims = ExternalClaims {
+      subject: String::from("248289761001"),
+      email: String::from("johndoe@company.com"),
+      ..Default::default()
+    };
+
+    let user = User {
+      repository: &mock_repository,
+      state: ExternalSignUp {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: ExternalSignInData {
+          provider: String::from("acme"),
+          claims: Some(claims.clone()),
+          ..Default::default()
+        },
+        username: String::from("johndoe.482913"),
+      },
+    };
+
+    assert_eq!(
+      user.response(),
+      ExternalSignUpData {
+        provider: String::from("acme"),
+        claims,
+        username: String::from("johndoe.482913"),
+      }
+    );
+  }
+}
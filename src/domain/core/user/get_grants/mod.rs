@@ -0,0 +1,50 @@
+use super::*;
+
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct GetGrants<Req, Db> {
+  request: Req,
+  db_data: Db,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn get_grants(self, request: Request<'a>) -> User<'a, GetGrants<Request<'a>, NoDbData>, R> {
+    User {
+      repository: self.repository,
+      state: GetGrants {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_get_grants_build() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).get_grants(Request { profile_id: UUID });
+
+    assert_eq!(sut.state.request, Request { profile_id: UUID });
+  }
+}
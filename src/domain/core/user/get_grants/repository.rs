@@ -0,0 +1,96 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::role::Grants,
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, GetGrants<Request<'a>, NoDbData>, R> {
+  pub async fn find_grants(self) -> Result<User<'a, GetGrants<Request<'a>, Grants>, R>, AppError> {
+    let db_data = self
+      .repository
+      .find_grants(self.state.request.profile_id)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: GetGrants {
+        request: self.state.request,
+        db_data,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations, FindGrants},
+    entities::role::{permissions, ADMIN_ROLE},
+  };
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  #[tokio::test]
+  async fn test_find_grants_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_grants: Some(FindGrants {
+        param_profile_id: UUID.to_owned(),
+        fn_returning: |_| {
+          Ok(Grants {
+            roles: vec![ADMIN_ROLE.to_string()],
+            permissions: vec![permissions::USERS_ADMIN.to_string()],
+          })
+        },
+        ..Default::default()
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: GetGrants {
+        request: Request { profile_id: UUID },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.find_grants().await.unwrap();
+
+    assert_eq!(
+      sut.state.db_data,
+      Grants {
+        roles: vec![ADMIN_ROLE.to_string()],
+        permissions: vec![permissions::USERS_ADMIN.to_string()],
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_fail_find_grants() {
+    const DB_ERROR_MESSAGE: &str = "Some error in the database";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_grants: Some(FindGrants {
+        param_profile_id: UUID.to_owned(),
+        fn_returning: |_| Err(AppError::database_error(DB_ERROR_MESSAGE)),
+        ..Default::default()
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: GetGrants {
+        request: Request { profile_id: UUID },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.find_grants().await.err();
+
+    assert_eq!(sut, Some(AppError::database_error(DB_ERROR_MESSAGE)));
+  }
+}
@@ -0,0 +1,51 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::role::Grants,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, GetGrants<Request<'a>, Grants>, R> {
+  pub fn response(self) -> Grants {
+    self.state.db_data
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    entities::role::ADMIN_ROLE,
+  };
+
+  #[test]
+  fn test_response_with_right_data() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: GetGrants {
+        request: Request { profile_id: UUID },
+        db_data: Grants {
+          roles: vec![ADMIN_ROLE.to_string()],
+          ..Default::default()
+        },
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(
+      sut,
+      Grants {
+        roles: vec![ADMIN_ROLE.to_string()],
+        ..Default::default()
+      }
+    );
+  }
+}
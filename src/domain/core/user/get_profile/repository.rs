@@ -0,0 +1,97 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::user::{FullProfile, UserColumns},
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, GetProfile<Request<'a>, NoDbData>, R> {
+  pub async fn find_profile(
+    self,
+  ) -> Result<User<'a, GetProfile<Request<'a>, FullProfile>, R>, AppError> {
+    let db_data = self
+      .repository
+      .find_full_profile_by(&UserColumns::Id(self.state.request.profile_id))
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: GetProfile {
+        request: self.state.request,
+        db_data,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, FindFullProfileBy,
+  };
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  #[tokio::test]
+  async fn test_find_profile_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_full_profile_by: Some(FindFullProfileBy {
+        calls: 1,
+        param_column_with: UserColumns::Id(UUID),
+        fn_returning: |_| {
+          Ok(FullProfile {
+            id: UUID.to_string(),
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: GetProfile {
+        request: Request { profile_id: UUID },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.find_profile().await.unwrap();
+
+    assert_eq!(
+      sut.state.db_data,
+      FullProfile {
+        id: UUID.to_string(),
+        ..Default::default()
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_fail_find_profile() {
+    const DB_ERROR_MESSAGE: &str = "Some error in the database";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_full_profile_by: Some(FindFullProfileBy {
+        calls: 1,
+        param_column_with: UserColumns::Id(UUID),
+        fn_returning: |_| Err(AppError::database_error(DB_ERROR_MESSAGE)),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: GetProfile {
+        request: Request { profile_id: UUID },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.find_profile().await.err();
+
+    assert_eq!(sut, Some(AppError::database_error(DB_ERROR_MESSAGE)));
+  }
+}
@@ -0,0 +1,220 @@
+use chrono::{Duration, Utc};
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::oauth::{NewAuthorizationCode, OAuthClientData},
+  error::AppError,
+  utilities::id_generator::IDGenerator,
+};
+
+use super::*;
+
+type UserGenerateStateIn<'a, R> =
+  User<'a, IssueAuthorizationCode<Request<'a>, OAuthClientData, NoCode, NotSaved>, R>;
+type UserGenerateStateOut<'a, R> =
+  User<'a, IssueAuthorizationCode<Request<'a>, OAuthClientData, NewAuthorizationCode, NotSaved>, R>;
+
+impl<'a, R: UserRepository> UserGenerateStateIn<'a, R> {
+  /// Every client must send a PKCE challenge, confidential ones included.
+  pub fn generate(
+    self,
+    id_generator: &impl IDGenerator,
+  ) -> Result<UserGenerateStateOut<'a, R>, AppError> {
+    let request = &self.state.request;
+
+    if request.code_challenge_method != PKCE_METHOD {
+      return Err(AppError::invalid_argument(
+        "code_challenge_method must be S256",
+      ));
+    }
+
+    let is_digest = request.code_challenge.len() == 43
+      && request
+        .code_challenge
+        .bytes()
+        .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_');
+    if !is_digest {
+      return Err(AppError::invalid_argument(
+        "code_challenge must be a base64url SHA-256 digest",
+      ));
+    }
+
+    if !request
+      .scope
+      .split_whitespace()
+      .any(|scope| scope == OPENID_SCOPE)
+    {
+      return Err(AppError::invalid_argument("scope must include openid"));
+    }
+
+    let code = NewAuthorizationCode {
+      code: id_generator.new_secret(),
+      client_id: self.state.db_data.client_id.clone(),
+      profile_id: request.profile_id.to_string(),
+      redirect_uri: request.redirect_uri.to_string(),
+      scope: request.scope.to_string(),
+      code_challenge: request.code_challenge.to_string(),
+      nonce: request.nonce.map(str::to_string),
+      expires_at: Utc::now().naive_utc() + Duration::minutes(request.expires_in_min),
+    };
+
+    Ok(User {
+      repository: self.repository,
+      state: IssueAuthorizationCode {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        code,
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::{
+    core::user::{
+      mocks::repository::{build_mock_user_repository, Expectations},
+      repository::MockUserRepository,
+    },
+    utilities::id_generator::MockIDGenerator,
+  };
+
+  use super::*;
+
+  const CLIENT_ID: &str = "5f1c2a9e-7b4d-4e3a-9c8f-1d2e3f4a5b6c";
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const REDIRECT_URI: &str = "https://app.example.com/callback";
+  const CODE: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+  const CODE_CHALLENGE: &str = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+
+  fn authorizing_user<'a>(
+    mock_repository: &'a MockUserRepository,
+    request: Request<'a>,
+  ) -> User<
+    'a,
+    IssueAuthorizationCode<Request<'a>, OAuthClientData, NoCode, NotSaved>,
+    MockUserRepository,
+  > {
+    User {
+      repository: mock_repository,
+      state: IssueAuthorizationCode {
+        request,
+        db_data: OAuthClientData {
+          client_id: CLIENT_ID.to_string(),
+          ..Default::default()
+        },
+        code: NoCode,
+        saved: NotSaved,
+      },
+    }
+  }
+
+  fn valid_request() -> Request<'static> {
+    Request {
+      profile_id: PROFILE_ID,
+      client_id: CLIENT_ID,
+      redirect_uri: REDIRECT_URI,
+      scope: "openid profile",
+      code_challenge: CODE_CHALLENGE,
+      code_challenge_method: PKCE_METHOD,
+      nonce: Some("n-0S6_WzA2Mj"),
+      expires_in_min: 5,
+    }
+  }
+
+  #[test]
+  fn test_generate_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let mut mock_id_generator = MockIDGenerator::new();
+    mock_id_generator
+      .expect_new_secret()
+      .times(1)
+      .returning(|| CODE.to_string());
+
+    let sut = authorizing_user(&mock_repository, valid_request())
+      .generate(&mock_id_generator)
+      .unwrap();
+
+    assert_eq!(sut.state.code.code, CODE);
+    assert_eq!(sut.state.code.client_id, CLIENT_ID);
+    assert_eq!(sut.state.code.profile_id, PROFILE_ID);
+    assert_eq!(sut.state.code.redirect_uri, REDIRECT_URI);
+    assert_eq!(sut.state.code.code_challenge, CODE_CHALLENGE);
+    assert_eq!(sut.state.code.nonce, Some(String::from("n-0S6_WzA2Mj")));
+    assert!(sut.state.code.expires_at > Utc::now().naive_utc() + Duration::minutes(4));
+  }
+
+  #[test]
+  fn test_generate_with_plain_challenge_method() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = authorizing_user(
+      &mock_repository,
+      Request {
+        code_challenge_method: "plain",
+        ..valid_request()
+      },
+    )
+    .generate(&MockIDGenerator::new())
+    .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "code_challenge_method must be S256"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_generate_with_malformed_challenge() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = authorizing_user(
+      &mock_repository,
+      Request {
+        code_challenge: "short",
+        ..valid_request()
+      },
+    )
+    .generate(&MockIDGenerator::new())
+    .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "code_challenge must be a base64url SHA-256 digest"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_generate_without_openid_scope() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = authorizing_user(
+      &mock_repository,
+      Request {
+        scope: "profile email",
+        ..valid_request()
+      },
+    )
+    .generate(&MockIDGenerator::new())
+    .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("scope must include openid"))
+    );
+  }
+}
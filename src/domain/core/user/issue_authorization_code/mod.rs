@@ -0,0 +1,84 @@
+use super::*;
+
+pub mod generator;
+pub mod repository;
+pub mod responder;
+
+/// Only S256 is accepted, `plain` would leak the verifier with the
+/// authorization request.
+pub const PKCE_METHOD: &str = "S256";
+pub const OPENID_SCOPE: &str = "openid";
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct NoCode;
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct IssueAuthorizationCode<Req, Db, Code, Save> {
+  request: Req,
+  db_data: Db,
+  code: Code,
+  saved: Save,
+}
+
+/// Authorization request of a client on behalf of the signed in profile.
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub client_id: &'a str,
+  pub redirect_uri: &'a str,
+  pub scope: &'a str,
+  pub code_challenge: &'a str,
+  pub code_challenge_method: &'a str,
+  pub nonce: Option<&'a str>,
+  pub expires_in_min: i64,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn issue_authorization_code(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, IssueAuthorizationCode<Request<'a>, NoDbData, NoCode, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: IssueAuthorizationCode {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_issue_authorization_code_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).issue_authorization_code(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -0,0 +1,207 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::oauth::{NewAuthorizationCode, OAuthClientData},
+  error::{AppError, Code},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, IssueAuthorizationCode<Request<'a>, NoDbData, NoCode, NotSaved>, R>
+{
+  /// Until the client and its redirect URI are known to be valid, errors must
+  /// not be redirected to the client.
+  pub async fn get_client(
+    self,
+  ) -> Result<
+    User<'a, IssueAuthorizationCode<Request<'a>, OAuthClientData, NoCode, NotSaved>, R>,
+    AppError,
+  > {
+    let client = self
+      .repository
+      .find_oauth_client(self.state.request.client_id)
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::invalid_argument("unknown OAuth client"),
+        _ => error,
+      })?;
+
+    if !client
+      .redirect_uris
+      .iter()
+      .any(|uri| uri == self.state.request.redirect_uri)
+    {
+      return Err(AppError::invalid_argument(
+        "redirect_uri is not registered for the client",
+      ));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: IssueAuthorizationCode {
+        request: self.state.request,
+        db_data: client,
+        code: self.state.code,
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+impl<'a, R: UserRepository>
+  User<'a, IssueAuthorizationCode<Request<'a>, OAuthClientData, NewAuthorizationCode, NotSaved>, R>
+{
+  pub async fn store(
+    self,
+  ) -> Result<
+    User<'a, IssueAuthorizationCode<Request<'a>, OAuthClientData, NewAuthorizationCode, Saved>, R>,
+    AppError,
+  > {
+    self
+      .repository
+      .store_authorization_code(&self.state.code)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: IssueAuthorizationCode {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        code: self.state.code,
+        saved: Saved(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::{
+    mocks::repository::{
+      build_mock_user_repository, Expectations, FindOAuthClient, StoreAuthorizationCode,
+    },
+    repository::MockUserRepository,
+  };
+
+  const CLIENT_ID: &str = "5f1c2a9e-7b4d-4e3a-9c8f-1d2e3f4a5b6c";
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const REDIRECT_URI: &str = "https://app.example.com/callback";
+  const CODE: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+
+  fn find_oauth_client(
+    fn_returning: fn(&str) -> Result<OAuthClientData, AppError>,
+  ) -> FindOAuthClient {
+    FindOAuthClient {
+      calls: 1,
+      param_client_id: CLIENT_ID.to_string(),
+      fn_returning,
+    }
+  }
+
+  fn registered_client(_: &str) -> Result<OAuthClientData, AppError> {
+    Ok(OAuthClientData {
+      client_id: CLIENT_ID.to_string(),
+      redirect_uris: vec![REDIRECT_URI.to_string()],
+      ..Default::default()
+    })
+  }
+
+  async fn get_client(
+    mock_repository: &MockUserRepository,
+    redirect_uri: &str,
+  ) -> Result<(), AppError> {
+    let user = User {
+      repository: mock_repository,
+      state: IssueAuthorizationCode {
+        request: Request {
+          client_id: CLIENT_ID,
+          redirect_uri,
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    };
+
+    user.get_client().await.map(|_| ())
+  }
+
+  #[tokio::test]
+  async fn test_get_client_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_oauth_client: Some(find_oauth_client(registered_client)),
+      ..Default::default()
+    });
+
+    assert_eq!(get_client(&mock_repository, REDIRECT_URI).await, Ok(()));
+  }
+
+  #[tokio::test]
+  async fn test_get_unknown_client() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_oauth_client: Some(find_oauth_client(|_| {
+        Err(AppError::not_found(
+          "DB: nothing found with given parameters",
+        ))
+      })),
+      ..Default::default()
+    });
+
+    assert_eq!(
+      get_client(&mock_repository, REDIRECT_URI).await,
+      Err(AppError::invalid_argument("unknown OAuth client"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_get_client_with_unregistered_redirect_uri() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_oauth_client: Some(find_oauth_client(registered_client)),
+      ..Default::default()
+    });
+
+    assert_eq!(
+      get_client(&mock_repository, "https://evil.example.com/callback").await,
+      Err(AppError::invalid_argument(
+        "redirect_uri is not registered for the client"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_store_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_authorization_code: Some(StoreAuthorizationCode {
+        calls: 1,
+        param_code: CODE.to_string(),
+        param_client_id: CLIENT_ID.to_string(),
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueAuthorizationCode {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: OAuthClientData {
+          ..Default::default()
+        },
+        code: NewAuthorizationCode {
+          code: CODE.to_string(),
+          client_id: CLIENT_ID.to_string(),
+          profile_id: PROFILE_ID.to_string(),
+          ..Default::default()
+        },
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.store().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+}
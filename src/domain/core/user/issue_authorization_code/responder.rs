@@ -0,0 +1,48 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::oauth::{NewAuthorizationCode, OAuthClientData},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, IssueAuthorizationCode<Request<'a>, OAuthClientData, NewAuthorizationCode, Saved>, R>
+{
+  pub fn response(self) -> String {
+    self.state.code.code
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_code() {
+    const CODE: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueAuthorizationCode {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: OAuthClientData {
+          ..Default::default()
+        },
+        code: NewAuthorizationCode {
+          code: CODE.to_string(),
+          ..Default::default()
+        },
+        saved: Saved(true),
+      },
+    };
+
+    assert_eq!(user.response(), CODE);
+  }
+}
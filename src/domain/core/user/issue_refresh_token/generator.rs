@@ -0,0 +1,119 @@
+use chrono::{Duration, Utc};
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::refresh_token::NewRefreshToken,
+  utilities::id_generator::IDGenerator,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, IssueRefreshToken<Request<'a>, NoToken, NotSaved>, R> {
+  pub fn generate(
+    self,
+    id_generator: &'a impl IDGenerator,
+  ) -> User<'a, IssueRefreshToken<Request<'a>, NewRefreshToken, NotSaved>, R> {
+    let family_id = self
+      .state
+      .request
+      .family_id
+      .map(ToString::to_string)
+      .unwrap_or_else(|| id_generator.new_uuid());
+
+    let token = NewRefreshToken {
+      token: id_generator.new_secret(),
+      family_id,
+      profile_id: self.state.request.profile_id.to_string(),
+      expires_at: Utc::now().naive_utc() + Duration::minutes(self.state.request.expires_in_min),
+    };
+
+    User {
+      repository: self.repository,
+      state: IssueRefreshToken {
+        request: self.state.request,
+        token,
+        saved: self.state.saved,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    utilities::id_generator::MockIDGenerator,
+  };
+
+  use super::*;
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const FAMILY_ID: &str = "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed";
+  const SECRET: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+
+  #[test]
+  fn test_generate_token_for_new_family() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueRefreshToken {
+        request: Request {
+          profile_id: PROFILE_ID,
+          family_id: None,
+          expires_in_min: 60,
+        },
+        ..Default::default()
+      },
+    };
+
+    let mut mock_id_generator = MockIDGenerator::new();
+    mock_id_generator
+      .expect_new_uuid()
+      .times(1)
+      .returning(|| FAMILY_ID.to_owned());
+    mock_id_generator
+      .expect_new_secret()
+      .times(1)
+      .returning(|| SECRET.to_owned());
+
+    let sut = user.generate(&mock_id_generator);
+
+    assert_eq!(sut.state.token.token, SECRET);
+    assert_eq!(sut.state.token.family_id, FAMILY_ID);
+    assert_eq!(sut.state.token.profile_id, PROFILE_ID);
+    assert!(sut.state.token.expires_at > Utc::now().naive_utc() + Duration::minutes(59));
+  }
+
+  #[test]
+  fn test_generate_token_keeping_family() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueRefreshToken {
+        request: Request {
+          profile_id: PROFILE_ID,
+          family_id: Some(FAMILY_ID),
+          expires_in_min: 60,
+        },
+        ..Default::default()
+      },
+    };
+
+    let mut mock_id_generator = MockIDGenerator::new();
+    mock_id_generator.expect_new_uuid().times(0);
+    mock_id_generator
+      .expect_new_secret()
+      .times(1)
+      .returning(|| SECRET.to_owned());
+
+    let sut = user.generate(&mock_id_generator);
+
+    assert_eq!(sut.state.token.family_id, FAMILY_ID);
+  }
+}
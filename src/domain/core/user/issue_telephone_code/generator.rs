@@ -0,0 +1,93 @@
+use chrono::{Duration, Utc};
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::{NewVerificationToken, VerificationPurpose},
+  utilities::id_generator::IDGenerator,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, IssueTelephoneCode<Request<'a>, LimitChecked, NoCode, NotSaved>, R>
+{
+  pub fn generate(
+    self,
+    id_generator: &'a impl IDGenerator,
+  ) -> User<'a, IssueTelephoneCode<Request<'a>, LimitChecked, NewVerificationToken, NotSaved>, R>
+  {
+    let request = &self.state.request;
+
+    let code = NewVerificationToken {
+      token: id_generator.new_numeric_code(request.code_length),
+      purpose: VerificationPurpose::Telephone,
+      profile_id: request.profile_id.to_string(),
+      target: request.number.to_string(),
+      expires_at: Utc::now().naive_utc() + Duration::minutes(request.expires_in_min),
+    };
+
+    User {
+      repository: self.repository,
+      state: IssueTelephoneCode {
+        request: self.state.request,
+        limit_checked: self.state.limit_checked,
+        code,
+        saved: self.state.saved,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use mockall::predicate;
+
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    utilities::id_generator::MockIDGenerator,
+  };
+
+  use super::*;
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const NUMBER: &str = "+1 4152370800";
+  const CODE: &str = "123456";
+
+  #[test]
+  fn test_generate_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueTelephoneCode {
+        request: Request {
+          profile_id: PROFILE_ID,
+          number: NUMBER,
+          code_length: 6,
+          expires_in_min: 10,
+          max_codes_per_hour: 5,
+        },
+        limit_checked: LimitChecked(true),
+        code: NoCode,
+        saved: NotSaved,
+      },
+    };
+
+    let mut mock_id_generator = MockIDGenerator::new();
+    mock_id_generator
+      .expect_new_numeric_code()
+      .times(1)
+      .with(predicate::eq(6))
+      .returning(|_| CODE.to_owned());
+
+    let sut = user.generate(&mock_id_generator);
+
+    assert_eq!(sut.state.code.token, CODE);
+    assert_eq!(sut.state.code.purpose, VerificationPurpose::Telephone);
+    assert_eq!(sut.state.code.profile_id, PROFILE_ID);
+    assert_eq!(sut.state.code.target, NUMBER);
+    assert!(sut.state.code.expires_at > Utc::now().naive_utc() + Duration::minutes(9));
+  }
+}
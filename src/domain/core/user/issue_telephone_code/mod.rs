@@ -0,0 +1,79 @@
+use super::*;
+
+pub mod generator;
+pub mod repository;
+pub mod responder;
+pub mod validator;
+
+#[derive(Default)]
+pub struct LimitNotChecked;
+
+#[derive(Debug, PartialEq)]
+pub struct LimitChecked(pub(super) bool);
+
+#[derive(Default)]
+pub struct NoCode;
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct IssueTelephoneCode<Req, Limit, Token, Save> {
+  request: Req,
+  limit_checked: Limit,
+  code: Token,
+  saved: Save,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub number: &'a str,
+  pub code_length: usize,
+  pub expires_in_min: i64,
+  pub max_codes_per_hour: i64,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn issue_telephone_code(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, IssueTelephoneCode<Request<'a>, LimitNotChecked, NoCode, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: IssueTelephoneCode {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_issue_telephone_code_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).issue_telephone_code(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
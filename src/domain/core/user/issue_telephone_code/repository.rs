@@ -0,0 +1,116 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::NewVerificationToken,
+  error::AppError,
+};
+
+use super::*;
+
+type UserStoreStateIn<'a, R> =
+  User<'a, IssueTelephoneCode<Request<'a>, LimitChecked, NewVerificationToken, NotSaved>, R>;
+type UserStoreStateOut<'a, R> =
+  User<'a, IssueTelephoneCode<Request<'a>, LimitChecked, NewVerificationToken, Saved>, R>;
+
+impl<'a, R: UserRepository> UserStoreStateIn<'a, R> {
+  pub async fn store(self) -> Result<UserStoreStateOut<'a, R>, AppError> {
+    self
+      .repository
+      .store_verification_token(&self.state.code)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: IssueTelephoneCode {
+        request: self.state.request,
+        limit_checked: self.state.limit_checked,
+        code: self.state.code,
+        saved: Saved(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{
+      build_mock_user_repository, Expectations, StoreVerificationToken,
+    },
+    entities::verification_token::VerificationPurpose,
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const NUMBER: &str = "+1 4152370800";
+
+  fn new_code() -> NewVerificationToken {
+    NewVerificationToken {
+      purpose: VerificationPurpose::Telephone,
+      profile_id: PROFILE_ID.to_string(),
+      target: NUMBER.to_string(),
+      ..Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn test_store_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_verification_token: Some(StoreVerificationToken {
+        calls: 1,
+        param_purpose: VerificationPurpose::Telephone,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_target: NUMBER.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueTelephoneCode {
+        request: Request {
+          ..Default::default()
+        },
+        limit_checked: LimitChecked(true),
+        code: new_code(),
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.store().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+
+  #[tokio::test]
+  async fn test_store_failed() {
+    const DB_ERROR_MESSAGE: &str = "Some error in the database";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_verification_token: Some(StoreVerificationToken {
+        calls: 1,
+        param_purpose: VerificationPurpose::Telephone,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_target: NUMBER.to_string(),
+        fn_returning: |_| Err(AppError::database_error(DB_ERROR_MESSAGE)),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueTelephoneCode {
+        request: Request {
+          ..Default::default()
+        },
+        limit_checked: LimitChecked(true),
+        code: new_code(),
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.store().await.err();
+
+    assert_eq!(sut, Some(AppError::database_error(DB_ERROR_MESSAGE)));
+  }
+}
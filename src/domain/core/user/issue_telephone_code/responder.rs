@@ -0,0 +1,48 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::NewVerificationToken,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, IssueTelephoneCode<Request<'a>, LimitChecked, NewVerificationToken, Saved>, R>
+{
+  pub fn response(self) -> String {
+    self.state.code.token
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_plain_code() {
+    const CODE: &str = "123456";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueTelephoneCode {
+        request: Request {
+          ..Default::default()
+        },
+        limit_checked: LimitChecked(true),
+        code: NewVerificationToken {
+          token: CODE.to_string(),
+          ..Default::default()
+        },
+        saved: Saved(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(sut, CODE);
+  }
+}
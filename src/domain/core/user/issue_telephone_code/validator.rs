@@ -0,0 +1,115 @@
+use chrono::{Duration, Utc};
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::VerificationPurpose,
+  error::AppError,
+};
+
+use super::*;
+
+const TOO_MANY_CODES: &str = "too many verification codes requested, try again later";
+
+type UserIssueStateIn<'a, R> =
+  User<'a, IssueTelephoneCode<Request<'a>, LimitNotChecked, NoCode, NotSaved>, R>;
+type UserIssueStateOut<'a, R> =
+  User<'a, IssueTelephoneCode<Request<'a>, LimitChecked, NoCode, NotSaved>, R>;
+
+impl<'a, R: UserRepository> UserIssueStateIn<'a, R> {
+  /// Every code costs a text message, so a profile may only ask for
+  /// `max_codes_per_hour` of them within the last hour.
+  pub async fn check_rate_limit(self) -> Result<UserIssueStateOut<'a, R>, AppError> {
+    let request = &self.state.request;
+
+    let issued = self
+      .repository
+      .count_verification_tokens(
+        request.profile_id,
+        VerificationPurpose::Telephone,
+        Utc::now().naive_utc() - Duration::hours(1),
+      )
+      .await?;
+
+    if issued >= request.max_codes_per_hour {
+      return Err(AppError::too_many_requests(TOO_MANY_CODES));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: IssueTelephoneCode {
+        request: self.state.request,
+        limit_checked: LimitChecked(true),
+        code: self.state.code,
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, CountVerificationTokens, Expectations,
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  fn mock_request() -> Request<'static> {
+    Request {
+      profile_id: PROFILE_ID,
+      max_codes_per_hour: 5,
+      ..Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn test_check_rate_limit_within_limit() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      count_verification_tokens: Some(CountVerificationTokens {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_purpose: VerificationPurpose::Telephone,
+        fn_returning: |_, _, _| Ok(4),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueTelephoneCode {
+        request: mock_request(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.check_rate_limit().await.unwrap();
+
+    assert_eq!(sut.state.limit_checked, LimitChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_rate_limit_exceeded() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      count_verification_tokens: Some(CountVerificationTokens {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_purpose: VerificationPurpose::Telephone,
+        fn_returning: |_, _, _| Ok(5),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueTelephoneCode {
+        request: mock_request(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.check_rate_limit().await.err();
+
+    assert_eq!(sut, Some(AppError::too_many_requests(TOO_MANY_CODES)));
+  }
+}
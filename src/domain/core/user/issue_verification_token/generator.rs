@@ -0,0 +1,81 @@
+use chrono::{Duration, Utc};
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::NewVerificationToken,
+  utilities::id_generator::IDGenerator,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, IssueVerificationToken<Request<'a>, NoToken, NotSaved>, R> {
+  pub fn generate(
+    self,
+    id_generator: &'a impl IDGenerator,
+  ) -> User<'a, IssueVerificationToken<Request<'a>, NewVerificationToken, NotSaved>, R> {
+    let token = NewVerificationToken {
+      token: id_generator.new_secret(),
+      purpose: self.state.request.purpose,
+      profile_id: self.state.request.profile_id.to_string(),
+      target: self.state.request.target.to_string(),
+      expires_at: Utc::now().naive_utc() + Duration::minutes(self.state.request.expires_in_min),
+    };
+
+    User {
+      repository: self.repository,
+      state: IssueVerificationToken {
+        request: self.state.request,
+        token,
+        saved: self.state.saved,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    utilities::id_generator::MockIDGenerator,
+  };
+
+  use super::*;
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const EMAIL: &str = "user@email.com";
+  const SECRET: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+
+  #[test]
+  fn test_generate_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueVerificationToken {
+        request: Request {
+          profile_id: PROFILE_ID,
+          purpose: VerificationPurpose::Email,
+          target: EMAIL,
+          expires_in_min: 60,
+        },
+        ..Default::default()
+      },
+    };
+
+    let mut mock_id_generator = MockIDGenerator::new();
+    mock_id_generator
+      .expect_new_secret()
+      .times(1)
+      .returning(|| SECRET.to_owned());
+
+    let sut = user.generate(&mock_id_generator);
+
+    assert_eq!(sut.state.token.token, SECRET);
+    assert_eq!(sut.state.token.purpose, VerificationPurpose::Email);
+    assert_eq!(sut.state.token.profile_id, PROFILE_ID);
+    assert_eq!(sut.state.token.target, EMAIL);
+    assert!(sut.state.token.expires_at > Utc::now().naive_utc() + Duration::minutes(59));
+  }
+}
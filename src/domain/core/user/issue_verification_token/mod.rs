@@ -0,0 +1,72 @@
+use crate::domain::entities::verification_token::VerificationPurpose;
+
+use super::*;
+
+pub mod generator;
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NoToken;
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct IssueVerificationToken<Req, Token, Save> {
+  request: Req,
+  token: Token,
+  saved: Save,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub purpose: VerificationPurpose,
+  pub target: &'a str,
+  pub expires_in_min: i64,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn issue_verification_token(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, IssueVerificationToken<Request<'a>, NoToken, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: IssueVerificationToken {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_issue_verification_token_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).issue_verification_token(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
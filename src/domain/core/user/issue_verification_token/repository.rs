@@ -0,0 +1,110 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::NewVerificationToken,
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, IssueVerificationToken<Request<'a>, NewVerificationToken, NotSaved>, R>
+{
+  pub async fn store(
+    self,
+  ) -> Result<User<'a, IssueVerificationToken<Request<'a>, NewVerificationToken, Saved>, R>, AppError>
+  {
+    self
+      .repository
+      .store_verification_token(&self.state.token)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: IssueVerificationToken {
+        request: self.state.request,
+        token: self.state.token,
+        saved: Saved(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, StoreVerificationToken,
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const EMAIL: &str = "user@email.com";
+
+  fn new_token() -> NewVerificationToken {
+    NewVerificationToken {
+      purpose: VerificationPurpose::Email,
+      profile_id: PROFILE_ID.to_string(),
+      target: EMAIL.to_string(),
+      ..Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn test_store_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_verification_token: Some(StoreVerificationToken {
+        calls: 1,
+        param_purpose: VerificationPurpose::Email,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_target: EMAIL.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueVerificationToken {
+        request: Request {
+          ..Default::default()
+        },
+        token: new_token(),
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.store().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+
+  #[tokio::test]
+  async fn test_store_failed() {
+    const DB_ERROR_MESSAGE: &str = "Some error in the database";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_verification_token: Some(StoreVerificationToken {
+        calls: 1,
+        param_purpose: VerificationPurpose::Email,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_target: EMAIL.to_string(),
+        fn_returning: |_| Err(AppError::database_error(DB_ERROR_MESSAGE)),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueVerificationToken {
+        request: Request {
+          ..Default::default()
+        },
+        token: new_token(),
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.store().await.err();
+
+    assert_eq!(sut, Some(AppError::database_error(DB_ERROR_MESSAGE)));
+  }
+}
@@ -0,0 +1,47 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::NewVerificationToken,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, IssueVerificationToken<Request<'a>, NewVerificationToken, Saved>, R>
+{
+  pub fn response(self) -> String {
+    self.state.token.token
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_plain_token() {
+    const SECRET: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueVerificationToken {
+        request: Request {
+          ..Default::default()
+        },
+        token: NewVerificationToken {
+          token: SECRET.to_string(),
+          ..Default::default()
+        },
+        saved: Saved(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(sut, SECRET);
+  }
+}
@@ -0,0 +1,94 @@
+use chrono::{Duration, Utc};
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::NewVerificationToken,
+  utilities::id_generator::IDGenerator,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, IssueWebAuthnChallenge<Request<'a>, ChallengeData, NoToken, NotSaved>, R>
+{
+  /// The challenge is a random base64url secret, which is also how the
+  /// browser reports it back in the client data.
+  pub fn generate(
+    self,
+    id_generator: &'a impl IDGenerator,
+  ) -> User<'a, IssueWebAuthnChallenge<Request<'a>, ChallengeData, NewVerificationToken, NotSaved>, R>
+  {
+    let token = NewVerificationToken {
+      token: id_generator.new_secret(),
+      purpose: self.state.request.purpose,
+      profile_id: self.state.db_data.user.id.clone(),
+      target: self.state.request.rp_id.to_string(),
+      expires_at: Utc::now().naive_utc() + Duration::minutes(self.state.request.expires_in_min),
+    };
+
+    User {
+      repository: self.repository,
+      state: IssueWebAuthnChallenge {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        token,
+        saved: self.state.saved,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    utilities::id_generator::MockIDGenerator,
+  };
+
+  use super::*;
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const RP_ID: &str = "localhost";
+  const SECRET: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+
+  #[test]
+  fn test_generate_challenge() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueWebAuthnChallenge {
+        request: Request {
+          purpose: VerificationPurpose::WebAuthnSignIn,
+          rp_id: RP_ID,
+          expires_in_min: 5,
+          ..Default::default()
+        },
+        db_data: ChallengeData {
+          user: UserData {
+            id: PROFILE_ID.to_string(),
+            ..Default::default()
+          },
+          credentials: vec![],
+        },
+        ..Default::default()
+      },
+    };
+
+    let mut mock_id_generator = MockIDGenerator::new();
+    mock_id_generator
+      .expect_new_secret()
+      .times(1)
+      .returning(|| SECRET.to_owned());
+
+    let sut = user.generate(&mock_id_generator);
+
+    assert_eq!(sut.state.token.token, SECRET);
+    assert_eq!(sut.state.token.purpose, VerificationPurpose::WebAuthnSignIn);
+    assert_eq!(sut.state.token.profile_id, PROFILE_ID);
+    assert_eq!(sut.state.token.target, RP_ID);
+    assert!(sut.state.token.expires_at > Utc::now().naive_utc() + Duration::minutes(4));
+  }
+}
@@ -0,0 +1,88 @@
+use crate::domain::entities::{
+  user::{UserColumns, UserData},
+  verification_token::VerificationPurpose,
+  webauthn::WebAuthnCredentialData,
+};
+
+use super::*;
+
+pub mod generator;
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default, Debug, PartialEq)]
+pub struct ChallengeData {
+  pub user: UserData,
+  pub credentials: Vec<WebAuthnCredentialData>,
+}
+
+#[derive(Default)]
+pub struct NoToken;
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct IssueWebAuthnChallenge<Req, Db, Token, Save> {
+  request: Req,
+  db_data: Db,
+  token: Token,
+  saved: Save,
+}
+
+/// `purpose` is either `WebAuthnRegistration` or `WebAuthnSignIn`, the
+/// challenge being stored as a single use verification token of the user.
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub column: UserColumns<'a>,
+  pub purpose: VerificationPurpose,
+  pub rp_id: &'a str,
+  pub expires_in_min: i64,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn issue_webauthn_challenge(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, IssueWebAuthnChallenge<Request<'a>, NoDbData, NoToken, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: IssueWebAuthnChallenge {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_issue_webauthn_challenge_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).issue_webauthn_challenge(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
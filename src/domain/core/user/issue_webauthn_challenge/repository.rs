@@ -0,0 +1,230 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::NewVerificationToken,
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, IssueWebAuthnChallenge<Request<'a>, NoDbData, NoToken, NotSaved>, R>
+{
+  /// A sign-in challenge is only issued to users with a passkey.
+  pub async fn get_user(
+    self,
+  ) -> Result<
+    User<'a, IssueWebAuthnChallenge<Request<'a>, ChallengeData, NoToken, NotSaved>, R>,
+    AppError,
+  > {
+    let user = self
+      .repository
+      .find_user_by(&self.state.request.column)
+      .await?;
+
+    let credentials = self.repository.find_webauthn_credentials(&user.id).await?;
+
+    if self.state.request.purpose == VerificationPurpose::WebAuthnSignIn && credentials.is_empty() {
+      return Err(AppError::invalid_argument("no passkey registered"));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: IssueWebAuthnChallenge {
+        request: self.state.request,
+        db_data: ChallengeData { user, credentials },
+        token: self.state.token,
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+impl<'a, R: UserRepository>
+  User<'a, IssueWebAuthnChallenge<Request<'a>, ChallengeData, NewVerificationToken, NotSaved>, R>
+{
+  pub async fn store(
+    self,
+  ) -> Result<
+    User<'a, IssueWebAuthnChallenge<Request<'a>, ChallengeData, NewVerificationToken, Saved>, R>,
+    AppError,
+  > {
+    self
+      .repository
+      .store_verification_token(&self.state.token)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: IssueWebAuthnChallenge {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        token: self.state.token,
+        saved: Saved(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{
+      build_mock_user_repository, Expectations, FindUserBy, FindWebAuthnCredentials,
+      StoreVerificationToken,
+    },
+    entities::webauthn::WebAuthnCredentialData,
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const USERNAME: &str = "john_doe";
+  const RP_ID: &str = "localhost";
+  const CREDENTIAL_ID: &str = "BwcHBwcHBwcHBwcHBwcHBw";
+
+  fn find_user_by() -> FindUserBy<'static> {
+    FindUserBy {
+      calls: 1,
+      param_column_with: UserColumns::Username(USERNAME),
+      fn_returning: |_| {
+        Ok(UserData {
+          id: PROFILE_ID.to_string(),
+          username: USERNAME.to_string(),
+          ..Default::default()
+        })
+      },
+    }
+  }
+
+  fn mock_request(purpose: VerificationPurpose) -> Request<'static> {
+    Request {
+      column: UserColumns::Username(USERNAME),
+      purpose,
+      rp_id: RP_ID,
+      expires_in_min: 5,
+    }
+  }
+
+  #[tokio::test]
+  async fn test_get_user_with_passkeys() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_user_by: Some(find_user_by()),
+      find_webauthn_credentials: Some(FindWebAuthnCredentials {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| {
+          Ok(vec![WebAuthnCredentialData {
+            id: 1,
+            credential_id: CREDENTIAL_ID.to_string(),
+            profile_id: PROFILE_ID.to_string(),
+            ..Default::default()
+          }])
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueWebAuthnChallenge {
+        request: mock_request(VerificationPurpose::WebAuthnSignIn),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_user().await.unwrap();
+
+    assert_eq!(sut.state.db_data.user.id, PROFILE_ID);
+    assert_eq!(
+      sut.state.db_data.credentials[0].credential_id,
+      CREDENTIAL_ID
+    );
+  }
+
+  #[tokio::test]
+  async fn test_get_user_to_register_first_passkey() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_user_by: Some(find_user_by()),
+      find_webauthn_credentials: Some(FindWebAuthnCredentials {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| Ok(vec![]),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueWebAuthnChallenge {
+        request: mock_request(VerificationPurpose::WebAuthnRegistration),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_user().await.unwrap();
+
+    assert!(sut.state.db_data.credentials.is_empty());
+  }
+
+  #[tokio::test]
+  async fn test_get_user_to_sign_in_without_passkeys() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_user_by: Some(find_user_by()),
+      find_webauthn_credentials: Some(FindWebAuthnCredentials {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| Ok(vec![]),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueWebAuthnChallenge {
+        request: mock_request(VerificationPurpose::WebAuthnSignIn),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_user().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("no passkey registered"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_store_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_verification_token: Some(StoreVerificationToken {
+        calls: 1,
+        param_purpose: VerificationPurpose::WebAuthnRegistration,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_target: RP_ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueWebAuthnChallenge {
+        request: mock_request(VerificationPurpose::WebAuthnRegistration),
+        db_data: ChallengeData {
+          ..Default::default()
+        },
+        token: NewVerificationToken {
+          purpose: VerificationPurpose::WebAuthnRegistration,
+          profile_id: PROFILE_ID.to_string(),
+          target: RP_ID.to_string(),
+          ..Default::default()
+        },
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.store().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+}
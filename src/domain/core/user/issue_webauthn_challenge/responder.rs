@@ -0,0 +1,84 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::{verification_token::NewVerificationToken, webauthn::WebAuthnChallenge},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, IssueWebAuthnChallenge<Request<'a>, ChallengeData, NewVerificationToken, Saved>, R>
+{
+  pub fn response(self) -> WebAuthnChallenge {
+    let ChallengeData { user, credentials } = self.state.db_data;
+
+    WebAuthnChallenge {
+      challenge: self.state.token.token,
+      profile_id: user.id,
+      username: user.username,
+      name: user.name,
+      credential_ids: credentials
+        .into_iter()
+        .map(|credential| credential.credential_id)
+        .collect(),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    entities::webauthn::WebAuthnCredentialData,
+  };
+
+  #[test]
+  fn test_response_with_challenge_and_passkeys() {
+    const SECRET: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+    const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+    const CREDENTIAL_ID: &str = "BwcHBwcHBwcHBwcHBwcHBw";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: IssueWebAuthnChallenge {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: ChallengeData {
+          user: UserData {
+            id: PROFILE_ID.to_string(),
+            username: String::from("john_doe"),
+            name: String::from("john doe"),
+            ..Default::default()
+          },
+          credentials: vec![WebAuthnCredentialData {
+            credential_id: CREDENTIAL_ID.to_string(),
+            ..Default::default()
+          }],
+        },
+        token: NewVerificationToken {
+          token: SECRET.to_string(),
+          ..Default::default()
+        },
+        saved: Saved(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(
+      sut,
+      WebAuthnChallenge {
+        challenge: SECRET.to_string(),
+        profile_id: PROFILE_ID.to_string(),
+        username: String::from("john_doe"),
+        name: String::from("john doe"),
+        credential_ids: vec![CREDENTIAL_ID.to_string()],
+      }
+    );
+  }
+}
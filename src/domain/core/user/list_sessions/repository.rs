@@ -0,0 +1,92 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::session::Session,
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, ListSessions<Request<'a>, NoDbData>, R> {
+  pub async fn find_sessions(
+    self,
+  ) -> Result<User<'a, ListSessions<Request<'a>, Vec<Session>>, R>, AppError> {
+    let db_data = self
+      .repository
+      .find_sessions(self.state.request.profile_id)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: ListSessions {
+        request: self.state.request,
+        db_data,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, FindSessions,
+  };
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  #[tokio::test]
+  async fn test_find_sessions_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_sessions: Some(FindSessions {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        fn_returning: |_| {
+          Ok(vec![Session {
+            device_label: String::from("Firefox on Linux"),
+            ..Default::default()
+          }])
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ListSessions {
+        request: Request { profile_id: UUID },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.find_sessions().await.unwrap();
+
+    assert_eq!(sut.state.db_data.len(), 1);
+    assert_eq!(sut.state.db_data[0].device_label, "Firefox on Linux");
+  }
+
+  #[tokio::test]
+  async fn test_fail_find_sessions() {
+    const DB_ERROR_MESSAGE: &str = "Some error in the database";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_sessions: Some(FindSessions {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        fn_returning: |_| Err(AppError::database_error(DB_ERROR_MESSAGE)),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ListSessions {
+        request: Request { profile_id: UUID },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.find_sessions().await.err();
+
+    assert_eq!(sut, Some(AppError::database_error(DB_ERROR_MESSAGE)));
+  }
+}
@@ -0,0 +1,44 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::session::Session,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, ListSessions<Request<'a>, Vec<Session>>, R> {
+  pub fn response(self) -> Vec<Session> {
+    self.state.db_data
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_right_data() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let session = Session {
+      id: String::from("1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed"),
+      device_label: String::from("Firefox on Linux"),
+      ..Default::default()
+    };
+    let user = User {
+      repository: &mock_repository,
+      state: ListSessions {
+        request: Request { profile_id: UUID },
+        db_data: vec![session.clone()],
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(sut, vec![session]);
+  }
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct ListSignIns<Req, Db> {
+  request: Req,
+  db_data: Db,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub page: i64,
+  pub per_page: i64,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn list_sign_ins(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, ListSignIns<Request<'a>, NoDbData>, R> {
+    User {
+      repository: self.repository,
+      state: ListSignIns {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_list_sign_ins_build() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).list_sign_ins(Request {
+      profile_id: UUID,
+      page: 2,
+      per_page: 10,
+    });
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        profile_id: UUID,
+        page: 2,
+        per_page: 10,
+      }
+    );
+  }
+}
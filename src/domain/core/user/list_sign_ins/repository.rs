@@ -0,0 +1,113 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::sign_in_event::SignInEventPage,
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, ListSignIns<Request<'a>, NoDbData>, R> {
+  pub async fn find_sign_ins(
+    self,
+  ) -> Result<User<'a, ListSignIns<Request<'a>, SignInEventPage>, R>, AppError> {
+    let request = &self.state.request;
+
+    let db_data = self
+      .repository
+      .find_sign_in_events(request.profile_id, request.page, request.per_page)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: ListSignIns {
+        request: self.state.request,
+        db_data,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations, FindSignInEvents},
+    entities::sign_in_event::SignInEvent,
+  };
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  #[tokio::test]
+  async fn test_find_sign_ins_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_sign_in_events: Some(FindSignInEvents {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        param_page: 2,
+        param_per_page: 10,
+        fn_returning: |_, page, per_page| {
+          Ok(SignInEventPage {
+            events: vec![SignInEvent {
+              succeeded: true,
+              ..Default::default()
+            }],
+            page,
+            per_page,
+            total: 11,
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ListSignIns {
+        request: Request {
+          profile_id: UUID,
+          page: 2,
+          per_page: 10,
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.find_sign_ins().await.unwrap();
+
+    assert_eq!(sut.state.db_data.events.len(), 1);
+    assert_eq!(sut.state.db_data.page, 2);
+    assert_eq!(sut.state.db_data.total, 11);
+  }
+
+  #[tokio::test]
+  async fn test_fail_find_sign_ins() {
+    const DB_ERROR_MESSAGE: &str = "Some error in the database";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_sign_in_events: Some(FindSignInEvents {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        param_page: 1,
+        param_per_page: 20,
+        fn_returning: |_, _, _| Err(AppError::database_error(DB_ERROR_MESSAGE)),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ListSignIns {
+        request: Request {
+          profile_id: UUID,
+          page: 1,
+          per_page: 20,
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.find_sign_ins().await.err();
+
+    assert_eq!(sut, Some(AppError::database_error(DB_ERROR_MESSAGE)));
+  }
+}
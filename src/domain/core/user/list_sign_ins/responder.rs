@@ -0,0 +1,55 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::sign_in_event::SignInEventPage,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, ListSignIns<Request<'a>, SignInEventPage>, R> {
+  pub fn response(self) -> SignInEventPage {
+    self.state.db_data
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_right_data() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ListSignIns {
+        request: Request {
+          profile_id: UUID,
+          page: 1,
+          per_page: 20,
+        },
+        db_data: SignInEventPage {
+          page: 1,
+          per_page: 20,
+          total: 0,
+          ..Default::default()
+        },
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(
+      sut,
+      SignInEventPage {
+        page: 1,
+        per_page: 20,
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -0,0 +1,74 @@
+use crate::domain::entities::user::UserFilter;
+
+use super::*;
+
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct ListUsers<Req, Db> {
+  request: Req,
+  db_data: Db,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request {
+  pub filter: UserFilter,
+  pub page: i64,
+  pub per_page: i64,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn list_users(self, request: Request) -> User<'a, ListUsers<Request, NoDbData>, R> {
+    User {
+      repository: self.repository,
+      state: ListUsers {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    entities::user::UserSort,
+  };
+
+  use super::*;
+
+  #[test]
+  fn test_list_users_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).list_users(Request {
+      filter: UserFilter {
+        username: Some("john".to_owned()),
+        sort: UserSort::UsernameAsc,
+        ..Default::default()
+      },
+      page: 2,
+      per_page: 10,
+    });
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        filter: UserFilter {
+          username: Some("john".to_owned()),
+          sort: UserSort::UsernameAsc,
+          ..Default::default()
+        },
+        page: 2,
+        per_page: 10,
+      }
+    );
+  }
+}
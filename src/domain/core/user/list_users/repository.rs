@@ -0,0 +1,120 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::user::UserSummaryPage,
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, ListUsers<Request, NoDbData>, R> {
+  pub async fn find_users(
+    self,
+  ) -> Result<User<'a, ListUsers<Request, UserSummaryPage>, R>, AppError> {
+    let request = &self.state.request;
+
+    let db_data = self
+      .repository
+      .find_users(&request.filter, request.page, request.per_page)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: ListUsers {
+        request: self.state.request,
+        db_data,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations, FindUsers},
+    entities::user::{UserFilter, UserSummary},
+  };
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  fn filter() -> UserFilter {
+    UserFilter {
+      email: Some("company.com".to_owned()),
+      ..Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn test_find_users_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_users: Some(FindUsers {
+        calls: 1,
+        param_filter: filter(),
+        param_page: 2,
+        param_per_page: 10,
+        fn_returning: |_, page, per_page| {
+          Ok(UserSummaryPage {
+            users: vec![UserSummary {
+              id: UUID.to_owned(),
+              ..Default::default()
+            }],
+            page,
+            per_page,
+            total: 11,
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ListUsers {
+        request: Request {
+          filter: filter(),
+          page: 2,
+          per_page: 10,
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.find_users().await.unwrap();
+
+    assert_eq!(sut.state.db_data.users.len(), 1);
+    assert_eq!(sut.state.db_data.page, 2);
+    assert_eq!(sut.state.db_data.total, 11);
+  }
+
+  #[tokio::test]
+  async fn test_fail_find_users() {
+    const DB_ERROR_MESSAGE: &str = "Some error in the database";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_users: Some(FindUsers {
+        calls: 1,
+        param_filter: filter(),
+        param_page: 1,
+        param_per_page: 20,
+        fn_returning: |_, _, _| Err(AppError::database_error(DB_ERROR_MESSAGE)),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ListUsers {
+        request: Request {
+          filter: filter(),
+          page: 1,
+          per_page: 20,
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.find_users().await.err();
+
+    assert_eq!(sut, Some(AppError::database_error(DB_ERROR_MESSAGE)));
+  }
+}
@@ -0,0 +1,53 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::user::UserSummaryPage,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, ListUsers<Request, UserSummaryPage>, R> {
+  pub fn response(self) -> UserSummaryPage {
+    self.state.db_data
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_right_data() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: ListUsers {
+        request: Request {
+          page: 1,
+          per_page: 20,
+          ..Default::default()
+        },
+        db_data: UserSummaryPage {
+          page: 1,
+          per_page: 20,
+          total: 0,
+          ..Default::default()
+        },
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(
+      sut,
+      UserSummaryPage {
+        page: 1,
+        per_page: 20,
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -1 +1,3 @@
+pub mod pkce;
 pub mod repository;
+pub mod webauthn;
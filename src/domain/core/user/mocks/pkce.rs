@@ -0,0 +1,23 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+use crate::domain::utilities::primitives::MockPrimitives;
+
+// RFC 7636 appendix B.
+pub const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+pub const CODE_CHALLENGE: &str = "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM";
+
+/// Primitives answering SHA-256 of `CODE_VERIFIER` with the hash behind
+/// `CODE_CHALLENGE`, and of anything else with zeros.
+pub fn mock_sha256() -> MockPrimitives {
+  let mut mock_primitives = MockPrimitives::new();
+
+  mock_primitives.expect_sha256().returning(|data| {
+    if data == CODE_VERIFIER.as_bytes() {
+      URL_SAFE_NO_PAD.decode(CODE_CHALLENGE).unwrap()
+    } else {
+      vec![0; 32]
+    }
+  });
+
+  mock_primitives
+}
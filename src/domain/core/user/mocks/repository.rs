@@ -1,11 +1,28 @@
+use chrono::NaiveDateTime;
+
 use crate::domain::{
-  entities::user::{UserColumns, UserData},
+  entities::{
+    external_identity::{
+      ExternalClaims, ExternalIdentityData, ExternalSignInData, NewExternalIdentity,
+      NewExternalSignIn,
+    },
+    oauth::{AuthorizationCodeData, NewAuthorizationCode, NewOAuthClient, OAuthClientData},
+    refresh_token::{NewRefreshToken, RefreshTokenData},
+    role::Grants,
+    session::{NewSession, Session},
+    sign_in_event::{NewSignInEvent, SignInEventPage},
+    totp::TotpAuthenticatorData,
+    user::{FullProfile, UserColumns, UserData, UserFilter, UserSummaryPage},
+    verification_token::{NewVerificationToken, VerificationPurpose, VerificationTokenData},
+    webauthn::{NewWebAuthnCredential, WebAuthnCredentialData},
+  },
   error::AppError,
 };
 
 use super::super::{
   repository::MockUserRepository,
   sign_up::{self, encrypter::PasswordEncrypted},
+  update_profile,
 };
 
 pub struct FindUserBy<'a> {
@@ -28,6 +45,26 @@ impl<'a> Default for FindUserBy<'a> {
   }
 }
 
+pub struct FindFullProfileBy<'a> {
+  pub calls: usize,
+  pub param_column_with: UserColumns<'a>,
+  pub fn_returning: fn(&UserColumns<'_>) -> Result<FullProfile, AppError>,
+}
+
+impl<'a> Default for FindFullProfileBy<'a> {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_column_with: Default::default(),
+      fn_returning: |_| {
+        Ok(FullProfile {
+          ..Default::default()
+        })
+      },
+    }
+  }
+}
+
 pub struct Store<'a> {
   pub calls: usize,
   pub param_user_data: sign_up::Request<'a, String, PasswordEncrypted>,
@@ -65,70 +102,1980 @@ impl Default for UpdatePassword {
   }
 }
 
-#[derive(Default)]
-pub struct Expectations<'a> {
-  pub find_user_by: Option<FindUserBy<'a>>,
-  pub store: Option<Store<'a>>,
-  pub update_password: Option<UpdatePassword>,
+pub struct RehashPassword {
+  pub calls: usize,
+  pub param_password: String,
+  pub param_verified_password: String,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&str, &str, &str) -> Result<(), AppError>,
 }
 
-pub fn build_mock_user_repository(expectations: Expectations<'static>) -> MockUserRepository {
-  let mut repository = MockUserRepository::new();
+impl Default for RehashPassword {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_password: Default::default(),
+      param_verified_password: Default::default(),
+      param_profile_id: Default::default(),
+      fn_returning: |_, _, _| Ok(()),
+    }
+  }
+}
 
-  expectations.find_user_by.map(|value| {
-    let FindUserBy {
-      calls,
-      param_column_with,
-      fn_returning,
-    } = value;
+pub struct UpdateProfile<'a> {
+  pub calls: usize,
+  pub param_request: update_profile::Request<'a>,
+  pub fn_returning: for<'b> fn(&update_profile::Request<'b>) -> Result<(), AppError>,
+}
 
-    repository
-      .expect_find_user_by()
-      .withf(move |column| *column == param_column_with)
-      .times(calls)
-      .returning(fn_returning);
-  });
+impl<'a> Default for UpdateProfile<'a> {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_request: update_profile::Request {
+        ..Default::default()
+      },
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
 
-  expectations.store.map(|value| {
-    let Store {
-      calls,
-      param_user_data,
-      fn_returning,
-    } = value;
+pub struct RegisterSignIn {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&str) -> Result<(), AppError>,
+}
 
-    repository
-      .expect_store()
-      .times(calls)
-      .withf(move |user_data| {
-        if *user_data != param_user_data {
-          return false;
-        }
+impl Default for RegisterSignIn {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
 
-        true
-      })
-      .returning(fn_returning)
-  });
+pub struct RegisterFailedSignIn {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_max_failed_attempts: i32,
+  pub fn_returning: fn(&str, i32) -> Result<bool, AppError>,
+}
 
-  expectations.update_password.map(|value| {
-    let UpdatePassword {
-      calls,
-      param_password,
-      param_profile_id,
-      fn_returning,
-    } = value;
+impl Default for RegisterFailedSignIn {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_max_failed_attempts: Default::default(),
+      fn_returning: |_, _| Ok(false),
+    }
+  }
+}
 
-    repository
-      .expect_update_password()
-      .times(calls)
-      .withf(move |password, profile_id| {
-        if password.to_string() == param_password && profile_id.to_string() == param_profile_id {
-          true
-        } else {
-          false
-        }
-      })
-      .returning(fn_returning)
-  });
+pub struct UnlockUser {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&str) -> Result<(), AppError>,
+}
+
+impl Default for UnlockUser {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct UpdateAccountDisabled {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_disabled: bool,
+  pub fn_returning: fn(&str, bool) -> Result<(), AppError>,
+}
+
+impl Default for UpdateAccountDisabled {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_disabled: Default::default(),
+      fn_returning: |_, _| Ok(()),
+    }
+  }
+}
+
+pub struct StoreSignInEvent {
+  pub calls: usize,
+  pub param_event: NewSignInEvent,
+  pub fn_returning: fn(&NewSignInEvent) -> Result<(), AppError>,
+}
+
+impl Default for StoreSignInEvent {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_event: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct FindSignInEvents {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_page: i64,
+  pub param_per_page: i64,
+  pub fn_returning: fn(&str, i64, i64) -> Result<SignInEventPage, AppError>,
+}
+
+impl Default for FindSignInEvents {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_page: 1,
+      param_per_page: 20,
+      fn_returning: |_, page, per_page| {
+        Ok(SignInEventPage {
+          page,
+          per_page,
+          ..Default::default()
+        })
+      },
+    }
+  }
+}
+
+pub struct StoreRefreshToken {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&NewRefreshToken) -> Result<(), AppError>,
+}
+
+impl Default for StoreRefreshToken {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct FindRefreshToken {
+  pub calls: usize,
+  pub param_token: String,
+  pub fn_returning: fn(&str) -> Result<RefreshTokenData, AppError>,
+}
+
+impl Default for FindRefreshToken {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_token: Default::default(),
+      fn_returning: |_| {
+        Ok(RefreshTokenData {
+          ..Default::default()
+        })
+      },
+    }
+  }
+}
+
+pub struct UseRefreshToken {
+  pub calls: usize,
+  pub param_id: i32,
+  pub fn_returning: fn(i32) -> Result<bool, AppError>,
+}
+
+impl Default for UseRefreshToken {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_id: Default::default(),
+      fn_returning: |_| Ok(true),
+    }
+  }
+}
+
+pub struct RevokeRefreshTokenFamily {
+  pub calls: usize,
+  pub param_family_id: String,
+  pub fn_returning: fn(&str) -> Result<(), AppError>,
+}
+
+impl Default for RevokeRefreshTokenFamily {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_family_id: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct StoreSession {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&NewSession) -> Result<(), AppError>,
+}
+
+impl Default for StoreSession {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct FindSessions {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&str) -> Result<Vec<Session>, AppError>,
+}
+
+impl Default for FindSessions {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(vec![]),
+    }
+  }
+}
+
+pub struct RevokeSession {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_session_id: String,
+  pub fn_returning: fn(&str, &str) -> Result<(), AppError>,
+}
+
+impl Default for RevokeSession {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_session_id: Default::default(),
+      fn_returning: |_, _| Ok(()),
+    }
+  }
+}
+
+pub struct RevokeOtherSessions {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_keep_session_id: Option<String>,
+  pub fn_returning: fn(&str, Option<&str>) -> Result<Vec<String>, AppError>,
+}
+
+impl Default for RevokeOtherSessions {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_keep_session_id: Default::default(),
+      fn_returning: |_, _| Ok(vec![]),
+    }
+  }
+}
+
+pub struct RevokeRefreshTokens {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&str) -> Result<(), AppError>,
+}
+
+impl Default for RevokeRefreshTokens {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct RequestDeletion {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&str) -> Result<(), AppError>,
+}
+
+impl Default for RequestDeletion {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct CancelDeletion {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&str) -> Result<(), AppError>,
+}
+
+impl Default for CancelDeletion {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct PurgeRequestedDeletions {
+  pub calls: usize,
+  pub param_grace_period_days: i32,
+  pub fn_returning: fn(i32) -> Result<i32, AppError>,
+}
+
+impl Default for PurgeRequestedDeletions {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_grace_period_days: Default::default(),
+      fn_returning: |_| Ok(0),
+    }
+  }
+}
+
+pub struct StoreVerificationToken {
+  pub calls: usize,
+  pub param_purpose: VerificationPurpose,
+  pub param_profile_id: String,
+  pub param_target: String,
+  pub fn_returning: fn(&NewVerificationToken) -> Result<(), AppError>,
+}
+
+impl Default for StoreVerificationToken {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_purpose: Default::default(),
+      param_profile_id: Default::default(),
+      param_target: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct FindVerificationToken {
+  pub calls: usize,
+  pub param_token: String,
+  pub param_purpose: VerificationPurpose,
+  pub fn_returning: fn(&str, VerificationPurpose) -> Result<VerificationTokenData, AppError>,
+}
+
+impl Default for FindVerificationToken {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_token: Default::default(),
+      param_purpose: Default::default(),
+      fn_returning: |_, _| {
+        Ok(VerificationTokenData {
+          ..Default::default()
+        })
+      },
+    }
+  }
+}
+
+pub struct UseVerificationToken {
+  pub calls: usize,
+  pub param_id: i32,
+  pub fn_returning: fn(i32) -> Result<bool, AppError>,
+}
+
+impl Default for UseVerificationToken {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_id: Default::default(),
+      fn_returning: |_| Ok(true),
+    }
+  }
+}
+
+pub struct CheckEmail {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_address: String,
+  pub fn_returning: fn(&str, &str) -> Result<(), AppError>,
+}
+
+impl Default for CheckEmail {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_address: Default::default(),
+      fn_returning: |_, _| Ok(()),
+    }
+  }
+}
+
+pub struct CountVerificationTokens {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_purpose: VerificationPurpose,
+  pub fn_returning: fn(&str, VerificationPurpose, NaiveDateTime) -> Result<i64, AppError>,
+}
+
+impl Default for CountVerificationTokens {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_purpose: Default::default(),
+      fn_returning: |_, _, _| Ok(0),
+    }
+  }
+}
+
+pub struct FindLatestVerificationToken {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_purpose: VerificationPurpose,
+  pub param_target: String,
+  pub fn_returning: fn(&str, VerificationPurpose, &str) -> Result<VerificationTokenData, AppError>,
+}
+
+impl Default for FindLatestVerificationToken {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_purpose: Default::default(),
+      param_target: Default::default(),
+      fn_returning: |_, _, _| {
+        Ok(VerificationTokenData {
+          ..Default::default()
+        })
+      },
+    }
+  }
+}
+
+pub struct AttemptVerificationToken {
+  pub calls: usize,
+  pub param_id: i32,
+  pub param_token: String,
+  pub param_max_attempts: i32,
+  pub fn_returning: fn(i32, &str, i32) -> Result<Option<bool>, AppError>,
+}
+
+impl Default for AttemptVerificationToken {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_id: Default::default(),
+      param_token: Default::default(),
+      param_max_attempts: Default::default(),
+      fn_returning: |_, _, _| Ok(Some(true)),
+    }
+  }
+}
+
+pub struct CheckTelephone {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_number: String,
+  pub fn_returning: fn(&str, &str) -> Result<(), AppError>,
+}
+
+impl Default for CheckTelephone {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_number: Default::default(),
+      fn_returning: |_, _| Ok(()),
+    }
+  }
+}
+
+pub struct FindTotpAuthenticator {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&str) -> Result<TotpAuthenticatorData, AppError>,
+}
+
+impl Default for FindTotpAuthenticator {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| {
+        Ok(TotpAuthenticatorData {
+          ..Default::default()
+        })
+      },
+    }
+  }
+}
+
+pub struct StoreTotpAuthenticator {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_secret: String,
+  pub fn_returning: fn(&str, &str) -> Result<(), AppError>,
+}
+
+impl Default for StoreTotpAuthenticator {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_secret: Default::default(),
+      fn_returning: |_, _| Ok(()),
+    }
+  }
+}
+
+pub struct ConfirmTotpAuthenticator {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_step: i64,
+  pub param_recovery_codes: Vec<String>,
+  pub fn_returning: fn(&str, i64, &[String]) -> Result<(), AppError>,
+}
+
+impl Default for ConfirmTotpAuthenticator {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_step: Default::default(),
+      param_recovery_codes: Default::default(),
+      fn_returning: |_, _, _| Ok(()),
+    }
+  }
+}
+
+pub struct UseTotpStep {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_step: i64,
+  pub fn_returning: fn(&str, i64) -> Result<bool, AppError>,
+}
+
+impl Default for UseTotpStep {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_step: Default::default(),
+      fn_returning: |_, _| Ok(true),
+    }
+  }
+}
+
+pub struct UseRecoveryCode {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_code: String,
+  pub fn_returning: fn(&str, &str) -> Result<bool, AppError>,
+}
+
+impl Default for UseRecoveryCode {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_code: Default::default(),
+      fn_returning: |_, _| Ok(true),
+    }
+  }
+}
+
+pub struct StoreWebAuthnCredential {
+  pub calls: usize,
+  pub param_credential_id: String,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&NewWebAuthnCredential) -> Result<(), AppError>,
+}
+
+impl Default for StoreWebAuthnCredential {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_credential_id: Default::default(),
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct FindWebAuthnCredential {
+  pub calls: usize,
+  pub param_credential_id: String,
+  pub fn_returning: fn(&str) -> Result<WebAuthnCredentialData, AppError>,
+}
+
+impl Default for FindWebAuthnCredential {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_credential_id: Default::default(),
+      fn_returning: |_| Ok(Default::default()),
+    }
+  }
+}
+
+pub struct FindWebAuthnCredentials {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&str) -> Result<Vec<WebAuthnCredentialData>, AppError>,
+}
+
+impl Default for FindWebAuthnCredentials {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(vec![]),
+    }
+  }
+}
+
+pub struct UpdateWebAuthnSignCount {
+  pub calls: usize,
+  pub param_id: i32,
+  pub param_sign_count: i64,
+  pub fn_returning: fn(i32, i64) -> Result<(), AppError>,
+}
+
+impl Default for UpdateWebAuthnSignCount {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_id: Default::default(),
+      param_sign_count: Default::default(),
+      fn_returning: |_, _| Ok(()),
+    }
+  }
+}
+
+pub struct StoreOAuthClient {
+  pub calls: usize,
+  pub param_client_id: String,
+  pub param_secret_hash: Option<String>,
+  pub fn_returning: fn(&NewOAuthClient) -> Result<(), AppError>,
+}
+
+impl Default for StoreOAuthClient {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_client_id: Default::default(),
+      param_secret_hash: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct FindOAuthClient {
+  pub calls: usize,
+  pub param_client_id: String,
+  pub fn_returning: fn(&str) -> Result<OAuthClientData, AppError>,
+}
+
+impl Default for FindOAuthClient {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_client_id: Default::default(),
+      fn_returning: |_| Ok(Default::default()),
+    }
+  }
+}
+
+pub struct StoreAuthorizationCode {
+  pub calls: usize,
+  pub param_code: String,
+  pub param_client_id: String,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&NewAuthorizationCode) -> Result<(), AppError>,
+}
+
+impl Default for StoreAuthorizationCode {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_code: Default::default(),
+      param_client_id: Default::default(),
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct FindAuthorizationCode {
+  pub calls: usize,
+  pub param_code: String,
+  pub fn_returning: fn(&str) -> Result<AuthorizationCodeData, AppError>,
+}
+
+impl Default for FindAuthorizationCode {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_code: Default::default(),
+      fn_returning: |_| Ok(Default::default()),
+    }
+  }
+}
+
+pub struct UseAuthorizationCode {
+  pub calls: usize,
+  pub param_id: i32,
+  pub fn_returning: fn(i32) -> Result<bool, AppError>,
+}
+
+impl Default for UseAuthorizationCode {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_id: Default::default(),
+      fn_returning: |_| Ok(true),
+    }
+  }
+}
+
+pub struct StoreExternalSignIn {
+  pub calls: usize,
+  pub param_state: String,
+  pub param_provider: String,
+  pub param_code_verifier: String,
+  pub param_nonce: String,
+  pub fn_returning: fn(&NewExternalSignIn) -> Result<(), AppError>,
+}
+
+impl Default for StoreExternalSignIn {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_state: Default::default(),
+      param_provider: Default::default(),
+      param_code_verifier: Default::default(),
+      param_nonce: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct FindExternalSignIn {
+  pub calls: usize,
+  pub param_state: String,
+  pub fn_returning: fn(&str) -> Result<ExternalSignInData, AppError>,
+}
+
+impl Default for FindExternalSignIn {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_state: Default::default(),
+      fn_returning: |_| Ok(Default::default()),
+    }
+  }
+}
+
+pub struct UseExternalSignIn {
+  pub calls: usize,
+  pub param_id: i32,
+  pub fn_returning: fn(i32) -> Result<bool, AppError>,
+}
+
+impl Default for UseExternalSignIn {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_id: Default::default(),
+      fn_returning: |_| Ok(true),
+    }
+  }
+}
+
+pub struct HoldExternalSignUp {
+  pub calls: usize,
+  pub param_id: i32,
+  pub param_claims: ExternalClaims,
+  pub fn_returning: fn(i32, &ExternalClaims, NaiveDateTime) -> Result<(), AppError>,
+}
+
+impl Default for HoldExternalSignUp {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_id: Default::default(),
+      param_claims: Default::default(),
+      fn_returning: |_, _, _| Ok(()),
+    }
+  }
+}
+
+pub struct CompleteExternalSignUp {
+  pub calls: usize,
+  pub param_id: i32,
+  pub fn_returning: fn(i32) -> Result<bool, AppError>,
+}
+
+impl Default for CompleteExternalSignUp {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_id: Default::default(),
+      fn_returning: |_| Ok(true),
+    }
+  }
+}
+
+pub struct FindExternalIdentity {
+  pub calls: usize,
+  pub param_provider: String,
+  pub param_subject: String,
+  pub fn_returning: fn(&str, &str) -> Result<ExternalIdentityData, AppError>,
+}
+
+impl Default for FindExternalIdentity {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_provider: Default::default(),
+      param_subject: Default::default(),
+      fn_returning: |_, _| Ok(Default::default()),
+    }
+  }
+}
+
+pub struct StoreExternalIdentity {
+  pub calls: usize,
+  pub param_provider: String,
+  pub param_subject: String,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&NewExternalIdentity) -> Result<(), AppError>,
+}
+
+impl Default for StoreExternalIdentity {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_provider: Default::default(),
+      param_subject: Default::default(),
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct FindGrants {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&str) -> Result<Grants, AppError>,
+}
+
+impl Default for FindGrants {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(Default::default()),
+    }
+  }
+}
+
+pub struct FindTokenVersion {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&str) -> Result<i32, AppError>,
+}
+
+impl Default for FindTokenVersion {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(0),
+    }
+  }
+}
+
+pub struct GrantRole {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_role: String,
+  pub fn_returning: fn(&str, &str) -> Result<bool, AppError>,
+}
+
+impl Default for GrantRole {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_role: Default::default(),
+      fn_returning: |_, _| Ok(true),
+    }
+  }
+}
+
+pub struct RevokeRole {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub param_role: String,
+  pub fn_returning: fn(&str, &str) -> Result<bool, AppError>,
+}
+
+impl Default for RevokeRole {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      param_role: Default::default(),
+      fn_returning: |_, _| Ok(true),
+    }
+  }
+}
+
+pub struct RequirePasswordReset {
+  pub calls: usize,
+  pub param_profile_id: String,
+  pub fn_returning: fn(&str) -> Result<(), AppError>,
+}
+
+impl Default for RequirePasswordReset {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_profile_id: Default::default(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+}
+
+pub struct FindUsers {
+  pub calls: usize,
+  pub param_filter: UserFilter,
+  pub param_page: i64,
+  pub param_per_page: i64,
+  pub fn_returning: fn(&UserFilter, i64, i64) -> Result<UserSummaryPage, AppError>,
+}
+
+impl Default for FindUsers {
+  fn default() -> Self {
+    Self {
+      calls: 1,
+      param_filter: Default::default(),
+      param_page: 1,
+      param_per_page: 20,
+      fn_returning: |_, page, per_page| {
+        Ok(UserSummaryPage {
+          page,
+          per_page,
+          ..Default::default()
+        })
+      },
+    }
+  }
+}
+
+#[derive(Default)]
+pub struct Expectations<'a> {
+  pub find_user_by: Option<FindUserBy<'a>>,
+  pub find_full_profile_by: Option<FindFullProfileBy<'a>>,
+  pub store: Option<Store<'a>>,
+  pub update_password: Option<UpdatePassword>,
+  pub rehash_password: Option<RehashPassword>,
+  pub update_profile: Option<UpdateProfile<'a>>,
+  pub register_sign_in: Option<RegisterSignIn>,
+  pub register_failed_sign_in: Option<RegisterFailedSignIn>,
+  pub unlock_user: Option<UnlockUser>,
+  pub update_account_disabled: Option<UpdateAccountDisabled>,
+  pub store_sign_in_event: Option<StoreSignInEvent>,
+  pub find_sign_in_events: Option<FindSignInEvents>,
+  pub store_refresh_token: Option<StoreRefreshToken>,
+  pub find_refresh_token: Option<FindRefreshToken>,
+  pub use_refresh_token: Option<UseRefreshToken>,
+  pub revoke_refresh_token_family: Option<RevokeRefreshTokenFamily>,
+  pub revoke_refresh_tokens: Option<RevokeRefreshTokens>,
+  pub store_session: Option<StoreSession>,
+  pub find_sessions: Option<FindSessions>,
+  pub revoke_session: Option<RevokeSession>,
+  pub revoke_other_sessions: Option<RevokeOtherSessions>,
+  pub request_deletion: Option<RequestDeletion>,
+  pub cancel_deletion: Option<CancelDeletion>,
+  pub purge_requested_deletions: Option<PurgeRequestedDeletions>,
+  pub store_verification_token: Option<StoreVerificationToken>,
+  pub find_verification_token: Option<FindVerificationToken>,
+  pub use_verification_token: Option<UseVerificationToken>,
+  pub check_email: Option<CheckEmail>,
+  pub count_verification_tokens: Option<CountVerificationTokens>,
+  pub find_latest_verification_token: Option<FindLatestVerificationToken>,
+  pub attempt_verification_token: Option<AttemptVerificationToken>,
+  pub check_telephone: Option<CheckTelephone>,
+  pub find_totp_authenticator: Option<FindTotpAuthenticator>,
+  pub store_totp_authenticator: Option<StoreTotpAuthenticator>,
+  pub confirm_totp_authenticator: Option<ConfirmTotpAuthenticator>,
+  pub use_totp_step: Option<UseTotpStep>,
+  pub use_recovery_code: Option<UseRecoveryCode>,
+  pub store_webauthn_credential: Option<StoreWebAuthnCredential>,
+  pub find_webauthn_credential: Option<FindWebAuthnCredential>,
+  pub find_webauthn_credentials: Option<FindWebAuthnCredentials>,
+  pub update_webauthn_sign_count: Option<UpdateWebAuthnSignCount>,
+  pub store_oauth_client: Option<StoreOAuthClient>,
+  pub find_oauth_client: Option<FindOAuthClient>,
+  pub store_authorization_code: Option<StoreAuthorizationCode>,
+  pub find_authorization_code: Option<FindAuthorizationCode>,
+  pub use_authorization_code: Option<UseAuthorizationCode>,
+  pub store_external_sign_in: Option<StoreExternalSignIn>,
+  pub find_external_sign_in: Option<FindExternalSignIn>,
+  pub use_external_sign_in: Option<UseExternalSignIn>,
+  pub hold_external_sign_up: Option<HoldExternalSignUp>,
+  pub complete_external_sign_up: Option<CompleteExternalSignUp>,
+  pub find_external_identity: Option<FindExternalIdentity>,
+  pub store_external_identity: Option<StoreExternalIdentity>,
+  pub find_grants: Option<FindGrants>,
+  pub find_token_version: Option<FindTokenVersion>,
+  pub grant_role: Option<GrantRole>,
+  pub revoke_role: Option<RevokeRole>,
+  pub require_password_reset: Option<RequirePasswordReset>,
+  pub find_users: Option<FindUsers>,
+}
+
+pub fn build_mock_user_repository(expectations: Expectations<'static>) -> MockUserRepository {
+  let mut repository = MockUserRepository::new();
+
+  if let Some(value) = expectations.find_user_by {
+    let FindUserBy {
+      calls,
+      param_column_with,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_user_by()
+      .withf(move |column| *column == param_column_with)
+      .times(calls)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_full_profile_by {
+    let FindFullProfileBy {
+      calls,
+      param_column_with,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_full_profile_by()
+      .withf(move |column| *column == param_column_with)
+      .times(calls)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.store {
+    let Store {
+      calls,
+      param_user_data,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_store()
+      .times(calls)
+      .withf(move |user_data| {
+        if *user_data != param_user_data {
+          return false;
+        }
+
+        true
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.update_password {
+    let UpdatePassword {
+      calls,
+      param_password,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_update_password()
+      .times(calls)
+      .withf(move |password, profile_id| {
+        password == param_password && profile_id == param_profile_id
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.rehash_password {
+    let RehashPassword {
+      calls,
+      param_password,
+      param_verified_password,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_rehash_password()
+      .times(calls)
+      .withf(move |password, verified_password, profile_id| {
+        password == param_password
+          && verified_password == param_verified_password
+          && profile_id == param_profile_id
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.update_profile {
+    let UpdateProfile {
+      calls,
+      param_request,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_update_profile()
+      .times(calls)
+      .withf(move |request| *request == param_request)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.register_sign_in {
+    let RegisterSignIn {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_register_sign_in()
+      .times(calls)
+      .withf(move |profile_id| profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.register_failed_sign_in {
+    let RegisterFailedSignIn {
+      calls,
+      param_profile_id,
+      param_max_failed_attempts,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_register_failed_sign_in()
+      .times(calls)
+      .withf(move |profile_id, max_failed_attempts| {
+        profile_id == param_profile_id && *max_failed_attempts == param_max_failed_attempts
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.unlock_user {
+    let UnlockUser {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_unlock_user()
+      .times(calls)
+      .withf(move |profile_id| profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.update_account_disabled {
+    let UpdateAccountDisabled {
+      calls,
+      param_profile_id,
+      param_disabled,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_update_account_disabled()
+      .times(calls)
+      .withf(move |profile_id, disabled| {
+        profile_id == param_profile_id && *disabled == param_disabled
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.store_sign_in_event {
+    let StoreSignInEvent {
+      calls,
+      param_event,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_store_sign_in_event()
+      .times(calls)
+      .withf(move |event| *event == param_event)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_sign_in_events {
+    let FindSignInEvents {
+      calls,
+      param_profile_id,
+      param_page,
+      param_per_page,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_sign_in_events()
+      .times(calls)
+      .withf(move |profile_id, page, per_page| {
+        profile_id == param_profile_id && *page == param_page && *per_page == param_per_page
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.store_refresh_token {
+    let StoreRefreshToken {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_store_refresh_token()
+      .times(calls)
+      .withf(move |refresh_token| refresh_token.profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_refresh_token {
+    let FindRefreshToken {
+      calls,
+      param_token,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_refresh_token()
+      .times(calls)
+      .withf(move |token| token == param_token)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.use_refresh_token {
+    let UseRefreshToken {
+      calls,
+      param_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_use_refresh_token()
+      .times(calls)
+      .withf(move |id| *id == param_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.revoke_refresh_token_family {
+    let RevokeRefreshTokenFamily {
+      calls,
+      param_family_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_revoke_refresh_token_family()
+      .times(calls)
+      .withf(move |family_id| family_id == param_family_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.revoke_refresh_tokens {
+    let RevokeRefreshTokens {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_revoke_refresh_tokens()
+      .times(calls)
+      .withf(move |profile_id| profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.store_session {
+    let StoreSession {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_store_session()
+      .times(calls)
+      .withf(move |session| session.profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_sessions {
+    let FindSessions {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_sessions()
+      .times(calls)
+      .withf(move |profile_id| profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.revoke_session {
+    let RevokeSession {
+      calls,
+      param_profile_id,
+      param_session_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_revoke_session()
+      .times(calls)
+      .withf(move |profile_id, session_id| {
+        profile_id == param_profile_id && session_id == param_session_id
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.revoke_other_sessions {
+    let RevokeOtherSessions {
+      calls,
+      param_profile_id,
+      param_keep_session_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_revoke_other_sessions()
+      .times(calls)
+      .withf(move |profile_id, keep_session_id| {
+        profile_id == param_profile_id && *keep_session_id == param_keep_session_id.as_deref()
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.request_deletion {
+    let RequestDeletion {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_request_deletion()
+      .times(calls)
+      .withf(move |profile_id| profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.cancel_deletion {
+    let CancelDeletion {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_cancel_deletion()
+      .times(calls)
+      .withf(move |profile_id| profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.purge_requested_deletions {
+    let PurgeRequestedDeletions {
+      calls,
+      param_grace_period_days,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_purge_requested_deletions()
+      .times(calls)
+      .withf(move |grace_period_days| *grace_period_days == param_grace_period_days)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.store_verification_token {
+    let StoreVerificationToken {
+      calls,
+      param_purpose,
+      param_profile_id,
+      param_target,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_store_verification_token()
+      .times(calls)
+      .withf(move |token| {
+        token.purpose == param_purpose
+          && token.profile_id == param_profile_id
+          && token.target == param_target
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_verification_token {
+    let FindVerificationToken {
+      calls,
+      param_token,
+      param_purpose,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_verification_token()
+      .times(calls)
+      .withf(move |token, purpose| token == param_token && *purpose == param_purpose)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.use_verification_token {
+    let UseVerificationToken {
+      calls,
+      param_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_use_verification_token()
+      .times(calls)
+      .withf(move |id| *id == param_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.check_email {
+    let CheckEmail {
+      calls,
+      param_profile_id,
+      param_address,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_check_email()
+      .times(calls)
+      .withf(move |profile_id, address| profile_id == param_profile_id && address == param_address)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.count_verification_tokens {
+    let CountVerificationTokens {
+      calls,
+      param_profile_id,
+      param_purpose,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_count_verification_tokens()
+      .times(calls)
+      .withf(move |profile_id, purpose, _| {
+        profile_id == param_profile_id && *purpose == param_purpose
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_latest_verification_token {
+    let FindLatestVerificationToken {
+      calls,
+      param_profile_id,
+      param_purpose,
+      param_target,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_latest_verification_token()
+      .times(calls)
+      .withf(move |profile_id, purpose, target| {
+        profile_id == param_profile_id && *purpose == param_purpose && target == param_target
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.attempt_verification_token {
+    let AttemptVerificationToken {
+      calls,
+      param_id,
+      param_token,
+      param_max_attempts,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_attempt_verification_token()
+      .times(calls)
+      .withf(move |id, token, max_attempts| {
+        *id == param_id && token == param_token && *max_attempts == param_max_attempts
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.check_telephone {
+    let CheckTelephone {
+      calls,
+      param_profile_id,
+      param_number,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_check_telephone()
+      .times(calls)
+      .withf(move |profile_id, number| profile_id == param_profile_id && number == param_number)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_totp_authenticator {
+    let FindTotpAuthenticator {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_totp_authenticator()
+      .times(calls)
+      .withf(move |profile_id| profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.store_totp_authenticator {
+    let StoreTotpAuthenticator {
+      calls,
+      param_profile_id,
+      param_secret,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_store_totp_authenticator()
+      .times(calls)
+      .withf(move |profile_id, secret| profile_id == param_profile_id && secret == param_secret)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.confirm_totp_authenticator {
+    let ConfirmTotpAuthenticator {
+      calls,
+      param_profile_id,
+      param_step,
+      param_recovery_codes,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_confirm_totp_authenticator()
+      .times(calls)
+      .withf(move |profile_id, step, recovery_codes| {
+        profile_id == param_profile_id
+          && *step == param_step
+          && recovery_codes == param_recovery_codes
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.use_totp_step {
+    let UseTotpStep {
+      calls,
+      param_profile_id,
+      param_step,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_use_totp_step()
+      .times(calls)
+      .withf(move |profile_id, step| profile_id == param_profile_id && *step == param_step)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.use_recovery_code {
+    let UseRecoveryCode {
+      calls,
+      param_profile_id,
+      param_code,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_use_recovery_code()
+      .times(calls)
+      .withf(move |profile_id, code| profile_id == param_profile_id && code == param_code)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.store_webauthn_credential {
+    let StoreWebAuthnCredential {
+      calls,
+      param_credential_id,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_store_webauthn_credential()
+      .times(calls)
+      .withf(move |credential| {
+        credential.credential_id == param_credential_id && credential.profile_id == param_profile_id
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_webauthn_credential {
+    let FindWebAuthnCredential {
+      calls,
+      param_credential_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_webauthn_credential()
+      .times(calls)
+      .withf(move |credential_id| credential_id == param_credential_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_webauthn_credentials {
+    let FindWebAuthnCredentials {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_webauthn_credentials()
+      .times(calls)
+      .withf(move |profile_id| profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.update_webauthn_sign_count {
+    let UpdateWebAuthnSignCount {
+      calls,
+      param_id,
+      param_sign_count,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_update_webauthn_sign_count()
+      .times(calls)
+      .withf(move |id, sign_count| *id == param_id && *sign_count == param_sign_count)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.store_oauth_client {
+    let StoreOAuthClient {
+      calls,
+      param_client_id,
+      param_secret_hash,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_store_oauth_client()
+      .times(calls)
+      .withf(move |client| {
+        client.client_id == param_client_id && client.secret_hash == param_secret_hash
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_oauth_client {
+    let FindOAuthClient {
+      calls,
+      param_client_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_oauth_client()
+      .times(calls)
+      .withf(move |client_id| client_id == param_client_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.store_authorization_code {
+    let StoreAuthorizationCode {
+      calls,
+      param_code,
+      param_client_id,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_store_authorization_code()
+      .times(calls)
+      .withf(move |code| {
+        code.code == param_code
+          && code.client_id == param_client_id
+          && code.profile_id == param_profile_id
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_authorization_code {
+    let FindAuthorizationCode {
+      calls,
+      param_code,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_authorization_code()
+      .times(calls)
+      .withf(move |code| code == param_code)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.use_authorization_code {
+    let UseAuthorizationCode {
+      calls,
+      param_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_use_authorization_code()
+      .times(calls)
+      .withf(move |id| *id == param_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.store_external_sign_in {
+    let StoreExternalSignIn {
+      calls,
+      param_state,
+      param_provider,
+      param_code_verifier,
+      param_nonce,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_store_external_sign_in()
+      .times(calls)
+      .withf(move |sign_in| {
+        sign_in.state == param_state
+          && sign_in.provider == param_provider
+          && sign_in.code_verifier == param_code_verifier
+          && sign_in.nonce == param_nonce
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_external_sign_in {
+    let FindExternalSignIn {
+      calls,
+      param_state,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_external_sign_in()
+      .times(calls)
+      .withf(move |state| state == param_state)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.use_external_sign_in {
+    let UseExternalSignIn {
+      calls,
+      param_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_use_external_sign_in()
+      .times(calls)
+      .withf(move |id| *id == param_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.hold_external_sign_up {
+    let HoldExternalSignUp {
+      calls,
+      param_id,
+      param_claims,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_hold_external_sign_up()
+      .times(calls)
+      .withf(move |id, claims, _| *id == param_id && *claims == param_claims)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.complete_external_sign_up {
+    let CompleteExternalSignUp {
+      calls,
+      param_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_complete_external_sign_up()
+      .times(calls)
+      .withf(move |id| *id == param_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_external_identity {
+    let FindExternalIdentity {
+      calls,
+      param_provider,
+      param_subject,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_external_identity()
+      .times(calls)
+      .withf(move |provider, subject| provider == param_provider && subject == param_subject)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.store_external_identity {
+    let StoreExternalIdentity {
+      calls,
+      param_provider,
+      param_subject,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_store_external_identity()
+      .times(calls)
+      .withf(move |identity| {
+        identity.provider == param_provider
+          && identity.subject == param_subject
+          && identity.profile_id == param_profile_id
+      })
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_grants {
+    let FindGrants {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_grants()
+      .times(calls)
+      .withf(move |profile_id| profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_token_version {
+    let FindTokenVersion {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_token_version()
+      .times(calls)
+      .withf(move |profile_id| profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.grant_role {
+    let GrantRole {
+      calls,
+      param_profile_id,
+      param_role,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_grant_role()
+      .times(calls)
+      .withf(move |profile_id, role| profile_id == param_profile_id && role == param_role)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.revoke_role {
+    let RevokeRole {
+      calls,
+      param_profile_id,
+      param_role,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_revoke_role()
+      .times(calls)
+      .withf(move |profile_id, role| profile_id == param_profile_id && role == param_role)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.require_password_reset {
+    let RequirePasswordReset {
+      calls,
+      param_profile_id,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_require_password_reset()
+      .times(calls)
+      .withf(move |profile_id| profile_id == param_profile_id)
+      .returning(fn_returning);
+  }
+
+  if let Some(value) = expectations.find_users {
+    let FindUsers {
+      calls,
+      param_filter,
+      param_page,
+      param_per_page,
+      fn_returning,
+    } = value;
+
+    repository
+      .expect_find_users()
+      .times(calls)
+      .withf(move |filter, page, per_page| {
+        *filter == param_filter && *page == param_page && *per_page == param_per_page
+      })
+      .returning(fn_returning);
+  }
 
   repository
 }
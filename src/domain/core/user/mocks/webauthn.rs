@@ -0,0 +1,114 @@
+use ciborium::value::Value;
+
+use crate::domain::utilities::primitives::MockPrimitives;
+
+pub const RP_ID: &str = "localhost";
+pub const ORIGIN: &str = "http://localhost:8080";
+pub const CHALLENGE: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+pub const CREDENTIAL_ID: [u8; 16] = [7; 16];
+/// Base64url of `CREDENTIAL_ID`.
+pub const CREDENTIAL_ID_B64: &str = "BwcHBwcHBwcHBwcHBwcHBw";
+/// Uncompressed point, its validity only matters to the signature check.
+pub const PUBLIC_KEY: [u8; 65] = [4; 65];
+/// User present, user verified and attested credential data.
+pub const FLAGS_REGISTRATION: u8 = 0x45;
+/// User present and user verified.
+pub const FLAGS_ASSERTION: u8 = 0x05;
+
+/// Stubbed SHA-256 of `RP_ID`.
+pub const RP_ID_HASH: [u8; 32] = [9; 32];
+/// Stubbed SHA-256 of anything but `RP_ID`, the client data included.
+pub const OTHER_HASH: [u8; 32] = [8; 32];
+
+/// Primitives answering SHA-256 with `RP_ID_HASH` and `OTHER_HASH`.
+pub fn mock_sha256() -> MockPrimitives {
+  let mut mock_primitives = MockPrimitives::new();
+
+  mock_primitives.expect_sha256().returning(|data| {
+    if data == RP_ID.as_bytes() {
+      RP_ID_HASH.to_vec()
+    } else {
+      OTHER_HASH.to_vec()
+    }
+  });
+
+  mock_primitives
+}
+
+pub fn client_data_json(ceremony: &str, challenge: &str, origin: &str) -> Vec<u8> {
+  serde_json::json!({
+    "type": ceremony,
+    "challenge": challenge,
+    "origin": origin,
+    "crossOrigin": false,
+  })
+  .to_string()
+  .into_bytes()
+}
+
+/// EC2 P-256 COSE key of an uncompressed point.
+pub fn cose_key(public_key: &[u8]) -> Vec<u8> {
+  let mut key = Vec::new();
+  ciborium::ser::into_writer(
+    &Value::Map(vec![
+      (Value::Integer(1.into()), Value::Integer(2.into())),
+      (Value::Integer(3.into()), Value::Integer((-7).into())),
+      (Value::Integer((-1).into()), Value::Integer(1.into())),
+      (
+        Value::Integer((-2).into()),
+        Value::Bytes(public_key[1..33].to_vec()),
+      ),
+      (
+        Value::Integer((-3).into()),
+        Value::Bytes(public_key[33..65].to_vec()),
+      ),
+    ]),
+    &mut key,
+  )
+  .unwrap();
+
+  key
+}
+
+/// Authenticator data scoped to the relying party hashed to `rp_id_hash`,
+/// attesting `CREDENTIAL_ID` with the given COSE key when there is one.
+pub fn authenticator_data(
+  rp_id_hash: &[u8],
+  flags: u8,
+  sign_count: u32,
+  cose_key: Option<&[u8]>,
+) -> Vec<u8> {
+  let mut data = rp_id_hash.to_vec();
+  data.push(flags);
+  data.extend_from_slice(&sign_count.to_be_bytes());
+
+  if let Some(cose_key) = cose_key {
+    data.extend_from_slice(&[0; 16]);
+    data.extend_from_slice(&(CREDENTIAL_ID.len() as u16).to_be_bytes());
+    data.extend_from_slice(&CREDENTIAL_ID);
+    data.extend_from_slice(cose_key);
+  }
+
+  data
+}
+
+pub fn attestation_object(format: &str, authenticator_data: &[u8]) -> Vec<u8> {
+  let mut object = Vec::new();
+  ciborium::ser::into_writer(
+    &Value::Map(vec![
+      (
+        Value::Text(String::from("fmt")),
+        Value::Text(format.to_string()),
+      ),
+      (Value::Text(String::from("attStmt")), Value::Map(vec![])),
+      (
+        Value::Text(String::from("authData")),
+        Value::Bytes(authenticator_data.to_vec()),
+      ),
+    ]),
+    &mut object,
+  )
+  .unwrap();
+
+  object
+}
@@ -1,9 +1,43 @@
 pub mod change_password;
+pub mod confirm_totp;
+pub mod create_session;
+pub mod enroll_totp;
+pub mod exchange_authorization_code;
+pub mod external_sign_in;
+pub mod external_sign_up;
+pub mod get_grants;
+pub mod get_profile;
+pub mod issue_authorization_code;
+pub mod issue_refresh_token;
+pub mod issue_telephone_code;
+pub mod issue_verification_token;
+pub mod issue_webauthn_challenge;
+pub mod list_sessions;
+pub mod list_sign_ins;
+pub mod list_users;
 #[cfg(test)]
 pub mod mocks;
+pub mod redeem_password_reset;
+pub mod register_oauth_client;
+pub mod register_webauthn;
 pub mod repository;
+pub mod request_deletion;
+pub mod request_password_reset;
+pub mod revoke_sessions;
+pub mod rotate_refresh_token;
+pub mod set_account_status;
+pub mod set_role;
 pub mod sign_in;
 pub mod sign_up;
+pub mod start_external_sign_in;
+pub mod totp;
+pub mod unlock_account;
+pub mod update_profile;
+pub mod verify_email;
+pub mod verify_mfa;
+pub mod verify_telephone;
+pub mod webauthn;
+pub mod webauthn_sign_in;
 
 use self::repository::UserRepository;
 
@@ -16,7 +50,7 @@ pub struct User<'a, State = NoState, R = NoRepository> {
 }
 
 impl<'a> User<'a> {
-  pub fn new<R: UserRepository>(repository: &'a R) -> User<NoState, R> {
+  pub fn new<R: UserRepository>(repository: &'a R) -> User<'a, NoState, R> {
     User {
       state: NoState,
       repository,
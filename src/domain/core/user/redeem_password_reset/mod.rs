@@ -0,0 +1,61 @@
+use super::*;
+
+pub mod repository;
+pub mod responder;
+pub mod validator;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct TokenNotChecked;
+
+#[derive(Debug, PartialEq)]
+pub struct TokenChecked(pub(super) bool);
+
+#[derive(Default)]
+pub struct RedeemPasswordReset<Req, Db, Check> {
+  request: Req,
+  db_data: Db,
+  token_checked: Check,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub token: &'a str,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn redeem_password_reset(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, RedeemPasswordReset<Request<'a>, NoDbData, TokenNotChecked>, R> {
+    User {
+      repository: self.repository,
+      state: RedeemPasswordReset {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_redeem_password_reset_build() {
+    const TOKEN: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).redeem_password_reset(Request { token: TOKEN });
+
+    assert_eq!(sut.state.request, Request { token: TOKEN });
+  }
+}
@@ -0,0 +1,119 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::{VerificationPurpose, VerificationTokenData},
+  error::{AppError, Code},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, RedeemPasswordReset<Request<'a>, NoDbData, TokenNotChecked>, R>
+{
+  pub async fn get_token(
+    self,
+  ) -> Result<
+    User<'a, RedeemPasswordReset<Request<'a>, VerificationTokenData, TokenNotChecked>, R>,
+    AppError,
+  > {
+    let db_data = self
+      .repository
+      .find_verification_token(self.state.request.token, VerificationPurpose::PasswordReset)
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::invalid_argument("invalid password reset token"),
+        _ => error,
+      })?;
+
+    Ok(User {
+      repository: self.repository,
+      state: RedeemPasswordReset {
+        request: self.state.request,
+        db_data,
+        token_checked: self.state.token_checked,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, FindVerificationToken,
+  };
+
+  const TOKEN: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  #[tokio::test]
+  async fn test_get_token_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_verification_token: Some(FindVerificationToken {
+        calls: 1,
+        param_token: TOKEN.to_string(),
+        param_purpose: VerificationPurpose::PasswordReset,
+        fn_returning: |_, _| {
+          Ok(VerificationTokenData {
+            id: 1,
+            purpose: VerificationPurpose::PasswordReset,
+            profile_id: PROFILE_ID.to_string(),
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RedeemPasswordReset {
+        request: Request { token: TOKEN },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_token().await.unwrap();
+
+    assert_eq!(
+      sut.state.db_data,
+      VerificationTokenData {
+        id: 1,
+        purpose: VerificationPurpose::PasswordReset,
+        profile_id: PROFILE_ID.to_string(),
+        ..Default::default()
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_get_nonexistent_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_verification_token: Some(FindVerificationToken {
+        calls: 1,
+        param_token: TOKEN.to_string(),
+        param_purpose: VerificationPurpose::PasswordReset,
+        fn_returning: |_, _| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RedeemPasswordReset {
+        request: Request { token: TOKEN },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_token().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("invalid password reset token"))
+    );
+  }
+}
@@ -0,0 +1,49 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::VerificationTokenData,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, RedeemPasswordReset<Request<'a>, VerificationTokenData, TokenChecked>, R>
+{
+  /// Returns the consumed token, whose profile may now set a new password.
+  pub fn response(self) -> VerificationTokenData {
+    self.state.db_data
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_consumed_token() {
+    const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RedeemPasswordReset {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: VerificationTokenData {
+          id: 1,
+          profile_id: PROFILE_ID.to_string(),
+          ..Default::default()
+        },
+        token_checked: TokenChecked(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(sut.profile_id, PROFILE_ID);
+  }
+}
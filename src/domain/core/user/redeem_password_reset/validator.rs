@@ -0,0 +1,159 @@
+use chrono::Utc;
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::VerificationTokenData,
+  error::AppError,
+};
+
+use super::*;
+
+const TOKEN_USED: &str = "password reset token already used";
+
+type UserRedeemStateIn<'a, R> =
+  User<'a, RedeemPasswordReset<Request<'a>, VerificationTokenData, TokenNotChecked>, R>;
+type UserRedeemStateOut<'a, R> =
+  User<'a, RedeemPasswordReset<Request<'a>, VerificationTokenData, TokenChecked>, R>;
+
+impl<'a, R: UserRepository> UserRedeemStateIn<'a, R> {
+  /// Consumes the reset token, so a link can only change the password once.
+  pub async fn check_token(self) -> Result<UserRedeemStateOut<'a, R>, AppError> {
+    let token = &self.state.db_data;
+
+    if token.used {
+      return Err(AppError::invalid_argument(TOKEN_USED));
+    }
+
+    if token.expires_at <= Utc::now().naive_utc() {
+      return Err(AppError::invalid_argument("password reset token expired"));
+    }
+
+    if !self.repository.use_verification_token(token.id).await? {
+      return Err(AppError::invalid_argument(TOKEN_USED));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: RedeemPasswordReset {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        token_checked: TokenChecked(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Duration;
+
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, UseVerificationToken,
+  };
+
+  const TOKEN: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+
+  fn token_data() -> VerificationTokenData {
+    VerificationTokenData {
+      id: 1,
+      expires_at: Utc::now().naive_utc() + Duration::minutes(10),
+      ..Default::default()
+    }
+  }
+
+  fn build_user<R: UserRepository>(
+    repository: &R,
+    db_data: VerificationTokenData,
+  ) -> UserRedeemStateIn<'_, R> {
+    User {
+      repository,
+      state: RedeemPasswordReset {
+        request: Request { token: TOKEN },
+        db_data,
+        token_checked: TokenNotChecked,
+      },
+    }
+  }
+
+  #[tokio::test]
+  async fn test_check_valid_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_verification_token: Some(UseVerificationToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(true),
+      }),
+      ..Default::default()
+    });
+
+    let sut = build_user(&mock_repository, token_data())
+      .check_token()
+      .await
+      .unwrap();
+
+    assert_eq!(sut.state.token_checked, TokenChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_used_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = build_user(
+      &mock_repository,
+      VerificationTokenData {
+        used: true,
+        ..token_data()
+      },
+    )
+    .check_token()
+    .await
+    .err();
+
+    assert_eq!(sut, Some(AppError::invalid_argument(TOKEN_USED)));
+  }
+
+  #[tokio::test]
+  async fn test_check_concurrently_used_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_verification_token: Some(UseVerificationToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(false),
+      }),
+      ..Default::default()
+    });
+
+    let sut = build_user(&mock_repository, token_data())
+      .check_token()
+      .await
+      .err();
+
+    assert_eq!(sut, Some(AppError::invalid_argument(TOKEN_USED)));
+  }
+
+  #[tokio::test]
+  async fn test_check_expired_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = build_user(
+      &mock_repository,
+      VerificationTokenData {
+        expires_at: Utc::now().naive_utc() - Duration::minutes(1),
+        ..token_data()
+      },
+    )
+    .check_token()
+    .await
+    .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("password reset token expired"))
+    );
+  }
+}
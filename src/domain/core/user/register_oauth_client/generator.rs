@@ -0,0 +1,140 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::oauth::NewOAuthClient,
+  error::AppError,
+  utilities::{crypto::Crypto, id_generator::IDGenerator},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, RegisterOAuthClient<Request<'a>, NoClient, NotSaved>, R> {
+  /// The secret of a confidential client is hashed like a password.
+  pub fn generate(
+    self,
+    id_generator: &impl IDGenerator,
+    crypto: &impl Crypto,
+  ) -> Result<User<'a, RegisterOAuthClient<Request<'a>, GeneratedClient, NotSaved>, R>, AppError>
+  {
+    let secret = self
+      .state
+      .request
+      .confidential
+      .then(|| id_generator.new_secret());
+    let secret_hash = secret
+      .as_deref()
+      .map(|secret| crypto.hash_password(secret))
+      .transpose()?;
+
+    let client = NewOAuthClient {
+      client_id: id_generator.new_uuid(),
+      name: self.state.request.name.to_string(),
+      redirect_uris: self.state.request.redirect_uris.to_vec(),
+      secret_hash,
+    };
+
+    Ok(User {
+      repository: self.repository,
+      state: RegisterOAuthClient {
+        request: self.state.request,
+        client: GeneratedClient { client, secret },
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    utilities::{crypto::MockCrypto, id_generator::MockIDGenerator},
+  };
+
+  use super::*;
+
+  const CLIENT_ID: &str = "5f1c2a9e-7b4d-4e3a-9c8f-1d2e3f4a5b6c";
+  const SECRET: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+  const SECRET_HASH: &str = "$2b$12$secret_hash";
+
+  fn mock_id_generator(secret_calls: usize) -> MockIDGenerator {
+    let mut mock_id_generator = MockIDGenerator::new();
+    mock_id_generator
+      .expect_new_uuid()
+      .times(1)
+      .returning(|| CLIENT_ID.to_string());
+    mock_id_generator
+      .expect_new_secret()
+      .times(secret_calls)
+      .returning(|| SECRET.to_string());
+    mock_id_generator
+  }
+
+  #[test]
+  fn test_generate_confidential_client() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let redirect_uris = vec![String::from("https://app.example.com/callback")];
+
+    let user = User {
+      repository: &mock_repository,
+      state: RegisterOAuthClient {
+        request: Request {
+          name: "Example app",
+          redirect_uris: &redirect_uris,
+          confidential: true,
+        },
+        ..Default::default()
+      },
+    };
+
+    let mut mock_crypto = MockCrypto::new();
+    mock_crypto
+      .expect_hash_password()
+      .withf(|secret| secret == SECRET)
+      .times(1)
+      .returning(|_| Ok(SECRET_HASH.to_string()));
+
+    let sut = user.generate(&mock_id_generator(1), &mock_crypto).unwrap();
+
+    assert_eq!(
+      sut.state.client,
+      GeneratedClient {
+        client: NewOAuthClient {
+          client_id: CLIENT_ID.to_string(),
+          name: String::from("Example app"),
+          redirect_uris,
+          secret_hash: Some(SECRET_HASH.to_string()),
+        },
+        secret: Some(SECRET.to_string()),
+      }
+    );
+  }
+
+  #[test]
+  fn test_generate_public_client_without_secret() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RegisterOAuthClient {
+        request: Request {
+          name: "Example app",
+          confidential: false,
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    };
+
+    let mut mock_crypto = MockCrypto::new();
+    mock_crypto.expect_hash_password().times(0);
+
+    let sut = user.generate(&mock_id_generator(0), &mock_crypto).unwrap();
+
+    assert_eq!(sut.state.client.secret, None);
+    assert_eq!(sut.state.client.client.secret_hash, None);
+  }
+}
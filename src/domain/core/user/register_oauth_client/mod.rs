@@ -0,0 +1,80 @@
+use crate::domain::entities::oauth::NewOAuthClient;
+
+use super::*;
+
+pub mod generator;
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NoClient;
+
+#[derive(Default, Debug, PartialEq)]
+pub struct GeneratedClient {
+  pub client: NewOAuthClient,
+  /// Clear secret, only handed to the administrator once.
+  pub secret: Option<String>,
+}
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct RegisterOAuthClient<Req, Client, Save> {
+  request: Req,
+  client: Client,
+  saved: Save,
+}
+
+/// Public clients, such as single page or mobile apps that cannot keep a
+/// secret, are registered with `confidential` false.
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub name: &'a str,
+  pub redirect_uris: &'a [String],
+  pub confidential: bool,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn register_oauth_client(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, RegisterOAuthClient<Request<'a>, NoClient, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: RegisterOAuthClient {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_register_oauth_client_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).register_oauth_client(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -0,0 +1,73 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, RegisterOAuthClient<Request<'a>, GeneratedClient, NotSaved>, R>
+{
+  pub async fn save(
+    self,
+  ) -> Result<User<'a, RegisterOAuthClient<Request<'a>, GeneratedClient, Saved>, R>, AppError> {
+    self
+      .repository
+      .store_oauth_client(&self.state.client.client)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: RegisterOAuthClient {
+        request: self.state.request,
+        client: self.state.client,
+        saved: Saved(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations, StoreOAuthClient},
+    entities::oauth::NewOAuthClient,
+  };
+
+  const CLIENT_ID: &str = "5f1c2a9e-7b4d-4e3a-9c8f-1d2e3f4a5b6c";
+
+  #[tokio::test]
+  async fn test_save_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_oauth_client: Some(StoreOAuthClient {
+        calls: 1,
+        param_client_id: CLIENT_ID.to_string(),
+        param_secret_hash: None,
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RegisterOAuthClient {
+        request: Request {
+          ..Default::default()
+        },
+        client: GeneratedClient {
+          client: NewOAuthClient {
+            client_id: CLIENT_ID.to_string(),
+            ..Default::default()
+          },
+          secret: None,
+        },
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.save().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+}
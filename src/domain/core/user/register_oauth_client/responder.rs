@@ -0,0 +1,69 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::oauth::OAuthClientCredentials,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, RegisterOAuthClient<Request<'a>, GeneratedClient, Saved>, R> {
+  pub fn response(self) -> OAuthClientCredentials {
+    let GeneratedClient { client, secret } = self.state.client;
+
+    OAuthClientCredentials {
+      client_id: client.client_id,
+      client_secret: secret,
+      name: client.name,
+      redirect_uris: client.redirect_uris,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    entities::oauth::NewOAuthClient,
+  };
+
+  #[test]
+  fn test_response_with_clear_secret() {
+    const CLIENT_ID: &str = "5f1c2a9e-7b4d-4e3a-9c8f-1d2e3f4a5b6c";
+    const SECRET: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RegisterOAuthClient {
+        request: Request {
+          ..Default::default()
+        },
+        client: GeneratedClient {
+          client: NewOAuthClient {
+            client_id: CLIENT_ID.to_string(),
+            name: String::from("Example app"),
+            redirect_uris: vec![String::from("https://app.example.com/callback")],
+            secret_hash: Some(String::from("$2b$12$secret_hash")),
+          },
+          secret: Some(SECRET.to_string()),
+        },
+        saved: Saved(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(
+      sut,
+      OAuthClientCredentials {
+        client_id: CLIENT_ID.to_string(),
+        client_secret: Some(SECRET.to_string()),
+        name: String::from("Example app"),
+        redirect_uris: vec![String::from("https://app.example.com/callback")],
+      }
+    );
+  }
+}
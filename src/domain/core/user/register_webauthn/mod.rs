@@ -0,0 +1,86 @@
+use super::{webauthn::ClientData, *};
+use crate::domain::entities::verification_token::VerificationTokenData;
+
+pub mod repository;
+pub mod responder;
+pub mod validator;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default, Debug, PartialEq)]
+pub struct ChallengeData {
+  pub token: VerificationTokenData,
+  pub client_data: ClientData,
+}
+
+#[derive(Default)]
+pub struct NoCredential;
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct RegisterWebAuthn<Req, Db, Credential, Save> {
+  request: Req,
+  db_data: Db,
+  credential: Credential,
+  saved: Save,
+}
+
+/// Response of `navigator.credentials.create()`, binary fields being base64url
+/// encoded as the browser gives them.
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub rp_id: &'a str,
+  pub origin: &'a str,
+  pub credential_id: &'a str,
+  pub client_data_json: &'a str,
+  pub attestation_object: &'a str,
+  pub name: &'a str,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn register_webauthn(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, RegisterWebAuthn<Request<'a>, NoDbData, NoCredential, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: RegisterWebAuthn {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_register_webauthn_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).register_webauthn(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
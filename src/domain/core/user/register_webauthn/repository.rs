@@ -0,0 +1,307 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, webauthn, User},
+  entities::{verification_token::VerificationPurpose, webauthn::NewWebAuthnCredential},
+  error::{AppError, Code},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, RegisterWebAuthn<Request<'a>, NoDbData, NoCredential, NotSaved>, R>
+{
+  /// Finds the challenge the client data answers.
+  pub async fn get_challenge(
+    self,
+  ) -> Result<
+    User<'a, RegisterWebAuthn<Request<'a>, ChallengeData, NoCredential, NotSaved>, R>,
+    AppError,
+  > {
+    let client_data = webauthn::parse_client_data(&webauthn::decode(
+      self.state.request.client_data_json,
+      "client_data_json",
+    )?)?;
+
+    let token = self
+      .repository
+      .find_verification_token(
+        &client_data.challenge,
+        VerificationPurpose::WebAuthnRegistration,
+      )
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::invalid_argument("invalid WebAuthn challenge"),
+        _ => error,
+      })?;
+
+    Ok(User {
+      repository: self.repository,
+      state: RegisterWebAuthn {
+        request: self.state.request,
+        db_data: ChallengeData { token, client_data },
+        credential: self.state.credential,
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+impl<'a, R: UserRepository>
+  User<'a, RegisterWebAuthn<Request<'a>, ChallengeData, NewWebAuthnCredential, NotSaved>, R>
+{
+  /// Consumes the challenge before storing the passkey, so an attestation
+  /// cannot be registered twice.
+  pub async fn save(
+    self,
+  ) -> Result<
+    User<'a, RegisterWebAuthn<Request<'a>, ChallengeData, NewWebAuthnCredential, Saved>, R>,
+    AppError,
+  > {
+    if !self
+      .repository
+      .use_verification_token(self.state.db_data.token.id)
+      .await?
+    {
+      return Err(AppError::invalid_argument(
+        "WebAuthn challenge already used",
+      ));
+    }
+
+    self
+      .repository
+      .store_webauthn_credential(&self.state.credential)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: RegisterWebAuthn {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        credential: self.state.credential,
+        saved: Saved(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+  use super::*;
+  use crate::domain::{
+    core::user::{
+      mocks::{
+        repository::{
+          build_mock_user_repository, Expectations, FindVerificationToken, StoreWebAuthnCredential,
+          UseVerificationToken,
+        },
+        webauthn::{client_data_json, CHALLENGE, CREDENTIAL_ID_B64, ORIGIN},
+      },
+      repository::MockUserRepository,
+    },
+    entities::verification_token::VerificationTokenData,
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  fn encoded_client_data() -> String {
+    URL_SAFE_NO_PAD.encode(client_data_json(
+      webauthn::CEREMONY_CREATE,
+      CHALLENGE,
+      ORIGIN,
+    ))
+  }
+
+  fn find_verification_token(
+    fn_returning: fn(&str, VerificationPurpose) -> Result<VerificationTokenData, AppError>,
+  ) -> FindVerificationToken {
+    FindVerificationToken {
+      calls: 1,
+      param_token: CHALLENGE.to_string(),
+      param_purpose: VerificationPurpose::WebAuthnRegistration,
+      fn_returning,
+    }
+  }
+
+  fn saving_user(
+    mock_repository: &MockUserRepository,
+  ) -> User<
+    '_,
+    RegisterWebAuthn<Request<'_>, ChallengeData, NewWebAuthnCredential, NotSaved>,
+    MockUserRepository,
+  > {
+    User {
+      repository: mock_repository,
+      state: RegisterWebAuthn {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: ChallengeData {
+          token: VerificationTokenData {
+            id: 1,
+            ..Default::default()
+          },
+          ..Default::default()
+        },
+        credential: NewWebAuthnCredential {
+          credential_id: CREDENTIAL_ID_B64.to_string(),
+          profile_id: PROFILE_ID.to_string(),
+          ..Default::default()
+        },
+        saved: NotSaved,
+      },
+    }
+  }
+
+  #[tokio::test]
+  async fn test_get_challenge_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_verification_token: Some(find_verification_token(|_, _| {
+        Ok(VerificationTokenData {
+          id: 1,
+          profile_id: PROFILE_ID.to_string(),
+          ..Default::default()
+        })
+      })),
+      ..Default::default()
+    });
+    let client_data = encoded_client_data();
+
+    let user = User {
+      repository: &mock_repository,
+      state: RegisterWebAuthn {
+        request: Request {
+          client_data_json: &client_data,
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_challenge().await.unwrap();
+
+    assert_eq!(sut.state.db_data.token.profile_id, PROFILE_ID);
+    assert_eq!(sut.state.db_data.client_data.challenge, CHALLENGE);
+  }
+
+  #[tokio::test]
+  async fn test_get_unknown_challenge() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_verification_token: Some(find_verification_token(|_, _| {
+        Err(AppError::not_found(
+          "DB: nothing found with given parameters",
+        ))
+      })),
+      ..Default::default()
+    });
+    let client_data = encoded_client_data();
+
+    let user = User {
+      repository: &mock_repository,
+      state: RegisterWebAuthn {
+        request: Request {
+          client_data_json: &client_data,
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_challenge().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("invalid WebAuthn challenge"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_get_challenge_with_malformed_client_data() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RegisterWebAuthn {
+        request: Request {
+          client_data_json: "bm90IGpzb24",
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_challenge().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("invalid WebAuthn client data"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_save_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_verification_token: Some(UseVerificationToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(true),
+      }),
+      store_webauthn_credential: Some(StoreWebAuthnCredential {
+        calls: 1,
+        param_credential_id: CREDENTIAL_ID_B64.to_string(),
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let sut = saving_user(&mock_repository).save().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+
+  #[tokio::test]
+  async fn test_save_with_concurrently_used_challenge() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_verification_token: Some(UseVerificationToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(false),
+      }),
+      ..Default::default()
+    });
+
+    let sut = saving_user(&mock_repository).save().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "WebAuthn challenge already used"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_save_already_registered_passkey() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_verification_token: Some(UseVerificationToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(true),
+      }),
+      store_webauthn_credential: Some(StoreWebAuthnCredential {
+        calls: 1,
+        param_credential_id: CREDENTIAL_ID_B64.to_string(),
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| Err(AppError::already_exists("already exists")),
+      }),
+      ..Default::default()
+    });
+
+    let sut = saving_user(&mock_repository).save().await.err();
+
+    assert_eq!(sut, Some(AppError::already_exists("already exists")));
+  }
+}
@@ -0,0 +1,51 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::webauthn::NewWebAuthnCredential,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, RegisterWebAuthn<Request<'a>, ChallengeData, NewWebAuthnCredential, Saved>, R>
+{
+  /// Returns the id of the passkey that has just been registered.
+  pub fn response(self) -> String {
+    self.state.credential.credential_id
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_credential_id() {
+    const CREDENTIAL_ID: &str = "BwcHBwcHBwcHBwcHBwcHBw";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RegisterWebAuthn {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: ChallengeData {
+          ..Default::default()
+        },
+        credential: NewWebAuthnCredential {
+          credential_id: CREDENTIAL_ID.to_string(),
+          ..Default::default()
+        },
+        saved: Saved(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(sut, CREDENTIAL_ID);
+  }
+}
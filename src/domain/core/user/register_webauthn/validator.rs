@@ -0,0 +1,297 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+
+use crate::domain::{
+  core::user::{repository::UserRepository, webauthn, User},
+  entities::webauthn::NewWebAuthnCredential,
+  error::AppError,
+  utilities::primitives::Primitives,
+};
+
+use super::*;
+
+type UserRegisterStateIn<'a, R> =
+  User<'a, RegisterWebAuthn<Request<'a>, ChallengeData, NoCredential, NotSaved>, R>;
+type UserRegisterStateOut<'a, R> =
+  User<'a, RegisterWebAuthn<Request<'a>, ChallengeData, NewWebAuthnCredential, NotSaved>, R>;
+
+impl<'a, R: UserRepository> UserRegisterStateIn<'a, R> {
+  /// The challenge must have been issued to the same profile for this relying
+  /// party, and the attestation must hold a user verified ES256 passkey whose
+  /// id is the one reported by the client.
+  pub fn check_attestation(
+    self,
+    primitives: &impl Primitives,
+  ) -> Result<UserRegisterStateOut<'a, R>, AppError> {
+    let request = &self.state.request;
+    let ChallengeData { token, client_data } = &self.state.db_data;
+
+    if token.profile_id != request.profile_id || token.target != request.rp_id {
+      return Err(AppError::invalid_argument("invalid WebAuthn challenge"));
+    }
+    if token.used {
+      return Err(AppError::invalid_argument(
+        "WebAuthn challenge already used",
+      ));
+    }
+    if token.expires_at <= Utc::now().naive_utc() {
+      return Err(AppError::invalid_argument("WebAuthn challenge expired"));
+    }
+
+    webauthn::check_client_data(client_data, webauthn::CEREMONY_CREATE, request.origin)?;
+
+    let authenticator_data = webauthn::parse_attestation_object(&webauthn::decode(
+      request.attestation_object,
+      "attestation_object",
+    )?)?;
+    webauthn::check_authenticator_data(primitives, &authenticator_data, request.rp_id)?;
+
+    let Some((credential_id, public_key)) = authenticator_data.credential else {
+      return Err(AppError::invalid_argument(
+        "attestation holds no credential",
+      ));
+    };
+    if credential_id != webauthn::decode(request.credential_id, "credential_id")? {
+      return Err(AppError::invalid_argument(
+        "credential id does not match the attestation",
+      ));
+    }
+
+    let credential = NewWebAuthnCredential {
+      credential_id: URL_SAFE_NO_PAD.encode(credential_id),
+      profile_id: token.profile_id.clone(),
+      public_key,
+      sign_count: i64::from(authenticator_data.sign_count),
+      name: request.name.to_string(),
+    };
+
+    Ok(User {
+      repository: self.repository,
+      state: RegisterWebAuthn {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        credential,
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Duration;
+
+  use super::*;
+  use crate::domain::{
+    core::user::{
+      mocks::{
+        repository::{build_mock_user_repository, Expectations},
+        webauthn::{
+          attestation_object, authenticator_data, client_data_json, cose_key, mock_sha256,
+          CHALLENGE, CREDENTIAL_ID_B64, FLAGS_ASSERTION, FLAGS_REGISTRATION, ORIGIN, PUBLIC_KEY,
+          RP_ID, RP_ID_HASH,
+        },
+      },
+      repository::MockUserRepository,
+    },
+    entities::verification_token::VerificationTokenData,
+    utilities::primitives::MockPrimitives,
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const NAME: &str = "Laptop";
+
+  fn encoded_attestation(flags: u8, cose: Option<&[u8]>) -> String {
+    URL_SAFE_NO_PAD.encode(attestation_object(
+      "none",
+      &authenticator_data(&RP_ID_HASH, flags, 0, cose),
+    ))
+  }
+
+  fn valid_attestation() -> String {
+    encoded_attestation(FLAGS_REGISTRATION, Some(&cose_key(&PUBLIC_KEY)))
+  }
+
+  fn challenge_data() -> ChallengeData {
+    ChallengeData {
+      token: VerificationTokenData {
+        id: 1,
+        profile_id: PROFILE_ID.to_string(),
+        target: RP_ID.to_string(),
+        expires_at: Utc::now().naive_utc() + Duration::minutes(5),
+        ..Default::default()
+      },
+      client_data: webauthn::parse_client_data(&client_data_json(
+        webauthn::CEREMONY_CREATE,
+        CHALLENGE,
+        ORIGIN,
+      ))
+      .unwrap(),
+    }
+  }
+
+  fn user<'a>(
+    mock_repository: &'a MockUserRepository,
+    attestation: &'a str,
+    db_data: ChallengeData,
+  ) -> UserRegisterStateIn<'a, MockUserRepository> {
+    User {
+      repository: mock_repository,
+      state: RegisterWebAuthn {
+        request: Request {
+          profile_id: PROFILE_ID,
+          rp_id: RP_ID,
+          origin: ORIGIN,
+          credential_id: CREDENTIAL_ID_B64,
+          client_data_json: "",
+          attestation_object: attestation,
+          name: NAME,
+        },
+        db_data,
+        credential: NoCredential,
+        saved: NotSaved,
+      },
+    }
+  }
+
+  #[test]
+  fn test_check_valid_attestation() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let attestation = valid_attestation();
+
+    let sut = user(&mock_repository, &attestation, challenge_data())
+      .check_attestation(&mock_sha256())
+      .unwrap();
+
+    assert_eq!(
+      sut.state.credential,
+      NewWebAuthnCredential {
+        credential_id: CREDENTIAL_ID_B64.to_string(),
+        profile_id: PROFILE_ID.to_string(),
+        public_key: PUBLIC_KEY.to_vec(),
+        sign_count: 0,
+        name: NAME.to_string(),
+      }
+    );
+  }
+
+  #[test]
+  fn test_check_challenge_of_another_profile() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let attestation = valid_attestation();
+    let mut db_data = challenge_data();
+    db_data.token.profile_id = String::from("a43f9ba5-3a79-4ef2-a4c6-6a2e1b0d8c5e");
+
+    let sut = user(&mock_repository, &attestation, db_data)
+      .check_attestation(&MockPrimitives::new())
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("invalid WebAuthn challenge"))
+    );
+  }
+
+  #[test]
+  fn test_check_expired_challenge() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let attestation = valid_attestation();
+    let mut db_data = challenge_data();
+    db_data.token.expires_at = Utc::now().naive_utc() - Duration::minutes(1);
+
+    let sut = user(&mock_repository, &attestation, db_data)
+      .check_attestation(&MockPrimitives::new())
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("WebAuthn challenge expired"))
+    );
+  }
+
+  #[test]
+  fn test_check_used_challenge() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let attestation = valid_attestation();
+    let mut db_data = challenge_data();
+    db_data.token.used = true;
+
+    let sut = user(&mock_repository, &attestation, db_data)
+      .check_attestation(&MockPrimitives::new())
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "WebAuthn challenge already used"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_check_sign_in_ceremony() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let attestation = valid_attestation();
+    let mut db_data = challenge_data();
+    db_data.client_data.ceremony = webauthn::CEREMONY_GET.to_string();
+
+    let sut = user(&mock_repository, &attestation, db_data)
+      .check_attestation(&MockPrimitives::new())
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "expected a webauthn.create WebAuthn ceremony"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_check_attestation_without_credential() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let attestation = encoded_attestation(FLAGS_ASSERTION, None);
+
+    let sut = user(&mock_repository, &attestation, challenge_data())
+      .check_attestation(&mock_sha256())
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "attestation holds no credential"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_check_attestation_of_another_credential() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let attestation = valid_attestation();
+    let mut sut = user(&mock_repository, &attestation, challenge_data());
+    sut.state.request.credential_id = "CAgICAgICAgICAgICAgICA";
+
+    let sut = sut.check_attestation(&mock_sha256()).err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "credential id does not match the attestation"
+      ))
+    );
+  }
+}
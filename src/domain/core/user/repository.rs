@@ -1,20 +1,205 @@
 use async_trait::async_trait;
+use chrono::NaiveDateTime;
 use mockall::automock;
 
 use crate::domain::{
-  entities::user::{UserColumns, UserData},
+  entities::{
+    external_identity::{
+      ExternalClaims, ExternalIdentityData, ExternalSignInData, NewExternalIdentity,
+      NewExternalSignIn,
+    },
+    oauth::{AuthorizationCodeData, NewAuthorizationCode, NewOAuthClient, OAuthClientData},
+    refresh_token::{NewRefreshToken, RefreshTokenData},
+    role::Grants,
+    session::{NewSession, Session},
+    sign_in_event::{NewSignInEvent, SignInEventPage},
+    totp::TotpAuthenticatorData,
+    user::{FullProfile, UserColumns, UserData, UserFilter, UserSummaryPage},
+    verification_token::{NewVerificationToken, VerificationPurpose, VerificationTokenData},
+    webauthn::{NewWebAuthnCredential, WebAuthnCredentialData},
+  },
   error::AppError,
 };
 
-use super::sign_up::{self, encrypter::PasswordEncrypted};
+use super::{
+  sign_up::{self, encrypter::PasswordEncrypted},
+  update_profile,
+};
 
 #[automock]
 #[async_trait]
 pub trait UserRepository: Sync + Send {
   async fn find_user_by<'a>(&self, column: &UserColumns<'a>) -> Result<UserData, AppError>;
+  async fn find_full_profile_by<'a>(
+    &self,
+    column: &UserColumns<'a>,
+  ) -> Result<FullProfile, AppError>;
   async fn store<'a>(
     &self,
     user_data: &sign_up::Request<'a, String, PasswordEncrypted>,
   ) -> Result<(), AppError>;
   async fn update_password(&self, password: &str, profile_id: &str) -> Result<(), AppError>;
+  /// Replaces `verified_password`, the hash the password was just checked
+  /// against, keeping the issued tokens valid. Fails when the hash changed in
+  /// the meantime.
+  async fn rehash_password(
+    &self,
+    password: &str,
+    verified_password: &str,
+    profile_id: &str,
+  ) -> Result<(), AppError>;
+  async fn update_profile<'a>(&self, profile: &update_profile::Request<'a>)
+    -> Result<(), AppError>;
+  async fn register_sign_in(&self, profile_id: &str) -> Result<(), AppError>;
+  async fn register_failed_sign_in(
+    &self,
+    profile_id: &str,
+    max_failed_attempts: i32,
+  ) -> Result<bool, AppError>;
+  async fn unlock_user(&self, profile_id: &str) -> Result<(), AppError>;
+  /// Disabling also bumps the token version and revokes the refresh tokens,
+  /// so tokens already issued stop working.
+  async fn update_account_disabled(&self, profile_id: &str, disabled: bool)
+    -> Result<(), AppError>;
+  async fn request_deletion(&self, profile_id: &str) -> Result<(), AppError>;
+  async fn cancel_deletion(&self, profile_id: &str) -> Result<(), AppError>;
+  /// Returns how many accounts were purged.
+  async fn purge_requested_deletions(&self, grace_period_days: i32) -> Result<i32, AppError>;
+  async fn store_sign_in_event(&self, event: &NewSignInEvent) -> Result<(), AppError>;
+  async fn find_sign_in_events(
+    &self,
+    profile_id: &str,
+    page: i64,
+    per_page: i64,
+  ) -> Result<SignInEventPage, AppError>;
+  async fn store_refresh_token(&self, refresh_token: &NewRefreshToken) -> Result<(), AppError>;
+  async fn find_refresh_token(&self, token: &str) -> Result<RefreshTokenData, AppError>;
+  async fn use_refresh_token(&self, id: i32) -> Result<bool, AppError>;
+  async fn revoke_refresh_token_family(&self, family_id: &str) -> Result<(), AppError>;
+  async fn revoke_refresh_tokens(&self, profile_id: &str) -> Result<(), AppError>;
+  async fn store_session(&self, session: &NewSession) -> Result<(), AppError>;
+  /// Sessions not ended that still hold a usable refresh token, last seen
+  /// first.
+  async fn find_sessions(&self, profile_id: &str) -> Result<Vec<Session>, AppError>;
+  /// Ends the session and revokes its refresh tokens, not found when the
+  /// profile has no such session or it already ended.
+  async fn revoke_session(&self, profile_id: &str, session_id: &str) -> Result<(), AppError>;
+  /// Ends every session of the profile but `keep_session_id` and revokes
+  /// their refresh tokens, returning the ids of the sessions ended.
+  async fn revoke_other_sessions<'a>(
+    &self,
+    profile_id: &str,
+    keep_session_id: Option<&'a str>,
+  ) -> Result<Vec<String>, AppError>;
+  async fn store_verification_token(&self, token: &NewVerificationToken) -> Result<(), AppError>;
+  async fn find_verification_token(
+    &self,
+    token: &str,
+    purpose: VerificationPurpose,
+  ) -> Result<VerificationTokenData, AppError>;
+  /// Marks the token as used, false when it was already used.
+  async fn use_verification_token(&self, id: i32) -> Result<bool, AppError>;
+  async fn check_email(&self, profile_id: &str, address: &str) -> Result<(), AppError>;
+  async fn count_verification_tokens(
+    &self,
+    profile_id: &str,
+    purpose: VerificationPurpose,
+    since: NaiveDateTime,
+  ) -> Result<i64, AppError>;
+  /// The token issued last for the target, used or not.
+  async fn find_latest_verification_token(
+    &self,
+    profile_id: &str,
+    purpose: VerificationPurpose,
+    target: &str,
+  ) -> Result<VerificationTokenData, AppError>;
+  /// Counts a guess against the unused token while it has taken fewer than
+  /// `max_attempts`, true when the guess matches it. None when no guess is
+  /// left, checked by the same update so parallel guesses cannot exceed it.
+  async fn attempt_verification_token(
+    &self,
+    id: i32,
+    token: &str,
+    max_attempts: i32,
+  ) -> Result<Option<bool>, AppError>;
+  async fn check_telephone(&self, profile_id: &str, number: &str) -> Result<(), AppError>;
+  async fn find_totp_authenticator(
+    &self,
+    profile_id: &str,
+  ) -> Result<TotpAuthenticatorData, AppError>;
+  /// Replaces a pending authenticator, a confirmed one is left untouched.
+  async fn store_totp_authenticator(&self, profile_id: &str, secret: &str) -> Result<(), AppError>;
+  /// Confirms the authenticator and replaces the profile recovery codes.
+  async fn confirm_totp_authenticator(
+    &self,
+    profile_id: &str,
+    step: i64,
+    recovery_codes: &[String],
+  ) -> Result<(), AppError>;
+  /// Records the time step of an accepted code, false when a code of the same
+  /// or a later step was already accepted.
+  async fn use_totp_step(&self, profile_id: &str, step: i64) -> Result<bool, AppError>;
+  /// Consumes a recovery code, false when it does not exist or was used.
+  async fn use_recovery_code(&self, profile_id: &str, code: &str) -> Result<bool, AppError>;
+  async fn store_webauthn_credential(
+    &self,
+    credential: &NewWebAuthnCredential,
+  ) -> Result<(), AppError>;
+  async fn find_webauthn_credential(
+    &self,
+    credential_id: &str,
+  ) -> Result<WebAuthnCredentialData, AppError>;
+  async fn find_webauthn_credentials(
+    &self,
+    profile_id: &str,
+  ) -> Result<Vec<WebAuthnCredentialData>, AppError>;
+  /// Records a sign-in with the passkey and the counter it reported.
+  async fn update_webauthn_sign_count(&self, id: i32, sign_count: i64) -> Result<(), AppError>;
+  async fn store_oauth_client(&self, client: &NewOAuthClient) -> Result<(), AppError>;
+  async fn find_oauth_client(&self, client_id: &str) -> Result<OAuthClientData, AppError>;
+  async fn store_authorization_code(&self, code: &NewAuthorizationCode) -> Result<(), AppError>;
+  async fn find_authorization_code(&self, code: &str) -> Result<AuthorizationCodeData, AppError>;
+  /// Marks the code as redeemed, false when it was already redeemed.
+  async fn use_authorization_code(&self, id: i32) -> Result<bool, AppError>;
+  async fn store_external_sign_in(&self, sign_in: &NewExternalSignIn) -> Result<(), AppError>;
+  async fn find_external_sign_in(&self, state: &str) -> Result<ExternalSignInData, AppError>;
+  /// Marks the sign-in as come back from the provider, false when it already
+  /// had.
+  async fn use_external_sign_in(&self, id: i32) -> Result<bool, AppError>;
+  /// Keeps the claims of a provider account that matched no profile, giving
+  /// the user until `expires_at` to complete the sign-up.
+  async fn hold_external_sign_up(
+    &self,
+    id: i32,
+    claims: &ExternalClaims,
+    expires_at: NaiveDateTime,
+  ) -> Result<(), AppError>;
+  /// False when the sign-up was already completed.
+  async fn complete_external_sign_up(&self, id: i32) -> Result<bool, AppError>;
+  async fn find_external_identity(
+    &self,
+    provider: &str,
+    subject: &str,
+  ) -> Result<ExternalIdentityData, AppError>;
+  async fn store_external_identity(&self, identity: &NewExternalIdentity) -> Result<(), AppError>;
+  async fn find_grants(&self, profile_id: &str) -> Result<Grants, AppError>;
+  /// Version access tokens of the profile are issued with, bumped on every
+  /// password update, account disable and role revocation.
+  async fn find_token_version(&self, profile_id: &str) -> Result<i32, AppError>;
+  /// False when the profile already had the role, not found when the
+  /// profile or the role does not exist.
+  async fn grant_role(&self, profile_id: &str, role: &str) -> Result<bool, AppError>;
+  /// False when the profile did not have the role, not found when the
+  /// profile or the role does not exist. A revocation bumps the token
+  /// version, so access tokens carrying the role stop working.
+  async fn revoke_role(&self, profile_id: &str, role: &str) -> Result<bool, AppError>;
+  /// Refuses password sign-ins until the password is reset. Also bumps the
+  /// token version and revokes the refresh tokens, so every session ends.
+  async fn require_password_reset(&self, profile_id: &str) -> Result<(), AppError>;
+  async fn find_users(
+    &self,
+    filter: &UserFilter,
+    page: i64,
+    per_page: i64,
+  ) -> Result<UserSummaryPage, AppError>;
 }
@@ -0,0 +1,78 @@
+use super::*;
+
+pub mod repository;
+pub mod validator;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct PasswordNotChecked;
+
+#[derive(Debug, PartialEq)]
+pub struct PasswordChecked(pub(super) bool);
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved;
+
+#[derive(Default)]
+pub struct RequestDeletion<Req, Db, PwdCheck, Save> {
+  request: Req,
+  db_data: Db,
+  password_checked: PwdCheck,
+  saved: Save,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn request_deletion(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, RequestDeletion<Request<'a>, NoDbData, PasswordNotChecked, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: RequestDeletion {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub password: &'a str,
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_request_deletion_build() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+    const PASSWORD: &str = "123456789";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).request_deletion(Request {
+      profile_id: UUID,
+      password: PASSWORD,
+    });
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        profile_id: UUID,
+        password: PASSWORD,
+      }
+    );
+  }
+}
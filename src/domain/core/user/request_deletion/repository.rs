@@ -0,0 +1,178 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::user::{UserColumns, UserData},
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, RequestDeletion<Request<'a>, NoDbData, PasswordNotChecked, NotSaved>, R>
+{
+  pub async fn get_user(
+    self,
+  ) -> Result<
+    User<'a, RequestDeletion<Request<'a>, UserData, PasswordNotChecked, NotSaved>, R>,
+    AppError,
+  > {
+    let user_data = self
+      .repository
+      .find_user_by(&UserColumns::Id(self.state.request.profile_id))
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: RequestDeletion {
+        request: self.state.request,
+        db_data: user_data,
+        password_checked: self.state.password_checked,
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+impl<'a, R: UserRepository>
+  User<'a, RequestDeletion<Request<'a>, UserData, PasswordChecked, NotSaved>, R>
+{
+  /// Marks the account for deletion and revokes its refresh tokens, the
+  /// account is purged once the grace period ends unless the user signs in.
+  pub async fn save(
+    self,
+  ) -> Result<User<'a, RequestDeletion<Request<'a>, UserData, PasswordChecked, Saved>, R>, AppError>
+  {
+    let profile_id = self.state.request.profile_id;
+
+    self.repository.request_deletion(profile_id).await?;
+    self.repository.revoke_refresh_tokens(profile_id).await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: RequestDeletion {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        password_checked: self.state.password_checked,
+        saved: Saved,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, FindUserBy, RequestDeletion as RequestDeletionMock,
+    RevokeRefreshTokens,
+  };
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  #[tokio::test]
+  async fn test_get_user_db_data_successfully() {
+    const USERNAME: &str = "john_doe";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_user_by: Some(FindUserBy {
+        calls: 1,
+        fn_returning: |_| {
+          Ok(UserData {
+            id: UUID.to_string(),
+            username: USERNAME.to_string(),
+            ..Default::default()
+          })
+        },
+        param_column_with: UserColumns::Id(UUID),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RequestDeletion {
+        request: Request {
+          profile_id: UUID,
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_user().await.unwrap();
+
+    assert_eq!(
+      sut.state.db_data,
+      UserData {
+        id: UUID.to_string(),
+        username: USERNAME.to_string(),
+        ..Default::default()
+      }
+    )
+  }
+
+  #[tokio::test]
+  async fn test_save_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      request_deletion: Some(RequestDeletionMock {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        fn_returning: |_| Ok(()),
+      }),
+      revoke_refresh_tokens: Some(RevokeRefreshTokens {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RequestDeletion {
+        request: Request {
+          profile_id: UUID,
+          ..Default::default()
+        },
+        db_data: UserData::default(),
+        password_checked: PasswordChecked(true),
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.save().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved);
+  }
+
+  #[tokio::test]
+  async fn test_save_failed() {
+    const DB_ERROR_MESSAGE: &str = "Some error in the database";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      request_deletion: Some(RequestDeletionMock {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        fn_returning: |_| Err(AppError::database_error(DB_ERROR_MESSAGE)),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RequestDeletion {
+        request: Request {
+          profile_id: UUID,
+          ..Default::default()
+        },
+        db_data: UserData::default(),
+        password_checked: PasswordChecked(true),
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.save().await.err();
+
+    assert_eq!(sut, Some(AppError::database_error(DB_ERROR_MESSAGE)));
+  }
+}
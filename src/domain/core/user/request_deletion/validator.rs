@@ -0,0 +1,124 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::user::UserData,
+  error::AppError,
+  utilities::crypto::Crypto,
+};
+
+use super::*;
+
+type UserRequestDeletionStateIn<'a, R> =
+  User<'a, RequestDeletion<Request<'a>, UserData, PasswordNotChecked, NotSaved>, R>;
+type UserRequestDeletionStateOut<'a, R> =
+  User<'a, RequestDeletion<Request<'a>, UserData, PasswordChecked, NotSaved>, R>;
+
+impl<'a, R: UserRepository> UserRequestDeletionStateIn<'a, R> {
+  pub fn check_password(
+    self,
+    cryto: &'a impl Crypto,
+  ) -> Result<UserRequestDeletionStateOut<'a, R>, AppError> {
+    if !cryto.verify_password(&self.state.db_data.password, self.state.request.password)? {
+      return Err(AppError::permission_denied("The given password is invalid"));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: RequestDeletion {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        password_checked: PasswordChecked(true),
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use mockall::predicate;
+
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    utilities::crypto::MockCrypto,
+  };
+
+  const HASH_PASSWORD: &str = "mg3824m1htv8913dxjrn9ui45g801q43tj";
+
+  #[test]
+  fn test_check_password_matches() {
+    const PASSWORD: &str = "123456789";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RequestDeletion {
+        db_data: UserData {
+          password: HASH_PASSWORD.to_owned(),
+          ..Default::default()
+        },
+        request: Request {
+          password: PASSWORD,
+          ..Default::default()
+        },
+        password_checked: PasswordNotChecked,
+        saved: NotSaved,
+      },
+    };
+
+    let mut mock_crypto = MockCrypto::new();
+
+    mock_crypto
+      .expect_verify_password()
+      .times(1)
+      .with(predicate::eq(HASH_PASSWORD), predicate::eq(PASSWORD))
+      .returning(|_, _| Ok(true));
+
+    let sut = user.check_password(&mock_crypto).unwrap();
+
+    assert!(sut.state.password_checked.0);
+  }
+
+  #[test]
+  fn test_check_password_not_matches() {
+    const WRONG_PASSWORD: &str = "987654321";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RequestDeletion {
+        db_data: UserData {
+          password: HASH_PASSWORD.to_owned(),
+          ..Default::default()
+        },
+        request: Request {
+          password: WRONG_PASSWORD,
+          ..Default::default()
+        },
+        password_checked: PasswordNotChecked,
+        saved: NotSaved,
+      },
+    };
+
+    let mut mock_crypto = MockCrypto::new();
+
+    mock_crypto
+      .expect_verify_password()
+      .times(1)
+      .with(predicate::eq(HASH_PASSWORD), predicate::eq(WRONG_PASSWORD))
+      .returning(|_, _| Ok(false));
+
+    let sut = user.check_password(&mock_crypto).err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::permission_denied("The given password is invalid"))
+    );
+  }
+}
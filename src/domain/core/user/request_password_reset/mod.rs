@@ -0,0 +1,69 @@
+use crate::domain::entities::user::UserColumns;
+
+use super::*;
+
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct RequestPasswordReset<Req, Db> {
+  request: Req,
+  db_data: Db,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub column: UserColumns<'a>,
+}
+
+/// Profile asking for a new password and the address the reset token goes to.
+#[derive(Default, Debug, PartialEq)]
+pub struct ResetTarget {
+  pub profile_id: String,
+  pub address: String,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn request_password_reset(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, RequestPasswordReset<Request<'a>, NoDbData>, R> {
+    User {
+      repository: self.repository,
+      state: RequestPasswordReset {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_request_password_reset_build() {
+    const USERNAME: &str = "john_doe";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).request_password_reset(Request {
+      column: UserColumns::Username(USERNAME),
+    });
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        column: UserColumns::Username(USERNAME),
+      }
+    );
+  }
+}
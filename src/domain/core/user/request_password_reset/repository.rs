@@ -0,0 +1,232 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::user::UserColumns,
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, RequestPasswordReset<Request<'a>, NoDbData>, R> {
+  /// Picks the address the reset token is sent to: the one used to ask for
+  /// the reset, otherwise the first verified address of the profile.
+  pub async fn find_target(
+    self,
+  ) -> Result<User<'a, RequestPasswordReset<Request<'a>, ResetTarget>, R>, AppError> {
+    let profile = self
+      .repository
+      .find_full_profile_by(&self.state.request.column)
+      .await?;
+
+    let address = match self.state.request.column {
+      UserColumns::Email(address) => Some(address.to_string()),
+      _ => profile
+        .emails
+        .iter()
+        .find(|email| email.checked)
+        .or_else(|| profile.emails.first())
+        .map(|email| email.address.clone()),
+    }
+    .ok_or_else(|| AppError::not_found("no email address to send the reset token"))?;
+
+    Ok(User {
+      repository: self.repository,
+      state: RequestPasswordReset {
+        request: self.state.request,
+        db_data: ResetTarget {
+          profile_id: profile.id,
+          address,
+        },
+      },
+    })
+  }
+}
+
+impl<'a, R: UserRepository> User<'a, RequestPasswordReset<Request<'a>, ResetTarget>, R> {
+  /// For resets forced by an administrator: password sign-ins are refused
+  /// until the reset and every session of the profile is signed out.
+  pub async fn require_reset(self) -> Result<Self, AppError> {
+    let profile_id = &self.state.db_data.profile_id;
+
+    self.repository.require_password_reset(profile_id).await?;
+
+    Ok(self)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{
+      build_mock_user_repository, Expectations, FindFullProfileBy, RequirePasswordReset,
+    },
+    entities::user::{Email, FullProfile},
+    error::Code,
+  };
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const USERNAME: &str = "john_doe";
+  const EMAIL: &str = "user@email.com";
+  const VERIFIED_EMAIL: &str = "verified@email.com";
+
+  fn build_user<'a, R: UserRepository>(
+    repository: &'a R,
+    column: UserColumns<'a>,
+  ) -> User<'a, RequestPasswordReset<Request<'a>, NoDbData>, R> {
+    User {
+      repository,
+      state: RequestPasswordReset {
+        request: Request { column },
+        ..Default::default()
+      },
+    }
+  }
+
+  #[tokio::test]
+  async fn test_find_target_by_email() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_full_profile_by: Some(FindFullProfileBy {
+        calls: 1,
+        param_column_with: UserColumns::Email(EMAIL),
+        fn_returning: |_| {
+          Ok(FullProfile {
+            id: UUID.to_string(),
+            emails: vec![
+              Email {
+                address: VERIFIED_EMAIL.to_string(),
+                checked: true,
+              },
+              Email {
+                address: EMAIL.to_string(),
+                checked: false,
+              },
+            ],
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let sut = build_user(&mock_repository, UserColumns::Email(EMAIL))
+      .find_target()
+      .await
+      .unwrap();
+
+    assert_eq!(
+      sut.state.db_data,
+      ResetTarget {
+        profile_id: UUID.to_string(),
+        address: EMAIL.to_string(),
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_find_target_prefers_verified_address() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_full_profile_by: Some(FindFullProfileBy {
+        calls: 1,
+        param_column_with: UserColumns::Username(USERNAME),
+        fn_returning: |_| {
+          Ok(FullProfile {
+            id: UUID.to_string(),
+            emails: vec![
+              Email {
+                address: EMAIL.to_string(),
+                checked: false,
+              },
+              Email {
+                address: VERIFIED_EMAIL.to_string(),
+                checked: true,
+              },
+            ],
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let sut = build_user(&mock_repository, UserColumns::Username(USERNAME))
+      .find_target()
+      .await
+      .unwrap();
+
+    assert_eq!(sut.state.db_data.address, VERIFIED_EMAIL);
+  }
+
+  #[tokio::test]
+  async fn test_find_target_without_email() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_full_profile_by: Some(FindFullProfileBy {
+        calls: 1,
+        param_column_with: UserColumns::Username(USERNAME),
+        fn_returning: |_| {
+          Ok(FullProfile {
+            id: UUID.to_string(),
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let sut = build_user(&mock_repository, UserColumns::Username(USERNAME))
+      .find_target()
+      .await
+      .err();
+
+    assert_eq!(sut.map(|error| error.code), Some(Code::NotFound));
+  }
+
+  #[tokio::test]
+  async fn test_fail_find_target() {
+    const DB_ERROR_MESSAGE: &str = "Some error in the database";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_full_profile_by: Some(FindFullProfileBy {
+        calls: 1,
+        param_column_with: UserColumns::Username(USERNAME),
+        fn_returning: |_| Err(AppError::database_error(DB_ERROR_MESSAGE)),
+      }),
+      ..Default::default()
+    });
+
+    let sut = build_user(&mock_repository, UserColumns::Username(USERNAME))
+      .find_target()
+      .await
+      .err();
+
+    assert_eq!(sut, Some(AppError::database_error(DB_ERROR_MESSAGE)));
+  }
+
+  #[tokio::test]
+  async fn test_require_reset() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      require_password_reset: Some(RequirePasswordReset {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RequestPasswordReset {
+        request: Request {
+          column: UserColumns::Id(UUID),
+        },
+        db_data: ResetTarget {
+          profile_id: UUID.to_string(),
+          address: EMAIL.to_string(),
+        },
+      },
+    };
+
+    let sut = user.require_reset().await.unwrap();
+
+    assert_eq!(sut.state.db_data.profile_id, UUID);
+  }
+}
@@ -0,0 +1,48 @@
+use crate::domain::core::user::{repository::UserRepository, User};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, RequestPasswordReset<Request<'a>, ResetTarget>, R> {
+  pub fn response(self) -> ResetTarget {
+    self.state.db_data
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_target() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+    const EMAIL: &str = "user@email.com";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RequestPasswordReset {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: ResetTarget {
+          profile_id: UUID.to_string(),
+          address: EMAIL.to_string(),
+        },
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(
+      sut,
+      ResetTarget {
+        profile_id: UUID.to_string(),
+        address: EMAIL.to_string(),
+      }
+    );
+  }
+}
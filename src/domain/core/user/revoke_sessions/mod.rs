@@ -0,0 +1,78 @@
+use super::*;
+
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NotRevoked;
+
+#[derive(Default)]
+pub struct RevokeSessions<Req, Revoked> {
+  request: Req,
+  revoked: Revoked,
+}
+
+/// Sessions of the profile to end.
+#[derive(Debug, PartialEq)]
+pub enum Sessions<'a> {
+  One(&'a str),
+  /// Every session but the given one, usually the one asking.
+  AllExcept(Option<&'a str>),
+}
+
+impl Default for Sessions<'_> {
+  fn default() -> Self {
+    Sessions::AllExcept(None)
+  }
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub sessions: Sessions<'a>,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn revoke_sessions(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, RevokeSessions<Request<'a>, NotRevoked>, R> {
+    User {
+      repository: self.repository,
+      state: RevokeSessions {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_revoke_sessions_build() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+    const SESSION_ID: &str = "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).revoke_sessions(Request {
+      profile_id: UUID,
+      sessions: Sessions::One(SESSION_ID),
+    });
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        profile_id: UUID,
+        sessions: Sessions::One(SESSION_ID),
+      }
+    );
+  }
+}
@@ -0,0 +1,146 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, RevokeSessions<Request<'a>, NotRevoked>, R> {
+  /// Ends the sessions along with their refresh tokens, their access tokens
+  /// are left to the token service.
+  pub async fn revoke(
+    self,
+  ) -> Result<User<'a, RevokeSessions<Request<'a>, Vec<String>>, R>, AppError> {
+    let request = &self.state.request;
+
+    let revoked = match request.sessions {
+      Sessions::One(session_id) => {
+        self
+          .repository
+          .revoke_session(request.profile_id, session_id)
+          .await?;
+
+        vec![session_id.to_string()]
+      }
+      Sessions::AllExcept(keep_session_id) => {
+        self
+          .repository
+          .revoke_other_sessions(request.profile_id, keep_session_id)
+          .await?
+      }
+    };
+
+    Ok(User {
+      repository: self.repository,
+      state: RevokeSessions {
+        request: self.state.request,
+        revoked,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, RevokeOtherSessions, RevokeSession,
+  };
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const SESSION_ID: &str = "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed";
+  const OTHER_SESSION_ID: &str = "5c0d6a8e-2b3f-4e1d-8a9c-7f6e5d4c3b2a";
+
+  #[tokio::test]
+  async fn test_revoke_one_session() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      revoke_session: Some(RevokeSession {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        param_session_id: SESSION_ID.to_owned(),
+        fn_returning: |_, _| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RevokeSessions {
+        request: Request {
+          profile_id: UUID,
+          sessions: Sessions::One(SESSION_ID),
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.revoke().await.unwrap();
+
+    assert_eq!(sut.state.revoked, vec![SESSION_ID.to_owned()]);
+  }
+
+  #[tokio::test]
+  async fn test_revoke_unknown_session() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      revoke_session: Some(RevokeSession {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        param_session_id: SESSION_ID.to_owned(),
+        fn_returning: |_, _| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RevokeSessions {
+        request: Request {
+          profile_id: UUID,
+          sessions: Sessions::One(SESSION_ID),
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.revoke().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::not_found(
+        "DB: nothing found with given parameters"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_revoke_other_sessions() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      revoke_other_sessions: Some(RevokeOtherSessions {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        param_keep_session_id: Some(SESSION_ID.to_owned()),
+        fn_returning: |_, _| Ok(vec![OTHER_SESSION_ID.to_owned()]),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RevokeSessions {
+        request: Request {
+          profile_id: UUID,
+          sessions: Sessions::AllExcept(Some(SESSION_ID)),
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.revoke().await.unwrap();
+
+    assert_eq!(sut.state.revoked, vec![OTHER_SESSION_ID.to_owned()]);
+  }
+}
@@ -0,0 +1,41 @@
+use crate::domain::core::user::{repository::UserRepository, User};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, RevokeSessions<Request<'a>, Vec<String>>, R> {
+  /// Ids of the sessions ended.
+  pub fn response(self) -> Vec<String> {
+    self.state.revoked
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_revoked_sessions() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+    const SESSION_ID: &str = "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RevokeSessions {
+        request: Request {
+          profile_id: UUID,
+          ..Default::default()
+        },
+        revoked: vec![SESSION_ID.to_owned()],
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(sut, vec![SESSION_ID.to_owned()]);
+  }
+}
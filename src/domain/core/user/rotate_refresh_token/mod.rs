@@ -0,0 +1,61 @@
+use super::*;
+
+pub mod repository;
+pub mod responder;
+pub mod validator;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct TokenNotChecked;
+
+#[derive(Debug, PartialEq)]
+pub struct TokenChecked(pub(super) bool);
+
+#[derive(Default)]
+pub struct RotateRefreshToken<Req, Db, Check> {
+  request: Req,
+  db_data: Db,
+  token_checked: Check,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub token: &'a str,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn rotate_refresh_token(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, RotateRefreshToken<Request<'a>, NoDbData, TokenNotChecked>, R> {
+    User {
+      repository: self.repository,
+      state: RotateRefreshToken {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_rotate_refresh_token_build() {
+    const TOKEN: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).rotate_refresh_token(Request { token: TOKEN });
+
+    assert_eq!(sut.state.request, Request { token: TOKEN });
+  }
+}
@@ -0,0 +1,115 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::refresh_token::RefreshTokenData,
+  error::{AppError, Code},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, RotateRefreshToken<Request<'a>, NoDbData, TokenNotChecked>, R>
+{
+  pub async fn get_token(
+    self,
+  ) -> Result<
+    User<'a, RotateRefreshToken<Request<'a>, RefreshTokenData, TokenNotChecked>, R>,
+    AppError,
+  > {
+    let db_data = self
+      .repository
+      .find_refresh_token(self.state.request.token)
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::unauthenticated("invalid refresh token"),
+        _ => error,
+      })?;
+
+    Ok(User {
+      repository: self.repository,
+      state: RotateRefreshToken {
+        request: self.state.request,
+        db_data,
+        token_checked: self.state.token_checked,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, FindRefreshToken,
+  };
+
+  const TOKEN: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  #[tokio::test]
+  async fn test_get_token_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_refresh_token: Some(FindRefreshToken {
+        calls: 1,
+        param_token: TOKEN.to_string(),
+        fn_returning: |_| {
+          Ok(RefreshTokenData {
+            id: 1,
+            profile_id: PROFILE_ID.to_string(),
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RotateRefreshToken {
+        request: Request { token: TOKEN },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_token().await.unwrap();
+
+    assert_eq!(
+      sut.state.db_data,
+      RefreshTokenData {
+        id: 1,
+        profile_id: PROFILE_ID.to_string(),
+        ..Default::default()
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_get_nonexistent_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_refresh_token: Some(FindRefreshToken {
+        calls: 1,
+        param_token: TOKEN.to_string(),
+        fn_returning: |_| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RotateRefreshToken {
+        request: Request { token: TOKEN },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_token().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::unauthenticated("invalid refresh token"))
+    );
+  }
+}
@@ -0,0 +1,48 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::refresh_token::RefreshTokenData,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, RotateRefreshToken<Request<'a>, RefreshTokenData, TokenChecked>, R>
+{
+  pub fn response(self) -> RefreshTokenData {
+    self.state.db_data
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_consumed_token() {
+    const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RotateRefreshToken {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: RefreshTokenData {
+          id: 1,
+          profile_id: PROFILE_ID.to_string(),
+          ..Default::default()
+        },
+        token_checked: TokenChecked(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(sut.profile_id, PROFILE_ID);
+  }
+}
@@ -0,0 +1,252 @@
+use chrono::Utc;
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::refresh_token::RefreshTokenData,
+  error::AppError,
+};
+
+use super::*;
+
+const ACCOUNT_DISABLED: &str = "account disabled";
+
+type UserRotateStateIn<'a, R> =
+  User<'a, RotateRefreshToken<Request<'a>, RefreshTokenData, TokenNotChecked>, R>;
+type UserRotateStateOut<'a, R> =
+  User<'a, RotateRefreshToken<Request<'a>, RefreshTokenData, TokenChecked>, R>;
+
+impl<'a, R: UserRepository> UserRotateStateIn<'a, R> {
+  /// Consumes the refresh token. A token presented a second time means it
+  /// leaked, so the whole family is revoked to also kill the attacker's copy.
+  pub async fn check_token(self) -> Result<UserRotateStateOut<'a, R>, AppError> {
+    let token = &self.state.db_data;
+
+    if token.used {
+      self
+        .repository
+        .revoke_refresh_token_family(&token.family_id)
+        .await?;
+      return Err(AppError::unauthenticated("refresh token reuse detected"));
+    }
+
+    if token.revoked {
+      return Err(AppError::unauthenticated("refresh token revoked"));
+    }
+
+    if token.expires_at <= Utc::now().naive_utc() {
+      return Err(AppError::unauthenticated("refresh token expired"));
+    }
+
+    if token.account_disabled {
+      return Err(AppError::permission_denied(ACCOUNT_DISABLED));
+    }
+
+    if !self.repository.use_refresh_token(token.id).await? {
+      self
+        .repository
+        .revoke_refresh_token_family(&token.family_id)
+        .await?;
+      return Err(AppError::unauthenticated("refresh token reuse detected"));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: RotateRefreshToken {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        token_checked: TokenChecked(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Duration;
+
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, RevokeRefreshTokenFamily, UseRefreshToken,
+  };
+
+  const TOKEN: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+  const FAMILY_ID: &str = "1b9d6bcd-bbfd-4b2d-9b5d-ab8dfbbd4bed";
+
+  fn token_data() -> RefreshTokenData {
+    RefreshTokenData {
+      id: 1,
+      family_id: FAMILY_ID.to_string(),
+      expires_at: Utc::now().naive_utc() + Duration::minutes(10),
+      ..Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn test_check_valid_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_refresh_token: Some(UseRefreshToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(true),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RotateRefreshToken {
+        request: Request { token: TOKEN },
+        db_data: token_data(),
+        token_checked: TokenNotChecked,
+      },
+    };
+
+    let sut = user.check_token().await.unwrap();
+
+    assert_eq!(sut.state.token_checked, TokenChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_reused_token_revokes_family() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      revoke_refresh_token_family: Some(RevokeRefreshTokenFamily {
+        calls: 1,
+        param_family_id: FAMILY_ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RotateRefreshToken {
+        request: Request { token: TOKEN },
+        db_data: RefreshTokenData {
+          used: true,
+          ..token_data()
+        },
+        token_checked: TokenNotChecked,
+      },
+    };
+
+    let sut = user.check_token().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::unauthenticated("refresh token reuse detected"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_concurrently_used_token_revokes_family() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_refresh_token: Some(UseRefreshToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(false),
+      }),
+      revoke_refresh_token_family: Some(RevokeRefreshTokenFamily {
+        calls: 1,
+        param_family_id: FAMILY_ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RotateRefreshToken {
+        request: Request { token: TOKEN },
+        db_data: token_data(),
+        token_checked: TokenNotChecked,
+      },
+    };
+
+    let sut = user.check_token().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::unauthenticated("refresh token reuse detected"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_revoked_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RotateRefreshToken {
+        request: Request { token: TOKEN },
+        db_data: RefreshTokenData {
+          revoked: true,
+          ..token_data()
+        },
+        token_checked: TokenNotChecked,
+      },
+    };
+
+    let sut = user.check_token().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::unauthenticated("refresh token revoked"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_token_of_disabled_account() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_refresh_token: Some(UseRefreshToken {
+        calls: 0,
+        param_id: 1,
+        fn_returning: |_| Ok(true),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RotateRefreshToken {
+        request: Request { token: TOKEN },
+        db_data: RefreshTokenData {
+          account_disabled: true,
+          ..token_data()
+        },
+        token_checked: TokenNotChecked,
+      },
+    };
+
+    let sut = user.check_token().await.err();
+
+    assert_eq!(sut, Some(AppError::permission_denied(ACCOUNT_DISABLED)));
+  }
+
+  #[tokio::test]
+  async fn test_check_expired_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: RotateRefreshToken {
+        request: Request { token: TOKEN },
+        db_data: RefreshTokenData {
+          expires_at: Utc::now().naive_utc() - Duration::minutes(1),
+          ..token_data()
+        },
+        token_checked: TokenNotChecked,
+      },
+    };
+
+    let sut = user.check_token().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::unauthenticated("refresh token expired"))
+    );
+  }
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct SetAccountStatus<Req, Save> {
+  request: Req,
+  saved: Save,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub disabled: bool,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn set_account_status(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, SetAccountStatus<Request<'a>, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: SetAccountStatus {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_set_account_status_build() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).set_account_status(Request {
+      profile_id: UUID,
+      disabled: true,
+    });
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        profile_id: UUID,
+        disabled: true,
+      }
+    );
+  }
+}
@@ -0,0 +1,131 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, SetAccountStatus<Request<'a>, NotSaved>, R> {
+  /// Disabling an account also invalidates its access tokens and revokes its
+  /// refresh tokens in the same update, so no session outlives it.
+  pub async fn save(self) -> Result<User<'a, SetAccountStatus<Request<'a>, Saved>, R>, AppError> {
+    let request = &self.state.request;
+    let request_disabled = request.disabled;
+
+    self
+      .repository
+      .update_account_disabled(request.profile_id, request.disabled)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: SetAccountStatus {
+        request: self.state.request,
+        saved: Saved(request_disabled),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, UpdateAccountDisabled,
+  };
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  #[tokio::test]
+  async fn test_save_disabled() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      update_account_disabled: Some(UpdateAccountDisabled {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        param_disabled: true,
+        fn_returning: |_, _| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SetAccountStatus {
+        request: Request {
+          profile_id: UUID,
+          disabled: true,
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.save().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+
+  #[tokio::test]
+  async fn test_save_enabled() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      update_account_disabled: Some(UpdateAccountDisabled {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        param_disabled: false,
+        fn_returning: |_, _| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SetAccountStatus {
+        request: Request {
+          profile_id: UUID,
+          disabled: false,
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.save().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(false));
+  }
+
+  #[tokio::test]
+  async fn test_save_with_nonexistent_user() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      update_account_disabled: Some(UpdateAccountDisabled {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        param_disabled: true,
+        fn_returning: |_, _| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SetAccountStatus {
+        request: Request {
+          profile_id: UUID,
+          disabled: true,
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.save().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::not_found(
+        "DB: nothing found with given parameters"
+      ))
+    );
+  }
+}
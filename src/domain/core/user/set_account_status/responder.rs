@@ -0,0 +1,38 @@
+use crate::domain::core::user::{repository::UserRepository, User};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, SetAccountStatus<Request<'a>, Saved>, R> {
+  /// Returns whether the account is disabled now.
+  pub fn response(self) -> bool {
+    self.state.saved.0
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_disabled_account() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SetAccountStatus {
+        request: Request {
+          profile_id: UUID,
+          disabled: true,
+        },
+        saved: Saved(true),
+      },
+    };
+
+    assert!(user.response());
+  }
+}
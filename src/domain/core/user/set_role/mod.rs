@@ -0,0 +1,69 @@
+use super::*;
+
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct SetRole<Req, Save> {
+  request: Req,
+  saved: Save,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub role: &'a str,
+  pub granted: bool,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn set_role(self, request: Request<'a>) -> User<'a, SetRole<Request<'a>, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: SetRole {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    entities::role::ADMIN_ROLE,
+  };
+
+  use super::*;
+
+  #[test]
+  fn test_set_role_build() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).set_role(Request {
+      profile_id: UUID,
+      role: ADMIN_ROLE,
+      granted: true,
+    });
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        profile_id: UUID,
+        role: ADMIN_ROLE,
+        granted: true,
+      }
+    );
+  }
+}
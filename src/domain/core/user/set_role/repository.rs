@@ -0,0 +1,143 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, SetRole<Request<'a>, NotSaved>, R> {
+  /// Grants or revokes the role. A grant shows up in the next token issued,
+  /// a revocation invalidates the access tokens already issued.
+  pub async fn save(self) -> Result<User<'a, SetRole<Request<'a>, Saved>, R>, AppError> {
+    let request = &self.state.request;
+
+    let changed = if request.granted {
+      self
+        .repository
+        .grant_role(request.profile_id, request.role)
+        .await?
+    } else {
+      self
+        .repository
+        .revoke_role(request.profile_id, request.role)
+        .await?
+    };
+
+    Ok(User {
+      repository: self.repository,
+      state: SetRole {
+        request: self.state.request,
+        saved: Saved(changed),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{
+      build_mock_user_repository, Expectations, GrantRole, RevokeRole,
+    },
+    entities::role::ADMIN_ROLE,
+  };
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  #[tokio::test]
+  async fn test_save_granted() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      grant_role: Some(GrantRole {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        param_role: ADMIN_ROLE.to_owned(),
+        fn_returning: |_, _| Ok(true),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SetRole {
+        request: Request {
+          profile_id: UUID,
+          role: ADMIN_ROLE,
+          granted: true,
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.save().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+
+  #[tokio::test]
+  async fn test_save_revoked_without_role() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      revoke_role: Some(RevokeRole {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        param_role: ADMIN_ROLE.to_owned(),
+        fn_returning: |_, _| Ok(false),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SetRole {
+        request: Request {
+          profile_id: UUID,
+          role: ADMIN_ROLE,
+          granted: false,
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.save().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(false));
+  }
+
+  #[tokio::test]
+  async fn test_save_with_unknown_role() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      grant_role: Some(GrantRole {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        param_role: "unknown".to_owned(),
+        fn_returning: |_, _| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SetRole {
+        request: Request {
+          profile_id: UUID,
+          role: "unknown",
+          granted: true,
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.save().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::not_found(
+        "DB: nothing found with given parameters"
+      ))
+    );
+  }
+}
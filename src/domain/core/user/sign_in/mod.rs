@@ -1,10 +1,17 @@
+use chrono::{Duration, Utc};
+
 use super::repository::UserRepository;
 pub(super) use super::*;
-use crate::domain::entities::user::UserColumns;
+use crate::domain::{
+  entities::user::{UserColumns, UserData},
+  error::AppError,
+};
 pub mod repository;
 pub mod responder;
 pub mod validator;
 
+pub const ACCOUNT_LOCKED: &str = "account locked after too many failed sign-in attempts";
+
 #[derive(Default)]
 pub struct NoDbData;
 
@@ -21,17 +28,61 @@ pub struct SignIn<Req, Db, PwdCheck> {
   password_checked: PwdCheck,
 }
 
+/// Failed attempts allowed before the account is blocked, and for how long
+/// the block lasts before the next sign-in lifts it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Lockout {
+  pub max_failed_attempts: i32,
+  pub duration_min: i64,
+}
+
+impl Lockout {
+  /// Refuses a user blocked by failed attempts until `duration_min` has
+  /// passed since the block; a block without date is only lifted by hand.
+  /// Once the time is over the caller still lifts the block with
+  /// `unlock_user`.
+  pub fn check(&self, user: &UserData) -> Result<(), AppError> {
+    if !user.blocked_by_attempts {
+      return Ok(());
+    }
+
+    let lock_expired = user.blocked_at.is_some_and(|blocked_at| {
+      blocked_at + Duration::minutes(self.duration_min) <= Utc::now().naive_utc()
+    });
+    if !lock_expired {
+      return Err(AppError::locked(ACCOUNT_LOCKED));
+    }
+
+    Ok(())
+  }
+}
+
+impl Default for Lockout {
+  fn default() -> Self {
+    Self {
+      max_failed_attempts: 5,
+      duration_min: 15,
+    }
+  }
+}
+
 #[derive(Default, PartialEq, Debug)]
 pub struct Request<'a> {
   pub column: UserColumns<'a>,
   pub password: &'a str,
+  pub ip_address: Option<&'a str>,
+  pub user_agent: Option<&'a str>,
+  /// Rejects email sign-ins whose address has not been verified yet.
+  pub require_verified_email: bool,
+  /// Rejects telephone sign-ins whose number has not been verified yet.
+  pub require_verified_telephone: bool,
 }
 
 impl<'a, R: UserRepository> User<'a, NoState, R> {
   pub fn sign_in(
     self,
     request: Request<'a>,
-  ) -> User<SignIn<Request<'a>, NoDbData, PasswordNotChecked>, R> {
+  ) -> User<'a, SignIn<Request<'a>, NoDbData, PasswordNotChecked>, R> {
     User {
       repository: self.repository,
       state: SignIn {
@@ -66,4 +117,28 @@ mod tests {
       }
     );
   }
+
+  #[test]
+  fn test_lockout_check() {
+    let lockout = Lockout::default();
+    let blocked_at = |minutes_ago| UserData {
+      blocked_by_attempts: true,
+      blocked_at: Some(Utc::now().naive_utc() - Duration::minutes(minutes_ago)),
+      ..Default::default()
+    };
+
+    assert!(lockout.check(&UserData::default()).is_ok());
+    assert!(lockout.check(&blocked_at(16)).is_ok());
+    assert_eq!(
+      lockout.check(&blocked_at(5)),
+      Err(AppError::locked(ACCOUNT_LOCKED))
+    );
+    assert_eq!(
+      lockout.check(&UserData {
+        blocked_by_attempts: true,
+        ..Default::default()
+      }),
+      Err(AppError::locked(ACCOUNT_LOCKED))
+    );
+  }
 }
@@ -1,6 +1,8 @@
 use crate::domain::{
   core::user::repository::UserRepository, entities::user::UserData, error::AppError,
+  utilities::crypto::Crypto,
 };
+use log::error;
 
 use super::*;
 
@@ -26,12 +28,151 @@ impl<'a, R: UserRepository> User<'a, SignIn<Request<'a>, NoDbData, PasswordNotCh
   }
 }
 
+impl<'a, R: UserRepository> User<'a, SignIn<Request<'a>, UserData, PasswordChecked>, R> {
+  /// Hashes the password again when the stored hash is of an older algorithm
+  /// or parameters, now that it is known to be right. The sign-in is already
+  /// recorded by now, so a failed rehash is only logged and retried on the
+  /// next sign-in.
+  pub async fn rehash_password(mut self, crypto: &impl Crypto) -> Self {
+    if !crypto.needs_rehash(&self.state.db_data.password) {
+      return self;
+    }
+
+    let rehashed = async {
+      let hash = crypto.hash_password(self.state.request.password)?;
+      self
+        .repository
+        .rehash_password(&hash, &self.state.db_data.password, &self.state.db_data.id)
+        .await?;
+
+      Ok::<_, AppError>(hash)
+    }
+    .await;
+
+    match rehashed {
+      Ok(hash) => self.state.db_data.password = hash,
+      Err(error) => error!(
+        "password rehash of {} failed: {}",
+        self.state.db_data.id, error
+      ),
+    }
+
+    self
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::domain::core::user::mocks::repository::{
-    build_mock_user_repository, Expectations, FindUserBy,
+  use crate::domain::{
+    core::user::mocks::repository::{
+      build_mock_user_repository, Expectations, FindUserBy, RehashPassword,
+    },
+    utilities::crypto::MockCrypto,
   };
+  use mockall::predicate;
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const PASSWORD: &str = "n8dCo3qY60UuIiXt8zrSLWGXceduTgnO";
+  const BCRYPT_HASH: &str = "$2b$08$9HkY2TbPZ4sSgQtN8ZNPQ.d6L1nB3Rj9K3Lw1uVx2ZkfM3tJp5n9S";
+  const ARGON2_HASH: &str = "$argon2id$v=19$m=19456,t=2,p=1$c2FsdHNhbHQ$aGFzaGhhc2g";
+
+  fn user_with_checked_password<R: UserRepository>(
+    repository: &R,
+  ) -> User<'_, SignIn<Request<'_>, UserData, PasswordChecked>, R> {
+    User {
+      repository,
+      state: SignIn {
+        request: Request {
+          password: PASSWORD,
+          ..Default::default()
+        },
+        db_data: UserData {
+          id: UUID.to_string(),
+          password: BCRYPT_HASH.to_string(),
+          ..Default::default()
+        },
+        password_checked: PasswordChecked(true),
+      },
+    }
+  }
+
+  #[tokio::test]
+  async fn test_rehash_outdated_password() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      rehash_password: Some(RehashPassword {
+        param_password: ARGON2_HASH.to_string(),
+        param_verified_password: BCRYPT_HASH.to_string(),
+        param_profile_id: UUID.to_string(),
+        ..Default::default()
+      }),
+      ..Default::default()
+    });
+    let mut mock_crypto = MockCrypto::new();
+    mock_crypto
+      .expect_needs_rehash()
+      .with(predicate::eq(BCRYPT_HASH))
+      .times(1)
+      .return_const(true);
+    mock_crypto
+      .expect_hash_password()
+      .with(predicate::eq(PASSWORD))
+      .times(1)
+      .returning(|_| Ok(ARGON2_HASH.to_string()));
+
+    let sut = user_with_checked_password(&mock_repository)
+      .rehash_password(&mock_crypto)
+      .await;
+
+    assert_eq!(sut.state.db_data.password, ARGON2_HASH);
+  }
+
+  #[tokio::test]
+  async fn test_keep_sign_in_when_rehash_fails() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      rehash_password: Some(RehashPassword {
+        param_password: ARGON2_HASH.to_string(),
+        param_verified_password: BCRYPT_HASH.to_string(),
+        param_profile_id: UUID.to_string(),
+        fn_returning: |_, _, _| Err(AppError::not_found("user does not exist")),
+        ..Default::default()
+      }),
+      ..Default::default()
+    });
+    let mut mock_crypto = MockCrypto::new();
+    mock_crypto
+      .expect_needs_rehash()
+      .times(1)
+      .return_const(true);
+    mock_crypto
+      .expect_hash_password()
+      .times(1)
+      .returning(|_| Ok(ARGON2_HASH.to_string()));
+
+    let sut = user_with_checked_password(&mock_repository)
+      .rehash_password(&mock_crypto)
+      .await;
+
+    assert_eq!(sut.state.db_data.password, BCRYPT_HASH);
+  }
+
+  #[tokio::test]
+  async fn test_keep_current_password_hash() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mut mock_crypto = MockCrypto::new();
+    mock_crypto
+      .expect_needs_rehash()
+      .times(1)
+      .return_const(false);
+
+    let sut = user_with_checked_password(&mock_repository)
+      .rehash_password(&mock_crypto)
+      .await;
+
+    assert_eq!(sut.state.db_data.password, BCRYPT_HASH);
+  }
 
   #[tokio::test]
   async fn test_get_user_db_data_successfully() {
@@ -49,6 +190,7 @@ mod tests {
             name: NAME.to_string(),
             username: USERNAME.to_string(),
             password: PASSWORD.to_string(),
+            ..Default::default()
           })
         },
         param_column_with: UserColumns::Username(USERNAME),
@@ -59,6 +201,7 @@ mod tests {
     let mock_request = Request {
       column: UserColumns::Username(USERNAME),
       password: PASSWORD,
+      ..Default::default()
     };
 
     let user = User {
@@ -78,6 +221,7 @@ mod tests {
         name: NAME.to_string(),
         username: USERNAME.to_string(),
         password: PASSWORD.to_string(),
+        ..Default::default()
       }
     );
     assert_eq!(
@@ -85,6 +229,7 @@ mod tests {
       Request {
         column: UserColumns::Username(USERNAME),
         password: PASSWORD,
+        ..Default::default()
       },
       "Get user data failed"
     )
@@ -108,6 +253,7 @@ mod tests {
     let mock_request = Request {
       column: UserColumns::Username(USERNAME),
       password: PASSWORD,
+      ..Default::default()
     };
 
     let user = User {
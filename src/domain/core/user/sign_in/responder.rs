@@ -11,6 +11,7 @@ impl<'a, R: UserRepository> User<'a, SignIn<Request<'a>, UserData, PasswordCheck
       id: self.state.db_data.id,
       username: self.state.db_data.username,
       name: self.state.db_data.name,
+      mfa_enabled: self.state.db_data.mfa_enabled,
     }
   }
 }
@@ -57,7 +58,8 @@ mod tests {
       PublicUserData {
         id: UUID.to_string(),
         name: NAME.to_string(),
-        username: USERNAME.to_string()
+        username: USERNAME.to_string(),
+        mfa_enabled: false,
       }
     )
   }
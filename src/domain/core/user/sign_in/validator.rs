@@ -1,20 +1,143 @@
 use super::*;
 use crate::domain::{
-  core::user::repository::UserRepository, entities::user::UserData, error::AppError,
+  core::user::repository::UserRepository,
+  entities::{
+    sign_in_event::{NewSignInEvent, SignInMethod},
+    user::UserData,
+  },
+  error::AppError,
   utilities::crypto::Crypto,
 };
 
+const ACCOUNT_DISABLED: &str = "account disabled";
+const PASSWORD_RESET_REQUIRED: &str = "password reset required";
+const EMAIL_NOT_VERIFIED: &str = "email address not verified";
+const TELEPHONE_NOT_VERIFIED: &str = "telephone number not verified";
+
 impl<'a, R: UserRepository> User<'a, SignIn<Request<'a>, UserData, PasswordNotChecked>, R> {
-  pub fn check_password(
+  async fn record_sign_in(&self, succeeded: bool) -> Result<(), AppError> {
+    let request = &self.state.request;
+
+    self
+      .repository
+      .store_sign_in_event(&NewSignInEvent {
+        profile_id: self.state.db_data.id.clone(),
+        method: SignInMethod::try_from(&request.column)?,
+        succeeded,
+        ip_address: request.ip_address.map(str::to_string),
+        user_agent: request.user_agent.map(str::to_string),
+      })
+      .await
+  }
+
+  /// Blocks by failed attempts hold until `lockout.duration_min` has passed.
+  async fn check_lock(&self, lockout: &Lockout) -> Result<(), AppError> {
+    let db_data = &self.state.db_data;
+
+    if let Err(error) = lockout.check(db_data) {
+      self.record_sign_in(false).await?;
+      return Err(error);
+    }
+    if db_data.blocked_by_attempts {
+      self.repository.unlock_user(&db_data.id).await?;
+    }
+
+    Ok(())
+  }
+
+  /// A wrong password counts towards the lockout.
+  async fn check_secret(&self, cryto: &impl Crypto, lockout: &Lockout) -> Result<(), AppError> {
+    let db_data = &self.state.db_data;
+
+    if cryto.verify_password(&db_data.password, self.state.request.password)? {
+      return Ok(());
+    }
+
+    self.record_sign_in(false).await?;
+    let blocked = self
+      .repository
+      .register_failed_sign_in(&db_data.id, lockout.max_failed_attempts)
+      .await?;
+
+    if blocked {
+      return Err(AppError::locked(ACCOUNT_LOCKED));
+    }
+    Err(AppError::unauthenticated("invalid password"))
+  }
+
+  /// Only checked once the password is right, so the status is not
+  /// disclosed to whoever is guessing it.
+  async fn check_status(&self) -> Result<(), AppError> {
+    let db_data = &self.state.db_data;
+
+    let refused = if db_data.account_disabled {
+      Some(ACCOUNT_DISABLED)
+    } else if db_data.password_reset_required {
+      Some(PASSWORD_RESET_REQUIRED)
+    } else {
+      None
+    };
+    if let Some(message) = refused {
+      self.record_sign_in(false).await?;
+      return Err(AppError::permission_denied(message));
+    }
+
+    Ok(())
+  }
+
+  /// Signing in by an unverified email or telephone, when the request
+  /// requires it verified.
+  async fn check_verification(&self) -> Result<(), AppError> {
+    let request = &self.state.request;
+    let db_data = &self.state.db_data;
+
+    let unverified = match request.column {
+      UserColumns::Email(_) if request.require_verified_email && !db_data.email_checked => {
+        Some(EMAIL_NOT_VERIFIED)
+      }
+      UserColumns::Telephone(_)
+        if request.require_verified_telephone && !db_data.telephone_checked =>
+      {
+        Some(TELEPHONE_NOT_VERIFIED)
+      }
+      _ => None,
+    };
+    if let Some(message) = unverified {
+      self.record_sign_in(false).await?;
+      return Err(AppError::permission_denied(message));
+    }
+
+    Ok(())
+  }
+
+  /// Cancels a pending deletion and registers the sign-in, unless the second
+  /// factor still has to complete it.
+  async fn complete_sign_in(&self) -> Result<(), AppError> {
+    let db_data = &self.state.db_data;
+
+    if db_data.mfa_enabled {
+      return Ok(());
+    }
+
+    if db_data.requested_deletion {
+      self.repository.cancel_deletion(&db_data.id).await?;
+    }
+    self.repository.register_sign_in(&db_data.id).await?;
+    self.record_sign_in(true).await
+  }
+
+  /// Every failed attempt on an existing account is recorded as a sign-in
+  /// event.
+  pub async fn check_password(
     self,
     cryto: &'a impl Crypto,
+    lockout: &Lockout,
   ) -> Result<User<'a, SignIn<Request<'a>, UserData, PasswordChecked>, R>, AppError> {
-    let req_password = self.state.request.password;
-    let hash_password = &self.state.db_data.password;
-
-    if !cryto.verify_password(hash_password, req_password)? {
-      return Err(AppError::unauthenticated("invalid password"));
-    }
+    self.check_lock(lockout).await?;
+    self.check_secret(cryto, lockout).await?;
+    self.check_status().await?;
+    self.check_verification().await?;
+    self.complete_sign_in().await?;
 
     let user = User {
       repository: self.repository,
@@ -31,93 +154,514 @@ impl<'a, R: UserRepository> User<'a, SignIn<Request<'a>, UserData, PasswordNotCh
 
 #[cfg(test)]
 mod tests {
+  use chrono::{Duration, Utc};
   use mockall::predicate;
 
   use super::*;
   use crate::domain::{
-    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    core::user::mocks::repository::{
+      build_mock_user_repository, CancelDeletion, Expectations, RegisterFailedSignIn,
+      RegisterSignIn, StoreSignInEvent, UnlockUser,
+    },
     utilities::crypto::MockCrypto,
   };
 
-  #[test]
-  fn test_check_password_with_valid_password() {
-    const PASSWORD: &str = "123456789";
-    const HASH_PASSWORD: &str = "mg3824m1htv8913dxjrn9ui45g801q43tj";
+  const ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const PASSWORD: &str = "123456789";
+  const WRONG_PASSWORD: &str = "987654321";
+  const HASH_PASSWORD: &str = "mg3824m1htv8913dxjrn9ui45g801q43tj";
+  const USERNAME: &str = "john_doe";
+  const EMAIL: &str = "john_doe@email.com";
+  const TELEPHONE: &str = "+1 4152370800";
+  const IP_ADDRESS: &str = "203.0.113.7";
+  const USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64)";
+
+  fn mock_request(password: &'static str) -> Request<'static> {
+    Request {
+      column: UserColumns::Username(USERNAME),
+      password,
+      ip_address: Some(IP_ADDRESS),
+      user_agent: Some(USER_AGENT),
+      require_verified_email: false,
+      require_verified_telephone: false,
+    }
+  }
+
+  fn store_sign_in_event(succeeded: bool) -> StoreSignInEvent {
+    StoreSignInEvent {
+      calls: 1,
+      param_event: NewSignInEvent {
+        profile_id: ID.to_owned(),
+        method: SignInMethod::Username,
+        succeeded,
+        ip_address: Some(IP_ADDRESS.to_owned()),
+        user_agent: Some(USER_AGENT.to_owned()),
+      },
+      fn_returning: |_| Ok(()),
+    }
+  }
+
+  fn mock_crypto_verify(password: &'static str, valid: bool) -> MockCrypto {
+    let mut mock_crypto = MockCrypto::new();
+
+    mock_crypto
+      .expect_verify_password()
+      .times(1)
+      .with(predicate::eq(HASH_PASSWORD), predicate::eq(password))
+      .returning(move |_, _| Ok(valid));
+
+    mock_crypto
+  }
 
+  fn mock_db_data() -> UserData {
+    UserData {
+      id: ID.to_owned(),
+      password: HASH_PASSWORD.to_owned(),
+      ..Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn test_check_password_with_valid_password() {
     let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(true)),
+      register_sign_in: Some(RegisterSignIn {
+        calls: 1,
+        param_profile_id: ID.to_owned(),
+        fn_returning: |_| Ok(()),
+      }),
       ..Default::default()
     });
 
-    let mock_db_data = UserData {
-      password: HASH_PASSWORD.to_owned(),
+    let user = User {
+      repository: &mock_repository,
+      state: SignIn {
+        request: mock_request(PASSWORD),
+        db_data: mock_db_data(),
+        ..Default::default()
+      },
+    };
+
+    let mock_crypto = mock_crypto_verify(PASSWORD, true);
+
+    let sut = user
+      .check_password(&mock_crypto, &Lockout::default())
+      .await
+      .unwrap();
+
+    assert!(sut.state.password_checked.0);
+  }
+
+  #[tokio::test]
+  async fn test_check_password_with_wrong_password() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      register_failed_sign_in: Some(RegisterFailedSignIn {
+        calls: 1,
+        param_profile_id: ID.to_owned(),
+        param_max_failed_attempts: 5,
+        fn_returning: |_, _| Ok(false),
+      }),
       ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SignIn {
+        request: mock_request(WRONG_PASSWORD),
+        db_data: mock_db_data(),
+        ..Default::default()
+      },
     };
 
-    let mock_request = Request {
-      password: PASSWORD,
+    let mock_crypto = mock_crypto_verify(WRONG_PASSWORD, false);
+
+    let sut = user
+      .check_password(&mock_crypto, &Lockout::default())
+      .await
+      .err();
+
+    assert_eq!(sut, Some(AppError::unauthenticated("invalid password")));
+  }
+
+  #[tokio::test]
+  async fn test_check_password_with_wrong_password_reaching_max_attempts() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      register_failed_sign_in: Some(RegisterFailedSignIn {
+        calls: 1,
+        param_profile_id: ID.to_owned(),
+        param_max_failed_attempts: 3,
+        fn_returning: |_, _| Ok(true),
+      }),
       ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SignIn {
+        request: mock_request(WRONG_PASSWORD),
+        db_data: mock_db_data(),
+        ..Default::default()
+      },
     };
 
+    let mock_crypto = mock_crypto_verify(WRONG_PASSWORD, false);
+
+    let sut = user
+      .check_password(
+        &mock_crypto,
+        &Lockout {
+          max_failed_attempts: 3,
+          ..Default::default()
+        },
+      )
+      .await
+      .err();
+
+    assert_eq!(sut, Some(AppError::locked(ACCOUNT_LOCKED)));
+  }
+
+  #[tokio::test]
+  async fn test_check_password_with_blocked_user() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      ..Default::default()
+    });
+
     let user = User {
       repository: &mock_repository,
       state: SignIn {
-        request: mock_request,
-        db_data: mock_db_data,
+        request: mock_request(PASSWORD),
+        db_data: UserData {
+          blocked_by_attempts: true,
+          blocked_at: Some(Utc::now().naive_utc() - Duration::minutes(5)),
+          ..mock_db_data()
+        },
         ..Default::default()
       },
     };
 
-    let mut mock_crypto = MockCrypto::new();
+    let sut = user
+      .check_password(&MockCrypto::new(), &Lockout::default())
+      .await
+      .err();
 
-    mock_crypto
-      .expect_verify_password()
-      .times(1)
-      .with(predicate::eq(HASH_PASSWORD), predicate::eq(PASSWORD))
-      .returning(|_, _| Ok(true));
+    assert_eq!(sut, Some(AppError::locked(ACCOUNT_LOCKED)));
+  }
+
+  #[tokio::test]
+  async fn test_check_password_with_user_blocked_without_date() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SignIn {
+        request: mock_request(PASSWORD),
+        db_data: UserData {
+          blocked_by_attempts: true,
+          blocked_at: None,
+          ..mock_db_data()
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user
+      .check_password(&MockCrypto::new(), &Lockout::default())
+      .await
+      .err();
+
+    assert_eq!(sut, Some(AppError::locked(ACCOUNT_LOCKED)));
+  }
+
+  #[tokio::test]
+  async fn test_check_password_with_expired_block() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(true)),
+      unlock_user: Some(UnlockUser {
+        calls: 1,
+        param_profile_id: ID.to_owned(),
+        fn_returning: |_| Ok(()),
+      }),
+      register_sign_in: Some(RegisterSignIn {
+        calls: 1,
+        param_profile_id: ID.to_owned(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SignIn {
+        request: mock_request(PASSWORD),
+        db_data: UserData {
+          blocked_by_attempts: true,
+          blocked_at: Some(Utc::now().naive_utc() - Duration::minutes(16)),
+          ..mock_db_data()
+        },
+        ..Default::default()
+      },
+    };
+
+    let mock_crypto = mock_crypto_verify(PASSWORD, true);
 
-    let sut = user.check_password(&mock_crypto).unwrap();
+    let sut = user
+      .check_password(&mock_crypto, &Lockout::default())
+      .await
+      .unwrap();
 
     assert!(sut.state.password_checked.0);
   }
 
-  #[test]
-  fn test_check_password_with_wrong_password() {
-    const HASH_PASSWORD: &str = "mg3824m1htv8913dxjrn9ui45g801q43tj";
-    const WRONG_PASSWORD: &str = "987654321";
+  #[tokio::test]
+  async fn test_check_password_with_disabled_account() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SignIn {
+        request: mock_request(PASSWORD),
+        db_data: UserData {
+          account_disabled: true,
+          ..mock_db_data()
+        },
+        ..Default::default()
+      },
+    };
+
+    let mock_crypto = mock_crypto_verify(PASSWORD, true);
+
+    let sut = user
+      .check_password(&mock_crypto, &Lockout::default())
+      .await
+      .err();
 
+    assert_eq!(sut, Some(AppError::permission_denied(ACCOUNT_DISABLED)));
+  }
+
+  #[tokio::test]
+  async fn test_check_password_with_password_reset_required() {
     let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(false)),
       ..Default::default()
     });
 
-    let mock_db_data = UserData {
-      password: HASH_PASSWORD.to_owned(),
+    let user = User {
+      repository: &mock_repository,
+      state: SignIn {
+        request: mock_request(PASSWORD),
+        db_data: UserData {
+          password_reset_required: true,
+          ..mock_db_data()
+        },
+        ..Default::default()
+      },
+    };
+
+    let mock_crypto = mock_crypto_verify(PASSWORD, true);
+
+    let sut = user
+      .check_password(&mock_crypto, &Lockout::default())
+      .await
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::permission_denied(PASSWORD_RESET_REQUIRED))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_password_cancels_requested_deletion() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(true)),
+      register_sign_in: Some(RegisterSignIn {
+        calls: 1,
+        param_profile_id: ID.to_owned(),
+        fn_returning: |_| Ok(()),
+      }),
+      cancel_deletion: Some(CancelDeletion {
+        calls: 1,
+        param_profile_id: ID.to_owned(),
+        fn_returning: |_| Ok(()),
+      }),
       ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SignIn {
+        request: mock_request(PASSWORD),
+        db_data: UserData {
+          requested_deletion: true,
+          ..mock_db_data()
+        },
+        ..Default::default()
+      },
     };
 
-    let mock_request = Request {
-      password: WRONG_PASSWORD,
+    let mock_crypto = mock_crypto_verify(PASSWORD, true);
+
+    let sut = user
+      .check_password(&mock_crypto, &Lockout::default())
+      .await
+      .unwrap();
+
+    assert!(sut.state.password_checked.0);
+  }
+
+  #[tokio::test]
+  async fn test_check_password_with_mfa_enabled_leaves_sign_in_to_second_factor() {
+    let mock_repository = build_mock_user_repository(Expectations {
       ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SignIn {
+        request: mock_request(PASSWORD),
+        db_data: UserData {
+          mfa_enabled: true,
+          requested_deletion: true,
+          ..mock_db_data()
+        },
+        ..Default::default()
+      },
     };
 
+    let mock_crypto = mock_crypto_verify(PASSWORD, true);
+
+    let sut = user
+      .check_password(&mock_crypto, &Lockout::default())
+      .await
+      .unwrap();
+
+    assert!(sut.state.password_checked.0);
+  }
+
+  #[tokio::test]
+  async fn test_check_password_with_unverified_email() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(StoreSignInEvent {
+        param_event: NewSignInEvent {
+          method: SignInMethod::Email,
+          ..store_sign_in_event(false).param_event
+        },
+        ..store_sign_in_event(false)
+      }),
+      ..Default::default()
+    });
+
     let user = User {
       repository: &mock_repository,
       state: SignIn {
-        request: mock_request,
-        db_data: mock_db_data,
+        request: Request {
+          column: UserColumns::Email(EMAIL),
+          require_verified_email: true,
+          ..mock_request(PASSWORD)
+        },
+        db_data: UserData {
+          email_checked: false,
+          ..mock_db_data()
+        },
         ..Default::default()
       },
     };
 
-    let mut mock_crypto = MockCrypto::new();
+    let mock_crypto = mock_crypto_verify(PASSWORD, true);
 
-    mock_crypto
-      .expect_verify_password()
-      .times(1)
-      .with(predicate::eq(HASH_PASSWORD), predicate::eq(WRONG_PASSWORD))
-      .returning(|_, _| Ok(false));
+    let sut = user
+      .check_password(&mock_crypto, &Lockout::default())
+      .await
+      .err();
 
-    let sut = user.check_password(&mock_crypto).err();
+    assert_eq!(sut, Some(AppError::permission_denied(EMAIL_NOT_VERIFIED)));
+  }
 
-    assert_eq!(sut, Some(AppError::unauthenticated("invalid password")));
+  #[tokio::test]
+  async fn test_check_password_unverified_email_ignored_for_username() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(true)),
+      register_sign_in: Some(RegisterSignIn {
+        calls: 1,
+        param_profile_id: ID.to_owned(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SignIn {
+        request: Request {
+          require_verified_email: true,
+          ..mock_request(PASSWORD)
+        },
+        db_data: UserData {
+          email_checked: false,
+          ..mock_db_data()
+        },
+        ..Default::default()
+      },
+    };
+
+    let mock_crypto = mock_crypto_verify(PASSWORD, true);
+
+    let sut = user
+      .check_password(&mock_crypto, &Lockout::default())
+      .await
+      .unwrap();
+
+    assert!(sut.state.password_checked.0);
+  }
+
+  #[tokio::test]
+  async fn test_check_password_with_unverified_telephone() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(StoreSignInEvent {
+        param_event: NewSignInEvent {
+          method: SignInMethod::Telephone,
+          ..store_sign_in_event(false).param_event
+        },
+        ..store_sign_in_event(false)
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: SignIn {
+        request: Request {
+          column: UserColumns::Telephone(TELEPHONE),
+          require_verified_telephone: true,
+          ..mock_request(PASSWORD)
+        },
+        db_data: UserData {
+          telephone_checked: false,
+          ..mock_db_data()
+        },
+        ..Default::default()
+      },
+    };
+
+    let mock_crypto = mock_crypto_verify(PASSWORD, true);
+
+    let sut = user
+      .check_password(&mock_crypto, &Lockout::default())
+      .await
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::permission_denied(TELEPHONE_NOT_VERIFIED))
+    );
   }
 }
@@ -23,7 +23,7 @@ impl<'a, R: UserRepository> User<'a, NoState, R> {
   pub fn sign_up(
     self,
     request: Request<'a, NotId, Password>,
-  ) -> User<SignUp<Request<'a, NotId, Password>, NotSaved>, R> {
+  ) -> User<'a, SignUp<Request<'a, NotId, Password>, NotSaved>, R> {
     User {
       repository: self.repository,
       state: SignUp {
@@ -0,0 +1,80 @@
+use chrono::{Duration, Utc};
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::external_identity::NewExternalSignIn,
+  utilities::id_generator::IDGenerator,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, StartExternalSignIn<Request<'a>, NoSignIn, NotSaved>, R> {
+  /// The state ties the callback to this browser, the verifier the code to
+  /// this sign-in and the nonce the ID token to it.
+  pub fn generate(
+    self,
+    id_generator: &impl IDGenerator,
+  ) -> User<'a, StartExternalSignIn<Request<'a>, NewExternalSignIn, NotSaved>, R> {
+    let request = &self.state.request;
+    let sign_in = NewExternalSignIn {
+      state: id_generator.new_secret(),
+      provider: request.provider.to_string(),
+      code_verifier: id_generator.new_secret(),
+      nonce: id_generator.new_secret(),
+      expires_at: Utc::now().naive_utc() + Duration::minutes(request.expires_in_min),
+    };
+
+    User {
+      repository: self.repository,
+      state: StartExternalSignIn {
+        request: self.state.request,
+        sign_in,
+        saved: self.state.saved,
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    utilities::id_generator::MockIDGenerator,
+  };
+
+  use super::*;
+
+  const SECRET: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+
+  #[test]
+  fn test_generate_sign_in() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let mut mock_id_generator = MockIDGenerator::new();
+    mock_id_generator
+      .expect_new_secret()
+      .times(3)
+      .returning(|| SECRET.to_string());
+
+    let user = User {
+      repository: &mock_repository,
+      state: StartExternalSignIn {
+        request: Request {
+          provider: "acme",
+          expires_in_min: 10,
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.generate(&mock_id_generator);
+
+    assert_eq!(sut.state.sign_in.state, SECRET);
+    assert_eq!(sut.state.sign_in.provider, "acme");
+    assert_eq!(sut.state.sign_in.code_verifier, SECRET);
+    assert_eq!(sut.state.sign_in.nonce, SECRET);
+    assert!(sut.state.sign_in.expires_at > Utc::now().naive_utc() + Duration::minutes(9));
+  }
+}
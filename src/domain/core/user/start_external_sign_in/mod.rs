@@ -0,0 +1,69 @@
+use super::*;
+
+pub mod generator;
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NoSignIn;
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct StartExternalSignIn<Req, SignIn, Save> {
+  request: Req,
+  sign_in: SignIn,
+  saved: Save,
+}
+
+/// Redirection of the user to the authorization endpoint of `provider`.
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub provider: &'a str,
+  pub expires_in_min: i64,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn start_external_sign_in(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, StartExternalSignIn<Request<'a>, NoSignIn, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: StartExternalSignIn {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_start_external_sign_in_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).start_external_sign_in(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
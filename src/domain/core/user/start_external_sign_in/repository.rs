@@ -0,0 +1,77 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::external_identity::NewExternalSignIn,
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, StartExternalSignIn<Request<'a>, NewExternalSignIn, NotSaved>, R>
+{
+  pub async fn store(
+    self,
+  ) -> Result<User<'a, StartExternalSignIn<Request<'a>, NewExternalSignIn, Saved>, R>, AppError> {
+    self
+      .repository
+      .store_external_sign_in(&self.state.sign_in)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: StartExternalSignIn {
+        request: self.state.request,
+        sign_in: self.state.sign_in,
+        saved: Saved(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, StoreExternalSignIn,
+  };
+
+  const STATE: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+  const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+  const NONCE: &str = "Qp8sX2nV5bL0cR7mK4jH1gF9dS6aZ3wE8tY5uI2oP0l";
+
+  #[tokio::test]
+  async fn test_store_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_external_sign_in: Some(StoreExternalSignIn {
+        calls: 1,
+        param_state: STATE.to_string(),
+        param_provider: String::from("acme"),
+        param_code_verifier: CODE_VERIFIER.to_string(),
+        param_nonce: NONCE.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: StartExternalSignIn {
+        request: Request {
+          ..Default::default()
+        },
+        sign_in: NewExternalSignIn {
+          state: STATE.to_string(),
+          provider: String::from("acme"),
+          code_verifier: CODE_VERIFIER.to_string(),
+          nonce: NONCE.to_string(),
+          ..Default::default()
+        },
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.store().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+}
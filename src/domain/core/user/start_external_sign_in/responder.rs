@@ -0,0 +1,65 @@
+use crate::domain::{
+  core::user::{
+    exchange_authorization_code::validator::pkce_challenge, repository::UserRepository, User,
+  },
+  entities::external_identity::{ExternalSignInStart, NewExternalSignIn},
+  utilities::primitives::Primitives,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, StartExternalSignIn<Request<'a>, NewExternalSignIn, Saved>, R>
+{
+  /// Only the challenge of the verifier leaves the server.
+  pub fn response(self, primitives: &impl Primitives) -> ExternalSignInStart {
+    let sign_in = self.state.sign_in;
+
+    ExternalSignInStart {
+      code_challenge: pkce_challenge(primitives, &sign_in.code_verifier),
+      state: sign_in.state,
+      nonce: sign_in.nonce,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::{
+    pkce::{mock_sha256, CODE_CHALLENGE, CODE_VERIFIER},
+    repository::{build_mock_user_repository, Expectations},
+  };
+
+  #[test]
+  fn test_response_with_challenge() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: StartExternalSignIn {
+        request: Request {
+          ..Default::default()
+        },
+        sign_in: NewExternalSignIn {
+          state: String::from("state"),
+          code_verifier: CODE_VERIFIER.to_string(),
+          nonce: String::from("nonce"),
+          ..Default::default()
+        },
+        saved: Saved(true),
+      },
+    };
+
+    assert_eq!(
+      user.response(&mock_sha256()),
+      ExternalSignInStart {
+        state: String::from("state"),
+        code_challenge: CODE_CHALLENGE.to_string(),
+        nonce: String::from("nonce"),
+      }
+    );
+  }
+}
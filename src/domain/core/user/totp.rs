@@ -0,0 +1,118 @@
+use chrono::Utc;
+
+use crate::domain::{error::AppError, utilities::primitives::Primitives};
+
+/// Seconds each code stays valid, the period authenticator apps assume.
+pub const PERIOD_SEC: i64 = 30;
+
+pub fn current_step() -> i64 {
+  Utc::now().timestamp() / PERIOD_SEC
+}
+
+/// Time step whose code is `code`, looking `drift_steps` around `now_step` to
+/// tolerate clock drift. Steps up to `last_used_step` are skipped so a code
+/// that was already accepted cannot be replayed.
+pub(super) fn find_step(
+  primitives: &impl Primitives,
+  secret: &str,
+  code: &str,
+  now_step: i64,
+  drift_steps: i64,
+  last_used_step: Option<i64>,
+) -> Result<Option<i64>, AppError> {
+  for step in (now_step - drift_steps)..=(now_step + drift_steps) {
+    if last_used_step.is_some_and(|last_used_step| step <= last_used_step) {
+      continue;
+    }
+    if primitives.totp_code(secret, step)? == code {
+      return Ok(Some(step));
+    }
+  }
+
+  Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+  use mockall::predicate;
+
+  use super::*;
+  use crate::domain::utilities::primitives::MockPrimitives;
+
+  const SECRET: &str = "JBSWY3DPEHPK3PXP";
+  const CODE: &str = "287082";
+  const NOW_STEP: i64 = 56_666_666;
+
+  fn mock_primitives_code_at(code_step: i64) -> MockPrimitives {
+    let mut mock_primitives = MockPrimitives::new();
+
+    mock_primitives
+      .expect_totp_code()
+      .with(predicate::eq(SECRET), predicate::always())
+      .returning(move |_, step| {
+        Ok(if step == code_step {
+          CODE.to_string()
+        } else {
+          String::from("000000")
+        })
+      });
+
+    mock_primitives
+  }
+
+  #[test]
+  fn test_find_step_of_current_code() {
+    let sut = find_step(
+      &mock_primitives_code_at(NOW_STEP),
+      SECRET,
+      CODE,
+      NOW_STEP,
+      1,
+      None,
+    );
+
+    assert_eq!(sut, Ok(Some(NOW_STEP)));
+  }
+
+  #[test]
+  fn test_find_step_within_drift() {
+    let sut = find_step(
+      &mock_primitives_code_at(NOW_STEP - 1),
+      SECRET,
+      CODE,
+      NOW_STEP,
+      1,
+      None,
+    );
+
+    assert_eq!(sut, Ok(Some(NOW_STEP - 1)));
+  }
+
+  #[test]
+  fn test_find_step_out_of_drift() {
+    let sut = find_step(
+      &mock_primitives_code_at(NOW_STEP - 2),
+      SECRET,
+      CODE,
+      NOW_STEP,
+      1,
+      None,
+    );
+
+    assert_eq!(sut, Ok(None));
+  }
+
+  #[test]
+  fn test_find_step_skips_used_steps() {
+    let sut = find_step(
+      &mock_primitives_code_at(NOW_STEP),
+      SECRET,
+      CODE,
+      NOW_STEP,
+      1,
+      Some(NOW_STEP),
+    );
+
+    assert_eq!(sut, Ok(None));
+  }
+}
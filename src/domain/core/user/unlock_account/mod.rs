@@ -0,0 +1,42 @@
+use super::*;
+
+pub mod repository;
+
+#[derive(Default)]
+pub struct UnlockAccount<Req> {
+  request: Req,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn unlock_account(self, request: Request<'a>) -> User<'a, UnlockAccount<Request<'a>>, R> {
+    User {
+      repository: self.repository,
+      state: UnlockAccount { request },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_unlock_account_build() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).unlock_account(Request { profile_id: UUID });
+
+    assert_eq!(sut.state.request, Request { profile_id: UUID });
+  }
+}
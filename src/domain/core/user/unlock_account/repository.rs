@@ -0,0 +1,80 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, UnlockAccount<Request<'a>>, R> {
+  /// Lifts the lockout after too many failed sign-ins and clears the count,
+  /// so the account gets the full number of attempts again.
+  pub async fn save(self) -> Result<(), AppError> {
+    self
+      .repository
+      .unlock_user(self.state.request.profile_id)
+      .await
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, UnlockUser,
+  };
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  #[tokio::test]
+  async fn test_save_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      unlock_user: Some(UnlockUser {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: UnlockAccount {
+        request: Request { profile_id: UUID },
+      },
+    };
+
+    assert_eq!(user.save().await, Ok(()));
+  }
+
+  #[tokio::test]
+  async fn test_save_with_nonexistent_user() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      unlock_user: Some(UnlockUser {
+        calls: 1,
+        param_profile_id: UUID.to_owned(),
+        fn_returning: |_| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: UnlockAccount {
+        request: Request { profile_id: UUID },
+      },
+    };
+
+    let sut = user.save().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::not_found(
+        "DB: nothing found with given parameters"
+      ))
+    );
+  }
+}
@@ -0,0 +1,81 @@
+use super::*;
+use chrono::NaiveDate;
+
+pub mod repository;
+pub mod responder;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct UpdateSaved;
+
+#[derive(Default)]
+pub struct UpdateProfile<Req, Save, Db> {
+  request: Req,
+  saved: Save,
+  db_data: Db,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn update_profile(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, UpdateProfile<Request<'a>, NotSaved, NoDbData>, R> {
+    User {
+      repository: self.repository,
+      state: UpdateProfile {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct Address<'a> {
+  pub street: &'a str,
+  pub neighborhood: &'a str,
+  pub city_id: i32,
+  pub postal_code: i32,
+}
+
+#[derive(Default, Debug, PartialEq, Clone)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub name: Option<&'a str>,
+  pub birth_date: Option<NaiveDate>,
+  pub gender_id: Option<i32>,
+  pub address: Option<Address<'a>>,
+  pub email_address: Option<&'a str>,
+  pub telephone_number: Option<&'a str>,
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_update_profile_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).update_profile(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -0,0 +1,163 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::user::{FullProfile, UserColumns},
+  error::AppError,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, UpdateProfile<Request<'a>, NotSaved, NoDbData>, R> {
+  pub async fn save(
+    self,
+  ) -> Result<User<'a, UpdateProfile<Request<'a>, UpdateSaved, NoDbData>, R>, AppError> {
+    self.repository.update_profile(&self.state.request).await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: UpdateProfile {
+        request: self.state.request,
+        saved: UpdateSaved,
+        db_data: self.state.db_data,
+      },
+    })
+  }
+}
+
+impl<'a, R: UserRepository> User<'a, UpdateProfile<Request<'a>, UpdateSaved, NoDbData>, R> {
+  pub async fn find_profile(
+    self,
+  ) -> Result<User<'a, UpdateProfile<Request<'a>, UpdateSaved, FullProfile>, R>, AppError> {
+    let db_data = self
+      .repository
+      .find_full_profile_by(&UserColumns::Id(self.state.request.profile_id))
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: UpdateProfile {
+        request: self.state.request,
+        saved: self.state.saved,
+        db_data,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, FindFullProfileBy,
+    UpdateProfile as UpdateProfileExpectation,
+  };
+
+  const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const NAME: &str = "john doe";
+
+  #[tokio::test]
+  async fn test_save_update_successfully() {
+    let mock_request = Request {
+      profile_id: UUID,
+      name: Some(NAME),
+      ..Default::default()
+    };
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      update_profile: Some(UpdateProfileExpectation {
+        calls: 1,
+        param_request: mock_request.clone(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: UpdateProfile {
+        request: mock_request,
+        saved: NotSaved,
+        db_data: NoDbData,
+      },
+    };
+
+    let sut = user.save().await.unwrap();
+
+    assert_eq!(sut.state.saved, UpdateSaved)
+  }
+
+  #[tokio::test]
+  async fn test_save_update_failed() {
+    const DB_ERROR_MESSAGE: &str = "Some error in the database";
+
+    let mock_request = Request {
+      profile_id: UUID,
+      name: Some(NAME),
+      ..Default::default()
+    };
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      update_profile: Some(UpdateProfileExpectation {
+        calls: 1,
+        param_request: mock_request.clone(),
+        fn_returning: |_| Err(AppError::database_error(DB_ERROR_MESSAGE)),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: UpdateProfile {
+        request: mock_request,
+        saved: NotSaved,
+        db_data: NoDbData,
+      },
+    };
+
+    let sut = user.save().await.err();
+
+    assert_eq!(sut, Some(AppError::database_error(DB_ERROR_MESSAGE)))
+  }
+
+  #[tokio::test]
+  async fn test_find_updated_profile_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_full_profile_by: Some(FindFullProfileBy {
+        calls: 1,
+        param_column_with: UserColumns::Id(UUID),
+        fn_returning: |_| {
+          Ok(FullProfile {
+            id: UUID.to_string(),
+            name: NAME.to_string(),
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: UpdateProfile {
+        request: Request {
+          profile_id: UUID,
+          name: Some(NAME),
+          ..Default::default()
+        },
+        saved: UpdateSaved,
+        db_data: NoDbData,
+      },
+    };
+
+    let sut = user.find_profile().await.unwrap();
+
+    assert_eq!(
+      sut.state.db_data,
+      FullProfile {
+        id: UUID.to_string(),
+        name: NAME.to_string(),
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -0,0 +1,56 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::user::FullProfile,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, UpdateProfile<Request<'a>, UpdateSaved, FullProfile>, R> {
+  pub fn response(self) -> FullProfile {
+    self.state.db_data
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_updated_data() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+    const NAME: &str = "john doe";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: UpdateProfile {
+        request: Request {
+          profile_id: UUID,
+          name: Some(NAME),
+          ..Default::default()
+        },
+        saved: UpdateSaved,
+        db_data: FullProfile {
+          id: UUID.to_string(),
+          name: NAME.to_string(),
+          ..Default::default()
+        },
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(
+      sut,
+      FullProfile {
+        id: UUID.to_string(),
+        name: NAME.to_string(),
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -0,0 +1,68 @@
+use super::*;
+
+pub mod repository;
+pub mod responder;
+pub mod validator;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct TokenNotChecked;
+
+#[derive(Debug, PartialEq)]
+pub struct TokenChecked(pub(super) bool);
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct VerifyEmail<Req, Db, Check, Save> {
+  request: Req,
+  db_data: Db,
+  token_checked: Check,
+  saved: Save,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub token: &'a str,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn verify_email(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, VerifyEmail<Request<'a>, NoDbData, TokenNotChecked, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: VerifyEmail {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_verify_email_build() {
+    const TOKEN: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let sut = User::new(&mock_repository).verify_email(Request { token: TOKEN });
+
+    assert_eq!(sut.state.request, Request { token: TOKEN });
+  }
+}
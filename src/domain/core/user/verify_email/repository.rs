@@ -0,0 +1,220 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::{VerificationPurpose, VerificationTokenData},
+  error::{AppError, Code},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, VerifyEmail<Request<'a>, NoDbData, TokenNotChecked, NotSaved>, R>
+{
+  pub async fn get_token(
+    self,
+  ) -> Result<
+    User<'a, VerifyEmail<Request<'a>, VerificationTokenData, TokenNotChecked, NotSaved>, R>,
+    AppError,
+  > {
+    let db_data = self
+      .repository
+      .find_verification_token(self.state.request.token, VerificationPurpose::Email)
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::invalid_argument("invalid verification token"),
+        _ => error,
+      })?;
+
+    Ok(User {
+      repository: self.repository,
+      state: VerifyEmail {
+        request: self.state.request,
+        db_data,
+        token_checked: self.state.token_checked,
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+impl<'a, R: UserRepository>
+  User<'a, VerifyEmail<Request<'a>, VerificationTokenData, TokenChecked, NotSaved>, R>
+{
+  pub async fn save(
+    self,
+  ) -> Result<
+    User<'a, VerifyEmail<Request<'a>, VerificationTokenData, TokenChecked, Saved>, R>,
+    AppError,
+  > {
+    let token = &self.state.db_data;
+
+    self
+      .repository
+      .check_email(&token.profile_id, &token.target)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: VerifyEmail {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        token_checked: self.state.token_checked,
+        saved: Saved(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, CheckEmail, Expectations, FindVerificationToken,
+  };
+
+  const TOKEN: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const EMAIL: &str = "user@email.com";
+
+  #[tokio::test]
+  async fn test_get_token_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_verification_token: Some(FindVerificationToken {
+        calls: 1,
+        param_token: TOKEN.to_string(),
+        param_purpose: VerificationPurpose::Email,
+        fn_returning: |_, _| {
+          Ok(VerificationTokenData {
+            id: 1,
+            profile_id: PROFILE_ID.to_string(),
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyEmail {
+        request: Request { token: TOKEN },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_token().await.unwrap();
+
+    assert_eq!(
+      sut.state.db_data,
+      VerificationTokenData {
+        id: 1,
+        profile_id: PROFILE_ID.to_string(),
+        ..Default::default()
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_get_nonexistent_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_verification_token: Some(FindVerificationToken {
+        calls: 1,
+        param_token: TOKEN.to_string(),
+        param_purpose: VerificationPurpose::Email,
+        fn_returning: |_, _| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyEmail {
+        request: Request { token: TOKEN },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_token().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("invalid verification token"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_save_checks_email() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      check_email: Some(CheckEmail {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_address: EMAIL.to_string(),
+        fn_returning: |_, _| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyEmail {
+        request: Request { token: TOKEN },
+        db_data: VerificationTokenData {
+          id: 1,
+          profile_id: PROFILE_ID.to_string(),
+          target: EMAIL.to_string(),
+          ..Default::default()
+        },
+        token_checked: TokenChecked(true),
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.save().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+
+  #[tokio::test]
+  async fn test_save_email_no_longer_in_profile() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      check_email: Some(CheckEmail {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_address: EMAIL.to_string(),
+        fn_returning: |_, _| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyEmail {
+        request: Request { token: TOKEN },
+        db_data: VerificationTokenData {
+          id: 1,
+          profile_id: PROFILE_ID.to_string(),
+          target: EMAIL.to_string(),
+          ..Default::default()
+        },
+        token_checked: TokenChecked(true),
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.save().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::not_found(
+        "DB: nothing found with given parameters"
+      ))
+    );
+  }
+}
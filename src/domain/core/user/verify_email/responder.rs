@@ -0,0 +1,49 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::VerificationTokenData,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, VerifyEmail<Request<'a>, VerificationTokenData, TokenChecked, Saved>, R>
+{
+  /// Returns the address that has just been verified.
+  pub fn response(self) -> String {
+    self.state.db_data.target
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_verified_address() {
+    const EMAIL: &str = "user@email.com";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyEmail {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: VerificationTokenData {
+          target: EMAIL.to_string(),
+          ..Default::default()
+        },
+        token_checked: TokenChecked(true),
+        saved: Saved(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(sut, EMAIL);
+  }
+}
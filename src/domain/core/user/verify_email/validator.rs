@@ -0,0 +1,180 @@
+use chrono::Utc;
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::VerificationTokenData,
+  error::AppError,
+};
+
+use super::*;
+
+type UserVerifyStateIn<'a, R> =
+  User<'a, VerifyEmail<Request<'a>, VerificationTokenData, TokenNotChecked, NotSaved>, R>;
+type UserVerifyStateOut<'a, R> =
+  User<'a, VerifyEmail<Request<'a>, VerificationTokenData, TokenChecked, NotSaved>, R>;
+
+impl<'a, R: UserRepository> UserVerifyStateIn<'a, R> {
+  /// Consumes the verification token, so a link can only be redeemed once.
+  pub async fn check_token(self) -> Result<UserVerifyStateOut<'a, R>, AppError> {
+    let token = &self.state.db_data;
+
+    if token.used {
+      return Err(AppError::invalid_argument(
+        "verification token already used",
+      ));
+    }
+
+    if token.expires_at <= Utc::now().naive_utc() {
+      return Err(AppError::invalid_argument("verification token expired"));
+    }
+
+    if !self.repository.use_verification_token(token.id).await? {
+      return Err(AppError::invalid_argument(
+        "verification token already used",
+      ));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: VerifyEmail {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        token_checked: TokenChecked(true),
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Duration;
+
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, Expectations, UseVerificationToken,
+  };
+
+  const TOKEN: &str = "kT3bO0pLZ1N8Gq5a8vYc0wZf6r1nH2sD9mXuE4iJ7lQ";
+
+  fn token_data() -> VerificationTokenData {
+    VerificationTokenData {
+      id: 1,
+      expires_at: Utc::now().naive_utc() + Duration::minutes(10),
+      ..Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn test_check_valid_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_verification_token: Some(UseVerificationToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(true),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyEmail {
+        request: Request { token: TOKEN },
+        db_data: token_data(),
+        token_checked: TokenNotChecked,
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.check_token().await.unwrap();
+
+    assert_eq!(sut.state.token_checked, TokenChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_used_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyEmail {
+        request: Request { token: TOKEN },
+        db_data: VerificationTokenData {
+          used: true,
+          ..token_data()
+        },
+        token_checked: TokenNotChecked,
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.check_token().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "verification token already used"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_concurrently_used_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_verification_token: Some(UseVerificationToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(false),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyEmail {
+        request: Request { token: TOKEN },
+        db_data: token_data(),
+        token_checked: TokenNotChecked,
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.check_token().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "verification token already used"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_expired_token() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyEmail {
+        request: Request { token: TOKEN },
+        db_data: VerificationTokenData {
+          expires_at: Utc::now().naive_utc() - Duration::minutes(1),
+          ..token_data()
+        },
+        token_checked: TokenNotChecked,
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.check_token().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument("verification token expired"))
+    );
+  }
+}
@@ -0,0 +1,85 @@
+use super::*;
+use crate::domain::entities::{totp::TotpAuthenticatorData, user::UserData};
+
+pub mod repository;
+pub mod responder;
+pub mod validator;
+
+pub use super::sign_in::Lockout;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default, Debug, PartialEq)]
+pub struct MfaData {
+  pub user: UserData,
+  pub authenticator: TotpAuthenticatorData,
+}
+
+#[derive(Default)]
+pub struct CodeNotChecked;
+
+#[derive(Debug, PartialEq)]
+pub struct CodeChecked(pub(super) bool);
+
+#[derive(Default)]
+pub struct VerifyMfa<Req, Db, Check> {
+  request: Req,
+  db_data: Db,
+  code_checked: Check,
+}
+
+/// Second step of a sign-in, either `code` from the authenticator or one of
+/// the recovery codes is expected.
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub code: Option<&'a str>,
+  pub recovery_code: Option<&'a str>,
+  /// Steps accepted before and after the current one.
+  pub allowed_drift_steps: i64,
+  pub lockout: Lockout,
+  pub ip_address: Option<&'a str>,
+  pub user_agent: Option<&'a str>,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn verify_mfa(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, VerifyMfa<Request<'a>, NoDbData, CodeNotChecked>, R> {
+    User {
+      repository: self.repository,
+      state: VerifyMfa {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_verify_mfa_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).verify_mfa(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
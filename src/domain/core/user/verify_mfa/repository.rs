@@ -0,0 +1,186 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::user::UserColumns,
+  error::{AppError, Code},
+};
+
+use super::*;
+
+const MFA_NOT_ENABLED: &str = "two-factor authentication not enabled";
+
+impl<'a, R: UserRepository> User<'a, VerifyMfa<Request<'a>, NoDbData, CodeNotChecked>, R> {
+  pub async fn get_data(
+    self,
+  ) -> Result<User<'a, VerifyMfa<Request<'a>, MfaData, CodeNotChecked>, R>, AppError> {
+    let profile_id = self.state.request.profile_id;
+
+    let user = self
+      .repository
+      .find_user_by(&UserColumns::Id(profile_id))
+      .await?;
+
+    let authenticator = self
+      .repository
+      .find_totp_authenticator(profile_id)
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::unauthenticated(MFA_NOT_ENABLED),
+        _ => error,
+      })?;
+
+    if !authenticator.confirmed {
+      return Err(AppError::unauthenticated(MFA_NOT_ENABLED));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: VerifyMfa {
+        request: self.state.request,
+        db_data: MfaData {
+          user,
+          authenticator,
+        },
+        code_checked: self.state.code_checked,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{
+      build_mock_user_repository, Expectations, FindTotpAuthenticator, FindUserBy,
+    },
+    entities::{totp::TotpAuthenticatorData, user::UserData},
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const SECRET: &str = "JBSWY3DPEHPK3PXP";
+
+  fn find_user_by() -> FindUserBy<'static> {
+    FindUserBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(PROFILE_ID),
+      fn_returning: |_| {
+        Ok(UserData {
+          id: PROFILE_ID.to_string(),
+          mfa_enabled: true,
+          ..Default::default()
+        })
+      },
+    }
+  }
+
+  fn mock_request() -> Request<'static> {
+    Request {
+      profile_id: PROFILE_ID,
+      ..Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn test_get_data_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_user_by: Some(find_user_by()),
+      find_totp_authenticator: Some(FindTotpAuthenticator {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| {
+          Ok(TotpAuthenticatorData {
+            secret: SECRET.to_string(),
+            confirmed: true,
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_data().await.unwrap();
+
+    assert_eq!(
+      sut.state.db_data,
+      MfaData {
+        user: UserData {
+          id: PROFILE_ID.to_string(),
+          mfa_enabled: true,
+          ..Default::default()
+        },
+        authenticator: TotpAuthenticatorData {
+          secret: SECRET.to_string(),
+          confirmed: true,
+          ..Default::default()
+        },
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_get_data_with_pending_authenticator() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_user_by: Some(find_user_by()),
+      find_totp_authenticator: Some(FindTotpAuthenticator {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| {
+          Ok(TotpAuthenticatorData {
+            confirmed: false,
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_data().await.err();
+
+    assert_eq!(sut, Some(AppError::unauthenticated(MFA_NOT_ENABLED)));
+  }
+
+  #[tokio::test]
+  async fn test_get_data_without_authenticator() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_user_by: Some(find_user_by()),
+      find_totp_authenticator: Some(FindTotpAuthenticator {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        fn_returning: |_| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_data().await.err();
+
+    assert_eq!(sut, Some(AppError::unauthenticated(MFA_NOT_ENABLED)));
+  }
+}
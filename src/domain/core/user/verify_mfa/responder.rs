@@ -0,0 +1,71 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::user::PublicUserData,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository> User<'a, VerifyMfa<Request<'a>, MfaData, CodeChecked>, R> {
+  pub fn response(self) -> PublicUserData {
+    let user = self.state.db_data.user;
+
+    PublicUserData {
+      id: user.id,
+      username: user.username,
+      name: user.name,
+      mfa_enabled: user.mfa_enabled,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{build_mock_user_repository, Expectations},
+    entities::user::UserData,
+  };
+
+  #[test]
+  fn test_response_with_right_data() {
+    const UUID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+    const NAME: &str = "john doe";
+    const USERNAME: &str = "john_doe";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: MfaData {
+          user: UserData {
+            id: UUID.to_string(),
+            name: NAME.to_string(),
+            username: USERNAME.to_string(),
+            mfa_enabled: true,
+            ..Default::default()
+          },
+          ..Default::default()
+        },
+        code_checked: CodeChecked(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(
+      sut,
+      PublicUserData {
+        id: UUID.to_string(),
+        name: NAME.to_string(),
+        username: USERNAME.to_string(),
+        mfa_enabled: true,
+      }
+    )
+  }
+}
@@ -0,0 +1,496 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, sign_in::ACCOUNT_LOCKED, totp, User},
+  entities::sign_in_event::{NewSignInEvent, SignInMethod},
+  error::AppError,
+  utilities::primitives::Primitives,
+};
+
+use super::*;
+
+const ACCOUNT_DISABLED: &str = "account disabled";
+const NO_CODE: &str = "no authentication code or recovery code provided";
+const CODE_INVALID: &str = "invalid authentication code";
+
+impl<'a, R: UserRepository> User<'a, VerifyMfa<Request<'a>, MfaData, CodeNotChecked>, R> {
+  async fn record_sign_in(&self, succeeded: bool) -> Result<(), AppError> {
+    let request = &self.state.request;
+
+    self
+      .repository
+      .store_sign_in_event(&NewSignInEvent {
+        profile_id: self.state.db_data.user.id.clone(),
+        method: SignInMethod::Mfa,
+        succeeded,
+        ip_address: request.ip_address.map(str::to_string),
+        user_agent: request.user_agent.map(str::to_string),
+      })
+      .await
+  }
+
+  /// Wrong codes count as failed sign-in attempts, so guessing them blocks
+  /// the account like guessing the password does. Each authenticator code and
+  /// recovery code is only accepted once. The password step of these users
+  /// leaves the sign-in to this one: only an accepted code registers it,
+  /// records it as succeeded and cancels a pending deletion.
+  pub async fn check_code(
+    self,
+    primitives: &impl Primitives,
+  ) -> Result<User<'a, VerifyMfa<Request<'a>, MfaData, CodeChecked>, R>, AppError> {
+    let request = &self.state.request;
+    let MfaData {
+      user,
+      authenticator,
+    } = &self.state.db_data;
+
+    if let Err(error) = request.lockout.check(user) {
+      self.record_sign_in(false).await?;
+      return Err(error);
+    }
+    if user.blocked_by_attempts {
+      self.repository.unlock_user(&user.id).await?;
+    }
+
+    if user.account_disabled {
+      self.record_sign_in(false).await?;
+      return Err(AppError::permission_denied(ACCOUNT_DISABLED));
+    }
+
+    let accepted = match (request.code, request.recovery_code) {
+      (Some(code), _) => {
+        let step = totp::find_step(
+          primitives,
+          &authenticator.secret,
+          code,
+          totp::current_step(),
+          request.allowed_drift_steps,
+          authenticator.last_used_step,
+        )?;
+
+        match step {
+          Some(step) => self.repository.use_totp_step(&user.id, step).await?,
+          None => false,
+        }
+      }
+      (None, Some(recovery_code)) => {
+        self
+          .repository
+          .use_recovery_code(&user.id, recovery_code)
+          .await?
+      }
+      (None, None) => return Err(AppError::invalid_argument(NO_CODE)),
+    };
+
+    if !accepted {
+      self.record_sign_in(false).await?;
+
+      let blocked = self
+        .repository
+        .register_failed_sign_in(&user.id, request.lockout.max_failed_attempts)
+        .await?;
+
+      if blocked {
+        return Err(AppError::locked(ACCOUNT_LOCKED));
+      }
+      return Err(AppError::unauthenticated(CODE_INVALID));
+    }
+
+    if user.requested_deletion {
+      self.repository.cancel_deletion(&user.id).await?;
+    }
+
+    self.repository.register_sign_in(&user.id).await?;
+    self.record_sign_in(true).await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: VerifyMfa {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        code_checked: CodeChecked(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::{Duration, Utc};
+  use mockall::predicate;
+
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::repository::{
+      build_mock_user_repository, CancelDeletion, Expectations, RegisterFailedSignIn,
+      RegisterSignIn, StoreSignInEvent, UnlockUser, UseRecoveryCode, UseTotpStep,
+    },
+    entities::{totp::TotpAuthenticatorData, user::UserData},
+    utilities::primitives::MockPrimitives,
+  };
+
+  const ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const SECRET: &str = "JBSWY3DPEHPK3PXP";
+  const CODE: &str = "287082";
+  const RECOVERY_CODE: &str = "0123456789";
+  const IP_ADDRESS: &str = "203.0.113.7";
+  const USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64)";
+
+  fn mock_request(
+    code: Option<&'static str>,
+    recovery_code: Option<&'static str>,
+  ) -> Request<'static> {
+    Request {
+      profile_id: ID,
+      code,
+      recovery_code,
+      allowed_drift_steps: 0,
+      lockout: Lockout::default(),
+      ip_address: Some(IP_ADDRESS),
+      user_agent: Some(USER_AGENT),
+    }
+  }
+
+  fn mock_db_data() -> MfaData {
+    MfaData {
+      user: UserData {
+        id: ID.to_string(),
+        mfa_enabled: true,
+        ..Default::default()
+      },
+      authenticator: TotpAuthenticatorData {
+        profile_id: ID.to_string(),
+        secret: SECRET.to_string(),
+        confirmed: true,
+        ..Default::default()
+      },
+    }
+  }
+
+  fn mock_primitives(code: &'static str) -> MockPrimitives {
+    let mut mock_primitives = MockPrimitives::new();
+
+    mock_primitives
+      .expect_totp_code()
+      .times(1)
+      .with(predicate::eq(SECRET), predicate::always())
+      .returning(move |_, _| Ok(code.to_string()));
+
+    mock_primitives
+  }
+
+  fn register_sign_in() -> RegisterSignIn {
+    RegisterSignIn {
+      calls: 1,
+      param_profile_id: ID.to_string(),
+      fn_returning: |_| Ok(()),
+    }
+  }
+
+  fn store_sign_in_event(succeeded: bool) -> StoreSignInEvent {
+    StoreSignInEvent {
+      calls: 1,
+      param_event: NewSignInEvent {
+        profile_id: ID.to_owned(),
+        method: SignInMethod::Mfa,
+        succeeded,
+        ip_address: Some(IP_ADDRESS.to_owned()),
+        user_agent: Some(USER_AGENT.to_owned()),
+      },
+      fn_returning: |_| Ok(()),
+    }
+  }
+
+  fn register_failed_sign_in(blocked: bool) -> RegisterFailedSignIn {
+    RegisterFailedSignIn {
+      calls: 1,
+      param_profile_id: ID.to_string(),
+      param_max_failed_attempts: 5,
+      fn_returning: if blocked {
+        |_, _| Ok(true)
+      } else {
+        |_, _| Ok(false)
+      },
+    }
+  }
+
+  #[tokio::test]
+  async fn test_check_valid_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_totp_step: Some(UseTotpStep {
+        calls: 1,
+        param_profile_id: ID.to_string(),
+        param_step: totp::current_step(),
+        fn_returning: |_, _| Ok(true),
+      }),
+      register_sign_in: Some(register_sign_in()),
+      store_sign_in_event: Some(store_sign_in_event(true)),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(Some(CODE), None),
+        db_data: mock_db_data(),
+        code_checked: CodeNotChecked,
+      },
+    };
+
+    let sut = user.check_code(&mock_primitives(CODE)).await.unwrap();
+
+    assert_eq!(sut.state.code_checked, CodeChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_replayed_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_totp_step: Some(UseTotpStep {
+        calls: 1,
+        param_profile_id: ID.to_string(),
+        param_step: totp::current_step(),
+        fn_returning: |_, _| Ok(false),
+      }),
+      register_failed_sign_in: Some(register_failed_sign_in(false)),
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(Some(CODE), None),
+        db_data: mock_db_data(),
+        code_checked: CodeNotChecked,
+      },
+    };
+
+    let sut = user.check_code(&mock_primitives(CODE)).await.err();
+
+    assert_eq!(sut, Some(AppError::unauthenticated(CODE_INVALID)));
+  }
+
+  #[tokio::test]
+  async fn test_check_wrong_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      register_failed_sign_in: Some(register_failed_sign_in(false)),
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(Some(CODE), None),
+        db_data: mock_db_data(),
+        code_checked: CodeNotChecked,
+      },
+    };
+
+    let sut = user.check_code(&mock_primitives("000000")).await.err();
+
+    assert_eq!(sut, Some(AppError::unauthenticated(CODE_INVALID)));
+  }
+
+  #[tokio::test]
+  async fn test_check_wrong_code_reaching_max_attempts() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      register_failed_sign_in: Some(register_failed_sign_in(true)),
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(Some(CODE), None),
+        db_data: mock_db_data(),
+        code_checked: CodeNotChecked,
+      },
+    };
+
+    let sut = user.check_code(&mock_primitives("000000")).await.err();
+
+    assert_eq!(sut, Some(AppError::locked(ACCOUNT_LOCKED)));
+  }
+
+  #[tokio::test]
+  async fn test_check_valid_recovery_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_recovery_code: Some(UseRecoveryCode {
+        calls: 1,
+        param_profile_id: ID.to_string(),
+        param_code: RECOVERY_CODE.to_string(),
+        fn_returning: |_, _| Ok(true),
+      }),
+      register_sign_in: Some(register_sign_in()),
+      store_sign_in_event: Some(store_sign_in_event(true)),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(None, Some(RECOVERY_CODE)),
+        db_data: mock_db_data(),
+        code_checked: CodeNotChecked,
+      },
+    };
+
+    let sut = user.check_code(&MockPrimitives::new()).await.unwrap();
+
+    assert_eq!(sut.state.code_checked, CodeChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_used_recovery_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_recovery_code: Some(UseRecoveryCode {
+        calls: 1,
+        param_profile_id: ID.to_string(),
+        param_code: RECOVERY_CODE.to_string(),
+        fn_returning: |_, _| Ok(false),
+      }),
+      register_failed_sign_in: Some(register_failed_sign_in(false)),
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(None, Some(RECOVERY_CODE)),
+        db_data: mock_db_data(),
+        code_checked: CodeNotChecked,
+      },
+    };
+
+    let sut = user.check_code(&MockPrimitives::new()).await.err();
+
+    assert_eq!(sut, Some(AppError::unauthenticated(CODE_INVALID)));
+  }
+
+  #[tokio::test]
+  async fn test_check_without_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(None, None),
+        db_data: mock_db_data(),
+        code_checked: CodeNotChecked,
+      },
+    };
+
+    let sut = user.check_code(&MockPrimitives::new()).await.err();
+
+    assert_eq!(sut, Some(AppError::invalid_argument(NO_CODE)));
+  }
+
+  #[tokio::test]
+  async fn test_check_code_with_blocked_user() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(Some(CODE), None),
+        db_data: MfaData {
+          user: UserData {
+            blocked_by_attempts: true,
+            blocked_at: Some(Utc::now().naive_utc() - Duration::minutes(5)),
+            ..mock_db_data().user
+          },
+          ..mock_db_data()
+        },
+        code_checked: CodeNotChecked,
+      },
+    };
+
+    let sut = user.check_code(&MockPrimitives::new()).await.err();
+
+    assert_eq!(sut, Some(AppError::locked(ACCOUNT_LOCKED)));
+  }
+  #[tokio::test]
+  async fn test_check_code_lifts_expired_lockout() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      unlock_user: Some(UnlockUser {
+        calls: 1,
+        param_profile_id: ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      use_totp_step: Some(UseTotpStep {
+        calls: 1,
+        param_profile_id: ID.to_string(),
+        param_step: totp::current_step(),
+        fn_returning: |_, _| Ok(true),
+      }),
+      register_sign_in: Some(register_sign_in()),
+      store_sign_in_event: Some(store_sign_in_event(true)),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(Some(CODE), None),
+        db_data: MfaData {
+          user: UserData {
+            blocked_by_attempts: true,
+            blocked_at: Some(Utc::now().naive_utc() - Duration::minutes(16)),
+            ..mock_db_data().user
+          },
+          ..mock_db_data()
+        },
+        code_checked: CodeNotChecked,
+      },
+    };
+
+    let sut = user.check_code(&mock_primitives(CODE)).await.unwrap();
+
+    assert_eq!(sut.state.code_checked, CodeChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_code_cancels_pending_deletion() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_totp_step: Some(UseTotpStep {
+        calls: 1,
+        param_profile_id: ID.to_string(),
+        param_step: totp::current_step(),
+        fn_returning: |_, _| Ok(true),
+      }),
+      cancel_deletion: Some(CancelDeletion {
+        calls: 1,
+        param_profile_id: ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      register_sign_in: Some(register_sign_in()),
+      store_sign_in_event: Some(store_sign_in_event(true)),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyMfa {
+        request: mock_request(Some(CODE), None),
+        db_data: MfaData {
+          user: UserData {
+            requested_deletion: true,
+            ..mock_db_data().user
+          },
+          ..mock_db_data()
+        },
+        code_checked: CodeNotChecked,
+      },
+    };
+
+    let sut = user.check_code(&mock_primitives(CODE)).await.unwrap();
+
+    assert_eq!(sut.state.code_checked, CodeChecked(true));
+  }
+}
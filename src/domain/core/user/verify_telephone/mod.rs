@@ -0,0 +1,77 @@
+use super::*;
+
+pub mod repository;
+pub mod responder;
+pub mod validator;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default)]
+pub struct CodeNotChecked;
+
+#[derive(Debug, PartialEq)]
+pub struct CodeChecked(pub(super) bool);
+
+#[derive(Default)]
+pub struct NotSaved;
+
+#[derive(Debug, PartialEq)]
+pub struct Saved(pub(super) bool);
+
+#[derive(Default)]
+pub struct VerifyTelephone<Req, Db, Check, Save> {
+  request: Req,
+  db_data: Db,
+  code_checked: Check,
+  saved: Save,
+}
+
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub profile_id: &'a str,
+  pub number: &'a str,
+  pub code: &'a str,
+  pub max_attempts: i32,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn verify_telephone(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, VerifyTelephone<Request<'a>, NoDbData, CodeNotChecked, NotSaved>, R> {
+    User {
+      repository: self.repository,
+      state: VerifyTelephone {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_verify_telephone_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).verify_telephone(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
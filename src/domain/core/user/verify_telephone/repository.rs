@@ -0,0 +1,200 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::{VerificationPurpose, VerificationTokenData},
+  error::{AppError, Code},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, VerifyTelephone<Request<'a>, NoDbData, CodeNotChecked, NotSaved>, R>
+{
+  pub async fn get_code(
+    self,
+  ) -> Result<
+    User<'a, VerifyTelephone<Request<'a>, VerificationTokenData, CodeNotChecked, NotSaved>, R>,
+    AppError,
+  > {
+    let request = &self.state.request;
+
+    let db_data = self
+      .repository
+      .find_latest_verification_token(
+        request.profile_id,
+        VerificationPurpose::Telephone,
+        request.number,
+      )
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => {
+          AppError::invalid_argument("no verification code requested for this number")
+        }
+        _ => error,
+      })?;
+
+    Ok(User {
+      repository: self.repository,
+      state: VerifyTelephone {
+        request: self.state.request,
+        db_data,
+        code_checked: self.state.code_checked,
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+impl<'a, R: UserRepository>
+  User<'a, VerifyTelephone<Request<'a>, VerificationTokenData, CodeChecked, NotSaved>, R>
+{
+  pub async fn save(
+    self,
+  ) -> Result<
+    User<'a, VerifyTelephone<Request<'a>, VerificationTokenData, CodeChecked, Saved>, R>,
+    AppError,
+  > {
+    let code = &self.state.db_data;
+
+    self
+      .repository
+      .check_telephone(&code.profile_id, &code.target)
+      .await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: VerifyTelephone {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        code_checked: self.state.code_checked,
+        saved: Saved(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, CheckTelephone, Expectations, FindLatestVerificationToken,
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const NUMBER: &str = "+1 4152370800";
+  const CODE: &str = "123456";
+
+  fn mock_request() -> Request<'static> {
+    Request {
+      profile_id: PROFILE_ID,
+      number: NUMBER,
+      code: CODE,
+      max_attempts: 5,
+    }
+  }
+
+  #[tokio::test]
+  async fn test_get_code_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_latest_verification_token: Some(FindLatestVerificationToken {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_purpose: VerificationPurpose::Telephone,
+        param_target: NUMBER.to_string(),
+        fn_returning: |_, _, _| {
+          Ok(VerificationTokenData {
+            id: 1,
+            target: NUMBER.to_string(),
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyTelephone {
+        request: mock_request(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_code().await.unwrap();
+
+    assert_eq!(
+      sut.state.db_data,
+      VerificationTokenData {
+        id: 1,
+        target: NUMBER.to_string(),
+        ..Default::default()
+      }
+    );
+  }
+
+  #[tokio::test]
+  async fn test_get_code_never_requested() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_latest_verification_token: Some(FindLatestVerificationToken {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_purpose: VerificationPurpose::Telephone,
+        param_target: NUMBER.to_string(),
+        fn_returning: |_, _, _| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyTelephone {
+        request: mock_request(),
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_code().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "no verification code requested for this number"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_save_checks_telephone() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      check_telephone: Some(CheckTelephone {
+        calls: 1,
+        param_profile_id: PROFILE_ID.to_string(),
+        param_number: NUMBER.to_string(),
+        fn_returning: |_, _| Ok(()),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyTelephone {
+        request: mock_request(),
+        db_data: VerificationTokenData {
+          id: 1,
+          profile_id: PROFILE_ID.to_string(),
+          target: NUMBER.to_string(),
+          ..Default::default()
+        },
+        code_checked: CodeChecked(true),
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.save().await.unwrap();
+
+    assert_eq!(sut.state.saved, Saved(true));
+  }
+}
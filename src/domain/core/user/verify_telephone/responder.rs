@@ -0,0 +1,49 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::VerificationTokenData,
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, VerifyTelephone<Request<'a>, VerificationTokenData, CodeChecked, Saved>, R>
+{
+  /// Returns the number that has just been verified.
+  pub fn response(self) -> String {
+    self.state.db_data.target
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  #[test]
+  fn test_response_with_verified_number() {
+    const NUMBER: &str = "+1 4152370800";
+
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyTelephone {
+        request: Request {
+          ..Default::default()
+        },
+        db_data: VerificationTokenData {
+          target: NUMBER.to_string(),
+          ..Default::default()
+        },
+        code_checked: CodeChecked(true),
+        saved: Saved(true),
+      },
+    };
+
+    let sut = user.response();
+
+    assert_eq!(sut, NUMBER);
+  }
+}
@@ -0,0 +1,255 @@
+use chrono::Utc;
+
+use crate::domain::{
+  core::user::{repository::UserRepository, User},
+  entities::verification_token::VerificationTokenData,
+  error::AppError,
+};
+
+use super::*;
+
+const CODE_USED: &str = "verification code already used";
+const CODE_EXPIRED: &str = "verification code expired";
+const CODE_INVALID: &str = "invalid verification code";
+const TOO_MANY_ATTEMPTS: &str = "too many wrong codes, request a new one";
+
+type UserVerifyStateIn<'a, R> =
+  User<'a, VerifyTelephone<Request<'a>, VerificationTokenData, CodeNotChecked, NotSaved>, R>;
+type UserVerifyStateOut<'a, R> =
+  User<'a, VerifyTelephone<Request<'a>, VerificationTokenData, CodeChecked, NotSaved>, R>;
+
+impl<'a, R: UserRepository> UserVerifyStateIn<'a, R> {
+  /// Codes are short, so each one only takes `max_attempts` guesses before a
+  /// new one has to be requested, counted by the repository as it records
+  /// the guess. A matching guess consumes the code.
+  pub async fn check_code(self) -> Result<UserVerifyStateOut<'a, R>, AppError> {
+    let code = &self.state.db_data;
+
+    if code.used {
+      return Err(AppError::invalid_argument(CODE_USED));
+    }
+
+    if code.expires_at <= Utc::now().naive_utc() {
+      return Err(AppError::invalid_argument(CODE_EXPIRED));
+    }
+
+    let request = &self.state.request;
+    match self
+      .repository
+      .attempt_verification_token(code.id, request.code, request.max_attempts)
+      .await?
+    {
+      Some(true) => {}
+      Some(false) => return Err(AppError::invalid_argument(CODE_INVALID)),
+      None => return Err(AppError::too_many_requests(TOO_MANY_ATTEMPTS)),
+    }
+
+    if !self.repository.use_verification_token(code.id).await? {
+      return Err(AppError::invalid_argument(CODE_USED));
+    }
+
+    Ok(User {
+      repository: self.repository,
+      state: VerifyTelephone {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        code_checked: CodeChecked(true),
+        saved: self.state.saved,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use chrono::Duration;
+
+  use super::*;
+  use crate::domain::core::user::mocks::repository::{
+    build_mock_user_repository, AttemptVerificationToken, Expectations, UseVerificationToken,
+  };
+
+  const CODE: &str = "123456";
+
+  fn mock_request() -> Request<'static> {
+    Request {
+      code: CODE,
+      max_attempts: 5,
+      ..Default::default()
+    }
+  }
+
+  fn code_data() -> VerificationTokenData {
+    VerificationTokenData {
+      id: 1,
+      expires_at: Utc::now().naive_utc() + Duration::minutes(10),
+      ..Default::default()
+    }
+  }
+
+  fn attempt_verification_token(matches: bool) -> AttemptVerificationToken {
+    AttemptVerificationToken {
+      calls: 1,
+      param_id: 1,
+      param_token: CODE.to_string(),
+      param_max_attempts: 5,
+      fn_returning: if matches {
+        |_, _, _| Ok(Some(true))
+      } else {
+        |_, _, _| Ok(Some(false))
+      },
+    }
+  }
+
+  #[tokio::test]
+  async fn test_check_valid_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      attempt_verification_token: Some(attempt_verification_token(true)),
+      use_verification_token: Some(UseVerificationToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(true),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyTelephone {
+        request: mock_request(),
+        db_data: code_data(),
+        code_checked: CodeNotChecked,
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.check_code().await.unwrap();
+
+    assert_eq!(sut.state.code_checked, CodeChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_wrong_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      attempt_verification_token: Some(attempt_verification_token(false)),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyTelephone {
+        request: mock_request(),
+        db_data: code_data(),
+        code_checked: CodeNotChecked,
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.check_code().await.err();
+
+    assert_eq!(sut, Some(AppError::invalid_argument(CODE_INVALID)));
+  }
+
+  #[tokio::test]
+  async fn test_check_code_after_too_many_attempts() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      attempt_verification_token: Some(AttemptVerificationToken {
+        fn_returning: |_, _, _| Ok(None),
+        ..attempt_verification_token(false)
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyTelephone {
+        request: mock_request(),
+        db_data: VerificationTokenData {
+          attempts: 5,
+          ..code_data()
+        },
+        code_checked: CodeNotChecked,
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.check_code().await.err();
+
+    assert_eq!(sut, Some(AppError::too_many_requests(TOO_MANY_ATTEMPTS)));
+  }
+
+  #[tokio::test]
+  async fn test_check_used_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyTelephone {
+        request: mock_request(),
+        db_data: VerificationTokenData {
+          used: true,
+          ..code_data()
+        },
+        code_checked: CodeNotChecked,
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.check_code().await.err();
+
+    assert_eq!(sut, Some(AppError::invalid_argument(CODE_USED)));
+  }
+
+  #[tokio::test]
+  async fn test_check_expired_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyTelephone {
+        request: mock_request(),
+        db_data: VerificationTokenData {
+          expires_at: Utc::now().naive_utc() - Duration::minutes(1),
+          ..code_data()
+        },
+        code_checked: CodeNotChecked,
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.check_code().await.err();
+
+    assert_eq!(sut, Some(AppError::invalid_argument(CODE_EXPIRED)));
+  }
+
+  #[tokio::test]
+  async fn test_check_concurrently_used_code() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      attempt_verification_token: Some(attempt_verification_token(true)),
+      use_verification_token: Some(UseVerificationToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(false),
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: VerifyTelephone {
+        request: mock_request(),
+        db_data: code_data(),
+        code_checked: CodeNotChecked,
+        saved: NotSaved,
+      },
+    };
+
+    let sut = user.check_code().await.err();
+
+    assert_eq!(sut, Some(AppError::invalid_argument(CODE_USED)));
+  }
+}
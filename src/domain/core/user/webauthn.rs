@@ -0,0 +1,346 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ciborium::value::Value;
+use serde::Deserialize;
+
+use crate::domain::{error::AppError, utilities::primitives::Primitives};
+
+pub const CEREMONY_CREATE: &str = "webauthn.create";
+pub const CEREMONY_GET: &str = "webauthn.get";
+/// COSE identifier of ES256, the only algorithm offered to authenticators.
+pub const ES256: i64 = -7;
+
+const USER_PRESENT: u8 = 0x01;
+const USER_VERIFIED: u8 = 0x04;
+const ATTESTED_CREDENTIAL: u8 = 0x40;
+/// rpIdHash, flags and signCount.
+const AUTHENTICATOR_DATA_MIN_LEN: usize = 37;
+/// AAGUID and credential id length, before the credential id.
+const ATTESTED_CREDENTIAL_HEADER_LEN: usize = 18;
+
+const INVALID_AUTHENTICATOR_DATA: &str = "invalid WebAuthn authenticator data";
+const UNSUPPORTED_KEY: &str = "only ES256 passkeys are supported";
+
+/// `clientDataJSON` collected by the browser, the fields checked here only.
+#[derive(Deserialize, Default, Debug, PartialEq)]
+pub struct ClientData {
+  #[serde(rename = "type")]
+  pub ceremony: String,
+  pub challenge: String,
+  pub origin: String,
+}
+
+/// Parsed authenticator data, see the WebAuthn specification §6.1.
+#[derive(Debug, PartialEq)]
+pub struct AuthenticatorData {
+  pub rp_id_hash: Vec<u8>,
+  pub flags: u8,
+  pub sign_count: u32,
+  /// Credential id with its uncompressed P-256 public key, only present when
+  /// a passkey is created.
+  pub credential: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+/// Decodes a base64url field of a WebAuthn response, padded or not.
+pub fn decode(value: &str, field: &str) -> Result<Vec<u8>, AppError> {
+  URL_SAFE_NO_PAD
+    .decode(value.trim_end_matches('='))
+    .map_err(|_| AppError::invalid_argument(format!("{} must be base64url encoded", field)))
+}
+
+pub fn parse_client_data(client_data_json: &[u8]) -> Result<ClientData, AppError> {
+  serde_json::from_slice(client_data_json)
+    .map_err(|_| AppError::invalid_argument("invalid WebAuthn client data"))
+}
+
+/// The client data must come from the expected ceremony on the configured
+/// origin, otherwise it may have been phished by another site.
+pub fn check_client_data(
+  client_data: &ClientData,
+  ceremony: &str,
+  origin: &str,
+) -> Result<(), AppError> {
+  if client_data.ceremony != ceremony {
+    return Err(AppError::invalid_argument(format!(
+      "expected a {} WebAuthn ceremony",
+      ceremony
+    )));
+  }
+  if client_data.origin != origin {
+    return Err(AppError::invalid_argument("WebAuthn origin not allowed"));
+  }
+
+  Ok(())
+}
+
+pub fn parse_authenticator_data(data: &[u8]) -> Result<AuthenticatorData, AppError> {
+  if data.len() < AUTHENTICATOR_DATA_MIN_LEN {
+    return Err(AppError::invalid_argument(INVALID_AUTHENTICATOR_DATA));
+  }
+
+  let flags = data[32];
+  let sign_count = u32::from_be_bytes([data[33], data[34], data[35], data[36]]);
+
+  let credential = if flags & ATTESTED_CREDENTIAL != 0 {
+    let rest = &data[AUTHENTICATOR_DATA_MIN_LEN..];
+    if rest.len() < ATTESTED_CREDENTIAL_HEADER_LEN {
+      return Err(AppError::invalid_argument(INVALID_AUTHENTICATOR_DATA));
+    }
+
+    let id_len = u16::from_be_bytes([rest[16], rest[17]]) as usize;
+    let rest = &rest[ATTESTED_CREDENTIAL_HEADER_LEN..];
+    if rest.len() < id_len {
+      return Err(AppError::invalid_argument(INVALID_AUTHENTICATOR_DATA));
+    }
+
+    let (credential_id, mut cose_key) = rest.split_at(id_len);
+    let cose_key: Value = ciborium::de::from_reader(&mut cose_key)
+      .map_err(|_| AppError::invalid_argument(INVALID_AUTHENTICATOR_DATA))?;
+
+    Some((credential_id.to_vec(), parse_cose_key(&cose_key)?))
+  } else {
+    None
+  };
+
+  Ok(AuthenticatorData {
+    rp_id_hash: data[..32].to_vec(),
+    flags,
+    sign_count,
+    credential,
+  })
+}
+
+/// The authenticator data must be scoped to `rp_id`, and the user must have
+/// been verified by the authenticator (PIN or biometrics), which is what lets
+/// a passkey replace both the password and the second factor.
+pub fn check_authenticator_data(
+  primitives: &impl Primitives,
+  data: &AuthenticatorData,
+  rp_id: &str,
+) -> Result<(), AppError> {
+  if data.rp_id_hash != primitives.sha256(rp_id.as_bytes()) {
+    return Err(AppError::invalid_argument(
+      "passkey is scoped to another relying party",
+    ));
+  }
+  if data.flags & USER_PRESENT == 0 || data.flags & USER_VERIFIED == 0 {
+    return Err(AppError::invalid_argument("user verification required"));
+  }
+
+  Ok(())
+}
+
+/// Authenticator data of an attestation object. Only the `none` format is
+/// accepted, which is what browsers send when no attestation is requested.
+pub fn parse_attestation_object(data: &[u8]) -> Result<AuthenticatorData, AppError> {
+  let invalid = || AppError::invalid_argument("invalid WebAuthn attestation object");
+
+  let attestation: Value = ciborium::de::from_reader(data).map_err(|_| invalid())?;
+  let attestation = attestation.as_map().ok_or_else(invalid)?;
+
+  let format = map_get(attestation, Value::Text(String::from("fmt")))
+    .and_then(Value::as_text)
+    .ok_or_else(invalid)?;
+  if format != "none" {
+    return Err(AppError::invalid_argument(
+      "only attestation format none is supported",
+    ));
+  }
+
+  let authenticator_data = map_get(attestation, Value::Text(String::from("authData")))
+    .and_then(Value::as_bytes)
+    .ok_or_else(invalid)?;
+
+  parse_authenticator_data(authenticator_data)
+}
+
+/// What the authenticator signs when asserting: its data followed by the
+/// SHA-256 hash of the client data.
+pub fn signed_data(
+  primitives: &impl Primitives,
+  authenticator_data: &[u8],
+  client_data_json: &[u8],
+) -> Vec<u8> {
+  let mut data = authenticator_data.to_vec();
+  data.extend_from_slice(&primitives.sha256(client_data_json));
+  data
+}
+
+fn map_get(map: &[(Value, Value)], key: Value) -> Option<&Value> {
+  map
+    .iter()
+    .find(|(entry_key, _)| *entry_key == key)
+    .map(|(_, value)| value)
+}
+
+/// Uncompressed SEC1 point of an EC2 P-256 COSE key.
+fn parse_cose_key(key: &Value) -> Result<Vec<u8>, AppError> {
+  let key = key
+    .as_map()
+    .ok_or_else(|| AppError::invalid_argument(INVALID_AUTHENTICATOR_DATA))?;
+  let integer = |label: i64| {
+    map_get(key, Value::Integer(label.into()))
+      .and_then(Value::as_integer)
+      .and_then(|value| i64::try_from(value).ok())
+  };
+  let coordinate = |label: i64| {
+    map_get(key, Value::Integer(label.into()))
+      .and_then(Value::as_bytes)
+      .filter(|value| value.len() == 32)
+  };
+
+  // kty EC2, alg ES256 and crv P-256
+  if integer(1) != Some(2) || integer(3) != Some(ES256) || integer(-1) != Some(1) {
+    return Err(AppError::invalid_argument(UNSUPPORTED_KEY));
+  }
+
+  match (coordinate(-2), coordinate(-3)) {
+    (Some(x), Some(y)) => Ok([&[0x04][..], x, y].concat()),
+    _ => Err(AppError::invalid_argument(UNSUPPORTED_KEY)),
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::domain::core::user::mocks::webauthn::{
+    attestation_object, authenticator_data, client_data_json, cose_key, mock_sha256, CHALLENGE,
+    CREDENTIAL_ID, FLAGS_ASSERTION, FLAGS_REGISTRATION, ORIGIN, OTHER_HASH, PUBLIC_KEY, RP_ID,
+    RP_ID_HASH,
+  };
+
+  #[test]
+  fn test_decode_with_and_without_padding() {
+    assert_eq!(decode("AQI", "field").unwrap(), vec![1, 2]);
+    assert_eq!(decode("AQI=", "field").unwrap(), vec![1, 2]);
+    assert_eq!(
+      decode("not base64!", "signature").err(),
+      Some(AppError::invalid_argument(
+        "signature must be base64url encoded"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_check_client_data() {
+    let client_data =
+      parse_client_data(&client_data_json(CEREMONY_CREATE, CHALLENGE, ORIGIN)).unwrap();
+
+    assert_eq!(client_data.challenge, CHALLENGE);
+    assert!(check_client_data(&client_data, CEREMONY_CREATE, ORIGIN).is_ok());
+    assert_eq!(
+      check_client_data(&client_data, CEREMONY_GET, ORIGIN).err(),
+      Some(AppError::invalid_argument(
+        "expected a webauthn.get WebAuthn ceremony"
+      ))
+    );
+    assert_eq!(
+      check_client_data(&client_data, CEREMONY_CREATE, "https://evil.example").err(),
+      Some(AppError::invalid_argument("WebAuthn origin not allowed"))
+    );
+  }
+
+  #[test]
+  fn test_parse_attestation_object() {
+    let data = authenticator_data(
+      &RP_ID_HASH,
+      FLAGS_REGISTRATION,
+      0,
+      Some(&cose_key(&PUBLIC_KEY)),
+    );
+
+    let sut = parse_attestation_object(&attestation_object("none", &data)).unwrap();
+
+    assert_eq!(sut.sign_count, 0);
+    assert_eq!(
+      sut.credential,
+      Some((CREDENTIAL_ID.to_vec(), PUBLIC_KEY.to_vec()))
+    );
+    assert!(check_authenticator_data(&mock_sha256(), &sut, RP_ID).is_ok());
+  }
+
+  #[test]
+  fn test_parse_attestation_object_with_attestation_statement() {
+    let data = authenticator_data(
+      &RP_ID_HASH,
+      FLAGS_REGISTRATION,
+      0,
+      Some(&cose_key(&PUBLIC_KEY)),
+    );
+
+    let sut = parse_attestation_object(&attestation_object("packed", &data)).err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(
+        "only attestation format none is supported"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_parse_rsa_credential() {
+    let mut rsa_key = Vec::new();
+    ciborium::ser::into_writer(
+      &Value::Map(vec![
+        (Value::Integer(1.into()), Value::Integer(3.into())),
+        (Value::Integer(3.into()), Value::Integer((-257).into())),
+      ]),
+      &mut rsa_key,
+    )
+    .unwrap();
+    let data = authenticator_data(&RP_ID_HASH, FLAGS_REGISTRATION, 0, Some(&rsa_key));
+
+    let sut = parse_authenticator_data(&data).err();
+
+    assert_eq!(sut, Some(AppError::invalid_argument(UNSUPPORTED_KEY)));
+  }
+
+  #[test]
+  fn test_parse_truncated_authenticator_data() {
+    let data = authenticator_data(
+      &RP_ID_HASH,
+      FLAGS_REGISTRATION,
+      0,
+      Some(&cose_key(&PUBLIC_KEY)),
+    );
+
+    let sut = parse_authenticator_data(&data[..50]).err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::invalid_argument(INVALID_AUTHENTICATOR_DATA))
+    );
+  }
+
+  #[test]
+  fn test_check_authenticator_data_of_other_relying_party() {
+    let data =
+      parse_authenticator_data(&authenticator_data(&OTHER_HASH, FLAGS_ASSERTION, 1, None)).unwrap();
+
+    assert_eq!(
+      check_authenticator_data(&mock_sha256(), &data, RP_ID).err(),
+      Some(AppError::invalid_argument(
+        "passkey is scoped to another relying party"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_check_authenticator_data_without_user_verification() {
+    let data =
+      parse_authenticator_data(&authenticator_data(&RP_ID_HASH, USER_PRESENT, 1, None)).unwrap();
+
+    assert_eq!(
+      check_authenticator_data(&mock_sha256(), &data, RP_ID).err(),
+      Some(AppError::invalid_argument("user verification required"))
+    );
+  }
+
+  #[test]
+  fn test_signed_data() {
+    let sut = signed_data(&mock_sha256(), &[1, 2, 3], b"{}");
+
+    assert_eq!(&sut[..3], &[1, 2, 3]);
+    assert_eq!(&sut[3..], &OTHER_HASH);
+  }
+}
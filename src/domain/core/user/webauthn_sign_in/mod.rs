@@ -0,0 +1,90 @@
+use super::{webauthn::ClientData, *};
+use crate::domain::entities::{
+  user::UserData, verification_token::VerificationTokenData, webauthn::WebAuthnCredentialData,
+};
+
+pub mod repository;
+pub mod responder;
+pub mod validator;
+
+pub use super::sign_in::Lockout;
+
+#[derive(Default)]
+pub struct NoDbData;
+
+#[derive(Default, Debug, PartialEq)]
+pub struct AssertionData {
+  pub user: UserData,
+  pub credential: WebAuthnCredentialData,
+  pub token: VerificationTokenData,
+  pub client_data: ClientData,
+}
+
+#[derive(Default)]
+pub struct AssertionNotChecked;
+
+#[derive(Debug, PartialEq)]
+pub struct AssertionChecked(pub(super) bool);
+
+#[derive(Default)]
+pub struct WebAuthnSignIn<Req, Db, Check> {
+  request: Req,
+  db_data: Db,
+  assertion_checked: Check,
+}
+
+/// Response of `navigator.credentials.get()`, binary fields being base64url
+/// encoded as the browser gives them.
+#[derive(Default, Debug, PartialEq)]
+pub struct Request<'a> {
+  pub credential_id: &'a str,
+  pub client_data_json: &'a str,
+  pub authenticator_data: &'a str,
+  pub signature: &'a str,
+  pub rp_id: &'a str,
+  pub origin: &'a str,
+  pub ip_address: Option<&'a str>,
+  pub user_agent: Option<&'a str>,
+  pub lockout: Lockout,
+}
+
+impl<'a, R: UserRepository> User<'a, NoState, R> {
+  pub fn webauthn_sign_in(
+    self,
+    request: Request<'a>,
+  ) -> User<'a, WebAuthnSignIn<Request<'a>, NoDbData, AssertionNotChecked>, R> {
+    User {
+      repository: self.repository,
+      state: WebAuthnSignIn {
+        request,
+        ..Default::default()
+      },
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::domain::core::user::mocks::repository::{build_mock_user_repository, Expectations};
+
+  use super::*;
+
+  #[test]
+  fn test_webauthn_sign_in_build() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let mock_request = Request {
+      ..Default::default()
+    };
+
+    let sut = User::new(&mock_repository).webauthn_sign_in(mock_request);
+
+    assert_eq!(
+      sut.state.request,
+      Request {
+        ..Default::default()
+      }
+    );
+  }
+}
@@ -0,0 +1,223 @@
+use crate::domain::{
+  core::user::{repository::UserRepository, webauthn, User},
+  entities::{user::UserColumns, verification_token::VerificationPurpose},
+  error::{AppError, Code},
+};
+
+use super::*;
+
+impl<'a, R: UserRepository>
+  User<'a, WebAuthnSignIn<Request<'a>, NoDbData, AssertionNotChecked>, R>
+{
+  /// Finds the passkey, its owner and the challenge the client data answers.
+  pub async fn get_data(
+    self,
+  ) -> Result<User<'a, WebAuthnSignIn<Request<'a>, AssertionData, AssertionNotChecked>, R>, AppError>
+  {
+    let request = &self.state.request;
+
+    let credential = self
+      .repository
+      .find_webauthn_credential(request.credential_id)
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::unauthenticated("unknown passkey"),
+        _ => error,
+      })?;
+
+    let user = self
+      .repository
+      .find_user_by(&UserColumns::Id(&credential.profile_id))
+      .await?;
+
+    let client_data = webauthn::parse_client_data(&webauthn::decode(
+      request.client_data_json,
+      "client_data_json",
+    )?)?;
+
+    let token = self
+      .repository
+      .find_verification_token(&client_data.challenge, VerificationPurpose::WebAuthnSignIn)
+      .await
+      .map_err(|error| match error.code {
+        Code::NotFound => AppError::unauthenticated("invalid WebAuthn challenge"),
+        _ => error,
+      })?;
+
+    Ok(User {
+      repository: self.repository,
+      state: WebAuthnSignIn {
+        request: self.state.request,
+        db_data: AssertionData {
+          user,
+          credential,
+          token,
+          client_data,
+        },
+        assertion_checked: self.state.assertion_checked,
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+  use super::*;
+  use crate::domain::{
+    core::user::mocks::{
+      repository::{
+        build_mock_user_repository, Expectations, FindUserBy, FindVerificationToken,
+        FindWebAuthnCredential,
+      },
+      webauthn::{client_data_json, CHALLENGE, CREDENTIAL_ID_B64, ORIGIN},
+    },
+    entities::{user::UserData, verification_token::VerificationTokenData},
+  };
+
+  const PROFILE_ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+
+  fn find_webauthn_credential() -> FindWebAuthnCredential {
+    FindWebAuthnCredential {
+      calls: 1,
+      param_credential_id: CREDENTIAL_ID_B64.to_string(),
+      fn_returning: |_| {
+        Ok(WebAuthnCredentialData {
+          id: 1,
+          credential_id: CREDENTIAL_ID_B64.to_string(),
+          profile_id: PROFILE_ID.to_string(),
+          ..Default::default()
+        })
+      },
+    }
+  }
+
+  fn find_user_by() -> FindUserBy<'static> {
+    FindUserBy {
+      calls: 1,
+      param_column_with: UserColumns::Id(PROFILE_ID),
+      fn_returning: |_| {
+        Ok(UserData {
+          id: PROFILE_ID.to_string(),
+          ..Default::default()
+        })
+      },
+    }
+  }
+
+  fn encoded_client_data() -> String {
+    URL_SAFE_NO_PAD.encode(client_data_json(webauthn::CEREMONY_GET, CHALLENGE, ORIGIN))
+  }
+
+  #[tokio::test]
+  async fn test_get_data_successfully() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_webauthn_credential: Some(find_webauthn_credential()),
+      find_user_by: Some(find_user_by()),
+      find_verification_token: Some(FindVerificationToken {
+        calls: 1,
+        param_token: CHALLENGE.to_string(),
+        param_purpose: VerificationPurpose::WebAuthnSignIn,
+        fn_returning: |_, _| {
+          Ok(VerificationTokenData {
+            id: 1,
+            profile_id: PROFILE_ID.to_string(),
+            ..Default::default()
+          })
+        },
+      }),
+      ..Default::default()
+    });
+    let client_data = encoded_client_data();
+
+    let user = User {
+      repository: &mock_repository,
+      state: WebAuthnSignIn {
+        request: Request {
+          credential_id: CREDENTIAL_ID_B64,
+          client_data_json: &client_data,
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_data().await.unwrap();
+
+    assert_eq!(sut.state.db_data.user.id, PROFILE_ID);
+    assert_eq!(sut.state.db_data.credential.id, 1);
+    assert_eq!(sut.state.db_data.token.id, 1);
+    assert_eq!(sut.state.db_data.client_data.challenge, CHALLENGE);
+  }
+
+  #[tokio::test]
+  async fn test_get_data_with_unknown_passkey() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_webauthn_credential: Some(FindWebAuthnCredential {
+        calls: 1,
+        param_credential_id: CREDENTIAL_ID_B64.to_string(),
+        fn_returning: |_| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+
+    let user = User {
+      repository: &mock_repository,
+      state: WebAuthnSignIn {
+        request: Request {
+          credential_id: CREDENTIAL_ID_B64,
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_data().await.err();
+
+    assert_eq!(sut, Some(AppError::unauthenticated("unknown passkey")));
+  }
+
+  #[tokio::test]
+  async fn test_get_data_with_unknown_challenge() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      find_webauthn_credential: Some(find_webauthn_credential()),
+      find_user_by: Some(find_user_by()),
+      find_verification_token: Some(FindVerificationToken {
+        calls: 1,
+        param_token: CHALLENGE.to_string(),
+        param_purpose: VerificationPurpose::WebAuthnSignIn,
+        fn_returning: |_, _| {
+          Err(AppError::not_found(
+            "DB: nothing found with given parameters",
+          ))
+        },
+      }),
+      ..Default::default()
+    });
+    let client_data = encoded_client_data();
+
+    let user = User {
+      repository: &mock_repository,
+      state: WebAuthnSignIn {
+        request: Request {
+          credential_id: CREDENTIAL_ID_B64,
+          client_data_json: &client_data,
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    };
+
+    let sut = user.get_data().await.err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::unauthenticated("invalid WebAuthn challenge"))
+    );
+  }
+}
@@ -0,0 +1,517 @@
+use chrono::Utc;
+
+use crate::domain::{
+  core::user::{repository::UserRepository, webauthn, User},
+  entities::sign_in_event::{NewSignInEvent, SignInMethod},
+  error::AppError,
+  utilities::primitives::Primitives,
+};
+
+use super::*;
+
+const ACCOUNT_DISABLED: &str = "account disabled";
+const INVALID_CHALLENGE: &str = "invalid WebAuthn challenge";
+const CHALLENGE_USED: &str = "WebAuthn challenge already used";
+
+type UserSignInStateIn<'a, R> =
+  User<'a, WebAuthnSignIn<Request<'a>, AssertionData, AssertionNotChecked>, R>;
+type UserSignInStateOut<'a, R> =
+  User<'a, WebAuthnSignIn<Request<'a>, AssertionData, AssertionChecked>, R>;
+
+/// Failed checks of the assertion are reported as failed authentications.
+fn unauthenticated(error: AppError) -> AppError {
+  AppError::unauthenticated(error.message)
+}
+
+impl<'a, R: UserRepository> UserSignInStateIn<'a, R> {
+  async fn record_sign_in(&self, succeeded: bool) -> Result<(), AppError> {
+    let request = &self.state.request;
+
+    self
+      .repository
+      .store_sign_in_event(&NewSignInEvent {
+        profile_id: self.state.db_data.user.id.clone(),
+        method: SignInMethod::Passkey,
+        succeeded,
+        ip_address: request.ip_address.map(str::to_string),
+        user_agent: request.user_agent.map(str::to_string),
+      })
+      .await
+  }
+
+  /// The challenge must have been issued to the owner of the passkey for this
+  /// relying party, and the authenticator must have verified the user, which
+  /// is why a passkey sign-in does not ask for the second factor. The
+  /// signature counter has to grow, unless the authenticator does not keep
+  /// one, as a counter going back means the passkey was cloned. Locked and
+  /// disabled accounts are refused like on password sign-ins, and signing in
+  /// cancels a pending deletion.
+  pub async fn check_assertion(
+    self,
+    primitives: &impl Primitives,
+  ) -> Result<UserSignInStateOut<'a, R>, AppError> {
+    let request = &self.state.request;
+    let AssertionData {
+      user,
+      credential,
+      token,
+      client_data,
+    } = &self.state.db_data;
+
+    if token.profile_id != credential.profile_id || token.target != request.rp_id {
+      return Err(AppError::unauthenticated(INVALID_CHALLENGE));
+    }
+    if token.used {
+      return Err(AppError::unauthenticated(CHALLENGE_USED));
+    }
+    if token.expires_at <= Utc::now().naive_utc() {
+      return Err(AppError::unauthenticated("WebAuthn challenge expired"));
+    }
+
+    webauthn::check_client_data(client_data, webauthn::CEREMONY_GET, request.origin)
+      .map_err(unauthenticated)?;
+
+    let raw_authenticator_data =
+      webauthn::decode(request.authenticator_data, "authenticator_data")?;
+    let authenticator_data = webauthn::parse_authenticator_data(&raw_authenticator_data)?;
+    webauthn::check_authenticator_data(primitives, &authenticator_data, request.rp_id)
+      .map_err(unauthenticated)?;
+
+    if let Err(error) = request.lockout.check(user) {
+      self.record_sign_in(false).await?;
+      return Err(error);
+    }
+    if user.blocked_by_attempts {
+      self.repository.unlock_user(&user.id).await?;
+    }
+
+    let signed_data = webauthn::signed_data(
+      primitives,
+      &raw_authenticator_data,
+      &webauthn::decode(request.client_data_json, "client_data_json")?,
+    );
+    let signature = webauthn::decode(request.signature, "signature")?;
+    if !primitives.verify_es256(&credential.public_key, &signed_data, &signature) {
+      self.record_sign_in(false).await?;
+      return Err(AppError::unauthenticated("invalid passkey signature"));
+    }
+
+    let sign_count = i64::from(authenticator_data.sign_count);
+    if (sign_count != 0 || credential.sign_count != 0) && sign_count <= credential.sign_count {
+      self.record_sign_in(false).await?;
+      return Err(AppError::unauthenticated(
+        "passkey signature counter did not increase",
+      ));
+    }
+
+    if user.account_disabled {
+      self.record_sign_in(false).await?;
+      return Err(AppError::permission_denied(ACCOUNT_DISABLED));
+    }
+
+    if !self.repository.use_verification_token(token.id).await? {
+      return Err(AppError::unauthenticated(CHALLENGE_USED));
+    }
+
+    self
+      .repository
+      .update_webauthn_sign_count(credential.id, sign_count)
+      .await?;
+
+    if user.requested_deletion {
+      self.repository.cancel_deletion(&user.id).await?;
+    }
+
+    self.repository.register_sign_in(&user.id).await?;
+    self.record_sign_in(true).await?;
+
+    Ok(User {
+      repository: self.repository,
+      state: WebAuthnSignIn {
+        request: self.state.request,
+        db_data: self.state.db_data,
+        assertion_checked: AssertionChecked(true),
+      },
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+  use chrono::Duration;
+  use mockall::predicate;
+
+  use super::*;
+  use crate::domain::{
+    core::user::{
+      mocks::{
+        repository::{
+          build_mock_user_repository, CancelDeletion, Expectations, RegisterSignIn,
+          StoreSignInEvent, UpdateWebAuthnSignCount, UseVerificationToken,
+        },
+        webauthn::{
+          authenticator_data, client_data_json, mock_sha256, CHALLENGE, CREDENTIAL_ID_B64,
+          FLAGS_ASSERTION, ORIGIN, OTHER_HASH, PUBLIC_KEY, RP_ID, RP_ID_HASH,
+        },
+      },
+      repository::MockUserRepository,
+      sign_in::ACCOUNT_LOCKED,
+    },
+    entities::{
+      user::UserData, verification_token::VerificationTokenData, webauthn::WebAuthnCredentialData,
+    },
+    utilities::primitives::MockPrimitives,
+  };
+
+  const ID: &str = "cc3b95d3-4ba4-4a90-bc09-119fd2a4c659";
+  const SIGNATURE: &str = "MEUCIQ";
+  const IP_ADDRESS: &str = "203.0.113.7";
+  const USER_AGENT: &str = "Mozilla/5.0 (X11; Linux x86_64)";
+
+  struct Encoded {
+    client_data_json: String,
+    authenticator_data: String,
+  }
+
+  fn encoded(sign_count: u32) -> Encoded {
+    Encoded {
+      client_data_json: URL_SAFE_NO_PAD.encode(client_data_json(
+        webauthn::CEREMONY_GET,
+        CHALLENGE,
+        ORIGIN,
+      )),
+      authenticator_data: URL_SAFE_NO_PAD.encode(authenticator_data(
+        &RP_ID_HASH,
+        FLAGS_ASSERTION,
+        sign_count,
+        None,
+      )),
+    }
+  }
+
+  fn mock_db_data(stored_sign_count: i64) -> AssertionData {
+    AssertionData {
+      user: UserData {
+        id: ID.to_string(),
+        ..Default::default()
+      },
+      credential: WebAuthnCredentialData {
+        id: 1,
+        credential_id: CREDENTIAL_ID_B64.to_string(),
+        profile_id: ID.to_string(),
+        public_key: PUBLIC_KEY.to_vec(),
+        sign_count: stored_sign_count,
+        ..Default::default()
+      },
+      token: VerificationTokenData {
+        id: 1,
+        profile_id: ID.to_string(),
+        target: RP_ID.to_string(),
+        expires_at: Utc::now().naive_utc() + Duration::minutes(5),
+        ..Default::default()
+      },
+      client_data: webauthn::parse_client_data(&client_data_json(
+        webauthn::CEREMONY_GET,
+        CHALLENGE,
+        ORIGIN,
+      ))
+      .unwrap(),
+    }
+  }
+
+  fn user<'a>(
+    mock_repository: &'a MockUserRepository,
+    encoded: &'a Encoded,
+    db_data: AssertionData,
+  ) -> UserSignInStateIn<'a, MockUserRepository> {
+    User {
+      repository: mock_repository,
+      state: WebAuthnSignIn {
+        request: Request {
+          credential_id: CREDENTIAL_ID_B64,
+          client_data_json: &encoded.client_data_json,
+          authenticator_data: &encoded.authenticator_data,
+          signature: SIGNATURE,
+          rp_id: RP_ID,
+          origin: ORIGIN,
+          ip_address: Some(IP_ADDRESS),
+          user_agent: Some(USER_AGENT),
+          lockout: Lockout::default(),
+        },
+        db_data,
+        assertion_checked: AssertionNotChecked,
+      },
+    }
+  }
+
+  /// Checks the signature of the authenticator data of `sign_count` followed
+  /// by the stubbed client data hash.
+  fn mock_primitives(sign_count: u32, valid: bool) -> MockPrimitives {
+    let mut mock_primitives = mock_sha256();
+    let signed_data = [
+      authenticator_data(&RP_ID_HASH, FLAGS_ASSERTION, sign_count, None),
+      OTHER_HASH.to_vec(),
+    ]
+    .concat();
+
+    mock_primitives
+      .expect_verify_es256()
+      .times(1)
+      .with(
+        predicate::eq(PUBLIC_KEY.to_vec()),
+        predicate::eq(signed_data),
+        predicate::eq(URL_SAFE_NO_PAD.decode(SIGNATURE).unwrap()),
+      )
+      .returning(move |_, _, _| valid);
+
+    mock_primitives
+  }
+
+  fn store_sign_in_event(succeeded: bool) -> StoreSignInEvent {
+    StoreSignInEvent {
+      calls: 1,
+      param_event: NewSignInEvent {
+        profile_id: ID.to_string(),
+        method: SignInMethod::Passkey,
+        succeeded,
+        ip_address: Some(IP_ADDRESS.to_string()),
+        user_agent: Some(USER_AGENT.to_string()),
+      },
+      fn_returning: |_| Ok(()),
+    }
+  }
+
+  fn successful_expectations() -> Expectations<'static> {
+    Expectations {
+      use_verification_token: Some(UseVerificationToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(true),
+      }),
+      update_webauthn_sign_count: Some(UpdateWebAuthnSignCount {
+        calls: 1,
+        param_id: 1,
+        param_sign_count: 8,
+        fn_returning: |_, _| Ok(()),
+      }),
+      register_sign_in: Some(RegisterSignIn {
+        calls: 1,
+        param_profile_id: ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      store_sign_in_event: Some(store_sign_in_event(true)),
+      ..Default::default()
+    }
+  }
+
+  #[tokio::test]
+  async fn test_check_valid_assertion() {
+    let mock_repository = build_mock_user_repository(successful_expectations());
+    let encoded = encoded(8);
+
+    let sut = user(&mock_repository, &encoded, mock_db_data(7))
+      .check_assertion(&mock_primitives(8, true))
+      .await
+      .unwrap();
+
+    assert_eq!(sut.state.assertion_checked, AssertionChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_valid_assertion_cancels_deletion() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      cancel_deletion: Some(CancelDeletion {
+        calls: 1,
+        param_profile_id: ID.to_string(),
+        fn_returning: |_| Ok(()),
+      }),
+      ..successful_expectations()
+    });
+    let encoded = encoded(8);
+    let mut db_data = mock_db_data(7);
+    db_data.user.requested_deletion = true;
+
+    let sut = user(&mock_repository, &encoded, db_data)
+      .check_assertion(&mock_primitives(8, true))
+      .await
+      .unwrap();
+
+    assert_eq!(sut.state.assertion_checked, AssertionChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_assertion_without_counter() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      update_webauthn_sign_count: Some(UpdateWebAuthnSignCount {
+        calls: 1,
+        param_id: 1,
+        param_sign_count: 0,
+        fn_returning: |_, _| Ok(()),
+      }),
+      ..successful_expectations()
+    });
+    let encoded = encoded(0);
+
+    let sut = user(&mock_repository, &encoded, mock_db_data(0))
+      .check_assertion(&mock_primitives(0, true))
+      .await
+      .unwrap();
+
+    assert_eq!(sut.state.assertion_checked, AssertionChecked(true));
+  }
+
+  #[tokio::test]
+  async fn test_check_assertion_with_invalid_signature() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      ..Default::default()
+    });
+    let encoded = encoded(8);
+
+    let sut = user(&mock_repository, &encoded, mock_db_data(7))
+      .check_assertion(&mock_primitives(8, false))
+      .await
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::unauthenticated("invalid passkey signature"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_assertion_with_counter_going_back() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      ..Default::default()
+    });
+    let encoded = encoded(7);
+
+    let sut = user(&mock_repository, &encoded, mock_db_data(7))
+      .check_assertion(&mock_primitives(7, true))
+      .await
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::unauthenticated(
+        "passkey signature counter did not increase"
+      ))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_challenge_of_another_profile() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let encoded = encoded(8);
+    let mut db_data = mock_db_data(7);
+    db_data.token.profile_id = String::from("a43f9ba5-3a79-4ef2-a4c6-6a2e1b0d8c5e");
+
+    let sut = user(&mock_repository, &encoded, db_data)
+      .check_assertion(&MockPrimitives::new())
+      .await
+      .err();
+
+    assert_eq!(sut, Some(AppError::unauthenticated(INVALID_CHALLENGE)));
+  }
+
+  #[tokio::test]
+  async fn test_check_expired_challenge() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let encoded = encoded(8);
+    let mut db_data = mock_db_data(7);
+    db_data.token.expires_at = Utc::now().naive_utc() - Duration::minutes(1);
+
+    let sut = user(&mock_repository, &encoded, db_data)
+      .check_assertion(&MockPrimitives::new())
+      .await
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::unauthenticated("WebAuthn challenge expired"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_assertion_from_another_origin() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      ..Default::default()
+    });
+    let encoded = encoded(8);
+    let mut db_data = mock_db_data(7);
+    db_data.client_data.origin = String::from("https://evil.example");
+
+    let sut = user(&mock_repository, &encoded, db_data)
+      .check_assertion(&MockPrimitives::new())
+      .await
+      .err();
+
+    assert_eq!(
+      sut,
+      Some(AppError::unauthenticated("WebAuthn origin not allowed"))
+    );
+  }
+
+  #[tokio::test]
+  async fn test_check_assertion_of_locked_account() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      ..Default::default()
+    });
+    let encoded = encoded(8);
+    let mut db_data = mock_db_data(7);
+    db_data.user.blocked_by_attempts = true;
+    db_data.user.blocked_at = Some(Utc::now().naive_utc());
+
+    let sut = user(&mock_repository, &encoded, db_data)
+      .check_assertion(&mock_sha256())
+      .await
+      .err();
+
+    assert_eq!(sut, Some(AppError::locked(ACCOUNT_LOCKED)));
+  }
+
+  #[tokio::test]
+  async fn test_check_assertion_of_disabled_account() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      store_sign_in_event: Some(store_sign_in_event(false)),
+      ..Default::default()
+    });
+    let encoded = encoded(8);
+    let mut db_data = mock_db_data(7);
+    db_data.user.account_disabled = true;
+
+    let sut = user(&mock_repository, &encoded, db_data)
+      .check_assertion(&mock_primitives(8, true))
+      .await
+      .err();
+
+    assert_eq!(sut, Some(AppError::permission_denied(ACCOUNT_DISABLED)));
+  }
+
+  #[tokio::test]
+  async fn test_check_concurrently_used_challenge() {
+    let mock_repository = build_mock_user_repository(Expectations {
+      use_verification_token: Some(UseVerificationToken {
+        calls: 1,
+        param_id: 1,
+        fn_returning: |_| Ok(false),
+      }),
+      ..Default::default()
+    });
+    let encoded = encoded(8);
+
+    let sut = user(&mock_repository, &encoded, mock_db_data(7))
+      .check_assertion(&mock_primitives(8, true))
+      .await
+      .err();
+
+    assert_eq!(sut, Some(AppError::unauthenticated(CHALLENGE_USED)));
+  }
+}
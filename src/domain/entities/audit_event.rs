@@ -0,0 +1,110 @@
+use chrono::NaiveDateTime;
+use serde_json::Value;
+
+use crate::domain::error::AppError;
+
+/// Actions recorded in the audit log.
+pub mod actions {
+  pub const REGISTER: &str = "user.register";
+  pub const SIGN_IN: &str = "user.sign_in";
+  pub const CHANGE_PASSWORD: &str = "user.change_password";
+  pub const RESET_PASSWORD: &str = "user.reset_password";
+  pub const REVOKE_SESSION: &str = "user.sessions.revoke";
+  pub const REVOKE_OTHER_SESSIONS: &str = "user.sessions.revoke_others";
+  pub const LIST_USERS: &str = "admin.users.list";
+  pub const VIEW_USER: &str = "admin.users.view";
+  pub const DISABLE_USER: &str = "admin.users.disable";
+  pub const ENABLE_USER: &str = "admin.users.enable";
+  pub const FORCE_PASSWORD_RESET: &str = "admin.users.force_password_reset";
+  pub const UNLOCK_USER: &str = "admin.users.unlock";
+  pub const GRANT_ROLE: &str = "admin.users.grant_role";
+  pub const REVOKE_ROLE: &str = "admin.users.revoke_role";
+  pub const REGISTER_OAUTH_CLIENT: &str = "admin.oauth_clients.register";
+  pub const LIST_AUDIT_EVENTS: &str = "admin.audit_events.list";
+  pub const VERIFY_AUDIT_EVENTS: &str = "admin.audit_events.verify";
+  pub const PERMISSION_DENIED: &str = "access.permission_denied";
+}
+
+#[derive(Default, PartialEq, Debug, Clone, Copy)]
+pub enum AuditOutcome {
+  #[default]
+  Success,
+  Failure,
+}
+
+impl AuditOutcome {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      AuditOutcome::Success => "success",
+      AuditOutcome::Failure => "failure",
+    }
+  }
+}
+
+impl TryFrom<&str> for AuditOutcome {
+  type Error = AppError;
+
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    match value {
+      "success" => Ok(AuditOutcome::Success),
+      "failure" => Ok(AuditOutcome::Failure),
+      _ => Err(AppError::invalid_argument(format!(
+        "unknown outcome {}, use success or failure",
+        value
+      ))),
+    }
+  }
+}
+
+/// Security relevant action, who did it, to whom and from where.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct NewAuditEvent {
+  /// Profile acting, unknown for failed sign-ins and registrations.
+  pub actor_id: Option<String>,
+  /// Profile the action was applied to.
+  pub subject_id: Option<String>,
+  pub action: String,
+  pub outcome: AuditOutcome,
+  pub ip_address: Option<String>,
+  pub user_agent: Option<String>,
+  pub metadata: Value,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct AuditEvent {
+  pub id: i64,
+  pub actor_id: Option<String>,
+  pub subject_id: Option<String>,
+  pub action: String,
+  pub outcome: AuditOutcome,
+  pub ip_address: Option<String>,
+  pub user_agent: Option<String>,
+  pub metadata: Value,
+  pub created_at: NaiveDateTime,
+  /// Hash of the previous event and this one, chaining the whole log.
+  pub hash: String,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct AuditEventFilter {
+  pub actor_id: Option<String>,
+  pub subject_id: Option<String>,
+  pub action: Option<String>,
+  pub outcome: Option<AuditOutcome>,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct AuditEventPage {
+  pub events: Vec<AuditEvent>,
+  pub page: i64,
+  pub per_page: i64,
+  pub total: i64,
+}
+
+/// Result of recomputing the hash chain of the audit log.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct AuditChainVerification {
+  pub checked: i64,
+  /// First event whose hash does not match its content or predecessor.
+  pub first_invalid_id: Option<i64>,
+}
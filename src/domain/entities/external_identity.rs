@@ -0,0 +1,95 @@
+use chrono::NaiveDateTime;
+
+use super::user::PublicUserData;
+
+/// Account of a user at an external identity provider, `subject` being its
+/// `sub` claim there.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct ExternalIdentityData {
+  pub id: i32,
+  pub provider: String,
+  pub subject: String,
+  pub profile_id: String,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct NewExternalIdentity {
+  pub provider: String,
+  pub subject: String,
+  pub profile_id: String,
+  pub email: Option<String>,
+}
+
+/// Claims of an ID token received from an external provider.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct ExternalIdToken {
+  pub iss: String,
+  pub aud: Vec<String>,
+  pub exp: i64,
+  pub nonce: Option<String>,
+  pub sub: String,
+  pub email: Option<String>,
+  pub email_verified: bool,
+  pub name: Option<String>,
+  pub preferred_username: Option<String>,
+}
+
+/// What the provider told us about a user who has no profile yet, kept until
+/// the sign-up is completed.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct ExternalClaims {
+  pub subject: String,
+  pub email: String,
+  pub email_verified: bool,
+  pub name: Option<String>,
+  pub preferred_username: Option<String>,
+}
+
+/// Sign-in started with an external provider, `state` being only stored
+/// hashed.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct NewExternalSignIn {
+  pub state: String,
+  pub provider: String,
+  pub code_verifier: String,
+  pub nonce: String,
+  pub expires_at: NaiveDateTime,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct ExternalSignInData {
+  pub id: i32,
+  pub provider: String,
+  pub code_verifier: String,
+  pub nonce: String,
+  pub expires_at: NaiveDateTime,
+  pub used: bool,
+  /// Set when the provider account matched no profile.
+  pub claims: Option<ExternalClaims>,
+  pub completed: bool,
+}
+
+/// Returned when starting a sign-in, to build the provider authorization URL.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct ExternalSignInStart {
+  pub state: String,
+  pub code_challenge: String,
+  pub nonce: String,
+}
+
+/// Outcome of coming back from a provider: either the profile the account is
+/// linked to, or what is needed to create one.
+#[derive(PartialEq, Debug)]
+pub enum ExternalAccount {
+  SignedIn(PublicUserData),
+  SignUpPending(ExternalClaims),
+}
+
+/// Sign-up of a provider account, `username` being generated from its
+/// claims.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct ExternalSignUpData {
+  pub provider: String,
+  pub claims: ExternalClaims,
+  pub username: String,
+}
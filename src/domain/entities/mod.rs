@@ -1 +1,11 @@
+pub mod audit_event;
+pub mod external_identity;
+pub mod oauth;
+pub mod refresh_token;
+pub mod role;
+pub mod session;
+pub mod sign_in_event;
+pub mod totp;
 pub mod user;
+pub mod verification_token;
+pub mod webauthn;
@@ -0,0 +1,65 @@
+use chrono::NaiveDateTime;
+
+/// Application signing users in through our OpenID Connect provider.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct OAuthClientData {
+  pub id: i32,
+  pub client_id: String,
+  pub name: String,
+  pub redirect_uris: Vec<String>,
+  /// Confidential clients authenticate with a secret, public ones only with
+  /// PKCE.
+  pub secret_hash: Option<String>,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct NewOAuthClient {
+  pub client_id: String,
+  pub name: String,
+  pub redirect_uris: Vec<String>,
+  pub secret_hash: Option<String>,
+}
+
+/// Returned once on registration, the secret is only stored hashed.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct OAuthClientCredentials {
+  pub client_id: String,
+  pub client_secret: Option<String>,
+  pub name: String,
+  pub redirect_uris: Vec<String>,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct NewAuthorizationCode {
+  pub code: String,
+  pub client_id: String,
+  pub profile_id: String,
+  pub redirect_uri: String,
+  pub scope: String,
+  /// Base64url SHA-256 of the PKCE code verifier.
+  pub code_challenge: String,
+  pub nonce: Option<String>,
+  pub expires_at: NaiveDateTime,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct AuthorizationCodeData {
+  pub id: i32,
+  pub client_id: String,
+  pub profile_id: String,
+  pub redirect_uri: String,
+  pub scope: String,
+  pub code_challenge: String,
+  pub nonce: Option<String>,
+  pub expires_at: NaiveDateTime,
+  pub used: bool,
+}
+
+/// What a redeemed authorization code lets the client be issued tokens for.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct AuthorizationGrant {
+  pub profile_id: String,
+  pub client_id: String,
+  pub scope: String,
+  pub nonce: Option<String>,
+}
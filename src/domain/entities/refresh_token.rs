@@ -0,0 +1,20 @@
+use chrono::NaiveDateTime;
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct NewRefreshToken {
+  pub token: String,
+  pub family_id: String,
+  pub profile_id: String,
+  pub expires_at: NaiveDateTime,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct RefreshTokenData {
+  pub id: i32,
+  pub family_id: String,
+  pub profile_id: String,
+  pub expires_at: NaiveDateTime,
+  pub used: bool,
+  pub revoked: bool,
+  pub account_disabled: bool,
+}
@@ -0,0 +1,17 @@
+/// Role seeded with every administration permission.
+pub const ADMIN_ROLE: &str = "admin";
+
+/// Permissions routes and use cases require, as stored in `permissions`.
+pub mod permissions {
+  pub const USERS_ADMIN: &str = "users:admin";
+  pub const OAUTH_CLIENTS_ADMIN: &str = "oauth_clients:admin";
+  pub const AUDIT_EVENTS_READ: &str = "audit_events:read";
+}
+
+/// Roles of a profile and the permissions they grant, carried by its access
+/// tokens.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct Grants {
+  pub roles: Vec<String>,
+  pub permissions: Vec<String>,
+}
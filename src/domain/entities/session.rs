@@ -0,0 +1,23 @@
+use chrono::NaiveDateTime;
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct NewSession {
+  pub id: String,
+  pub profile_id: String,
+  /// Browser and system read from the user agent, for the user to tell
+  /// their devices apart.
+  pub device_label: String,
+  pub ip_address: Option<String>,
+  pub user_agent: Option<String>,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct Session {
+  pub id: String,
+  pub device_label: String,
+  pub ip_address: Option<String>,
+  pub user_agent: Option<String>,
+  pub created_at: NaiveDateTime,
+  /// Last time the session signed in or refreshed its tokens.
+  pub last_seen_at: NaiveDateTime,
+}
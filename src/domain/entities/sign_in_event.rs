@@ -0,0 +1,87 @@
+use chrono::NaiveDateTime;
+
+use super::user::UserColumns;
+use crate::domain::error::AppError;
+
+#[derive(Default, PartialEq, Debug, Clone, Copy)]
+pub enum SignInMethod {
+  #[default]
+  Username,
+  Email,
+  Telephone,
+  Passkey,
+  External,
+  /// Second factor of a password sign-in.
+  Mfa,
+}
+
+impl SignInMethod {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      SignInMethod::Username => "username",
+      SignInMethod::Email => "email",
+      SignInMethod::Telephone => "telephone",
+      SignInMethod::Passkey => "passkey",
+      SignInMethod::External => "external",
+      SignInMethod::Mfa => "mfa",
+    }
+  }
+}
+
+impl TryFrom<&UserColumns<'_>> for SignInMethod {
+  type Error = AppError;
+
+  fn try_from(value: &UserColumns<'_>) -> Result<Self, Self::Error> {
+    match value {
+      UserColumns::Username(_) => Ok(SignInMethod::Username),
+      UserColumns::Email(_) => Ok(SignInMethod::Email),
+      UserColumns::Telephone(_) => Ok(SignInMethod::Telephone),
+      UserColumns::Id(_) => Err(AppError::invalid_argument("sign in by id is not supported")),
+    }
+  }
+}
+
+impl TryFrom<&str> for SignInMethod {
+  type Error = AppError;
+
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    match value {
+      "username" => Ok(SignInMethod::Username),
+      "email" => Ok(SignInMethod::Email),
+      "telephone" => Ok(SignInMethod::Telephone),
+      "passkey" => Ok(SignInMethod::Passkey),
+      "external" => Ok(SignInMethod::External),
+      "mfa" => Ok(SignInMethod::Mfa),
+      _ => Err(AppError::internal(format!(
+        "unknown sign in method {}",
+        value
+      ))),
+    }
+  }
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct NewSignInEvent {
+  pub profile_id: String,
+  pub method: SignInMethod,
+  pub succeeded: bool,
+  pub ip_address: Option<String>,
+  pub user_agent: Option<String>,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct SignInEvent {
+  pub method: SignInMethod,
+  pub succeeded: bool,
+  pub ip_address: Option<String>,
+  pub user_agent: Option<String>,
+  pub created_at: NaiveDateTime,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct SignInEventPage {
+  pub events: Vec<SignInEvent>,
+  pub page: i64,
+  pub per_page: i64,
+  pub total: i64,
+}
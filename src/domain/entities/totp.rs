@@ -0,0 +1,17 @@
+/// TOTP authenticator of a profile, only asked for at sign-in once confirmed.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct TotpAuthenticatorData {
+  pub profile_id: String,
+  /// Base32 shared secret, as shown to the user at enrollment.
+  pub secret: String,
+  /// Time step of the last accepted code, so a code cannot be replayed.
+  pub last_used_step: Option<i64>,
+  pub confirmed: bool,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct TotpEnrollment {
+  pub secret: String,
+  /// `otpauth://` URI to be rendered as a QR code for authenticator apps.
+  pub provisioning_uri: String,
+}
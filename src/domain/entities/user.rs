@@ -1,3 +1,7 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::domain::error::AppError;
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum UserColumns<'a> {
   Id(&'a str),
@@ -18,6 +22,20 @@ pub struct UserData {
   pub name: String,
   pub username: String,
   pub password: String,
+  pub account_disabled: bool,
+  pub requested_deletion: bool,
+  pub blocked_by_attempts: bool,
+  pub blocked_at: Option<NaiveDateTime>,
+  /// Whether the email the user was found by is verified; for other lookups,
+  /// whether any of the user emails is.
+  pub email_checked: bool,
+  /// Same as `email_checked` for the user telephones.
+  pub telephone_checked: bool,
+  /// Whether a confirmed TOTP authenticator is required at sign-in.
+  pub mfa_enabled: bool,
+  /// Set by an administrator, password sign-ins are refused until the
+  /// password is reset.
+  pub password_reset_required: bool,
 }
 
 #[derive(Debug, PartialEq)]
@@ -25,4 +43,110 @@ pub struct PublicUserData {
   pub id: String,
   pub username: String,
   pub name: String,
+  pub mfa_enabled: bool,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct Telephone {
+  pub number: String,
+  pub checked: bool,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct Email {
+  pub address: String,
+  pub checked: bool,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct Address {
+  pub street: String,
+  pub neighborhood: String,
+  pub postal_code: i32,
+  pub city: String,
+  pub state: String,
+  pub country: String,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct FullProfile {
+  pub id: String,
+  pub name: String,
+  pub username: String,
+  pub birth_date: NaiveDate,
+  pub gender: String,
+  pub telephones: Vec<Telephone>,
+  pub emails: Vec<Email>,
+  pub address: Address,
+}
+
+/// Order of the user listing, newest accounts first unless asked otherwise.
+#[derive(Default, PartialEq, Debug, Clone, Copy)]
+pub enum UserSort {
+  #[default]
+  CreatedAtDesc,
+  CreatedAtAsc,
+  UsernameAsc,
+  UsernameDesc,
+}
+
+impl UserSort {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      UserSort::CreatedAtDesc => "-created_at",
+      UserSort::CreatedAtAsc => "created_at",
+      UserSort::UsernameAsc => "username",
+      UserSort::UsernameDesc => "-username",
+    }
+  }
+}
+
+impl TryFrom<&str> for UserSort {
+  type Error = AppError;
+
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    match value {
+      "-created_at" => Ok(UserSort::CreatedAtDesc),
+      "created_at" => Ok(UserSort::CreatedAtAsc),
+      "username" => Ok(UserSort::UsernameAsc),
+      "-username" => Ok(UserSort::UsernameDesc),
+      _ => Err(AppError::invalid_argument(format!(
+        "unknown sort {}, use created_at or username, prefixed with - to reverse",
+        value
+      ))),
+    }
+  }
+}
+
+/// Narrows the user listing; usernames and emails match when they contain
+/// the given text, ignoring case, and the creation dates are inclusive.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct UserFilter {
+  pub username: Option<String>,
+  pub email: Option<String>,
+  pub created_from: Option<NaiveDate>,
+  pub created_to: Option<NaiveDate>,
+  pub sort: UserSort,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct UserSummary {
+  pub id: String,
+  pub name: String,
+  pub username: String,
+  pub emails: Vec<String>,
+  pub roles: Vec<String>,
+  pub account_disabled: bool,
+  pub blocked_by_attempts: bool,
+  pub requested_deletion: bool,
+  pub password_reset_required: bool,
+  pub created_at: NaiveDateTime,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct UserSummaryPage {
+  pub users: Vec<UserSummary>,
+  pub page: i64,
+  pub per_page: i64,
+  pub total: i64,
 }
@@ -0,0 +1,69 @@
+use chrono::NaiveDateTime;
+
+use crate::domain::error::AppError;
+
+/// What a verification token proves once it is redeemed.
+#[derive(Default, PartialEq, Debug, Clone, Copy)]
+pub enum VerificationPurpose {
+  #[default]
+  Email,
+  Telephone,
+  PasswordReset,
+  /// Challenge to be signed when registering a passkey.
+  WebAuthnRegistration,
+  /// Challenge to be signed when signing in with a passkey.
+  WebAuthnSignIn,
+}
+
+impl VerificationPurpose {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      VerificationPurpose::Email => "email",
+      VerificationPurpose::Telephone => "telephone",
+      VerificationPurpose::PasswordReset => "password_reset",
+      VerificationPurpose::WebAuthnRegistration => "webauthn_registration",
+      VerificationPurpose::WebAuthnSignIn => "webauthn_sign_in",
+    }
+  }
+}
+
+impl TryFrom<&str> for VerificationPurpose {
+  type Error = AppError;
+
+  fn try_from(value: &str) -> Result<Self, Self::Error> {
+    match value {
+      "email" => Ok(VerificationPurpose::Email),
+      "telephone" => Ok(VerificationPurpose::Telephone),
+      "password_reset" => Ok(VerificationPurpose::PasswordReset),
+      "webauthn_registration" => Ok(VerificationPurpose::WebAuthnRegistration),
+      "webauthn_sign_in" => Ok(VerificationPurpose::WebAuthnSignIn),
+      _ => Err(AppError::internal(format!(
+        "unknown verification purpose {}",
+        value
+      ))),
+    }
+  }
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct NewVerificationToken {
+  pub token: String,
+  pub purpose: VerificationPurpose,
+  pub profile_id: String,
+  /// Address or number being verified, or the relying party of a WebAuthn
+  /// challenge.
+  pub target: String,
+  pub expires_at: NaiveDateTime,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct VerificationTokenData {
+  pub id: i32,
+  pub purpose: VerificationPurpose,
+  pub profile_id: String,
+  pub target: String,
+  pub expires_at: NaiveDateTime,
+  pub used: bool,
+  /// Wrong guesses made against the token.
+  pub attempts: i32,
+}
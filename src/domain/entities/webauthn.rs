@@ -0,0 +1,35 @@
+/// Passkey of a profile, registered through WebAuthn.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct WebAuthnCredentialData {
+  pub id: i32,
+  /// Base64url credential id chosen by the authenticator.
+  pub credential_id: String,
+  pub profile_id: String,
+  /// Uncompressed P-256 point (SEC1), the only key type accepted.
+  pub public_key: Vec<u8>,
+  /// Signature counter last reported by the authenticator.
+  pub sign_count: i64,
+  pub name: String,
+}
+
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct NewWebAuthnCredential {
+  pub credential_id: String,
+  pub profile_id: String,
+  pub public_key: Vec<u8>,
+  pub sign_count: i64,
+  pub name: String,
+}
+
+/// Challenge issued for a registration or a sign-in ceremony, with what the
+/// client needs to call the authenticator.
+#[derive(Default, PartialEq, Debug, Clone)]
+pub struct WebAuthnChallenge {
+  pub challenge: String,
+  pub profile_id: String,
+  pub username: String,
+  pub name: String,
+  /// Passkeys the profile already has, to exclude on registration or to
+  /// pick from on sign-in.
+  pub credential_ids: Vec<String>,
+}
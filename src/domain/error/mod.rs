@@ -9,6 +9,8 @@ pub enum Code {
   PermissionDenied,
   Internal,
   Unauthenticated,
+  Locked,
+  TooManyRequests,
   DatabaseError,
   SQLError,
   OIError,
@@ -131,6 +133,22 @@ impl AppError {
       source: None,
     }
   }
+  pub fn locked(message: impl Into<String>) -> AppError {
+    Self {
+      code: Code::Locked,
+      message: message.into(),
+      details: None,
+      source: None,
+    }
+  }
+  pub fn too_many_requests(message: impl Into<String>) -> AppError {
+    Self {
+      code: Code::TooManyRequests,
+      message: message.into(),
+      details: None,
+      source: None,
+    }
+  }
   pub fn database_error(message: impl Into<String>) -> AppError {
     Self {
       code: Code::DatabaseError,
@@ -6,4 +6,7 @@ use crate::domain::error::AppError;
 pub trait Crypto: Sync + Send {
   fn hash_password(&self, password: &str) -> Result<String, AppError>;
   fn verify_password(&self, hash: &str, password: &str) -> Result<bool, AppError>;
+  /// Whether `hash` was made with another algorithm or parameters than the
+  /// ones new passwords are hashed with.
+  fn needs_rehash(&self, hash: &str) -> bool;
 }
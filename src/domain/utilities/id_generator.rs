@@ -3,4 +3,9 @@ use mockall::automock;
 #[automock]
 pub trait IDGenerator: Sync + Send {
   fn new_uuid(&self) -> String;
+  fn new_secret(&self) -> String;
+  /// Random digits, short enough to be typed from a text message.
+  fn new_numeric_code(&self, length: usize) -> String;
+  /// Base32 secret shared with TOTP authenticator apps.
+  fn new_totp_secret(&self) -> String;
 }
@@ -1,8 +1,10 @@
-use self::{crypto::Crypto, id_generator::IDGenerator};
+use self::{crypto::Crypto, id_generator::IDGenerator, primitives::Primitives};
 pub mod crypto;
 pub mod id_generator;
+pub mod primitives;
 
-pub struct Utilities<C: Crypto, ID: IDGenerator> {
+pub struct Utilities<C: Crypto, ID: IDGenerator, P: Primitives> {
   pub crypto: C,
   pub id_generator: ID,
+  pub primitives: P,
 }
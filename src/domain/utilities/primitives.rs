@@ -0,0 +1,14 @@
+use mockall::automock;
+
+use crate::domain::error::AppError;
+
+/// Hashes and codes the MFA and passkey checks are built on.
+#[automock]
+pub trait Primitives: Sync + Send {
+  /// RFC 6238 code of the base32 `secret` for the given time step.
+  fn totp_code(&self, secret: &str, step: i64) -> Result<String, AppError>;
+  fn sha256(&self, data: &[u8]) -> Vec<u8>;
+  /// ES256 check of a DER `signature` against an uncompressed SEC1 public
+  /// key. A malformed key is just a mismatch.
+  fn verify_es256(&self, public_key: &[u8], message: &[u8], signature: &[u8]) -> bool;
+}
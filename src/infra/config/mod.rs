@@ -3,4 +3,5 @@ pub mod database;
 pub mod log;
 pub mod routes;
 pub mod services;
+pub mod settings;
 pub mod utilities;
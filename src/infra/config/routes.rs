@@ -6,12 +6,51 @@ use utoipa_swagger_ui::SwaggerUi;
 pub fn routes_config(cfg: &mut web::ServiceConfig) {
   cfg
     .service(routers::index_get)
+    .service(routers::jwks_get)
+    .service(routers::openid_configuration_get)
     .service(v1::auth::controller::sign_in)
+    .service(v1::auth::controller::verify_mfa)
     .service(v1::auth::controller::register)
     .service(v1::auth::controller::change_password)
+    .service(v1::auth::controller::refresh)
+    .service(v1::auth::controller::logout)
+    .service(v1::auth::controller::verify_email)
+    .service(v1::auth::controller::forgot_password)
+    .service(v1::auth::controller::reset_password)
+    .service(v1::auth::controller::webauthn_registration_options)
+    .service(v1::auth::controller::register_webauthn)
+    .service(v1::auth::controller::webauthn_sign_in_options)
+    .service(v1::auth::controller::webauthn_sign_in)
+    .service(v1::auth::controller::external_authorize)
+    .service(v1::auth::controller::external_callback)
+    .service(v1::auth::controller::external_sign_up)
+    .service(v1::users::controller::get_me)
+    .service(v1::users::controller::update_me)
+    .service(v1::users::controller::list_my_sign_ins)
+    .service(v1::users::controller::list_my_sessions)
+    .service(v1::users::controller::revoke_my_session)
+    .service(v1::users::controller::revoke_my_other_sessions)
+    .service(v1::users::controller::delete_me)
+    .service(v1::users::controller::send_telephone_code)
+    .service(v1::users::controller::verify_telephone)
+    .service(v1::users::controller::enroll_totp)
+    .service(v1::users::controller::confirm_totp)
+    .service(v1::admin::controller::list_users)
+    .service(v1::admin::controller::get_user)
+    .service(v1::admin::controller::disable_user)
+    .service(v1::admin::controller::enable_user)
+    .service(v1::admin::controller::force_password_reset)
+    .service(v1::admin::controller::unlock_user)
+    .service(v1::admin::controller::grant_role)
+    .service(v1::admin::controller::revoke_role)
+    .service(v1::admin::controller::register_oauth_client)
+    .service(v1::admin::controller::verify_audit_events)
+    .service(v1::admin::controller::list_audit_events)
+    .service(v1::oauth::controller::authorize)
+    .service(v1::oauth::controller::exchange_code)
+    .service(v1::oauth::controller::userinfo)
     .service(SwaggerUi::new("/swagger-ui/{_:.*}").url("/api-docs/openapi.json", ApiDoc::openapi()))
     .service(actix_files::Files::new("/coverage", "./coverage/html").index_file("index.html"))
     .service(actix_files::Files::new("/docs", "./docs").index_file("index.html"))
-    .service(actix_files::Files::new("/logs", "./logs").show_files_listing())
     .default_service(web::to(routers::not_found));
 }
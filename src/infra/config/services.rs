@@ -1,19 +1,377 @@
-use crate::{adapter::services::jwt::JWTService, application::services::Services};
+use std::{
+  fs,
+  path::PathBuf,
+  sync::{Arc, OnceLock},
+  time::Duration,
+};
+
+use crate::{
+  adapter::{
+    routers::helpers::{actix_authenticated_user::AppServices, client_ip::ClientIpConfig},
+    services::{
+      audit_log::AuditLogDB,
+      email::{FileEmailSender, SmtpEmailSender, SmtpSecurity},
+      http_client::{HttpClient, MAX_BODY_BYTES},
+      identity_provider::HttpIdentityProviderClient,
+      jwt::JWTService,
+      jwt_keys::{JWTKeyPem, JWTKeys},
+      rate_limit::{RateLimitStoreDB, RateLimitStoreMemory},
+      sms::{FileSmsSender, HttpSmsSender},
+      token_revocation::TokenRevocationStoreDB,
+    },
+  },
+  application::services::{
+    notification::{email_sender::EmailSender, sms_sender::SmsSender},
+    security::{
+      email_verification_policy::EmailVerificationPolicy,
+      external_identity_policy::{ExternalIdentityPolicy, ExternalProvider},
+      mfa_policy::MfaPolicy,
+      oidc_policy::OidcPolicy,
+      password_reset_policy::PasswordResetPolicy,
+      rate_limit::{RateLimit, RateLimitStore},
+      sign_in_policy::SignInPolicy,
+      telephone_verification_policy::TelephoneVerificationPolicy,
+      token_policy::TokenPolicy,
+      token_revocation::TokenRevocationStore,
+      webauthn_policy::WebAuthnPolicy,
+    },
+    Services,
+  },
+  domain::error::AppError,
+  infra::http::rate_limit::RateLimiter,
+};
 use actix_web::web;
+use jsonwebtoken::{get_current_timestamp, Algorithm};
+use sqlx::{Pool, Postgres};
+
+use super::settings::{
+  settings, EmailDelivery, JwtAlgorithm, JwtSettings, RateLimitStoreKind, SmsDelivery, SmtpTls,
+};
+
+static JWT_KEYS: OnceLock<JWTKeys> = OnceLock::new();
+static HTTP_CLIENT: OnceLock<HttpClient> = OnceLock::new();
+static UNVERSIONED_UNTIL: OnceLock<usize> = OnceLock::new();
 
-pub fn services_config(cfg: &mut web::ServiceConfig) {
-  cfg.app_data(web::Data::new(get_services()));
+pub fn services_config(postgres_pool: Pool<Postgres>) -> impl FnOnce(&mut web::ServiceConfig) {
+  move |cfg| {
+    cfg.app_data(web::Data::new(get_services(postgres_pool)));
+    cfg.app_data(web::Data::new(ClientIpConfig {
+      trust_forwarded_for: settings().http.trust_forwarded_for,
+    }));
+  }
 }
 
-pub fn get_services<'a>() -> Services<JWTService<'a>> {
+pub fn get_services(postgres_pool: Pool<Postgres>) -> AppServices {
   Services {
-    token: get_jwt_service(),
+    token: get_jwt_service(TokenRevocationStoreDB {
+      pool: postgres_pool.clone(),
+    }),
+    email: get_email_sender(),
+    sms: get_sms_sender(),
+    identity_provider: get_identity_provider_client(),
+    audit: AuditLogDB {
+      pool: postgres_pool,
+    },
+    token_policy: get_token_policy(),
+    sign_in_policy: get_sign_in_policy(),
+    email_verification_policy: get_email_verification_policy(),
+    telephone_verification_policy: get_telephone_verification_policy(),
+    password_reset_policy: get_password_reset_policy(),
+    mfa_policy: get_mfa_policy(),
+    webauthn_policy: get_webauthn_policy(),
+    external_identity_policy: get_external_identity_policy(),
+    oidc_policy: get_oidc_policy(),
+  }
+}
+
+pub fn get_email_sender() -> Box<dyn EmailSender> {
+  let email = &settings().email;
+
+  match email.delivery {
+    EmailDelivery::File => Box::new(FileEmailSender {
+      outbox_dir: PathBuf::from(&email.outbox_dir),
+    }),
+    EmailDelivery::Smtp => {
+      let smtp = &email.smtp;
+      let security = match smtp.tls {
+        SmtpTls::Starttls => SmtpSecurity::StartTls,
+        SmtpTls::Tls => SmtpSecurity::Tls,
+        SmtpTls::None => SmtpSecurity::None,
+      };
+      let credentials =
+        (!smtp.username.is_empty()).then_some((smtp.username.as_str(), smtp.password.as_str()));
+
+      Box::new(
+        SmtpEmailSender::new(
+          &smtp.host,
+          smtp.port,
+          security,
+          credentials,
+          &smtp.from,
+          Duration::from_secs(smtp.timeout_sec),
+        )
+        .expect("SMTP email sender setup failed!"),
+      )
+    }
   }
 }
 
-pub fn get_jwt_service<'a>() -> JWTService<'a> {
+pub fn get_sms_sender() -> Box<dyn SmsSender> {
+  let sms = &settings().sms;
+
+  match sms.delivery {
+    SmsDelivery::File => Box::new(FileSmsSender {
+      outbox_dir: PathBuf::from(&sms.outbox_dir),
+    }),
+    SmsDelivery::Http => {
+      let gateway = &sms.gateway;
+
+      Box::new(HttpSmsSender {
+        http: HttpClient::new(Duration::from_secs(gateway.timeout_sec), MAX_BODY_BYTES)
+          .expect("HTTP client setup failed!"),
+        url: gateway.url.clone(),
+        username: gateway.username.clone(),
+        password: gateway.password.clone(),
+        from: gateway.from.clone(),
+      })
+    }
+  }
+}
+
+pub fn get_jwt_service<S: TokenRevocationStore>(revocation_store: S) -> JWTService<'static, S> {
+  let jwt = &settings().jwt;
+
   JWTService {
-    iss: env!("CARGO_PKG_NAME").to_string(),
-    key: b"JWT_SECRET",
+    iss: jwt.issuer.clone(),
+    keys: get_jwt_keys(),
+    revocation_store,
+    unversioned_until: *UNVERSIONED_UNTIL
+      .get_or_init(|| (get_current_timestamp() + 60 * jwt.ttl.unversioned_min) as usize),
+  }
+}
+
+pub fn get_jwt_keys() -> &'static JWTKeys {
+  JWT_KEYS.get_or_init(|| load_jwt_keys(&settings().jwt).expect("JWT keys loading failed!"))
+}
+
+fn load_jwt_keys(jwt: &JwtSettings) -> Result<JWTKeys, AppError> {
+  let signing_key = match &jwt.signing_key {
+    Some(signing_key) => signing_key,
+    None => return Ok(JWTKeys::from_secret(jwt.secret.as_bytes())),
+  };
+
+  let mut pems = Vec::with_capacity(jwt.keys.len());
+  for key in &jwt.keys {
+    let private_pem = match &key.private_key_file {
+      Some(file) => Some(fs::read(file)?),
+      None => None,
+    };
+    pems.push((key, private_pem, fs::read(&key.public_key_file)?));
+  }
+
+  let keys: Vec<JWTKeyPem> = pems
+    .iter()
+    .map(|(key, private_pem, public_pem)| JWTKeyPem {
+      kid: &key.kid,
+      algorithm: match key.algorithm {
+        JwtAlgorithm::RS256 => Algorithm::RS256,
+        JwtAlgorithm::EdDSA => Algorithm::EdDSA,
+      },
+      private_pem: private_pem.as_deref(),
+      public_pem,
+    })
+    .collect();
+
+  JWTKeys::from_pem(signing_key, &keys)
+}
+
+pub fn get_token_policy() -> TokenPolicy {
+  let jwt = &settings().jwt;
+
+  TokenPolicy {
+    authentication_audience: jwt.audiences.authentication.clone(),
+    mfa_audience: jwt.audiences.mfa.clone(),
+    access_token_expiration_min: jwt.ttl.access_min,
+    refresh_token_expiration_min: jwt.ttl.refresh_min,
+  }
+}
+
+pub fn get_sign_in_policy() -> SignInPolicy {
+  let sign_in = &settings().sign_in;
+
+  SignInPolicy {
+    max_failed_attempts: sign_in.max_failed_attempts,
+    lockout_duration_min: sign_in.lockout_min,
+  }
+}
+
+pub fn get_email_verification_policy() -> EmailVerificationPolicy {
+  let email = &settings().email;
+
+  EmailVerificationPolicy {
+    token_expiration_min: email.verification_ttl_min,
+    required_for_sign_in: email.require_verified_for_sign_in,
+  }
+}
+
+pub fn get_telephone_verification_policy() -> TelephoneVerificationPolicy {
+  let sms = &settings().sms;
+
+  TelephoneVerificationPolicy {
+    code_length: sms.code_length,
+    code_expiration_min: sms.code_ttl_min,
+    max_codes_per_hour: sms.max_codes_per_hour,
+    max_attempts: sms.max_attempts,
+    required_for_sign_in: sms.require_verified_for_sign_in,
+  }
+}
+
+pub fn get_password_reset_policy() -> PasswordResetPolicy {
+  PasswordResetPolicy {
+    token_expiration_min: settings().password_reset.token_ttl_min,
+  }
+}
+
+pub fn get_mfa_policy() -> MfaPolicy {
+  let mfa = &settings().mfa;
+
+  MfaPolicy {
+    issuer: mfa.issuer.clone(),
+    token_expiration_min: mfa.token_ttl_min,
+    recovery_code_count: mfa.recovery_codes,
+    allowed_drift_steps: mfa.allowed_drift_steps,
+  }
+}
+
+pub fn get_webauthn_policy() -> WebAuthnPolicy {
+  let webauthn = &settings().webauthn;
+
+  WebAuthnPolicy {
+    rp_id: webauthn.rp_id.clone(),
+    rp_name: webauthn.rp_name.clone(),
+    origin: webauthn.origin.clone(),
+    challenge_expiration_min: webauthn.challenge_ttl_min,
+  }
+}
+
+pub fn get_identity_provider_client() -> HttpIdentityProviderClient {
+  let http = HTTP_CLIENT.get_or_init(|| {
+    HttpClient::new(
+      Duration::from_secs(settings().external_identity.http_timeout_sec),
+      MAX_BODY_BYTES,
+    )
+    .expect("HTTP client setup failed!")
+  });
+
+  HttpIdentityProviderClient { http: http.clone() }
+}
+
+pub fn get_external_identity_policy() -> ExternalIdentityPolicy {
+  let external_identity = &settings().external_identity;
+
+  ExternalIdentityPolicy {
+    providers: external_identity
+      .providers
+      .iter()
+      .map(|provider| ExternalProvider {
+        name: provider.name.clone(),
+        issuer: provider.issuer.clone(),
+        client_id: provider.client_id.clone(),
+        client_secret: provider.client_secret.clone(),
+        authorization_endpoint: provider.authorization_endpoint.clone(),
+        token_endpoint: provider.token_endpoint.clone(),
+        jwks_uri: provider.jwks_uri.clone(),
+        redirect_uri: provider.redirect_uri.clone(),
+        scope: provider.scope.clone(),
+      })
+      .collect(),
+    state_expiration_min: external_identity.state_ttl_min,
+    sign_up_expiration_min: external_identity.sign_up_ttl_min,
+  }
+}
+
+pub fn get_oidc_policy() -> OidcPolicy {
+  let oidc = &settings().oidc;
+
+  OidcPolicy {
+    userinfo_audience: oidc.userinfo_audience.clone(),
+    code_expiration_min: oidc.code_ttl_min,
+    token_expiration_min: oidc.token_ttl_min,
+  }
+}
+
+pub fn get_rate_limiter(postgres_pool: Pool<Postgres>) -> RateLimiter {
+  let rate_limit = &settings().rate_limit;
+  let store: Arc<dyn RateLimitStore> = match rate_limit.store {
+    RateLimitStoreKind::Memory => Arc::new(RateLimitStoreMemory::default()),
+    RateLimitStoreKind::Postgres => Arc::new(RateLimitStoreDB {
+      pool: postgres_pool,
+    }),
+  };
+
+  RateLimiter {
+    store,
+    paths: rate_limit.paths.clone(),
+    trust_forwarded_for: settings().http.trust_forwarded_for,
+    ip: RateLimit {
+      capacity: rate_limit.ip.capacity,
+      refill_per_min: rate_limit.ip.refill_per_min,
+    },
+    identifier: RateLimit {
+      capacity: rate_limit.identifier.capacity,
+      refill_per_min: rate_limit.identifier.refill_per_min,
+    },
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::infra::config::settings::JwtKeySettings;
+
+  const FIXTURES: &str = "src/adapter/services/fixtures";
+
+  #[test]
+  fn test_load_jwt_keys_from_files() {
+    let jwt = JwtSettings {
+      signing_key: Some(String::from("ed25519")),
+      keys: vec![
+        JwtKeySettings {
+          kid: String::from("ed25519"),
+          algorithm: JwtAlgorithm::EdDSA,
+          private_key_file: Some(format!("{}/ed25519_private.pem", FIXTURES)),
+          public_key_file: format!("{}/ed25519_public.pem", FIXTURES),
+        },
+        JwtKeySettings {
+          kid: String::from("rsa"),
+          algorithm: JwtAlgorithm::RS256,
+          private_key_file: None,
+          public_key_file: format!("{}/rsa_public.pem", FIXTURES),
+        },
+      ],
+      ..Default::default()
+    };
+
+    let keys = load_jwt_keys(&jwt).unwrap();
+
+    assert_eq!(keys.header().alg, Algorithm::EdDSA);
+    assert_eq!(keys.header().kid, Some(String::from("ed25519")));
+    assert_eq!(keys.jwks().keys.len(), 2);
+  }
+
+  #[test]
+  fn test_load_jwt_keys_with_missing_file() {
+    let jwt = JwtSettings {
+      signing_key: Some(String::from("rsa")),
+      keys: vec![JwtKeySettings {
+        kid: String::from("rsa"),
+        algorithm: JwtAlgorithm::RS256,
+        private_key_file: Some(format!("{}/missing.pem", FIXTURES)),
+        public_key_file: format!("{}/rsa_public.pem", FIXTURES),
+      }],
+      ..Default::default()
+    };
+
+    assert!(load_jwt_keys(&jwt).is_err());
   }
 }
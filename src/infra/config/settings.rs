@@ -0,0 +1,2085 @@
+use std::{env, fs, io::ErrorKind, sync::OnceLock};
+
+use serde::Deserialize;
+
+pub const DEFAULT_JWT_SECRET: &str = "JWT_SECRET";
+const DEFAULT_SETTINGS_FILE: &str = "config/settings.toml";
+
+static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+fn env_var(key: &str) -> Option<String> {
+  let value = env::var(key).ok();
+
+  // the test binary talks to a local database with the development defaults
+  #[cfg(test)]
+  if key == "APP_PROFILE" {
+    return value.or_else(|| Some(String::from("dev")));
+  }
+
+  value
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Profile {
+  Dev,
+  #[default]
+  Production,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct JwtAudiences {
+  pub authentication: String,
+  pub mfa: String,
+}
+
+impl Default for JwtAudiences {
+  fn default() -> Self {
+    Self {
+      authentication: String::from("authentication_user"),
+      mfa: String::from("mfa_pending"),
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct JwtTtl {
+  pub access_min: u64,
+  pub refresh_min: i64,
+  /// Minutes after startup tokens without a `ver` claim are still accepted.
+  pub unversioned_min: u64,
+}
+
+impl Default for JwtTtl {
+  fn default() -> Self {
+    Self {
+      access_min: 120,
+      refresh_min: 60 * 24 * 30,
+      unversioned_min: 120,
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum JwtAlgorithm {
+  RS256,
+  EdDSA,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct JwtKeySettings {
+  pub kid: String,
+  pub algorithm: JwtAlgorithm,
+  pub private_key_file: Option<String>,
+  pub public_key_file: String,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct JwtSettings {
+  pub secret: String,
+  pub issuer: String,
+  pub audiences: JwtAudiences,
+  pub ttl: JwtTtl,
+  /// kid of the key used to sign new tokens, HS256 with `secret` when unset.
+  pub signing_key: Option<String>,
+  pub keys: Vec<JwtKeySettings>,
+}
+
+impl Default for JwtSettings {
+  fn default() -> Self {
+    Self {
+      secret: DEFAULT_JWT_SECRET.to_string(),
+      issuer: env!("CARGO_PKG_NAME").to_string(),
+      audiences: JwtAudiences::default(),
+      ttl: JwtTtl::default(),
+      signing_key: None,
+      keys: Vec::new(),
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SignInSettings {
+  pub max_failed_attempts: i32,
+  pub lockout_min: i64,
+}
+
+impl Default for SignInSettings {
+  fn default() -> Self {
+    Self {
+      max_failed_attempts: 5,
+      lockout_min: 15,
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PasswordHashAlgorithm {
+  #[default]
+  Argon2id,
+  Bcrypt,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct Argon2Settings {
+  pub memory_kib: u32,
+  pub iterations: u32,
+  pub parallelism: u32,
+}
+
+impl Default for Argon2Settings {
+  fn default() -> Self {
+    Self {
+      memory_kib: 19 * 1024,
+      iterations: 2,
+      parallelism: 1,
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PasswordHashingSettings {
+  /// Algorithm of new hashes, the other one is still verified and replaced on
+  /// the next sign-in.
+  pub algorithm: PasswordHashAlgorithm,
+  pub argon2: Argon2Settings,
+  pub bcrypt_cost: u32,
+}
+
+impl Default for PasswordHashingSettings {
+  fn default() -> Self {
+    Self {
+      algorithm: PasswordHashAlgorithm::default(),
+      argon2: Argon2Settings::default(),
+      bcrypt_cost: 8,
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct EmailSettings {
+  pub delivery: EmailDelivery,
+  /// Directory where outgoing emails are written as `.eml` files by the
+  /// `file` delivery.
+  pub outbox_dir: String,
+  pub smtp: SmtpSettings,
+  pub verification_ttl_min: i64,
+  pub require_verified_for_sign_in: bool,
+}
+
+impl Default for EmailSettings {
+  fn default() -> Self {
+    Self {
+      delivery: EmailDelivery::default(),
+      outbox_dir: String::from("outbox/email"),
+      smtp: SmtpSettings::default(),
+      verification_ttl_min: 60 * 24,
+      require_verified_for_sign_in: false,
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum EmailDelivery {
+  /// Writes to `outbox_dir`, only allowed in the dev profile.
+  #[default]
+  File,
+  Smtp,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SmtpTls {
+  #[default]
+  Starttls,
+  /// TLS from the first byte, usually on port 465.
+  Tls,
+  /// Plain text, only allowed for a relay on this machine.
+  None,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SmtpSettings {
+  pub host: String,
+  pub port: u16,
+  pub tls: SmtpTls,
+  /// Signs in to the relay when set.
+  pub username: String,
+  pub password: String,
+  /// Sender mailbox, as `name <address>` or a bare address.
+  pub from: String,
+  pub timeout_sec: u64,
+}
+
+impl Default for SmtpSettings {
+  fn default() -> Self {
+    Self {
+      host: String::new(),
+      port: 587,
+      tls: SmtpTls::default(),
+      username: String::new(),
+      password: String::new(),
+      from: String::new(),
+      timeout_sec: 10,
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SmsSettings {
+  pub delivery: SmsDelivery,
+  /// Directory where outgoing text messages are written as `.sms` files by
+  /// the `file` delivery.
+  pub outbox_dir: String,
+  pub gateway: SmsGatewaySettings,
+  pub code_length: usize,
+  pub code_ttl_min: i64,
+  pub max_codes_per_hour: i64,
+  pub max_attempts: i32,
+  pub require_verified_for_sign_in: bool,
+}
+
+impl Default for SmsSettings {
+  fn default() -> Self {
+    Self {
+      delivery: SmsDelivery::default(),
+      outbox_dir: String::from("outbox/sms"),
+      gateway: SmsGatewaySettings::default(),
+      code_length: 6,
+      code_ttl_min: 10,
+      max_codes_per_hour: 5,
+      max_attempts: 5,
+      require_verified_for_sign_in: false,
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SmsDelivery {
+  /// Writes to `outbox_dir`, only allowed in the dev profile.
+  #[default]
+  File,
+  Http,
+}
+
+/// HTTP gateway taking `To`, `From` and `Body` as a form post, as Twilio's
+/// Messages resource does.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SmsGatewaySettings {
+  pub url: String,
+  /// Sent with HTTP Basic authentication when set.
+  pub username: String,
+  pub password: String,
+  /// Sender number or alphanumeric sender id.
+  pub from: String,
+  pub timeout_sec: u64,
+}
+
+impl Default for SmsGatewaySettings {
+  fn default() -> Self {
+    Self {
+      url: String::new(),
+      username: String::new(),
+      password: String::new(),
+      from: String::new(),
+      timeout_sec: 10,
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PasswordResetSettings {
+  pub token_ttl_min: i64,
+}
+
+impl Default for PasswordResetSettings {
+  fn default() -> Self {
+    Self { token_ttl_min: 60 }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct MfaSettings {
+  pub issuer: String,
+  pub token_ttl_min: u64,
+  pub recovery_codes: usize,
+  pub allowed_drift_steps: i64,
+}
+
+impl Default for MfaSettings {
+  fn default() -> Self {
+    Self {
+      issuer: env!("CARGO_PKG_NAME").to_string(),
+      token_ttl_min: 5,
+      recovery_codes: 10,
+      allowed_drift_steps: 1,
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct WebAuthnSettings {
+  pub rp_id: String,
+  pub rp_name: String,
+  pub origin: String,
+  pub challenge_ttl_min: i64,
+}
+
+impl Default for WebAuthnSettings {
+  fn default() -> Self {
+    Self {
+      rp_id: String::from("localhost"),
+      rp_name: env!("CARGO_PKG_NAME").to_string(),
+      origin: String::from("http://localhost:8080"),
+      challenge_ttl_min: 5,
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct OidcSettings {
+  /// Audience of the access tokens issued to OpenID Connect clients.
+  pub userinfo_audience: String,
+  pub code_ttl_min: i64,
+  pub token_ttl_min: u64,
+}
+
+impl Default for OidcSettings {
+  fn default() -> Self {
+    Self {
+      userinfo_audience: String::from("oidc_userinfo"),
+      code_ttl_min: 5,
+      token_ttl_min: 60,
+    }
+  }
+}
+
+/// External OpenID Connect provider users can sign in with, using the
+/// credentials it issued to us.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ExternalProviderSettings {
+  pub name: String,
+  pub issuer: String,
+  pub client_id: String,
+  pub client_secret: String,
+  pub authorization_endpoint: String,
+  pub token_endpoint: String,
+  pub jwks_uri: String,
+  pub redirect_uri: String,
+  pub scope: String,
+}
+
+impl Default for ExternalProviderSettings {
+  fn default() -> Self {
+    Self {
+      name: String::new(),
+      issuer: String::new(),
+      client_id: String::new(),
+      client_secret: String::new(),
+      authorization_endpoint: String::new(),
+      token_endpoint: String::new(),
+      jwks_uri: String::new(),
+      redirect_uri: String::new(),
+      scope: String::from("openid email profile"),
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct ExternalIdentitySettings {
+  pub state_ttl_min: i64,
+  pub sign_up_ttl_min: i64,
+  pub http_timeout_sec: u64,
+  pub providers: Vec<ExternalProviderSettings>,
+}
+
+impl Default for ExternalIdentitySettings {
+  fn default() -> Self {
+    Self {
+      state_ttl_min: 10,
+      sign_up_ttl_min: 30,
+      http_timeout_sec: 10,
+      providers: Vec::new(),
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct AccountDeletionSettings {
+  pub grace_period_days: i32,
+  pub purge_interval_min: u64,
+}
+
+impl Default for AccountDeletionSettings {
+  fn default() -> Self {
+    Self {
+      grace_period_days: 30,
+      purge_interval_min: 60,
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct HttpSettings {
+  /// Take the client IP from `Forwarded`/`X-Forwarded-For` rather than the
+  /// peer, only safe behind a proxy that sets them.
+  pub trust_forwarded_for: bool,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum RateLimitStoreKind {
+  #[default]
+  Memory,
+  Postgres,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct RateLimitBucketSettings {
+  pub capacity: u32,
+  pub refill_per_min: u32,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct RateLimitSettings {
+  pub enabled: bool,
+  pub store: RateLimitStoreKind,
+  /// Paths whose POST requests are limited.
+  pub paths: Vec<String>,
+  /// Minutes between purges of the full buckets of the postgres store.
+  pub purge_interval_min: u64,
+  pub ip: RateLimitBucketSettings,
+  pub identifier: RateLimitBucketSettings,
+}
+
+impl Default for RateLimitSettings {
+  fn default() -> Self {
+    Self {
+      enabled: true,
+      store: RateLimitStoreKind::default(),
+      paths: vec![
+        String::from("/v1/auth/sign_in"),
+        String::from("/v1/auth/register"),
+      ],
+      purge_interval_min: 10,
+      ip: RateLimitBucketSettings {
+        capacity: 20,
+        refill_per_min: 10,
+      },
+      identifier: RateLimitBucketSettings {
+        capacity: 10,
+        refill_per_min: 2,
+      },
+    }
+  }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct AdminSettings {
+  pub profile_ids: Vec<String>,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(default)]
+pub struct Settings {
+  pub profile: Profile,
+  pub jwt: JwtSettings,
+  pub sign_in: SignInSettings,
+  pub password_hashing: PasswordHashingSettings,
+  pub email: EmailSettings,
+  pub sms: SmsSettings,
+  pub password_reset: PasswordResetSettings,
+  pub mfa: MfaSettings,
+  pub webauthn: WebAuthnSettings,
+  pub oidc: OidcSettings,
+  pub external_identity: ExternalIdentitySettings,
+  pub account_deletion: AccountDeletionSettings,
+  pub http: HttpSettings,
+  pub rate_limit: RateLimitSettings,
+  pub admin: AdminSettings,
+}
+
+/// Settings loaded once from `APP_CONFIG_FILE` (default `config/settings.toml`)
+/// with environment overrides applied on top. Panics on invalid settings, so
+/// call it at startup before serving requests.
+pub fn settings() -> &'static Settings {
+  SETTINGS.get_or_init(|| {
+    let settings = Settings::load().expect("settings loading failed!");
+    settings.validate().expect("invalid settings!");
+    settings
+  })
+}
+
+impl Settings {
+  pub fn load() -> Result<Settings, String> {
+    let content = Settings::read_file(DEFAULT_SETTINGS_FILE, &env_var)?;
+
+    Settings::from_sources(content.as_deref(), env_var)
+  }
+
+  /// Reads `APP_CONFIG_FILE`, or `default_file` when it is unset. Only an
+  /// explicit `APP_PROFILE=dev` may start without the default file, anything
+  /// else would boot a production server on the development defaults.
+  fn read_file(
+    default_file: &str,
+    env_var: &impl Fn(&str) -> Option<String>,
+  ) -> Result<Option<String>, String> {
+    let path = env_var("APP_CONFIG_FILE").unwrap_or_else(|| default_file.to_string());
+
+    match fs::read_to_string(&path) {
+      Ok(content) => Ok(Some(content)),
+      Err(error)
+        if error.kind() == ErrorKind::NotFound
+          && path == default_file
+          && env_var("APP_PROFILE").is_some_and(|profile| profile.eq_ignore_ascii_case("dev")) =>
+      {
+        Ok(None)
+      }
+      Err(error) => Err(format!(
+        "failed to read {}: {} (set APP_PROFILE=dev to start with the defaults)",
+        path, error
+      )),
+    }
+  }
+
+  fn from_sources(
+    content: Option<&str>,
+    env_var: impl Fn(&str) -> Option<String>,
+  ) -> Result<Settings, String> {
+    let mut settings: Settings = match content {
+      Some(content) => {
+        toml::from_str(content).map_err(|error| format!("invalid settings file: {}", error))?
+      }
+      None => Settings::default(),
+    };
+
+    if let Some(profile) = env_var("APP_PROFILE") {
+      settings.profile = match profile.to_lowercase().as_str() {
+        "dev" => Profile::Dev,
+        "production" => Profile::Production,
+        _ => return Err(format!("APP_PROFILE: unknown profile {}", profile)),
+      };
+    }
+    if let Some(secret) = env_var("JWT_SECRET") {
+      settings.jwt.secret = secret;
+    }
+    if let Some(signing_key) = env_var("JWT_SIGNING_KEY") {
+      settings.jwt.signing_key = Some(signing_key);
+    }
+    if let Some(issuer) = env_var("JWT_ISSUER") {
+      settings.jwt.issuer = issuer;
+    }
+    if let Some(audience) = env_var("JWT_AUDIENCE_AUTHENTICATION") {
+      settings.jwt.audiences.authentication = audience;
+    }
+    if let Some(audience) = env_var("JWT_AUDIENCE_MFA") {
+      settings.jwt.audiences.mfa = audience;
+    }
+    if let Some(ttl) = env_var("JWT_ACCESS_TTL_MIN") {
+      settings.jwt.ttl.access_min = ttl
+        .parse()
+        .map_err(|_| format!("JWT_ACCESS_TTL_MIN: invalid number {}", ttl))?;
+    }
+    if let Some(ttl) = env_var("JWT_REFRESH_TTL_MIN") {
+      settings.jwt.ttl.refresh_min = ttl
+        .parse()
+        .map_err(|_| format!("JWT_REFRESH_TTL_MIN: invalid number {}", ttl))?;
+    }
+    if let Some(ttl) = env_var("JWT_UNVERSIONED_TTL_MIN") {
+      settings.jwt.ttl.unversioned_min = ttl
+        .parse()
+        .map_err(|_| format!("JWT_UNVERSIONED_TTL_MIN: invalid number {}", ttl))?;
+    }
+    if let Some(attempts) = env_var("SIGN_IN_MAX_FAILED_ATTEMPTS") {
+      settings.sign_in.max_failed_attempts = attempts
+        .parse()
+        .map_err(|_| format!("SIGN_IN_MAX_FAILED_ATTEMPTS: invalid number {}", attempts))?;
+    }
+    if let Some(lockout) = env_var("SIGN_IN_LOCKOUT_MIN") {
+      settings.sign_in.lockout_min = lockout
+        .parse()
+        .map_err(|_| format!("SIGN_IN_LOCKOUT_MIN: invalid number {}", lockout))?;
+    }
+    if let Some(algorithm) = env_var("PASSWORD_HASHING_ALGORITHM") {
+      settings.password_hashing.algorithm = match algorithm.to_lowercase().as_str() {
+        "argon2id" => PasswordHashAlgorithm::Argon2id,
+        "bcrypt" => PasswordHashAlgorithm::Bcrypt,
+        _ => {
+          return Err(format!(
+            "PASSWORD_HASHING_ALGORITHM: unknown algorithm {}",
+            algorithm
+          ))
+        }
+      };
+    }
+    if let Some(memory) = env_var("PASSWORD_HASHING_ARGON2_MEMORY_KIB") {
+      settings.password_hashing.argon2.memory_kib = memory.parse().map_err(|_| {
+        format!(
+          "PASSWORD_HASHING_ARGON2_MEMORY_KIB: invalid number {}",
+          memory
+        )
+      })?;
+    }
+    if let Some(iterations) = env_var("PASSWORD_HASHING_ARGON2_ITERATIONS") {
+      settings.password_hashing.argon2.iterations = iterations.parse().map_err(|_| {
+        format!(
+          "PASSWORD_HASHING_ARGON2_ITERATIONS: invalid number {}",
+          iterations
+        )
+      })?;
+    }
+    if let Some(parallelism) = env_var("PASSWORD_HASHING_ARGON2_PARALLELISM") {
+      settings.password_hashing.argon2.parallelism = parallelism.parse().map_err(|_| {
+        format!(
+          "PASSWORD_HASHING_ARGON2_PARALLELISM: invalid number {}",
+          parallelism
+        )
+      })?;
+    }
+    if let Some(cost) = env_var("PASSWORD_HASHING_BCRYPT_COST") {
+      settings.password_hashing.bcrypt_cost = cost
+        .parse()
+        .map_err(|_| format!("PASSWORD_HASHING_BCRYPT_COST: invalid number {}", cost))?;
+    }
+    if let Some(delivery) = env_var("EMAIL_DELIVERY") {
+      settings.email.delivery = match delivery.to_lowercase().as_str() {
+        "file" => EmailDelivery::File,
+        "smtp" => EmailDelivery::Smtp,
+        _ => return Err(format!("EMAIL_DELIVERY: unknown delivery {}", delivery)),
+      };
+    }
+    if let Some(host) = env_var("EMAIL_SMTP_HOST") {
+      settings.email.smtp.host = host;
+    }
+    if let Some(port) = env_var("EMAIL_SMTP_PORT") {
+      settings.email.smtp.port = port
+        .parse()
+        .map_err(|_| format!("EMAIL_SMTP_PORT: invalid port {}", port))?;
+    }
+    if let Some(tls) = env_var("EMAIL_SMTP_TLS") {
+      settings.email.smtp.tls = match tls.to_lowercase().as_str() {
+        "starttls" => SmtpTls::Starttls,
+        "tls" => SmtpTls::Tls,
+        "none" => SmtpTls::None,
+        _ => return Err(format!("EMAIL_SMTP_TLS: unknown mode {}", tls)),
+      };
+    }
+    if let Some(username) = env_var("EMAIL_SMTP_USERNAME") {
+      settings.email.smtp.username = username;
+    }
+    if let Some(password) = env_var("EMAIL_SMTP_PASSWORD") {
+      settings.email.smtp.password = password;
+    }
+    if let Some(from) = env_var("EMAIL_SMTP_FROM") {
+      settings.email.smtp.from = from;
+    }
+    if let Some(outbox_dir) = env_var("EMAIL_OUTBOX_DIR") {
+      settings.email.outbox_dir = outbox_dir;
+    }
+    if let Some(ttl) = env_var("EMAIL_VERIFICATION_TTL_MIN") {
+      settings.email.verification_ttl_min = ttl
+        .parse()
+        .map_err(|_| format!("EMAIL_VERIFICATION_TTL_MIN: invalid number {}", ttl))?;
+    }
+    if let Some(required) = env_var("EMAIL_REQUIRE_VERIFIED_FOR_SIGN_IN") {
+      settings.email.require_verified_for_sign_in = required.parse().map_err(|_| {
+        format!(
+          "EMAIL_REQUIRE_VERIFIED_FOR_SIGN_IN: invalid boolean {}",
+          required
+        )
+      })?;
+    }
+    if let Some(delivery) = env_var("SMS_DELIVERY") {
+      settings.sms.delivery = match delivery.to_lowercase().as_str() {
+        "file" => SmsDelivery::File,
+        "http" => SmsDelivery::Http,
+        _ => return Err(format!("SMS_DELIVERY: unknown delivery {}", delivery)),
+      };
+    }
+    if let Some(url) = env_var("SMS_GATEWAY_URL") {
+      settings.sms.gateway.url = url;
+    }
+    if let Some(username) = env_var("SMS_GATEWAY_USERNAME") {
+      settings.sms.gateway.username = username;
+    }
+    if let Some(password) = env_var("SMS_GATEWAY_PASSWORD") {
+      settings.sms.gateway.password = password;
+    }
+    if let Some(from) = env_var("SMS_GATEWAY_FROM") {
+      settings.sms.gateway.from = from;
+    }
+    if let Some(outbox_dir) = env_var("SMS_OUTBOX_DIR") {
+      settings.sms.outbox_dir = outbox_dir;
+    }
+    if let Some(length) = env_var("SMS_CODE_LENGTH") {
+      settings.sms.code_length = length
+        .parse()
+        .map_err(|_| format!("SMS_CODE_LENGTH: invalid number {}", length))?;
+    }
+    if let Some(ttl) = env_var("SMS_CODE_TTL_MIN") {
+      settings.sms.code_ttl_min = ttl
+        .parse()
+        .map_err(|_| format!("SMS_CODE_TTL_MIN: invalid number {}", ttl))?;
+    }
+    if let Some(codes) = env_var("SMS_MAX_CODES_PER_HOUR") {
+      settings.sms.max_codes_per_hour = codes
+        .parse()
+        .map_err(|_| format!("SMS_MAX_CODES_PER_HOUR: invalid number {}", codes))?;
+    }
+    if let Some(attempts) = env_var("SMS_MAX_ATTEMPTS") {
+      settings.sms.max_attempts = attempts
+        .parse()
+        .map_err(|_| format!("SMS_MAX_ATTEMPTS: invalid number {}", attempts))?;
+    }
+    if let Some(required) = env_var("SMS_REQUIRE_VERIFIED_FOR_SIGN_IN") {
+      settings.sms.require_verified_for_sign_in = required.parse().map_err(|_| {
+        format!(
+          "SMS_REQUIRE_VERIFIED_FOR_SIGN_IN: invalid boolean {}",
+          required
+        )
+      })?;
+    }
+    if let Some(ttl) = env_var("PASSWORD_RESET_TTL_MIN") {
+      settings.password_reset.token_ttl_min = ttl
+        .parse()
+        .map_err(|_| format!("PASSWORD_RESET_TTL_MIN: invalid number {}", ttl))?;
+    }
+    if let Some(issuer) = env_var("MFA_ISSUER") {
+      settings.mfa.issuer = issuer;
+    }
+    if let Some(ttl) = env_var("MFA_TOKEN_TTL_MIN") {
+      settings.mfa.token_ttl_min = ttl
+        .parse()
+        .map_err(|_| format!("MFA_TOKEN_TTL_MIN: invalid number {}", ttl))?;
+    }
+    if let Some(codes) = env_var("MFA_RECOVERY_CODES") {
+      settings.mfa.recovery_codes = codes
+        .parse()
+        .map_err(|_| format!("MFA_RECOVERY_CODES: invalid number {}", codes))?;
+    }
+    if let Some(steps) = env_var("MFA_ALLOWED_DRIFT_STEPS") {
+      settings.mfa.allowed_drift_steps = steps
+        .parse()
+        .map_err(|_| format!("MFA_ALLOWED_DRIFT_STEPS: invalid number {}", steps))?;
+    }
+    if let Some(rp_id) = env_var("WEBAUTHN_RP_ID") {
+      settings.webauthn.rp_id = rp_id;
+    }
+    if let Some(rp_name) = env_var("WEBAUTHN_RP_NAME") {
+      settings.webauthn.rp_name = rp_name;
+    }
+    if let Some(origin) = env_var("WEBAUTHN_ORIGIN") {
+      settings.webauthn.origin = origin;
+    }
+    if let Some(ttl) = env_var("WEBAUTHN_CHALLENGE_TTL_MIN") {
+      settings.webauthn.challenge_ttl_min = ttl
+        .parse()
+        .map_err(|_| format!("WEBAUTHN_CHALLENGE_TTL_MIN: invalid number {}", ttl))?;
+    }
+    if let Some(audience) = env_var("OIDC_USERINFO_AUDIENCE") {
+      settings.oidc.userinfo_audience = audience;
+    }
+    if let Some(ttl) = env_var("OIDC_CODE_TTL_MIN") {
+      settings.oidc.code_ttl_min = ttl
+        .parse()
+        .map_err(|_| format!("OIDC_CODE_TTL_MIN: invalid number {}", ttl))?;
+    }
+    if let Some(ttl) = env_var("OIDC_TOKEN_TTL_MIN") {
+      settings.oidc.token_ttl_min = ttl
+        .parse()
+        .map_err(|_| format!("OIDC_TOKEN_TTL_MIN: invalid number {}", ttl))?;
+    }
+    if let Some(ttl) = env_var("EXTERNAL_IDENTITY_STATE_TTL_MIN") {
+      settings.external_identity.state_ttl_min = ttl
+        .parse()
+        .map_err(|_| format!("EXTERNAL_IDENTITY_STATE_TTL_MIN: invalid number {}", ttl))?;
+    }
+    if let Some(ttl) = env_var("EXTERNAL_IDENTITY_SIGN_UP_TTL_MIN") {
+      settings.external_identity.sign_up_ttl_min = ttl
+        .parse()
+        .map_err(|_| format!("EXTERNAL_IDENTITY_SIGN_UP_TTL_MIN: invalid number {}", ttl))?;
+    }
+    if let Some(timeout) = env_var("EXTERNAL_IDENTITY_HTTP_TIMEOUT_SEC") {
+      settings.external_identity.http_timeout_sec = timeout.parse().map_err(|_| {
+        format!(
+          "EXTERNAL_IDENTITY_HTTP_TIMEOUT_SEC: invalid number {}",
+          timeout
+        )
+      })?;
+    }
+    for provider in settings.external_identity.providers.iter_mut() {
+      let key = format!(
+        "EXTERNAL_IDENTITY_{}_CLIENT_SECRET",
+        provider.name.to_uppercase().replace('-', "_")
+      );
+      if let Some(client_secret) = env_var(&key) {
+        provider.client_secret = client_secret;
+      }
+    }
+    if let Some(days) = env_var("ACCOUNT_DELETION_GRACE_PERIOD_DAYS") {
+      settings.account_deletion.grace_period_days = days.parse().map_err(|_| {
+        format!(
+          "ACCOUNT_DELETION_GRACE_PERIOD_DAYS: invalid number {}",
+          days
+        )
+      })?;
+    }
+    if let Some(interval) = env_var("ACCOUNT_DELETION_PURGE_INTERVAL_MIN") {
+      settings.account_deletion.purge_interval_min = interval.parse().map_err(|_| {
+        format!(
+          "ACCOUNT_DELETION_PURGE_INTERVAL_MIN: invalid number {}",
+          interval
+        )
+      })?;
+    }
+    if let Some(trust) = env_var("HTTP_TRUST_FORWARDED_FOR") {
+      settings.http.trust_forwarded_for = trust
+        .parse()
+        .map_err(|_| format!("HTTP_TRUST_FORWARDED_FOR: invalid boolean {}", trust))?;
+    }
+    if let Some(enabled) = env_var("RATE_LIMIT_ENABLED") {
+      settings.rate_limit.enabled = enabled
+        .parse()
+        .map_err(|_| format!("RATE_LIMIT_ENABLED: invalid boolean {}", enabled))?;
+    }
+    if let Some(store) = env_var("RATE_LIMIT_STORE") {
+      settings.rate_limit.store = match store.to_lowercase().as_str() {
+        "memory" => RateLimitStoreKind::Memory,
+        "postgres" => RateLimitStoreKind::Postgres,
+        _ => return Err(format!("RATE_LIMIT_STORE: unknown store {}", store)),
+      };
+    }
+    if let Some(interval) = env_var("RATE_LIMIT_PURGE_INTERVAL_MIN") {
+      settings.rate_limit.purge_interval_min = interval
+        .parse()
+        .map_err(|_| format!("RATE_LIMIT_PURGE_INTERVAL_MIN: invalid number {}", interval))?;
+    }
+    if let Some(capacity) = env_var("RATE_LIMIT_IP_CAPACITY") {
+      settings.rate_limit.ip.capacity = capacity
+        .parse()
+        .map_err(|_| format!("RATE_LIMIT_IP_CAPACITY: invalid number {}", capacity))?;
+    }
+    if let Some(refill) = env_var("RATE_LIMIT_IP_REFILL_PER_MIN") {
+      settings.rate_limit.ip.refill_per_min = refill
+        .parse()
+        .map_err(|_| format!("RATE_LIMIT_IP_REFILL_PER_MIN: invalid number {}", refill))?;
+    }
+    if let Some(capacity) = env_var("RATE_LIMIT_IDENTIFIER_CAPACITY") {
+      settings.rate_limit.identifier.capacity = capacity.parse().map_err(|_| {
+        format!(
+          "RATE_LIMIT_IDENTIFIER_CAPACITY: invalid number {}",
+          capacity
+        )
+      })?;
+    }
+    if let Some(refill) = env_var("RATE_LIMIT_IDENTIFIER_REFILL_PER_MIN") {
+      settings.rate_limit.identifier.refill_per_min = refill.parse().map_err(|_| {
+        format!(
+          "RATE_LIMIT_IDENTIFIER_REFILL_PER_MIN: invalid number {}",
+          refill
+        )
+      })?;
+    }
+    if let Some(profile_ids) = env_var("ADMIN_PROFILE_IDS") {
+      settings.admin.profile_ids = profile_ids
+        .split(',')
+        .map(str::trim)
+        .filter(|profile_id| !profile_id.is_empty())
+        .map(str::to_string)
+        .collect();
+    }
+
+    Ok(settings)
+  }
+
+  pub fn validate(&self) -> Result<(), String> {
+    match &self.jwt.signing_key {
+      Some(signing_key) => {
+        let key = self
+          .jwt
+          .keys
+          .iter()
+          .find(|key| &key.kid == signing_key)
+          .ok_or_else(|| format!("jwt.signing_key {} not found in jwt.keys", signing_key))?;
+
+        if key.private_key_file.is_none() {
+          return Err(format!(
+            "jwt.keys {}: private_key_file is required for the signing key",
+            signing_key
+          ));
+        }
+      }
+      None => {
+        if self.jwt.secret.is_empty() {
+          return Err(String::from("jwt.secret must not be empty"));
+        }
+        if self.profile != Profile::Dev && self.jwt.secret == DEFAULT_JWT_SECRET {
+          return Err(String::from(
+            "jwt.secret is still the default value, set JWT_SECRET outside the dev profile",
+          ));
+        }
+      }
+    }
+    for (index, key) in self.jwt.keys.iter().enumerate() {
+      if self.jwt.keys[..index]
+        .iter()
+        .any(|other| other.kid == key.kid)
+      {
+        return Err(format!("jwt.keys {}: duplicated kid", key.kid));
+      }
+    }
+    if self.jwt.issuer.is_empty() {
+      return Err(String::from("jwt.issuer must not be empty"));
+    }
+    if self.jwt.audiences.authentication.is_empty() {
+      return Err(String::from(
+        "jwt.audiences.authentication must not be empty",
+      ));
+    }
+    if self.jwt.audiences.mfa.is_empty()
+      || self.jwt.audiences.mfa == self.jwt.audiences.authentication
+    {
+      return Err(String::from(
+        "jwt.audiences.mfa must not be empty nor equal to jwt.audiences.authentication",
+      ));
+    }
+    if self.jwt.ttl.access_min == 0 || self.jwt.ttl.refresh_min <= 0 {
+      return Err(String::from("jwt.ttl values must be greater than zero"));
+    }
+    if self.sign_in.max_failed_attempts <= 0 || self.sign_in.lockout_min <= 0 {
+      return Err(String::from("sign_in values must be greater than zero"));
+    }
+    let argon2 = &self.password_hashing.argon2;
+    if argon2.iterations == 0
+      || argon2.parallelism == 0
+      || argon2.memory_kib < 8 * argon2.parallelism
+    {
+      return Err(String::from(
+        "password_hashing.argon2 needs iterations and parallelism above zero and 8 KiB of memory per lane",
+      ));
+    }
+    if !(4..=31).contains(&self.password_hashing.bcrypt_cost) {
+      return Err(String::from(
+        "password_hashing.bcrypt_cost must be between 4 and 31",
+      ));
+    }
+    match self.email.delivery {
+      EmailDelivery::File => {
+        if self.profile != Profile::Dev {
+          return Err(String::from(
+            "email.delivery file only writes to a local outbox, use smtp outside the dev profile",
+          ));
+        }
+      }
+      EmailDelivery::Smtp => {
+        let smtp = &self.email.smtp;
+        if smtp.host.is_empty() || smtp.from.is_empty() || smtp.timeout_sec == 0 {
+          return Err(String::from(
+            "email.smtp needs host, from and a timeout_sec greater than zero",
+          ));
+        }
+        if smtp.tls == SmtpTls::None && !is_local_host(&smtp.host) {
+          return Err(String::from(
+            "email.smtp.tls none is only allowed for a relay on localhost",
+          ));
+        }
+      }
+    }
+    if self.email.verification_ttl_min <= 0 {
+      return Err(String::from(
+        "email.verification_ttl_min must be greater than zero",
+      ));
+    }
+    match self.sms.delivery {
+      SmsDelivery::File => {
+        if self.profile != Profile::Dev {
+          return Err(String::from(
+            "sms.delivery file only writes to a local outbox, use http outside the dev profile",
+          ));
+        }
+      }
+      SmsDelivery::Http => {
+        let gateway = &self.sms.gateway;
+        if !is_secure_url(&gateway.url) || gateway.from.is_empty() || gateway.timeout_sec == 0 {
+          return Err(String::from(
+            "sms.gateway needs an https url, from and a timeout_sec greater than zero",
+          ));
+        }
+      }
+    }
+    if !(4..=10).contains(&self.sms.code_length) {
+      return Err(String::from("sms.code_length must be between 4 and 10"));
+    }
+    if self.sms.code_ttl_min <= 0 || self.sms.max_codes_per_hour <= 0 || self.sms.max_attempts <= 0
+    {
+      return Err(String::from("sms values must be greater than zero"));
+    }
+    if self.password_reset.token_ttl_min <= 0 {
+      return Err(String::from(
+        "password_reset.token_ttl_min must be greater than zero",
+      ));
+    }
+    if self.mfa.issuer.is_empty() {
+      return Err(String::from("mfa.issuer must not be empty"));
+    }
+    if self.mfa.token_ttl_min == 0 || self.mfa.recovery_codes == 0 {
+      return Err(String::from("mfa values must be greater than zero"));
+    }
+    if !(0..=2).contains(&self.mfa.allowed_drift_steps) {
+      return Err(String::from(
+        "mfa.allowed_drift_steps must be between 0 and 2",
+      ));
+    }
+    if self.webauthn.rp_id.is_empty() || self.webauthn.rp_name.is_empty() {
+      return Err(String::from(
+        "webauthn.rp_id and webauthn.rp_name must not be empty",
+      ));
+    }
+    let origin_host = self
+      .webauthn
+      .origin
+      .strip_prefix("https://")
+      .or_else(|| self.webauthn.origin.strip_prefix("http://"))
+      .map(|host| host.split(':').next().unwrap_or_default());
+    if !origin_host.is_some_and(|host| {
+      host == self.webauthn.rp_id || host.ends_with(&format!(".{}", self.webauthn.rp_id))
+    }) {
+      return Err(String::from(
+        "webauthn.origin must be an http(s) origin within webauthn.rp_id",
+      ));
+    }
+    if self.webauthn.challenge_ttl_min <= 0 {
+      return Err(String::from(
+        "webauthn.challenge_ttl_min must be greater than zero",
+      ));
+    }
+    if self.oidc.userinfo_audience.is_empty()
+      || self.oidc.userinfo_audience == self.jwt.audiences.authentication
+      || self.oidc.userinfo_audience == self.jwt.audiences.mfa
+    {
+      return Err(String::from(
+        "oidc.userinfo_audience must not be empty nor equal to a jwt.audiences value",
+      ));
+    }
+    if self.oidc.code_ttl_min <= 0 || self.oidc.token_ttl_min == 0 {
+      return Err(String::from("oidc values must be greater than zero"));
+    }
+    let external_identity = &self.external_identity;
+    if external_identity.state_ttl_min <= 0
+      || external_identity.sign_up_ttl_min <= 0
+      || external_identity.http_timeout_sec == 0
+    {
+      return Err(String::from(
+        "external_identity values must be greater than zero",
+      ));
+    }
+    for (index, provider) in external_identity.providers.iter().enumerate() {
+      let valid_name = !provider.name.is_empty()
+        && provider
+          .name
+          .bytes()
+          .all(|byte| byte.is_ascii_lowercase() || byte.is_ascii_digit() || byte == b'-');
+      if !valid_name
+        || external_identity.providers[..index]
+          .iter()
+          .any(|other| other.name == provider.name)
+      {
+        return Err(String::from(
+          "external_identity.providers need unique names of lowercase letters, digits and dashes",
+        ));
+      }
+      if provider.issuer.is_empty()
+        || provider.client_id.is_empty()
+        || provider.client_secret.is_empty()
+        || provider.redirect_uri.is_empty()
+      {
+        return Err(format!(
+          "external_identity provider {} needs issuer, client_id, client_secret and redirect_uri",
+          provider.name
+        ));
+      }
+      if !is_secure_url(&provider.authorization_endpoint)
+        || !is_secure_url(&provider.token_endpoint)
+        || !is_secure_url(&provider.jwks_uri)
+      {
+        return Err(format!(
+          "external_identity provider {} endpoints must be https, or http on localhost",
+          provider.name
+        ));
+      }
+      if !provider.scope.split(' ').any(|scope| scope == "openid") {
+        return Err(format!(
+          "external_identity provider {} scope must include openid",
+          provider.name
+        ));
+      }
+    }
+    if self.account_deletion.grace_period_days < 0 {
+      return Err(String::from(
+        "account_deletion.grace_period_days must not be negative",
+      ));
+    }
+    if self.account_deletion.purge_interval_min == 0 {
+      return Err(String::from(
+        "account_deletion.purge_interval_min must be greater than zero",
+      ));
+    }
+    let rate_limit = &self.rate_limit;
+    if rate_limit.purge_interval_min == 0
+      || rate_limit.ip.capacity == 0
+      || rate_limit.ip.refill_per_min == 0
+      || rate_limit.identifier.capacity == 0
+      || rate_limit.identifier.refill_per_min == 0
+    {
+      return Err(String::from("rate_limit values must be greater than zero"));
+    }
+
+    Ok(())
+  }
+}
+
+/// Plain http is only accepted for servers on this machine, as used in
+/// development and tests.
+fn is_secure_url(url: &str) -> bool {
+  if let Some(rest) = url.strip_prefix("https://") {
+    return !rest.is_empty();
+  }
+
+  url
+    .strip_prefix("http://")
+    .is_some_and(|rest| is_local_host(rest.split(['/', ':']).next().unwrap_or_default()))
+}
+
+fn is_local_host(host: &str) -> bool {
+  host == "localhost" || host == "127.0.0.1"
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const SETTINGS_FILE: &str = r#"
+    profile = "production"
+
+    [jwt]
+    secret = "file_secret"
+    issuer = "file_issuer"
+
+    [jwt.audiences]
+    authentication = "file_audience"
+    mfa = "file_mfa_audience"
+
+    [jwt.ttl]
+    access_min = 15
+    refresh_min = 1440
+    unversioned_min = 30
+
+    [sign_in]
+    max_failed_attempts = 3
+    lockout_min = 60
+
+    [password_hashing]
+    algorithm = "bcrypt"
+    bcrypt_cost = 10
+
+    [password_hashing.argon2]
+    memory_kib = 65536
+    iterations = 3
+    parallelism = 4
+
+    [email]
+    delivery = "smtp"
+    outbox_dir = "/tmp/outbox"
+    verification_ttl_min = 30
+    require_verified_for_sign_in = true
+
+    [email.smtp]
+    host = "smtp.example.com"
+    port = 465
+    tls = "tls"
+    username = "file_smtp_user"
+    password = "file_smtp_password"
+    from = "Example <no-reply@example.com>"
+
+    [sms]
+    delivery = "http"
+    outbox_dir = "/tmp/sms"
+    code_length = 8
+    code_ttl_min = 5
+    max_codes_per_hour = 3
+    max_attempts = 3
+    require_verified_for_sign_in = true
+
+    [sms.gateway]
+    url = "https://sms.example.com/Messages.json"
+    username = "file_sms_user"
+    password = "file_sms_password"
+    from = "+14155550100"
+
+    [password_reset]
+    token_ttl_min = 20
+
+    [mfa]
+    issuer = "File Issuer"
+    token_ttl_min = 3
+    recovery_codes = 8
+    allowed_drift_steps = 2
+
+    [webauthn]
+    rp_id = "example.com"
+    rp_name = "File Relying Party"
+    origin = "https://auth.example.com"
+    challenge_ttl_min = 2
+
+    [oidc]
+    userinfo_audience = "file_userinfo"
+    code_ttl_min = 2
+    token_ttl_min = 30
+
+    [external_identity]
+    state_ttl_min = 5
+    sign_up_ttl_min = 15
+    http_timeout_sec = 3
+
+    [[external_identity.providers]]
+    name = "acme"
+    issuer = "https://idp.acme.com"
+    client_id = "file_client"
+    client_secret = "file_client_secret"
+    authorization_endpoint = "https://idp.acme.com/authorize"
+    token_endpoint = "https://idp.acme.com/token"
+    jwks_uri = "https://idp.acme.com/jwks"
+    redirect_uri = "https://auth.example.com/callback/acme"
+
+    [account_deletion]
+    grace_period_days = 7
+    purge_interval_min = 30
+
+    [http]
+    trust_forwarded_for = true
+
+    [rate_limit]
+    store = "postgres"
+    paths = ["/v1/auth/sign_in"]
+    purge_interval_min = 5
+
+    [rate_limit.ip]
+    capacity = 50
+    refill_per_min = 25
+
+    [rate_limit.identifier]
+    capacity = 4
+    refill_per_min = 1
+
+    [admin]
+    profile_ids = ["cc3b95d3-4ba4-4a90-bc09-119fd2a4c659"]
+  "#;
+
+  /// Delivery a production profile can start with.
+  const PRODUCTION_DELIVERY: &str = r#"
+    [email]
+    delivery = "smtp"
+
+    [email.smtp]
+    host = "smtp.example.com"
+    from = "no-reply@example.com"
+
+    [sms]
+    delivery = "http"
+
+    [sms.gateway]
+    url = "https://sms.example.com/Messages.json"
+    from = "+14155550100"
+  "#;
+
+  fn production_email() -> EmailSettings {
+    EmailSettings {
+      delivery: EmailDelivery::Smtp,
+      smtp: SmtpSettings {
+        host: String::from("smtp.example.com"),
+        from: String::from("no-reply@example.com"),
+        ..Default::default()
+      },
+      ..Default::default()
+    }
+  }
+
+  fn production_sms() -> SmsSettings {
+    SmsSettings {
+      delivery: SmsDelivery::Http,
+      gateway: SmsGatewaySettings {
+        url: String::from("https://sms.example.com/Messages.json"),
+        from: String::from("+14155550100"),
+        ..Default::default()
+      },
+      ..Default::default()
+    }
+  }
+
+  #[test]
+  fn test_load_defaults() {
+    let settings = Settings::from_sources(None, |_| None).unwrap();
+
+    assert_eq!(settings, Settings::default());
+    assert_eq!(settings.profile, Profile::Production);
+    assert_eq!(settings.jwt.secret, DEFAULT_JWT_SECRET);
+    assert!(settings.validate().is_err());
+  }
+
+  #[test]
+  fn test_load_from_file() {
+    let settings = Settings::from_sources(Some(SETTINGS_FILE), |_| None).unwrap();
+
+    assert_eq!(settings.profile, Profile::Production);
+    assert_eq!(settings.jwt.secret, "file_secret");
+    assert_eq!(settings.jwt.issuer, "file_issuer");
+    assert_eq!(settings.jwt.audiences.authentication, "file_audience");
+    assert_eq!(settings.jwt.audiences.mfa, "file_mfa_audience");
+    assert_eq!(settings.jwt.ttl.access_min, 15);
+    assert_eq!(settings.jwt.ttl.refresh_min, 1440);
+    assert_eq!(settings.jwt.ttl.unversioned_min, 30);
+    assert_eq!(settings.sign_in.max_failed_attempts, 3);
+    assert_eq!(settings.sign_in.lockout_min, 60);
+    assert_eq!(
+      settings.password_hashing,
+      PasswordHashingSettings {
+        algorithm: PasswordHashAlgorithm::Bcrypt,
+        argon2: Argon2Settings {
+          memory_kib: 65536,
+          iterations: 3,
+          parallelism: 4,
+        },
+        bcrypt_cost: 10,
+      }
+    );
+    assert_eq!(settings.email.delivery, EmailDelivery::Smtp);
+    assert_eq!(settings.email.outbox_dir, "/tmp/outbox");
+    assert_eq!(
+      settings.email.smtp,
+      SmtpSettings {
+        host: String::from("smtp.example.com"),
+        port: 465,
+        tls: SmtpTls::Tls,
+        username: String::from("file_smtp_user"),
+        password: String::from("file_smtp_password"),
+        from: String::from("Example <no-reply@example.com>"),
+        timeout_sec: 10,
+      }
+    );
+    assert_eq!(settings.email.verification_ttl_min, 30);
+    assert!(settings.email.require_verified_for_sign_in);
+    assert_eq!(settings.sms.delivery, SmsDelivery::Http);
+    assert_eq!(settings.sms.outbox_dir, "/tmp/sms");
+    assert_eq!(
+      settings.sms.gateway,
+      SmsGatewaySettings {
+        url: String::from("https://sms.example.com/Messages.json"),
+        username: String::from("file_sms_user"),
+        password: String::from("file_sms_password"),
+        from: String::from("+14155550100"),
+        timeout_sec: 10,
+      }
+    );
+    assert_eq!(settings.sms.code_length, 8);
+    assert_eq!(settings.sms.code_ttl_min, 5);
+    assert_eq!(settings.sms.max_codes_per_hour, 3);
+    assert_eq!(settings.sms.max_attempts, 3);
+    assert!(settings.sms.require_verified_for_sign_in);
+    assert_eq!(settings.password_reset.token_ttl_min, 20);
+    assert_eq!(settings.mfa.issuer, "File Issuer");
+    assert_eq!(settings.mfa.token_ttl_min, 3);
+    assert_eq!(settings.mfa.recovery_codes, 8);
+    assert_eq!(settings.mfa.allowed_drift_steps, 2);
+    assert_eq!(settings.webauthn.rp_id, "example.com");
+    assert_eq!(settings.webauthn.rp_name, "File Relying Party");
+    assert_eq!(settings.webauthn.origin, "https://auth.example.com");
+    assert_eq!(settings.webauthn.challenge_ttl_min, 2);
+    assert_eq!(settings.oidc.userinfo_audience, "file_userinfo");
+    assert_eq!(settings.oidc.code_ttl_min, 2);
+    assert_eq!(settings.oidc.token_ttl_min, 30);
+    assert_eq!(settings.external_identity.state_ttl_min, 5);
+    assert_eq!(settings.external_identity.sign_up_ttl_min, 15);
+    assert_eq!(settings.external_identity.http_timeout_sec, 3);
+    assert_eq!(
+      settings.external_identity.providers,
+      vec![ExternalProviderSettings {
+        name: String::from("acme"),
+        issuer: String::from("https://idp.acme.com"),
+        client_id: String::from("file_client"),
+        client_secret: String::from("file_client_secret"),
+        authorization_endpoint: String::from("https://idp.acme.com/authorize"),
+        token_endpoint: String::from("https://idp.acme.com/token"),
+        jwks_uri: String::from("https://idp.acme.com/jwks"),
+        redirect_uri: String::from("https://auth.example.com/callback/acme"),
+        scope: String::from("openid email profile"),
+      }]
+    );
+    assert!(settings.validate().is_ok());
+    assert_eq!(settings.account_deletion.grace_period_days, 7);
+    assert_eq!(settings.account_deletion.purge_interval_min, 30);
+    assert!(settings.http.trust_forwarded_for);
+    assert!(settings.rate_limit.enabled);
+    assert_eq!(settings.rate_limit.store, RateLimitStoreKind::Postgres);
+    assert_eq!(
+      settings.rate_limit.paths,
+      vec![String::from("/v1/auth/sign_in")]
+    );
+    assert_eq!(settings.rate_limit.purge_interval_min, 5);
+    assert_eq!(
+      settings.rate_limit.ip,
+      RateLimitBucketSettings {
+        capacity: 50,
+        refill_per_min: 25,
+      }
+    );
+    assert_eq!(
+      settings.rate_limit.identifier,
+      RateLimitBucketSettings {
+        capacity: 4,
+        refill_per_min: 1,
+      }
+    );
+    assert_eq!(
+      settings.admin.profile_ids,
+      vec![String::from("cc3b95d3-4ba4-4a90-bc09-119fd2a4c659")]
+    );
+  }
+
+  #[test]
+  fn test_load_partial_file_keeps_defaults() {
+    let settings = Settings::from_sources(Some("[jwt.ttl]\naccess_min = 5"), |_| None).unwrap();
+
+    assert_eq!(settings.jwt.ttl.access_min, 5);
+    assert_eq!(settings.jwt.ttl.refresh_min, JwtTtl::default().refresh_min);
+    assert_eq!(settings.jwt.secret, DEFAULT_JWT_SECRET);
+  }
+
+  #[test]
+  fn test_env_overrides_file() {
+    let settings = Settings::from_sources(Some(SETTINGS_FILE), |key| match key {
+      "APP_PROFILE" => Some(String::from("dev")),
+      "JWT_SECRET" => Some(String::from("env_secret")),
+      "JWT_ACCESS_TTL_MIN" => Some(String::from("30")),
+      "JWT_UNVERSIONED_TTL_MIN" => Some(String::from("0")),
+      "SIGN_IN_LOCKOUT_MIN" => Some(String::from("5")),
+      "PASSWORD_HASHING_ALGORITHM" => Some(String::from("Argon2id")),
+      "PASSWORD_HASHING_ARGON2_MEMORY_KIB" => Some(String::from("32768")),
+      "EMAIL_REQUIRE_VERIFIED_FOR_SIGN_IN" => Some(String::from("false")),
+      "EMAIL_SMTP_HOST" => Some(String::from("localhost")),
+      "EMAIL_SMTP_PORT" => Some(String::from("25")),
+      "EMAIL_SMTP_TLS" => Some(String::from("None")),
+      "EMAIL_SMTP_PASSWORD" => Some(String::from("env_smtp_password")),
+      "SMS_GATEWAY_URL" => Some(String::from("http://localhost:8088/sms")),
+      "SMS_GATEWAY_USERNAME" => Some(String::from("env_sms_user")),
+      "SMS_CODE_TTL_MIN" => Some(String::from("15")),
+      "PASSWORD_RESET_TTL_MIN" => Some(String::from("45")),
+      "MFA_TOKEN_TTL_MIN" => Some(String::from("10")),
+      "WEBAUTHN_ORIGIN" => Some(String::from("https://example.com:8443")),
+      "OIDC_TOKEN_TTL_MIN" => Some(String::from("15")),
+      "EXTERNAL_IDENTITY_SIGN_UP_TTL_MIN" => Some(String::from("45")),
+      "EXTERNAL_IDENTITY_ACME_CLIENT_SECRET" => Some(String::from("env_client_secret")),
+      "ACCOUNT_DELETION_GRACE_PERIOD_DAYS" => Some(String::from("0")),
+      "HTTP_TRUST_FORWARDED_FOR" => Some(String::from("false")),
+      "RATE_LIMIT_STORE" => Some(String::from("memory")),
+      "RATE_LIMIT_IDENTIFIER_CAPACITY" => Some(String::from("3")),
+      "ADMIN_PROFILE_IDS" => Some(String::from("first-admin, second-admin,")),
+      _ => None,
+    })
+    .unwrap();
+
+    assert_eq!(settings.profile, Profile::Dev);
+    assert_eq!(settings.jwt.secret, "env_secret");
+    assert_eq!(settings.jwt.issuer, "file_issuer");
+    assert_eq!(settings.jwt.ttl.access_min, 30);
+    assert_eq!(settings.jwt.ttl.unversioned_min, 0);
+    assert_eq!(settings.sign_in.max_failed_attempts, 3);
+    assert_eq!(settings.sign_in.lockout_min, 5);
+    assert_eq!(
+      settings.password_hashing.algorithm,
+      PasswordHashAlgorithm::Argon2id
+    );
+    assert_eq!(settings.password_hashing.argon2.memory_kib, 32768);
+    assert_eq!(settings.password_hashing.argon2.iterations, 3);
+    assert!(!settings.email.require_verified_for_sign_in);
+    assert_eq!(settings.email.delivery, EmailDelivery::Smtp);
+    assert_eq!(settings.email.smtp.host, "localhost");
+    assert_eq!(settings.email.smtp.port, 25);
+    assert_eq!(settings.email.smtp.tls, SmtpTls::None);
+    assert_eq!(settings.email.smtp.username, "file_smtp_user");
+    assert_eq!(settings.email.smtp.password, "env_smtp_password");
+    assert_eq!(settings.sms.delivery, SmsDelivery::Http);
+    assert_eq!(settings.sms.gateway.url, "http://localhost:8088/sms");
+    assert_eq!(settings.sms.gateway.username, "env_sms_user");
+    assert_eq!(settings.sms.gateway.password, "file_sms_password");
+    assert_eq!(settings.sms.code_ttl_min, 15);
+    assert_eq!(settings.sms.max_attempts, 3);
+    assert_eq!(settings.password_reset.token_ttl_min, 45);
+    assert_eq!(settings.mfa.token_ttl_min, 10);
+    assert_eq!(settings.mfa.recovery_codes, 8);
+    assert_eq!(settings.webauthn.rp_id, "example.com");
+    assert_eq!(settings.webauthn.origin, "https://example.com:8443");
+    assert_eq!(settings.oidc.code_ttl_min, 2);
+    assert_eq!(settings.oidc.token_ttl_min, 15);
+    assert_eq!(settings.external_identity.state_ttl_min, 5);
+    assert_eq!(settings.external_identity.sign_up_ttl_min, 45);
+    assert_eq!(
+      settings.external_identity.providers[0].client_secret,
+      "env_client_secret"
+    );
+    assert_eq!(settings.account_deletion.grace_period_days, 0);
+    assert_eq!(settings.account_deletion.purge_interval_min, 30);
+    assert!(!settings.http.trust_forwarded_for);
+    assert_eq!(settings.rate_limit.store, RateLimitStoreKind::Memory);
+    assert_eq!(settings.rate_limit.ip.capacity, 50);
+    assert_eq!(settings.rate_limit.identifier.capacity, 3);
+    assert_eq!(settings.rate_limit.identifier.refill_per_min, 1);
+    assert_eq!(
+      settings.admin.profile_ids,
+      vec![String::from("first-admin"), String::from("second-admin")]
+    );
+  }
+
+  #[test]
+  fn test_load_with_invalid_env_value() {
+    let settings = Settings::from_sources(None, |key| match key {
+      "JWT_REFRESH_TTL_MIN" => Some(String::from("forever")),
+      _ => None,
+    });
+
+    assert_eq!(
+      settings,
+      Err(String::from("JWT_REFRESH_TTL_MIN: invalid number forever"))
+    );
+  }
+
+  #[test]
+  fn test_load_with_invalid_file() {
+    let settings = Settings::from_sources(Some("profile = \"staging\""), |_| None);
+
+    assert!(settings.is_err());
+  }
+
+  #[test]
+  fn test_refuse_missing_default_file_outside_dev() {
+    let content = Settings::read_file("config/missing.toml", &|_| None);
+
+    assert!(content.is_err());
+  }
+
+  #[test]
+  fn test_start_without_default_file_in_dev() {
+    let content = Settings::read_file("config/missing.toml", &|key| match key {
+      "APP_PROFILE" => Some(String::from("Dev")),
+      _ => None,
+    });
+
+    assert_eq!(content, Ok(None));
+  }
+
+  #[test]
+  fn test_refuse_missing_config_file_in_dev() {
+    let content = Settings::read_file("config/settings.toml", &|key| match key {
+      "APP_PROFILE" => Some(String::from("dev")),
+      "APP_CONFIG_FILE" => Some(String::from("config/missing.toml")),
+      _ => None,
+    });
+
+    assert!(content.is_err());
+  }
+
+  #[test]
+  fn test_refuse_default_secret_outside_dev() {
+    let settings = Settings {
+      profile: Profile::Production,
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "jwt.secret is still the default value, set JWT_SECRET outside the dev profile"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_accept_custom_secret_outside_dev() {
+    let settings = Settings {
+      profile: Profile::Production,
+      jwt: JwtSettings {
+        secret: String::from("a-long-and-random-production-secret"),
+        ..Default::default()
+      },
+      email: production_email(),
+      sms: production_sms(),
+      ..Default::default()
+    };
+
+    assert!(settings.validate().is_ok());
+  }
+
+  #[test]
+  fn test_refuse_file_email_delivery_outside_dev() {
+    let settings = Settings {
+      profile: Profile::Production,
+      jwt: JwtSettings {
+        secret: String::from("a-long-and-random-production-secret"),
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "email.delivery file only writes to a local outbox, use smtp outside the dev profile"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_file_sms_delivery_outside_dev() {
+    let settings = Settings {
+      profile: Profile::Production,
+      jwt: JwtSettings {
+        secret: String::from("a-long-and-random-production-secret"),
+        ..Default::default()
+      },
+      email: production_email(),
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "sms.delivery file only writes to a local outbox, use http outside the dev profile"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_plain_http_sms_gateway() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      sms: SmsSettings {
+        gateway: SmsGatewaySettings {
+          url: String::from("http://sms.example.com/Messages.json"),
+          ..production_sms().gateway
+        },
+        ..production_sms()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "sms.gateway needs an https url, from and a timeout_sec greater than zero"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_plain_smtp_to_remote_relay() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      email: EmailSettings {
+        smtp: SmtpSettings {
+          tls: SmtpTls::None,
+          ..production_email().smtp
+        },
+        ..production_email()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "email.smtp.tls none is only allowed for a relay on localhost"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_zero_max_failed_attempts() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      sign_in: SignInSettings {
+        max_failed_attempts: 0,
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from("sign_in values must be greater than zero"))
+    );
+  }
+
+  #[test]
+  fn test_refuse_argon2_memory_below_lanes() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      password_hashing: PasswordHashingSettings {
+        argon2: Argon2Settings {
+          memory_kib: 16,
+          iterations: 2,
+          parallelism: 4,
+        },
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "password_hashing.argon2 needs iterations and parallelism above zero and 8 KiB of memory per lane"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_short_sms_code() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      sms: SmsSettings {
+        code_length: 3,
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from("sms.code_length must be between 4 and 10"))
+    );
+  }
+
+  #[test]
+  fn test_refuse_mfa_audience_equal_to_authentication() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      jwt: JwtSettings {
+        audiences: JwtAudiences {
+          mfa: String::from("authentication_user"),
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "jwt.audiences.mfa must not be empty nor equal to jwt.audiences.authentication"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_wide_mfa_drift() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      mfa: MfaSettings {
+        allowed_drift_steps: 3,
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "mfa.allowed_drift_steps must be between 0 and 2"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_webauthn_origin_outside_rp_id() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      webauthn: WebAuthnSettings {
+        rp_id: String::from("example.com"),
+        origin: String::from("https://example.com.evil.net"),
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "webauthn.origin must be an http(s) origin within webauthn.rp_id"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_userinfo_audience_equal_to_authentication() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      oidc: OidcSettings {
+        userinfo_audience: JwtAudiences::default().authentication,
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "oidc.userinfo_audience must not be empty nor equal to a jwt.audiences value"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_external_provider_over_plain_http() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      external_identity: ExternalIdentitySettings {
+        providers: vec![ExternalProviderSettings {
+          name: String::from("acme"),
+          issuer: String::from("http://idp.acme.com"),
+          client_id: String::from("client"),
+          client_secret: String::from("secret"),
+          authorization_endpoint: String::from("https://idp.acme.com/authorize"),
+          token_endpoint: String::from("http://idp.acme.com/token"),
+          redirect_uri: String::from("https://auth.example.com/callback/acme"),
+          ..Default::default()
+        }],
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "external_identity provider acme endpoints must be https, or http on localhost"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_duplicated_external_provider() {
+    let provider = ExternalProviderSettings {
+      name: String::from("acme"),
+      issuer: String::from("https://idp.acme.com"),
+      client_id: String::from("client"),
+      client_secret: String::from("secret"),
+      authorization_endpoint: String::from("https://idp.acme.com/authorize"),
+      token_endpoint: String::from("https://idp.acme.com/token"),
+      jwks_uri: String::from("https://idp.acme.com/jwks"),
+      redirect_uri: String::from("https://auth.example.com/callback/acme"),
+      ..Default::default()
+    };
+    let settings = Settings {
+      profile: Profile::Dev,
+      external_identity: ExternalIdentitySettings {
+        providers: vec![provider.clone(), provider],
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "external_identity.providers need unique names of lowercase letters, digits and dashes"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_negative_grace_period() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      account_deletion: AccountDeletionSettings {
+        grace_period_days: -1,
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "account_deletion.grace_period_days must not be negative"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_zero_rate_limit_refill() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      rate_limit: RateLimitSettings {
+        identifier: RateLimitBucketSettings {
+          capacity: 5,
+          refill_per_min: 0,
+        },
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from("rate_limit values must be greater than zero"))
+    );
+  }
+
+  #[test]
+  fn test_load_keys_from_file() {
+    let content = format!(
+      "{}{}",
+      r#"
+        [jwt]
+        signing_key = "2023-11"
+
+        [[jwt.keys]]
+        kid = "2023-11"
+        algorithm = "EdDSA"
+        private_key_file = "keys/2023-11.pem"
+        public_key_file = "keys/2023-11.pub.pem"
+
+        [[jwt.keys]]
+        kid = "2023-10"
+        algorithm = "RS256"
+        public_key_file = "keys/2023-10.pub.pem"
+        "#,
+      PRODUCTION_DELIVERY
+    );
+    let settings = Settings::from_sources(Some(&content), |_| None).unwrap();
+
+    assert_eq!(settings.jwt.signing_key, Some(String::from("2023-11")));
+    assert_eq!(settings.jwt.keys.len(), 2);
+    assert_eq!(settings.jwt.keys[0].algorithm, JwtAlgorithm::EdDSA);
+    assert_eq!(settings.jwt.keys[1].private_key_file, None);
+    assert!(settings.validate().is_ok());
+  }
+
+  #[test]
+  fn test_accept_default_secret_outside_dev_with_signing_key() {
+    let settings = Settings {
+      profile: Profile::Production,
+      jwt: JwtSettings {
+        signing_key: Some(String::from("2023-11")),
+        keys: vec![jwt_key("2023-11", Some("keys/2023-11.pem"))],
+        ..Default::default()
+      },
+      email: production_email(),
+      sms: production_sms(),
+      ..Default::default()
+    };
+
+    assert!(settings.validate().is_ok());
+  }
+
+  #[test]
+  fn test_refuse_unknown_signing_key() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      jwt: JwtSettings {
+        signing_key: Some(String::from("2023-12")),
+        keys: vec![jwt_key("2023-11", Some("keys/2023-11.pem"))],
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from(
+        "jwt.signing_key 2023-12 not found in jwt.keys"
+      ))
+    );
+  }
+
+  #[test]
+  fn test_refuse_signing_key_without_private_key() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      jwt: JwtSettings {
+        signing_key: Some(String::from("2023-11")),
+        keys: vec![jwt_key("2023-11", None)],
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert!(settings.validate().is_err());
+  }
+
+  #[test]
+  fn test_refuse_duplicated_kid() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      jwt: JwtSettings {
+        keys: vec![jwt_key("2023-11", None), jwt_key("2023-11", None)],
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert_eq!(
+      settings.validate(),
+      Err(String::from("jwt.keys 2023-11: duplicated kid"))
+    );
+  }
+
+  fn jwt_key(kid: &str, private_key_file: Option<&str>) -> JwtKeySettings {
+    JwtKeySettings {
+      kid: kid.to_string(),
+      algorithm: JwtAlgorithm::RS256,
+      private_key_file: private_key_file.map(ToString::to_string),
+      public_key_file: format!("keys/{}.pub.pem", kid),
+    }
+  }
+
+  #[test]
+  fn test_refuse_zero_ttl() {
+    let settings = Settings {
+      profile: Profile::Dev,
+      jwt: JwtSettings {
+        ttl: JwtTtl {
+          access_min: 0,
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+      ..Default::default()
+    };
+
+    assert!(settings.validate().is_err());
+  }
+}
@@ -1,12 +1,40 @@
 use crate::{
-  adapter::utilities::{bcrypt::BCrypt, id_generator::NewID},
+  adapter::utilities::{
+    argon2::Argon2,
+    bcrypt::BCrypt,
+    id_generator::NewID,
+    phc::{PasswordAlgorithm, PhcCrypto},
+    primitives::CryptoPrimitives,
+  },
   domain::utilities::Utilities,
 };
 use actix_web::web;
 
+use super::settings::{settings, PasswordHashAlgorithm};
+
 pub fn utilities_config(cfg: &mut web::ServiceConfig) {
   cfg.app_data(web::Data::new(Utilities {
-    crypto: BCrypt { cost: 8 },
+    crypto: get_crypto(),
     id_generator: NewID,
+    primitives: CryptoPrimitives,
   }));
 }
+
+pub fn get_crypto() -> PhcCrypto {
+  let password_hashing = &settings().password_hashing;
+
+  PhcCrypto {
+    algorithm: match password_hashing.algorithm {
+      PasswordHashAlgorithm::Argon2id => PasswordAlgorithm::Argon2id,
+      PasswordHashAlgorithm::Bcrypt => PasswordAlgorithm::BCrypt,
+    },
+    argon2: Argon2 {
+      memory_kib: password_hashing.argon2.memory_kib,
+      iterations: password_hashing.argon2.iterations,
+      parallelism: password_hashing.argon2.parallelism,
+    },
+    bcrypt: BCrypt {
+      cost: password_hashing.bcrypt_cost,
+    },
+  }
+}
@@ -1,4 +1,4 @@
-use crate::adapter::routers::{v1, v1::auth};
+use crate::adapter::routers::{self, v1, v1::admin, v1::auth, v1::oauth, v1::users};
 use utoipa::OpenApi;
 use utoipa::{
   openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
@@ -22,6 +22,15 @@ impl Modify for SecurityAddon {
           .description(Some("Set the JWT Token"))
           .build(),
       ),
+    );
+    components.add_security_scheme(
+      "oauth_client_basic",
+      SecurityScheme::Http(
+        HttpBuilder::new()
+          .scheme(HttpAuthScheme::Basic)
+          .description(Some("OAuth client_id and client_secret"))
+          .build(),
+      ),
     )
   }
 }
@@ -36,16 +45,99 @@ impl Modify for SecurityAddon {
     )
   ),
   paths(
+    routers::jwks::jwks_get,
+    routers::openid_configuration::openid_configuration_get,
     v1::auth::controller::sign_in,
+    v1::auth::controller::verify_mfa,
     v1::auth::controller::register,
     v1::auth::controller::change_password,
+    v1::auth::controller::refresh,
+    v1::auth::controller::logout,
+    v1::auth::controller::verify_email,
+    v1::auth::controller::forgot_password,
+    v1::auth::controller::reset_password,
+    v1::auth::controller::webauthn_registration_options,
+    v1::auth::controller::register_webauthn,
+    v1::auth::controller::webauthn_sign_in_options,
+    v1::auth::controller::webauthn_sign_in,
+    v1::auth::controller::external_authorize,
+    v1::auth::controller::external_callback,
+    v1::auth::controller::external_sign_up,
+    v1::users::controller::get_me,
+    v1::users::controller::update_me,
+    v1::users::controller::list_my_sign_ins,
+    v1::users::controller::list_my_sessions,
+    v1::users::controller::revoke_my_session,
+    v1::users::controller::revoke_my_other_sessions,
+    v1::users::controller::delete_me,
+    v1::users::controller::send_telephone_code,
+    v1::users::controller::verify_telephone,
+    v1::users::controller::enroll_totp,
+    v1::users::controller::confirm_totp,
+    v1::admin::controller::list_users,
+    v1::admin::controller::get_user,
+    v1::admin::controller::disable_user,
+    v1::admin::controller::enable_user,
+    v1::admin::controller::force_password_reset,
+    v1::admin::controller::unlock_user,
+    v1::admin::controller::grant_role,
+    v1::admin::controller::revoke_role,
+    v1::admin::controller::register_oauth_client,
+    v1::admin::controller::list_audit_events,
+    v1::admin::controller::verify_audit_events,
+    v1::oauth::controller::authorize,
+    v1::oauth::controller::exchange_code,
+    v1::oauth::controller::userinfo,
   ),
   components(
     schemas(
       auth::dtos::UserSignInRequest,
       auth::dtos::UserRegistrationRequest,
       auth::dtos::ChangeUserPasswordRequest,
+      auth::dtos::RefreshTokenRequest,
+      auth::dtos::VerifyEmailRequest,
+      auth::dtos::ForgotPasswordRequest,
+      auth::dtos::ResetPasswordRequest,
+      auth::dtos::WebAuthnRegistrationOptionsHttp,
+      auth::dtos::RegisterWebAuthnRequest,
+      auth::dtos::WebAuthnCredentialResponseHttp,
+      auth::dtos::WebAuthnSignInOptionsRequest,
+      auth::dtos::WebAuthnSignInOptionsHttp,
+      auth::dtos::WebAuthnSignInRequest,
       auth::dtos::UserAuthenticationResponseHttp,
+      auth::dtos::MfaChallengeResponseHttp,
+      auth::dtos::VerifyMfaRequest,
+      auth::dtos::ExternalAuthorizationHttp,
+      auth::dtos::ExternalCallbackRequest,
+      auth::dtos::ExternalSignUpRequiredHttp,
+      auth::dtos::ExternalSignUpRequest,
+      users::dtos::UserProfileResponseHttp,
+      users::dtos::UpdateUserProfileRequest,
+      users::dtos::TelephoneHttp,
+      users::dtos::EmailHttp,
+      users::dtos::AddressHttp,
+      users::dtos::SignInEventHttp,
+      users::dtos::SignInHistoryResponseHttp,
+      users::dtos::SessionHttp,
+      users::dtos::SessionListResponseHttp,
+      users::dtos::DeleteUserRequest,
+      users::dtos::VerifyTelephoneRequest,
+      users::dtos::TotpEnrollmentHttp,
+      users::dtos::ConfirmTotpRequest,
+      users::dtos::RecoveryCodesHttp,
+      admin::dtos::UserSummaryHttp,
+      admin::dtos::UserListResponseHttp,
+      admin::dtos::RegisterOAuthClientRequest,
+      admin::dtos::OAuthClientResponseHttp,
+      admin::dtos::AuditEventHttp,
+      admin::dtos::AuditEventListResponseHttp,
+      admin::dtos::AuditChainVerificationHttp,
+      oauth::dtos::AuthorizationRedirectHttp,
+      oauth::dtos::TokenRequestForm,
+      oauth::dtos::TokenResponseHttp,
+      oauth::dtos::OAuthErrorHttp,
+      oauth::dtos::UserInfoHttp,
+      routers::openid_configuration::OpenIdConfigurationHttp,
     )
   ),
   tags(
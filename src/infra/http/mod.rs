@@ -1,17 +1,27 @@
+pub mod rate_limit;
+
 use super::config::{
-  cors::default_cors, routes::routes_config, services::services_config, utilities::utilities_config,
+  cors::default_cors,
+  routes::routes_config,
+  services::{get_rate_limiter, services_config},
+  settings::settings,
+  utilities::utilities_config,
 };
 use crate::AppState;
-use actix_web::{web, App, HttpServer};
+use actix_web::{middleware::Condition, web, App, HttpServer};
 
 pub async fn start_http_server(state: AppState) -> Result<(), std::io::Error> {
+  let rate_limit_enabled = settings().rate_limit.enabled;
+  // built once so that every worker shares the in-memory buckets
+  let rate_limiter = get_rate_limiter(state.postgres_pool.clone());
   let app_state = web::Data::new(state);
 
   HttpServer::new(move || {
     App::new()
+      .wrap(Condition::new(rate_limit_enabled, rate_limiter.clone()))
       .wrap(default_cors())
       .app_data(app_state.clone())
-      .configure(services_config)
+      .configure(services_config(app_state.postgres_pool.clone()))
       .configure(utilities_config)
       .configure(routes_config)
   })
@@ -0,0 +1,349 @@
+use std::{
+  future::{ready, Ready},
+  pin::Pin,
+  rc::Rc,
+  sync::Arc,
+};
+
+use actix_web::{
+  body::EitherBody,
+  dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform},
+  error::PayloadError,
+  http::{
+    header::{HeaderMap, HeaderName, HeaderValue, RETRY_AFTER},
+    Method,
+  },
+  web::Bytes,
+  Error, HttpResponse,
+};
+use futures_util::{future::LocalBoxFuture, stream, Stream};
+use serde_json::Value;
+
+use crate::{
+  adapter::routers::helpers::client_ip::client_ip,
+  application::services::security::rate_limit::{RateLimit, RateLimitDecision, RateLimitStore},
+  domain::error::AppError,
+};
+
+/// Body fields naming the account a request targets, the first one present
+/// keys its bucket.
+const IDENTIFIER_FIELDS: [&str; 3] = ["username", "email", "telephone"];
+
+/// Throttles the POST requests to `paths` with a token bucket per client IP
+/// and another per account identifier found in the JSON body. Once either is
+/// empty the request is answered `429 Too Many Requests` with `Retry-After`;
+/// the `RateLimit-*` headers describe the most restrictive of the two.
+#[derive(Clone)]
+pub struct RateLimiter {
+  pub store: Arc<dyn RateLimitStore>,
+  pub paths: Vec<String>,
+  pub trust_forwarded_for: bool,
+  pub ip: RateLimit,
+  pub identifier: RateLimit,
+}
+
+impl RateLimiter {
+  fn limits(&self, req: &ServiceRequest) -> bool {
+    req.method() == Method::POST && self.paths.iter().any(|path| path == req.path())
+  }
+
+  fn client_ip(&self, req: &ServiceRequest) -> Option<String> {
+    client_ip(req.request(), self.trust_forwarded_for)
+  }
+
+  /// Takes a token from the IP bucket and, while it allows the request, from
+  /// the identifier bucket. None when the request has neither.
+  async fn take(
+    &self,
+    ip: Option<&str>,
+    identifier: Option<&str>,
+  ) -> Result<Option<RateLimitDecision>, AppError> {
+    let mut decision = None;
+
+    if let Some(ip) = ip {
+      let ip_decision = self.store.take(&format!("ip:{}", ip), &self.ip).await?;
+      if !ip_decision.allowed {
+        return Ok(Some(ip_decision));
+      }
+      decision = Some(ip_decision);
+    }
+
+    if let Some(identifier) = identifier {
+      let identifier_decision = self
+        .store
+        .take(&format!("identifier:{}", identifier), &self.identifier)
+        .await?;
+      decision = match decision {
+        Some(ip_decision)
+          if identifier_decision.allowed
+            && ip_decision.remaining <= identifier_decision.remaining =>
+        {
+          Some(ip_decision)
+        }
+        _ => Some(identifier_decision),
+      };
+    }
+
+    Ok(decision)
+  }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RateLimiter
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<EitherBody<B>>;
+  type Error = Error;
+  type Transform = RateLimiterMiddleware<S>;
+  type InitError = ();
+  type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+  fn new_transform(&self, service: S) -> Self::Future {
+    ready(Ok(RateLimiterMiddleware {
+      service: Rc::new(service),
+      limiter: Rc::new(self.clone()),
+    }))
+  }
+}
+
+pub struct RateLimiterMiddleware<S> {
+  service: Rc<S>,
+  limiter: Rc<RateLimiter>,
+}
+
+impl<S, B> Service<ServiceRequest> for RateLimiterMiddleware<S>
+where
+  S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+  B: 'static,
+{
+  type Response = ServiceResponse<EitherBody<B>>;
+  type Error = Error;
+  type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+  forward_ready!(service);
+
+  fn call(&self, mut req: ServiceRequest) -> Self::Future {
+    let service = Rc::clone(&self.service);
+    let limiter = Rc::clone(&self.limiter);
+
+    Box::pin(async move {
+      if !limiter.limits(&req) {
+        return service
+          .call(req)
+          .await
+          .map(ServiceResponse::map_into_left_body);
+      }
+
+      // the handler still needs the body once the identifier is read
+      let body = req.extract::<Bytes>().await?;
+      let identifier = identifier(&body);
+      req.set_payload(payload(body));
+
+      let ip = limiter.client_ip(&req);
+      let decision = match limiter.take(ip.as_deref(), identifier.as_deref()).await {
+        Ok(Some(decision)) => decision,
+        Ok(None) => {
+          return service
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_left_body)
+        }
+        Err(error) => {
+          let response: HttpResponse = error.into();
+          return Ok(req.into_response(response).map_into_right_body());
+        }
+      };
+
+      if !decision.allowed {
+        let mut response: HttpResponse = AppError::too_many_requests(format!(
+          "too many requests, retry in {} seconds",
+          decision.retry_after_sec
+        ))
+        .into();
+        set_headers(response.headers_mut(), &decision);
+
+        return Ok(req.into_response(response).map_into_right_body());
+      }
+
+      let mut response = service.call(req).await?;
+      set_headers(response.headers_mut(), &decision);
+
+      Ok(response.map_into_left_body())
+    })
+  }
+}
+
+fn identifier(body: &[u8]) -> Option<String> {
+  let body: Value = serde_json::from_slice(body).ok()?;
+
+  IDENTIFIER_FIELDS
+    .iter()
+    .filter_map(|field| body.get(field)?.as_str())
+    .map(|identifier| identifier.trim().to_lowercase())
+    .find(|identifier| !identifier.is_empty())
+}
+
+fn payload(body: Bytes) -> Payload {
+  let stream: Pin<Box<dyn Stream<Item = Result<Bytes, PayloadError>>>> =
+    Box::pin(stream::once(async move { Ok(body) }));
+
+  Payload::from(stream)
+}
+
+fn set_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+  headers.insert(
+    HeaderName::from_static("ratelimit-limit"),
+    HeaderValue::from(decision.limit),
+  );
+  headers.insert(
+    HeaderName::from_static("ratelimit-remaining"),
+    HeaderValue::from(decision.remaining),
+  );
+  headers.insert(
+    HeaderName::from_static("ratelimit-reset"),
+    HeaderValue::from(decision.reset_sec),
+  );
+  if !decision.allowed {
+    headers.insert(RETRY_AFTER, HeaderValue::from(decision.retry_after_sec));
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{
+    adapter::services::rate_limit::RateLimitStoreMemory,
+    application::services::security::rate_limit::MockRateLimitStore,
+  };
+  use actix_web::{http::header::ContentType, test, web, App};
+  use serde_json::json;
+
+  const SIGN_IN: &str = "/v1/auth/sign_in";
+  const IP: &str = "203.0.113.7:40000";
+  const OTHER_IP: &str = "198.51.100.23:40000";
+
+  fn rate_limiter(store: Arc<dyn RateLimitStore>) -> RateLimiter {
+    RateLimiter {
+      store,
+      paths: vec![SIGN_IN.to_string()],
+      trust_forwarded_for: false,
+      ip: RateLimit {
+        capacity: 2,
+        refill_per_min: 1,
+      },
+      identifier: RateLimit {
+        capacity: 1,
+        refill_per_min: 1,
+      },
+    }
+  }
+
+  async fn echo(body: web::Json<Value>) -> HttpResponse {
+    HttpResponse::Ok().json(body.0)
+  }
+
+  fn sign_in(peer_addr: &str, username: &str) -> test::TestRequest {
+    test::TestRequest::post()
+      .uri(SIGN_IN)
+      .peer_addr(peer_addr.parse().unwrap())
+      .insert_header(ContentType::json())
+      .set_payload(json!({ "username": username, "password": "12345678" }).to_string())
+  }
+
+  #[actix_web::test]
+  async fn test_limit_by_ip() {
+    let app = test::init_service(
+      App::new()
+        .wrap(rate_limiter(Arc::new(RateLimitStoreMemory::default())))
+        .route(SIGN_IN, web::post().to(echo)),
+    )
+    .await;
+
+    let res = test::call_service(&app, sign_in(IP, "john.doe").to_request()).await;
+
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get("ratelimit-limit").unwrap(), "1");
+    assert_eq!(res.headers().get("ratelimit-remaining").unwrap(), "0");
+    let body: Value = test::read_body_json(res).await;
+    assert_eq!(body["username"], "john.doe");
+
+    let res = test::call_service(&app, sign_in(IP, "jane.doe").to_request()).await;
+
+    assert_eq!(res.status(), 200);
+    assert_eq!(res.headers().get("ratelimit-limit").unwrap(), "2");
+    assert_eq!(res.headers().get("ratelimit-remaining").unwrap(), "0");
+
+    let res = test::call_service(&app, sign_in(IP, "mary.doe").to_request()).await;
+
+    assert_eq!(res.status(), 429);
+    assert_eq!(res.headers().get(RETRY_AFTER).unwrap(), "60");
+    assert_eq!(res.headers().get("ratelimit-reset").unwrap(), "120");
+  }
+
+  #[actix_web::test]
+  async fn test_limit_by_identifier_across_ips() {
+    let app = test::init_service(
+      App::new()
+        .wrap(rate_limiter(Arc::new(RateLimitStoreMemory::default())))
+        .route(SIGN_IN, web::post().to(echo)),
+    )
+    .await;
+
+    let res = test::call_service(&app, sign_in(IP, "john.doe").to_request()).await;
+
+    assert_eq!(res.status(), 200);
+
+    let res = test::call_service(&app, sign_in(OTHER_IP, " John.Doe ").to_request()).await;
+
+    assert_eq!(res.status(), 429);
+    assert_eq!(res.headers().get("ratelimit-limit").unwrap(), "1");
+    assert!(res.headers().contains_key(RETRY_AFTER));
+  }
+
+  #[actix_web::test]
+  async fn test_other_requests_are_not_limited() {
+    let mut store = MockRateLimitStore::new();
+    store.expect_take().never();
+    let app = test::init_service(
+      App::new()
+        .wrap(rate_limiter(Arc::new(store)))
+        .route(SIGN_IN, web::get().to(HttpResponse::Ok))
+        .route("/v1/auth/refresh", web::post().to(echo)),
+    )
+    .await;
+
+    let req = test::TestRequest::get()
+      .uri(SIGN_IN)
+      .peer_addr(IP.parse().unwrap())
+      .to_request();
+    let res = test::call_service(&app, req).await;
+
+    assert_eq!(res.status(), 200);
+    assert!(!res.headers().contains_key("ratelimit-limit"));
+
+    let req = sign_in(IP, "john.doe").uri("/v1/auth/refresh").to_request();
+    let res = test::call_service(&app, req).await;
+
+    assert_eq!(res.status(), 200);
+  }
+
+  #[actix_web::test]
+  async fn test_store_failure() {
+    let mut store = MockRateLimitStore::new();
+    store
+      .expect_take()
+      .times(1)
+      .returning(|_, _| Err(AppError::internal("database is down")));
+    let app = test::init_service(
+      App::new()
+        .wrap(rate_limiter(Arc::new(store)))
+        .route(SIGN_IN, web::post().to(echo)),
+    )
+    .await;
+
+    let res = test::call_service(&app, sign_in(IP, "john.doe").to_request()).await;
+
+    assert_eq!(res.status(), 500);
+  }
+}
@@ -1,3 +1,4 @@
 pub mod config;
 pub mod docs;
 pub mod http;
+pub mod tasks;
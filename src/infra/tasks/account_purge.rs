@@ -0,0 +1,41 @@
+use std::time::Duration;
+
+use log::{error, info};
+use sqlx::{Pool, Postgres};
+
+use crate::{
+  adapter::repositories::user::UserRepositoryDB,
+  domain::{core::user::repository::UserRepository, error::AppError},
+  infra::config::settings::settings,
+};
+
+/// Deletes, every `purge_interval_min`, the accounts whose deletion request
+/// is older than the configured grace period.
+pub fn spawn_account_purge(pool: Pool<Postgres>) {
+  let account_deletion = &settings().account_deletion;
+  let grace_period_days = account_deletion.grace_period_days;
+  let period = Duration::from_secs(account_deletion.purge_interval_min * 60);
+
+  tokio::spawn(async move {
+    let mut interval = tokio::time::interval(period);
+
+    loop {
+      interval.tick().await;
+
+      match purge_requested_deletions(&pool, grace_period_days).await {
+        Ok(0) => {}
+        Ok(purged) => info!("purged {} accounts requested for deletion", purged),
+        Err(error) => error!("account purge failed: {:?}", error),
+      }
+    }
+  });
+}
+
+pub async fn purge_requested_deletions(
+  pool: &Pool<Postgres>,
+  grace_period_days: i32,
+) -> Result<i32, AppError> {
+  UserRepositoryDB { pool }
+    .purge_requested_deletions(grace_period_days)
+    .await
+}
@@ -0,0 +1,28 @@
+use log::{error, info};
+use sqlx::{Pool, Postgres};
+
+use crate::{
+  adapter::repositories::user::UserRepositoryDB,
+  domain::{core::user::repository::UserRepository, entities::role::ADMIN_ROLE, error::AppError},
+};
+
+/// Grants the admin role to the configured profiles, so a new deployment has
+/// an administrator to assign the other roles.
+pub async fn bootstrap_admins(pool: &Pool<Postgres>, profile_ids: &[String]) {
+  for profile_id in profile_ids {
+    match grant_admin_role(pool, profile_id).await {
+      Ok(true) => info!("granted the admin role to {}", profile_id),
+      Ok(false) => {}
+      Err(error) => error!(
+        "granting the admin role to {} failed: {:?}",
+        profile_id, error
+      ),
+    }
+  }
+}
+
+pub async fn grant_admin_role(pool: &Pool<Postgres>, profile_id: &str) -> Result<bool, AppError> {
+  UserRepositoryDB { pool }
+    .grant_role(profile_id, ADMIN_ROLE)
+    .await
+}
@@ -0,0 +1,3 @@
+pub mod account_purge;
+pub mod admin_bootstrap;
+pub mod rate_limit_purge;
@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+use log::{error, info};
+use sqlx::{Pool, Postgres};
+
+use crate::{
+  adapter::services::rate_limit::RateLimitStoreDB,
+  infra::config::settings::{settings, RateLimitStoreKind},
+};
+
+/// Deletes, every `purge_interval_min`, the full buckets of the postgres
+/// rate limit store, which requests only drop for their own key.
+pub fn spawn_rate_limit_purge(pool: Pool<Postgres>) {
+  let rate_limit = &settings().rate_limit;
+  if !rate_limit.enabled || rate_limit.store != RateLimitStoreKind::Postgres {
+    return;
+  }
+  let period = Duration::from_secs(rate_limit.purge_interval_min * 60);
+
+  tokio::spawn(async move {
+    let store = RateLimitStoreDB { pool };
+    let mut interval = tokio::time::interval(period);
+
+    loop {
+      interval.tick().await;
+
+      match store.purge_full_buckets().await {
+        Ok(0) => {}
+        Ok(purged) => info!("purged {} full rate limit buckets", purged),
+        Err(error) => error!("rate limit bucket purge failed: {:?}", error),
+      }
+    }
+  });
+}
@@ -1,4 +1,11 @@
-use crate::infra::{config, http::start_http_server};
+use crate::infra::{
+  config,
+  http::start_http_server,
+  tasks::{
+    account_purge::spawn_account_purge, admin_bootstrap::bootstrap_admins,
+    rate_limit_purge::spawn_rate_limit_purge,
+  },
+};
 use log::info;
 use sqlx::{Pool, Postgres};
 
@@ -17,11 +24,25 @@ pub struct AppState {
 async fn main() -> std::io::Result<()> {
   config::log::setup_logger().expect("log configuration failed!");
 
+  info!("loading settings");
+  let settings = config::settings::settings();
+  info!("settings loaded with {:?} profile", settings.profile);
+  config::services::get_jwt_keys();
+
   info!("starting postgres pool creation");
   let postgres_pool = config::database::postgres_pool(None)
     .await
     .expect("postgres pool creation failed!");
 
+  info!("granting the admin role to the configured profiles");
+  bootstrap_admins(&postgres_pool, &settings.admin.profile_ids).await;
+
+  info!("starting account purge task");
+  spawn_account_purge(postgres_pool.clone());
+
+  info!("starting rate limit purge task");
+  spawn_rate_limit_purge(postgres_pool.clone());
+
   info!("starting application");
   start_http_server(AppState { postgres_pool }).await
 }
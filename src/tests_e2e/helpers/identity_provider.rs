@@ -0,0 +1,219 @@
+use std::{
+  collections::HashMap,
+  io::{BufRead, BufReader, Read, Write},
+  net::{TcpListener, TcpStream},
+  sync::{Arc, Mutex},
+  thread,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::Utc;
+use serde_json::json;
+
+use crate::{
+  adapter::{
+    services::jwt_keys::{
+      fixtures::{rsa_rotated_key, RSA_ROTATED_KID},
+      JWTKeys,
+    },
+    utilities::primitives::CryptoPrimitives,
+  },
+  application::services::security::external_identity_policy::ExternalProvider,
+  domain::core::user::exchange_authorization_code::validator::pkce_challenge,
+};
+
+const CLIENT_ID: &str = "phoenix";
+const CLIENT_SECRET: &str = "local-idp-secret";
+const REDIRECT_URI: &str = "http://localhost:4000/external/callback";
+
+/// Account the user signs in with at the provider.
+#[derive(Clone, Debug, Default)]
+pub struct LocalAccount {
+  pub subject: String,
+  pub email: String,
+  pub email_verified: bool,
+  pub name: String,
+}
+
+/// Authorization request waiting for its code to be redeemed.
+struct Grant {
+  account: LocalAccount,
+  code_challenge: String,
+  nonce: String,
+  redirect_uri: String,
+}
+
+type Grants = Arc<Mutex<HashMap<String, Grant>>>;
+
+/// OpenID Connect provider listening on localhost. The browser side of the
+/// flow is simulated by `authorize`, and its token endpoint redeems codes
+/// like a real provider: once, for its client and PKCE verifier.
+pub struct LocalIdentityProvider {
+  pub issuer: String,
+  grants: Grants,
+}
+
+impl LocalIdentityProvider {
+  pub fn start() -> Self {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let issuer = format!("http://{}", listener.local_addr().unwrap());
+    let grants: Grants = Arc::default();
+
+    let server_issuer = issuer.clone();
+    let server_grants = grants.clone();
+    thread::spawn(move || {
+      for stream in listener.incoming().flatten() {
+        serve(stream, &server_issuer, &server_grants);
+      }
+    });
+
+    LocalIdentityProvider { issuer, grants }
+  }
+
+  /// Configuration of the provider as the API gets it from the settings.
+  pub fn provider(&self, name: &str) -> ExternalProvider {
+    ExternalProvider {
+      name: name.to_string(),
+      issuer: self.issuer.clone(),
+      client_id: CLIENT_ID.to_string(),
+      client_secret: CLIENT_SECRET.to_string(),
+      authorization_endpoint: format!("{}/authorize", self.issuer),
+      token_endpoint: format!("{}/token", self.issuer),
+      jwks_uri: format!("{}/jwks", self.issuer),
+      redirect_uri: REDIRECT_URI.to_string(),
+      scope: String::from("openid email profile"),
+    }
+  }
+
+  /// Signs `account` in at the authorization URL the API sent the browser
+  /// to, returning the code and state the browser is sent back with.
+  pub fn authorize(&self, authorization_url: &str, account: &LocalAccount) -> (String, String) {
+    assert!(authorization_url.starts_with(&format!("{}/authorize?", self.issuer)));
+    assert_eq!(
+      query_param(authorization_url, "client_id").unwrap(),
+      CLIENT_ID
+    );
+    assert_eq!(
+      query_param(authorization_url, "code_challenge_method").unwrap(),
+      "S256"
+    );
+
+    let code = format!("code-{}", self.grants.lock().unwrap().len() + 1);
+    self.grants.lock().unwrap().insert(
+      code.clone(),
+      Grant {
+        account: account.clone(),
+        code_challenge: query_param(authorization_url, "code_challenge").unwrap(),
+        nonce: query_param(authorization_url, "nonce").unwrap(),
+        redirect_uri: query_param(authorization_url, "redirect_uri").unwrap(),
+      },
+    );
+
+    (code, query_param(authorization_url, "state").unwrap())
+  }
+}
+
+/// Query parameter `name` of `url`, decoded.
+pub fn query_param(url: &str, name: &str) -> Option<String> {
+  let (_, query) = url.split_once('?')?;
+  query.split('&').find_map(|pair| {
+    let (key, value) = pair.split_once('=')?;
+    (key == name).then(|| urlencoding::decode(value).unwrap().into_owned())
+  })
+}
+
+fn serve(stream: TcpStream, issuer: &str, grants: &Grants) {
+  let mut reader = BufReader::new(stream);
+  let mut headers = HashMap::new();
+  let mut line = String::new();
+
+  reader.read_line(&mut line).unwrap();
+  let is_token_request = line.starts_with("POST /token ");
+  let is_jwks_request = line.starts_with("GET /jwks ");
+  loop {
+    line.clear();
+    reader.read_line(&mut line).unwrap();
+    let Some((key, value)) = line.trim_end().split_once(": ") else {
+      break;
+    };
+    headers.insert(key.to_lowercase(), value.to_string());
+  }
+
+  let length = headers
+    .get("content-length")
+    .map_or(0, |length| length.parse().unwrap());
+  let mut body = vec![0; length];
+  reader.read_exact(&mut body).unwrap();
+  let form = format!("?{}", String::from_utf8(body).unwrap());
+
+  let basic = format!(
+    "Basic {}",
+    STANDARD.encode(format!("{}:{}", CLIENT_ID, CLIENT_SECRET))
+  );
+  let (status, response) = if is_jwks_request {
+    ("200 OK", json!(keys().jwks()))
+  } else if !is_token_request {
+    ("404 Not Found", json!({ "error": "not_found" }))
+  } else if headers.get("authorization") != Some(&basic) {
+    ("401 Unauthorized", json!({ "error": "invalid_client" }))
+  } else {
+    let code = query_param(&form, "code").unwrap_or_default();
+    match grants.lock().unwrap().remove(&code) {
+      Some(grant)
+        if query_param(&form, "grant_type").as_deref() == Some("authorization_code")
+          && query_param(&form, "redirect_uri") == Some(grant.redirect_uri.clone())
+          && query_param(&form, "code_verifier")
+            .map(|verifier| pkce_challenge(&CryptoPrimitives, &verifier))
+            == Some(grant.code_challenge.clone()) =>
+      {
+        (
+          "200 OK",
+          json!({
+            "access_token": "local-access-token",
+            "token_type": "Bearer",
+            "id_token": id_token(issuer, &grant),
+          }),
+        )
+      }
+      _ => ("400 Bad Request", json!({ "error": "invalid_grant" })),
+    }
+  };
+
+  let body = response.to_string();
+  let mut stream = reader.into_inner();
+  write!(
+    stream,
+    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+    status,
+    body.len(),
+    body
+  )
+  .unwrap();
+}
+
+/// Signing key of the provider, published at its `/jwks`.
+fn keys() -> JWTKeys {
+  JWTKeys::from_pem(RSA_ROTATED_KID, &[rsa_rotated_key()]).unwrap()
+}
+
+fn id_token(issuer: &str, grant: &Grant) -> String {
+  let keys = keys();
+  let account = &grant.account;
+
+  jsonwebtoken::encode(
+    &keys.header(),
+    &json!({
+      "iss": issuer,
+      "aud": CLIENT_ID,
+      "exp": Utc::now().timestamp() + 300,
+      "iat": Utc::now().timestamp(),
+      "nonce": grant.nonce,
+      "sub": account.subject,
+      "email": account.email,
+      "email_verified": account.email_verified,
+      "name": account.name,
+    }),
+    keys.encoding_key(),
+  )
+  .unwrap()
+}
@@ -1,6 +1,7 @@
 use jsonwebtoken::get_current_timestamp;
 
 use crate::{
+  adapter::services::token_revocation::TokenRevocationStoreMemory,
   application::services::security::token_service::TokenService,
   infra::config::services::get_jwt_service,
 };
@@ -9,8 +10,11 @@ pub enum TokenExpirationExpect {
   GreatThan(u64),
 }
 
-pub fn assert_jwt(token: &str, sub: &str, aud: &str, expiration: TokenExpirationExpect) {
-  let token_decoded = get_jwt_service().decode(token).unwrap();
+pub async fn assert_jwt(token: &str, sub: &str, aud: &str, expiration: TokenExpirationExpect) {
+  let token_decoded = get_jwt_service(TokenRevocationStoreMemory::default())
+    .decode(token)
+    .await
+    .unwrap();
 
   assert_eq!(token_decoded.sub, sub);
   assert_eq!(token_decoded.aud, aud);
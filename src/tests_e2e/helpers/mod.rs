@@ -1,2 +1,5 @@
+pub mod identity_provider;
 pub mod jwt;
+pub mod oauth;
 pub mod user_repository;
+pub mod webauthn;
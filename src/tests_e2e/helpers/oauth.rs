@@ -0,0 +1,105 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use jsonwebtoken::Validation;
+use sqlx::PgPool;
+
+use crate::{
+  adapter::{repositories::user::UserRepositoryDB, utilities::primitives::CryptoPrimitives},
+  application::services::security::token_service::IdToken,
+  domain::{
+    core::user::{
+      exchange_authorization_code::validator::pkce_challenge, repository::UserRepository,
+    },
+    entities::oauth::NewOAuthClient,
+  },
+  infra::config::services::get_jwt_keys,
+};
+
+const REDIRECT_URI: &str = "http://localhost:4000/callback";
+const CODE_VERIFIER: &str = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+/// Confidential client running on localhost, driving the authorization code
+/// flow the way a relying party does.
+pub struct LocalClient {
+  pub client_id: String,
+  pub client_secret: String,
+  pub redirect_uri: &'static str,
+  pub code_verifier: &'static str,
+}
+
+impl LocalClient {
+  pub async fn register(pool: &PgPool, client_id: &str, client_secret: &str) -> Self {
+    UserRepositoryDB { pool }
+      .store_oauth_client(&NewOAuthClient {
+        client_id: client_id.to_string(),
+        name: String::from("Local client"),
+        redirect_uris: vec![REDIRECT_URI.to_string()],
+        secret_hash: Some(bcrypt::hash(client_secret, 5).unwrap()),
+      })
+      .await
+      .unwrap();
+
+    LocalClient {
+      client_id: client_id.to_string(),
+      client_secret: client_secret.to_string(),
+      redirect_uri: REDIRECT_URI,
+      code_verifier: CODE_VERIFIER,
+    }
+  }
+
+  /// Path and query of `GET /v1/oauth/authorize`.
+  pub fn authorize_uri(&self, state: &str, nonce: &str) -> String {
+    format!(
+      "/v1/oauth/authorize?response_type=code&client_id={}&redirect_uri={}&scope={}&state={}&nonce={}&code_challenge={}&code_challenge_method=S256",
+      self.client_id,
+      urlencoding::encode(self.redirect_uri),
+      urlencoding::encode("openid profile"),
+      state,
+      nonce,
+      pkce_challenge(&CryptoPrimitives, self.code_verifier),
+    )
+  }
+
+  /// Value of the `Authorization` header for `client_secret_basic`.
+  pub fn basic_authorization(&self) -> String {
+    let credentials = format!(
+      "{}:{}",
+      urlencoding::encode(&self.client_id),
+      urlencoding::encode(&self.client_secret)
+    );
+    format!("Basic {}", STANDARD.encode(credentials))
+  }
+
+  /// Form of `POST /v1/oauth/token` redeeming `code`.
+  pub fn token_form(&self, code: &str) -> String {
+    format!(
+      "grant_type=authorization_code&code={}&redirect_uri={}&code_verifier={}",
+      urlencoding::encode(code),
+      urlencoding::encode(self.redirect_uri),
+      self.code_verifier,
+    )
+  }
+
+  /// Query parameter `name` of the URI the browser was sent back with.
+  pub fn callback_param(redirect_to: &str, name: &str) -> Option<String> {
+    let (_, query) = redirect_to.split_once('?')?;
+    query.split('&').find_map(|pair| {
+      let (key, value) = pair.split_once('=')?;
+      (key == name).then(|| urlencoding::decode(value).unwrap().into_owned())
+    })
+  }
+
+  /// Verifies the ID token signature, issuer and audience as the client
+  /// would, with the keys published at the JWKS endpoint.
+  pub fn verify_id_token(&self, id_token: &str, issuer: &str) -> IdToken {
+    let header = jsonwebtoken::decode_header(id_token).unwrap();
+    let (key, algorithm) = get_jwt_keys().decoding_key(&header).unwrap();
+
+    let mut validation = Validation::new(algorithm);
+    validation.set_audience(&[&self.client_id]);
+    validation.set_issuer(&[issuer]);
+
+    jsonwebtoken::decode::<IdToken>(id_token, key, &validation)
+      .unwrap()
+      .claims
+  }
+}
@@ -3,6 +3,7 @@ use sqlx::PgPool;
 
 use crate::domain::entities::user::{UserColumns, UserData};
 
+#[allow(clippy::too_many_arguments)]
 pub async fn insert_user(
   pool: &PgPool,
   id: &str,
@@ -40,6 +41,43 @@ pub async fn insert_user(
   .unwrap();
 }
 
+pub async fn disable_user(pool: &PgPool, id: &str) {
+  sqlx::query!(
+    "UPDATE users SET account_disabled = TRUE FROM profiles WHERE profiles.user_id = users.id AND profiles.id = $1",
+    id,
+  )
+  .execute(pool)
+  .await
+  .unwrap();
+}
+
+pub async fn requested_deletion(pool: &PgPool, id: &str) -> bool {
+  sqlx::query!(
+    "SELECT users.requested_deletion FROM users JOIN profiles ON profiles.user_id = users.id WHERE profiles.id = $1",
+    id,
+  )
+  .fetch_one(pool)
+  .await
+  .unwrap()
+  .requested_deletion
+}
+
+pub async fn email_checked(pool: &PgPool, address: &str) -> bool {
+  sqlx::query!("SELECT checked FROM emails WHERE address = $1", address)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+    .checked
+}
+
+pub async fn telephone_checked(pool: &PgPool, number: &str) -> bool {
+  sqlx::query!("SELECT checked FROM telephones WHERE number = $1", number)
+    .fetch_one(pool)
+    .await
+    .unwrap()
+    .checked
+}
+
 pub async fn assert_user(
   pool: &PgPool,
   by_column: &UserColumns<'_>,
@@ -0,0 +1,90 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ring::{
+  rand::SystemRandom,
+  signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING},
+};
+use serde_json::{json, Value};
+
+use crate::{
+  adapter::utilities::primitives::CryptoPrimitives,
+  domain::{
+    core::user::{
+      mocks::webauthn::{
+        attestation_object, authenticator_data, client_data_json, cose_key, CREDENTIAL_ID_B64,
+        FLAGS_ASSERTION, FLAGS_REGISTRATION,
+      },
+      webauthn::{signed_data, CEREMONY_CREATE, CEREMONY_GET},
+    },
+    utilities::primitives::Primitives,
+  },
+};
+
+/// Platform authenticator in software, holding a single P-256 passkey and
+/// answering ceremonies the way a browser relays them.
+pub struct SoftAuthenticator {
+  key_pair: EcdsaKeyPair,
+  rng: SystemRandom,
+  rp_id: String,
+  origin: String,
+}
+
+impl SoftAuthenticator {
+  pub fn new(rp_id: &str, origin: &str) -> Self {
+    let rng = SystemRandom::new();
+    let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+
+    SoftAuthenticator {
+      key_pair: EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref()).unwrap(),
+      rng,
+      rp_id: rp_id.to_string(),
+      origin: origin.to_string(),
+    }
+  }
+
+  pub fn credential_id(&self) -> &'static str {
+    CREDENTIAL_ID_B64
+  }
+
+  /// Body of `POST /v1/auth/webauthn/register` answering `challenge`.
+  pub fn create(&self, challenge: &str) -> Value {
+    let client_data = client_data_json(CEREMONY_CREATE, challenge, &self.origin);
+    let data = authenticator_data(
+      &CryptoPrimitives.sha256(self.rp_id.as_bytes()),
+      FLAGS_REGISTRATION,
+      0,
+      Some(&cose_key(self.key_pair.public_key().as_ref())),
+    );
+
+    json!({
+      "credential_id": CREDENTIAL_ID_B64,
+      "client_data_json": URL_SAFE_NO_PAD.encode(client_data),
+      "attestation_object": URL_SAFE_NO_PAD.encode(attestation_object("none", &data)),
+      "name": "Soft authenticator",
+    })
+  }
+
+  /// Body of `POST /v1/auth/webauthn/sign_in` answering `challenge`.
+  pub fn get(&self, challenge: &str, sign_count: u32) -> Value {
+    let client_data = client_data_json(CEREMONY_GET, challenge, &self.origin);
+    let data = authenticator_data(
+      &CryptoPrimitives.sha256(self.rp_id.as_bytes()),
+      FLAGS_ASSERTION,
+      sign_count,
+      None,
+    );
+    let signature = self
+      .key_pair
+      .sign(
+        &self.rng,
+        &signed_data(&CryptoPrimitives, &data, &client_data),
+      )
+      .unwrap();
+
+    json!({
+      "credential_id": CREDENTIAL_ID_B64,
+      "client_data_json": URL_SAFE_NO_PAD.encode(client_data),
+      "authenticator_data": URL_SAFE_NO_PAD.encode(data),
+      "signature": URL_SAFE_NO_PAD.encode(signature.as_ref()),
+    })
+  }
+}
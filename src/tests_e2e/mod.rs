@@ -1,22 +1,59 @@
 pub mod helpers;
 use crate::{
-  adapter::routers::v1::auth::dtos::UserAuthenticationResponseHttp,
-  application::services::security::token_service::TokenService,
-  domain::entities::user::{UserColumns, UserData},
+  adapter::{
+    repositories::user::UserRepositoryDB,
+    routers::v1::{
+      admin::dtos::{AuditChainVerificationHttp, AuditEventListResponseHttp, UserListResponseHttp},
+      auth::dtos::{
+        ExternalAuthorizationHttp, ExternalSignUpRequiredHttp, MfaChallengeResponseHttp,
+        UserAuthenticationResponseHttp, WebAuthnCredentialResponseHttp,
+        WebAuthnRegistrationOptionsHttp, WebAuthnSignInOptionsHttp,
+      },
+      oauth::dtos::{AuthorizationRedirectHttp, OAuthErrorHttp, TokenResponseHttp, UserInfoHttp},
+      users::dtos::{
+        RecoveryCodesHttp, SessionListResponseHttp, SignInHistoryResponseHttp, TotpEnrollmentHttp,
+        UserProfileResponseHttp,
+      },
+    },
+    services::token_revocation::TokenRevocationStoreMemory,
+    utilities::primitives::CryptoPrimitives,
+  },
+  application::services::{
+    security::{external_identity_policy::ExternalIdentityPolicy, token_service::TokenService},
+    Services,
+  },
+  domain::{
+    core::user::{repository::UserRepository, totp},
+    entities::{
+      role::{permissions, Grants, ADMIN_ROLE},
+      user::{UserColumns, UserData},
+      verification_token::{NewVerificationToken, VerificationPurpose},
+    },
+    utilities::{crypto::Crypto, primitives::Primitives},
+  },
   infra::config::{
     cors::default_cors,
     routes::routes_config,
-    services::{get_jwt_service, services_config},
-    utilities::utilities_config,
+    services::{get_jwt_service, get_services, services_config},
+    settings::settings,
+    utilities::{get_crypto, utilities_config},
   },
+  infra::tasks::admin_bootstrap::grant_admin_role,
   tests_e2e::helpers::{
+    identity_provider::{LocalAccount, LocalIdentityProvider},
     jwt::{assert_jwt, TokenExpirationExpect},
-    user_repository::{assert_user, insert_user},
+    oauth::LocalClient,
+    user_repository::{
+      assert_user, disable_user, email_checked, insert_user, requested_deletion, telephone_checked,
+    },
+    webauthn::SoftAuthenticator,
   },
   AppState,
 };
 use actix_web::{
-  http::header::{ContentType, HeaderValue, AUTHORIZATION},
+  http::header::{
+    ContentType, HeaderValue, AUTHORIZATION, CACHE_CONTROL, USER_AGENT, WWW_AUTHENTICATE,
+  },
   test::{self},
   web, App,
 };
@@ -24,7 +61,7 @@ use serde_json::json;
 use sqlx::{PgPool, Result};
 use uuid::Uuid;
 
-const TWO_HOURS: u64 = 1000 * 60 * 120;
+const TWO_HOURS: u64 = 60 * 120;
 const NAME: &str = "John Doe";
 const USERNAME: &str = "john.doe";
 const BIRTH_DATE: &str = "1990-01-01";
@@ -41,20 +78,20 @@ const TELEPHONE_NUMBER: &str = "+1 4152370800 ";
 
 #[sqlx::test]
 async fn test_get_index(pool: PgPool) -> Result<()> {
-  let mut app = test::init_service(
+  let app = test::init_service(
     App::new()
       .wrap(default_cors())
       .app_data(web::Data::new(AppState {
         postgres_pool: pool.clone(),
       }))
-      .configure(services_config)
+      .configure(services_config(pool.clone()))
       .configure(utilities_config)
       .configure(routes_config),
   )
   .await;
 
   let req = test::TestRequest::get().uri("/").to_request();
-  let res = test::call_service(&mut app, req).await;
+  let res = test::call_service(&app, req).await;
 
   assert_eq!(res.status(), 200);
   Ok(())
@@ -79,8 +116,9 @@ async fn test_register(pool: PgPool) -> Result<()> {
     &body.token,
     &body.id,
     "authentication_user",
-    TokenExpirationExpect::GreatThan(TWO_HOURS - 1),
-  );
+    TokenExpirationExpect::GreatThan(TWO_HOURS - 60),
+  )
+  .await;
   assert_user(
     &pool,
     &UserColumns::Id(&body.id),
@@ -89,8 +127,11 @@ async fn test_register(pool: PgPool) -> Result<()> {
       name: NAME.to_string(),
       username: USERNAME.to_string(),
       password: PASSWORD.to_string(),
+      ..Default::default()
     },
-    Some(|pwd, hash| bcrypt::verify(pwd, hash).unwrap()),
+    Some(|pwd, hash| {
+      hash.starts_with("$argon2id$") && get_crypto().verify_password(hash, pwd).unwrap()
+    }),
   )
   .await;
   Ok(())
@@ -343,13 +384,13 @@ async fn test_sign_with_username(pool: PgPool) -> Result<()> {
   )
   .await;
 
-  let mut app = test::init_service(
+  let app = test::init_service(
     App::new()
       .wrap(default_cors())
       .app_data(web::Data::new(AppState {
         postgres_pool: pool.clone(),
       }))
-      .configure(services_config)
+      .configure(services_config(pool.clone()))
       .configure(utilities_config)
       .configure(routes_config),
   )
@@ -367,7 +408,7 @@ async fn test_sign_with_username(pool: PgPool) -> Result<()> {
     )
     .to_request();
 
-  let res = test::call_service(&mut app, req).await;
+  let res = test::call_service(&app, req).await;
 
   assert_eq!(res.status(), 200);
 
@@ -379,8 +420,9 @@ async fn test_sign_with_username(pool: PgPool) -> Result<()> {
     &body.token,
     &body.id,
     "authentication_user",
-    TokenExpirationExpect::GreatThan(TWO_HOURS - 1),
-  );
+    TokenExpirationExpect::GreatThan(TWO_HOURS - 60),
+  )
+  .await;
   assert_user(
     &pool,
     &UserColumns::Id(&body.id),
@@ -389,8 +431,11 @@ async fn test_sign_with_username(pool: PgPool) -> Result<()> {
       name: NAME.to_string(),
       username: USERNAME.to_string(),
       password: PASSWORD.to_string(),
+      ..Default::default()
     },
-    Some(|pwd, hash| bcrypt::verify(pwd, hash).unwrap()),
+    Some(|pwd, hash| {
+      hash.starts_with("$argon2id$") && get_crypto().verify_password(hash, pwd).unwrap()
+    }),
   )
   .await;
   Ok(())
@@ -416,13 +461,13 @@ async fn test_sign_with_email(pool: PgPool) -> Result<()> {
   )
   .await;
 
-  let mut app = test::init_service(
+  let app = test::init_service(
     App::new()
       .wrap(default_cors())
       .app_data(web::Data::new(AppState {
         postgres_pool: pool.clone(),
       }))
-      .configure(services_config)
+      .configure(services_config(pool.clone()))
       .configure(utilities_config)
       .configure(routes_config),
   )
@@ -440,7 +485,7 @@ async fn test_sign_with_email(pool: PgPool) -> Result<()> {
     )
     .to_request();
 
-  let res = test::call_service(&mut app, req).await;
+  let res = test::call_service(&app, req).await;
 
   assert_eq!(res.status(), 200);
 
@@ -452,8 +497,9 @@ async fn test_sign_with_email(pool: PgPool) -> Result<()> {
     &body.token,
     &body.id,
     "authentication_user",
-    TokenExpirationExpect::GreatThan(TWO_HOURS - 1),
-  );
+    TokenExpirationExpect::GreatThan(TWO_HOURS - 60),
+  )
+  .await;
   assert_user(
     &pool,
     &UserColumns::Id(&body.id),
@@ -462,8 +508,11 @@ async fn test_sign_with_email(pool: PgPool) -> Result<()> {
       name: NAME.to_string(),
       username: USERNAME.to_string(),
       password: PASSWORD.to_string(),
+      ..Default::default()
     },
-    Some(|pwd, hash| bcrypt::verify(pwd, hash).unwrap()),
+    Some(|pwd, hash| {
+      hash.starts_with("$argon2id$") && get_crypto().verify_password(hash, pwd).unwrap()
+    }),
   )
   .await;
   Ok(())
@@ -489,13 +538,13 @@ async fn test_sign_with_telephone(pool: PgPool) -> Result<()> {
   )
   .await;
 
-  let mut app = test::init_service(
+  let app = test::init_service(
     App::new()
       .wrap(default_cors())
       .app_data(web::Data::new(AppState {
         postgres_pool: pool.clone(),
       }))
-      .configure(services_config)
+      .configure(services_config(pool.clone()))
       .configure(utilities_config)
       .configure(routes_config),
   )
@@ -513,7 +562,7 @@ async fn test_sign_with_telephone(pool: PgPool) -> Result<()> {
     )
     .to_request();
 
-  let res = test::call_service(&mut app, req).await;
+  let res = test::call_service(&app, req).await;
 
   assert_eq!(res.status(), 200);
 
@@ -525,8 +574,9 @@ async fn test_sign_with_telephone(pool: PgPool) -> Result<()> {
     &body.token,
     &body.id,
     "authentication_user",
-    TokenExpirationExpect::GreatThan(TWO_HOURS - 1),
-  );
+    TokenExpirationExpect::GreatThan(TWO_HOURS - 60),
+  )
+  .await;
   assert_user(
     &pool,
     &UserColumns::Id(&body.id),
@@ -535,8 +585,11 @@ async fn test_sign_with_telephone(pool: PgPool) -> Result<()> {
       name: NAME.to_string(),
       username: USERNAME.to_string(),
       password: PASSWORD.to_string(),
+      ..Default::default()
     },
-    Some(|pwd, hash| bcrypt::verify(pwd, hash).unwrap()),
+    Some(|pwd, hash| {
+      hash.starts_with("$argon2id$") && get_crypto().verify_password(hash, pwd).unwrap()
+    }),
   )
   .await;
   Ok(())
@@ -562,13 +615,13 @@ async fn test_sign_with_username_given_wrong_password(pool: PgPool) -> Result<()
   )
   .await;
 
-  let mut app = test::init_service(
+  let app = test::init_service(
     App::new()
       .wrap(default_cors())
       .app_data(web::Data::new(AppState {
         postgres_pool: pool.clone(),
       }))
-      .configure(services_config)
+      .configure(services_config(pool.clone()))
       .configure(utilities_config)
       .configure(routes_config),
   )
@@ -586,19 +639,18 @@ async fn test_sign_with_username_given_wrong_password(pool: PgPool) -> Result<()
     )
     .to_request();
 
-  let res = test::call_service(&mut app, req).await;
+  let res = test::call_service(&app, req).await;
 
   assert_eq!(res.status(), 401);
   Ok(())
 }
 
 #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
-async fn test_change_password_successfully(pool: PgPool) -> Result<()> {
+async fn test_sign_in_locked_after_too_many_wrong_passwords(pool: PgPool) -> Result<()> {
   let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
-  let id = Uuid::new_v4().to_string();
   insert_user(
     &pool,
-    &id,
+    &Uuid::new_v4().to_string(),
     NAME,
     USERNAME,
     BIRTH_DATE,
@@ -613,48 +665,62 @@ async fn test_change_password_successfully(pool: PgPool) -> Result<()> {
   )
   .await;
 
-  let token = get_jwt_service()
-    .encode(id.clone(), "authentication_user".to_string(), 10)
-    .unwrap();
-
-  let mut app = test::init_service(
+  let app = test::init_service(
     App::new()
       .wrap(default_cors())
       .app_data(web::Data::new(AppState {
         postgres_pool: pool.clone(),
       }))
-      .configure(services_config)
+      .configure(services_config(pool.clone()))
       .configure(utilities_config)
       .configure(routes_config),
   )
   .await;
 
-  let req = test::TestRequest::put()
-    .uri("/v1/auth/change_password")
+  let max_failed_attempts = settings().sign_in.max_failed_attempts;
+  for attempt in 1..=max_failed_attempts {
+    let req = test::TestRequest::post()
+      .uri("/v1/auth/sign_in")
+      .insert_header(ContentType::json())
+      .set_payload(
+        json!({
+          "username": USERNAME,
+          "password": WRONG_PASSWORD,
+        })
+        .to_string(),
+      )
+      .to_request();
+
+    let res = test::call_service(&app, req).await;
+
+    let expected_status = if attempt < max_failed_attempts {
+      401
+    } else {
+      423
+    };
+    assert_eq!(res.status(), expected_status);
+  }
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
     .insert_header(ContentType::json())
-    .append_header((
-      AUTHORIZATION,
-      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
-    ))
     .set_payload(
       json!({
-        "profile_id": id,
-        "old_password": PASSWORD,
-        "password": NEW_PASSWORD,
-        "password_repetition": NEW_PASSWORD,
+        "username": USERNAME,
+        "password": PASSWORD,
       })
       .to_string(),
     )
     .to_request();
 
-  let res = test::call_service(&mut app, req).await;
+  let res = test::call_service(&app, req).await;
 
-  assert_eq!(res.status(), 200);
+  assert_eq!(res.status(), 423);
   Ok(())
 }
 
 #[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
-async fn test_change_password_given_old_wrong_password(pool: PgPool) -> Result<()> {
+async fn test_sign_in_with_disabled_account(pool: PgPool) -> Result<()> {
   let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
   let id = Uuid::new_v4().to_string();
   insert_user(
@@ -673,135 +739,3232 @@ async fn test_change_password_given_old_wrong_password(pool: PgPool) -> Result<(
     Some(TELEPHONE_NUMBER),
   )
   .await;
+  disable_user(&pool, &id).await;
 
-  let token = get_jwt_service()
-    .encode(id.clone(), "authentication_user".to_string(), 10)
-    .unwrap();
-
-  let mut app = test::init_service(
+  let app = test::init_service(
     App::new()
       .wrap(default_cors())
       .app_data(web::Data::new(AppState {
         postgres_pool: pool.clone(),
       }))
-      .configure(services_config)
+      .configure(services_config(pool.clone()))
       .configure(utilities_config)
       .configure(routes_config),
   )
   .await;
 
-  let req = test::TestRequest::put()
-    .uri("/v1/auth/change_password")
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
     .insert_header(ContentType::json())
-    .append_header((
-      AUTHORIZATION,
-      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
-    ))
     .set_payload(
       json!({
-        "profile_id": id,
-        "old_password": WRONG_PASSWORD,
-        "password": NEW_PASSWORD,
-        "password_repetition": NEW_PASSWORD,
+        "username": USERNAME,
+        "password": PASSWORD,
       })
       .to_string(),
     )
     .to_request();
 
-  let res = test::call_service(&mut app, req).await;
+  let res = test::call_service(&app, req).await;
 
   assert_eq!(res.status(), 403);
   Ok(())
 }
 
-#[sqlx::test(migrations = false)]
-async fn test_change_password_given_not_match_new_password(pool: PgPool) -> Result<()> {
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_disable_user_with_admin_role(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
   let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+  let other_id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &other_id,
+    "Jane Doe",
+    "jane.doe",
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    "janedoe@company.com",
+    None,
+  )
+  .await;
+  assert!(grant_admin_role(&pool, &id).await.unwrap());
 
-  let token = get_jwt_service()
-    .encode(id.clone(), "authentication_user".to_string(), 10)
-    .unwrap();
-
-  let mut app = test::init_service(
+  let app = test::init_service(
     App::new()
       .wrap(default_cors())
       .app_data(web::Data::new(AppState {
         postgres_pool: pool.clone(),
       }))
-      .configure(services_config)
+      .configure(services_config(pool.clone()))
       .configure(utilities_config)
       .configure(routes_config),
   )
   .await;
 
-  let req = test::TestRequest::put()
-    .uri("/v1/auth/change_password")
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
     .insert_header(ContentType::json())
-    .append_header((
-      AUTHORIZATION,
-      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
-    ))
     .set_payload(
       json!({
-        "profile_id": id,
-        "old_password": PASSWORD,
-        "password": NEW_PASSWORD,
-        "password_repetition": WRONG_PASSWORD,
+        "username": USERNAME,
+        "password": PASSWORD,
       })
       .to_string(),
     )
     .to_request();
 
-  let res = test::call_service(&mut app, req).await;
+  let res = test::call_service(&app, req).await;
 
-  assert_eq!(res.status(), 400);
+  assert_eq!(res.status(), 200);
+  let body: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+  let token_decoded = get_jwt_service(TokenRevocationStoreMemory::default())
+    .decode(&body.token)
+    .await
+    .unwrap();
+  assert_eq!(token_decoded.roles, vec![ADMIN_ROLE.to_string()]);
+  assert_eq!(
+    token_decoded.scope,
+    format!(
+      "{} {} {}",
+      permissions::AUDIT_EVENTS_READ,
+      permissions::OAUTH_CLIENTS_ADMIN,
+      permissions::USERS_ADMIN
+    )
+  );
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "username": "jane.doe", "password": PASSWORD }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let other: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+
+  let req = test::TestRequest::post()
+    .uri(&format!("/v1/admin/users/{}/disable", other_id))
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", body.token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", other.token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/refresh")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "refresh_token": other.refresh_token }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+  let live_tokens = sqlx::query_scalar!(
+    r#"SELECT COUNT(*) AS "total!" FROM refresh_tokens WHERE profile_id = $1 AND revoked_at IS NULL"#,
+    other_id
+  )
+  .fetch_one(&pool)
+  .await?;
+  assert_eq!(live_tokens, 0);
   Ok(())
 }
 
-#[sqlx::test(migrations = false)]
-async fn test_change_password_given_wrong_token_for_profile_id(pool: PgPool) -> Result<()> {
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_disable_user_without_admin_permission(pool: PgPool) -> Result<()> {
   let id = Uuid::new_v4().to_string();
-  let other_id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    PASSWORD,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
 
-  let token = get_jwt_service()
-    .encode(id.clone(), "authentication_user".to_string(), 10)
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
     .unwrap();
 
-  let authorization = format!("Bearer {}", token);
-
-  let mut app = test::init_service(
+  let app = test::init_service(
     App::new()
       .wrap(default_cors())
       .app_data(web::Data::new(AppState {
         postgres_pool: pool.clone(),
       }))
-      .configure(services_config)
+      .configure(services_config(pool.clone()))
       .configure(utilities_config)
       .configure(routes_config),
   )
   .await;
 
-  let req = test::TestRequest::put()
-    .uri("/v1/auth/change_password")
-    .insert_header(ContentType::json())
+  let req = test::TestRequest::post()
+    .uri(&format!("/v1/admin/users/{}/disable", id))
     .append_header((
       AUTHORIZATION,
-      HeaderValue::from_str(&authorization).unwrap(),
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
     ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 403);
+
+  let denied = sqlx::query!(
+    "SELECT actor_id, outcome, metadata FROM audit_events WHERE action = 'access.permission_denied'"
+  )
+  .fetch_one(&pool)
+  .await?;
+  assert_eq!(denied.actor_id.as_deref(), Some(id.as_str()));
+  assert_eq!(denied.outcome, "failure");
+  assert_eq!(denied.metadata["permission"], "users:admin");
+  assert_eq!(
+    denied.metadata["path"],
+    format!("/v1/admin/users/{}/disable", id)
+  );
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_admin_manage_users(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+  let other_id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &other_id,
+    "Jane Doe",
+    "jane.doe",
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    "janedoe@company.com",
+    None,
+  )
+  .await;
+  assert!(grant_admin_role(&pool, &id).await.unwrap());
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
     .set_payload(
       json!({
-        "profile_id": other_id,
-        "old_password": PASSWORD,
-        "password": NEW_PASSWORD,
-        "password_repetition": NEW_PASSWORD,
+        "username": USERNAME,
+        "password": PASSWORD,
       })
       .to_string(),
     )
     .to_request();
 
-  let res = test::call_service(&mut app, req).await;
+  let res = test::call_service(&app, req).await;
 
-  assert_eq!(res.status(), 403);
+  assert_eq!(res.status(), 200);
+  let body: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+  let authorization = HeaderValue::from_str(&format!("Bearer {}", body.token)).unwrap();
+
+  let req = test::TestRequest::get()
+    .uri("/v1/admin/users?email=JANEDOE&sort=-username")
+    .append_header((AUTHORIZATION, authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let users: UserListResponseHttp = test::read_body_json(res).await;
+  assert_eq!(users.total, 1);
+  assert_eq!(users.items[0].id, other_id);
+  assert_eq!(users.items[0].emails, vec!["janedoe@company.com"]);
+
+  let req = test::TestRequest::get()
+    .uri("/v1/admin/users?sort=email")
+    .append_header((AUTHORIZATION, authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 400);
+
+  let req = test::TestRequest::get()
+    .uri(&format!("/v1/admin/users/{}", other_id))
+    .append_header((AUTHORIZATION, authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let profile: UserProfileResponseHttp = test::read_body_json(res).await;
+  assert_eq!(profile.username, "jane.doe");
+
+  let req = test::TestRequest::put()
+    .uri(&format!(
+      "/v1/admin/users/{}/roles/{}",
+      other_id, ADMIN_ROLE
+    ))
+    .append_header((AUTHORIZATION, authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "username": "jane.doe", "password": PASSWORD }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let other: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+
+  let req = test::TestRequest::delete()
+    .uri(&format!(
+      "/v1/admin/users/{}/roles/{}",
+      other_id, ADMIN_ROLE
+    ))
+    .append_header((AUTHORIZATION, authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let req = test::TestRequest::get()
+    .uri("/v1/admin/users")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", other.token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+
+  let req = test::TestRequest::put()
+    .uri(&format!("/v1/admin/users/{}/roles/superuser", other_id))
+    .append_header((AUTHORIZATION, authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 404);
+
+  let req = test::TestRequest::post()
+    .uri(&format!("/v1/admin/users/{}/password-reset", other_id))
+    .append_header((AUTHORIZATION, authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(
+      json!({
+        "username": "jane.doe",
+        "password": PASSWORD,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 403);
+
+  let req = test::TestRequest::post()
+    .uri(&format!("/v1/admin/users/{}/unlock", other_id))
+    .append_header((AUTHORIZATION, authorization))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_audit_log_records_security_events(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let admin_id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &admin_id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+  assert!(grant_admin_role(&pool, &admin_id).await.unwrap());
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/register")
+    .insert_header(ContentType::json())
+    .insert_header((USER_AGENT, "audit-test/1.0"))
+    .set_payload(
+      json!({
+        "username": "jane.doe",
+        "name": "Jane Doe",
+        "gender_id": GENDER_ID,
+        "birth_date": BIRTH_DATE,
+        "password": PASSWORD,
+        "password_repetition": PASSWORD,
+        "address_street": STREET,
+        "address_neighborhood": NEIGHBORHOOD,
+        "address_city_id": CITY_ID,
+        "address_postal_code": POSTAL_CODE,
+        "email": "janedoe@company.com",
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 201);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(
+      json!({
+        "username": "jane.doe",
+        "password": "wrong password",
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(
+      json!({
+        "username": USERNAME,
+        "password": PASSWORD,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let body: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+  let authorization = HeaderValue::from_str(&format!("Bearer {}", body.token)).unwrap();
+
+  let req = test::TestRequest::get()
+    .uri("/v1/admin/audit-events?action=user.register")
+    .append_header((AUTHORIZATION, authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let events: AuditEventListResponseHttp = test::read_body_json(res).await;
+  assert_eq!(events.total, 1);
+  assert_eq!(events.items[0].outcome, "success");
+  assert_eq!(
+    events.items[0].user_agent.as_deref(),
+    Some("audit-test/1.0")
+  );
+  assert_eq!(events.items[0].metadata["username"], "jane.doe");
+  let jane_id = events.items[0].subject_id.clone().unwrap();
+
+  let req = test::TestRequest::get()
+    .uri("/v1/admin/audit-events?action=user.sign_in&outcome=failure")
+    .append_header((AUTHORIZATION, authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let events: AuditEventListResponseHttp = test::read_body_json(res).await;
+  assert_eq!(events.total, 1);
+  assert_eq!(events.items[0].metadata["identifier"], "jane.doe");
+
+  let req = test::TestRequest::post()
+    .uri(&format!("/v1/admin/users/{}/disable", jane_id))
+    .append_header((AUTHORIZATION, authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let req = test::TestRequest::get()
+    .uri(&format!("/v1/admin/audit-events?actor_id={}", admin_id))
+    .append_header((AUTHORIZATION, authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let events: AuditEventListResponseHttp = test::read_body_json(res).await;
+  let actions: Vec<&str> = events
+    .items
+    .iter()
+    .map(|event| event.action.as_str())
+    .collect();
+  assert_eq!(
+    actions,
+    vec![
+      "admin.users.disable",
+      "admin.audit_events.list",
+      "admin.audit_events.list",
+      "user.sign_in"
+    ]
+  );
+
+  let req = test::TestRequest::get()
+    .uri("/v1/admin/audit-events/verify")
+    .append_header((AUTHORIZATION, authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let verification: AuditChainVerificationHttp = test::read_body_json(res).await;
+  assert!(verification.valid);
+  assert_eq!(verification.checked, 7);
+
+  let req = test::TestRequest::get()
+    .uri("/v1/admin/audit-events?outcome=denied")
+    .append_header((AUTHORIZATION, authorization))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 400);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_change_password_successfully(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::put()
+    .uri("/v1/auth/change_password")
+    .insert_header(ContentType::json())
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .set_payload(
+      json!({
+        "profile_id": id,
+        "old_password": PASSWORD,
+        "password": NEW_PASSWORD,
+        "password_repetition": NEW_PASSWORD,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_change_password_given_old_wrong_password(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::put()
+    .uri("/v1/auth/change_password")
+    .insert_header(ContentType::json())
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .set_payload(
+      json!({
+        "profile_id": id,
+        "old_password": WRONG_PASSWORD,
+        "password": NEW_PASSWORD,
+        "password_repetition": NEW_PASSWORD,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 403);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_change_password_given_not_match_new_password(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::put()
+    .uri("/v1/auth/change_password")
+    .insert_header(ContentType::json())
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .set_payload(
+      json!({
+        "profile_id": id,
+        "old_password": PASSWORD,
+        "password": NEW_PASSWORD,
+        "password_repetition": WRONG_PASSWORD,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 400);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_change_password_given_wrong_token_for_profile_id(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+  let other_id = Uuid::new_v4().to_string();
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let authorization = format!("Bearer {}", token);
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::put()
+    .uri("/v1/auth/change_password")
+    .insert_header(ContentType::json())
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&authorization).unwrap(),
+    ))
+    .set_payload(
+      json!({
+        "profile_id": other_id,
+        "old_password": PASSWORD,
+        "password": NEW_PASSWORD,
+        "password_repetition": NEW_PASSWORD,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 403);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_get_me_successfully(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let body: UserProfileResponseHttp = test::read_body_json(res).await;
+
+  assert_eq!(body.id, id);
+  assert_eq!(body.name, NAME);
+  assert_eq!(body.username, USERNAME);
+  assert_eq!(body.birth_date, BIRTH_DATE);
+  assert_eq!(body.gender, "Male");
+  assert_eq!(body.emails.len(), 1);
+  assert_eq!(body.emails[0].address, EMAIL_ADDRESS);
+  assert_eq!(body.telephones.len(), 1);
+  assert_eq!(body.telephones[0].number, TELEPHONE_NUMBER);
+  assert_eq!(body.address.street, STREET);
+  assert_eq!(body.address.city, "New York City");
+  assert_eq!(body.address.country, "United States");
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_list_my_sign_ins(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  insert_user(
+    &pool,
+    &Uuid::new_v4().to_string(),
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(
+      json!({
+        "username": USERNAME,
+        "password": WRONG_PASSWORD,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+  assert_eq!(res.status(), 401);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .insert_header((USER_AGENT, "e2e-test-agent"))
+    .peer_addr("203.0.113.7:40000".parse().unwrap())
+    .set_payload(
+      json!({
+        "email": EMAIL_ADDRESS,
+        "password": PASSWORD,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+  assert_eq!(res.status(), 200);
+
+  let sign_in: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me/sign-ins?page=1&per_page=1")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", sign_in.token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let body: SignInHistoryResponseHttp = test::read_body_json(res).await;
+
+  assert_eq!(body.total, 2);
+  assert_eq!(body.page, 1);
+  assert_eq!(body.per_page, 1);
+  assert_eq!(body.items.len(), 1);
+  assert!(body.items[0].succeeded);
+  assert_eq!(body.items[0].method, "email");
+  assert_eq!(body.items[0].ip_address.as_deref(), Some("203.0.113.7"));
+  assert_eq!(body.items[0].user_agent.as_deref(), Some("e2e-test-agent"));
+
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me/sign-ins?page=0")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", sign_in.token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 400);
+  Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_get_me_without_token(pool: PgPool) -> Result<()> {
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::get().uri("/v1/users/me").to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+  assert_eq!(
+    res.headers().get(WWW_AUTHENTICATE),
+    Some(&HeaderValue::from_static("Bearer"))
+  );
+  Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_get_me_with_invalid_token(pool: PgPool) -> Result<()> {
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me")
+    .append_header((AUTHORIZATION, HeaderValue::from_static("Bearer invalid")))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+  assert_eq!(
+    res.headers().get(WWW_AUTHENTICATE),
+    Some(&HeaderValue::from_static("Bearer"))
+  );
+  Ok(())
+}
+
+#[sqlx::test]
+async fn test_get_me_with_token_for_other_service(pool: PgPool) -> Result<()> {
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      Uuid::new_v4().to_string(),
+      "mfa_pending".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+  assert_eq!(
+    res.headers().get(WWW_AUTHENTICATE),
+    Some(&HeaderValue::from_static("Bearer"))
+  );
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_update_me_successfully(pool: PgPool) -> Result<()> {
+  const NEW_NAME: &str = "Jane Doe";
+  const NEW_STREET: &str = "1 Wall St";
+  const NEW_EMAIL_ADDRESS: &str = "janedoe@company.com";
+
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::patch()
+    .uri("/v1/users/me")
+    .insert_header(ContentType::json())
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .set_payload(
+      json!({
+        "name": NEW_NAME,
+        "address_street": NEW_STREET,
+        "address_neighborhood": NEIGHBORHOOD,
+        "address_city_id": CITY_ID,
+        "address_postal_code": POSTAL_CODE,
+        "email": NEW_EMAIL_ADDRESS,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let body: UserProfileResponseHttp = test::read_body_json(res).await;
+
+  assert_eq!(body.id, id);
+  assert_eq!(body.name, NEW_NAME);
+  assert_eq!(body.username, USERNAME);
+  assert_eq!(body.address.street, NEW_STREET);
+  assert_eq!(body.emails.len(), 2);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_update_me_with_partial_address(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::patch()
+    .uri("/v1/users/me")
+    .insert_header(ContentType::json())
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .set_payload(json!({ "address_street": STREET }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 400);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_update_me_with_nonexistent_profile(pool: PgPool) -> Result<()> {
+  let id = Uuid::new_v4().to_string();
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::patch()
+    .uri("/v1/users/me")
+    .insert_header(ContentType::json())
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .set_payload(json!({ "name": NAME }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  // without a profile there is no token version to match
+  assert_eq!(res.status(), 401);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_delete_me_and_cancel_by_signing_in(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::delete()
+    .uri("/v1/users/me")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "password": WRONG_PASSWORD }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 403);
+
+  let req = test::TestRequest::delete()
+    .uri("/v1/users/me")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "password": PASSWORD }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 202);
+  assert!(requested_deletion(&pool, &id).await);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(
+      json!({
+        "username": USERNAME,
+        "password": PASSWORD,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  assert!(!requested_deletion(&pool, &id).await);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_sessions_sign_out_everywhere_else(pool: PgPool) -> Result<()> {
+  const LAPTOP: &str = "Mozilla/5.0 (X11; Linux x86_64; rv:120.0) Gecko/20100101 Firefox/120.0";
+  const PHONE: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 17_1 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/17.1 Mobile/15E148 Safari/604.1";
+
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .insert_header((USER_AGENT, LAPTOP))
+    .set_payload(json!({ "username": USERNAME, "password": PASSWORD }).to_string())
+    .to_request();
+  let laptop: UserAuthenticationResponseHttp = test::call_and_read_body_json(&app, req).await;
+  let laptop_authorization = HeaderValue::from_str(&format!("Bearer {}", laptop.token)).unwrap();
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .insert_header((USER_AGENT, PHONE))
+    .set_payload(json!({ "username": USERNAME, "password": PASSWORD }).to_string())
+    .to_request();
+  let phone: UserAuthenticationResponseHttp = test::call_and_read_body_json(&app, req).await;
+  let phone_authorization = HeaderValue::from_str(&format!("Bearer {}", phone.token)).unwrap();
+
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me/sessions")
+    .append_header((AUTHORIZATION, laptop_authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let sessions: SessionListResponseHttp = test::read_body_json(res).await;
+  assert_eq!(sessions.items.len(), 2);
+  let current = sessions
+    .items
+    .iter()
+    .find(|session| session.current)
+    .unwrap();
+  assert_eq!(current.device_label, "Firefox on Linux");
+  assert_eq!(current.user_agent.as_deref(), Some(LAPTOP));
+  let other = sessions
+    .items
+    .iter()
+    .find(|session| !session.current)
+    .unwrap();
+  assert_eq!(other.device_label, "Safari on iOS");
+  let laptop_session_id = current.id.clone();
+
+  let req = test::TestRequest::delete()
+    .uri(&format!("/v1/users/me/sessions/{}", Uuid::new_v4()))
+    .append_header((AUTHORIZATION, laptop_authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 404);
+
+  let req = test::TestRequest::delete()
+    .uri("/v1/users/me/sessions")
+    .append_header((AUTHORIZATION, laptop_authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me")
+    .append_header((AUTHORIZATION, phone_authorization))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/refresh")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "refresh_token": phone.refresh_token }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me/sessions")
+    .append_header((AUTHORIZATION, laptop_authorization.clone()))
+    .to_request();
+  let sessions: SessionListResponseHttp = test::call_and_read_body_json(&app, req).await;
+
+  assert_eq!(sessions.items.len(), 1);
+  assert_eq!(sessions.items[0].id, laptop_session_id);
+
+  let req = test::TestRequest::delete()
+    .uri(&format!("/v1/users/me/sessions/{}", laptop_session_id))
+    .append_header((AUTHORIZATION, laptop_authorization.clone()))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me")
+    .append_header((AUTHORIZATION, laptop_authorization))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_change_password_invalidates_prior_tokens(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "username": USERNAME, "password": PASSWORD }).to_string())
+    .to_request();
+  let laptop: UserAuthenticationResponseHttp = test::call_and_read_body_json(&app, req).await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "username": USERNAME, "password": PASSWORD }).to_string())
+    .to_request();
+  let phone: UserAuthenticationResponseHttp = test::call_and_read_body_json(&app, req).await;
+
+  let req = test::TestRequest::put()
+    .uri("/v1/auth/change_password")
+    .insert_header(ContentType::json())
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", laptop.token)).unwrap(),
+    ))
+    .set_payload(
+      json!({
+        "profile_id": id,
+        "old_password": PASSWORD,
+        "password": NEW_PASSWORD,
+        "password_repetition": NEW_PASSWORD,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let changed: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+
+  for token in [&laptop.token, &phone.token] {
+    let req = test::TestRequest::get()
+      .uri("/v1/users/me")
+      .append_header((
+        AUTHORIZATION,
+        HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+      ))
+      .to_request();
+
+    let res = test::call_service(&app, req).await;
+
+    assert_eq!(res.status(), 401);
+  }
+
+  for refresh_token in [&laptop.refresh_token, &phone.refresh_token] {
+    let req = test::TestRequest::post()
+      .uri("/v1/auth/refresh")
+      .insert_header(ContentType::json())
+      .set_payload(json!({ "refresh_token": refresh_token }).to_string())
+      .to_request();
+
+    let res = test::call_service(&app, req).await;
+
+    assert_eq!(res.status(), 401);
+  }
+
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", changed.token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/refresh")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "refresh_token": changed.refresh_token }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_refresh_token_successfully(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "username": USERNAME, "password": PASSWORD }).to_string())
+    .to_request();
+  let signed_in: UserAuthenticationResponseHttp = test::call_and_read_body_json(&app, req).await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/refresh")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "refresh_token": signed_in.refresh_token }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let body: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+
+  assert_eq!(body.id, id);
+  assert_eq!(body.name, NAME);
+  assert_eq!(body.username, USERNAME);
+  assert_ne!(body.refresh_token, signed_in.refresh_token);
+  assert_jwt(
+    &body.token,
+    &id,
+    "authentication_user",
+    TokenExpirationExpect::GreatThan(TWO_HOURS - 60),
+  )
+  .await;
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_refresh_token_reuse_revokes_family(pool: PgPool) -> Result<()> {
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  insert_user(
+    &pool,
+    &Uuid::new_v4().to_string(),
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "username": USERNAME, "password": PASSWORD }).to_string())
+    .to_request();
+  let signed_in: UserAuthenticationResponseHttp = test::call_and_read_body_json(&app, req).await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/refresh")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "refresh_token": signed_in.refresh_token }).to_string())
+    .to_request();
+  let refreshed: UserAuthenticationResponseHttp = test::call_and_read_body_json(&app, req).await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/refresh")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "refresh_token": signed_in.refresh_token }).to_string())
+    .to_request();
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/refresh")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "refresh_token": refreshed.refresh_token }).to_string())
+    .to_request();
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+  Ok(())
+}
+
+#[sqlx::test]
+async fn test_refresh_with_invalid_token(pool: PgPool) -> Result<()> {
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/refresh")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "refresh_token": "not-a-valid-refresh-token" }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_logout_revokes_token(pool: PgPool) -> Result<()> {
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    PASSWORD,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/logout")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+  Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_logout_without_token(pool: PgPool) -> Result<()> {
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/logout")
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+  assert_eq!(
+    res.headers().get(WWW_AUTHENTICATE),
+    Some(&HeaderValue::from_static("Bearer"))
+  );
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_verify_email(pool: PgPool) -> Result<()> {
+  const VERIFICATION_TOKEN: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    PASSWORD,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  UserRepositoryDB { pool: &pool }
+    .store_verification_token(&NewVerificationToken {
+      token: VERIFICATION_TOKEN.to_string(),
+      purpose: VerificationPurpose::Email,
+      profile_id: id.clone(),
+      target: EMAIL_ADDRESS.to_string(),
+      expires_at: chrono::Utc::now().naive_utc() + chrono::Duration::minutes(10),
+    })
+    .await
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/verify-email")
+    .insert_header(ContentType::json())
+    .set_payload(
+      json!({
+        "token": VERIFICATION_TOKEN,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  assert!(email_checked(&pool, EMAIL_ADDRESS).await);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/verify-email")
+    .insert_header(ContentType::json())
+    .set_payload(
+      json!({
+        "token": VERIFICATION_TOKEN,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 400);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_forgot_password(pool: PgPool) -> Result<()> {
+  insert_user(
+    &pool,
+    &Uuid::new_v4().to_string(),
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    PASSWORD,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  for username in [USERNAME, "jane.doe"] {
+    let req = test::TestRequest::post()
+      .uri("/v1/auth/password/forgot")
+      .insert_header(ContentType::json())
+      .set_payload(json!({ "username": username }).to_string())
+      .to_request();
+
+    let res = test::call_service(&app, req).await;
+
+    assert_eq!(res.status(), 202);
+  }
+
+  let tokens = sqlx::query!(
+    "SELECT COUNT(*) AS total FROM verification_tokens WHERE purpose = 'password_reset' AND target = $1",
+    EMAIL_ADDRESS
+  )
+  .fetch_one(&pool)
+  .await?;
+  assert_eq!(tokens.total, Some(1));
+
+  // The reset token must not be readable from the dev outbox over HTTP.
+  for uri in ["/logs/outbox/", "/outbox/email/"] {
+    let req = test::TestRequest::get().uri(uri).to_request();
+
+    let res = test::call_service(&app, req).await;
+
+    assert_eq!(res.status(), 404);
+  }
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_reset_password(pool: PgPool) -> Result<()> {
+  const RESET_TOKEN: &str = "Zr4mT8wQ1xN6bV3cL9kJ2hG5fD7sA0pE4yU8iO1nM6q";
+
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  UserRepositoryDB { pool: &pool }
+    .store_verification_token(&NewVerificationToken {
+      token: RESET_TOKEN.to_string(),
+      purpose: VerificationPurpose::PasswordReset,
+      profile_id: id.clone(),
+      target: EMAIL_ADDRESS.to_string(),
+      expires_at: chrono::Utc::now().naive_utc() + chrono::Duration::minutes(10),
+    })
+    .await
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "username": USERNAME, "password": PASSWORD }).to_string())
+    .to_request();
+  let signed_in: UserAuthenticationResponseHttp = test::call_and_read_body_json(&app, req).await;
+
+  let reset_payload = json!({
+    "token": RESET_TOKEN,
+    "password": NEW_PASSWORD,
+    "password_repetition": NEW_PASSWORD,
+  })
+  .to_string();
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/password/reset")
+    .insert_header(ContentType::json())
+    .set_payload(reset_payload.clone())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/refresh")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "refresh_token": signed_in.refresh_token }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "username": USERNAME, "password": NEW_PASSWORD }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/password/reset")
+    .insert_header(ContentType::json())
+    .set_payload(reset_payload)
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 400);
+
+  let outcomes: Vec<String> = sqlx::query!(
+    "SELECT outcome FROM audit_events WHERE action = 'user.reset_password' ORDER BY id"
+  )
+  .fetch_all(&pool)
+  .await?
+  .into_iter()
+  .map(|event| event.outcome)
+  .collect();
+  assert_eq!(outcomes, vec!["success", "failure"]);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_send_telephone_code(pool: PgPool) -> Result<()> {
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    PASSWORD,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri(&format!(
+      "/v1/users/me/telephones/{}/verification",
+      TELEPHONE_NUMBER.replace(' ', "%20")
+    ))
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 202);
+
+  let codes = sqlx::query!(
+    "SELECT COUNT(*) AS total FROM verification_tokens WHERE purpose = 'telephone' AND target = $1",
+    TELEPHONE_NUMBER
+  )
+  .fetch_one(&pool)
+  .await?;
+  assert_eq!(codes.total, Some(1));
+
+  let req = test::TestRequest::post()
+    .uri("/v1/users/me/telephones/+1%202025550143/verification")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 404);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_verify_telephone(pool: PgPool) -> Result<()> {
+  const CODE: &str = "482910";
+
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    PASSWORD,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  UserRepositoryDB { pool: &pool }
+    .store_verification_token(&NewVerificationToken {
+      token: CODE.to_string(),
+      purpose: VerificationPurpose::Telephone,
+      profile_id: id.clone(),
+      target: TELEPHONE_NUMBER.to_string(),
+      expires_at: chrono::Utc::now().naive_utc() + chrono::Duration::minutes(10),
+    })
+    .await
+    .unwrap();
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let uri = format!(
+    "/v1/users/me/telephones/{}/verify",
+    TELEPHONE_NUMBER.replace(' ', "%20")
+  );
+
+  let req = test::TestRequest::post()
+    .uri(&uri)
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "code": "000000" }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 400);
+  assert!(!telephone_checked(&pool, TELEPHONE_NUMBER).await);
+
+  let req = test::TestRequest::post()
+    .uri(&uri)
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "code": CODE }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  assert!(telephone_checked(&pool, TELEPHONE_NUMBER).await);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_sign_in_with_totp(pool: PgPool) -> Result<()> {
+  let id = Uuid::new_v4().to_string();
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/users/me/mfa/totp")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 201);
+  let enrollment: TotpEnrollmentHttp = test::read_body_json(res).await;
+  assert!(enrollment.provisioning_uri.starts_with("otpauth://totp/"));
+
+  let code = CryptoPrimitives
+    .totp_code(&enrollment.secret, totp::current_step())
+    .unwrap();
+  let req = test::TestRequest::post()
+    .uri("/v1/users/me/mfa/totp/confirm")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "code": code }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let body: RecoveryCodesHttp = test::read_body_json(res).await;
+  assert_eq!(body.recovery_codes.len(), 10);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/sign_in")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "username": USERNAME, "password": PASSWORD }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 202);
+  let challenge: MfaChallengeResponseHttp = test::read_body_json(res).await;
+  assert!(challenge.mfa_required);
+  assert_jwt(
+    &challenge.mfa_token,
+    &id,
+    "mfa_pending",
+    TokenExpirationExpect::GreatThan(60 * 5 - 60),
+  )
+  .await;
+  let mfa_token_decoded = get_jwt_service(TokenRevocationStoreMemory::default())
+    .decode(&challenge.mfa_token)
+    .await
+    .unwrap();
+  assert_eq!(mfa_token_decoded.ver, Some(0));
+
+  // the pending token does not grant access to the API
+  let req = test::TestRequest::get()
+    .uri("/v1/users/me")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", challenge.mfa_token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+
+  let verify_request = |recovery_code: &str| {
+    test::TestRequest::post()
+      .uri("/v1/auth/mfa/verify")
+      .insert_header(ContentType::json())
+      .set_payload(
+        json!({
+          "mfa_token": challenge.mfa_token,
+          "recovery_code": recovery_code,
+        })
+        .to_string(),
+      )
+      .to_request()
+  };
+
+  let res = test::call_service(&app, verify_request("not-a-code")).await;
+
+  assert_eq!(res.status(), 401);
+
+  let res = test::call_service(&app, verify_request(&body.recovery_codes[0])).await;
+
+  assert_eq!(res.status(), 200);
+  let auth: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+  assert_jwt(
+    &auth.token,
+    &id,
+    "authentication_user",
+    TokenExpirationExpect::GreatThan(TWO_HOURS - 60),
+  )
+  .await;
+
+  // both the pending token and the recovery code are single-use
+  let res = test::call_service(&app, verify_request(&body.recovery_codes[1])).await;
+
+  assert_eq!(res.status(), 401);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_sign_in_with_passkey(pool: PgPool) -> Result<()> {
+  let id = Uuid::new_v4().to_string();
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+  let webauthn = &settings().webauthn;
+  let authenticator = SoftAuthenticator::new(&webauthn.rp_id, &webauthn.origin);
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/webauthn/register/options")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let options: WebAuthnRegistrationOptionsHttp = test::read_body_json(res).await;
+  assert_eq!(options.rp_id, webauthn.rp_id);
+  assert_eq!(options.user_id, id);
+  assert!(options.exclude_credentials.is_empty());
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/webauthn/register")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .insert_header(ContentType::json())
+    .set_payload(authenticator.create(&options.challenge).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 201);
+  let credential: WebAuthnCredentialResponseHttp = test::read_body_json(res).await;
+  assert_eq!(credential.credential_id, authenticator.credential_id());
+
+  let sign_in_options = || {
+    test::TestRequest::post()
+      .uri("/v1/auth/webauthn/sign_in/options")
+      .insert_header(ContentType::json())
+      .set_payload(json!({ "username": USERNAME }).to_string())
+      .to_request()
+  };
+  let sign_in = |body: serde_json::Value| {
+    test::TestRequest::post()
+      .uri("/v1/auth/webauthn/sign_in")
+      .insert_header(ContentType::json())
+      .set_payload(body.to_string())
+      .to_request()
+  };
+
+  let res = test::call_service(&app, sign_in_options()).await;
+
+  assert_eq!(res.status(), 200);
+  let options: WebAuthnSignInOptionsHttp = test::read_body_json(res).await;
+  assert_eq!(
+    options.allow_credentials,
+    vec![authenticator.credential_id().to_string()]
+  );
+
+  let assertion = authenticator.get(&options.challenge, 1);
+  let res = test::call_service(&app, sign_in(assertion.clone())).await;
+
+  assert_eq!(res.status(), 200);
+  let auth: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+  assert_jwt(
+    &auth.token,
+    &id,
+    "authentication_user",
+    TokenExpirationExpect::GreatThan(TWO_HOURS - 60),
+  )
+  .await;
+
+  // the challenge is single-use
+  let res = test::call_service(&app, sign_in(assertion)).await;
+
+  assert_eq!(res.status(), 401);
+
+  // a signature of another challenge is rejected
+  let res = test::call_service(&app, sign_in_options()).await;
+  let options: WebAuthnSignInOptionsHttp = test::read_body_json(res).await;
+  let mut forged = authenticator.get(&options.challenge, 2);
+  forged["signature"] = authenticator.get("another-challenge", 2)["signature"].clone();
+  let res = test::call_service(&app, sign_in(forged)).await;
+
+  assert_eq!(res.status(), 401);
+
+  // a counter that did not increase hints at a cloned passkey
+  let res = test::call_service(&app, sign_in(authenticator.get(&options.challenge, 1))).await;
+
+  assert_eq!(res.status(), 401);
+  Ok(())
+}
+
+#[sqlx::test(migrations = false)]
+async fn test_get_jwks(pool: PgPool) -> Result<()> {
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::get()
+    .uri("/.well-known/jwks.json")
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let body: serde_json::Value = test::read_body_json(res).await;
+
+  // the default settings sign with a shared secret, which is never published
+  assert_eq!(body, json!({ "keys": [] }));
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_openid_connect_authorization_code_flow(pool: PgPool) -> Result<()> {
+  let id = Uuid::new_v4().to_string();
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+  let client = LocalClient::register(&pool, "local-client", "local-client-secret").await;
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::get()
+    .uri(&client.authorize_uri("af0ifjsldkj", "n-0S6_WzA2Mj"))
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let body: AuthorizationRedirectHttp = test::read_body_json(res).await;
+  assert!(body.redirect_to.starts_with(client.redirect_uri));
+  assert_eq!(
+    LocalClient::callback_param(&body.redirect_to, "state").as_deref(),
+    Some("af0ifjsldkj")
+  );
+  let code = LocalClient::callback_param(&body.redirect_to, "code").unwrap();
+
+  let req = test::TestRequest::post()
+    .uri("/v1/oauth/token")
+    .insert_header(ContentType::form_url_encoded())
+    .append_header((AUTHORIZATION, client.basic_authorization()))
+    .set_payload(client.token_form(&code))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  assert_eq!(res.headers().get(CACHE_CONTROL).unwrap(), "no-store");
+  let tokens: TokenResponseHttp = test::read_body_json(res).await;
+  assert_eq!(tokens.token_type, "Bearer");
+  assert_eq!(tokens.scope, "openid profile");
+
+  let id_token = client.verify_id_token(&tokens.id_token, &settings().jwt.issuer);
+  assert_eq!(id_token.sub, id);
+  assert_eq!(id_token.nonce.as_deref(), Some("n-0S6_WzA2Mj"));
+
+  let req = test::TestRequest::get()
+    .uri("/v1/oauth/userinfo")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", tokens.access_token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let userinfo: UserInfoHttp = test::read_body_json(res).await;
+  assert_eq!(userinfo.sub, id);
+  assert_eq!(userinfo.preferred_username, USERNAME);
+  assert_eq!(userinfo.email.as_deref(), Some(EMAIL_ADDRESS));
+  assert_eq!(userinfo.email_verified, Some(false));
+
+  // the access token goes with the profile's other tokens
+  sqlx::query!(
+    "UPDATE users SET token_version = token_version + 1
+    FROM profiles WHERE profiles.user_id = users.id AND profiles.id = $1",
+    id
+  )
+  .execute(&pool)
+  .await?;
+
+  let req = test::TestRequest::get()
+    .uri("/v1/oauth/userinfo")
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", tokens.access_token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/oauth/token")
+    .insert_header(ContentType::form_url_encoded())
+    .append_header((AUTHORIZATION, client.basic_authorization()))
+    .set_payload(client.token_form(&code))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 400);
+  let error: OAuthErrorHttp = test::read_body_json(res).await;
+  assert_eq!(error.error, "invalid_grant");
+  assert_eq!(error.error_description, "authorization code already used");
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_oauth_token_with_wrong_client_secret(pool: PgPool) -> Result<()> {
+  let id = Uuid::new_v4().to_string();
+  let hash_pwd = bcrypt::hash(PASSWORD, 5).unwrap();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    &hash_pwd,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let token = get_jwt_service(TokenRevocationStoreMemory::default())
+    .encode(
+      id.clone(),
+      "authentication_user".to_string(),
+      Grants::default(),
+      None,
+      0,
+      10,
+    )
+    .unwrap();
+  let client = LocalClient::register(&pool, "local-client", "local-client-secret").await;
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::get()
+    .uri(&client.authorize_uri("af0ifjsldkj", "n-0S6_WzA2Mj"))
+    .append_header((
+      AUTHORIZATION,
+      HeaderValue::from_str(&format!("Bearer {}", token)).unwrap(),
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let body: AuthorizationRedirectHttp = test::read_body_json(res).await;
+  let code = LocalClient::callback_param(&body.redirect_to, "code").unwrap();
+
+  let req = test::TestRequest::post()
+    .uri("/v1/oauth/token")
+    .insert_header(ContentType::form_url_encoded())
+    .set_payload(format!(
+      "{}&client_id={}&client_secret=wrong-secret",
+      client.token_form(&code),
+      client.client_id
+    ))
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+  let error: OAuthErrorHttp = test::read_body_json(res).await;
+  assert_eq!(error.error, "invalid_client");
+  Ok(())
+}
+
+#[sqlx::test]
+async fn test_get_openid_configuration(pool: PgPool) -> Result<()> {
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .configure(services_config(pool.clone()))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::get()
+    .uri("/.well-known/openid-configuration")
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+
+  let body: serde_json::Value = test::read_body_json(res).await;
+
+  assert_eq!(body["issuer"], json!(settings().jwt.issuer));
+  assert_eq!(
+    body["token_endpoint"],
+    json!("http://localhost:8080/v1/oauth/token")
+  );
+  assert_eq!(body["code_challenge_methods_supported"], json!(["S256"]));
+  assert_eq!(
+    body["id_token_signing_alg_values_supported"],
+    json!(["HS256"])
+  );
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_external_sign_up_then_sign_in(pool: PgPool) -> Result<()> {
+  let idp = LocalIdentityProvider::start();
+  let account = LocalAccount {
+    subject: String::from("248289761001"),
+    email: EMAIL_ADDRESS.to_string(),
+    email_verified: true,
+    name: NAME.to_string(),
+  };
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .app_data(web::Data::new(Services {
+        external_identity_policy: ExternalIdentityPolicy {
+          providers: vec![idp.provider("local")],
+          ..Default::default()
+        },
+        ..get_services(pool.clone())
+      }))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::get()
+    .uri("/v1/auth/external/local/authorize")
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let body: ExternalAuthorizationHttp = test::read_body_json(res).await;
+  let (code, state) = idp.authorize(&body.authorization_url, &account);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/external/local/callback")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "code": code, "state": state }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 202);
+  let body: ExternalSignUpRequiredHttp = test::read_body_json(res).await;
+  assert_eq!(body.signup_token, state);
+  assert_eq!(body.name.as_deref(), Some(NAME));
+  assert_eq!(body.email, EMAIL_ADDRESS);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/external/sign_up")
+    .insert_header(ContentType::json())
+    .set_payload(
+      json!({
+        "signup_token": body.signup_token,
+        "name": NAME,
+        "birth_date": BIRTH_DATE,
+        "gender_id": GENDER_ID,
+        "address_street": STREET,
+        "address_neighborhood": NEIGHBORHOOD,
+        "address_city_id": CITY_ID,
+        "address_postal_code": POSTAL_CODE,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 201);
+  let signed_up: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+  assert!(signed_up.username.starts_with("johndoe."));
+  assert!(email_checked(&pool, EMAIL_ADDRESS).await);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/external/sign_up")
+    .insert_header(ContentType::json())
+    .set_payload(
+      json!({
+        "signup_token": state,
+        "name": NAME,
+        "birth_date": BIRTH_DATE,
+        "gender_id": GENDER_ID,
+        "address_street": STREET,
+        "address_neighborhood": NEIGHBORHOOD,
+        "address_city_id": CITY_ID,
+        "address_postal_code": POSTAL_CODE,
+      })
+      .to_string(),
+    )
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+
+  let req = test::TestRequest::get()
+    .uri("/v1/auth/external/local/authorize")
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let body: ExternalAuthorizationHttp = test::read_body_json(res).await;
+  let (code, state) = idp.authorize(&body.authorization_url, &account);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/external/local/callback")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "code": code, "state": state }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let signed_in: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+  assert_eq!(signed_in.id, signed_up.id);
+  assert_eq!(signed_in.username, signed_up.username);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_external_sign_in_links_verified_email(pool: PgPool) -> Result<()> {
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    PASSWORD,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  UserRepositoryDB { pool: &pool }
+    .check_email(&id, EMAIL_ADDRESS)
+    .await
+    .unwrap();
+
+  let idp = LocalIdentityProvider::start();
+  let account = LocalAccount {
+    subject: String::from("248289761001"),
+    email: EMAIL_ADDRESS.to_string(),
+    email_verified: true,
+    name: NAME.to_string(),
+  };
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .app_data(web::Data::new(Services {
+        external_identity_policy: ExternalIdentityPolicy {
+          providers: vec![idp.provider("local")],
+          ..Default::default()
+        },
+        ..get_services(pool.clone())
+      }))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::get()
+    .uri("/v1/auth/external/local/authorize")
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let body: ExternalAuthorizationHttp = test::read_body_json(res).await;
+  let (code, state) = idp.authorize(&body.authorization_url, &account);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/external/local/callback")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "code": code, "state": state }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let body: UserAuthenticationResponseHttp = test::read_body_json(res).await;
+  assert_eq!(body.id, id);
+  assert_eq!(body.username, USERNAME);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/external/local/callback")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "code": code, "state": state }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 401);
+  Ok(())
+}
+
+#[sqlx::test(fixtures("countries", "states", "cities", "genders"))]
+async fn test_external_sign_in_refuses_unverified_email(pool: PgPool) -> Result<()> {
+  let id = Uuid::new_v4().to_string();
+  insert_user(
+    &pool,
+    &id,
+    NAME,
+    USERNAME,
+    BIRTH_DATE,
+    GENDER_ID,
+    PASSWORD,
+    STREET,
+    NEIGHBORHOOD,
+    CITY_ID,
+    POSTAL_CODE,
+    EMAIL_ADDRESS,
+    Some(TELEPHONE_NUMBER),
+  )
+  .await;
+
+  let idp = LocalIdentityProvider::start();
+  let account = LocalAccount {
+    subject: String::from("248289761001"),
+    email: EMAIL_ADDRESS.to_string(),
+    email_verified: true,
+    name: NAME.to_string(),
+  };
+
+  let app = test::init_service(
+    App::new()
+      .wrap(default_cors())
+      .app_data(web::Data::new(AppState {
+        postgres_pool: pool.clone(),
+      }))
+      .app_data(web::Data::new(Services {
+        external_identity_policy: ExternalIdentityPolicy {
+          providers: vec![idp.provider("local")],
+          ..Default::default()
+        },
+        ..get_services(pool.clone())
+      }))
+      .configure(utilities_config)
+      .configure(routes_config),
+  )
+  .await;
+
+  let req = test::TestRequest::get()
+    .uri("/v1/auth/external/local/authorize")
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 200);
+  let body: ExternalAuthorizationHttp = test::read_body_json(res).await;
+  let (code, state) = idp.authorize(&body.authorization_url, &account);
+
+  let req = test::TestRequest::post()
+    .uri("/v1/auth/external/local/callback")
+    .insert_header(ContentType::json())
+    .set_payload(json!({ "code": code, "state": state }).to_string())
+    .to_request();
+
+  let res = test::call_service(&app, req).await;
+
+  assert_eq!(res.status(), 409);
   Ok(())
 }
 
@@ -843,13 +4006,13 @@ async fn test_register_with_default(
   pool: PgPool,
   request: RequestRegisterDefault<'_>,
 ) -> actix_web::dev::ServiceResponse<actix_web::body::EitherBody<actix_web::body::BoxBody>> {
-  let mut app = test::init_service(
+  let app = test::init_service(
     App::new()
       .wrap(default_cors())
       .app_data(web::Data::new(AppState {
         postgres_pool: pool.clone(),
       }))
-      .configure(services_config)
+      .configure(services_config(pool.clone()))
       .configure(utilities_config)
       .configure(routes_config),
   )
@@ -877,5 +4040,5 @@ async fn test_register_with_default(
     )
     .to_request();
 
-  test::call_service(&mut app, req).await
+  test::call_service(&app, req).await
 }